@@ -1,5 +1,5 @@
 // Build script to compile Protocol Buffer definitions for kaswallet-daemon gRPC
-// and capture build timestamp
+// and capture build info (timestamp, git commit, rustc version)
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Compile protobuf definitions
@@ -9,8 +9,29 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let build_time = chrono::Local::now().format("%Y-%m-%d %H:%M:%S %Z").to_string();
     println!("cargo:rustc-env=BUILD_TIMESTAMP={}", build_time);
 
+    // Capture git commit and compiler version for `--version` / `diag versions`.
+    // Builds outside a git checkout (e.g. from a source tarball) report "unknown".
+    let git_commit = command_output("git", &["rev-parse", "--short=12", "HEAD"])
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=GIT_COMMIT={}", git_commit);
+
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let rustc_version = command_output(&rustc, &["--version"])
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=RUSTC_VERSION={}", rustc_version);
+
     // Note: Not using rerun-if-changed means this script runs on every build,
     // ensuring BUILD_TIMESTAMP is always current
 
     Ok(())
 }
+
+/// Trimmed stdout of a successful command, None if it failed or printed nothing
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = std::process::Command::new(program).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let text = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!text.is_empty()).then_some(text)
+}
@@ -62,8 +62,8 @@ async fn main() -> Result<()> {
             let balance = response.into_inner();
 
             // Convert sompi to KAS (1 KAS = 100,000,000 sompi)
-            let available_kas = balance.available as f64 / 100_000_000.0;
-            let pending_kas = balance.pending as f64 / 100_000_000.0;
+            let available_kas = sompi_to_kas(balance.available);
+            let pending_kas = sompi_to_kas(balance.pending);
 
             println!("✓ Balance:");
             println!("  Available: {} KAS ({} sompi)", available_kas, balance.available);
@@ -73,8 +73,8 @@ async fn main() -> Result<()> {
             if !balance.address_balances.is_empty() {
                 println!("\n  Per-address breakdown:");
                 for addr_balance in balance.address_balances {
-                    let addr_available = addr_balance.available as f64 / 100_000_000.0;
-                    let addr_pending = addr_balance.pending as f64 / 100_000_000.0;
+                    let addr_available = sompi_to_kas(addr_balance.available);
+                    let addr_pending = sompi_to_kas(addr_balance.pending);
                     println!("    {}", addr_balance.address);
                     println!("      Available: {} KAS", addr_available);
                     println!("      Pending:   {} KAS", addr_pending);
@@ -99,8 +99,8 @@ async fn main() -> Result<()> {
     /*
     // UNCOMMENT AND CONFIGURE TO SEND A TRANSACTION
     let destination = "kaspatest:qq...".to_string(); // Replace with actual address
-    let amount_kas = 1.0; // Amount in KAS
-    let amount_sompi = (amount_kas * 100_000_000.0) as u64;
+    let amount_kas = "1.0"; // Amount in KAS
+    let amount_sompi = kas_to_sompi(amount_kas).expect("invalid amount");
     let password = "your_password".to_string(); // Replace with actual password
 
     println!("Sending {} KAS ({} sompi) to {}...", amount_kas, amount_sompi, destination);
@@ -129,8 +129,8 @@ async fn main() -> Result<()> {
 
     // Example 5: Helper function to convert between KAS and sompi
     println!("--- Example 5: Currency Conversion ---");
-    let kas_amount = 2.5;
-    let sompi_amount = kas_to_sompi(kas_amount);
+    let kas_amount = "2.5";
+    let sompi_amount = kas_to_sompi(kas_amount).expect("valid amount");
     println!("{} KAS = {} sompi", kas_amount, sompi_amount);
 
     let sompi_amount = 150_000_000u64;
@@ -142,12 +142,32 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
-/// Convert KAS to sompi (1 KAS = 100,000,000 sompi)
-fn kas_to_sompi(kas: f64) -> u64 {
-    (kas * 100_000_000.0) as u64
+const SOMPI_PER_KAS: u64 = 100_000_000;
+
+/// Convert a decimal KAS string to exact sompi (1 KAS = 100,000,000 sompi)
+/// Parses the digits directly so amounts like "0.29" don't lose a sompi to
+/// float rounding; more than 8 decimal places is an error.
+fn kas_to_sompi(kas: &str) -> Result<u64, String> {
+    let (whole, fraction) = kas.trim().split_once('.').unwrap_or((kas.trim(), ""));
+    if (whole.is_empty() && fraction.is_empty())
+        || !whole.chars().all(|c| c.is_ascii_digit())
+        || !fraction.chars().all(|c| c.is_ascii_digit())
+    {
+        return Err(format!("invalid amount '{}'", kas));
+    }
+    if fraction.len() > 8 {
+        return Err(format!("'{}' has more than 8 decimal places", kas));
+    }
+
+    let whole: u64 = if whole.is_empty() { 0 } else { whole.parse().map_err(|e| format!("{}", e))? };
+    let fraction: u64 = format!("{:0<8}", fraction).parse().map_err(|e| format!("{}", e))?;
+    whole
+        .checked_mul(SOMPI_PER_KAS)
+        .and_then(|w| w.checked_add(fraction))
+        .ok_or_else(|| format!("'{}' is too large", kas))
 }
 
-/// Convert sompi to KAS (1 KAS = 100,000,000 sompi)
+/// Convert sompi to KAS for display (1 KAS = 100,000,000 sompi)
 fn sompi_to_kas(sompi: u64) -> f64 {
-    sompi as f64 / 100_000_000.0
+    sompi as f64 / SOMPI_PER_KAS as f64
 }
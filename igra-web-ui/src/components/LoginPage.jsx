@@ -19,7 +19,8 @@ export default function LoginPage({ onLogin }) {
         }
       })
 
-      if (response.ok) {
+      // 503 only means the node is degraded; the server itself answered
+      if (response.ok || response.status === 503) {
         // Token is valid, save it and notify parent
         localStorage.setItem('igra_token', token)
         onLogin(token)
@@ -1,27 +1,62 @@
-/// Main TUI application
+//! Main TUI application
 
 use anyhow::Result;
-use crossterm::{
-    event::{self, Event, KeyCode},
-    execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
-};
+use crossterm::event::{self, Event, KeyCode};
 use ratatui::{
     backend::CrosstermBackend,
     Terminal,
 };
 use std::io;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use crate::core::{ConfigManager, DockerManager};
-use crate::core::wallet::WalletManager;
+use crate::core::backend::{container_backend, ContainerBackend, WalletBackend};
+use crate::core::wallet::{FeeEstimate, ReceiveAddress, SendFeePolicy, WalletManager};
+use crate::core::wallet_password::{PasswordSource, WalletPassword, WalletPasswordConfig};
+use zeroize::{Zeroize, Zeroizing};
 use crate::core::ssl::SslManager;
-use crate::screens::Dashboard;
+use crate::core::storage::PruneTarget;
+use crate::core::export::{ExportFormat, ExportTable};
+use crate::screens::{Dashboard, RenderState};
+use crate::screens::palette::{CommandPalette, PaletteCommand};
+use crate::screens::watch::{FeedFilter, RangeField, RangeFilter};
+
+/// Palette results kept for the "Recent" list
+const NOTIFICATION_HISTORY: usize = 20;
+
+/// Upper bound for each fetch a screen refresh waits on
+const REFRESH_FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Footer spinner frames, one per 100ms event-loop tick
+const SPINNER_FRAMES: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
 
 // Constants for log buffer management
-const MAX_LOG_LINES: usize = 10_000;  // Maximum lines to keep in memory
-const INITIAL_LOG_FETCH: usize = 1000;  // Lines to fetch on initial load
-const LIVE_LOG_FETCH: usize = 100;  // Lines to fetch in live mode updates
+
+/// Step of the [E]xport prompt, shown in the status line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExportPrompt {
+    Format,
+    /// Config only: ask before writing passwords, keys and tokens in clear
+    Secrets(ExportFormat),
+}
+
+/// What a background action reports back (see `App::spawn_action`)
+struct ActionOutcome {
+    status: String,
+    /// Reload the current screen once the result is in
+    refresh: bool,
+}
+
+/// A long action (image pull, profile start, ...) running while the UI keeps
+/// drawing; the footer shows a spinner until its outcome arrives
+struct PendingAction {
+    label: String,
+    started: Instant,
+    outcome_rx: tokio::sync::oneshot::Receiver<ActionOutcome>,
+    /// Palette input and shortcut, echoed to the notifications when it finishes
+    palette_echo: Option<(String, &'static str)>,
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Screen {
@@ -38,6 +73,48 @@ pub enum ServicesView {
     Profiles,
 }
 
+/// Quick state filter on the Services table, ANDed with the name search
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServiceStateFilter {
+    Running,
+    Stopped,
+    Unhealthy,
+}
+
+impl ServiceStateFilter {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ServiceStateFilter::Running => "running",
+            ServiceStateFilter::Stopped => "stopped",
+            ServiceStateFilter::Unhealthy => "unhealthy",
+        }
+    }
+
+    /// A crash-looping (restarting) container counts as unhealthy, not stopped
+    pub fn matches(&self, container: &crate::core::docker::ContainerInfo) -> bool {
+        use crate::utils::ContainerState;
+        match self {
+            ServiceStateFilter::Running => container.state == ContainerState::Running,
+            ServiceStateFilter::Stopped => !matches!(container.state, ContainerState::Running | ContainerState::Restarting),
+            ServiceStateFilter::Unhealthy => {
+                container.state == ContainerState::Restarting || container.health.as_deref() == Some("unhealthy")
+            }
+        }
+    }
+}
+
+/// Fee line of the send dialog (see `WalletBackend::estimate_fee`)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SendFeePreview {
+    /// Asked in the background, no answer yet
+    Estimating,
+    Ready(FeeEstimate),
+    /// Amount plus fee is more than the wallet can spend
+    Insufficient,
+    /// The daemon can't be asked, or the fee policy leaves the fee to it
+    Unavailable,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ConfigSection {
     Environment,
@@ -56,6 +133,21 @@ impl Screen {
         }
     }
 
+    /// Name stored in config.toml `[session]`
+    pub fn key(&self) -> &'static str {
+        match self {
+            Screen::Services => "services",
+            Screen::Wallets => "wallets",
+            Screen::Watch => "watch",
+            Screen::Config => "config",
+            Screen::Storage => "storage",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Screen> {
+        Self::all().iter().copied().find(|screen| screen.key() == key)
+    }
+
     pub fn all() -> &'static [Screen] {
         &[
             Screen::Services,
@@ -80,42 +172,72 @@ pub struct SystemResources {
     pub cpu_frequency_ghz: f32,
     pub cpu_model: String,
     pub public_ip: Option<String>,
+    /// Set when DOCKER_HOST is another machine; the figures above are still this one's
+    pub remote_docker_host: Option<String>,
 }
 
 pub struct App {
     dashboard: Dashboard,
-    docker: DockerManager,
+    docker: Arc<dyn ContainerBackend>,
     config: ConfigManager,
-    wallet_manager: WalletManager,
+    wallet_manager: Arc<dyn WalletBackend>,
     ssl_manager: SslManager,
+    low_balance_threshold: f64, // Per-network, from config.toml
+    log_limits: crate::utils::LogLimits, // Log buffer/fetch sizes, from config.toml
+    wallet_passwords: WalletPasswordConfig, // Per-worker password sources, from config.toml
     current_screen: Screen,
     selected_index: usize,
+    /// Row from the last session, applied once the restored screen's list has loaded
+    restored_selection: Option<usize>,
     should_quit: bool,
     last_refresh: Instant,
     refresh_interval: Duration,
     status_message: Option<String>,
+    pending_action: Option<PendingAction>,
     show_help: bool,
+    // Health overlay: one row per check, result filled in as each finishes
+    show_health: bool,
+    palette: Option<CommandPalette>,
+    export_prompt: Option<ExportPrompt>,
+    notifications: std::collections::VecDeque<String>,
+    health_rows: Vec<(String, Option<crate::core::health::HealthResult>)>,
+    health_generation: u64, // Results from an earlier run are ignored
+    health_started: Instant,
+    health_tx: tokio::sync::mpsc::UnboundedSender<(u64, usize, crate::core::health::HealthResult)>,
+    health_rx: tokio::sync::mpsc::UnboundedReceiver<(u64, usize, crate::core::health::HealthResult)>,
     // Background data refresh channels
     container_data_rx: tokio::sync::mpsc::UnboundedReceiver<Vec<crate::core::docker::ContainerInfo>>,
     container_stats_rx: tokio::sync::mpsc::UnboundedReceiver<std::collections::HashMap<String, crate::core::docker::ContainerStats>>,
     image_versions_rx: tokio::sync::mpsc::UnboundedReceiver<std::collections::HashMap<String, crate::core::versions::ImageVersion>>,
+    dependency_health_rx: tokio::sync::mpsc::UnboundedReceiver<crate::core::health::DependencyHealth>,
     // Watch screen channels
-    watch_transactions_tx: tokio::sync::mpsc::UnboundedSender<Vec<crate::core::l2_monitor::TransactionInfo>>,
-    watch_transactions_rx: tokio::sync::mpsc::UnboundedReceiver<Vec<crate::core::l2_monitor::TransactionInfo>>,
+    watch_transactions_tx: tokio::sync::mpsc::UnboundedSender<crate::core::l2_monitor::PollUpdate>,
+    watch_transactions_rx: tokio::sync::mpsc::UnboundedReceiver<crate::core::l2_monitor::PollUpdate>,
     watch_stats_tx: tokio::sync::mpsc::UnboundedSender<crate::core::l2_monitor::Statistics>,
     watch_stats_rx: tokio::sync::mpsc::UnboundedReceiver<crate::core::l2_monitor::Statistics>,
     // Detail view live logs channels
     detail_logs_live_tx: tokio::sync::mpsc::UnboundedSender<Vec<crate::core::ParsedLogLine>>,
     detail_logs_live_rx: tokio::sync::mpsc::UnboundedReceiver<Vec<crate::core::ParsedLogLine>>,
     detail_logs_live_task_handle: Option<tokio::task::JoinHandle<()>>,
+    // Execution-layer metrics sampler (runs while its detail view is open)
+    reth_metrics_tx: tokio::sync::mpsc::UnboundedSender<crate::core::reth_metrics::RethMetrics>,
+    reth_metrics_rx: tokio::sync::mpsc::UnboundedReceiver<crate::core::reth_metrics::RethMetrics>,
+    reth_sampler_handle: Option<tokio::task::JoinHandle<()>>,
     // Cached data for actions
-    containers: Vec<crate::core::docker::ContainerInfo>,
-    container_stats: std::collections::HashMap<String, crate::core::docker::ContainerStats>,
-    image_versions: std::collections::HashMap<String, crate::core::versions::ImageVersion>,
+    // Large snapshots are shared with the dashboard by Arc rather than copied.
+    // Background tasks send owned data over channels and only this (UI) task
+    // swaps in a new Arc, so a snapshot is never mutated while rendered.
+    containers: Arc<Vec<crate::core::docker::ContainerInfo>>,
+    container_stats: Arc<std::collections::HashMap<String, crate::core::docker::ContainerStats>>,
+    image_versions: Arc<std::collections::HashMap<String, crate::core::versions::ImageVersion>>,
+    /// Docker / kaspad / execution-layer lights in the title block
+    dependency_health: crate::core::health::DependencyHealth,
+    // Set when background data changed; the dashboard is synced once per frame
+    services_dirty: bool,
     reth_metrics: Option<crate::core::reth_metrics::RethMetrics>,
-    reth_metrics_timestamp: Option<Instant>,
+    reth_history: crate::core::reth_metrics::RethHistory,
     wallets: Vec<crate::core::wallet::WalletInfo>,
-    config_data: Vec<(String, String)>,
+    config_data: Arc<Vec<(String, String)>>,
     active_profiles: Vec<String>,
     ssl_cert_info: Option<crate::core::ssl::CertificateInfo>,
     ssl_domain: String,
@@ -123,7 +245,10 @@ pub struct App {
     // Config editing state
     edit_mode: bool,
     edit_buffer: String,
+    edit_cursor: usize, // Cursor position in edit_buffer (in chars)
     edit_key: Option<String>,
+    // Running services that need a recreate for saved .env edits -> edited keys
+    pending_restart: std::collections::BTreeMap<String, std::collections::BTreeSet<String>>,
     // Service detail view state
     detail_view_service: Option<String>,
     detail_logs: Vec<crate::core::ParsedLogLine>,  // Pre-parsed logs for fast rendering
@@ -140,40 +265,51 @@ pub struct App {
     // Wallet detail view state
     detail_view_wallet: Option<usize>, // worker_id
     detail_wallet_addresses: Vec<(String, f64, f64)>, // (address, available, pending)
+    detail_receive_address: Option<ReceiveAddress>,
     detail_wallet_utxos: Vec<crate::core::wallet::UtxoInfo>, // UTXOs for activity view
+    /// Total In / Total Out from the ledger (None when it couldn't be read)
+    detail_wallet_totals: Option<crate::core::wallet_history::WalletTotals>,
     // Search/filter state
     search_mode: bool,
     search_buffer: String,
     filtered_indices: Vec<usize>, // Indices of items that match search
+    service_state_filter: Option<ServiceStateFilter>, // Hides Services rows in other states
+    config_search_values: bool,   // Config search also matches (non-secret) values
     // Wallet transaction state
     show_send_dialog: bool,
     send_amount: String,
     send_address: String,
-    send_input_field: usize, // 0 = amount, 1 = address
+    send_input_field: usize, // 0 = amount, 1 = address, 2 = password (prompt source only)
     send_use_wallet_selector: bool, // Toggle between manual address entry and wallet selection
     send_selected_wallet_index: usize, // Index of selected wallet for destination
     send_source_address: String, // Source wallet address to display
+    send_all: bool, // Sweep the whole balance (no change output)
+    send_fee: Option<SendFeePreview>, // None until there is an amount to estimate
+    send_fee_rx: Option<tokio::sync::oneshot::Receiver<crate::core::error::Result<FeeEstimate>>>, // Latest estimate in flight
+    send_needs_password: bool, // Source wallet's password is typed in the dialog
+    send_password: Zeroizing<String>, // Wiped on send/cancel, only ever rendered masked
     // New feature states
     detail_wallet_scroll: usize, // Scroll offset for transaction list
     detail_addresses_scroll: usize, // Scroll offset for addresses
     show_tx_detail: bool, // Transaction detail modal
+    show_address_qr: bool, // Receive address QR code modal
     selected_tx_index: Option<usize>, // Selected transaction for detail view
     tx_search_mode: bool, // Transaction search/filter mode
     tx_search_buffer: String, // Search query for transactions
     filtered_tx_indices: Vec<usize>, // Filtered transaction indices
-    auto_refresh_enabled: bool, // Auto-refresh toggle
-    color_theme: String, // Color theme name
     // New v0.5.0 dashboard reorganization states
     services_view: ServicesView, // Services/Profiles tab view
     config_section: ConfigSection, // Config multi-tab section
     // Watch screen state
     watch_monitor: Option<std::sync::Arc<crate::core::l2_monitor::TransactionMonitor>>,
-    watch_transactions: Vec<crate::core::l2_monitor::TransactionInfo>,
+    watch_intervals_tx: Option<tokio::sync::watch::Sender<crate::utils::WatchIntervals>>, // Re-arms the polling task's timers
+    watch_feed: crate::screens::watch::TransactionFeed,
+    watch_unseen_new: usize, // Shown as "+N new" after resume until scrolled to top
     watch_statistics: Option<crate::core::l2_monitor::Statistics>,
-    watch_filter: crate::screens::watch::TransactionFilter,
-    watch_scroll_offset: usize,
-    watch_recording_file: Option<std::fs::File>,
-    watch_recording_format: String,
+    watch_filter: FeedFilter,
+    watch_show_breakdown: bool, // Per-type counts and fees under the statistics
+    watch_range_input: Option<(RangeField, String)>, // Value/fee range being typed
+    watch_recorder: Option<crate::core::recorder::TransactionRecorder>,
     // Storage screen state
     storage_analysis: Option<crate::core::storage::StorageAnalysis>,
     storage_last_update: Option<Instant>,
@@ -182,25 +318,24 @@ pub struct App {
     storage_show_details: bool,     // Toggle details table view
 }
 
-impl App {
-    pub fn new() -> Result<Self> {
-        let docker = DockerManager::new_sync()?;
-        let config = ConfigManager::load_from_project()?;
-        let wallet_manager = WalletManager::new()?;
-        let ssl_manager = SslManager::new()?;
 
-        // Get domain from config
-        let ssl_domain = config.get("IGRA_ORCHESTRA_DOMAIN")
-            .unwrap_or("N/A")
-            .to_string();
+/// Receiving ends of the background tasks that keep container data, stats,
+/// image versions and dependency health fresh
+struct DataFeeds {
+    container_data_rx: tokio::sync::mpsc::UnboundedReceiver<Vec<crate::core::docker::ContainerInfo>>,
+    container_stats_rx: tokio::sync::mpsc::UnboundedReceiver<std::collections::HashMap<String, crate::core::docker::ContainerStats>>,
+    image_versions_rx: tokio::sync::mpsc::UnboundedReceiver<std::collections::HashMap<String, crate::core::versions::ImageVersion>>,
+    dependency_health_rx: tokio::sync::mpsc::UnboundedReceiver<crate::core::health::DependencyHealth>,
+}
 
-        // Create channels for background updates
+impl DataFeeds {
+    /// Start the background tasks
+    /// `configured_profiles` is COMPOSE_PROFILES, for the dependency lights
+    fn spawn(docker: &Arc<dyn ContainerBackend>, configured_profiles: Vec<String>) -> Self {
         let (container_data_tx, container_data_rx) = tokio::sync::mpsc::unbounded_channel();
         let (container_stats_tx, container_stats_rx) = tokio::sync::mpsc::unbounded_channel();
         let (image_versions_tx, image_versions_rx) = tokio::sync::mpsc::unbounded_channel();
-        let (watch_transactions_tx, watch_transactions_rx) = tokio::sync::mpsc::unbounded_channel();
-        let (watch_stats_tx, watch_stats_rx) = tokio::sync::mpsc::unbounded_channel();
-        let (detail_logs_live_tx, detail_logs_live_rx) = tokio::sync::mpsc::unbounded_channel();
+        let (dependency_health_tx, dependency_health_rx) = tokio::sync::mpsc::unbounded_channel();
 
         // Spawn background task to fetch container data
         let docker_clone = docker.clone();
@@ -224,34 +359,8 @@ impl App {
                 // Small delay before first stats collection
                 tokio::time::sleep(Duration::from_millis(500)).await;
 
-                // Fetch current running containers from Docker
-                if let Ok(containers) = docker_clone2.list_containers().await {
-                    use std::collections::HashMap;
-                    use futures::future::join_all;
-
-                    let running_containers: Vec<String> = containers
-                        .iter()
-                        .filter(|c| c.state.is_running())
-                        .map(|c| c.name.clone())
-                        .collect();
-
-                    // Fetch stats in parallel
-                    let stats_futures = running_containers.iter().map(|name| {
-                        let docker = docker_clone2.clone();
-                        let name = name.clone();
-                        async move {
-                            docker.get_container_stats(&name).await.ok().flatten().map(|stats| (name, stats))
-                        }
-                    });
-
-                    let stats_results = join_all(stats_futures).await;
-
-                    // Build stats map
-                    let mut stats_map = HashMap::new();
-                    for result in stats_results.into_iter().flatten() {
-                        stats_map.insert(result.0, result.1);
-                    }
-
+                // One list + one df call, then per-container stats with bounded parallelism
+                if let Ok(stats_map) = docker_clone2.get_all_container_stats().await {
                     // Send to main thread
                     let _ = container_stats_tx.send(stats_map);
                 }
@@ -273,7 +382,7 @@ impl App {
                     // Extract image name and current tag
                     let image_str = container.image
                         .split('/')
-                        .last()
+                        .next_back()
                         .unwrap_or(&container.image);
 
                     let (name, tag) = if let Some((n, t)) = image_str.split_once(':') {
@@ -304,7 +413,7 @@ impl App {
                         // Extract image name and current tag
                         let image_str = container.image
                             .split('/')
-                            .last()
+                            .next_back()
                             .unwrap_or(&container.image);
 
                         let (name, tag) = if let Some((n, t)) = image_str.split_once(':') {
@@ -324,37 +433,123 @@ impl App {
             }
         });
 
+        // Spawn background task for the title block's dependency lights.
+        // Probes can take seconds, so they never run on the render path.
+        let docker_clone4 = docker.clone();
+        tokio::spawn(async move {
+            use crate::core::health::{HealthChecker, DEPENDENCY_CHECK_INTERVAL};
+
+            let checker = HealthChecker::new();
+            loop {
+                let health = checker.check_dependencies(docker_clone4.as_ref(), &configured_profiles).await;
+                if dependency_health_tx.send(health).is_err() {
+                    break;
+                }
+                tokio::time::sleep(DEPENDENCY_CHECK_INTERVAL).await;
+            }
+        });
+
+        Self { container_data_rx, container_stats_rx, image_versions_rx, dependency_health_rx }
+    }
+
+    /// Feeds nothing ever arrives on
+    #[cfg(test)]
+    fn idle() -> Self {
+        Self {
+            container_data_rx: tokio::sync::mpsc::unbounded_channel().1,
+            container_stats_rx: tokio::sync::mpsc::unbounded_channel().1,
+            image_versions_rx: tokio::sync::mpsc::unbounded_channel().1,
+            dependency_health_rx: tokio::sync::mpsc::unbounded_channel().1,
+        }
+    }
+}
+
+impl App {
+    pub async fn new() -> Result<Self> {
+        let docker = container_backend(DockerManager::new().await?);
+        let config = ConfigManager::load_from_project()?;
+        let wallet_manager: Arc<dyn WalletBackend> = Arc::new(WalletManager::new()?);
+        let ssl_manager = SslManager::new()?;
+
+        let feeds = DataFeeds::spawn(&docker, config.compose_profiles());
+        let mut app = Self::with_backends(docker, config, wallet_manager, ssl_manager, feeds);
+        app.restore_session();
+        Ok(app)
+    }
+
+    /// Build the app around existing backends. The background fetches come in
+    /// through `feeds`; unlike `new`, the last session isn't restored.
+    fn with_backends(
+        docker: Arc<dyn ContainerBackend>,
+        config: ConfigManager,
+        wallet_manager: Arc<dyn WalletBackend>,
+        ssl_manager: SslManager,
+        feeds: DataFeeds,
+    ) -> Self {
+        let DataFeeds { container_data_rx, container_stats_rx, image_versions_rx, dependency_health_rx } = feeds;
+
+        // Get domain from config
+        let ssl_domain = config.get("IGRA_ORCHESTRA_DOMAIN")
+            .unwrap_or("N/A")
+            .to_string();
+
+        // Create channels for background updates
+        let (watch_transactions_tx, watch_transactions_rx) = tokio::sync::mpsc::unbounded_channel();
+        let (watch_stats_tx, watch_stats_rx) = tokio::sync::mpsc::unbounded_channel();
+        let (detail_logs_live_tx, detail_logs_live_rx) = tokio::sync::mpsc::unbounded_channel();
+        let (reth_metrics_tx, reth_metrics_rx) = tokio::sync::mpsc::unbounded_channel();
+        let (health_tx, health_rx) = tokio::sync::mpsc::unbounded_channel();
+
         // Create dashboard and initialize with network info
         let mut dashboard = Dashboard::new();
-        dashboard.update_network(docker.network().to_string());
+        dashboard.update_network(docker.network());
+        let low_balance_threshold = crate::utils::AppConfig::low_balance_threshold(docker.network());
+        dashboard.update_low_balance_threshold(low_balance_threshold);
 
-        Ok(Self {
+        Self {
             dashboard,
             docker,
             config,
             wallet_manager,
             ssl_manager,
+            low_balance_threshold,
+            log_limits: crate::utils::AppConfig::log_limits(),
+            wallet_passwords: crate::utils::AppConfig::wallet_passwords(),
             current_screen: Screen::Services,
             selected_index: 0,
+            restored_selection: None,
             should_quit: false,
             last_refresh: Instant::now(),
             refresh_interval: Duration::from_secs(2),
-            status_message: None,
+            status_message: crate::core::docker::remote_docker_warning().map(|w| format!("⚠ {}", w)),
+            pending_action: None,
             show_help: false,
+            show_health: false,
+            palette: None,
+            export_prompt: None,
+            notifications: std::collections::VecDeque::new(),
+            health_rows: Vec::new(),
+            health_generation: 0,
+            health_started: Instant::now(),
+            health_tx,
+            health_rx,
             container_data_rx,
             container_stats_rx,
             image_versions_rx,
+            dependency_health_rx,
             watch_transactions_tx,
             watch_transactions_rx,
             watch_stats_tx,
             watch_stats_rx,
-            containers: Vec::new(),
-            container_stats: std::collections::HashMap::new(),
-            image_versions: std::collections::HashMap::new(),
+            containers: Arc::default(),
+            container_stats: Arc::default(),
+            image_versions: Arc::default(),
+            dependency_health: Default::default(),
+            services_dirty: true,
             reth_metrics: None,
-            reth_metrics_timestamp: None,
+            reth_history: crate::core::reth_metrics::RethHistory::default(),
             wallets: Vec::new(),
-            config_data: Vec::new(),
+            config_data: Arc::default(),
             active_profiles: Vec::new(),
             ssl_cert_info: None,
             ssl_domain,
@@ -370,10 +565,13 @@ impl App {
                 cpu_frequency_ghz: 0.0,
                 cpu_model: String::new(),
                 public_ip: None,
+                remote_docker_host: None,
             },
             edit_mode: false,
             edit_buffer: String::new(),
+            edit_cursor: 0,
             edit_key: None,
+            pending_restart: std::collections::BTreeMap::new(),
             detail_view_service: None,
             detail_logs: Vec::new(),
             detail_logs_scroll_offset: 0,  // 0 = bottom/auto-follow
@@ -386,10 +584,14 @@ impl App {
             profile_selected_service: 0,
             detail_view_wallet: None,
             detail_wallet_addresses: Vec::new(),
+            detail_receive_address: None,
             detail_wallet_utxos: Vec::new(),
+            detail_wallet_totals: None,
             search_mode: false,
             search_buffer: String::new(),
             filtered_indices: Vec::new(),
+            service_state_filter: None,
+            config_search_values: true,
             show_send_dialog: false,
             send_amount: String::new(),
             send_address: String::new(),
@@ -397,26 +599,32 @@ impl App {
             send_use_wallet_selector: false,
             send_selected_wallet_index: 0,
             send_source_address: String::new(),
+            send_all: false,
+            send_fee: None,
+            send_fee_rx: None,
+            send_needs_password: false,
+            send_password: Zeroizing::new(String::new()),
             // New feature initializations
             detail_wallet_scroll: 0,
             detail_addresses_scroll: 0,
             show_tx_detail: false,
+            show_address_qr: false,
             selected_tx_index: None,
             tx_search_mode: false,
             tx_search_buffer: String::new(),
             filtered_tx_indices: Vec::new(),
-            auto_refresh_enabled: true,
-            color_theme: "dark".to_string(),
             // New v0.5.0 dashboard reorganization initializations
             services_view: ServicesView::Services,
             config_section: ConfigSection::Environment,
             watch_monitor: None,
-            watch_transactions: Vec::new(),
+            watch_intervals_tx: None,
+            watch_feed: crate::screens::watch::TransactionFeed::default(),
+            watch_unseen_new: 0,
             watch_statistics: None,
-            watch_filter: crate::screens::watch::TransactionFilter::All,
-            watch_scroll_offset: 0,
-            watch_recording_file: None,
-            watch_recording_format: "text".to_string(),
+            watch_filter: FeedFilter::default(),
+            watch_show_breakdown: false,
+            watch_range_input: None,
+            watch_recorder: None,
             storage_analysis: None,
             storage_last_update: None,
             storage_scroll_offset: 0,
@@ -425,7 +633,67 @@ impl App {
             detail_logs_live_tx,
             detail_logs_live_rx,
             detail_logs_live_task_handle: None,
-        })
+            reth_metrics_tx,
+            reth_metrics_rx,
+            reth_sampler_handle: None,
+        }
+    }
+
+    /// Reopen the screen and tab the last session ended on (`session.json`,
+    /// unless config.toml turns `[session] restore` off)
+    fn restore_session(&mut self) {
+        let Some(session) = crate::utils::AppConfig::session() else {
+            return;
+        };
+        let Some(screen) = session.screen.as_deref().and_then(Screen::from_key) else {
+            return;
+        };
+
+        self.current_screen = screen;
+        match (screen, session.view.as_deref()) {
+            (Screen::Services, Some("profiles")) => self.services_view = ServicesView::Profiles,
+            (Screen::Config, Some("rpc_tokens")) => self.config_section = ConfigSection::RpcTokens,
+            (Screen::Config, Some("ssl")) => self.config_section = ConfigSection::SslCerts,
+            _ => {}
+        }
+        self.restored_selection = Some(session.selected);
+    }
+
+    /// Select the restored row once its list has loaded, clamped to the
+    /// current length in case it got shorter (a service was removed, ...)
+    fn apply_restored_selection(&mut self) {
+        let Some(index) = self.restored_selection else {
+            return;
+        };
+        let loaded = match self.current_screen {
+            Screen::Services => self.services_view == ServicesView::Profiles || !self.containers.is_empty(),
+            Screen::Wallets => !self.wallets.is_empty(),
+            Screen::Config => self.config_section != ConfigSection::Environment || !self.config_data.is_empty(),
+            // The feed starts empty every run; the top is the only sensible row
+            Screen::Watch | Screen::Storage => true,
+        };
+        if loaded {
+            self.selected_index = index.min(self.get_max_selection());
+            self.restored_selection = None;
+        }
+    }
+
+    /// Remember the screen, tab and row for the next launch
+    fn save_session(&self) {
+        let view = match self.current_screen {
+            Screen::Services => Some(match self.services_view {
+                ServicesView::Services => "services",
+                ServicesView::Profiles => "profiles",
+            }),
+            Screen::Config => Some(match self.config_section {
+                ConfigSection::Environment => "environment",
+                ConfigSection::RpcTokens => "rpc_tokens",
+                ConfigSection::SslCerts => "ssl",
+            }),
+            _ => None,
+        };
+        let selected = self.restored_selection.unwrap_or(self.selected_index);
+        let _ = crate::utils::AppConfig::save_session(self.current_screen.key(), view, selected);
     }
 
     pub fn collect_system_resources() -> SystemResources {
@@ -477,7 +745,7 @@ impl App {
 
         // Get public IP (non-blocking, use cached value on failure)
         let public_ip = Command::new("curl")
-            .args(&["-s", "--max-time", "2", "https://api.ipify.org"])
+            .args(["-s", "--max-time", "2", "https://api.ipify.org"])
             .output()
             .ok()
             .and_then(|output| String::from_utf8(output.stdout).ok())
@@ -495,20 +763,27 @@ impl App {
             cpu_frequency_ghz,
             cpu_model,
             public_ip,
+            remote_docker_host: crate::core::docker::remote_docker_host(),
         }
     }
 
+    /// Push cached container data to the dashboard and clear the dirty flag
+    fn sync_services_to_dashboard(&mut self) {
+        self.dashboard.update_services(
+            &self.containers,
+            &self.active_profiles,
+            &self.container_stats,
+            &self.image_versions,
+        );
+        self.services_dirty = false;
+    }
+
     /// Update dashboard with existing cached data (non-blocking, no async calls)
     fn update_dashboard_for_current_screen(&mut self) {
         match self.current_screen {
             Screen::Services => {
                 // Update both services and profiles (merged screen)
-                self.dashboard.update_services(
-                    self.containers.clone(),
-                    self.active_profiles.clone(),
-                    self.container_stats.clone(),
-                    self.image_versions.clone()
-                );
+                self.sync_services_to_dashboard();
                 self.dashboard.update_profiles(self.active_profiles.clone());
             }
             Screen::Wallets => {
@@ -517,17 +792,18 @@ impl App {
             }
             Screen::Watch => {
                 // Watch screen handles its own updates via monitor
+                self.ensure_watch_polling();
             }
             Screen::Config => {
                 // Update all config sections (environment, RPC tokens, SSL)
-                self.config_data = self.config.keys()
+                self.config_data = Arc::new(self.config.keys()
                     .into_iter()
                     .map(|k| {
                         let val = self.config.get(&k).unwrap_or("");
                         (k.clone(), val.to_string())
                     })
-                    .collect();
-                self.dashboard.update_config(self.config_data.clone());
+                    .collect());
+                self.dashboard.update_config(Arc::clone(&self.config_data), self.config.overridden_keys());
 
                 let tokens = self.config.get_rpc_tokens();
                 let domain = self.config.get("IGRA_ORCHESTRA_DOMAIN")
@@ -543,6 +819,67 @@ impl App {
         }
     }
 
+    /// Start the Watch background polling on first visit. On later visits the
+    /// intervals are re-read from config.toml and, if changed, the running
+    /// task re-arms its timers.
+    fn ensure_watch_polling(&mut self) {
+        let intervals = crate::utils::AppConfig::watch_intervals();
+
+        if let Some(intervals_tx) = &self.watch_intervals_tx {
+            intervals_tx.send_if_modified(|current| {
+                let changed = *current != intervals;
+                *current = intervals;
+                changed
+            });
+            return;
+        }
+
+        let Ok(monitor) = crate::core::l2_monitor::TransactionMonitor::new_sync() else {
+            return;
+        };
+        let monitor_arc = std::sync::Arc::new(monitor);
+        self.watch_monitor = Some(monitor_arc.clone());
+
+        let (intervals_tx, mut intervals_rx) = tokio::sync::watch::channel(intervals);
+        self.watch_intervals_tx = Some(intervals_tx);
+
+        // Spawn background polling task for Watch screen
+        let watch_tx_tx = self.watch_transactions_tx.clone();
+        let watch_stats_tx = self.watch_stats_tx.clone();
+        tokio::spawn(async move {
+            let (mut poll_trigger, mut l1_interval) = crate::core::l2_monitor::poll_timers(intervals);
+
+            loop {
+                tokio::select! {
+                    _ = poll_trigger.tick() => {
+                        // Poll for new transactions
+                        if let Ok(update) = monitor_arc.poll().await {
+                            if !update.is_empty() {
+                                let _ = watch_tx_tx.send(update);
+                            }
+                        }
+
+                        // Get current statistics
+                        let stats = monitor_arc.get_statistics().await;
+                        let _ = watch_stats_tx.send(stats);
+                    }
+                    _ = l1_interval.tick() => {
+                        // Update L1 data periodically
+                        let _ = monitor_arc.update_l1_data().await;
+                    }
+                    changed = intervals_rx.changed() => {
+                        // Sender gone means the App is shutting down
+                        if changed.is_err() {
+                            break;
+                        }
+                        let intervals = *intervals_rx.borrow_and_update();
+                        (poll_trigger, l1_interval) = crate::core::l2_monitor::poll_timers(intervals);
+                    }
+                }
+            }
+        });
+    }
+
     fn set_status(&mut self, message: String) {
         self.status_message = Some(message);
     }
@@ -551,13 +888,85 @@ impl App {
         self.status_message = None;
     }
 
+    /// Run `action` in the background and spin in the footer until it
+    /// finishes. One at a time: while one runs, starting another is refused.
+    fn spawn_action<F>(&mut self, label: String, action: F)
+    where
+        F: std::future::Future<Output = ActionOutcome> + Send + 'static,
+    {
+        if let Some(pending) = &self.pending_action {
+            self.set_status(format!("⚠ Wait for {} to finish", pending.label));
+            return;
+        }
+
+        let (outcome_tx, outcome_rx) = tokio::sync::oneshot::channel();
+        tokio::spawn(async move {
+            let _ = outcome_tx.send(action.await);
+        });
+        self.clear_status();
+        self.pending_action = Some(PendingAction {
+            label,
+            started: Instant::now(),
+            outcome_rx,
+            palette_echo: None,
+        });
+    }
+
+    /// Pick up a finished background action: stop the spinner, show its
+    /// outcome and refresh if asked
+    async fn poll_pending_action(&mut self) {
+        let Some(pending) = self.pending_action.as_mut() else { return };
+
+        let outcome = match pending.outcome_rx.try_recv() {
+            Ok(outcome) => outcome,
+            Err(tokio::sync::oneshot::error::TryRecvError::Empty) => return,
+            // The task panicked; don't leave the spinner running
+            Err(tokio::sync::oneshot::error::TryRecvError::Closed) => ActionOutcome {
+                status: format!("✗ {} failed unexpectedly", pending.label),
+                refresh: false,
+            },
+        };
+        let Some(pending) = self.pending_action.take() else { return };
+
+        let outcome = ActionOutcome { status: crate::core::dry_run::tag(&outcome.status), ..outcome };
+        self.set_status(outcome.status.clone());
+        if let Some((input, keybinding)) = pending.palette_echo {
+            self.echo_palette_outcome(&input, keybinding, &outcome.status);
+            self.set_status(format!("{} - shortcut: {}", outcome.status, keybinding));
+        }
+        if outcome.refresh {
+            if let Err(e) = self.refresh_data().await {
+                self.set_status(format!("{} (refresh failed: {})", outcome.status, e));
+            }
+        }
+    }
+
+    /// Footer text: the spinner while an action runs (followed by any newer
+    /// status, e.g. a refusal), otherwise the status message
+    fn status_line(&self) -> Option<String> {
+        let Some(pending) = &self.pending_action else {
+            return self.status_message.clone();
+        };
+        let elapsed = pending.started.elapsed();
+        let frame = SPINNER_FRAMES[(elapsed.as_millis() / 100) as usize % SPINNER_FRAMES.len()];
+        let spinner = format!("{} {}... {}s", frame, pending.label, elapsed.as_secs());
+        Some(match &self.status_message {
+            Some(status) => format!("{} | {}", spinner, status),
+            None => spinner,
+        })
+    }
+
     pub async fn run(&mut self) -> Result<()> {
-        // Setup terminal
-        enable_raw_mode()?;
-        let mut stdout = io::stdout();
-        execute!(stdout, EnterAlternateScreen)?;
-        let backend = CrosstermBackend::new(stdout);
-        let mut terminal = Terminal::new(backend)?;
+        // Setup terminal (restored on panic as well as on exit)
+        crate::utils::terminal::enter(false)?;
+        let backend = CrosstermBackend::new(io::stdout());
+        let mut terminal = match Terminal::new(backend) {
+            Ok(terminal) => terminal,
+            Err(e) => {
+                crate::utils::terminal::restore();
+                return Err(e.into());
+            }
+        };
 
         // Spawn background storage snapshot tasks
         // Task 1: Immediate check on startup
@@ -574,18 +983,20 @@ impl App {
             }
         });
 
-        // Initial data load
-        self.refresh_data().await?;
+        // A session restored onto Watch needs its poller, normally started on first visit
+        if self.current_screen == Screen::Watch {
+            self.ensure_watch_polling();
+        }
 
-        let result = self.run_loop(&mut terminal).await;
+        // Initial data load
+        let result = match self.refresh_data().await {
+            Ok(()) => self.run_loop(&mut terminal).await,
+            Err(e) => Err(e),
+        };
 
         // Restore terminal
-        disable_raw_mode()?;
-        execute!(
-            terminal.backend_mut(),
-            LeaveAlternateScreen
-        )?;
-        terminal.show_cursor()?;
+        crate::utils::terminal::restore();
+        self.save_session();
 
         result
     }
@@ -595,24 +1006,12 @@ impl App {
         // Only refresh system resources and screen-specific data here
         self.system_resources = Self::collect_system_resources();
 
-        // Update Reth metrics if viewing execution-layer detail
-        if let Some(ref service) = self.detail_view_service {
-            if service == "execution-layer" {
-                let _ = self.update_reth_metrics().await;
-            }
-        }
-
         // Update dashboard based on current screen
         match self.current_screen {
             Screen::Services => {
                 // Container data already updated by background task
                 // Update both services and profiles views
-                self.dashboard.update_services(
-                    self.containers.clone(),
-                    self.active_profiles.clone(),
-                    self.container_stats.clone(),
-                    self.image_versions.clone()
-                );
+                self.sync_services_to_dashboard();
                 self.dashboard.update_profiles(self.active_profiles.clone());
             }
             Screen::Wallets => {
@@ -624,14 +1023,14 @@ impl App {
             }
             Screen::Config => {
                 // Update all config sections
-                self.config_data = self.config.keys()
+                self.config_data = Arc::new(self.config.keys()
                     .into_iter()
                     .map(|k| {
                         let val = self.config.get(&k).unwrap_or("");
                         (k.clone(), val.to_string())
                     })
-                    .collect();
-                self.dashboard.update_config(self.config_data.clone());
+                    .collect());
+                self.dashboard.update_config(Arc::clone(&self.config_data), self.config.overridden_keys());
 
                 let tokens = self.config.get_rpc_tokens();
                 let domain = self.config.get("IGRA_ORCHESTRA_DOMAIN")
@@ -639,9 +1038,17 @@ impl App {
                     .to_string();
                 self.dashboard.update_rpc_tokens(tokens, domain);
 
-                // Load SSL certificate info
+                // Load SSL certificate info, bounded so a stuck read can't
+                // hold up the rest of the screen
                 if self.ssl_domain != "N/A" {
-                    match self.ssl_manager.get_certificate_info(&self.ssl_domain).await {
+                    let cert_info = tokio::time::timeout(
+                        REFRESH_FETCH_TIMEOUT,
+                        self.ssl_manager.get_certificate_info(&self.ssl_domain),
+                    )
+                    .await
+                    .map_err(anyhow::Error::from)
+                    .and_then(|r| r);
+                    match cert_info {
                         Ok(cert_info) => {
                             self.dashboard.update_ssl(Some(cert_info.clone()));
                             self.ssl_cert_info = Some(cert_info);
@@ -680,29 +1087,75 @@ impl App {
         Ok(())
     }
 
-    /// Fetch and update Reth metrics with TPS calculation
-    async fn update_reth_metrics(&mut self) -> Result<()> {
-        // Fetch current metrics
-        let mut current_metrics = crate::core::reth_metrics::fetch_reth_metrics().await?;
+    /// Open the health overlay and run every check in its own task, so the
+    /// overlay appears immediately and rows resolve as checks finish
+    fn start_health_checks(&mut self) {
+        use crate::core::health::HealthChecker;
 
-        // Calculate TPS if we have previous metrics
-        if let (Some(ref prev_metrics), Some(prev_timestamp)) =
-            (&self.reth_metrics, self.reth_metrics_timestamp) {
-            let elapsed = prev_timestamp.elapsed().as_secs_f64();
-            if let Some(tps) = crate::core::reth_metrics::calculate_tps(
-                &current_metrics,
-                prev_metrics,
-                elapsed
-            ) {
-                current_metrics.tps = Some(tps);
-            }
+        let running: Vec<String> = self.containers
+            .iter()
+            .filter(|c| c.state == crate::utils::ContainerState::Running)
+            .map(|c| c.name.clone())
+            .collect();
+        let checks = HealthChecker::checks_for(&running);
+
+        self.health_generation += 1;
+        self.health_started = Instant::now();
+        self.health_rows = checks.iter().map(|c| (c.name.clone(), None)).collect();
+        self.show_health = true;
+
+        let checker = HealthChecker::with_pool(self.wallet_manager.pool().clone())
+            .with_low_balance_threshold(self.low_balance_threshold);
+        for (index, check) in checks.into_iter().enumerate() {
+            let checker = checker.clone();
+            let tx = self.health_tx.clone();
+            let generation = self.health_generation;
+            tokio::spawn(async move {
+                let result = checker.run(&check).await;
+                let _ = tx.send((generation, index, result));
+            });
         }
+    }
 
-        // Store current metrics and timestamp for next calculation
-        self.reth_metrics_timestamp = Some(Instant::now());
-        self.reth_metrics = Some(current_metrics);
+    /// Run the Reth metrics sampler only while the execution-layer detail
+    /// view is open. Samples come in at a fixed cadence so the sparkline
+    /// time axis doesn't depend on UI refreshes or key presses.
+    fn sync_reth_sampler(&mut self) {
+        let wanted = self.detail_view_service.as_deref() == Some("execution-layer");
+
+        if wanted && self.reth_sampler_handle.is_none() {
+            let tx = self.reth_metrics_tx.clone();
+            self.reth_sampler_handle = Some(tokio::spawn(async move {
+                use crate::core::reth_metrics::{calculate_tps, fetch_reth_metrics, SAMPLE_INTERVAL};
+
+                let mut interval = tokio::time::interval(SAMPLE_INTERVAL);
+                interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+                let mut previous: Option<(crate::core::reth_metrics::RethMetrics, Instant)> = None;
+
+                loop {
+                    interval.tick().await;
+                    let Ok(mut metrics) = fetch_reth_metrics().await else {
+                        continue;
+                    };
 
-        Ok(())
+                    let now = Instant::now();
+                    if let Some((ref prev, at)) = previous {
+                        metrics.tps = calculate_tps(&metrics, prev, now.duration_since(at).as_secs_f64());
+                    }
+                    previous = Some((metrics.clone(), now));
+
+                    if tx.send(metrics).is_err() {
+                        break;
+                    }
+                }
+            }));
+        } else if !wanted {
+            if let Some(handle) = self.reth_sampler_handle.take() {
+                handle.abort();
+                self.reth_metrics = None;
+                self.reth_history = Default::default();
+            }
+        }
     }
 
     async fn run_loop<B: ratatui::backend::Backend>(
@@ -710,72 +1163,68 @@ impl App {
         terminal: &mut Terminal<B>,
     ) -> Result<()> {
         loop {
+            self.sync_reth_sampler();
+            while let Ok(metrics) = self.reth_metrics_rx.try_recv() {
+                self.reth_history.record(&metrics);
+                self.reth_metrics = Some(metrics);
+            }
+            self.poll_pending_action().await;
+            self.poll_send_fee();
+            while let Ok((generation, index, result)) = self.health_rx.try_recv() {
+                if generation == self.health_generation {
+                    if let Some(row) = self.health_rows.get_mut(index) {
+                        row.1 = Some(result);
+                    }
+                }
+            }
+
             // Check for new container data from background task (non-blocking)
             while let Ok(containers) = self.container_data_rx.try_recv() {
-                self.containers = containers;
+                self.containers = Arc::new(containers);
                 // Derive profiles synchronously from container list (no blocking!)
                 self.active_profiles = DockerManager::get_active_profiles_from_list(&self.containers);
-
-                // Update dashboard with new container data
-                if self.current_screen == Screen::Services {
-                    self.dashboard.update_services(
-                        self.containers.clone(),
-                        self.active_profiles.clone(),
-                        self.container_stats.clone(),
-                        self.image_versions.clone()
-                    );
+                self.services_dirty = true;
+                if self.services_filtered() {
+                    self.snap_service_selection();
                 }
             }
+            self.apply_restored_selection();
 
             // Check for new container stats from background task (non-blocking)
-            while let Ok(stats) = self.container_stats_rx.try_recv() {
-                self.container_stats = stats;
-
-                // Update dashboard with new stats
-                if self.current_screen == Screen::Services {
-                    self.dashboard.update_services(
-                        self.containers.clone(),
-                        self.active_profiles.clone(),
-                        self.container_stats.clone(),
-                        self.image_versions.clone()
-                    );
+            while let Ok(mut stats) = self.container_stats_rx.try_recv() {
+                for (name, current) in stats.iter_mut() {
+                    if let Some(previous) = self.container_stats.get(name) {
+                        current.set_block_rates(previous);
+                    }
                 }
+                self.container_stats = Arc::new(stats);
+                self.services_dirty = true;
             }
 
             // Check for new image versions from background task (non-blocking)
             while let Ok(versions) = self.image_versions_rx.try_recv() {
-                self.image_versions = versions;
+                self.image_versions = Arc::new(versions);
+                self.services_dirty = true;
+            }
 
-                // Update dashboard with new version info
-                if self.current_screen == Screen::Services {
-                    self.dashboard.update_services(
-                        self.containers.clone(),
-                        self.active_profiles.clone(),
-                        self.container_stats.clone(),
-                        self.image_versions.clone()
-                    );
-                }
+            // Check for new dependency health from background task (non-blocking)
+            while let Ok(health) = self.dependency_health_rx.try_recv() {
+                self.dependency_health = health;
             }
 
             // Check for new watch transactions from background task (non-blocking)
-            while let Ok(new_txs) = self.watch_transactions_rx.try_recv() {
-                let tx_count = new_txs.len(); // Save count before moving
-
-                // Add new transactions to the beginning (newest first)
-                for tx in new_txs.into_iter().rev() {
-                    self.watch_transactions.insert(0, tx);
-                }
-                // Keep only last 100 transactions
-                if self.watch_transactions.len() > 100 {
-                    self.watch_transactions.truncate(100);
-                }
-
-                // Record transactions to file if enabled
-                if let Some(ref mut file) = self.watch_recording_file {
-                    for tx in &self.watch_transactions[0..self.watch_transactions.len().min(tx_count)] {
-                        let _ = Self::write_transaction_to_file(file, tx, &self.watch_recording_format);
+            while let Ok(update) = self.watch_transactions_rx.try_recv() {
+                // Record transactions to file if enabled (also while paused)
+                if let Some(ref mut recorder) = self.watch_recorder {
+                    for tx in &update.transactions {
+                        let _ = recorder.record(tx);
                     }
                 }
+
+                // Mark reorged first so re-included transactions come back as confirmed
+                self.watch_feed.mark_reorged(&update.reorged);
+                // Newest first; held back while the feed is paused
+                self.watch_feed.push(update.transactions);
             }
 
             // Check for new watch statistics from background task (non-blocking)
@@ -787,28 +1236,35 @@ impl App {
             while let Ok(new_logs) = self.detail_logs_live_rx.try_recv() {
                 // Append new logs (deduplicate to avoid showing same lines)
                 // Simple dedup: only add lines that aren't already at the end
+                let mut visible_added = 0;
                 for new_log in new_logs {
-                    // Check if this line already exists at the end (last 100 lines)
-                    let check_range = self.detail_logs.len().saturating_sub(100);
+                    // Check if this line already exists at the end (last live fetch)
+                    let check_range = self.detail_logs.len().saturating_sub(self.log_limits.live_fetch);
                     let already_exists = self.detail_logs[check_range..]
                         .iter()
                         .any(|existing| existing.raw_line == new_log.raw_line);
 
                     if !already_exists {
+                        if new_log.matches_filter(self.detail_logs_filter.as_ref()) {
+                            visible_added += 1;
+                        }
                         self.detail_logs.push(new_log);
                     }
                 }
 
                 // Trim buffer if it exceeds max size (keep most recent lines)
-                if self.detail_logs.len() > MAX_LOG_LINES {
-                    let excess = self.detail_logs.len() - MAX_LOG_LINES;
+                if self.detail_logs.len() > self.log_limits.max_lines {
+                    let excess = self.detail_logs.len() - self.log_limits.max_lines;
                     self.detail_logs.drain(0..excess);
-                    // Adjust scroll offset if needed
-                    self.detail_logs_scroll_offset = self.detail_logs_scroll_offset.saturating_sub(excess);
                 }
 
                 // If at bottom (scroll_offset == 0), stay at bottom (auto-follow)
-                // Otherwise keep current scroll position
+                // Otherwise keep the same lines in view: the offset counts
+                // filtered lines from the bottom, so only matching lines shift it
+                if self.detail_logs_scroll_offset > 0 {
+                    self.detail_logs_scroll_offset = (self.detail_logs_scroll_offset + visible_added)
+                        .min(self.filtered_detail_log_count());
+                }
             }
 
             // Refresh non-container data periodically
@@ -819,11 +1275,19 @@ impl App {
                 }
             }
 
+            // One dashboard update per frame, however many channels produced data
+            if self.services_dirty && self.current_screen == Screen::Services {
+                self.sync_services_to_dashboard();
+            }
+
             terminal.draw(|f| self.render(f))?;
 
             if event::poll(Duration::from_millis(100))? {
-                if let Event::Key(key_event) = event::read()? {
-                    self.handle_key(key_event).await?;
+                match event::read()? {
+                    Event::Key(key_event) => self.handle_key(key_event).await?,
+                    // Resize the buffers now so the next frame redraws at the new size
+                    Event::Resize(_, _) => terminal.autoresize()?,
+                    _ => {}
                 }
             }
 
@@ -839,16 +1303,34 @@ impl App {
         let key = key_event.code;
         let modifiers = key_event.modifiers;
 
+        // Once the user moves around, a late restored selection would only get in the way
+        self.restored_selection = None;
+
         // Handle edit mode separately
         if self.edit_mode {
             return self.handle_edit_key(key).await;
         }
 
+        if self.palette.is_some() {
+            return self.handle_palette_key(key).await;
+        }
+
+        if let Some(prompt) = self.export_prompt {
+            self.handle_export_key(prompt, key);
+            return Ok(());
+        }
+
         // Handle transaction search mode separately
         if self.tx_search_mode {
             return self.handle_tx_search_key(key).await;
         }
 
+        // Handle Watch value/fee range input separately
+        if self.watch_range_input.is_some() {
+            self.handle_watch_range_key(key);
+            return Ok(());
+        }
+
         // Handle search mode separately
         if self.search_mode {
             return self.handle_search_key(key).await;
@@ -875,6 +1357,8 @@ impl App {
                 // Close detail views, help, or quit (in priority order)
                 if self.show_help {
                     self.show_help = false;
+                } else if self.show_health {
+                    self.show_health = false;
                 } else if self.detail_view_service.is_some() {
                     // Close service logs detail view
                     self.detail_view_service = None;
@@ -892,6 +1376,7 @@ impl App {
                     // Close wallet detail view
                     self.detail_view_wallet = None;
                     self.detail_wallet_utxos.clear();
+                    self.detail_wallet_totals = None;
                     self.selected_tx_index = None;
                 } else {
                     self.should_quit = true;
@@ -900,6 +1385,16 @@ impl App {
             KeyCode::Char('?') | KeyCode::F(1) => {
                 self.show_help = !self.show_help;
             }
+            KeyCode::Char(':') => {
+                self.open_palette();
+            }
+            KeyCode::Char('H') => {
+                if self.show_health {
+                    self.show_health = false;
+                } else {
+                    self.start_health_checks();
+                }
+            }
             KeyCode::Char('r') => {
                 // Refresh all data
                 self.set_status("Refreshing...".to_string());
@@ -977,42 +1472,6 @@ impl App {
                 self.current_screen = Screen::Watch;
                 self.selected_index = 0;
                 self.update_dashboard_for_current_screen();
-                // Initialize watch monitor and start background polling if not already initialized
-                if self.watch_monitor.is_none() {
-                    if let Ok(monitor) = crate::core::l2_monitor::TransactionMonitor::new_sync() {
-                        let monitor_arc = std::sync::Arc::new(monitor);
-                        self.watch_monitor = Some(monitor_arc.clone());
-
-                        // Spawn background polling task for Watch screen
-                        let watch_tx_tx = self.watch_transactions_tx.clone();
-                        let watch_stats_tx = self.watch_stats_tx.clone();
-                        tokio::spawn(async move {
-                            let mut poll_interval = tokio::time::interval(Duration::from_secs(1));
-                            let mut l1_interval = tokio::time::interval(Duration::from_secs(10));
-
-                            loop {
-                                tokio::select! {
-                                    _ = poll_interval.tick() => {
-                                        // Poll for new transactions
-                                        if let Ok(new_txs) = monitor_arc.poll_new_transactions().await {
-                                            if !new_txs.is_empty() {
-                                                let _ = watch_tx_tx.send(new_txs);
-                                            }
-                                        }
-
-                                        // Get current statistics
-                                        let stats = monitor_arc.get_statistics().await;
-                                        let _ = watch_stats_tx.send(stats);
-                                    }
-                                    _ = l1_interval.tick() => {
-                                        // Update L1 data periodically
-                                        let _ = monitor_arc.update_l1_data().await;
-                                    }
-                                }
-                            }
-                        });
-                    }
-                }
             }
             KeyCode::Char('4') => {
                 self.current_screen = Screen::Config;
@@ -1052,10 +1511,17 @@ impl App {
                         self.storage_scroll_offset -= 1;
                     }
                 }
-                // Move selection
+                // Move selection, skipping rows the state filter hides
+                else if self.services_filtered() {
+                    self.step_service_selection(false);
+                }
                 else if self.selected_index > 0 {
                     self.selected_index -= 1;
                 }
+
+                if self.current_screen == Screen::Watch && self.selected_index == 0 {
+                    self.watch_unseen_new = 0;
+                }
             }
             KeyCode::Down | KeyCode::Char('j') => {
                 use event::KeyModifiers;
@@ -1101,7 +1567,10 @@ impl App {
                         }
                     }
                 }
-                // Move selection
+                // Move selection, skipping rows the state filter hides
+                else if self.services_filtered() {
+                    self.step_service_selection(true);
+                }
                 else {
                     let max = self.get_max_selection();
                     if self.selected_index < max {
@@ -1151,22 +1620,21 @@ impl App {
                 }
             }
             KeyCode::Char(' ') => {
-                // Space for toggle on Services/Profiles view
+                // Space for toggle on Services/Profiles view, pause/resume on Watch
                 if self.current_screen == Screen::Services && self.services_view == ServicesView::Profiles {
                     self.handle_action().await?;
+                } else if self.current_screen == Screen::Watch {
+                    self.toggle_watch_pause();
                 }
             }
             KeyCode::Char('s') => {
                 // Quick action: Start
-                match self.current_screen {
-                    Screen::Services => {
-                        if self.services_view == ServicesView::Services {
-                            self.handle_service_start().await?;
-                        } else {
-                            self.handle_profile_start().await?;
-                        }
+                if self.current_screen == Screen::Services {
+                    if self.services_view == ServicesView::Services {
+                        self.handle_service_start().await?;
+                    } else {
+                        self.handle_profile_start().await?;
                     }
-                    _ => {}
                 }
             }
             KeyCode::Char('x') => {
@@ -1179,13 +1647,25 @@ impl App {
                             self.handle_profile_stop().await?;
                         }
                     }
+                    Screen::Watch => {
+                        self.watch_filter = FeedFilter::default();
+                        self.selected_index = 0;
+                        self.set_status("Watch filters cleared".to_string());
+                    }
                     _ => {}
                 }
             }
             KeyCode::Char('R') => {
-                // Quick action: Restart (capital R)
+                // Quick action: Restart (capital R); on Profiles, recreate the profile;
+                // on Config, recreate services with pending edits
                 if self.current_screen == Screen::Services {
-                    self.handle_service_restart().await?;
+                    if self.services_view == ServicesView::Services {
+                        self.handle_service_restart().await?;
+                    } else {
+                        self.handle_profile_recreate().await?;
+                    }
+                } else if self.current_screen == Screen::Config {
+                    self.handle_pending_restart().await?;
                 }
             }
             KeyCode::Char('d') => {
@@ -1215,9 +1695,16 @@ impl App {
                 }
             }
             KeyCode::Char('e') => {
-                // Edit config value
+                // Edit config value, or set the fee range on Watch
                 if self.current_screen == Screen::Config {
                     self.enter_edit_mode();
+                } else if self.current_screen == Screen::Watch {
+                    self.start_watch_range_input(RangeField::Fee);
+                }
+            }
+            KeyCode::Char('v') => {
+                if self.current_screen == Screen::Watch {
+                    self.start_watch_range_input(RangeField::Value);
                 }
             }
             KeyCode::Char('c') => {
@@ -1225,7 +1712,8 @@ impl App {
                 if self.current_screen == Screen::Config && self.config_section == ConfigSection::SslCerts {
                     self.handle_ssl_check().await?;
                 } else if self.current_screen == Screen::Watch {
-                    self.watch_transactions.clear();
+                    self.watch_feed.clear();
+                    self.watch_unseen_new = 0;
                     self.set_status("Transaction history cleared".to_string());
                 }
             }
@@ -1259,14 +1747,46 @@ impl App {
                     self.storage_chart_days = 90;
                 }
             }
+            KeyCode::Char('E') => {
+                // Export the current screen's table (capital E)
+                self.start_export();
+            }
+            KeyCode::Char('S') | KeyCode::Char('U') | KeyCode::Char('A') => {
+                // State filters on the Services table (capitals): Stopped, Unhealthy, Active
+                if self.current_screen == Screen::Services && self.services_view == ServicesView::Services {
+                    let filter = match key {
+                        KeyCode::Char('S') => ServiceStateFilter::Stopped,
+                        KeyCode::Char('U') => ServiceStateFilter::Unhealthy,
+                        _ => ServiceStateFilter::Running,
+                    };
+                    self.toggle_service_state_filter(filter);
+                }
+            }
+            KeyCode::Char('C') => {
+                // Clear the state filter and the search on the Services table (capital C)
+                if self.current_screen == Screen::Services && self.services_view == ServicesView::Services {
+                    self.clear_service_filters();
+                }
+            }
             KeyCode::Char('D') => {
                 // Toggle details table in Storage screen (capital D)
                 if self.current_screen == Screen::Storage {
                     self.storage_show_details = !self.storage_show_details;
                 }
             }
+            KeyCode::Char('b') => {
+                // Per-type breakdown on Watch
+                if self.current_screen == Screen::Watch {
+                    self.watch_show_breakdown = !self.watch_show_breakdown;
+                }
+            }
             KeyCode::Char('f') => {
-                // Toggle follow mode in logs viewer (or filter toggle in Watch screen)
+                // Cycle the transaction type filter on Watch
+                if self.current_screen == Screen::Watch {
+                    self.watch_filter.tx_type = self.watch_filter.tx_type.next();
+                    self.selected_index = 0;
+                    self.set_status(format!("Watch filter: {}", self.watch_filter.describe()));
+                }
             }
             KeyCode::Char('l') => {
                 // Show logs: from Services list OR from Profile detail view
@@ -1286,11 +1806,11 @@ impl App {
                         self.profile_selected_service = 0;
                         // Open logs for selected service
                         self.set_status(format!("Loading logs for {}...", service_name));
-                        match self.docker.get_logs(&service_name, Some(INITIAL_LOG_FETCH)).await {
+                        match self.docker.get_logs(&service_name, Some(self.log_limits.initial_fetch), false).await {
                             Ok(logs) => {
                                 // Parse logs once on load
                                 self.detail_logs = logs.lines()
-                                    .map(|s| crate::core::parse_docker_log_line(s))
+                                    .map(crate::core::parse_docker_log_line)
                                     .collect();
                                 self.detail_view_service = Some(service_name);
                                 self.clear_status();
@@ -1308,12 +1828,6 @@ impl App {
             KeyCode::Char('i') => {
                 // Filter INFO in logs
             }
-            KeyCode::Char('t') => {
-                // Toggle compact/detailed log view
-            }
-            KeyCode::Char('g') => {
-                // Toggle log grouping by level/module
-            }
             KeyCode::PageUp => {
                 // Page up in logs
             }
@@ -1336,7 +1850,7 @@ impl App {
                     self.search_mode = true;
                     self.search_buffer.clear();
                     self.filtered_indices.clear();
-                    self.set_status("Search: (type to filter, Enter to apply, Esc to cancel)".to_string());
+                    self.set_status(self.search_prompt());
                 }
             }
             _ => {}
@@ -1354,7 +1868,7 @@ impl App {
                 }
             }
             Screen::Wallets => self.wallets.len().saturating_sub(1),
-            Screen::Watch => self.watch_transactions.len().saturating_sub(1),
+            Screen::Watch => self.watch_feed.transactions().len().saturating_sub(1),
             Screen::Config => {
                 match self.config_section {
                     ConfigSection::Environment => self.config_data.len().saturating_sub(1),
@@ -1386,7 +1900,7 @@ impl App {
     }
 
     async fn show_service_details(&mut self) -> Result<()> {
-        if self.selected_index >= self.containers.len() {
+        if !self.selected_service_visible() {
             return Ok(());
         }
 
@@ -1394,10 +1908,10 @@ impl App {
         self.set_status(format!("Loading details for {}...", service));
 
         // Load logs (initial fetch) - parse once on load
-        match self.docker.get_logs(&service, Some(INITIAL_LOG_FETCH)).await {
+        match self.docker.get_logs(&service, Some(self.log_limits.initial_fetch), false).await {
             Ok(logs) => {
                 self.detail_logs = logs.lines()
-                    .map(|s| crate::core::parse_docker_log_line(s))
+                    .map(crate::core::parse_docker_log_line)
                     .collect();
             }
             Err(_) => {
@@ -1420,7 +1934,7 @@ impl App {
     }
 
     async fn show_service_config(&mut self) -> Result<()> {
-        if self.selected_index >= self.containers.len() {
+        if !self.selected_service_visible() {
             return Ok(());
         }
 
@@ -1474,29 +1988,47 @@ impl App {
 
         self.set_status(format!("Loading wallet details for worker {}...", worker_id));
 
-        // Fetch detailed balance info with per-address breakdown
-        let address_balances = match self.wallet_manager.get_balance_detailed(worker_id).await {
-            Ok(balances) => balances,
-            Err(e) => {
+        // Per-address balances (daemon) and UTXOs (kaspad) are independent,
+        // so fetch them together
+        let (address_balances, utxos, receive_address) = tokio::join!(
+            tokio::time::timeout(REFRESH_FETCH_TIMEOUT, self.wallet_manager.get_balance_detailed(worker_id)),
+            tokio::time::timeout(REFRESH_FETCH_TIMEOUT, self.wallet_manager.get_utxos(worker_id)),
+            tokio::time::timeout(REFRESH_FETCH_TIMEOUT, self.wallet_manager.current_receive_address(worker_id)),
+        );
+
+        let address_balances = match address_balances {
+            Ok(Ok(balances)) => balances,
+            Ok(Err(e)) => {
                 self.set_status(format!("✗ Failed to load wallet details: {}", e));
                 self.detail_wallet_addresses = Vec::new();
                 self.detail_wallet_utxos = Vec::new();
                 return Ok(());
             }
-        };
-
-        // Fetch UTXOs for activity history
-        let utxos = match self.wallet_manager.get_utxos(worker_id).await {
-            Ok(utxos) => utxos,
-            Err(e) => {
-                // UTXOs are optional, continue without them
-                eprintln!("Warning: Failed to load UTXOs: {}", e);
-                Vec::new()
+            Err(_) => {
+                self.set_status(format!("✗ Wallet {} did not answer within {}s", worker_id, REFRESH_FETCH_TIMEOUT.as_secs()));
+                self.detail_wallet_addresses = Vec::new();
+                self.detail_wallet_utxos = Vec::new();
+                return Ok(());
             }
         };
 
+        // UTXOs are optional: show the balances without activity history
+        let (utxos, utxo_warning) = match utxos {
+            Ok(Ok(utxos)) => (utxos, None),
+            Ok(Err(e)) => (Vec::new(), Some(format!("⚠ Activity unavailable: {}", e))),
+            Err(_) => (Vec::new(), Some(format!("⚠ Activity unavailable: kaspad did not answer within {}s", REFRESH_FETCH_TIMEOUT.as_secs()))),
+        };
+
         self.detail_wallet_addresses = address_balances;
         self.detail_wallet_utxos = utxos;
+        // Listing the UTXOs brought the ledger up to date
+        self.detail_wallet_totals = self.wallet_manager.wallet_totals(worker_id).ok();
+        self.detail_receive_address = Some(match receive_address {
+            Ok(Ok(Some(address))) => ReceiveAddress::Current(address),
+            Ok(Ok(None)) => ReceiveAddress::Unavailable("daemon did not report a current address".to_string()),
+            Ok(Err(e)) => ReceiveAddress::Unavailable(e.to_string()),
+            Err(_) => ReceiveAddress::Unavailable(format!("no answer within {}s", REFRESH_FETCH_TIMEOUT.as_secs())),
+        });
         self.detail_view_wallet = Some(worker_id);
         self.detail_wallet_scroll = 0; // Reset scroll when opening wallet detail
         self.detail_addresses_scroll = 0;
@@ -1506,11 +2038,91 @@ impl App {
         } else {
             None
         };
-        self.clear_status();
+        match utxo_warning {
+            Some(warning) => self.set_status(warning),
+            None => self.clear_status(),
+        }
 
         Ok(())
     }
 
+    /// `N` in the wallet detail view: derive a fresh receive address and show
+    /// it in place of the current one
+    async fn rotate_receive_address(&mut self, worker_id: usize) {
+        if let Some(reason) = self.wallets.iter().find(|w| w.worker_id == worker_id).and_then(|w| w.not_ready_reason()) {
+            self.set_status(format!("✗ {}", reason));
+            return;
+        }
+
+        self.set_status(format!("Generating a new receive address for wallet {}...", worker_id));
+        match tokio::time::timeout(REFRESH_FETCH_TIMEOUT, self.wallet_manager.new_receive_address(worker_id)).await {
+            Ok(Ok(address)) => {
+                let previous = self.detail_receive_address.as_ref().and_then(|r| r.address()).map(str::to_string);
+                let status = match previous {
+                    Some(_) => "✓ New receive address generated - the previous one still receives funds",
+                    None => "✓ New receive address generated",
+                };
+                self.detail_receive_address = Some(ReceiveAddress::Generated { address, previous });
+                self.set_status(status.to_string());
+            }
+            Ok(Err(e)) => self.set_status(format!("✗ Failed to generate an address: {}", e)),
+            Err(_) => self.set_status(format!("✗ Wallet {} did not answer within {}s", worker_id, REFRESH_FETCH_TIMEOUT.as_secs())),
+        }
+    }
+
+    /// `r` in the wallet detail view: re-query only this worker and patch its
+    /// entry in the wallet list, keeping the scroll position
+    async fn refresh_wallet_detail(&mut self, worker_id: usize) {
+        self.set_status(format!("Refreshing wallet {}...", worker_id));
+
+        let (info, address_balances, utxos) = tokio::join!(
+            tokio::time::timeout(REFRESH_FETCH_TIMEOUT, self.wallet_manager.get_wallet_info(worker_id)),
+            tokio::time::timeout(REFRESH_FETCH_TIMEOUT, self.wallet_manager.get_balance_detailed(worker_id)),
+            tokio::time::timeout(REFRESH_FETCH_TIMEOUT, self.wallet_manager.get_utxos(worker_id)),
+        );
+
+        if let Ok(Ok(info)) = info {
+            if let Some(wallet) = self.wallets.iter_mut().find(|w| w.worker_id == worker_id) {
+                *wallet = info;
+            }
+            self.dashboard.update_wallets(self.wallets.clone());
+        }
+
+        let address_balances = match address_balances {
+            Ok(Ok(balances)) => balances,
+            Ok(Err(e)) => {
+                self.set_status(format!("✗ Failed to refresh wallet {}: {}", worker_id, e));
+                return;
+            }
+            Err(_) => {
+                self.set_status(format!("✗ Wallet {} did not answer within {}s", worker_id, REFRESH_FETCH_TIMEOUT.as_secs()));
+                return;
+            }
+        };
+        self.detail_wallet_addresses = address_balances;
+
+        match utxos {
+            Ok(Ok(utxos)) => {
+                self.detail_wallet_utxos = utxos;
+                self.detail_wallet_totals = self.wallet_manager.wallet_totals(worker_id).ok();
+                self.selected_tx_index = match self.detail_wallet_utxos.len() {
+                    0 => None,
+                    len => Some(self.selected_tx_index.unwrap_or(0).min(len - 1)),
+                };
+                if self.selected_tx_index.is_none() {
+                    self.show_tx_detail = false;
+                }
+                self.set_status(format!("✓ Refreshed wallet {}", worker_id));
+            }
+            // Keep the activity already shown rather than blanking it
+            Ok(Err(e)) => self.set_status(format!("⚠ Refreshed balances; activity unavailable: {}", e)),
+            Err(_) => self.set_status(format!(
+                "⚠ Refreshed balances; kaspad did not answer within {}s",
+                REFRESH_FETCH_TIMEOUT.as_secs()
+            )),
+        }
+    }
+
     async fn handle_generate_wallet(&mut self) -> Result<()> {
         if self.selected_index >= self.wallets.len() {
             return Ok(());
@@ -1524,28 +2136,36 @@ impl App {
             return Ok(());
         }
 
+        if let Some(reason) = wallet.not_ready_reason() {
+            self.set_status(format!("✗ {}", reason));
+            return Ok(());
+        }
+
         if wallet.address.is_some() {
             self.set_status(format!("✗ Wallet {} already exists", worker_id));
             return Ok(());
         }
 
-        self.set_status(format!("Generating wallet for worker {}...", worker_id));
-
-        // Use default password from config or "password"
-        let password = self.config.get(&format!("W{}_KASWALLET_PASSWORD", worker_id))
-            .unwrap_or("password");
-
-        match self.wallet_manager.generate_wallet(worker_id, password).await {
-            Ok(address) => {
-                self.set_status(format!("✓ Generated wallet {}: {}", worker_id, address));
-                // Refresh wallet data
-                tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
-                self.refresh_data().await?;
-            }
-            Err(e) => {
-                self.set_status(format!("✗ Failed to generate wallet {}: {}", worker_id, e));
+        // No dialog here, so a prompt source resolves to None
+        let password = self.wallet_passwords.resolve(worker_id, &self.config, None).ok();
+        let manager = Arc::clone(&self.wallet_manager);
+
+        self.spawn_action(format!("Generating wallet for worker {}", worker_id), async move {
+            match manager.generate_wallet(worker_id, password.as_ref()).await {
+                Ok(address) => {
+                    // Give the daemon a moment before the wallet list is re-read
+                    tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+                    ActionOutcome {
+                        status: format!("✓ Generated wallet {}: {}", worker_id, address),
+                        refresh: true,
+                    }
+                }
+                Err(e) => ActionOutcome {
+                    status: format!("✗ Failed to generate wallet {}: {}", worker_id, e),
+                    refresh: false,
+                },
             }
-        }
+        });
 
         Ok(())
     }
@@ -1557,8 +2177,8 @@ impl App {
 
         let wallet = &self.wallets[self.selected_index];
 
-        if !wallet.container_running {
-            self.set_status(format!("✗ Wallet {} container not running", wallet.worker_id));
+        if let Some(reason) = wallet.not_ready_reason() {
+            self.set_status(format!("✗ {}", reason));
             return;
         }
 
@@ -1569,6 +2189,9 @@ impl App {
 
         // Capture source address
         self.send_source_address = wallet.address.clone().unwrap_or_default();
+        let worker_id = wallet.worker_id;
+        self.send_needs_password = self.wallet_passwords.source_for(worker_id) == PasswordSource::Prompt;
+        self.send_password.zeroize();
 
         // Open the send dialog
         self.show_send_dialog = true;
@@ -1577,11 +2200,20 @@ impl App {
         self.send_input_field = 0;
         self.send_use_wallet_selector = false;
         self.send_selected_wallet_index = 0;
-        self.set_status("Enter transaction details | Tab: switch | s: select wallet | Enter: send | Esc: cancel".to_string());
+        self.send_all = false;
+        self.refresh_send_fee();
+        self.set_status("Enter transaction details | Tab: switch | a: send all | s: select wallet | Enter: send | Esc: cancel".to_string());
     }
 
     async fn handle_send_dialog_key(&mut self, key: KeyCode) -> Result<()> {
         match key {
+            // Password field takes every character, including the s/a shortcuts
+            KeyCode::Char(c) if self.send_input_field == 2 => {
+                self.send_password.push(c);
+            }
+            KeyCode::Backspace if self.send_input_field == 2 => {
+                self.send_password.pop();
+            }
             KeyCode::Char('s') | KeyCode::Char('S') => {
                 // Toggle wallet selector mode
                 if self.send_input_field == 1 {
@@ -1595,11 +2227,26 @@ impl App {
                     }
                 }
             }
+            KeyCode::Char('a') | KeyCode::Char('A') if self.send_input_field == 0 => {
+                // Toggle sending the entire balance
+                self.send_all = !self.send_all;
+                self.refresh_send_fee();
+                if self.send_all {
+                    self.send_amount.clear();
+                    self.set_status("Sending entire balance (no change output) | a: enter an amount instead".to_string());
+                } else {
+                    self.set_status("Enter amount | a: send all".to_string());
+                }
+            }
             KeyCode::Char(c) => {
                 if self.send_input_field == 0 {
+                    if self.send_all {
+                        return Ok(());
+                    }
                     // Amount field - only allow numbers and decimal point
                     if c.is_ascii_digit() || c == '.' {
                         self.send_amount.push(c);
+                        self.refresh_send_fee();
                     }
                 } else if !self.send_use_wallet_selector {
                     // Address field (manual entry only)
@@ -1609,29 +2256,30 @@ impl App {
             KeyCode::Backspace => {
                 if self.send_input_field == 0 {
                     self.send_amount.pop();
+                    self.refresh_send_fee();
                 } else if !self.send_use_wallet_selector {
                     self.send_address.pop();
                 }
             }
             KeyCode::Up => {
                 // Navigate wallet selector
-                if self.send_input_field == 1 && self.send_use_wallet_selector {
-                    if self.send_selected_wallet_index > 0 {
-                        self.send_selected_wallet_index -= 1;
-                    }
+                if self.send_input_field == 1 && self.send_use_wallet_selector && self.send_selected_wallet_index > 0 {
+                    self.send_selected_wallet_index -= 1;
                 }
             }
             KeyCode::Down => {
                 // Navigate wallet selector
-                if self.send_input_field == 1 && self.send_use_wallet_selector {
-                    if self.send_selected_wallet_index + 1 < self.wallets.len() {
-                        self.send_selected_wallet_index += 1;
-                    }
+                if self.send_input_field == 1
+                    && self.send_use_wallet_selector
+                    && self.send_selected_wallet_index + 1 < self.wallets.len()
+                {
+                    self.send_selected_wallet_index += 1;
                 }
             }
             KeyCode::Tab => {
                 // Switch between fields
-                self.send_input_field = if self.send_input_field == 0 { 1 } else { 0 };
+                let fields = if self.send_needs_password { 3 } else { 2 };
+                self.send_input_field = (self.send_input_field + 1) % fields;
             }
             KeyCode::Enter => {
                 // Send transaction
@@ -1642,6 +2290,7 @@ impl App {
                 self.show_send_dialog = false;
                 self.send_amount.clear();
                 self.send_address.clear();
+                self.send_password.zeroize();
                 self.set_status("Transaction cancelled".to_string());
             }
             _ => {}
@@ -1649,9 +2298,72 @@ impl App {
         Ok(())
     }
 
+    /// Yellow hint for the send dialog: warn when change would be dust.
+    /// Read off the fee estimate, so it follows the same policy.
+    fn send_change_hint(&self) -> Option<String> {
+        if self.send_all {
+            return None;
+        }
+        match self.send_fee? {
+            SendFeePreview::Insufficient => Some("⚠ Amount plus estimated fee exceeds the spendable balance".to_string()),
+            SendFeePreview::Ready(estimate) if estimate.is_dust_change() => Some(format!(
+                "⚠ Change of {}{} KAS is below dust and would be lost to fees - press 'a' to send all",
+                if estimate.at_least { "at most " } else { "" },
+                crate::utils::format_sompi(estimate.change_sompi)
+            )),
+            _ => None,
+        }
+    }
+
+    /// Re-estimate the send's fee in the background after the amount
+    /// changes. A newer request replaces the one in flight, whose answer is
+    /// dropped with its receiver.
+    fn refresh_send_fee(&mut self) {
+        self.send_fee_rx = None;
+        let amount_sompi = if self.send_all {
+            None
+        } else {
+            match crate::utils::kas_to_sompi(&self.send_amount) {
+                Ok(amount) if amount > 0 => Some(amount),
+                _ => {
+                    self.send_fee = None;
+                    return;
+                }
+            }
+        };
+        let Some(worker_id) = self.wallets.get(self.selected_index).map(|w| w.worker_id) else { return };
+
+        // A standard send's fee doesn't depend on where it goes, so estimate
+        // against the wallet's own address while the destination is typed
+        let to_address = self.send_source_address.clone();
+        let manager = Arc::clone(&self.wallet_manager);
+        let (estimate_tx, estimate_rx) = tokio::sync::oneshot::channel();
+        tokio::spawn(async move {
+            // The policy `send_transaction` applies, so the preview matches the send
+            let estimate = manager.estimate_fee(worker_id, &to_address, amount_sompi, SendFeePolicy::default()).await;
+            let _ = estimate_tx.send(estimate);
+        });
+        self.send_fee = Some(SendFeePreview::Estimating);
+        self.send_fee_rx = Some(estimate_rx);
+    }
+
+    /// Pick up a finished fee estimate. A failure other than the funds
+    /// falling short shows the estimate as unavailable rather than a wrong
+    /// number.
+    fn poll_send_fee(&mut self) {
+        let Some(estimate_rx) = self.send_fee_rx.as_mut() else { return };
+        self.send_fee = match estimate_rx.try_recv() {
+            Ok(Ok(estimate)) => Some(SendFeePreview::Ready(estimate)),
+            Ok(Err(crate::core::Error::InvalidInput(_))) => Some(SendFeePreview::Insufficient),
+            Ok(Err(_)) | Err(tokio::sync::oneshot::error::TryRecvError::Closed) => Some(SendFeePreview::Unavailable),
+            Err(tokio::sync::oneshot::error::TryRecvError::Empty) => return,
+        };
+        self.send_fee_rx = None;
+    }
+
     async fn execute_send_transaction(&mut self) -> Result<()> {
         // Validate inputs
-        if self.send_amount.is_empty() {
+        if self.send_amount.is_empty() && !self.send_all {
             self.set_status("✗ Amount is required".to_string());
             return Ok(());
         }
@@ -1678,15 +2390,20 @@ impl App {
             self.send_address.clone()
         };
 
-        let amount: f64 = match self.send_amount.parse() {
-            Ok(a) => a,
-            Err(_) => {
-                self.set_status("✗ Invalid amount".to_string());
-                return Ok(());
+        // Exact decimal parse - no float rounding, no sub-sompi amounts
+        let amount_sompi = if self.send_all {
+            0 // Ignored by the daemon for send-all
+        } else {
+            match crate::utils::kas_to_sompi(&self.send_amount) {
+                Ok(a) => a,
+                Err(e) => {
+                    self.set_status(format!("✗ {}", e));
+                    return Ok(());
+                }
             }
         };
 
-        if amount <= 0.0 {
+        if amount_sompi == 0 && !self.send_all {
             self.set_status("✗ Amount must be greater than 0".to_string());
             return Ok(());
         }
@@ -1700,20 +2417,42 @@ impl App {
 
         // Check if wallet has sufficient balance
         if let Some(balance) = wallet.balance {
-            if amount > balance {
+            if !self.send_all && crate::utils::sompi_to_kas(amount_sompi) > balance {
                 self.set_status(format!("✗ Insufficient balance. Available: {:.8} KAS", balance));
                 return Ok(());
             }
         }
 
-        self.set_status(format!("Sending {:.8} KAS to {}...", amount, destination_address));
+        let prompted = if self.send_needs_password {
+            if self.send_password.is_empty() {
+                self.set_status("✗ Wallet password is required".to_string());
+                return Ok(());
+            }
+            Some(WalletPassword::new(std::mem::take(&mut *self.send_password)))
+        } else {
+            None
+        };
+        let password = match self.wallet_passwords.resolve(worker_id, &self.config, prompted) {
+            Ok(p) => p,
+            Err(e) => {
+                self.set_status(format!("✗ {:#}", e));
+                return Ok(());
+            }
+        };
 
-        // Get password from config
-        let password = self.config.get(&format!("W{}_KASWALLET_PASSWORD", worker_id))
-            .unwrap_or("password");
+        if self.send_all {
+            self.set_status(format!("Sending entire balance to {}...", destination_address));
+        } else {
+            self.set_status(format!("Sending {} KAS to {}...", crate::utils::format_sompi(amount_sompi), destination_address));
+        }
 
         // Send transaction
-        match self.wallet_manager.send_transaction(worker_id, &destination_address, amount, password).await {
+        match self.wallet_manager.send_transaction(worker_id, &destination_address, amount_sompi, &password, self.send_all).await {
+            Ok(summary) if crate::core::dry_run::enabled() => {
+                // Already reads "(dry-run) Would send ..."
+                self.set_status(summary);
+                self.show_send_dialog = false;
+            }
             Ok(tx_id) => {
                 self.set_status(format!("✓ Transaction sent! ID: {}", tx_id));
                 self.show_send_dialog = false;
@@ -1733,7 +2472,7 @@ impl App {
     }
 
     async fn handle_service_start(&mut self) -> Result<()> {
-        if self.selected_index >= self.containers.len() {
+        if !self.selected_service_visible() {
             return Ok(());
         }
 
@@ -1754,7 +2493,7 @@ impl App {
     }
 
     async fn handle_service_stop(&mut self) -> Result<()> {
-        if self.selected_index >= self.containers.len() {
+        if !self.selected_service_visible() {
             return Ok(());
         }
 
@@ -1775,7 +2514,7 @@ impl App {
     }
 
     async fn handle_service_restart(&mut self) -> Result<()> {
-        if self.selected_index >= self.containers.len() {
+        if !self.selected_service_visible() {
             return Ok(());
         }
 
@@ -1808,58 +2547,76 @@ impl App {
         }
     }
 
-    async fn handle_profile_toggle(&mut self) -> Result<()> {
+    async fn handle_profile_start(&mut self) -> Result<()> {
         let profile = match self.get_profile_name(self.selected_index) {
             Some(p) => p,
             None => return Ok(()),
         };
 
-        // Check if profile is active
-        let is_active = self.active_profiles.contains(&profile);
+        let docker = self.docker.clone();
+        self.spawn_action(format!("Starting profile {}", profile), async move {
+            match docker.start_profile(&profile).await {
+                Ok(warnings) => ActionOutcome {
+                    status: match warnings.first() {
+                        Some(first) if warnings.len() > 1 => {
+                            format!("✓ Started profile {} (⚠ {} and {} more)", profile, first, warnings.len() - 1)
+                        }
+                        Some(first) => format!("✓ Started profile {} (⚠ {})", profile, first),
+                        None => format!("✓ Started profile {}", profile),
+                    },
+                    refresh: true,
+                },
+                Err(e) => ActionOutcome {
+                    status: format!("✗ Failed to start profile {}: {}", profile, e),
+                    refresh: false,
+                },
+            }
+        });
 
-        if is_active {
-            self.handle_profile_stop().await
-        } else {
-            self.handle_profile_start().await
-        }
+        Ok(())
     }
 
-    async fn handle_profile_start(&mut self) -> Result<()> {
+    async fn handle_profile_stop(&mut self) -> Result<()> {
         let profile = match self.get_profile_name(self.selected_index) {
             Some(p) => p,
             None => return Ok(()),
         };
 
-        self.set_status(format!("Starting profile {}...", profile));
-
-        match self.docker.start_profile(&profile).await {
-            Ok(_) => {
-                self.set_status(format!("✓ Started profile {}", profile));
-                self.refresh_data().await?;
-            }
-            Err(e) => {
-                self.set_status(format!("✗ Failed to start profile {}: {}", profile, e));
+        let docker = self.docker.clone();
+        self.spawn_action(format!("Stopping profile {}", profile), async move {
+            match docker.stop_profile(&profile).await {
+                Ok(_) => ActionOutcome {
+                    status: format!("✓ Stopped profile {}", profile),
+                    refresh: true,
+                },
+                Err(e) => ActionOutcome {
+                    status: format!("✗ Failed to stop profile {}: {}", profile, e),
+                    refresh: false,
+                },
             }
-        }
+        });
 
         Ok(())
     }
 
-    async fn handle_profile_stop(&mut self) -> Result<()> {
+    async fn handle_profile_recreate(&mut self) -> Result<()> {
         let profile = match self.get_profile_name(self.selected_index) {
             Some(p) => p,
             None => return Ok(()),
         };
 
-        self.set_status(format!("Stopping profile {}...", profile));
+        self.set_status(format!("Recreating profile {}...", profile));
 
-        match self.docker.stop_profile(&profile).await {
-            Ok(_) => {
-                self.set_status(format!("✓ Stopped profile {}", profile));
+        match self.docker.recreate_profile(&profile).await {
+            Ok(recreated) => {
+                for service in &recreated {
+                    self.pending_restart.remove(service);
+                }
+                self.set_status(format!("✓ Recreated {}: {}", profile, recreated.join(", ")));
                 self.refresh_data().await?;
             }
             Err(e) => {
-                self.set_status(format!("✗ Failed to stop profile {}: {}", profile, e));
+                self.set_status(format!("✗ Failed to recreate profile {}: {}", profile, e));
             }
         }
 
@@ -1911,7 +2668,7 @@ impl App {
             Ok(tokens) => {
                 match self.config.save() {
                     Ok(_) => {
-                        self.set_status(format!("✓ Generated {} tokens and saved to .env", tokens.len()));
+                        self.set_status(crate::core::dry_run::tag(&format!("✓ Generated {} tokens and saved to .env", tokens.len())));
                         // Reload config
                         self.config = ConfigManager::load_from_project()?;
                         self.refresh_data().await?;
@@ -1930,16 +2687,14 @@ impl App {
     }
 
     async fn handle_upgrade(&mut self) -> Result<()> {
-        self.set_status("Pulling latest Docker images...".to_string());
-
-        match self.docker.pull_images().await {
-            Ok(_) => {
-                self.set_status("✓ Images updated. Restart services to apply changes.".to_string());
-            }
-            Err(e) => {
-                self.set_status(format!("✗ Failed to pull images: {}", e));
-            }
-        }
+        let docker = self.docker.clone();
+        self.spawn_action("Pulling latest Docker images".to_string(), async move {
+            let status = match docker.pull_images().await {
+                Ok(_) => "✓ Images updated. Restart services to apply changes.".to_string(),
+                Err(e) => format!("✗ Failed to pull images: {}", e),
+            };
+            ActionOutcome { status, refresh: false }
+        });
 
         Ok(())
     }
@@ -1982,7 +2737,7 @@ impl App {
 
         match self.ssl_manager.force_renewal().await {
             Ok(_) => {
-                self.set_status("✓ Traefik restarted. Certificate will renew if needed.".to_string());
+                self.set_status(crate::core::dry_run::tag("✓ Traefik restarted. Certificate will renew if needed (force: igra-cli ssl renew --reissue)"));
                 // Wait a moment then refresh
                 tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
                 self.refresh_data().await?;
@@ -1998,35 +2753,17 @@ impl App {
     async fn handle_storage_prune(&mut self) -> Result<()> {
         self.set_status("Pruning Docker build cache...".to_string());
 
-        let output = std::process::Command::new("docker")
-            .args(&["builder", "prune", "-f"])
-            .output();
-
-        match output {
-            Ok(result) => {
-                if result.status.success() {
-                    let stdout = String::from_utf8_lossy(&result.stdout);
-                    // Parse reclaimed space from output
-                    if let Some(line) = stdout.lines().find(|l| l.contains("Total reclaimed space")) {
-                        self.set_status(format!("✓ Build cache pruned - {}", line.trim()));
-                    } else {
-                        self.set_status("✓ Build cache pruned successfully".to_string());
-                    }
-
-                    // Force immediate storage snapshot and refresh display
-                    tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
-                    tokio::spawn(async {
-                        let _ = crate::core::storage::check_and_save_snapshot_if_needed().await;
-                    });
-                    self.storage_last_update = None; // Force refresh
-                    self.refresh_data().await?;
-                } else {
-                    let stderr = String::from_utf8_lossy(&result.stderr);
-                    self.set_status(format!("✗ Failed to prune cache: {}", stderr));
-                }
+        match crate::core::storage::prune(PruneTarget::BuildCache) {
+            Ok(reclaimed) => {
+                let message = match reclaimed {
+                    Some(line) => format!("✓ Build cache pruned - {}", line),
+                    None => "✓ Build cache pruned successfully".to_string(),
+                };
+                self.set_status(crate::core::dry_run::tag(&message));
+                self.refresh_after_prune().await?;
             }
             Err(e) => {
-                self.set_status(format!("✗ Failed to run docker command: {}", e));
+                self.set_status(format!("✗ Failed to prune cache: {:#}", e));
             }
         }
 
@@ -2036,41 +2773,33 @@ impl App {
     async fn handle_storage_prune_images(&mut self) -> Result<()> {
         self.set_status("Pruning unused Docker images...".to_string());
 
-        let output = std::process::Command::new("docker")
-            .args(&["image", "prune", "-f"])
-            .output();
-
-        match output {
-            Ok(result) => {
-                if result.status.success() {
-                    let stdout = String::from_utf8_lossy(&result.stdout);
-                    // Parse reclaimed space from output
-                    if let Some(line) = stdout.lines().find(|l| l.contains("Total reclaimed space")) {
-                        self.set_status(format!("✓ Images pruned - {}", line.trim()));
-                    } else {
-                        self.set_status("✓ Unused images pruned successfully".to_string());
-                    }
-
-                    // Force immediate storage snapshot and refresh display
-                    tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
-                    tokio::spawn(async {
-                        let _ = crate::core::storage::check_and_save_snapshot_if_needed().await;
-                    });
-                    self.storage_last_update = None; // Force refresh
-                    self.refresh_data().await?;
-                } else {
-                    let stderr = String::from_utf8_lossy(&result.stderr);
-                    self.set_status(format!("✗ Failed to prune images: {}", stderr));
-                }
+        match crate::core::storage::prune(PruneTarget::Images) {
+            Ok(reclaimed) => {
+                let message = match reclaimed {
+                    Some(line) => format!("✓ Images pruned - {}", line),
+                    None => "✓ Unused images pruned successfully".to_string(),
+                };
+                self.set_status(crate::core::dry_run::tag(&message));
+                self.refresh_after_prune().await?;
             }
             Err(e) => {
-                self.set_status(format!("✗ Failed to run docker command: {}", e));
+                self.set_status(format!("✗ Failed to prune images: {:#}", e));
             }
         }
 
         Ok(())
     }
 
+    /// Force an immediate storage snapshot and refresh the display
+    async fn refresh_after_prune(&mut self) -> Result<()> {
+        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+        tokio::spawn(async {
+            let _ = crate::core::storage::check_and_save_snapshot_if_needed().await;
+        });
+        self.storage_last_update = None; // Force refresh
+        self.refresh_data().await
+    }
+
     async fn handle_detail_view_key(&mut self, key: KeyCode, modifiers: event::KeyModifiers) -> Result<()> {
         match key {
             KeyCode::Up | KeyCode::Char('k') => {
@@ -2081,13 +2810,13 @@ impl App {
 
                     if is_ctrl && is_shift {
                         // Jump to top
-                        self.detail_logs_scroll_offset = self.detail_logs.len();
+                        self.detail_logs_scroll_offset = self.filtered_detail_log_count();
                     } else if is_ctrl {
                         // Fast scroll up (50 lines)
-                        self.detail_logs_scroll_offset = (self.detail_logs_scroll_offset + 50).min(self.detail_logs.len());
+                        self.detail_logs_scroll_offset = (self.detail_logs_scroll_offset + 50).min(self.filtered_detail_log_count());
                     } else {
                         // Normal scroll up (5 lines)
-                        self.detail_logs_scroll_offset = (self.detail_logs_scroll_offset + 5).min(self.detail_logs.len());
+                        self.detail_logs_scroll_offset = (self.detail_logs_scroll_offset + 5).min(self.filtered_detail_log_count());
                     }
                 }
             }
@@ -2113,7 +2842,7 @@ impl App {
                 // Page up in logs
                 if self.detail_view_service.is_some() {
                     // Scroll by ~100 lines (full screen)
-                    self.detail_logs_scroll_offset = (self.detail_logs_scroll_offset + 100).min(self.detail_logs.len());
+                    self.detail_logs_scroll_offset = (self.detail_logs_scroll_offset + 100).min(self.filtered_detail_log_count());
                 }
             }
             KeyCode::PageDown => {
@@ -2141,7 +2870,9 @@ impl App {
             }
             KeyCode::Esc | KeyCode::Char('q') => {
                 // Close modal if showing, otherwise exit detail view
-                if self.show_tx_detail {
+                if self.show_address_qr {
+                    self.show_address_qr = false;
+                } else if self.show_tx_detail {
                     self.show_tx_detail = false;
                 } else {
                     // Exit detail view
@@ -2152,7 +2883,9 @@ impl App {
                     self.detail_logs.clear();
                     self.detail_view_wallet = None;
                     self.detail_wallet_addresses.clear();
+                    self.detail_receive_address = None;
                     self.detail_wallet_utxos.clear();
+                    self.detail_wallet_totals = None;
                     self.detail_wallet_scroll = 0;
                     self.detail_addresses_scroll = 0;
                     self.selected_tx_index = None;
@@ -2210,10 +2943,10 @@ impl App {
                 // Refresh logs
                 if let Some(service) = &self.detail_view_service {
                     let service = service.clone();
-                    match self.docker.get_logs(&service, Some(INITIAL_LOG_FETCH)).await {
+                    match self.docker.get_logs(&service, Some(self.log_limits.initial_fetch), false).await {
                         Ok(logs) => {
                             self.detail_logs = logs.lines()
-                                .map(|s| crate::core::parse_docker_log_line(s))
+                                .map(crate::core::parse_docker_log_line)
                                 .collect();
                             self.set_status("✓ Refreshed logs".to_string());
                         }
@@ -2221,6 +2954,21 @@ impl App {
                             self.set_status(format!("✗ Failed to refresh logs: {}", e));
                         }
                     }
+                } else if let Some(worker_id) = self.detail_view_wallet {
+                    self.refresh_wallet_detail(worker_id).await;
+                }
+            }
+            KeyCode::Char('N') => {
+                // New receive address in wallet detail view
+                if let Some(worker_id) = self.detail_view_wallet {
+                    self.rotate_receive_address(worker_id).await;
+                }
+            }
+            KeyCode::Char('Q') => {
+                // Toggle receive address QR code in wallet detail view
+                if self.detail_view_wallet.is_some() {
+                    self.show_address_qr = !self.show_address_qr;
+                    self.show_tx_detail = false;
                 }
             }
             KeyCode::Char('l') => {
@@ -2247,7 +2995,7 @@ impl App {
                 // Cycle through log filters: All → Error → Warn → Info → Debug → Trace → All
                 if self.detail_view_service.is_some() {
                     use crate::core::LogLevel;
-                    self.detail_logs_filter = match self.detail_logs_filter {
+                    let next = match self.detail_logs_filter {
                         None => Some(LogLevel::Error),
                         Some(LogLevel::Error) => Some(LogLevel::Warn),
                         Some(LogLevel::Warn) => Some(LogLevel::Info),
@@ -2255,63 +3003,43 @@ impl App {
                         Some(LogLevel::Debug) => Some(LogLevel::Trace),
                         Some(LogLevel::Trace) | Some(LogLevel::Unknown) => None,
                     };
-                    let filter_name = match self.detail_logs_filter {
-                        None => "ALL",
-                        Some(LogLevel::Error) => "ERROR",
-                        Some(LogLevel::Warn) => "WARN",
-                        Some(LogLevel::Info) => "INFO",
-                        Some(LogLevel::Debug) => "DEBUG",
-                        Some(LogLevel::Trace) => "TRACE",
-                        Some(LogLevel::Unknown) => "ALL",
-                    };
-                    self.set_status(format!("✓ Log filter: {}", filter_name));
+                    self.set_detail_logs_filter(next);
                 }
             }
             KeyCode::Char('e') => {
                 // Filter ERROR only
                 if self.detail_view_service.is_some() {
-                    use crate::core::LogLevel;
-                    self.detail_logs_filter = Some(LogLevel::Error);
-                    self.set_status("✓ Log filter: ERROR".to_string());
+                    self.set_detail_logs_filter(Some(crate::core::LogLevel::Error));
                 }
             }
             KeyCode::Char('w') => {
                 // Filter WARN only
                 if self.detail_view_service.is_some() {
-                    use crate::core::LogLevel;
-                    self.detail_logs_filter = Some(LogLevel::Warn);
-                    self.set_status("✓ Log filter: WARN".to_string());
+                    self.set_detail_logs_filter(Some(crate::core::LogLevel::Warn));
                 }
             }
             KeyCode::Char('i') => {
                 // Filter INFO only
                 if self.detail_view_service.is_some() {
-                    use crate::core::LogLevel;
-                    self.detail_logs_filter = Some(LogLevel::Info);
-                    self.set_status("✓ Log filter: INFO".to_string());
+                    self.set_detail_logs_filter(Some(crate::core::LogLevel::Info));
                 }
             }
             KeyCode::Char('d') => {
                 // Filter DEBUG in log view
                 if self.detail_view_service.is_some() {
-                    use crate::core::LogLevel;
-                    self.detail_logs_filter = Some(LogLevel::Debug);
-                    self.set_status("✓ Log filter: DEBUG".to_string());
+                    self.set_detail_logs_filter(Some(crate::core::LogLevel::Debug));
                 }
             }
             KeyCode::Char('t') => {
                 // Filter TRACE only
                 if self.detail_view_service.is_some() {
-                    use crate::core::LogLevel;
-                    self.detail_logs_filter = Some(LogLevel::Trace);
-                    self.set_status("✓ Log filter: TRACE".to_string());
+                    self.set_detail_logs_filter(Some(crate::core::LogLevel::Trace));
                 }
             }
             KeyCode::Char('a') => {
                 // Show ALL logs (clear filter)
                 if self.detail_view_service.is_some() {
-                    self.detail_logs_filter = None;
-                    self.set_status("✓ Log filter: ALL".to_string());
+                    self.set_detail_logs_filter(None);
                 }
             }
             _ => {}
@@ -2319,6 +3047,23 @@ impl App {
         Ok(())
     }
 
+    /// Number of buffered detail log lines that pass the active level filter
+    fn filtered_detail_log_count(&self) -> usize {
+        self.detail_logs
+            .iter()
+            .filter(|log| log.matches_filter(self.detail_logs_filter.as_ref()))
+            .count()
+    }
+
+    /// Change the detail log filter; the existing buffer re-filters on the next
+    /// frame, so jump back to the latest matching lines
+    fn set_detail_logs_filter(&mut self, filter: Option<crate::core::LogLevel>) {
+        self.detail_logs_filter = filter;
+        self.detail_logs_scroll_offset = 0;
+        let label = crate::core::LogLevel::filter_label(self.detail_logs_filter.as_ref());
+        self.set_status(format!("✓ Log filter: {}", label));
+    }
+
     fn enter_edit_mode(&mut self) {
         if self.selected_index >= self.config_data.len() {
             return;
@@ -2326,25 +3071,65 @@ impl App {
 
         let (key, value) = &self.config_data[self.selected_index];
 
-        // Don't allow editing of sensitive fields
-        if key.contains("PASSWORD") || key.contains("SECRET") || key.contains("KEY") || key.contains("TOKEN") {
-            self.set_status("Cannot edit sensitive fields directly. Edit .env file manually.".to_string());
-            return;
-        }
+        // Edit what's in the file, not the environment override
+        let file_value = self.config.file_value(key).unwrap_or(value).to_string();
 
         self.edit_mode = true;
         self.edit_key = Some(key.clone());
-        self.edit_buffer = value.clone();
-        self.set_status(format!("Editing {} - [Enter] Save | [Esc] Cancel", key));
+        self.edit_buffer = file_value;
+        self.edit_cursor = self.edit_buffer.chars().count();
+
+        // Secrets are shown in clear while editing, so make that obvious
+        if self.config.source(key) == Some(crate::core::config::ConfigSource::Environment) {
+            self.set_status(format!("⚠ {} is overridden by an environment variable - edits to .env won't take effect", key));
+        } else if crate::utils::is_sensitive_key(key) {
+            self.set_status(format!("⚠ Editing secret {} (shown in clear) - [Enter] Save | [Esc] Cancel", key));
+        } else {
+            self.set_status(format!("Editing {} - [Enter] Save | [Esc] Cancel", key));
+        }
+    }
+
+    /// Byte offset in edit_buffer for a char position
+    fn edit_byte_offset(&self, char_pos: usize) -> usize {
+        self.edit_buffer
+            .char_indices()
+            .nth(char_pos)
+            .map(|(i, _)| i)
+            .unwrap_or(self.edit_buffer.len())
     }
 
     async fn handle_edit_key(&mut self, key: KeyCode) -> Result<()> {
+        let len = self.edit_buffer.chars().count();
         match key {
             KeyCode::Char(c) => {
-                self.edit_buffer.push(c);
+                let offset = self.edit_byte_offset(self.edit_cursor);
+                self.edit_buffer.insert(offset, c);
+                self.edit_cursor += 1;
             }
             KeyCode::Backspace => {
-                self.edit_buffer.pop();
+                if self.edit_cursor > 0 {
+                    self.edit_cursor -= 1;
+                    let offset = self.edit_byte_offset(self.edit_cursor);
+                    self.edit_buffer.remove(offset);
+                }
+            }
+            KeyCode::Delete => {
+                if self.edit_cursor < len {
+                    let offset = self.edit_byte_offset(self.edit_cursor);
+                    self.edit_buffer.remove(offset);
+                }
+            }
+            KeyCode::Left => {
+                self.edit_cursor = self.edit_cursor.saturating_sub(1);
+            }
+            KeyCode::Right => {
+                self.edit_cursor = (self.edit_cursor + 1).min(len);
+            }
+            KeyCode::Home => {
+                self.edit_cursor = 0;
+            }
+            KeyCode::End => {
+                self.edit_cursor = len;
             }
             KeyCode::Enter => {
                 // Save the edit
@@ -2354,6 +3139,7 @@ impl App {
                 // Cancel edit
                 self.edit_mode = false;
                 self.edit_buffer.clear();
+                self.edit_cursor = 0;
                 self.edit_key = None;
                 self.set_status("Edit cancelled".to_string());
             }
@@ -2374,7 +3160,7 @@ impl App {
         let value = self.edit_buffer.clone();
 
         // Validate based on key type
-        let validation_error = self.validate_config_value(&key, &value);
+        let validation_error = ConfigManager::validate_value(&key, &value);
         if let Some(error) = validation_error {
             self.set_status(format!("✗ Validation failed: {}", error));
             return Ok(());
@@ -2386,9 +3172,19 @@ impl App {
         // Save to file
         match self.config.save() {
             Ok(_) => {
-                self.set_status(format!("✓ Saved {} = {}", key, value));
+                let restart = self.note_restart_required(&key);
+                if self.config.source(&key) == Some(crate::core::config::ConfigSource::Environment) {
+                    self.set_status(crate::core::dry_run::tag(&format!("✓ Saved {} to .env (still overridden by environment)", key)));
+                } else if !restart.is_empty() {
+                    self.set_status(crate::core::dry_run::tag(&format!("✓ Saved {} - ⚠ restart {} to apply - [R] Recreate", key, restart.join(", "))));
+                } else if crate::utils::is_sensitive_key(&key) {
+                    self.set_status(crate::core::dry_run::tag(&format!("✓ Saved {}", key)));
+                } else {
+                    self.set_status(crate::core::dry_run::tag(&format!("✓ Saved {} = {}", key, value)));
+                }
                 self.edit_mode = false;
                 self.edit_buffer.clear();
+                self.edit_cursor = 0;
                 self.edit_key = None;
 
                 // Reload config
@@ -2403,53 +3199,425 @@ impl App {
         Ok(())
     }
 
-    fn validate_config_value(&self, key: &str, value: &str) -> Option<String> {
-        use crate::utils::{is_valid_domain, is_valid_email, is_valid_hex};
+    /// Record which running services read `key` from the compose file and so
+    /// must be recreated before an edit applies. Returns those services.
+    fn note_restart_required(&mut self, key: &str) -> Vec<String> {
+        let Ok(services) = self.docker.parse_compose_file() else {
+            return Vec::new();
+        };
 
-        // Empty values are generally not allowed
-        if value.trim().is_empty() {
-            return Some("Value cannot be empty".to_string());
+        // Stopped services get the new value when they're next started
+        let affected: Vec<String> = crate::core::docker::services_using_env(&services, key)
+            .into_iter()
+            .filter(|service| self.containers.iter().any(|c| &c.name == service && c.state.is_running()))
+            .collect();
+        for service in &affected {
+            self.pending_restart.entry(service.clone()).or_default().insert(key.to_string());
         }
+        affected
+    }
 
-        // Domain validation
-        if key.contains("DOMAIN") && !is_valid_domain(value) {
-            return Some("Invalid domain format".to_string());
+    /// Footer hint while config edits are waiting for a recreate
+    fn pending_restart_hint(&self) -> Option<String> {
+        if self.pending_restart.is_empty() {
+            return None;
         }
+        let services: Vec<&str> = self.pending_restart.keys().map(|s| s.as_str()).collect();
+        Some(format!("⚠ restart {} to apply config changes - [R] Recreate", services.join(", ")))
+    }
 
-        // Email validation
-        if key.contains("EMAIL") && !is_valid_email(value) {
-            return Some("Invalid email format".to_string());
+    /// Recreate the services with pending config edits (only those services,
+    /// not their dependencies)
+    async fn handle_pending_restart(&mut self) -> Result<()> {
+        if self.pending_restart.is_empty() {
+            self.set_status("No config changes waiting for a restart".to_string());
+            return Ok(());
         }
 
-        // Hex validation for keys/secrets
-        if (key.contains("_KEY") || key.contains("_SECRET")) && key != "OVH_APPLICATION_KEY" && key != "OVH_APPLICATION_SECRET" {
-            if !is_valid_hex(value) {
-                return Some("Must be a valid hex string".to_string());
+        let services: Vec<String> = self.pending_restart.keys().cloned().collect();
+        // Profile services are only visible to compose with their profile enabled
+        let mut profiles: Vec<String> = match self.docker.parse_compose_file() {
+            Ok(parsed) => services
+                .iter()
+                .filter_map(|s| parsed.get(s))
+                .flat_map(|config| config.profiles.iter().cloned())
+                .collect(),
+            Err(e) => {
+                self.set_status(format!("✗ Failed to read compose file: {}", e));
+                return Ok(());
+            }
+        };
+        profiles.sort();
+        profiles.dedup();
+
+        self.set_status(format!("Recreating {}...", services.join(", ")));
+
+        match self.docker.recreate_services(&profiles, &services).await {
+            Ok(()) => {
+                self.pending_restart.clear();
+                self.set_status(format!("✓ Recreated {}", services.join(", ")));
+                self.refresh_data().await?;
+            }
+            Err(e) => {
+                self.set_status(format!("✗ Failed to recreate {}: {}", services.join(", "), e));
             }
         }
 
-        // Port validation
-        if key.contains("PORT") {
-            if value.parse::<u16>().is_err() {
-                return Some("Must be a valid port number (1-65535)".to_string());
+        Ok(())
+    }
+
+    fn open_palette(&mut self) {
+        let services = self.containers.iter().map(|c| c.name.clone()).collect();
+        let profiles = (0..).map_while(|i| self.get_profile_name(i)).collect();
+        self.show_help = false;
+        self.palette = Some(CommandPalette::new(services, profiles));
+    }
+
+    async fn handle_palette_key(&mut self, key: KeyCode) -> Result<()> {
+        let Some(palette) = self.palette.as_mut() else { return Ok(()) };
+
+        match key {
+            KeyCode::Esc => self.palette = None,
+            KeyCode::Tab => palette.complete(),
+            KeyCode::Down => palette.select_next(),
+            KeyCode::Up => palette.select_prev(),
+            KeyCode::Backspace => {
+                palette.input.pop();
+                palette.selected = 0;
+                palette.error = None;
             }
+            KeyCode::Char(c) => {
+                palette.input.push(c);
+                palette.selected = 0;
+                palette.error = None;
+            }
+            KeyCode::Enter => match palette.parse() {
+                Ok(command) => {
+                    let input = palette.input.trim().to_string();
+                    self.palette = None;
+                    self.clear_status();
+                    let was_idle = self.pending_action.is_none();
+                    self.run_palette_command(&command).await?;
+
+                    // A command that went to the background is echoed when it finishes
+                    if let Some(pending) = self.pending_action.as_mut().filter(|_| was_idle) {
+                        pending.palette_echo = Some((input, command.keybinding()));
+                        return Ok(());
+                    }
+
+                    // Echo the outcome with the shortcut for next time
+                    let outcome = self.status_message.clone().unwrap_or_else(|| "✓ Done".to_string());
+                    self.echo_palette_outcome(&input, command.keybinding(), &outcome);
+                    if !matches!(command, PaletteCommand::Quit) {
+                        self.set_status(format!("{} - shortcut: {}", outcome, command.keybinding()));
+                    }
+                }
+                Err(e) => palette.error = Some(format!("{} - [Tab] to complete", e)),
+            },
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Add a palette command's result to the "Recent" list
+    fn echo_palette_outcome(&mut self, input: &str, keybinding: &str, outcome: &str) {
+        self.notifications.push_back(format!(
+            "{} :{} → {} ({})",
+            chrono::Local::now().format("%H:%M:%S"),
+            input,
+            outcome,
+            keybinding
+        ));
+        while self.notifications.len() > NOTIFICATION_HISTORY {
+            self.notifications.pop_front();
         }
+    }
 
-        // URL validation (basic)
-        if key.contains("URL") || key.contains("ENDPOINT") {
-            if !value.starts_with("http://") && !value.starts_with("https://") {
-                return Some("Must start with http:// or https://".to_string());
+    /// Run a palette command through the same handler its key uses, with the
+    /// screen and selection set as if the user had navigated there
+    async fn run_palette_command(&mut self, command: &PaletteCommand) -> Result<()> {
+        let select_service = |app: &mut Self, name: &str| {
+            app.current_screen = Screen::Services;
+            app.services_view = ServicesView::Services;
+            app.selected_index = app.containers.iter().position(|c| c.name == name).unwrap_or(usize::MAX);
+            app.update_dashboard_for_current_screen();
+        };
+        let select_profile = |app: &mut Self, name: &str| {
+            app.current_screen = Screen::Services;
+            app.services_view = ServicesView::Profiles;
+            app.selected_index = (0..)
+                .map_while(|i| app.get_profile_name(i))
+                .position(|p| p == name)
+                .unwrap_or(usize::MAX);
+            app.update_dashboard_for_current_screen();
+        };
+        let select_config = |app: &mut Self, section: ConfigSection| {
+            app.current_screen = Screen::Config;
+            app.config_section = section;
+            app.selected_index = 0;
+            app.update_dashboard_for_current_screen();
+        };
+
+        match command {
+            PaletteCommand::Start(name) => {
+                select_service(self, name);
+                self.handle_service_start().await?;
+            }
+            PaletteCommand::Stop(name) => {
+                select_service(self, name);
+                self.handle_service_stop().await?;
+            }
+            PaletteCommand::Restart(name) => {
+                select_service(self, name);
+                self.handle_service_restart().await?;
+            }
+            PaletteCommand::Logs(name) => {
+                select_service(self, name);
+                self.show_service_details().await?;
+            }
+            PaletteCommand::StartProfile(name) => {
+                select_profile(self, name);
+                self.handle_profile_start().await?;
+            }
+            PaletteCommand::StopProfile(name) => {
+                select_profile(self, name);
+                self.handle_profile_stop().await?;
+            }
+            PaletteCommand::RecreateProfile(name) => {
+                select_profile(self, name);
+                self.handle_profile_recreate().await?;
+            }
+            PaletteCommand::RenewSsl => {
+                select_config(self, ConfigSection::SslCerts);
+                self.handle_ssl_renew().await?;
+            }
+            PaletteCommand::CheckSsl => {
+                select_config(self, ConfigSection::SslCerts);
+                self.handle_ssl_check().await?;
+            }
+            PaletteCommand::GenerateTokens => {
+                select_config(self, ConfigSection::RpcTokens);
+                self.handle_generate_tokens().await?;
+            }
+            PaletteCommand::Refresh => {
+                self.refresh_data().await?;
+                self.set_status("✓ Refreshed".to_string());
+            }
+            PaletteCommand::Health => self.start_health_checks(),
+            PaletteCommand::Upgrade => self.handle_upgrade().await?,
+            PaletteCommand::Help => self.show_help = true,
+            PaletteCommand::Quit => self.should_quit = true,
+            PaletteCommand::Goto(screen) => {
+                self.current_screen = *screen;
+                self.selected_index = 0;
+                self.update_dashboard_for_current_screen();
             }
         }
+        Ok(())
+    }
 
-        // Network validation
-        if key == "NETWORK" {
-            if value != "testnet" && value != "mainnet" {
-                return Some("Must be either 'testnet' or 'mainnet'".to_string());
+    fn start_watch_range_input(&mut self, field: RangeField) {
+        self.watch_range_input = Some((field, String::new()));
+        self.set_status(format!("{}: _", field.prompt()));
+    }
+
+    /// Keys while typing a Watch value/fee range (prompt shown in the status bar)
+    fn handle_watch_range_key(&mut self, key: KeyCode) {
+        let Some((field, mut buffer)) = self.watch_range_input.take() else { return };
+
+        match key {
+            KeyCode::Char(c) => buffer.push(c),
+            KeyCode::Backspace => {
+                buffer.pop();
+            }
+            KeyCode::Enter => match RangeFilter::parse(&buffer) {
+                Ok(range) => {
+                    self.watch_filter.set_range(field, range);
+                    self.selected_index = 0;
+                    self.set_status(format!("Watch filter: {}", self.watch_filter.describe()));
+                    return;
+                }
+                Err(e) => {
+                    self.set_status(format!("✗ {} - {}: {}_", e, field.prompt(), buffer));
+                    self.watch_range_input = Some((field, buffer));
+                    return;
+                }
+            },
+            KeyCode::Esc => {
+                self.set_status("Range unchanged".to_string());
+                return;
             }
+            _ => {}
+        }
+
+        self.set_status(format!("{}: {}_", field.prompt(), buffer));
+        self.watch_range_input = Some((field, buffer));
+    }
+
+    fn start_export(&mut self) {
+        if self.export_table(false).is_none() {
+            self.set_status("⚠ Nothing to export on the Storage screen".to_string());
+            return;
+        }
+        self.export_prompt = Some(ExportPrompt::Format);
+        self.set_status(format!("Export {}: [c] CSV | [j] JSON | [Esc] Cancel", self.current_screen.title()));
+    }
+
+    fn handle_export_key(&mut self, prompt: ExportPrompt, key: KeyCode) {
+        match (prompt, key) {
+            (_, KeyCode::Esc) => {
+                self.export_prompt = None;
+                self.set_status("Export cancelled".to_string());
+            }
+            (ExportPrompt::Format, KeyCode::Char(c @ ('c' | 'j'))) => {
+                let format = if c == 'c' { ExportFormat::Csv } else { ExportFormat::Json };
+                if self.current_screen == Screen::Config {
+                    self.export_prompt = Some(ExportPrompt::Secrets(format));
+                    self.set_status("Include secrets (passwords, keys, tokens)? [y/N]".to_string());
+                } else {
+                    self.export_prompt = None;
+                    self.export_current_screen(format, false);
+                }
+            }
+            // Anything but an explicit yes keeps secrets masked
+            (ExportPrompt::Secrets(format), key) => {
+                self.export_prompt = None;
+                let include_secrets = matches!(key, KeyCode::Char('y') | KeyCode::Char('Y'));
+                self.export_current_screen(format, include_secrets);
+            }
+            (ExportPrompt::Format, _) => {}
+        }
+    }
+
+    /// Write the current screen's table to a timestamped file in the working directory
+    fn export_current_screen(&mut self, format: ExportFormat, include_secrets: bool) {
+        let Some(table) = self.export_table(include_secrets) else {
+            return;
+        };
+        let result = std::env::current_dir()
+            .map_err(anyhow::Error::from)
+            .and_then(|dir| table.write(&dir, format));
+
+        match result {
+            Ok(path) => self.set_status(format!(
+                "✓ Exported {} rows{} to {}",
+                table.rows.len(),
+                if include_secrets { " (with secrets)" } else { "" },
+                path.display()
+            )),
+            Err(e) => self.set_status(format!("✗ Export failed: {:#}", e)),
         }
+    }
 
-        None
+    /// The rows the current screen renders, from the same cached data.
+    /// None for screens without a table (Storage).
+    fn export_table(&self, include_secrets: bool) -> Option<ExportTable> {
+        let table = match self.current_screen {
+            Screen::Services => {
+                let mut table = ExportTable::new(
+                    "services",
+                    &["name", "state", "status", "health", "image", "cpu_percent", "memory_mb", "ports"],
+                );
+                for c in self.containers.iter() {
+                    let stats = self.container_stats.get(&c.name);
+                    table.push_row(vec![
+                        c.name.clone(),
+                        format!("{:?}", c.state),
+                        c.status.clone(),
+                        c.health.clone().unwrap_or_default(),
+                        c.image.clone(),
+                        stats.map(|s| format!("{:.1}", s.cpu_percent)).unwrap_or_default(),
+                        stats
+                            .map(|s| format!("{:.1}", s.memory_usage as f64 / 1024.0 / 1024.0))
+                            .unwrap_or_default(),
+                        c.ports.join(" "),
+                    ]);
+                }
+                table
+            }
+            Screen::Wallets => {
+                let mut table = ExportTable::new(
+                    "wallets",
+                    &["worker", "status", "address", "balance_kas", "fees_spent_kas", "burn_rate_kas_per_hour"],
+                );
+                let kas = |v: Option<f64>| v.map(|v| format!("{:.8}", v)).unwrap_or_default();
+                for w in &self.wallets {
+                    table.push_row(vec![
+                        w.worker_id.to_string(),
+                        w.status().to_string(),
+                        w.address.clone().unwrap_or_default(),
+                        kas(w.balance),
+                        kas(w.fees_spent),
+                        kas(w.fee_burn_rate),
+                    ]);
+                }
+                table
+            }
+            Screen::Watch => {
+                let mut table = ExportTable::new(
+                    "transactions",
+                    &[
+                        "timestamp", "type", "hash", "from", "to", "value_ikas", "gas_fee_ikas",
+                        "l1_fee_kas", "status", "block_number", "method", "reorged",
+                    ],
+                );
+                for tx in self.watch_feed.transactions().iter().filter(|tx| self.watch_filter.matches(tx)) {
+                    table.push_row(vec![
+                        tx.timestamp.format("%Y-%m-%d %H:%M:%S").to_string(),
+                        tx.tx_type.to_string(),
+                        tx.hash.clone(),
+                        tx.from.clone(),
+                        tx.to.clone().unwrap_or_default(),
+                        tx.value_ikas().to_string(),
+                        tx.gas_fee_ikas().to_string(),
+                        tx.l1_fee.map(|f| f.to_string()).unwrap_or_default(),
+                        tx.status.to_string(),
+                        tx.block_number.to_string(),
+                        tx.method.clone().unwrap_or_default(),
+                        tx.reorged.to_string(),
+                    ]);
+                }
+                table
+            }
+            Screen::Config => {
+                let mut table = ExportTable::new("config", &["key", "value"]);
+                for (key, value) in self.config_data.iter() {
+                    let value = if include_secrets {
+                        value.clone()
+                    } else {
+                        crate::utils::mask_config_value(key, value)
+                    };
+                    table.push_row(vec![key.clone(), value]);
+                }
+                table
+            }
+            Screen::Storage => return None,
+        };
+        Some(table)
+    }
+
+    /// Freeze/unfreeze the Watch list. Recording keeps running while paused;
+    /// on resume the selection moves with its transaction instead of jumping
+    fn toggle_watch_pause(&mut self) {
+        if !self.watch_feed.is_paused() {
+            self.watch_feed.pause();
+            self.set_status("⏸ Watch paused - new transactions are buffered (Space to resume)".to_string());
+            return;
+        }
+
+        let (total, shown) = self.watch_feed.resume();
+        if self.selected_index > 0 {
+            let visible = self.watch_feed.transactions()[..shown]
+                .iter()
+                .filter(|tx| self.watch_filter.matches(tx))
+                .count();
+            let max = self.watch_feed.transactions().len().saturating_sub(1);
+            self.selected_index = (self.selected_index + visible).min(max);
+        }
+        if total > 0 {
+            self.watch_unseen_new = total;
+        }
+        self.set_status(format!("▶ Watch resumed: +{} new", total));
     }
 
     fn next_screen(&mut self) {
@@ -2470,18 +3638,32 @@ impl App {
         self.current_screen = screens[prev_idx];
     }
 
+    fn search_prompt(&self) -> String {
+        if self.current_screen == Screen::Config {
+            let scope = if self.config_search_values { "keys + values" } else { "keys only" };
+            format!("Search ({}, Tab to switch): {} (Enter to apply, Esc to cancel)", scope, self.search_buffer)
+        } else {
+            format!("Search: {} (Enter to apply, Esc to cancel)", self.search_buffer)
+        }
+    }
+
     async fn handle_search_key(&mut self, key: KeyCode) -> Result<()> {
         match key {
             KeyCode::Char(c) => {
                 self.search_buffer.push(c);
                 // Apply filter in real-time
                 self.apply_search_filter();
-                self.set_status(format!("Search: {} (Enter to apply, Esc to cancel)", self.search_buffer));
+                self.set_status(self.search_prompt());
             }
             KeyCode::Backspace => {
                 self.search_buffer.pop();
                 self.apply_search_filter();
-                self.set_status(format!("Search: {} (Enter to apply, Esc to cancel)", self.search_buffer));
+                self.set_status(self.search_prompt());
+            }
+            KeyCode::Tab if self.current_screen == Screen::Config => {
+                self.config_search_values = !self.config_search_values;
+                self.apply_search_filter();
+                self.set_status(self.search_prompt());
             }
             KeyCode::Enter => {
                 // Apply search and exit search mode
@@ -2508,6 +3690,9 @@ impl App {
             }
             _ => {}
         }
+        if self.services_filtered() {
+            self.snap_service_selection();
+        }
         Ok(())
     }
 
@@ -2587,18 +3772,15 @@ impl App {
             Screen::Services => {
                 // Filter services by name or status
                 for (idx, container) in self.containers.iter().enumerate() {
-                    if container.name.to_lowercase().contains(&query)
-                        || container.status.to_lowercase().contains(&query)
-                        || container.image.to_lowercase().contains(&query) {
+                    if Self::service_matches_query(container, &query) {
                         self.filtered_indices.push(idx);
                     }
                 }
             }
             Screen::Config => {
-                // Filter config by key or value
+                // Filter config by key, and by value unless searching keys only
                 for (idx, (key, value)) in self.config_data.iter().enumerate() {
-                    if key.to_lowercase().contains(&query)
-                        || value.to_lowercase().contains(&query) {
+                    if crate::utils::config_search_matches(key, value, &query, self.config_search_values) {
                         self.filtered_indices.push(idx);
                     }
                 }
@@ -2620,6 +3802,88 @@ impl App {
         }
     }
 
+    fn service_matches_query(container: &crate::core::docker::ContainerInfo, query: &str) -> bool {
+        container.name.to_lowercase().contains(query)
+            || container.status.to_lowercase().contains(query)
+            || container.image.to_lowercase().contains(query)
+    }
+
+    /// Rows the Services table shows under the state filter: the filter's
+    /// state AND the name search. None when no state filter is set, in which
+    /// case every row shows and the search only highlights.
+    fn visible_service_indices(&self) -> Option<Vec<usize>> {
+        let filter = self.service_state_filter?;
+        let query = self.search_buffer.to_lowercase();
+        Some(
+            self.containers
+                .iter()
+                .enumerate()
+                .filter(|(_, c)| filter.matches(c) && (query.is_empty() || Self::service_matches_query(c, &query)))
+                .map(|(idx, _)| idx)
+                .collect(),
+        )
+    }
+
+    /// Whether Up/Down and the service actions are limited to filtered rows
+    fn services_filtered(&self) -> bool {
+        self.current_screen == Screen::Services
+            && self.services_view == ServicesView::Services
+            && self.service_state_filter.is_some()
+    }
+
+    /// Whether the selected row is one the Services table shows
+    fn selected_service_visible(&self) -> bool {
+        self.selected_index < self.containers.len()
+            && self.visible_service_indices().is_none_or(|rows| rows.contains(&self.selected_index))
+    }
+
+    /// Move the selection to the next (or previous) visible Services row
+    fn step_service_selection(&mut self, forward: bool) {
+        let rows = self.visible_service_indices().unwrap_or_default();
+        let next = if !rows.contains(&self.selected_index) {
+            rows.first()
+        } else if forward {
+            rows.iter().find(|&&idx| idx > self.selected_index)
+        } else {
+            rows.iter().rev().find(|&&idx| idx < self.selected_index)
+        };
+        if let Some(&idx) = next {
+            self.selected_index = idx;
+        }
+    }
+
+    /// Keep the selection on a visible row after the filter or the containers change
+    fn snap_service_selection(&mut self) {
+        if let Some(rows) = self.visible_service_indices() {
+            if !rows.contains(&self.selected_index) {
+                if let Some(&first) = rows.first() {
+                    self.selected_index = first;
+                }
+            }
+        }
+    }
+
+    /// Toggle a state filter: pressing the active one's key again removes it
+    fn toggle_service_state_filter(&mut self, filter: ServiceStateFilter) {
+        if self.service_state_filter == Some(filter) {
+            self.service_state_filter = None;
+            self.set_status("Service filter cleared".to_string());
+            return;
+        }
+        self.service_state_filter = Some(filter);
+        self.snap_service_selection();
+        let shown = self.visible_service_indices().map_or(0, |rows| rows.len());
+        self.set_status(format!("Showing {} services: {} of {}", filter.label(), shown, self.containers.len()));
+    }
+
+    /// Drop the state filter and the name search together
+    fn clear_service_filters(&mut self) {
+        self.service_state_filter = None;
+        self.search_buffer.clear();
+        self.filtered_indices.clear();
+        self.set_status("Service filters cleared".to_string());
+    }
+
     fn render(&self, frame: &mut ratatui::Frame) {
         // Get container info for detail view
         let detail_container = self.detail_view_service.as_ref().and_then(|service_name| {
@@ -2631,104 +3895,76 @@ impl App {
             self.wallets.iter().find(|w| w.worker_id == worker_id)
         });
 
-        self.dashboard.render(
-            frame,
-            self.current_screen,
-            self.services_view,
-            self.config_section,
-            self.selected_index,
-            self.status_message.as_deref(),
-            self.edit_mode,
-            self.edit_buffer.as_str(),
+        let send_hint = self.send_change_hint();
+        let visible_services = self.visible_service_indices();
+        let restart_hint = self.pending_restart_hint();
+        let status_line = self.status_line();
+        let state = RenderState {
+            current_screen: self.current_screen,
+            services_view: self.services_view,
+            config_section: self.config_section,
+            selected_index: self.selected_index,
+            status_message: status_line.as_deref(),
+            edit_mode: self.edit_mode,
+            edit_buffer: self.edit_buffer.as_str(),
+            edit_cursor: self.edit_cursor,
             detail_container,
-            &self.detail_logs,
-            self.detail_logs_live_mode,
-            self.detail_logs_grouping,
-            self.detail_logs_filter.as_ref(),
-            self.detail_logs_scroll_offset,
-            &self.system_resources,
-            self.show_help,
-            self.search_mode,
-            &self.search_buffer,
-            &self.filtered_indices,
-            self.show_send_dialog,
-            &self.send_amount,
-            &self.send_address,
-            self.send_input_field,
-            self.send_use_wallet_selector,
-            self.send_selected_wallet_index,
-            &self.send_source_address,
-            &self.wallets,
-            self.reth_metrics.as_ref(),
+            detail_logs: &self.detail_logs,
+            detail_logs_live_mode: self.detail_logs_live_mode,
+            detail_logs_grouping: self.detail_logs_grouping,
+            detail_logs_filter: self.detail_logs_filter.as_ref(),
+            detail_logs_scroll_offset: self.detail_logs_scroll_offset,
+            system_resources: &self.system_resources,
+            dependency_health: self.dependency_health,
+            show_help: self.show_help,
+            show_health: self.show_health,
+            health_rows: &self.health_rows,
+            health_tick: self.health_started.elapsed().as_millis() as usize / 100,
+            search_mode: self.search_mode,
+            search_buffer: &self.search_buffer,
+            filtered_indices: &self.filtered_indices,
+            service_state_filter: self.service_state_filter,
+            visible_services: visible_services.as_deref(),
+            show_send_dialog: self.show_send_dialog,
+            send_amount: &self.send_amount,
+            send_address: &self.send_address,
+            send_input_field: self.send_input_field,
+            send_use_wallet_selector: self.send_use_wallet_selector,
+            send_selected_wallet_index: self.send_selected_wallet_index,
+            send_source_address: &self.send_source_address,
+            send_all: self.send_all,
+            send_hint: send_hint.as_deref(),
+            send_fee: self.send_fee,
+            restart_hint: restart_hint.as_deref(),
+            palette: self.palette.as_ref(),
+            notifications: &self.notifications,
+            send_password_len: self.send_needs_password.then(|| self.send_password.chars().count()),
+            wallets: &self.wallets,
+            reth_metrics: self.reth_metrics.as_ref(),
+            reth_history: &self.reth_history,
             detail_wallet,
-            &self.detail_wallet_addresses,
-            &self.detail_wallet_utxos,
-            self.detail_wallet_scroll,
-            self.show_tx_detail,
-            self.selected_tx_index,
-            self.tx_search_mode,
-            &self.tx_search_buffer,
-            &self.filtered_tx_indices,
-            self.watch_statistics.as_ref(),
-            &self.watch_transactions,
-            &self.watch_filter,
-            self.watch_scroll_offset,
-            self.storage_analysis.as_ref(),
-            self.storage_scroll_offset,
-            self.storage_chart_days,
-            self.storage_show_details,
-        );
-    }
-
-    /// Write a transaction to file in the specified format
-    fn write_transaction_to_file(
-        file: &mut std::fs::File,
-        tx: &crate::core::l2_monitor::TransactionInfo,
-        format: &str,
-    ) -> anyhow::Result<()> {
-        use std::io::Write;
-
-        match format {
-            "json" => {
-                let json = serde_json::to_string(tx)?;
-                writeln!(file, "{}", json)?;
-            }
-            "csv" => {
-                // CSV format
-                writeln!(
-                    file,
-                    "{},{},{},{},{},{},{},{},{},{}",
-                    tx.timestamp.format("%Y-%m-%d %H:%M:%S"),
-                    tx.tx_type,
-                    tx.hash,
-                    tx.from,
-                    tx.to.as_deref().unwrap_or(""),
-                    tx.value_ikas(),
-                    tx.gas_fee_ikas(),
-                    tx.l1_fee.unwrap_or(0.0),
-                    tx.status,
-                    tx.block_number
-                )?;
-            }
-            _ => {
-                // Text format
-                writeln!(file, "[{}] {}", tx.timestamp.format("%H:%M:%S"), tx.tx_type)?;
-                writeln!(file, "  Hash: {}", tx.hash)?;
-                writeln!(file, "  From: {}", tx.from)?;
-                if let Some(ref to) = tx.to {
-                    writeln!(file, "  To:   {}", to)?;
-                }
-                writeln!(file, "  Value: {} iKAS", tx.value_ikas())?;
-                writeln!(file, "  Gas: {} iKAS", tx.gas_fee_ikas())?;
-                if let Some(l1_fee) = tx.l1_fee {
-                    writeln!(file, "  L1 Fee: {} KAS", l1_fee)?;
-                }
-                writeln!(file, "  Status: {}", if tx.status { "Success" } else { "Failed" })?;
-                writeln!(file)?;
-            }
-        }
+            detail_wallet_addresses: &self.detail_wallet_addresses,
+            detail_receive_address: self.detail_receive_address.as_ref(),
+            detail_wallet_utxos: &self.detail_wallet_utxos,
+            detail_wallet_totals: self.detail_wallet_totals.as_ref(),
+            detail_wallet_scroll: self.detail_wallet_scroll,
+            show_tx_detail: self.show_tx_detail,
+            show_address_qr: self.show_address_qr,
+            selected_tx_index: self.selected_tx_index,
+            tx_search_buffer: &self.tx_search_buffer,
+            filtered_tx_indices: &self.filtered_tx_indices,
+            watch_stats: self.watch_statistics.as_ref(),
+            watch_feed: &self.watch_feed,
+            watch_unseen_new: self.watch_unseen_new,
+            watch_filter: &self.watch_filter,
+            watch_show_breakdown: self.watch_show_breakdown,
+            storage_analysis: self.storage_analysis.as_ref(),
+            storage_scroll_offset: self.storage_scroll_offset,
+            storage_chart_days: self.storage_chart_days,
+            storage_show_details: self.storage_show_details,
+        };
 
-        Ok(())
+        self.dashboard.render(frame, &state);
     }
 
     /// Start live mode background polling for detail view logs
@@ -2743,6 +3979,7 @@ impl App {
 
         let docker = self.docker.clone();
         let tx = self.detail_logs_live_tx.clone();
+        let live_fetch = self.log_limits.live_fetch;
 
         let handle = tokio::spawn(async move {
             // Use 250ms polling interval for near real-time updates
@@ -2751,13 +3988,13 @@ impl App {
             loop {
                 interval.tick().await;
 
-                // Fetch last 50 lines - deduplication in app.rs handles overlap
-                match docker.get_logs(&service_name, Some(50)).await {
+                // Fetch the most recent lines - deduplication in run_loop handles overlap
+                match docker.get_logs(&service_name, Some(live_fetch), false).await {
                     Ok(logs) => {
                         if !logs.is_empty() {
                             // Parse and send through channel (ignore errors if receiver dropped)
                             let log_lines: Vec<crate::core::ParsedLogLine> = logs.lines()
-                                .map(|s| crate::core::parse_docker_log_line(s))
+                                .map(crate::core::parse_docker_log_line)
                                 .collect();
                             let _ = tx.send(log_lines);
                         }
@@ -2781,3 +4018,377 @@ impl App {
         self.detail_logs_live_mode = false;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::mock_backend::{MockContainerBackend, MockWalletBackend};
+    use crossterm::event::{KeyEvent, KeyModifiers};
+
+    /// An app over mock backends and a temporary project with three .env keys
+    fn mock_app(docker: Arc<MockContainerBackend>, wallets: Arc<MockWalletBackend>) -> (App, tempfile::TempDir) {
+        let dir = tempfile::tempdir().unwrap();
+        let env_file = dir.path().join(".env");
+        std::fs::write(&env_file, "NETWORK=testnet\nIGRA_ORCHESTRA_DOMAIN=example.com\nNODE_IMAGE=kaspad:latest\n")
+            .unwrap();
+        let mut app = App::with_backends(
+            docker.clone(),
+            ConfigManager::load(&env_file).unwrap(),
+            wallets,
+            SslManager::offline(dir.path()),
+            DataFeeds::idle(),
+        );
+        // What the container feed would have delivered
+        app.containers = Arc::new(docker.containers.clone());
+        (app, dir)
+    }
+
+    /// No containers and no wallets
+    fn offline_app() -> (App, tempfile::TempDir) {
+        mock_app(Arc::new(MockContainerBackend::new()), Arc::new(MockWalletBackend::new()))
+    }
+
+    async fn press(app: &mut App, key: KeyCode) {
+        app.handle_key(KeyEvent::new(key, KeyModifiers::NONE)).await.unwrap();
+    }
+
+    async fn type_text(app: &mut App, text: &str) {
+        for c in text.chars() {
+            press(app, KeyCode::Char(c)).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_screen_navigation() {
+        let (mut app, _dir) = offline_app();
+        assert_eq!(app.current_screen, Screen::Services);
+
+        // Arrows wrap around the screen list (Watch is skipped: visiting it starts polling)
+        press(&mut app, KeyCode::Left).await;
+        assert_eq!(app.current_screen, Screen::Storage);
+        press(&mut app, KeyCode::Left).await;
+        assert_eq!(app.current_screen, Screen::Config);
+        press(&mut app, KeyCode::Right).await;
+        press(&mut app, KeyCode::Right).await;
+        assert_eq!(app.current_screen, Screen::Services);
+
+        app.selected_index = 2;
+        press(&mut app, KeyCode::Char('4')).await;
+        assert_eq!((app.current_screen, app.selected_index), (Screen::Config, 0));
+        assert_eq!(app.config_data.len(), 3);
+        press(&mut app, KeyCode::Char('2')).await;
+        assert_eq!(app.current_screen, Screen::Wallets);
+        press(&mut app, KeyCode::Char('1')).await;
+        assert_eq!(app.current_screen, Screen::Services);
+    }
+
+    #[tokio::test]
+    async fn test_tab_cycles_sub_views() {
+        let (mut app, _dir) = offline_app();
+        press(&mut app, KeyCode::Tab).await;
+        assert_eq!(app.services_view, ServicesView::Profiles);
+        press(&mut app, KeyCode::BackTab).await;
+        assert_eq!(app.services_view, ServicesView::Services);
+
+        press(&mut app, KeyCode::Char('4')).await;
+        app.selected_index = 1;
+        press(&mut app, KeyCode::Tab).await;
+        assert_eq!((app.config_section, app.selected_index), (ConfigSection::RpcTokens, 0));
+        press(&mut app, KeyCode::Tab).await;
+        press(&mut app, KeyCode::Tab).await;
+        assert_eq!(app.config_section, ConfigSection::Environment);
+        press(&mut app, KeyCode::BackTab).await;
+        assert_eq!(app.config_section, ConfigSection::SslCerts);
+
+        // Tab does nothing on screens without sub-views
+        press(&mut app, KeyCode::Char('2')).await;
+        press(&mut app, KeyCode::Tab).await;
+        assert_eq!((app.current_screen, app.config_section), (Screen::Wallets, ConfigSection::SslCerts));
+    }
+
+    #[tokio::test]
+    async fn test_selection_clamped_to_list() {
+        let (mut app, _dir) = offline_app();
+        // No containers listed: nothing to move to
+        press(&mut app, KeyCode::Down).await;
+        assert_eq!(app.selected_index, 0);
+
+        press(&mut app, KeyCode::Char('4')).await;
+        for _ in 0..5 {
+            press(&mut app, KeyCode::Char('j')).await;
+        }
+        assert_eq!(app.selected_index, 2);
+        press(&mut app, KeyCode::Up).await;
+        assert_eq!(app.selected_index, 1);
+        for _ in 0..5 {
+            press(&mut app, KeyCode::Char('k')).await;
+        }
+        assert_eq!(app.selected_index, 0);
+    }
+
+    #[tokio::test]
+    async fn test_search_mode() {
+        let (mut app, _dir) = offline_app();
+        press(&mut app, KeyCode::Char('4')).await;
+        press(&mut app, KeyCode::Char('/')).await;
+        assert!(app.search_mode);
+
+        // Keys are typed into the query, not handled as shortcuts
+        type_text(&mut app, "domainq").await;
+        assert!(!app.should_quit);
+        press(&mut app, KeyCode::Backspace).await;
+        assert_eq!(app.search_buffer, "domain");
+        let expected = app.config_data.iter().position(|(key, _)| key == "IGRA_ORCHESTRA_DOMAIN").unwrap();
+        assert_eq!(app.filtered_indices, vec![expected]);
+
+        press(&mut app, KeyCode::Enter).await;
+        assert!(!app.search_mode);
+        assert_eq!(app.selected_index, expected);
+
+        press(&mut app, KeyCode::Char('/')).await;
+        type_text(&mut app, "net").await;
+        press(&mut app, KeyCode::Esc).await;
+        assert!(!app.search_mode);
+        assert!(app.search_buffer.is_empty() && app.filtered_indices.is_empty());
+        assert!(!app.should_quit);
+
+        // Storage has nothing to search
+        press(&mut app, KeyCode::Right).await;
+        press(&mut app, KeyCode::Char('/')).await;
+        assert!(!app.search_mode);
+    }
+
+    #[tokio::test]
+    async fn test_esc_closes_overlays_before_quitting() {
+        let (mut app, _dir) = offline_app();
+        press(&mut app, KeyCode::Char('?')).await;
+        assert!(app.show_help);
+        press(&mut app, KeyCode::Esc).await;
+        assert!(!app.show_help && !app.should_quit);
+
+        press(&mut app, KeyCode::Char(':')).await;
+        assert!(app.palette.is_some());
+        type_text(&mut app, "q").await;
+        assert!(!app.should_quit);
+        press(&mut app, KeyCode::Esc).await;
+        assert!(app.palette.is_none());
+
+        press(&mut app, KeyCode::Esc).await;
+        assert!(app.should_quit);
+    }
+
+    #[tokio::test]
+    async fn test_service_detail_and_actions() {
+        let mut docker = MockContainerBackend::new().with_containers(&["execution-layer", "kaspad"]);
+        docker.logs.insert("kaspad".to_string(), "line one\nline two\n".to_string());
+        let docker = Arc::new(docker);
+        let (mut app, _dir) = mock_app(docker.clone(), Arc::new(MockWalletBackend::new()));
+
+        press(&mut app, KeyCode::Down).await;
+        press(&mut app, KeyCode::Down).await;
+        assert_eq!(app.selected_index, 1);
+
+        press(&mut app, KeyCode::Enter).await;
+        assert_eq!(app.detail_view_service.as_deref(), Some("kaspad"));
+        assert_eq!(app.detail_logs.len(), 2);
+
+        press(&mut app, KeyCode::Char('s')).await;
+        assert_eq!(docker.calls(), vec!["start_service kaspad".to_string()]);
+        assert_eq!(app.status_message.as_deref(), Some("✓ Started kaspad"));
+
+        press(&mut app, KeyCode::Esc).await;
+        assert_eq!(app.detail_view_service, None);
+        assert!(!app.should_quit);
+    }
+
+    #[tokio::test]
+    async fn test_service_state_filters() {
+        use crate::utils::ContainerState;
+        let mut docker = MockContainerBackend::new()
+            .with_containers(&["execution-layer", "kaspad", "kaswallet-0", "kaswallet-1", "traefik"]);
+        for idx in [1, 3] {
+            docker.containers[idx].state = ContainerState::Stopped;
+            docker.containers[idx].status = "Exited (0) 5 minutes ago".to_string();
+        }
+        docker.containers[4].health = Some("unhealthy".to_string());
+        let docker = Arc::new(docker);
+        let (mut app, _dir) = mock_app(docker.clone(), Arc::new(MockWalletBackend::new()));
+
+        // Stopped only: the selection jumps to a visible row and skips hidden ones
+        press(&mut app, KeyCode::Char('S')).await;
+        assert_eq!(app.visible_service_indices(), Some(vec![1, 3]));
+        assert_eq!(app.selected_index, 1);
+        press(&mut app, KeyCode::Down).await;
+        assert_eq!(app.selected_index, 3);
+        press(&mut app, KeyCode::Down).await;
+        assert_eq!(app.selected_index, 3);
+
+        // AND with the name search
+        press(&mut app, KeyCode::Char('/')).await;
+        type_text(&mut app, "kaswallet").await;
+        press(&mut app, KeyCode::Enter).await;
+        assert_eq!(app.visible_service_indices(), Some(vec![3]));
+        press(&mut app, KeyCode::Char('s')).await;
+        assert_eq!(docker.calls(), vec!["start_service kaswallet-1".to_string()]);
+
+        // Nothing matches: actions don't reach a hidden container
+        press(&mut app, KeyCode::Char('U')).await;
+        assert_eq!(app.visible_service_indices(), Some(vec![]));
+        press(&mut app, KeyCode::Char('x')).await;
+        assert_eq!(docker.calls().len(), 1);
+
+        // One key clears the state filter and the search
+        press(&mut app, KeyCode::Char('C')).await;
+        assert_eq!(app.service_state_filter, None);
+        assert!(app.search_buffer.is_empty());
+        assert_eq!(app.visible_service_indices(), None);
+
+        // The same key again turns a filter off
+        press(&mut app, KeyCode::Char('U')).await;
+        assert_eq!(app.visible_service_indices(), Some(vec![4]));
+        press(&mut app, KeyCode::Char('U')).await;
+        assert_eq!(app.service_state_filter, None);
+    }
+
+    #[tokio::test]
+    async fn test_wallet_detail_modal_closes_first() {
+        let mut wallets = MockWalletBackend::new().with_wallets(&[(0, 10.0), (1, 0.5)]);
+        wallets.addresses.insert(1, vec![("kaspatest:worker1".to_string(), 0.5, 0.0)]);
+        wallets.utxos.insert(
+            1,
+            vec![crate::core::wallet::UtxoInfo {
+                address: "kaspatest:worker1".to_string(),
+                tx_id: "abc".to_string(),
+                amount_kas: 0.5,
+                amount_sompi: 50_000_000,
+                block_daa_score: 0,
+                is_coinbase: false,
+                timestamp_ms: 0,
+                source_addresses: Vec::new(),
+                confirmations: None,
+            }],
+        );
+        let (mut app, _dir) = mock_app(Arc::new(MockContainerBackend::new()), Arc::new(wallets));
+
+        press(&mut app, KeyCode::Char('2')).await;
+        press(&mut app, KeyCode::Char('r')).await;
+        assert_eq!(app.wallets.len(), 2);
+        press(&mut app, KeyCode::Down).await;
+        press(&mut app, KeyCode::Enter).await;
+        assert_eq!(app.detail_view_wallet, Some(1));
+        assert_eq!(app.detail_wallet_addresses.len(), 1);
+
+        press(&mut app, KeyCode::Enter).await;
+        assert!(app.show_tx_detail);
+        assert_eq!(app.selected_tx_index, Some(0));
+
+        press(&mut app, KeyCode::Esc).await;
+        assert!(!app.show_tx_detail);
+        assert_eq!(app.detail_view_wallet, Some(1));
+
+        press(&mut app, KeyCode::Esc).await;
+        assert_eq!(app.detail_view_wallet, None);
+        assert!(app.detail_wallet_utxos.is_empty());
+        assert!(!app.should_quit);
+    }
+
+    /// Let the background fee estimate finish and pick it up
+    async fn settle_send_fee(app: &mut App) {
+        for _ in 0..100 {
+            app.poll_send_fee();
+            if app.send_fee != Some(SendFeePreview::Estimating) {
+                return;
+            }
+            tokio::task::yield_now().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_send_dialog_fee_estimate() {
+        let one_kas = crate::utils::SOMPI_PER_KAS;
+        let mut wallets = MockWalletBackend::new().with_wallets(&[(0, 2.0)]);
+        wallets.utxos.insert(
+            0,
+            vec![crate::core::wallet::UtxoInfo {
+                address: "kaspatest:worker0".to_string(),
+                tx_id: "abc".to_string(),
+                amount_kas: 2.0,
+                amount_sompi: 2 * one_kas,
+                block_daa_score: 0,
+                is_coinbase: false,
+                timestamp_ms: 0,
+                source_addresses: Vec::new(),
+                confirmations: None,
+            }],
+        );
+        let (mut app, _dir) = mock_app(Arc::new(MockContainerBackend::new()), Arc::new(wallets));
+
+        press(&mut app, KeyCode::Char('2')).await;
+        press(&mut app, KeyCode::Char('r')).await;
+        press(&mut app, KeyCode::Char('t')).await;
+        assert!(app.show_send_dialog);
+        assert_eq!(app.send_fee, None);
+
+        // Follows the amount as it is typed
+        type_text(&mut app, "1").await;
+        settle_send_fee(&mut app).await;
+        let Some(SendFeePreview::Ready(one)) = app.send_fee else { panic!("no estimate: {:?}", app.send_fee) };
+        assert_eq!(one.amount_sompi, one_kas);
+        assert!(one.fee_sompi > 0);
+        // Sent without a fee policy, so the daemon's rate is only bounded below
+        assert!(one.at_least);
+
+        type_text(&mut app, ".5").await;
+        settle_send_fee(&mut app).await;
+        let Some(SendFeePreview::Ready(more)) = app.send_fee else { panic!("no estimate: {:?}", app.send_fee) };
+        assert_eq!(more.amount_sompi, one_kas + one_kas / 2);
+
+        // More than the wallet holds: the hint says so
+        for _ in 0..3 {
+            press(&mut app, KeyCode::Backspace).await;
+        }
+        type_text(&mut app, "5").await;
+        settle_send_fee(&mut app).await;
+        assert_eq!(app.send_fee, Some(SendFeePreview::Insufficient));
+        assert!(app.send_change_hint().unwrap().contains("exceeds the spendable balance"));
+
+        // Leaves change below dust
+        press(&mut app, KeyCode::Backspace).await;
+        type_text(&mut app, "1.9999").await;
+        settle_send_fee(&mut app).await;
+        assert!(app.send_change_hint().unwrap().contains("below dust"));
+
+        // Send-all totals the whole balance
+        press(&mut app, KeyCode::Char('a')).await;
+        settle_send_fee(&mut app).await;
+        let Some(SendFeePreview::Ready(all)) = app.send_fee else { panic!("no estimate: {:?}", app.send_fee) };
+        assert_eq!(all.total_sompi(), 2 * one_kas);
+    }
+
+    #[tokio::test]
+    async fn test_receive_address_rotation() {
+        let wallets = Arc::new(MockWalletBackend::new().with_wallets(&[(0, 10.0)]));
+        let (mut app, _dir) = mock_app(Arc::new(MockContainerBackend::new()), Arc::clone(&wallets));
+
+        press(&mut app, KeyCode::Char('2')).await;
+        press(&mut app, KeyCode::Char('r')).await;
+        press(&mut app, KeyCode::Enter).await;
+        // Opening the view shows the current address without generating one
+        assert_eq!(app.detail_receive_address, Some(ReceiveAddress::Current("kaspatest:worker0".to_string())));
+        assert!(wallets.calls().is_empty());
+
+        press(&mut app, KeyCode::Char('N')).await;
+        assert_eq!(wallets.calls(), vec!["new_address 0"]);
+        assert_eq!(
+            app.detail_receive_address,
+            Some(ReceiveAddress::Generated {
+                address: "kaspatest:worker0-1".to_string(),
+                previous: Some("kaspatest:worker0".to_string()),
+            })
+        );
+        assert!(app.status_message.as_deref().unwrap_or_default().contains("previous one still receives"));
+
+        press(&mut app, KeyCode::Esc).await;
+        assert_eq!(app.detail_receive_address, None);
+    }
+}
@@ -1,6 +1,6 @@
 /// Simple utility to get wallet addresses via gRPC
-use igra_cli::core::wallet::kaswallet_proto::wallet_client::WalletClient;
-use igra_cli::core::wallet::kaswallet_proto::NewAddressRequest;
+use igra_mgt::core::wallet::kaswallet_proto::wallet_client::WalletClient;
+use igra_mgt::core::wallet::kaswallet_proto::NewAddressRequest;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
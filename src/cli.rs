@@ -1,28 +1,67 @@
-/// CLI argument parsing and command handling
+//! CLI argument parsing and command handling
 
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
 
-// Build timestamp injected at compile time
+// Build info injected at compile time by build.rs
 pub const BUILD_TIMESTAMP: &str = env!("BUILD_TIMESTAMP");
+pub const GIT_COMMIT: &str = env!("GIT_COMMIT");
+pub const RUSTC_VERSION: &str = env!("RUSTC_VERSION");
 pub const VERSION_WITH_BUILD: &str = concat!(env!("CARGO_PKG_VERSION"), " (built: ", env!("BUILD_TIMESTAMP"), ")");
-
-// Get version with timestamp
-pub fn get_version() -> &'static str {
-    VERSION_WITH_BUILD
-}
+/// Shown by `--version` (`-V` keeps the short form)
+pub const LONG_VERSION: &str = concat!(
+    env!("CARGO_PKG_VERSION"), "\n",
+    "commit:  ", env!("GIT_COMMIT"), "\n",
+    "built:   ", env!("BUILD_TIMESTAMP"), "\n",
+    "rustc:   ", env!("RUSTC_VERSION"),
+);
 
 #[derive(Parser)]
 #[command(name = "igra-cli")]
-#[command(author, version = VERSION_WITH_BUILD, about, long_about = None)]
+#[command(author, version = VERSION_WITH_BUILD, long_version = LONG_VERSION, about, long_about = None)]
 pub struct Cli {
     #[command(subcommand)]
     pub command: Option<Commands>,
+
+    /// Compose file to manage (default: docker-compose.yml in the project root)
+    #[arg(long, global = true, value_name = "FILE")]
+    pub compose_file: Option<std::path::PathBuf>,
+
+    /// Compose project name (default: igra-orchestra-<network>)
+    #[arg(long, global = true, value_name = "NAME")]
+    pub project: Option<String>,
+
+    /// Env file to manage, e.g. .env.staging (default: .env in the project root)
+    #[arg(long, global = true, value_name = "FILE")]
+    pub env_file: Option<std::path::PathBuf>,
+
+    /// Container runtime: docker or podman (default: detected from
+    /// DOCKER_HOST and the sockets present)
+    #[arg(long, global = true, value_name = "RUNTIME")]
+    pub backend: Option<crate::core::docker::ContainerRuntime>,
+
+    /// Check and print what start, stop, restart, upgrade, backup and sends
+    /// would do, without touching containers or wallets
+    #[arg(long, global = true)]
+    pub dry_run: bool,
 }
 
 #[derive(Subcommand)]
 pub enum Commands {
     /// Show service status
-    Status,
+    Status {
+        /// Clear and reprint the table every --interval seconds (Ctrl+C to exit)
+        #[arg(short, long)]
+        watch: bool,
+
+        /// Seconds between refreshes in --watch mode
+        #[arg(short = 'n', long, default_value = "2", value_parser = clap::value_parser!(u64).range(1..))]
+        interval: u64,
+
+        /// Print a one-line summary for shell prompts and tmux status bars
+        /// (returns within a second, never fails)
+        #[arg(long, conflicts_with = "watch")]
+        oneline: bool,
+    },
 
     /// Start services or profiles
     Start {
@@ -36,10 +75,14 @@ pub enum Commands {
 
     /// Stop services
     Stop {
-        /// Stop all services
+        /// Stop all running containers of the compose project
         #[arg(short, long)]
         all: bool,
 
+        /// Skip the confirmation for --all
+        #[arg(short, long, requires = "all")]
+        yes: bool,
+
         /// Specific service to stop
         service: Option<String>,
     },
@@ -59,9 +102,27 @@ pub enum Commands {
         #[arg(short, long)]
         follow: bool,
 
-        /// Number of lines to show
-        #[arg(short = 'n', long, default_value = "100")]
-        tail: usize,
+        /// Number of lines to show (default: 100, or 10000 with --since)
+        #[arg(short = 'n', long)]
+        tail: Option<usize>,
+
+        /// Show logs since a time: "14:00", "2024-05-01 14:00", RFC3339, or "30m"
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Strip ANSI colors (automatic when output is not a terminal)
+        #[arg(long)]
+        no_color: bool,
+    },
+
+    /// Run a command inside a running service container (debugging)
+    Exec {
+        /// Service name
+        service: String,
+
+        /// Command and arguments, after `--`
+        #[arg(last = true, required = true)]
+        cmd: Vec<String>,
     },
 
     /// RPC management commands
@@ -82,18 +143,34 @@ pub enum Commands {
         command: BackupCommands,
     },
 
+    /// SSL certificate checks
+    Ssl {
+        #[command(subcommand)]
+        command: SslCommands,
+    },
+
     /// Configuration management
     Config {
         #[command(subcommand)]
         command: ConfigCommands,
     },
 
-    /// Resource monitoring
-    Monitor,
+    /// Live L2 monitoring view (same options as `watch`)
+    Monitor {
+        #[command(flatten)]
+        args: WatchArgs,
+    },
 
     /// Health check report
     Health,
 
+    /// List published host ports of running services and flag conflicts
+    Ports {
+        /// Also check this profile's ports against what is already in use on the host
+        #[arg(short, long)]
+        profile: Option<String>,
+    },
+
     /// Check for updates
     Upgrade {
         /// Check for updates without pulling
@@ -107,6 +184,26 @@ pub enum Commands {
         /// Apply upgrades
         #[arg(short, long)]
         apply: bool,
+
+        /// Profile to recreate when applying (defaults to active profiles)
+        #[arg(long)]
+        profile: Option<String>,
+
+        /// With --apply: recreate every service of the profile(s), not just
+        /// those whose image changed (volumes are kept)
+        #[arg(long, requires = "apply")]
+        force_recreate: bool,
+    },
+
+    /// Update the igra-cli binary itself to the latest release
+    SelfUpdate {
+        /// Only report whether a newer release exists
+        #[arg(short, long)]
+        check: bool,
+
+        /// Skip the confirmation
+        #[arg(short, long)]
+        yes: bool,
     },
 
     /// Run diagnostics
@@ -114,6 +211,9 @@ pub enum Commands {
         /// Generate diagnostic report
         #[arg(short, long)]
         report: bool,
+
+        #[command(subcommand)]
+        command: Option<DiagCommands>,
     },
 
     /// Run setup wizard
@@ -121,17 +221,8 @@ pub enum Commands {
 
     /// Watch L2 transactions in real-time
     Watch {
-        /// Filter by type (all, transfer, contract, entry)
-        #[arg(short, long, default_value = "all")]
-        filter: String,
-
-        /// Record transactions to file
-        #[arg(short, long)]
-        record: Option<String>,
-
-        /// Output format for recording (json, csv, text)
-        #[arg(long, default_value = "text")]
-        format: String,
+        #[command(flatten)]
+        args: WatchArgs,
     },
 
     /// Run HTTP API server mode
@@ -148,6 +239,10 @@ pub enum Commands {
         /// Enable CORS for cross-origin requests
         #[arg(long)]
         cors: bool,
+
+        /// Enable routes that move funds (wallet send); they also require IGRA_WEB_ADMIN_TOKEN
+        #[arg(long)]
+        allow_destructive: bool,
     },
 
     /// Install web UI as a systemd service
@@ -171,6 +266,58 @@ pub enum Commands {
     },
 }
 
+/// Options shared by `watch` and `monitor`
+#[derive(Args, Debug, Clone)]
+pub struct WatchArgs {
+    /// Filter by type (all, transfer, contract, entry)
+    #[arg(short, long, default_value = "all")]
+    pub filter: String,
+
+    /// Record transactions to file
+    #[arg(short, long)]
+    pub record: Option<String>,
+
+    /// Output format for recording (json, csv, text)
+    #[arg(long, default_value = "text")]
+    pub format: String,
+
+    /// Rotate the recording file once it reaches this size (MB)
+    #[arg(long)]
+    pub record_max_mb: Option<u64>,
+
+    /// Number of rotated recording files to keep
+    #[arg(long, default_value = "5")]
+    pub record_keep: usize,
+
+    /// Contract ABI JSON used to name decoded method calls
+    #[arg(long)]
+    pub abi: Option<String>,
+
+    /// Poll for new transactions every N milliseconds (default: [watch] in config.toml, else 1000)
+    #[arg(long, value_parser = clap::value_parser!(u64).range(crate::utils::app_config::MIN_WATCH_POLL_MS..))]
+    pub poll_ms: Option<u64>,
+
+    /// Refresh L1 data every N seconds (default: [watch] in config.toml, else 10)
+    #[arg(long, value_parser = clap::value_parser!(u64).range(crate::utils::app_config::MIN_WATCH_L1_INTERVAL_S..))]
+    pub l1_interval_s: Option<u64>,
+
+    /// Follow new blocks over a WebSocket subscription, polling only while
+    /// it's unavailable (default: [watch] mode in config.toml)
+    #[arg(long)]
+    pub ws: bool,
+
+    /// POST alerts (failed-tx spikes, TPS drops, node disconnects) to this
+    /// Discord, Slack or generic JSON webhook
+    #[arg(long, value_name = "URL")]
+    pub alert_webhook: Option<String>,
+}
+
+#[derive(Subcommand)]
+pub enum DiagCommands {
+    /// Print CLI build info, Docker/Compose versions and running image tags
+    Versions,
+}
+
 #[derive(Subcommand)]
 pub enum RpcCommands {
     /// List all RPC tokens
@@ -184,6 +331,18 @@ pub enum RpcCommands {
         /// Token number to test (1-46)
         #[arg(short, long)]
         token: Option<usize>,
+
+        /// Request timeout in seconds
+        #[arg(long, default_value = "10")]
+        timeout: u64,
+
+        /// Retries after connection/transport errors
+        #[arg(long, default_value = "0")]
+        retries: u32,
+
+        /// Skip TLS certificate verification (self-signed dev setups only)
+        #[arg(long)]
+        insecure: bool,
     },
 }
 
@@ -197,6 +356,19 @@ pub enum TokenCommands {
 
     /// Test a specific token
     Test { token_number: usize },
+
+    /// Regenerate a single token, leaving the others untouched
+    Rotate {
+        token_number: usize,
+
+        /// Recreate the services that read the token so it takes effect now
+        #[arg(long)]
+        recreate: bool,
+
+        /// Test the new token against the RPC endpoint afterwards
+        #[arg(long)]
+        test: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -205,10 +377,93 @@ pub enum WalletCommands {
     List,
 
     /// Check wallet balance
-    Balance { worker_id: usize },
+    Balance {
+        #[arg(required_unless_present = "all")]
+        worker_id: Option<usize>,
+
+        /// Show every worker wallet (available and pending)
+        #[arg(long, conflicts_with = "worker_id")]
+        all: bool,
+    },
 
     /// Generate new wallet
     Generate { worker_id: usize },
+
+    /// Export worker addresses for funding scripts
+    ExportAddresses {
+        /// Output format (csv, json)
+        #[arg(long, default_value = "csv")]
+        format: String,
+
+        /// Shorthand for --format json
+        #[arg(long, conflicts_with = "format")]
+        json: bool,
+
+        /// Skip workers without a generated wallet
+        #[arg(long)]
+        only_generated: bool,
+    },
+
+    /// Export a worker's incoming and outgoing transactions, oldest first
+    History {
+        /// Worker ID (0-4)
+        #[arg(long)]
+        worker: usize,
+
+        /// Output format (csv, json)
+        #[arg(long, default_value = "csv")]
+        format: String,
+
+        /// Only transactions from this time on: RFC3339, YYYY-MM-DD [HH:MM] or a duration like 30d
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Only transactions before this time (same formats as --since)
+        #[arg(long)]
+        until: Option<String>,
+    },
+
+    /// Pay several addresses from a worker wallet (CSV lines: address,amount in KAS)
+    SendMany {
+        worker_id: usize,
+
+        /// Payout CSV file
+        #[arg(long)]
+        file: std::path::PathBuf,
+
+        /// Don't ask for confirmation before sending
+        #[arg(short, long)]
+        yes: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum SslCommands {
+    /// Check the served certificate; exit 1 if expiring within --warn-days, 2 if invalid
+    Check {
+        /// Domain to check (default: IGRA_ORCHESTRA_DOMAIN); need not be in the config
+        #[arg(long)]
+        domain: Option<String>,
+
+        /// Days of validity below which the check fails
+        #[arg(long, default_value = "14")]
+        warn_days: i64,
+    },
+
+    /// Restart Traefik so it renews certificates that are due
+    Renew {
+        /// Domain to reissue (default: IGRA_ORCHESTRA_DOMAIN)
+        #[arg(long)]
+        domain: Option<String>,
+
+        /// Delete the domain's certificate from acme.json (backed up first) to force a new one
+        #[arg(long)]
+        reissue: bool,
+
+        /// Don't ask for confirmation before editing acme.json
+        #[arg(short, long, requires = "reissue")]
+        yes: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -232,8 +487,35 @@ pub enum ConfigCommands {
     Edit,
 
     /// Validate configuration
-    Validate,
+    Validate {
+        /// Also check docker-compose.yml with `docker compose config` and
+        /// report variables it references that are not set
+        #[arg(long)]
+        compose: bool,
+    },
 
     /// Generate RPC tokens
     GenerateTokens,
+
+    /// Create .env from .env.example with generated secrets and RPC tokens
+    Init {
+        /// Overwrite an existing .env
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Move passwords, secrets and tokens from .env into the encrypted secrets store
+    EncryptSecrets,
+
+    /// Show edits made through igra-cli (CLI, TUI, web API), newest first.
+    /// Secret values are never recorded.
+    History {
+        /// Only this key
+        #[arg(long)]
+        key: Option<String>,
+
+        /// Show at most N entries
+        #[arg(short = 'n', long, default_value = "50")]
+        limit: usize,
+    },
 }
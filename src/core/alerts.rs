@@ -0,0 +1,359 @@
+//! Alerts raised by `igra-cli watch` and their delivery to a webhook
+//!
+//! `AlertMonitor` looks at the monitor's statistics after every poll and
+//! reports conditions when they start and when they clear, so a webhook gets
+//! one message per incident rather than one per poll. `WebhookNotifier` posts
+//! them in the background: a slow or unreachable webhook never holds up the
+//! poll loop.
+
+use anyhow::anyhow;
+use chrono::Utc;
+use serde_json::json;
+use std::collections::{HashSet, VecDeque};
+use std::time::{Duration, Instant};
+
+use crate::core::error::Result;
+use crate::core::l2_monitor::Statistics;
+
+/// Consecutive failed polls before the node counts as disconnected
+const DISCONNECT_AFTER_FAILURES: u32 = 3;
+/// Window over which failed transactions and TPS are measured
+const ALERT_WINDOW: Duration = Duration::from_secs(60);
+/// Failed transactions in a window needed for a spike, whatever the share
+const MIN_FAILED_FOR_SPIKE: u64 = 5;
+/// Share of failed transactions in a window that counts as a spike
+const FAILED_SPIKE_PCT: u64 = 20;
+/// Transactions in the previous window needed before a drop is reported
+const MIN_TX_FOR_TPS_DROP: u64 = 30;
+/// TPS falling below this fraction of the previous window is a drop
+const TPS_DROP_RATIO: f64 = 0.5;
+
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(5);
+/// Attempts per alert, retried on transport errors, 429 and 5xx
+const WEBHOOK_ATTEMPTS: u32 = 3;
+const WEBHOOK_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AlertKind {
+    NodeDisconnected,
+    FailedTxSpike,
+    TpsDrop,
+}
+
+impl AlertKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AlertKind::NodeDisconnected => "node_disconnected",
+            AlertKind::FailedTxSpike => "failed_tx_spike",
+            AlertKind::TpsDrop => "tps_drop",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Alert {
+    pub kind: AlertKind,
+    /// False for the notice that a condition has cleared
+    pub firing: bool,
+    pub message: String,
+    /// Latest block seen by the monitor
+    pub block: u64,
+}
+
+impl Alert {
+    /// One-line summary, as shown in chat
+    pub fn summary(&self) -> String {
+        let icon = if self.firing { "⚠" } else { "✓" };
+        format!("{} igra-cli watch: {}", icon, self.message)
+    }
+
+    /// Body posted to the webhook. `content` (Discord) and `text` (Slack) both
+    /// carry the summary; the remaining fields are for generic receivers.
+    pub fn payload(&self) -> serde_json::Value {
+        let summary = self.summary();
+        json!({
+            "content": summary,
+            "text": summary,
+            "alert": self.kind.as_str(),
+            "status": if self.firing { "firing" } else { "resolved" },
+            "message": self.message,
+            "block": self.block,
+            "source": "igra-cli watch",
+            "timestamp": Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        })
+    }
+}
+
+/// Turns poll results into alerts, raising each condition once and
+/// reporting when it clears
+#[derive(Debug, Default)]
+pub struct AlertMonitor {
+    /// (instant, total_transactions, failed_transactions) after each successful poll
+    samples: VecDeque<(Instant, u64, u64)>,
+    consecutive_failures: u32,
+    active: HashSet<AlertKind>,
+    /// Transactions per window before the current TPS drop began
+    tps_baseline: Option<u64>,
+    last_block: u64,
+}
+
+impl AlertMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a failed poll
+    pub fn poll_failed(&mut self, error: &str) -> Vec<Alert> {
+        self.consecutive_failures += 1;
+        let mut alerts = Vec::new();
+        if self.consecutive_failures >= DISCONNECT_AFTER_FAILURES {
+            let message = format!(
+                "L2 node unreachable ({} failed polls in a row): {}",
+                self.consecutive_failures, error
+            );
+            self.raise(AlertKind::NodeDisconnected, message, &mut alerts);
+        }
+        alerts
+    }
+
+    /// Record a successful poll and check the statistics it left behind
+    pub fn observe(&mut self, stats: &Statistics, at: Instant) -> Vec<Alert> {
+        let mut alerts = Vec::new();
+        self.consecutive_failures = 0;
+        self.last_block = stats.current_block;
+        self.clear(AlertKind::NodeDisconnected, "L2 node reachable again".to_string(), &mut alerts);
+
+        self.samples
+            .push_back((at, stats.total_transactions, stats.failed_transactions));
+        // Keep two windows, plus the sample the older one starts from
+        while self.samples.len() > 1 && at.saturating_duration_since(self.samples[1].0) > ALERT_WINDOW * 2 {
+            self.samples.pop_front();
+        }
+
+        self.check_failed_spike(at, &mut alerts);
+        self.check_tps_drop(at, &mut alerts);
+        alerts
+    }
+
+    /// Newest sample at or before `t`
+    fn sample_at(&self, t: Instant) -> Option<(Instant, u64, u64)> {
+        self.samples.iter().rev().find(|(at, _, _)| *at <= t).copied()
+    }
+
+    fn check_failed_spike(&mut self, now: Instant, alerts: &mut Vec<Alert>) {
+        let Some(&(_, total, failed)) = self.samples.back() else { return };
+        let baseline = now
+            .checked_sub(ALERT_WINDOW)
+            .and_then(|start| self.sample_at(start))
+            .or_else(|| self.samples.front().copied());
+        let Some((_, base_total, base_failed)) = baseline else { return };
+
+        let window_total = total.saturating_sub(base_total);
+        let window_failed = failed.saturating_sub(base_failed);
+        if window_failed >= MIN_FAILED_FOR_SPIKE && window_failed * 100 >= window_total * FAILED_SPIKE_PCT {
+            let message = format!(
+                "{} of {} transactions failed in the last {}s",
+                window_failed,
+                window_total,
+                ALERT_WINDOW.as_secs()
+            );
+            self.raise(AlertKind::FailedTxSpike, message, alerts);
+        } else {
+            self.clear(AlertKind::FailedTxSpike, "Failed transaction rate back to normal".to_string(), alerts);
+        }
+    }
+
+    /// Compares the last window with the one before. While a drop is active
+    /// the pre-drop window stays the reference, so a sustained drop isn't
+    /// reported as recovered once both windows are low.
+    fn check_tps_drop(&mut self, now: Instant, alerts: &mut Vec<Alert>) {
+        let Some(window_start) = now.checked_sub(ALERT_WINDOW) else { return };
+        let Some(previous_start) = window_start.checked_sub(ALERT_WINDOW) else { return };
+        let (Some(start), Some(middle), Some(end)) = (
+            self.sample_at(previous_start),
+            self.sample_at(window_start),
+            self.samples.back().copied(),
+        ) else {
+            return;
+        };
+        let previous = middle.1.saturating_sub(start.1);
+        let current = end.1.saturating_sub(middle.1);
+        let window_secs = ALERT_WINDOW.as_secs_f64();
+
+        let baseline = self.tps_baseline.unwrap_or(previous);
+        if baseline >= MIN_TX_FOR_TPS_DROP && (current as f64) < baseline as f64 * TPS_DROP_RATIO {
+            self.tps_baseline = Some(baseline);
+            let message = format!(
+                "TPS dropped to {:.2} from {:.2} over the last {}s",
+                current as f64 / window_secs,
+                baseline as f64 / window_secs,
+                ALERT_WINDOW.as_secs()
+            );
+            self.raise(AlertKind::TpsDrop, message, alerts);
+        } else {
+            self.tps_baseline = None;
+            let message = format!("TPS recovered to {:.2}", current as f64 / window_secs);
+            self.clear(AlertKind::TpsDrop, message, alerts);
+        }
+    }
+
+    fn raise(&mut self, kind: AlertKind, message: String, alerts: &mut Vec<Alert>) {
+        if self.active.insert(kind) {
+            alerts.push(Alert { kind, firing: true, message, block: self.last_block });
+        }
+    }
+
+    fn clear(&mut self, kind: AlertKind, message: String, alerts: &mut Vec<Alert>) {
+        if self.active.remove(&kind) {
+            alerts.push(Alert { kind, firing: false, message, block: self.last_block });
+        }
+    }
+}
+
+/// Posts alerts to a Discord, Slack or generic JSON webhook
+#[derive(Clone)]
+pub struct WebhookNotifier {
+    url: reqwest::Url,
+    client: reqwest::Client,
+}
+
+impl WebhookNotifier {
+    pub fn new(url: &str) -> Result<Self> {
+        let url = reqwest::Url::parse(url).map_err(|e| anyhow!("Invalid webhook URL '{}': {}", url, e))?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(anyhow!("Webhook URL must be http or https: {}", url).into());
+        }
+        let client = reqwest::Client::builder()
+            .user_agent(format!("igra-cli/{}", env!("CARGO_PKG_VERSION")))
+            .timeout(WEBHOOK_TIMEOUT)
+            .build()
+            .map_err(|e| anyhow!("Failed to create webhook client: {}", e))?;
+        Ok(Self { url, client })
+    }
+
+    /// Host the alerts go to, for display (the path often holds a token)
+    pub fn host(&self) -> &str {
+        self.url.host_str().unwrap_or_default()
+    }
+
+    /// Deliver in the background; returns immediately
+    pub fn send(&self, alert: &Alert) {
+        let notifier = self.clone();
+        let payload = alert.payload();
+        tokio::spawn(async move {
+            notifier.deliver(&payload).await;
+        });
+    }
+
+    /// Undeliverable alerts are dropped: the watch screen has nowhere to report them
+    async fn deliver(&self, payload: &serde_json::Value) {
+        for attempt in 1..=WEBHOOK_ATTEMPTS {
+            let retry = match self.client.post(self.url.clone()).json(payload).send().await {
+                Ok(response) => {
+                    let status = response.status();
+                    status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+                }
+                Err(_) => true,
+            };
+            if !retry {
+                return;
+            }
+            if attempt < WEBHOOK_ATTEMPTS {
+                tokio::time::sleep(WEBHOOK_RETRY_DELAY * attempt).await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(total: u64, failed: u64) -> Statistics {
+        Statistics {
+            current_block: 100,
+            total_transactions: total,
+            failed_transactions: failed,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_disconnect_raised_once_and_resolved() {
+        let mut monitor = AlertMonitor::new();
+        assert!(monitor.poll_failed("connection refused").is_empty());
+        assert!(monitor.poll_failed("connection refused").is_empty());
+        let alerts = monitor.poll_failed("connection refused");
+        assert_eq!(alerts.len(), 1);
+        assert_eq!((alerts[0].kind, alerts[0].firing), (AlertKind::NodeDisconnected, true));
+        assert!(monitor.poll_failed("connection refused").is_empty());
+
+        let alerts = monitor.observe(&stats(0, 0), Instant::now());
+        assert_eq!(alerts.len(), 1);
+        assert_eq!((alerts[0].kind, alerts[0].firing), (AlertKind::NodeDisconnected, false));
+    }
+
+    #[test]
+    fn test_failed_spike() {
+        let start = Instant::now();
+        let mut monitor = AlertMonitor::new();
+        assert!(monitor.observe(&stats(100, 0), start).is_empty());
+        // 4 failures: below the minimum count
+        assert!(monitor.observe(&stats(110, 4), start + Duration::from_secs(10)).is_empty());
+
+        let alerts = monitor.observe(&stats(120, 6), start + Duration::from_secs(20));
+        assert_eq!(alerts.len(), 1);
+        assert_eq!((alerts[0].kind, alerts[0].firing), (AlertKind::FailedTxSpike, true));
+        assert_eq!(alerts[0].message, "6 of 20 transactions failed in the last 60s");
+
+        // Once the failures leave the window, the spike clears
+        let alerts = monitor.observe(&stats(200, 6), start + Duration::from_secs(90));
+        assert_eq!(alerts.len(), 1);
+        assert_eq!((alerts[0].kind, alerts[0].firing), (AlertKind::FailedTxSpike, false));
+    }
+
+    #[test]
+    fn test_tps_drop_held_until_recovery() {
+        let start = Instant::now();
+        let at = |secs: u64| start + Duration::from_secs(secs);
+        let mut monitor = AlertMonitor::new();
+        monitor.observe(&stats(0, 0), at(0));
+        monitor.observe(&stats(60, 0), at(60));
+        assert!(monitor.observe(&stats(120, 0), at(120)).is_empty());
+
+        // 10 transactions against 60 in the previous minute
+        let alerts = monitor.observe(&stats(130, 0), at(180));
+        assert_eq!(alerts.len(), 1);
+        assert_eq!((alerts[0].kind, alerts[0].firing), (AlertKind::TpsDrop, true));
+
+        // Still low: both windows are now quiet, but it isn't a recovery
+        assert!(monitor.observe(&stats(140, 0), at(240)).is_empty());
+
+        let alerts = monitor.observe(&stats(200, 0), at(300));
+        assert_eq!(alerts.len(), 1);
+        assert_eq!((alerts[0].kind, alerts[0].firing), (AlertKind::TpsDrop, false));
+    }
+
+    #[test]
+    fn test_payload_fields() {
+        let alert = Alert {
+            kind: AlertKind::TpsDrop,
+            firing: true,
+            message: "TPS dropped".to_string(),
+            block: 42,
+        };
+        let payload = alert.payload();
+        assert_eq!(payload["content"], "⚠ igra-cli watch: TPS dropped");
+        assert_eq!(payload["text"], payload["content"]);
+        assert_eq!(payload["alert"], "tps_drop");
+        assert_eq!(payload["status"], "firing");
+        assert_eq!(payload["block"], 42);
+    }
+
+    #[test]
+    fn test_webhook_url_validation() {
+        assert!(WebhookNotifier::new("https://hooks.slack.com/services/x").is_ok());
+        assert!(WebhookNotifier::new("ftp://example.com").is_err());
+        assert!(WebhookNotifier::new("not a url").is_err());
+    }
+}
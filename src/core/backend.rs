@@ -0,0 +1,233 @@
+//! Container and wallet backends behind the TUI
+//!
+//! `App` talks to containers and wallets through these traits rather than
+//! `DockerManager` / `WalletManager` directly, so another container runtime
+//! (`PodmanBackend`) or a test double can stand in. The traits hold only what
+//! the TUI uses; the CLI and web server keep using the managers, except for
+//! profile start/stop which go through `container_backend`.
+
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::core::docker::{
+    container_runtime, ComposeServiceConfig, ContainerInfo, ContainerRuntime, ContainerStats, DockerManager,
+    ServiceConfigComparison,
+};
+use crate::core::error::Result;
+use crate::core::network::Network;
+use crate::core::podman::PodmanBackend;
+use crate::core::wallet::{FeeEstimate, SendFeePolicy, UtxoInfo, WalletClientPool, WalletInfo, WalletManager};
+use crate::core::wallet_history::WalletTotals;
+use crate::core::wallet_password::WalletPassword;
+
+/// Runs the compose project's services
+#[async_trait]
+pub trait ContainerBackend: Send + Sync {
+    /// Network the project is configured for
+    fn network(&self) -> Network;
+
+    async fn list_containers(&self) -> Result<Vec<ContainerInfo>>;
+
+    /// Stats by container name
+    async fn get_all_container_stats(&self) -> Result<HashMap<String, ContainerStats>>;
+
+    async fn get_logs(&self, service: &str, tail: Option<usize>, strip_ansi: bool) -> Result<String>;
+
+    async fn get_service_config_comparison(&self, service: &str) -> Result<ServiceConfigComparison>;
+
+    /// Services defined in the compose file
+    fn parse_compose_file(&self) -> Result<HashMap<String, ComposeServiceConfig>>;
+
+    async fn start_service(&self, service: &str) -> Result<()>;
+
+    async fn stop_service(&self, service: &str) -> Result<()>;
+
+    async fn restart_service(&self, service: &str) -> Result<()>;
+
+    /// Returns warnings about the compose file that didn't stop the start
+    async fn start_profile(&self, profile: &str) -> Result<Vec<String>>;
+
+    async fn stop_profile(&self, profile: &str) -> Result<()>;
+
+    /// Recreate the profile's services, returning their names
+    async fn recreate_profile(&self, profile: &str) -> Result<Vec<String>>;
+
+    async fn recreate_services(&self, profiles: &[String], services: &[String]) -> Result<()>;
+
+    async fn pull_images(&self) -> Result<()>;
+}
+
+/// Holds the workers' wallets
+#[async_trait]
+pub trait WalletBackend: Send + Sync {
+    async fn list_wallets(&self) -> Result<Vec<WalletInfo>>;
+
+    async fn get_wallet_info(&self, worker_id: usize) -> Result<WalletInfo>;
+
+    /// (address, available, pending) per address
+    async fn get_balance_detailed(&self, worker_id: usize) -> Result<Vec<(String, f64, f64)>>;
+
+    async fn get_utxos(&self, worker_id: usize) -> Result<Vec<UtxoInfo>>;
+
+    /// Cumulative received / sent from the transaction ledger
+    fn wallet_totals(&self, worker_id: usize) -> Result<WalletTotals>;
+
+    /// Fee and total of a send, without sending; None amount is a send-all
+    async fn estimate_fee(
+        &self,
+        worker_id: usize,
+        to_address: &str,
+        amount_sompi: Option<u64>,
+        fee_policy: SendFeePolicy,
+    ) -> Result<FeeEstimate>;
+
+    /// Returns a summary of the sent transactions
+    async fn send_transaction(
+        &self,
+        worker_id: usize,
+        to_address: &str,
+        amount_sompi: u64,
+        password: &WalletPassword,
+        send_all: bool,
+    ) -> Result<String>;
+
+    /// Most recent address, None when there is none or the daemon can't tell
+    async fn current_receive_address(&self, worker_id: usize) -> Result<Option<String>>;
+
+    /// Derive a fresh receive address
+    async fn new_receive_address(&self, worker_id: usize) -> Result<String>;
+
+    /// Returns the new wallet's address
+    async fn generate_wallet(&self, worker_id: usize, password: Option<&WalletPassword>) -> Result<String>;
+
+    /// kaswallet connections, shared with the health checks
+    fn pool(&self) -> &WalletClientPool;
+}
+
+/// The backend for the runtime in use (see `container_runtime`)
+pub fn container_backend(docker: DockerManager) -> Arc<dyn ContainerBackend> {
+    match container_runtime() {
+        ContainerRuntime::Docker => Arc::new(docker),
+        ContainerRuntime::Podman => Arc::new(PodmanBackend::new(docker)),
+    }
+}
+
+#[async_trait]
+impl ContainerBackend for DockerManager {
+    fn network(&self) -> Network {
+        DockerManager::network(self)
+    }
+
+    async fn list_containers(&self) -> Result<Vec<ContainerInfo>> {
+        DockerManager::list_containers(self).await
+    }
+
+    async fn get_all_container_stats(&self) -> Result<HashMap<String, ContainerStats>> {
+        DockerManager::get_all_container_stats(self).await
+    }
+
+    async fn get_logs(&self, service: &str, tail: Option<usize>, strip_ansi: bool) -> Result<String> {
+        DockerManager::get_logs(self, service, tail, strip_ansi).await
+    }
+
+    async fn get_service_config_comparison(&self, service: &str) -> Result<ServiceConfigComparison> {
+        DockerManager::get_service_config_comparison(self, service).await
+    }
+
+    fn parse_compose_file(&self) -> Result<HashMap<String, ComposeServiceConfig>> {
+        DockerManager::parse_compose_file(self)
+    }
+
+    async fn start_service(&self, service: &str) -> Result<()> {
+        DockerManager::start_service(self, service).await
+    }
+
+    async fn stop_service(&self, service: &str) -> Result<()> {
+        DockerManager::stop_service(self, service).await
+    }
+
+    async fn restart_service(&self, service: &str) -> Result<()> {
+        DockerManager::restart_service(self, service).await
+    }
+
+    async fn start_profile(&self, profile: &str) -> Result<Vec<String>> {
+        DockerManager::start_profile(self, profile).await
+    }
+
+    async fn stop_profile(&self, profile: &str) -> Result<()> {
+        DockerManager::stop_profile(self, profile).await
+    }
+
+    async fn recreate_profile(&self, profile: &str) -> Result<Vec<String>> {
+        DockerManager::recreate_profile(self, profile).await
+    }
+
+    async fn recreate_services(&self, profiles: &[String], services: &[String]) -> Result<()> {
+        DockerManager::recreate_services(self, profiles, services).await
+    }
+
+    async fn pull_images(&self) -> Result<()> {
+        DockerManager::pull_images(self).await
+    }
+}
+
+#[async_trait]
+impl WalletBackend for WalletManager {
+    async fn list_wallets(&self) -> Result<Vec<WalletInfo>> {
+        WalletManager::list_wallets(self).await
+    }
+
+    async fn get_wallet_info(&self, worker_id: usize) -> Result<WalletInfo> {
+        WalletManager::get_wallet_info(self, worker_id).await
+    }
+
+    async fn get_balance_detailed(&self, worker_id: usize) -> Result<Vec<(String, f64, f64)>> {
+        WalletManager::get_balance_detailed(self, worker_id).await
+    }
+
+    async fn get_utxos(&self, worker_id: usize) -> Result<Vec<UtxoInfo>> {
+        WalletManager::get_utxos(self, worker_id).await
+    }
+
+    fn wallet_totals(&self, worker_id: usize) -> Result<WalletTotals> {
+        WalletManager::wallet_totals(self, worker_id)
+    }
+
+    async fn estimate_fee(
+        &self,
+        worker_id: usize,
+        to_address: &str,
+        amount_sompi: Option<u64>,
+        fee_policy: SendFeePolicy,
+    ) -> Result<FeeEstimate> {
+        WalletManager::estimate_fee(self, worker_id, to_address, amount_sompi, fee_policy).await
+    }
+
+    async fn send_transaction(
+        &self,
+        worker_id: usize,
+        to_address: &str,
+        amount_sompi: u64,
+        password: &WalletPassword,
+        send_all: bool,
+    ) -> Result<String> {
+        WalletManager::send_transaction(self, worker_id, to_address, amount_sompi, password, send_all).await
+    }
+
+    async fn current_receive_address(&self, worker_id: usize) -> Result<Option<String>> {
+        WalletManager::current_receive_address(self, worker_id).await
+    }
+
+    async fn new_receive_address(&self, worker_id: usize) -> Result<String> {
+        WalletManager::new_receive_address(self, worker_id).await
+    }
+
+    async fn generate_wallet(&self, worker_id: usize, password: Option<&WalletPassword>) -> Result<String> {
+        WalletManager::generate_wallet(self, worker_id, password).await
+    }
+
+    fn pool(&self) -> &WalletClientPool {
+        WalletManager::pool(self)
+    }
+}
@@ -1,34 +1,35 @@
-/// Backup and restore management
-///
-/// Backup functionality is not yet fully implemented.
-///
-/// Manual backup procedures:
-/// 1. Stop services: `docker compose down`
-/// 2. Backup Docker volumes:
-///    ```
-///    docker run --rm \
-///      -v igra-data:/data \
-///      -v $(pwd):/backup \
-///      alpine tar czf /backup/data-backup.tar.gz /data
-///    ```
-/// 3. Backup configuration and keys:
-///    ```
-///    tar czf config-backup.tar.gz .env keys/
-///    ```
-///
-/// Restore procedures:
-/// 1. Stop services: `docker compose down`
-/// 2. Restore volumes and configuration files
-/// 3. Restart services: `docker compose --profile <profile> up -d`
-///
-/// For automated backup implementation, consider:
-/// - Integration with existing backup scripts
-/// - Scheduled backups via cron
-/// - Remote backup storage (S3, rsync, etc.)
+//! Backup and restore management
+//!
+//! Backup functionality is not yet fully implemented.
+//!
+//! Manual backup procedures:
+//! 1. Stop services: `docker compose down`
+//! 2. Backup Docker volumes:
+//!    ```sh
+//!    docker run --rm \
+//!      -v igra-data:/data \
+//!      -v $(pwd):/backup \
+//!      alpine tar czf /backup/data-backup.tar.gz /data
+//!    ```
+//! 3. Backup configuration and keys:
+//!    ```sh
+//!    tar czf config-backup.tar.gz .env keys/
+//!    ```
+//!
+//! Restore procedures:
+//! 1. Stop services: `docker compose down`
+//! 2. Restore volumes and configuration files
+//! 3. Restart services: `docker compose --profile <profile> up -d`
+//!
+//! For automated backup implementation, consider:
+//! - Integration with existing backup scripts
+//! - Scheduled backups via cron
+//! - Remote backup storage (S3, rsync, etc.)
 
 use anyhow::Result;
 
 #[allow(dead_code)]
+#[derive(Default)]
 pub struct BackupManager;
 
 #[allow(dead_code)]
@@ -1,13 +1,21 @@
-/// Configuration management for .env files
-///
-/// Handles reading, writing, and validating IGRA Orchestra configuration
+//! Configuration management for .env files
+//!
+//! Handles reading, writing, and validating IGRA Orchestra configuration
 
 use anyhow::{anyhow, Context, Result};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
 
-use crate::utils::{generate_hex_string, is_valid_domain, is_valid_email, is_valid_hex, RPC_TOKEN_COUNT};
+use crate::core::secrets::SecretsStore;
+use crate::utils::{generate_hex_secret, generate_secret, is_valid_domain, is_valid_email, is_valid_hex, RPC_TOKEN_COUNT};
+
+/// Length of generated passwords and secrets
+const SECRET_LENGTH: usize = 32;
+
+/// `--env-file`, when given
+static ENV_FILE: OnceLock<PathBuf> = OnceLock::new();
 
 #[derive(Debug, Clone)]
 pub struct ConfigValue {
@@ -16,17 +24,80 @@ pub struct ConfigValue {
     pub comment: Option<String>,
 }
 
+/// Where the effective value of a config key comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigSource {
+    File,
+    Environment,
+    /// The encrypted secrets store
+    Secrets,
+}
+
 pub struct ConfigManager {
     env_file: PathBuf,
     config: HashMap<String, ConfigValue>,
+    /// Process environment values that take precedence over the file
+    env_overrides: HashMap<String, String>,
+    /// Encrypted store for sensitive values, when configured
+    secrets: Option<SecretsStore>,
+    /// Keys whose value lives in the secrets store rather than .env
+    secret_keys: HashSet<String>,
+    /// Why the secrets store couldn't be decrypted
+    secrets_error: Option<String>,
+    /// Values as last loaded or saved, to log what a save changes (see `config_audit`)
+    saved: Mutex<HashMap<String, String>>,
+    /// File `save` takes the layout from, when not `env_file` itself
+    /// (a config started from a template by `init_from_example`)
+    layout_file: Option<PathBuf>,
 }
 
 impl ConfigManager {
-    /// Load configuration from .env file in project root
+    /// Load configuration from .env file in project root, merged with the
+    /// secrets store if one is configured. The process environment takes
+    /// precedence when `[env] overrides` is set in config.toml.
     pub fn load_from_project() -> Result<Self> {
         let project_root = crate::utils::get_project_root()?;
-        let env_file = project_root.join(".env");
-        Self::load(env_file)
+        let env_file = Self::env_file_in(&project_root);
+        if Self::env_file_override().is_some() && !env_file.is_file() {
+            return Err(anyhow!("Env file not found: {} (from --env-file)", env_file.display()));
+        }
+        let mut config = Self::load(env_file)?;
+        if let Some(store) = SecretsStore::configured(&project_root) {
+            config.attach_secrets(store);
+        }
+        if crate::utils::AppConfig::env_overrides() {
+            config = config.with_env_overrides();
+        }
+        Ok(config)
+    }
+
+    /// Use `env_file` instead of the project's .env for everything after
+    /// this (`--env-file`), compose included. Call once at startup; a
+    /// relative path is taken from the current directory.
+    pub fn use_env_file(env_file: &Path) -> Result<()> {
+        let env_file = if env_file.is_absolute() {
+            env_file.to_path_buf()
+        } else {
+            std::env::current_dir()
+                .context("Failed to read the current directory")?
+                .join(env_file)
+        };
+        ENV_FILE
+            .set(env_file)
+            .map_err(|_| anyhow!("Env file already configured"))
+    }
+
+    /// The `--env-file` path, if one was given
+    pub fn env_file_override() -> Option<&'static Path> {
+        ENV_FILE.get().map(PathBuf::as_path)
+    }
+
+    /// The env file in use for `project_root`: `--env-file`, else its .env
+    pub fn env_file_in(project_root: &Path) -> PathBuf {
+        match Self::env_file_override() {
+            Some(path) => path.to_path_buf(),
+            None => project_root.join(crate::utils::DEFAULT_ENV_FILE),
+        }
     }
 
     /// Load configuration from .env file
@@ -74,15 +145,165 @@ impl ConfigManager {
             }
         }
 
-        Ok(Self { env_file, config })
+        let saved = config.iter().map(|(key, v)| (key.clone(), v.value.clone())).collect();
+        Ok(Self {
+            env_file,
+            config,
+            env_overrides: HashMap::new(),
+            secrets: None,
+            secret_keys: HashSet::new(),
+            secrets_error: None,
+            saved: Mutex::new(saved),
+            layout_file: None,
+        })
+    }
+
+    /// Merge the decrypted secrets store over the .env values. A store that
+    /// can't be decrypted is remembered rather than returned as an error, so
+    /// the TUI and read-only commands still work; `require_secrets` and
+    /// `save` refuse to go on without it.
+    pub fn attach_secrets(&mut self, store: SecretsStore) {
+        match store.load() {
+            Ok(entries) => {
+                let saved = self.saved.get_mut().unwrap_or_else(|e| e.into_inner());
+                for (key, value) in entries {
+                    saved.insert(key.clone(), value.clone());
+                    self.secret_keys.insert(key.clone());
+                    let comment = self.config.remove(&key).and_then(|v| v.comment);
+                    self.config.insert(key.clone(), ConfigValue { key, value, comment });
+                }
+            }
+            Err(e) => self.secrets_error = Some(format!("{:#}", e)),
+        }
+        self.secrets = Some(store);
+    }
+
+    /// Fail when a secrets store is configured but couldn't be decrypted.
+    /// Commands that use passwords or tokens call this instead of carrying on
+    /// with whatever (possibly blank) value .env has.
+    pub fn require_secrets(&self) -> Result<()> {
+        match &self.secrets_error {
+            Some(e) => Err(anyhow!("{}", e)),
+            None => Ok(()),
+        }
+    }
+
+    /// Move every non-empty sensitive value (passwords, secrets, keys, tokens)
+    /// from .env into the secrets store. Returns the keys moved, sorted.
+    pub fn move_secrets_to_store(&mut self) -> Result<Vec<String>> {
+        if self.secrets.is_none() {
+            return Err(anyhow!("No secrets store configured (set [secrets] file in config.toml)"));
+        }
+        self.require_secrets()?;
+
+        let mut moved: Vec<String> = self
+            .config
+            .values()
+            .filter(|v| crate::utils::is_sensitive_key(&v.key) && !v.value.is_empty())
+            .filter(|v| !self.secret_keys.contains(&v.key))
+            .map(|v| v.key.clone())
+            .collect();
+        moved.sort();
+
+        self.secret_keys.extend(moved.iter().cloned());
+        self.save()?;
+        Ok(moved)
     }
 
-    /// Save configuration to .env file
+    /// Start `env_file` from the `.env.example` template next to it.
+    /// Nothing is written until `save`, so a failed init leaves no
+    /// half-filled file. Refuses to replace an existing file unless `force`
+    /// is set.
+    pub fn init_from_example<P: AsRef<Path>>(env_file: P, force: bool) -> Result<Self> {
+        let env_file = env_file.as_ref();
+        let example = env_file.with_file_name(".env.example");
+
+        if !example.exists() {
+            return Err(anyhow!("Template not found at {}", example.display()));
+        }
+        if env_file.exists() && !force {
+            return Err(anyhow!(
+                "{} already exists (use --force to overwrite it)",
+                env_file.display()
+            ));
+        }
+
+        let mut config = Self::load(&example)
+            .with_context(|| format!("Failed to read {}", example.display()))?;
+        config.env_file = env_file.to_path_buf();
+        config.layout_file = Some(example);
+        Ok(config)
+    }
+
+    /// Fill every *_PASSWORD / *_SECRET key with a fresh random value.
+    /// OVH_* credentials are issued by OVH and left untouched.
+    /// Returns the keys that were generated, sorted.
+    pub fn generate_secrets(&mut self) -> Vec<String> {
+        let mut keys: Vec<String> = self
+            .config
+            .keys()
+            .filter(|k| k.ends_with("_PASSWORD") || k.ends_with("_SECRET"))
+            .filter(|k| !k.starts_with("OVH_"))
+            .cloned()
+            .collect();
+        keys.sort();
+
+        for key in &keys {
+            // Secrets must pass validate_value's hex check, passwords needn't
+            let value = if key.ends_with("_SECRET") {
+                generate_hex_secret(SECRET_LENGTH)
+            } else {
+                generate_secret(SECRET_LENGTH)
+            };
+            self.set(key.clone(), value);
+        }
+
+        keys
+    }
+
+    /// Let process environment variables override file values for keys defined in the file
+    /// (same precedence docker compose applies when reading .env)
+    pub fn with_env_overrides(self) -> Self {
+        self.with_env_from(|key| std::env::var(key).ok())
+    }
+
+    /// `with_env_overrides` reading variables through `lookup`
+    fn with_env_from(mut self, lookup: impl Fn(&str) -> Option<String>) -> Self {
+        self.env_overrides = self
+            .config
+            .keys()
+            .filter_map(|key| lookup(key).map(|value| (key.clone(), value)))
+            .collect();
+        self
+    }
+
+    /// Save configuration to .env file. Written to a temp file alongside and
+    /// renamed over the original, so a crash never leaves a truncated .env.
+    /// Values held in the secrets store are re-encrypted there and left out
+    /// of .env. In a dry run nothing is written (see `dry_run`).
     pub fn save(&self) -> Result<()> {
+        if crate::core::dry_run::skip(|| format!("write {}", self.env_file.display())) {
+            return Ok(());
+        }
+
+        if let Some(ref store) = self.secrets {
+            self.require_secrets()
+                .context("Refusing to save configuration without the secrets store")?;
+
+            let mut entries: Vec<(String, String)> = self
+                .secret_keys
+                .iter()
+                .filter_map(|key| self.config.get(key).map(|v| (key.clone(), v.value.clone())))
+                .collect();
+            entries.sort();
+            // Before .env, so a failure never loses the only copy of a value
+            store.save(&entries)?;
+        }
+
         let mut lines = Vec::new();
 
         // Preserve order by reading original file
-        let original = fs::read_to_string(&self.env_file)?;
+        let original = fs::read_to_string(self.layout_file.as_ref().unwrap_or(&self.env_file))?;
         for line in original.lines() {
             let line_trimmed = line.trim();
 
@@ -90,6 +311,9 @@ impl ConfigManager {
                 lines.push(line.to_string());
             } else if let Some((key, _)) = line_trimmed.split_once('=') {
                 let key = key.trim();
+                if self.secret_keys.contains(key) {
+                    continue;
+                }
                 if let Some(value) = self.config.get(key) {
                     lines.push(format!("{}={}", key, value.value));
                 } else {
@@ -98,22 +322,107 @@ impl ConfigManager {
             }
         }
 
-        fs::write(&self.env_file, lines.join("\n"))
+        let mut tmp = self.env_file.clone().into_os_string();
+        tmp.push(".tmp");
+        let tmp = PathBuf::from(tmp);
+        fs::write(&tmp, lines.join("\n"))
             .context("Failed to write .env file")?;
 
+        // Keep the original mode (.env holds passwords)
+        if let Ok(meta) = fs::metadata(&self.env_file) {
+            fs::set_permissions(&tmp, meta.permissions()).ok();
+        }
+
+        if let Err(e) = fs::rename(&tmp, &self.env_file) {
+            let _ = fs::remove_file(&tmp);
+            return Err(e).context("Failed to replace .env file");
+        }
+
+        self.record_changes();
         Ok(())
     }
 
-    /// Get a configuration value
+    /// Log what the last save changed to the local audit log. Best effort:
+    /// the new values are written either way. Sensitive keys and values from
+    /// the secrets store are logged without values.
+    fn record_changes(&self) {
+        use crate::core::config_audit;
+
+        let mut saved = self.saved.lock().unwrap_or_else(|e| e.into_inner());
+        // Only keys already in .env, or in the store, are written by `save`
+        let after: HashMap<String, String> = self
+            .config
+            .iter()
+            .filter(|(key, _)| saved.contains_key(*key) || self.secret_keys.contains(*key))
+            .map(|(key, v)| (key.clone(), v.value.clone()))
+            .collect();
+        let is_secret = |key: &str| crate::utils::is_sensitive_key(key) || self.secret_keys.contains(key);
+        let entries = config_audit::changes(&self.env_file, &saved, &after, is_secret, config_audit::source());
+        let _ = config_audit::append(&config_audit::log_file(&self.env_file), &entries);
+        *saved = after;
+    }
+
+    /// The local audit log of edits to this env file (see `config_audit`)
+    pub fn audit_log(&self) -> PathBuf {
+        crate::core::config_audit::log_file(&self.env_file)
+    }
+
+    /// Get the effective configuration value (environment override first, then file)
     pub fn get(&self, key: &str) -> Option<&str> {
+        self.env_overrides
+            .get(key)
+            .map(|v| v.as_str())
+            .or_else(|| self.file_value(key))
+    }
+
+    /// Profiles compose enables without `--profile`: COMPOSE_PROFILES from the
+    /// environment, else from the .env file (compose's own precedence)
+    pub fn compose_profiles(&self) -> Vec<String> {
+        let value = std::env::var("COMPOSE_PROFILES").ok().or_else(|| self.file_value("COMPOSE_PROFILES").map(str::to_string));
+        value
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Get the value as written in the .env file, ignoring environment overrides
+    pub fn file_value(&self, key: &str) -> Option<&str> {
         self.config.get(key).map(|v| v.value.as_str())
     }
 
-    /// Set a configuration value
+    /// Where the effective value of a key comes from
+    pub fn source(&self, key: &str) -> Option<ConfigSource> {
+        if self.env_overrides.contains_key(key) {
+            Some(ConfigSource::Environment)
+        } else if self.secret_keys.contains(key) {
+            Some(ConfigSource::Secrets)
+        } else if self.config.contains_key(key) {
+            Some(ConfigSource::File)
+        } else {
+            None
+        }
+    }
+
+    /// Keys whose file value is overridden by the process environment
+    pub fn overridden_keys(&self) -> Vec<String> {
+        let mut keys: Vec<String> = self.env_overrides.keys().cloned().collect();
+        keys.sort();
+        keys
+    }
+
+    /// Set a configuration value. With a secrets store, sensitive keys are
+    /// kept in the store.
     pub fn set(&mut self, key: impl Into<String>, value: impl Into<String>) {
         let key = key.into();
         let value = value.into();
 
+        if self.secrets.is_some() && crate::utils::is_sensitive_key(&key) {
+            self.secret_keys.insert(key.clone());
+        }
+
         if let Some(existing) = self.config.get_mut(&key) {
             existing.value = value;
         } else {
@@ -141,20 +450,38 @@ impl ConfigManager {
 
     /// Generate a single RPC access token
     pub fn generate_rpc_token(&mut self, index: usize) -> Result<String> {
-        if index < 1 || index > RPC_TOKEN_COUNT {
+        if !(1..=RPC_TOKEN_COUNT).contains(&index) {
             return Err(anyhow!(
                 "Token index must be between 1 and {}",
                 RPC_TOKEN_COUNT
             ));
         }
 
-        let token = generate_hex_string(32);
+        let token = generate_hex_secret(32);
         let key = format!("RPC_ACCESS_TOKEN_{}", index);
         self.set(key, &token);
 
         Ok(token)
     }
 
+    /// Replace one RPC access token with a fresh value, leaving the others
+    /// as they are. Refuses slots that aren't in .env (or the secrets store),
+    /// since `save` would drop them, and slots overridden by the process
+    /// environment, where the new value would never be used.
+    pub fn rotate_rpc_token(&mut self, index: usize) -> Result<String> {
+        let key = format!("RPC_ACCESS_TOKEN_{}", index);
+        match self.source(&key) {
+            Some(ConfigSource::Environment) => {
+                return Err(anyhow!("{} is set in the process environment, which overrides .env", key));
+            }
+            None if (1..=RPC_TOKEN_COUNT).contains(&index) => {
+                return Err(anyhow!("{} is not defined in .env", key));
+            }
+            _ => {}
+        }
+        self.generate_rpc_token(index)
+    }
+
     /// Generate all RPC access tokens
     pub fn generate_all_rpc_tokens(&mut self) -> Result<Vec<String>> {
         let mut tokens = Vec::new();
@@ -243,6 +570,58 @@ impl ConfigManager {
         errors
     }
 
+    /// Check a single value before it is written (None = valid)
+    pub fn validate_value(key: &str, value: &str) -> Option<String> {
+        // Empty values are generally not allowed
+        if value.trim().is_empty() {
+            return Some("Value cannot be empty".to_string());
+        }
+
+        // Line breaks or other control characters would corrupt the .env file
+        if value.chars().any(|c| c.is_control()) {
+            return Some("Value cannot contain newlines or control characters".to_string());
+        }
+
+        // Domain validation
+        if key.contains("DOMAIN") && !is_valid_domain(value) {
+            return Some("Invalid domain format".to_string());
+        }
+
+        // Email validation
+        if key.contains("EMAIL") && !is_valid_email(value) {
+            return Some("Invalid email format".to_string());
+        }
+
+        // Hex validation for keys/secrets
+        if (key.contains("_KEY") || key.contains("_SECRET"))
+            && key != "OVH_APPLICATION_KEY"
+            && key != "OVH_APPLICATION_SECRET"
+            && !is_valid_hex(value)
+        {
+            return Some("Must be a valid hex string".to_string());
+        }
+
+        // Port validation
+        if key.contains("PORT") && value.parse::<u16>().is_err() {
+            return Some("Must be a valid port number (1-65535)".to_string());
+        }
+
+        // URL validation (basic)
+        if (key.contains("URL") || key.contains("ENDPOINT"))
+            && !value.starts_with("http://")
+            && !value.starts_with("https://")
+        {
+            return Some("Must start with http:// or https://".to_string());
+        }
+
+        // Network validation
+        if key == "NETWORK" && value.parse::<crate::core::network::Network>().is_err() {
+            return Some("Must be either 'testnet' or 'mainnet'".to_string());
+        }
+
+        None
+    }
+
     /// Get all configuration keys
     pub fn keys(&self) -> Vec<String> {
         let mut keys: Vec<String> = self.config.keys().cloned().collect();
@@ -250,11 +629,11 @@ impl ConfigManager {
         keys
     }
 
-    /// Export configuration to HashMap
+    /// Export effective configuration to HashMap
     pub fn to_map(&self) -> HashMap<String, String> {
         self.config
-            .iter()
-            .map(|(k, v)| (k.clone(), v.value.clone()))
+            .keys()
+            .filter_map(|k| self.get(k).map(|v| (k.clone(), v.to_string())))
             .collect()
     }
 }
@@ -308,6 +687,22 @@ mod tests {
         assert_eq!(errors.len(), 0);
     }
 
+    #[test]
+    fn test_env_overrides() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "IGRA_TEST_OVERRIDE_KEY=from-file").unwrap();
+        writeln!(file, "IGRA_TEST_PLAIN_KEY=plain").unwrap();
+
+        let env = |key: &str| (key == "IGRA_TEST_OVERRIDE_KEY").then(|| "from-env".to_string());
+        let config = ConfigManager::load(file.path()).unwrap().with_env_from(env);
+
+        assert_eq!(config.get("IGRA_TEST_OVERRIDE_KEY"), Some("from-env"));
+        assert_eq!(config.file_value("IGRA_TEST_OVERRIDE_KEY"), Some("from-file"));
+        assert_eq!(config.source("IGRA_TEST_OVERRIDE_KEY"), Some(ConfigSource::Environment));
+        assert_eq!(config.source("IGRA_TEST_PLAIN_KEY"), Some(ConfigSource::File));
+        assert_eq!(config.overridden_keys(), vec!["IGRA_TEST_OVERRIDE_KEY".to_string()]);
+    }
+
     #[test]
     fn test_generate_tokens() {
         let mut file = NamedTempFile::new().unwrap();
@@ -319,4 +714,141 @@ mod tests {
         assert_eq!(token.len(), 32);
         assert!(is_valid_hex(&token));
     }
+
+    #[test]
+    fn test_rotate_rpc_token() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "# Tokens").unwrap();
+        writeln!(file, "RPC_ACCESS_TOKEN_1=aaaa").unwrap();
+        writeln!(file, "RPC_ACCESS_TOKEN_2=bbbb").unwrap();
+        writeln!(file, "NETWORK=testnet").unwrap();
+
+        let mut config = ConfigManager::load(file.path()).unwrap();
+        let token = config.rotate_rpc_token(2).unwrap();
+        config.save().unwrap();
+
+        let reloaded = ConfigManager::load(file.path()).unwrap();
+        assert_eq!(reloaded.get("RPC_ACCESS_TOKEN_1"), Some("aaaa"));
+        assert_eq!(reloaded.get("RPC_ACCESS_TOKEN_2"), Some(token.as_str()));
+        assert_ne!(token, "bbbb");
+        assert_eq!(reloaded.get("NETWORK"), Some("testnet"));
+
+        // Out of range, or not in the file at all
+        assert!(config.rotate_rpc_token(0).is_err());
+        assert!(config.rotate_rpc_token(RPC_TOKEN_COUNT + 1).is_err());
+        assert!(config.rotate_rpc_token(3).is_err());
+    }
+
+    #[test]
+    fn test_save_records_changes_without_secret_values() {
+        let dir = tempfile::tempdir().unwrap();
+        let env_file = dir.path().join(".env");
+        fs::write(&env_file, "NETWORK=testnet\nW0_KASWALLET_PASSWORD=hunter2\nNODE_ID=node-1\n").unwrap();
+
+        let mut config = ConfigManager::load(&env_file).unwrap();
+        config.set("NETWORK", "mainnet");
+        config.set("W0_KASWALLET_PASSWORD", "correct horse");
+        config.save().unwrap();
+        // Nothing changed since, so nothing more is logged
+        config.save().unwrap();
+
+        let entries = crate::core::config_audit::read(&config.audit_log());
+        let described: Vec<(String, String)> = entries.iter().map(|e| (e.key.clone(), e.describe())).collect();
+        assert_eq!(
+            described,
+            vec![
+                ("NETWORK".to_string(), "testnet → mainnet".to_string()),
+                ("W0_KASWALLET_PASSWORD".to_string(), "changed (value not recorded)".to_string()),
+            ]
+        );
+        let log = fs::read_to_string(config.audit_log()).unwrap();
+        assert!(!log.contains("hunter2") && !log.contains("correct horse"));
+    }
+
+    #[test]
+    fn test_init_from_example() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join(".env.example"),
+            "NETWORK=testnet\nW0_KASWALLET_PASSWORD=changeme\nJWT_SECRET=\nOVH_APPLICATION_SECRET=\n",
+        )
+        .unwrap();
+        let env_file = dir.path().join(".env");
+
+        let mut config = ConfigManager::init_from_example(&env_file, false).unwrap();
+        // Nothing on disk until it is saved
+        assert!(!env_file.exists());
+        let generated = config.generate_secrets();
+        assert_eq!(generated, vec!["JWT_SECRET".to_string(), "W0_KASWALLET_PASSWORD".to_string()]);
+        assert_eq!(config.get("W0_KASWALLET_PASSWORD").unwrap().len(), SECRET_LENGTH);
+        assert_ne!(config.get("W0_KASWALLET_PASSWORD"), Some("changeme"));
+        assert!(ConfigManager::validate_value("JWT_SECRET", config.get("JWT_SECRET").unwrap()).is_none());
+        assert_eq!(config.get("OVH_APPLICATION_SECRET"), Some(""));
+        assert_eq!(config.get("NETWORK"), Some("testnet"));
+
+        config.save().unwrap();
+        let saved = ConfigManager::load(&env_file).unwrap();
+        assert_eq!(saved.get("W0_KASWALLET_PASSWORD"), config.get("W0_KASWALLET_PASSWORD"));
+        assert_eq!(saved.get("NETWORK"), Some("testnet"));
+        // The template is left as it was
+        let template = fs::read_to_string(dir.path().join(".env.example")).unwrap();
+        assert!(template.contains("W0_KASWALLET_PASSWORD=changeme"));
+
+        // An existing .env is only replaced with force
+        assert!(ConfigManager::init_from_example(&env_file, false).is_err());
+        assert!(ConfigManager::init_from_example(&env_file, true).is_ok());
+    }
+    #[test]
+    fn test_secrets_store() {
+        use crate::core::secrets::{KeySource, SecretsConfig};
+        use age::secrecy::ExposeSecret;
+
+        let dir = tempfile::tempdir().unwrap();
+        let env_file = dir.path().join(".env");
+        fs::write(&env_file, "NETWORK=testnet\n# Worker 0\nW0_KASWALLET_PASSWORD=hunter2\n").unwrap();
+
+        // A key file rather than an environment variable, which parallel tests would share
+        let identity = age::x25519::Identity::generate();
+        let key_file = dir.path().join("secrets.key");
+        fs::write(&key_file, identity.to_string().expose_secret()).unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&key_file, fs::Permissions::from_mode(0o600)).unwrap();
+        }
+        let store = |file: &str, key_file: &Path| {
+            SecretsStore::new(dir.path(), SecretsConfig {
+                file: Some(file.into()),
+                key_source: KeySource::Keyfile,
+                key_file: Some(key_file.to_path_buf()),
+                key_env: None,
+            })
+            .unwrap()
+        };
+
+        // A store that doesn't exist yet is empty; migrating creates it
+        let mut config = ConfigManager::load(&env_file).unwrap();
+        config.attach_secrets(store("new.env.age", &key_file));
+        assert_eq!(config.get("W0_KASWALLET_PASSWORD"), Some("hunter2"));
+        assert_eq!(config.move_secrets_to_store().unwrap(), vec!["W0_KASWALLET_PASSWORD".to_string()]);
+
+        // Plaintext is gone from .env but still readable through the store
+        let env_contents = fs::read_to_string(&env_file).unwrap();
+        assert!(!env_contents.contains("hunter2"));
+        assert!(env_contents.contains("NETWORK=testnet"));
+        assert!(!String::from_utf8_lossy(&fs::read(dir.path().join("new.env.age")).unwrap()).contains("hunter2"));
+
+        let mut config = ConfigManager::load(&env_file).unwrap();
+        config.attach_secrets(store("new.env.age", &key_file));
+        assert_eq!(config.get("W0_KASWALLET_PASSWORD"), Some("hunter2"));
+        assert_eq!(config.source("W0_KASWALLET_PASSWORD"), Some(ConfigSource::Secrets));
+        assert!(config.require_secrets().is_ok());
+
+        // Without the key, secrets are refused rather than left blank
+        fs::copy(dir.path().join("new.env.age"), dir.path().join("other.env.age")).unwrap();
+        let mut config = ConfigManager::load(&env_file).unwrap();
+        config.attach_secrets(store("other.env.age", &dir.path().join("missing.key")));
+        assert!(config.require_secrets().is_err());
+        assert!(config.save().is_err());
+    }
 }
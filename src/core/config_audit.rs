@@ -0,0 +1,257 @@
+//! Local audit log of config edits behind `igra-cli config history`
+//!
+//! Every `ConfigManager::save` appends one JSON line per changed key to
+//! `config_audit.jsonl` next to the env file: when, which file, key, old and
+//! new value, and whether the edit came from the CLI, the TUI or the web API.
+//! Sensitive keys (passwords, secrets, keys, tokens) and values held in the
+//! secrets store are logged as changed without either value. The log rotates
+//! at `MAX_LOG_BYTES`, keeping `KEEP_ROTATED` older files.
+//!
+//! It only sees edits made through igra-cli on this machine; a hand-edited
+//! .env shows up as nothing at all. It is a convenience, not a substitute for
+//! keeping the configuration in version control.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+const LOG_FILE: &str = "config_audit.jsonl";
+/// Rotate once the current file would grow past this
+const MAX_LOG_BYTES: u64 = 512 * 1024;
+/// Rotated files kept (`config_audit.jsonl.1` is the newest)
+const KEEP_ROTATED: usize = 2;
+
+/// Which front end made an edit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeSource {
+    Cli,
+    Tui,
+    Api,
+}
+
+impl ChangeSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangeSource::Cli => "cli",
+            ChangeSource::Tui => "tui",
+            ChangeSource::Api => "api",
+        }
+    }
+}
+
+static SOURCE: OnceLock<ChangeSource> = OnceLock::new();
+
+/// Attribute this process's edits to `source` (set once at startup)
+pub fn set_source(source: ChangeSource) {
+    let _ = SOURCE.set(source);
+}
+
+/// The front end edits are attributed to; the CLI unless set
+pub fn source() -> ChangeSource {
+    SOURCE.get().copied().unwrap_or(ChangeSource::Cli)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub timestamp: DateTime<Utc>,
+    /// Env file name, e.g. ".env" or ".env.staging"
+    pub file: String,
+    pub key: String,
+    /// None when the key is new, or the value is secret
+    pub old: Option<String>,
+    /// None when the value is secret
+    pub new: Option<String>,
+    /// Values left out because the key is sensitive
+    pub redacted: bool,
+    pub source: ChangeSource,
+}
+
+impl AuditEntry {
+    /// "old → new", or "changed (value not recorded)" for secrets
+    pub fn describe(&self) -> String {
+        if self.redacted {
+            return "changed (value not recorded)".to_string();
+        }
+        let show = |value: &Option<String>| match value.as_deref() {
+            None => "(unset)".to_string(),
+            Some("") => "(empty)".to_string(),
+            Some(value) => value.to_string(),
+        };
+        format!("{} → {}", show(&self.old), show(&self.new))
+    }
+}
+
+/// The audit log for the env file `env_file`
+pub fn log_file(env_file: &Path) -> PathBuf {
+    env_file.with_file_name(LOG_FILE)
+}
+
+/// Entries for the keys that differ between `before` and `after`, including
+/// keys that were removed (logged with no new value), sorted by key.
+/// `is_secret` decides which keys are logged without values.
+pub fn changes(
+    env_file: &Path,
+    before: &HashMap<String, String>,
+    after: &HashMap<String, String>,
+    is_secret: impl Fn(&str) -> bool,
+    source: ChangeSource,
+) -> Vec<AuditEntry> {
+    let file = env_file.file_name().map(|f| f.to_string_lossy().into_owned()).unwrap_or_default();
+    let timestamp = Utc::now();
+
+    let changed = after
+        .iter()
+        .filter(|(key, value)| before.get(*key) != Some(*value))
+        .map(|(key, value)| (key, Some(value)));
+    let removed = before.keys().filter(|key| !after.contains_key(*key)).map(|key| (key, None));
+
+    let mut entries: Vec<AuditEntry> = changed
+        .chain(removed)
+        .map(|(key, value)| {
+            let redacted = is_secret(key);
+            AuditEntry {
+                timestamp,
+                file: file.clone(),
+                key: key.clone(),
+                old: if redacted { None } else { before.get(key).cloned() },
+                new: if redacted { None } else { value.cloned() },
+                redacted,
+                source,
+            }
+        })
+        .collect();
+    entries.sort_by(|a, b| a.key.cmp(&b.key));
+    entries
+}
+
+/// Append `entries` to the log at `path`, rotating it first if it would
+/// outgrow `MAX_LOG_BYTES`
+pub fn append(path: &Path, entries: &[AuditEntry]) -> Result<()> {
+    append_capped(path, entries, MAX_LOG_BYTES)
+}
+
+fn append_capped(path: &Path, entries: &[AuditEntry], max_bytes: u64) -> Result<()> {
+    if entries.is_empty() {
+        return Ok(());
+    }
+
+    let mut lines = String::new();
+    for entry in entries {
+        lines.push_str(&serde_json::to_string(entry).context("Failed to serialize audit entry")?);
+        lines.push('\n');
+    }
+
+    let size = fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+    if size > 0 && size + lines.len() as u64 > max_bytes {
+        rotate(path)?;
+    }
+
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    file.write_all(lines.as_bytes())
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+fn rotated_path(path: &Path, n: usize) -> PathBuf {
+    let mut rotated = path.as_os_str().to_owned();
+    rotated.push(format!(".{}", n));
+    PathBuf::from(rotated)
+}
+
+fn rotate(path: &Path) -> Result<()> {
+    fs::remove_file(rotated_path(path, KEEP_ROTATED)).ok();
+    for n in (1..KEEP_ROTATED).rev() {
+        fs::rename(rotated_path(path, n), rotated_path(path, n + 1)).ok();
+    }
+    fs::rename(path, rotated_path(path, 1)).with_context(|| format!("Failed to rotate {}", path.display()))
+}
+
+/// Every entry still on disk, oldest first. Unreadable lines are skipped.
+pub fn read(path: &Path) -> Vec<AuditEntry> {
+    let files = (1..=KEEP_ROTATED).rev().map(|n| rotated_path(path, n)).chain([path.to_path_buf()]);
+    files
+        .filter_map(|file| fs::read_to_string(file).ok())
+        .flat_map(|content| {
+            content
+                .lines()
+                .filter_map(|line| serde_json::from_str::<AuditEntry>(line).ok())
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_changes_redact_secrets() {
+        let before = values(&[("NETWORK", "testnet"), ("W0_KASWALLET_PASSWORD", "hunter2"), ("DOMAIN", "a.example")]);
+        let after = values(&[("NETWORK", "mainnet"), ("W0_KASWALLET_PASSWORD", "correct horse"), ("DOMAIN", "a.example")]);
+
+        let entries = changes(Path::new("/srv/igra/.env"), &before, &after, crate::utils::is_sensitive_key, ChangeSource::Tui);
+        assert_eq!(entries.len(), 2);
+
+        assert_eq!(entries[0].key, "NETWORK");
+        assert_eq!(entries[0].file, ".env");
+        assert_eq!(entries[0].describe(), "testnet → mainnet");
+
+        assert_eq!(entries[1].key, "W0_KASWALLET_PASSWORD");
+        assert!(entries[1].redacted);
+        assert_eq!(entries[1].describe(), "changed (value not recorded)");
+        let line = serde_json::to_string(&entries[1]).unwrap();
+        assert!(!line.contains("hunter2") && !line.contains("correct horse"));
+    }
+
+    #[test]
+    fn test_changes_include_removed_keys() {
+        let before = values(&[("NETWORK", "testnet"), ("OLD_FLAG", "1"), ("W0_KASWALLET_PASSWORD", "hunter2")]);
+        let after = values(&[("NETWORK", "testnet")]);
+
+        let entries = changes(Path::new("/srv/igra/.env"), &before, &after, crate::utils::is_sensitive_key, ChangeSource::Cli);
+        assert_eq!(entries.len(), 2);
+
+        assert_eq!(entries[0].key, "OLD_FLAG");
+        assert_eq!(entries[0].describe(), "1 → (unset)");
+
+        assert_eq!(entries[1].key, "W0_KASWALLET_PASSWORD");
+        assert!(entries[1].redacted);
+        assert!(!serde_json::to_string(&entries[1]).unwrap().contains("hunter2"));
+    }
+
+    #[test]
+    fn test_append_rotates_and_reads_oldest_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = log_file(&dir.path().join(".env"));
+
+        let entry = |n: usize| {
+            let before = values(&[("IGRA_PORT", &n.to_string())]);
+            let after = values(&[("IGRA_PORT", &(n + 1).to_string())]);
+            changes(&dir.path().join(".env"), &before, &after, |_| false, ChangeSource::Cli)
+        };
+        let line_len = serde_json::to_string(&entry(0)[0]).unwrap().len() as u64 + 1;
+
+        // Room for two lines per file: eight appends fill four files, of which
+        // the current one and KEEP_ROTATED (2) older ones are kept
+        for n in 0..8 {
+            append_capped(&path, &entry(n), line_len * 2).unwrap();
+        }
+        assert!(!rotated_path(&path, KEEP_ROTATED + 1).exists());
+
+        let olds: Vec<String> = read(&path).into_iter().filter_map(|e| e.old).collect();
+        assert_eq!(olds, vec!["2", "3", "4", "5", "6", "7"]);
+    }
+}
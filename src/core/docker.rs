@@ -1,17 +1,254 @@
-/// Docker and Docker Compose integration
-///
-/// Manages Docker containers, images, and docker-compose operations
+//! Docker and Docker Compose integration
+//!
+//! Manages Docker containers, images, and docker-compose operations
 
-use anyhow::{anyhow, Context, Result};
+use anyhow::{anyhow, Context};
 use bollard::Docker;
-use bollard::container::{ListContainersOptions, StatsOptions};
+use bollard::container::{ListContainersOptions, NetworkStats, StatsOptions};
 use bollard::models::ContainerSummary;
+use chrono::{DateTime, SecondsFormat, Utc};
 use std::collections::HashMap;
+use std::ffi::OsString;
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
+use std::sync::OnceLock;
+
+use crate::utils::{get_project_root, ContainerState, DEFAULT_COMPOSE_FILE};
+use crate::core::error::{Error, Result};
+use crate::core::log_parser::{parse_service_logs, strip_ansi_codes, ServiceMetrics};
+use crate::core::network::Network;
+
+/// Upper bound on concurrent per-container Docker calls during a refresh
+const MAX_CONCURRENT_PER_CONTAINER: usize = 6;
+
+/// Label compose puts on every container of a project; all project scoping
+/// (listing, `stop --all`) filters on it
+pub const COMPOSE_PROJECT_LABEL: &str = "com.docker.compose.project";
+
+/// Set to `v1` or `v2` to pick the Compose CLI instead of detecting it
+/// (`podman` or `podman-compose` under Podman)
+pub const COMPOSE_OVERRIDE_ENV: &str = "IGRA_COMPOSE";
+
+/// Container engine behind the Docker API socket and the CLI commands
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ContainerRuntime {
+    #[default]
+    Docker,
+    /// Podman through its Docker-compatible API socket
+    Podman,
+}
+
+impl ContainerRuntime {
+    /// CLI binary, for the commands that have no API equivalent
+    pub fn program(self) -> &'static str {
+        match self {
+            ContainerRuntime::Docker => "docker",
+            ContainerRuntime::Podman => "podman",
+        }
+    }
+
+    fn installed(self) -> bool {
+        Command::new(self.program())
+            .arg("--version")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .map(|status| status.success())
+            .unwrap_or(false)
+    }
+}
+
+impl std::str::FromStr for ContainerRuntime {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "docker" => Ok(ContainerRuntime::Docker),
+            "podman" => Ok(ContainerRuntime::Podman),
+            other => Err(format!("unknown backend '{}' (expected 'docker' or 'podman')", other)),
+        }
+    }
+}
+
+impl std::fmt::Display for ContainerRuntime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ContainerRuntime::Docker => "Docker",
+            ContainerRuntime::Podman => "Podman",
+        })
+    }
+}
+
+static RUNTIME: OnceLock<ContainerRuntime> = OnceLock::new();
+
+/// The runtime chosen with `--backend`, else detected from DOCKER_HOST and
+/// the sockets present (once per process)
+pub fn container_runtime() -> ContainerRuntime {
+    *RUNTIME.get_or_init(|| {
+        let docker_host = std::env::var("DOCKER_HOST").ok();
+        detect_runtime(docker_host.as_deref(), Path::new(SYSTEM_DOCKER_SOCKET), user_runtime_dir().as_deref())
+    })
+}
+
+/// `docker` or `podman`, for CLI commands outside of compose
+pub fn runtime_program() -> &'static str {
+    container_runtime().program()
+}
+
+/// Docker unless the environment points at Podman: a DOCKER_HOST naming a
+/// podman socket, a docker.sock that is a symlink to one (podman-docker), or
+/// no Docker socket at all but a Podman one
+fn detect_runtime(docker_host: Option<&str>, system_socket: &Path, runtime_dir: Option<&Path>) -> ContainerRuntime {
+    if let Some(host) = docker_host.map(str::trim).filter(|h| !h.is_empty()) {
+        return if host.contains("podman") { ContainerRuntime::Podman } else { ContainerRuntime::Docker };
+    }
+
+    let rootless_docker = runtime_dir.map(|dir| dir.join("docker.sock"));
+    for socket in std::iter::once(system_socket.to_path_buf()).chain(rootless_docker) {
+        if let Ok(target) = socket.canonicalize() {
+            return if target.to_string_lossy().contains("podman") {
+                ContainerRuntime::Podman
+            } else {
+                ContainerRuntime::Docker
+            };
+        }
+    }
+
+    if podman_sockets(runtime_dir).iter().any(|socket| socket.exists()) {
+        ContainerRuntime::Podman
+    } else {
+        ContainerRuntime::Docker
+    }
+}
+
+/// Rootless socket first, then the system one
+fn podman_sockets(runtime_dir: Option<&Path>) -> Vec<PathBuf> {
+    runtime_dir
+        .map(|dir| dir.join("podman").join("podman.sock"))
+        .into_iter()
+        .chain(std::iter::once(PathBuf::from(SYSTEM_PODMAN_SOCKET)))
+        .collect()
+}
+
+/// Which Compose CLI runs compose commands
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComposeCli {
+    /// The `docker compose` plugin (v2)
+    Plugin,
+    /// The standalone `docker-compose` binary (v1, 1.28+ for profiles)
+    Standalone,
+    /// `podman compose`, Podman's wrapper around an installed compose
+    /// provider (docker-compose or podman-compose)
+    Podman,
+    /// The standalone `podman-compose` script
+    PodmanCompose,
+}
+
+impl ComposeCli {
+    pub fn program(self) -> &'static str {
+        match self {
+            ComposeCli::Plugin => "docker",
+            ComposeCli::Standalone => "docker-compose",
+            ComposeCli::Podman => "podman",
+            ComposeCli::PodmanCompose => "podman-compose",
+        }
+    }
+
+    /// Arguments before the compose subcommand
+    fn base_args(self) -> &'static [&'static str] {
+        match self {
+            ComposeCli::Plugin | ComposeCli::Podman => &["compose"],
+            ComposeCli::Standalone | ComposeCli::PodmanCompose => &[],
+        }
+    }
+
+    /// `config` arguments printing the resolved configuration. Only v2 has
+    /// `--format`; the others print YAML, which `parse_compose_config` also
+    /// reads.
+    fn config_args(self) -> &'static [&'static str] {
+        match self {
+            ComposeCli::Plugin => &["config", "--format", "json"],
+            ComposeCli::Standalone | ComposeCli::Podman | ComposeCli::PodmanCompose => &["config"],
+        }
+    }
+
+    /// Whether `--profile` can be trusted to select services for `up` and
+    /// `stop`. podman-compose (directly or as the `podman compose`
+    /// provider) has handled profiles differently across releases, so under
+    /// Podman the profile's services are named explicitly instead.
+    pub fn handles_profiles(self) -> bool {
+        matches!(self, ComposeCli::Plugin | ComposeCli::Standalone)
+    }
+
+    fn works(self) -> bool {
+        Command::new(self.program())
+            .args(self.base_args())
+            .arg("version")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .map(|status| status.success())
+            .unwrap_or(false)
+    }
+}
+
+/// The CLI named by `override_` (the `IGRA_COMPOSE` value), else the first
+/// that `works` for the runtime: v2 then v1 for Docker, the built-in
+/// `podman compose` then podman-compose for Podman
+fn choose_compose(
+    runtime: ContainerRuntime,
+    override_: Option<&str>,
+    works: impl Fn(ComposeCli) -> bool,
+) -> std::result::Result<ComposeCli, DockerError> {
+    let choice = override_.map(|value| value.trim().to_ascii_lowercase());
+    let candidates: &[ComposeCli] = match (choice.as_deref(), runtime) {
+        (None | Some(""), ContainerRuntime::Docker) => &[ComposeCli::Plugin, ComposeCli::Standalone],
+        (None | Some(""), ContainerRuntime::Podman) => &[ComposeCli::Podman, ComposeCli::PodmanCompose],
+        (Some("v2"), _) => &[ComposeCli::Plugin],
+        (Some("v1"), _) => &[ComposeCli::Standalone],
+        (Some("podman"), _) => &[ComposeCli::Podman],
+        (Some("podman-compose"), _) => &[ComposeCli::PodmanCompose],
+        (Some(other), _) => return Err(DockerError::ComposeOverride { value: other.to_string() }),
+    };
+    candidates
+        .iter()
+        .copied()
+        .find(|cli| works(*cli))
+        .ok_or(match runtime {
+            ContainerRuntime::Docker => DockerError::ComposeMissing,
+            ContainerRuntime::Podman => DockerError::PodmanComposeMissing,
+        })
+}
+
+/// The Compose CLI to use. Detected once per process once found; while it
+/// is missing every call looks again, so installing compose doesn't need a
+/// restart of the TUI or server.
+pub fn compose_cli() -> std::result::Result<ComposeCli, DockerError> {
+    static CLI: OnceLock<ComposeCli> = OnceLock::new();
+    if let Some(cli) = CLI.get() {
+        return Ok(*cli);
+    }
+
+    let override_ = std::env::var(COMPOSE_OVERRIDE_ENV).ok();
+    let cli = choose_compose(container_runtime(), override_.as_deref(), ComposeCli::works)?;
+    Ok(*CLI.get_or_init(|| cli))
+}
 
-use crate::utils::{get_project_root, ContainerState};
-use crate::core::log_parser::{parse_service_logs, ServiceMetrics};
+/// Output of `compose config` (JSON from v2, YAML from v1)
+fn parse_compose_config(output: &str) -> Option<serde_json::Value> {
+    serde_yaml::from_str(output).ok()
+}
+
+/// Top-level `name` from `compose config`; v1 doesn't print one
+fn compose_project_name(config: &str) -> Option<String> {
+    let config = parse_compose_config(config)?;
+    config
+        .get("name")?
+        .as_str()
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+}
 
 #[derive(Debug, Clone)]
 pub struct ContainerInfo {
@@ -22,10 +259,229 @@ pub struct ContainerInfo {
     pub state: ContainerState,
     pub health: Option<String>,
     pub created: i64,
+    /// Last start time from inspect (None if never started or not inspected)
+    pub started_at: Option<DateTime<Utc>>,
+    /// Last exit time from inspect (None while it has never exited)
+    pub finished_at: Option<DateTime<Utc>>,
     pub ports: Vec<String>,
     pub metrics: ServiceMetrics,
 }
 
+impl ContainerInfo {
+    pub fn created_at(&self) -> Option<DateTime<Utc>> {
+        DateTime::from_timestamp(self.created, 0).filter(|_| self.created > 0)
+    }
+
+    /// Time since the last start, only while running. Clamped at zero so clock
+    /// skew between host and daemon can't produce a negative uptime.
+    pub fn uptime(&self, now: DateTime<Utc>) -> Option<chrono::Duration> {
+        if self.state != ContainerState::Running {
+            return None;
+        }
+        self.started_at.map(|started| (now - started).max(chrono::Duration::zero()))
+    }
+
+    /// One-line start/age summary for the detail view,
+    /// e.g. "Started: 2025-10-21 08:00 (up 3h 12m)"
+    pub fn start_summary(&self, now: DateTime<Utc>) -> String {
+        let local = |t: DateTime<Utc>| t.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string();
+
+        match (self.started_at, self.uptime(now)) {
+            (Some(started), Some(up)) => format!(
+                "Started: {} (up {})",
+                local(started),
+                crate::utils::format_duration(up.num_seconds() as u64)
+            ),
+            (Some(started), None) => match self.finished_at.filter(|finished| *finished >= started) {
+                Some(finished) => format!("Last started: {}, exited: {}", local(started), local(finished)),
+                None => format!("Last started: {}", local(started)),
+            },
+            (None, _) => match self.created_at() {
+                Some(created) if self.state == ContainerState::Running => format!("Created: {}", local(created)),
+                Some(created) => format!("Created: {} (never started)", local(created)),
+                None => "Started: unknown".to_string(),
+            },
+        }
+    }
+}
+
+/// Services that belong to a compose profile, sorted
+fn profile_services(services: &HashMap<String, ComposeServiceConfig>, profile: &str) -> Vec<String> {
+    let mut names: Vec<String> = services
+        .iter()
+        .filter(|(_, config)| config.profiles.iter().any(|p| p == profile))
+        .map(|(name, _)| name.clone())
+        .collect();
+    names.sort();
+    names
+}
+
+/// Services whose compose definition interpolates `key` (`${KEY}`,
+/// `${KEY:-default}`, `$KEY`), sorted. Containers keep the values they were
+/// created with, so these only see a new .env value once recreated.
+pub fn services_using_env(services: &HashMap<String, ComposeServiceConfig>, key: &str) -> Vec<String> {
+    let Ok(re) = regex::Regex::new(&format!(r"\$\{{{key}[}}:?+-]|\${key}\b", key = regex::escape(key))) else {
+        return Vec::new();
+    };
+
+    let mut names: Vec<String> = services
+        .iter()
+        .filter(|(_, config)| {
+            config
+                .environment
+                .values()
+                .chain(&config.volumes)
+                .chain(&config.ports)
+                .chain(config.image.iter())
+                .chain(config.command.iter())
+                .chain(config.entrypoint.iter())
+                .any(|text| re.is_match(text))
+        })
+        .map(|(name, _)| name.clone())
+        .collect();
+    names.sort();
+    names
+}
+
+/// `(service, variable, required)` for every `${VAR}`, `${VAR:?..}` or `$VAR`
+/// a service interpolates while `is_set(VAR)` is false, sorted. `required` is
+/// true for `${VAR:?..}` / `${VAR?..}`, which compose refuses to run without;
+/// the others it only warns about and leaves blank. References with a default
+/// (`${VAR:-x}`) or alternate value (`${VAR:+x}`) are fine unset, and `$$` is
+/// a literal dollar sign.
+fn unset_env_refs(
+    services: &HashMap<String, ComposeServiceConfig>,
+    is_set: impl Fn(&str) -> bool,
+) -> Vec<(String, String, bool)> {
+    static REF_RE: OnceLock<regex::Regex> = OnceLock::new();
+    let re = REF_RE.get_or_init(|| {
+        regex::Regex::new(r"\$\$|\$\{([A-Za-z_][A-Za-z0-9_]*)(:?[-+?])?|\$([A-Za-z_][A-Za-z0-9_]*)").unwrap()
+    });
+
+    let mut unset: Vec<(String, String, bool)> = Vec::new();
+    for (name, config) in services {
+        let texts = config
+            .environment
+            .values()
+            .chain(&config.volumes)
+            .chain(&config.ports)
+            .chain(config.image.iter())
+            .chain(config.command.iter())
+            .chain(config.entrypoint.iter());
+        for text in texts {
+            for caps in re.captures_iter(text) {
+                let Some(var) = caps.get(1).or_else(|| caps.get(3)) else {
+                    continue;
+                };
+                let required = caps.get(2).is_some_and(|op| op.as_str().ends_with('?'));
+                let has_fallback = caps.get(2).is_some() && !required;
+                if !has_fallback && !is_set(var.as_str()) {
+                    unset.push((name.clone(), var.as_str().to_string(), required));
+                }
+            }
+        }
+    }
+    unset.sort();
+    unset.dedup();
+    unset
+}
+
+/// Something `compose_problems` found in the compose file
+#[derive(Debug, Clone, PartialEq)]
+struct ComposeProblem {
+    service: Option<String>,
+    message: String,
+    /// `up` would fail on it; otherwise compose warns and carries on
+    blocking: bool,
+}
+
+impl ComposeProblem {
+    /// "service: message"
+    fn describe(&self) -> String {
+        match &self.service {
+            Some(service) => format!("{}: {}", service, self.message),
+            None => self.message.clone(),
+        }
+    }
+}
+
+/// Make a `docker compose config` error line readable: drop the log prefix
+/// and pull out the service when compose names one
+/// ("services.viaduct.environment.[]: required variable ..." -> viaduct, "required variable ...")
+fn compose_config_problem(line: &str) -> Option<(Option<String>, String)> {
+    let line = strip_ansi_codes(line.trim());
+    let line = line
+        .strip_prefix("level=error msg=")
+        .map(|rest| rest.trim_matches('"').to_string())
+        .unwrap_or(line);
+    let line = line.trim();
+    if line.is_empty() {
+        return None;
+    }
+
+    let located = line.find("services.").and_then(|start| {
+        let path_end = line[start..].find(": ")? + start;
+        let service = line[start + "services.".len()..path_end].split('.').next()?;
+        Some((Some(service.to_string()), line[path_end + 2..].to_string()))
+    });
+    Some(located.unwrap_or_else(|| (None, line.to_string())))
+}
+
+/// Parse an inspect timestamp. Docker reports "0001-01-01T00:00:00Z" for
+/// containers that have never started; treat that as absent.
+fn parse_docker_time(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|t| t.with_timezone(&Utc))
+        .filter(|t| t.timestamp() > 0)
+}
+
+/// `inspect --format` for `parse_start_times`
+const START_TIMES_FORMAT: &str = "{{.Id}} {{.State.StartedAt}} {{.State.FinishedAt}}";
+
+/// (started, finished) of one container
+type StartTimes = (Option<DateTime<Utc>>, Option<DateTime<Utc>>);
+
+/// Start times by container id from `START_TIMES_FORMAT` lines
+fn parse_start_times(output: &str) -> HashMap<String, StartTimes> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let id = fields.next()?;
+            let started = fields.next().and_then(parse_docker_time);
+            let finished = fields.next().and_then(parse_docker_time);
+            Some((id.to_string(), (started, finished)))
+        })
+        .collect()
+}
+
+/// Time since `started_at` (inspect's StartedAt), e.g. "3h 12m"; "N/A"
+/// unless running. Clamped at zero like `ContainerInfo::uptime`.
+fn running_uptime(running: bool, started_at: Option<&str>, now: DateTime<Utc>) -> String {
+    match started_at.and_then(parse_docker_time) {
+        Some(started) if running => {
+            let up = (now - started).max(chrono::Duration::zero());
+            crate::utils::format_duration(up.num_seconds() as u64)
+        }
+        _ => "N/A".to_string(),
+    }
+}
+
+/// Result of a command executed inside a service container
+#[derive(Debug, Clone)]
+pub struct ExecOutput {
+    pub stdout: String,
+    pub stderr: String,
+    pub exit_code: i32,
+}
+
+impl ExecOutput {
+    pub fn success(&self) -> bool {
+        self.exit_code == 0
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ContainerStats {
     pub cpu_percent: f64,
@@ -35,6 +491,57 @@ pub struct ContainerStats {
     pub network_tx: u64,
     pub container_size: u64,    // Container filesystem size in bytes
     pub volume_size: u64,       // Total volume data size in bytes
+    pub block_read: Option<u64>,  // Cumulative bytes; None if the storage driver doesn't report it
+    pub block_write: Option<u64>,
+    pub block_read_rate: Option<f64>,  // Bytes/sec since the previous sample
+    pub block_write_rate: Option<f64>,
+    pub sampled_at: std::time::Instant,
+}
+
+impl ContainerStats {
+    /// Fill in block I/O rates from the previous sample of the same container.
+    /// Left as None when a counter went backwards (container restarted).
+    pub fn set_block_rates(&mut self, previous: &ContainerStats) {
+        let elapsed = self.sampled_at.duration_since(previous.sampled_at).as_secs_f64();
+        if elapsed <= 0.0 {
+            return;
+        }
+        let rate = |now: Option<u64>, before: Option<u64>| {
+            now?.checked_sub(before?).map(|delta| delta as f64 / elapsed)
+        };
+        self.block_read_rate = rate(self.block_read, previous.block_read);
+        self.block_write_rate = rate(self.block_write, previous.block_write);
+    }
+}
+
+/// Sum `blkio_stats.io_service_bytes_recursive` entries into (read, write).
+/// cgroup v1 reports "Read"/"Write", v2 "read"/"write"; drivers that report
+/// nothing give None rather than 0.
+fn block_io_totals<'a>(entries: impl IntoIterator<Item = (&'a str, u64)>) -> (Option<u64>, Option<u64>) {
+    let (mut read, mut write) = (None, None);
+    for (op, value) in entries {
+        if op.eq_ignore_ascii_case("read") {
+            *read.get_or_insert(0) += value;
+        } else if op.eq_ignore_ascii_case("write") {
+            *write.get_or_insert(0) += value;
+        }
+    }
+    (read, write)
+}
+
+/// (rx, tx) bytes. Docker reports the container's `eth0`; Podman keys
+/// interfaces by its own names (rootless networking has no `eth0`), so
+/// without one every interface is summed.
+fn network_totals(networks: Option<&HashMap<String, NetworkStats>>) -> (u64, u64) {
+    let Some(networks) = networks else {
+        return (0, 0);
+    };
+    match networks.get("eth0") {
+        Some(net) => (net.rx_bytes, net.tx_bytes),
+        None => networks
+            .values()
+            .fold((0, 0), |(rx, tx), net| (rx + net.rx_bytes, tx + net.tx_bytes)),
+    }
 }
 
 /// Service configuration from docker-compose.yml
@@ -76,111 +583,649 @@ pub struct ServiceConfigComparison {
     pub config_drift: Vec<String>,  // Human-readable drift descriptions
 }
 
+/// Compose file / project overrides (`--compose-file`, `--project`)
+#[derive(Debug, Clone, Default)]
+pub struct ComposeTarget {
+    pub compose_file: Option<PathBuf>,
+    pub project: Option<String>,
+}
+
+impl ComposeTarget {
+    pub fn validate(&self) -> Result<()> {
+        if let Some(ref file) = self.compose_file {
+            if !file.is_file() {
+                return Err(Error::invalid_input(format!("Compose file not found: {}", file.display())));
+            }
+        }
+        if let Some(ref project) = self.project {
+            if project.trim().is_empty() {
+                return Err(Error::invalid_input("Compose project name cannot be empty"));
+            }
+        }
+        Ok(())
+    }
+}
+
+static COMPOSE_TARGET: OnceLock<ComposeTarget> = OnceLock::new();
+
+/// Directory of the `--compose-file` set with `DockerManager::configure`, if any
+pub fn compose_target_root() -> Option<PathBuf> {
+    let file = COMPOSE_TARGET.get()?.compose_file.as_ref()?;
+    file.canonicalize().ok()?.parent().map(Path::to_path_buf)
+}
+
+/// Docker daemon unreachable, classified so the user gets a concrete fix
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum DockerError {
+    #[error("Permission denied on Docker socket {socket}")]
+    PermissionDenied { socket: String },
+    #[error("Docker daemon is not running (no daemon at {socket})")]
+    DaemonNotRunning { socket: String },
+    #[error("Docker Compose not found (neither 'docker compose' nor 'docker-compose' works)")]
+    ComposeMissing,
+    #[error("Podman Compose not found (neither 'podman compose' nor 'podman-compose' works)")]
+    PodmanComposeMissing,
+    #[error("IGRA_COMPOSE must be v1, v2, podman or podman-compose, not '{value}'")]
+    ComposeOverride { value: String },
+    #[error("Podman API service is not running (no socket at {socket})")]
+    PodmanNotRunning { socket: String },
+    #[error("{program} backend requested but '{program}' is not installed")]
+    RuntimeMissing { program: String },
+    #[error("Failed to connect to Docker at {endpoint}: {message}")]
+    Connect { endpoint: String, message: String },
+    #[error("SSH connection to {destination} failed: {message}")]
+    Ssh { destination: String, message: String },
+}
+
+impl DockerError {
+    /// What to do about it
+    pub fn hint(&self) -> String {
+        match self {
+            DockerError::PermissionDenied { .. } => {
+                "Add your user to the docker group (sudo usermod -aG docker $USER), \
+                 then log out and back in - or run igra-cli with sudo"
+                    .to_string()
+            }
+            DockerError::DaemonNotRunning { .. } => {
+                "Start Docker (sudo systemctl start docker, or systemctl --user start docker \
+                 for rootless), or point DOCKER_HOST at the right socket"
+                    .to_string()
+            }
+            DockerError::ComposeMissing => {
+                format!(
+                    "Install the Compose v2 plugin (e.g. apt install docker-compose-plugin), or \
+                     docker-compose 1.28+. If {} is set, the version it names must be installed.",
+                    COMPOSE_OVERRIDE_ENV
+                )
+            }
+            DockerError::PodmanComposeMissing => {
+                format!(
+                    "Install podman-compose (e.g. apt install podman-compose or pip install \
+                     podman-compose), which 'podman compose' also uses. If {} is set, the \
+                     version it names must be installed.",
+                    COMPOSE_OVERRIDE_ENV
+                )
+            }
+            DockerError::ComposeOverride { .. } => {
+                format!(
+                    "Set {}=v2 for 'docker compose' or {}=v1 for 'docker-compose' (podman or \
+                     podman-compose under Podman), or unset it to detect",
+                    COMPOSE_OVERRIDE_ENV, COMPOSE_OVERRIDE_ENV
+                )
+            }
+            DockerError::PodmanNotRunning { .. } => {
+                "Enable the Podman API socket (systemctl --user enable --now podman.socket, \
+                 or sudo systemctl enable --now podman.socket for rootful Podman)"
+                    .to_string()
+            }
+            DockerError::RuntimeMissing { program } => {
+                let other = if program == "podman" { "docker" } else { "podman" };
+                format!(
+                    "Install {}, or drop --backend to detect the runtime (--backend {} if \
+                     that is what this host runs)",
+                    program, other
+                )
+            }
+            DockerError::Connect { .. } => {
+                "Check that DOCKER_HOST is correct and the daemon is reachable \
+                 (for TLS, DOCKER_CERT_PATH must hold ca.pem, cert.pem and key.pem)"
+                    .to_string()
+            }
+            DockerError::Ssh { destination, .. } => {
+                format!(
+                    "Check that 'ssh {}' logs in without a prompt (key loaded in ssh-agent, \
+                     host already in known_hosts) and that the remote user can use {} \
+                     (member of the docker group)",
+                    destination, REMOTE_DOCKER_SOCKET
+                )
+            }
+        }
+    }
+}
+
+/// Where the Docker daemon should be: DOCKER_HOST, the system socket, or
+/// the rootless socket under the user's runtime dir
+#[derive(Debug, Clone, PartialEq)]
+enum DockerEndpoint {
+    Unix(PathBuf),
+    Tcp(String),
+    /// `ssh://[user@]host[:port]`, without the scheme
+    Ssh(String),
+}
+
+const SYSTEM_DOCKER_SOCKET: &str = "/var/run/docker.sock";
+/// Rootful Podman's API socket (podman.socket unit)
+const SYSTEM_PODMAN_SOCKET: &str = "/run/podman/podman.sock";
+/// Daemon socket on the far side of an `ssh://` DOCKER_HOST
+const REMOTE_DOCKER_SOCKET: &str = "/var/run/docker.sock";
+/// How long to wait for the SSH tunnel to come up
+const SSH_TUNNEL_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(15);
+
+fn resolve_docker_endpoint(
+    runtime: ContainerRuntime,
+    docker_host: Option<&str>,
+    system_socket: &Path,
+    runtime_dir: Option<&Path>,
+) -> DockerEndpoint {
+    if let Some(host) = docker_host.map(str::trim).filter(|h| !h.is_empty()) {
+        if let Some(path) = host.strip_prefix("unix://") {
+            return DockerEndpoint::Unix(PathBuf::from(path));
+        }
+        if let Some(destination) = host.strip_prefix("ssh://") {
+            return DockerEndpoint::Ssh(destination.trim_end_matches('/').to_string());
+        }
+        return DockerEndpoint::Tcp(host.to_string());
+    }
+
+    if runtime == ContainerRuntime::Podman {
+        let sockets = podman_sockets(runtime_dir);
+        let socket = sockets.iter().find(|socket| socket.exists()).unwrap_or(&sockets[0]);
+        return DockerEndpoint::Unix(socket.clone());
+    }
+
+    let system = system_socket.to_path_buf();
+    if system.exists() {
+        return DockerEndpoint::Unix(system);
+    }
+
+    // Rootless Docker: $XDG_RUNTIME_DIR/docker.sock
+    if let Some(rootless) = runtime_dir.map(|dir| dir.join("docker.sock")) {
+        if rootless.exists() {
+            return DockerEndpoint::Unix(rootless);
+        }
+    }
+
+    DockerEndpoint::Unix(system)
+}
+
+/// User runtime dir, falling back to /run/user/<uid> when XDG_RUNTIME_DIR is unset
+fn user_runtime_dir() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os("XDG_RUNTIME_DIR") {
+        return Some(PathBuf::from(dir));
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        let uid = std::fs::metadata("/proc/self").ok()?.uid();
+        Some(PathBuf::from(format!("/run/user/{}", uid)))
+    }
+    #[cfg(not(unix))]
+    None
+}
+
+/// Connect to the daemon, probing unix sockets up front so permission and
+/// not-running problems surface here instead of on the first API call
+pub async fn connect_docker() -> std::result::Result<Docker, DockerError> {
+    let docker_host = std::env::var("DOCKER_HOST").ok();
+    let runtime = container_runtime();
+    let endpoint = resolve_docker_endpoint(
+        runtime,
+        docker_host.as_deref(),
+        Path::new(SYSTEM_DOCKER_SOCKET),
+        user_runtime_dir().as_deref(),
+    );
+
+    match endpoint {
+        DockerEndpoint::Unix(path) => {
+            let socket = path.display().to_string();
+
+            #[cfg(unix)]
+            if let Err(e) = std::os::unix::net::UnixStream::connect(&path) {
+                return Err(match e.kind() {
+                    std::io::ErrorKind::PermissionDenied => DockerError::PermissionDenied { socket },
+                    std::io::ErrorKind::NotFound | std::io::ErrorKind::ConnectionRefused => match runtime {
+                        ContainerRuntime::Docker => DockerError::DaemonNotRunning { socket },
+                        ContainerRuntime::Podman => DockerError::PodmanNotRunning { socket },
+                    },
+                    _ => DockerError::Connect { endpoint: socket, message: e.to_string() },
+                });
+            }
+
+            Docker::connect_with_unix(&socket, 120, bollard::API_DEFAULT_VERSION)
+                .map_err(|e| DockerError::Connect { endpoint: socket, message: e.to_string() })
+        }
+        DockerEndpoint::Tcp(host) if tls_verify_enabled() => connect_tls(host),
+        DockerEndpoint::Tcp(host) => {
+            Docker::connect_with_http(&host, 120, bollard::API_DEFAULT_VERSION)
+                .map_err(|e| DockerError::Connect { endpoint: host, message: e.to_string() })
+        }
+        DockerEndpoint::Ssh(destination) => {
+            let socket = ssh_tunnel(&destination).await?;
+            let socket = socket.display().to_string();
+            Docker::connect_with_unix(&socket, 120, bollard::API_DEFAULT_VERSION)
+                .map_err(|e| DockerError::Connect { endpoint: format!("ssh://{}", destination), message: e.to_string() })
+        }
+    }
+}
+
+/// DOCKER_TLS_VERIFY set to anything non-empty, as the docker CLI treats it
+fn tls_verify_enabled() -> bool {
+    std::env::var("DOCKER_TLS_VERIFY").map(|v| !v.is_empty()).unwrap_or(false)
+}
+
+/// TCP with client certificates from DOCKER_CERT_PATH (default ~/.docker)
+fn connect_tls(host: String) -> std::result::Result<Docker, DockerError> {
+    let cert_dir = std::env::var_os("DOCKER_CERT_PATH")
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".docker")))
+        .unwrap_or_default();
+
+    let [ca, cert, key] = ["ca.pem", "cert.pem", "key.pem"].map(|name| cert_dir.join(name));
+    if let Some(missing) = [&ca, &cert, &key].into_iter().find(|path| !path.exists()) {
+        return Err(DockerError::Connect {
+            endpoint: host,
+            message: format!("DOCKER_TLS_VERIFY is set but {} does not exist", missing.display()),
+        });
+    }
+
+    Docker::connect_with_ssl(&host, &key, &cert, &ca, 120, bollard::API_DEFAULT_VERSION)
+        .map_err(|e| DockerError::Connect { endpoint: host, message: format!("TLS: {}", e) })
+}
+
+/// bollard has no SSH transport, so forward a local unix socket to the
+/// remote daemon socket with `ssh -L`. An open tunnel is reused for the rest
+/// of the process; a failed one isn't remembered, and one whose socket has
+/// gone (ssh died) is reopened, so the Docker retry loop can recover.
+async fn ssh_tunnel(destination: &str) -> std::result::Result<PathBuf, DockerError> {
+    static TUNNEL: tokio::sync::Mutex<Option<SshTunnel>> = tokio::sync::Mutex::const_new(None);
+    // Held while opening, so concurrent connects share one tunnel
+    let mut tunnel = TUNNEL.lock().await;
+    if let Some(open) = tunnel.as_ref().filter(|open| open.socket.exists()) {
+        return Ok(open.socket.clone());
+    }
+
+    let open = open_ssh_tunnel(destination).await?;
+    let socket = open.socket.clone();
+    *tunnel = Some(open);
+    Ok(socket)
+}
+
+/// Local end of an SSH tunnel
+struct SshTunnel {
+    socket: PathBuf,
+    /// Private (0700) directory holding the socket, removed with the tunnel
+    _dir: tempfile::TempDir,
+}
+
+/// `[user@]host[:port]` -> (`[user@]host`, port)
+fn split_ssh_destination(destination: &str) -> (&str, Option<&str>) {
+    match destination.rsplit_once(':') {
+        Some((target, port)) if !port.is_empty() && port.chars().all(|c| c.is_ascii_digit()) => (target, Some(port)),
+        _ => (destination, None),
+    }
+}
+
+async fn open_ssh_tunnel(destination: &str) -> std::result::Result<SshTunnel, DockerError> {
+    let ssh_error = |message: String| DockerError::Ssh { destination: destination.to_string(), message };
+
+    // Only this user can reach the forwarded daemon: the socket lives in a
+    // fresh private directory, under the user's runtime dir when there is one
+    let parent = user_runtime_dir()
+        .filter(|dir| dir.is_dir())
+        .unwrap_or_else(std::env::temp_dir);
+    let dir = tempfile::Builder::new()
+        .prefix("igra-docker-")
+        .tempdir_in(&parent)
+        .map_err(|e| ssh_error(format!("could not create a socket directory in {}: {}", parent.display(), e)))?;
+    let socket = dir.path().join("docker.sock");
+
+    let (target, port) = split_ssh_destination(destination);
+    let mut ssh_args = vec![
+        "-nNT".to_string(),
+        // Never prompt: a password prompt would hang behind the TUI
+        "-o".to_string(), "BatchMode=yes".to_string(),
+        "-o".to_string(), "ExitOnForwardFailure=yes".to_string(),
+        "-L".to_string(), format!("{}:{}", socket.display(), REMOTE_DOCKER_SOCKET),
+    ];
+    if let Some(port) = port {
+        ssh_args.extend(["-p".to_string(), port.to_string()]);
+    }
+    ssh_args.push(target.to_string());
+
+    // The wrapper kills ssh once our end of its stdin closes - on exit or crash
+    // alike - so the tunnel never outlives this process
+    let script = r#"ssh "$@" & pid=$!; (read _; kill $pid) 2>/dev/null & wait $pid"#;
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(script)
+        .arg("sh")
+        .args(&ssh_args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| ssh_error(format!("could not run ssh: {}", e)))?;
+
+    let deadline = std::time::Instant::now() + SSH_TUNNEL_TIMEOUT;
+    loop {
+        if socket.exists() {
+            // Held open (never written) for the life of the process
+            std::mem::forget(child.stdin.take());
+            return Ok(SshTunnel { socket, _dir: dir });
+        }
+
+        if let Ok(Some(_)) = child.try_wait() {
+            let mut stderr = String::new();
+            if let Some(mut pipe) = child.stderr.take() {
+                use std::io::Read;
+                let _ = pipe.read_to_string(&mut stderr);
+            }
+            let message = stderr.lines().last().unwrap_or("ssh exited").trim().to_string();
+            return Err(ssh_error(message));
+        }
+
+        if std::time::Instant::now() >= deadline {
+            // Dropping stdin makes the wrapper kill ssh
+            drop(child.stdin.take());
+            let _ = child.wait();
+            return Err(ssh_error(format!("timed out after {}s", SSH_TUNNEL_TIMEOUT.as_secs())));
+        }
+
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+}
+
+/// DOCKER_HOST when it points at another machine (`ssh://`, or `tcp://` to a
+/// non-loopback address)
+pub fn remote_docker_host() -> Option<String> {
+    let host = std::env::var("DOCKER_HOST").ok()?;
+    let host = host.trim();
+    is_remote_docker_host(host).then(|| host.to_string())
+}
+
+fn is_remote_docker_host(host: &str) -> bool {
+    if host.starts_with("ssh://") {
+        return true;
+    }
+    let Some(address) = ["tcp://", "http://", "https://"].iter().find_map(|scheme| host.strip_prefix(scheme)) else {
+        return false;
+    };
+    let authority = address.split('/').next().unwrap_or_default();
+    let name = authority.rsplit_once(':').map(|(name, _)| name).unwrap_or(authority);
+    !matches!(name.trim_matches(|c| c == '[' || c == ']'), "" | "localhost" | "127.0.0.1" | "::1")
+}
+
+/// Shown when the daemon is remote: containers are managed there, but
+/// file-based features still read and write this machine
+pub fn remote_docker_warning() -> Option<String> {
+    remote_docker_host().map(|host| {
+        format!(
+            "Docker daemon is remote ({}): .env, docker-compose.yml, acme.json and system \
+             resources are read from this machine, not the remote host",
+            host
+        )
+    })
+}
+
+/// Docker engine version, None if the daemon can't be reached
+pub async fn engine_version() -> Option<String> {
+    let docker = connect_docker().await.ok()?;
+    docker.version().await.ok()?.version
+}
+
+/// `compose version --short`, None if no Compose CLI works
+pub fn compose_version() -> Option<String> {
+    let cli = compose_cli().ok()?;
+    let output = Command::new(cli.program())
+        .args(cli.base_args())
+        .args(["version", "--short"])
+        .stderr(Stdio::null())
+        .output()
+        .ok()
+        .filter(|out| out.status.success())?;
+    let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    match cli {
+        _ if version.is_empty() => None,
+        ComposeCli::Plugin => Some(version),
+        ComposeCli::Standalone => Some(format!("{} (docker-compose)", version)),
+        ComposeCli::Podman => Some(format!("{} (podman compose)", version)),
+        ComposeCli::PodmanCompose => Some(format!("{} (podman-compose)", version)),
+    }
+}
+
+/// Compose project name: `--project`, else one per network
+fn project_name(project: Option<&str>, network: Network) -> String {
+    project.map(str::to_string).unwrap_or_else(|| format!("igra-orchestra-{}", network))
+}
+
+/// Where the project lives, as `--compose-file`/`--project`/`--env-file` or
+/// the default layout say. Only reads files; runs no Docker or compose.
+struct ProjectLocation {
+    project_root: PathBuf,
+    compose_file: PathBuf,
+    project: Option<String>,
+    network: Network,
+    /// .env compose interpolates from (`--env-file`, else the project's)
+    env_file: PathBuf,
+}
+
+impl ProjectLocation {
+    fn find() -> Result<Self> {
+        let target = COMPOSE_TARGET.get().cloned().unwrap_or_default();
+        let (project_root, compose_file) = match target.compose_file {
+            Some(file) => {
+                let file = file.canonicalize()
+                    .context(format!("Compose file not found: {}", file.display()))?;
+                let root = file.parent()
+                    .map(Path::to_path_buf)
+                    .context("Compose file has no parent directory")?;
+                (root, file)
+            }
+            None => {
+                let root = get_project_root()?;
+                let file = root.join(DEFAULT_COMPOSE_FILE);
+                (root, file)
+            }
+        };
+
+        if !compose_file.exists() {
+            return Err(anyhow!(
+                "docker-compose.yml not found at {}",
+                compose_file.display()
+            )
+            .into());
+        }
+
+        // Read network from .env file (missing = testnet, unrecognized = error)
+        let env_file = crate::core::ConfigManager::env_file_in(&project_root);
+        let network = Network::from_env_file(&env_file)?;
+
+        Ok(Self { project_root, compose_file, project: target.project, network, env_file })
+    }
+
+    fn project_name(&self) -> String {
+        project_name(self.project.as_deref(), self.network)
+    }
+}
+
+/// The project's containers straight from the Docker API, without the
+/// compose CLI or the enrichment of `list_containers` (`status --oneline`)
+pub struct ProjectContainers {
+    docker: Docker,
+    project: String,
+    pub env_file: PathBuf,
+}
+
+impl ProjectContainers {
+    /// Find the project and connect to Docker. A remote DOCKER_HOST may open
+    /// an SSH tunnel first.
+    pub async fn connect() -> Result<Self> {
+        let location = ProjectLocation::find()?;
+        Ok(Self { docker: connect_docker().await?, project: location.project_name(), env_file: location.env_file })
+    }
+
+    /// Name and state of every project container, from one API call
+    pub async fn states(&self) -> Result<Vec<(String, ContainerState)>> {
+        let mut filters = HashMap::new();
+        filters.insert("label".to_string(), vec![format!("{}={}", COMPOSE_PROJECT_LABEL, self.project)]);
+        let options = Some(ListContainersOptions { all: true, filters, ..Default::default() });
+
+        Ok(self
+            .docker
+            .list_containers(options)
+            .await?
+            .into_iter()
+            .map(|c| {
+                let name = c.names.and_then(|names| names.into_iter().next()).unwrap_or_default();
+                let state = c.state.as_deref().unwrap_or("unknown").into();
+                (name.trim_start_matches('/').to_string(), state)
+            })
+            .collect())
+    }
+}
+
 #[derive(Clone)]
 pub struct DockerManager {
     docker: Docker,
     project_root: PathBuf,
     compose_file: PathBuf,
-    network: String,
+    project: Option<String>,
+    network: Network,
+    compose: ComposeCli,
+    /// .env compose interpolates from (`--env-file`, else the project's)
+    env_file: PathBuf,
 }
 
 impl DockerManager {
-    /// Create a new Docker manager (synchronous version for App initialization)
-    pub fn new_sync() -> Result<Self> {
-        let docker = Docker::connect_with_local_defaults()
-            .context("Failed to connect to Docker daemon. Is Docker running?")?;
+    /// Use a specific compose file / project for every manager created after
+    /// this. Call once at startup; validates the compose file exists.
+    pub fn configure(target: ComposeTarget) -> Result<()> {
+        target.validate()?;
+        COMPOSE_TARGET
+            .set(target)
+            .map_err(|_| anyhow!("Compose target already configured").into())
+    }
 
-        let project_root = get_project_root()?;
-        let compose_file = project_root.join("docker-compose.yml");
+    /// Use `runtime` instead of detecting one (`--backend`). Call once at
+    /// startup, before any manager is created; fails if its CLI isn't
+    /// installed.
+    pub fn use_runtime(runtime: ContainerRuntime) -> Result<()> {
+        if !runtime.installed() {
+            return Err(DockerError::RuntimeMissing { program: runtime.program().to_string() }.into());
+        }
+        RUNTIME
+            .set(runtime)
+            .map_err(|_| anyhow!("Container runtime already chosen").into())
+    }
 
-        if !compose_file.exists() {
-            return Err(anyhow!(
-                "docker-compose.yml not found at {}",
-                compose_file.display()
-            ));
-        }
-
-        // Read network from .env file
-        let env_file = project_root.join(".env");
-        let network = if env_file.exists() {
-            std::fs::read_to_string(&env_file)?
-                .lines()
-                .find(|line| line.trim().starts_with("NETWORK="))
-                .and_then(|line| line.split('=').nth(1))
-                .map(|s| s.trim().to_string())
-                .unwrap_or_else(|| "testnet".to_string())
-        } else {
-            "testnet".to_string()
-        };
+    /// Create a new Docker manager
+    pub async fn new() -> Result<Self> {
+        let docker = connect_docker().await?;
+        let compose = compose_cli()?;
+        let ProjectLocation { project_root, compose_file, project, network, env_file } = ProjectLocation::find()?;
 
         Ok(Self {
             docker,
             project_root,
             compose_file,
+            project,
             network,
+            compose,
+            env_file,
         })
     }
 
-    /// Create a new Docker manager (async wrapper for compatibility)
-    pub async fn new() -> Result<Self> {
-        Self::new_sync()
-    }
-
     /// Get project root directory
     pub fn project_root(&self) -> &Path {
         &self.project_root
     }
 
-    /// Get network name (testnet/mainnet)
-    pub fn network(&self) -> &str {
-        &self.network
+    /// Compose project name used for container labels
+    pub fn project_name(&self) -> String {
+        project_name(self.project.as_deref(), self.network)
     }
 
-    /// List all IGRA Orchestra containers
-    pub async fn list_containers(&self) -> Result<Vec<ContainerInfo>> {
-        let mut filters = HashMap::new();
-        filters.insert(
-            "label".to_string(),
-            vec![format!("com.docker.compose.project=igra-orchestra-{}", self.network)],
-        );
+    /// `[compose] -f <file> [-p <project>] [--env-file <file>]` for
+    /// `compose_program()` - every compose call goes through this so they all
+    /// act on the same project and environment
+    fn compose_base_args(&self) -> Vec<OsString> {
+        let mut args: Vec<OsString> = self.compose.base_args().iter().map(OsString::from).collect();
+        args.extend(["-f".into(), self.compose_file.clone().into_os_string()]);
+        // Compose reads the project's .env by itself; only a different one is passed
+        if crate::core::ConfigManager::env_file_override().is_some() {
+            args.extend(["--env-file".into(), self.env_file.clone().into_os_string()]);
+        }
+        if let Some(ref project) = self.project {
+            args.push("-p".into());
+            args.push(project.into());
+        }
+        args
+    }
 
-        let options = Some(ListContainersOptions {
-            all: true,
-            size: true,  // Enable size information
-            filters,
-            ..Default::default()
-        });
+    /// `docker` or `docker-compose`, whichever runs compose commands
+    fn compose_program(&self) -> &'static str {
+        self.compose.program()
+    }
 
-        let containers = self.docker.list_containers(options).await?;
+    /// Network the project is configured for
+    pub fn network(&self) -> Network {
+        self.network
+    }
 
-        let mut container_infos: Vec<ContainerInfo> = containers
+    /// List all IGRA Orchestra containers
+    pub async fn list_containers(&self) -> Result<Vec<ContainerInfo>> {
+        let mut container_infos: Vec<ContainerInfo> = self
+            .list_summaries(true, false)
+            .await?
             .into_iter()
             .map(|c| self.container_summary_to_info(c))
             .collect();
 
-        // Enrich with metrics by parsing logs (in parallel for performance)
-        // Collect running container names
+        // Docker lists newest first, so a recreated container would move and the
+        // TUI selection jump between refreshes; keep a stable order instead
+        container_infos.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let ids: Vec<String> = container_infos.iter().map(|c| c.id.clone()).collect();
+        let start_times = self.start_times(&ids).await;
+        for container in container_infos.iter_mut() {
+            if let Some(&(started_at, finished_at)) = start_times.get(&container.id) {
+                container.started_at = started_at;
+                container.finished_at = finished_at;
+            }
+        }
+
+        // Enrich with metrics by parsing logs (bounded parallelism: each fetch is a compose subprocess)
         let running_names: Vec<String> = container_infos
             .iter()
             .filter(|c| c.state == ContainerState::Running)
             .map(|c| c.name.clone())
             .collect();
 
-        // Fetch logs in parallel (much faster than sequential)
-        use futures::future::join_all;
-        let log_futures = running_names.iter().map(|name| {
-            let name = name.clone();
-            async move {
-                // Fetch last 20 lines - enough for parsing, faster than 50
-                self.get_logs(&name, Some(20)).await.ok().map(|logs| (name.clone(), logs))
-            }
-        });
-
-        let metrics_results = join_all(log_futures).await;
+        use futures::stream::{self, StreamExt};
+        let per_container: Vec<(String, Option<String>)> = stream::iter(running_names)
+            .map(|name| async move {
+                // Fetch last 20 lines - enough for parsing, faster than 50.
+                // A container that vanished since the list call just yields None.
+                let logs = self.get_logs(&name, Some(20), false).await;
+                (name, logs.ok())
+            })
+            .buffer_unordered(MAX_CONCURRENT_PER_CONTAINER)
+            .collect()
+            .await;
 
         // Parse logs and apply metrics to containers
-        for result in metrics_results.into_iter().flatten() {
-            let (name, logs) = result;
+        for (name, logs) in per_container {
+            let Some(logs) = logs else { continue };
             let mut metrics = parse_service_logs(&name, &logs);
 
             // For execution-layer, also fetch Reth Prometheus metrics
@@ -208,21 +1253,119 @@ impl DockerManager {
         Ok(container_infos)
     }
 
+    /// Start and exit times of the given containers from a single `inspect`
+    /// of them all, running or not. The list endpoint doesn't carry them.
+    /// Ids that vanished since the list call are left out (inspect still
+    /// prints the others, exiting non-zero); on any other failure the times
+    /// stay unknown.
+    async fn start_times(&self, ids: &[String]) -> HashMap<String, (Option<DateTime<Utc>>, Option<DateTime<Utc>>)> {
+        if ids.is_empty() {
+            return HashMap::new();
+        }
+        let output = tokio::process::Command::new(runtime_program())
+            .args(["inspect", "--type", "container", "--format", START_TIMES_FORMAT])
+            .args(ids)
+            .output()
+            .await;
+        match output {
+            Ok(output) => parse_start_times(&String::from_utf8_lossy(&output.stdout)),
+            Err(_) => HashMap::new(),
+        }
+    }
+
+    /// One list call for the project's containers. `size` asks Docker for
+    /// SizeRootFs, which is slow, so only the stats path requests it.
+    async fn list_summaries(&self, all: bool, size: bool) -> Result<Vec<ContainerSummary>> {
+        self.list_project_summaries(&self.project_name(), all, size).await
+    }
+
+    async fn list_project_summaries(&self, project: &str, all: bool, size: bool) -> Result<Vec<ContainerSummary>> {
+        let mut filters = HashMap::new();
+        filters.insert(
+            "label".to_string(),
+            vec![format!("{}={}", COMPOSE_PROJECT_LABEL, project)],
+        );
+
+        let options = Some(ListContainersOptions {
+            all,
+            size,
+            filters,
+            ..Default::default()
+        });
+
+        Ok(self.docker.list_containers(options).await?)
+    }
+
+    /// Names of the running project containers: one list call, without the
+    /// log and inspect enrichment of `list_containers`
+    pub async fn running_container_names(&self) -> Result<Vec<String>> {
+        Ok(self
+            .list_summaries(false, false)
+            .await?
+            .into_iter()
+            .filter_map(|c| c.names.and_then(|names| names.into_iter().next()))
+            .map(|n| n.trim_start_matches('/').to_string())
+            .collect())
+    }
+
     /// Get container info by name
     pub async fn get_container(&self, name: &str) -> Result<Option<ContainerInfo>> {
         let containers = self.list_containers().await?;
         Ok(containers.into_iter().find(|c| c.name == name))
     }
 
-    /// Get container stats
-    pub async fn get_container_stats(&self, name: &str) -> Result<Option<ContainerStats>> {
-        let container_id = match self.get_container(name).await? {
-            Some(info) => info.id,
-            None => return Ok(None),
-        };
+    /// Get container stats
+    #[deprecated(note = "fetches every container's stats; use get_all_container_stats")]
+    pub async fn get_container_stats(&self, name: &str) -> Result<Option<ContainerStats>> {
+        Ok(self.get_all_container_stats().await?.remove(name))
+    }
+
+    /// Stats for every running container, keyed by name: one list call and one
+    /// `docker system df` for sizes, then the per-container stats calls (which
+    /// Docker has no bulk endpoint for) with bounded parallelism
+    pub async fn get_all_container_stats(&self) -> Result<HashMap<String, ContainerStats>> {
+        let summaries = self.list_summaries(false, true).await?;
+        let volume_sizes = self.get_all_volume_sizes().await.unwrap_or_default();
+
+        use futures::stream::{self, StreamExt};
+        let volume_sizes = &volume_sizes;
+        let results: Vec<Option<(String, ContainerStats)>> = stream::iter(summaries)
+            .map(|summary| self.summary_stats(summary, volume_sizes))
+            .buffer_unordered(MAX_CONCURRENT_PER_CONTAINER)
+            .collect()
+            .await;
+
+        Ok(results.into_iter().flatten().collect())
+    }
+
+    /// Fetch live stats for a listed container. None if it vanished since the
+    /// list call or Docker returned nothing.
+    async fn summary_stats(
+        &self,
+        summary: ContainerSummary,
+        volume_sizes: &HashMap<String, u64>,
+    ) -> Option<(String, ContainerStats)> {
+        let id = summary.id?;
+        let name = summary
+            .names
+            .as_ref()
+            .and_then(|names| names.first())
+            .map(|n| n.trim_start_matches('/').to_string())?;
+
+        // Virtual size (image + container layers), same figure as `docker ps --size`
+        let container_size = summary.size_root_fs.unwrap_or(0).max(0) as u64;
+
+        // Named volumes mounted by the container, sized from `docker system df -v`
+        let volume_size = summary
+            .mounts
+            .iter()
+            .flatten()
+            .filter_map(|mount| mount.name.as_ref())
+            .filter_map(|volume| volume_sizes.get(volume))
+            .sum();
 
         let mut stats_stream = self.docker.stats(
-            &container_id,
+            &id,
             Some(StatsOptions {
                 stream: false,
                 one_shot: true,
@@ -230,52 +1373,40 @@ impl DockerManager {
         );
 
         use futures::StreamExt;
-        if let Some(Ok(stats)) = stats_stream.next().await {
-            let cpu_delta = stats.cpu_stats.cpu_usage.total_usage
-                - stats.precpu_stats.cpu_usage.total_usage;
-            let system_delta = stats.cpu_stats.system_cpu_usage.unwrap_or(0)
-                - stats.precpu_stats.system_cpu_usage.unwrap_or(0);
-            let num_cpus = stats.cpu_stats.online_cpus.unwrap_or(1) as u64;
-
-            let cpu_percent = if system_delta > 0 {
-                (cpu_delta as f64 / system_delta as f64) * num_cpus as f64 * 100.0
-            } else {
-                0.0
-            };
+        let stats = match stats_stream.next().await {
+            Some(Ok(stats)) => stats,
+            _ => return None,
+        };
 
-            let memory_usage = stats.memory_stats.usage.unwrap_or(0);
-            let memory_limit = stats.memory_stats.limit.unwrap_or(0);
-
-            let (network_rx, network_tx) = stats
-                .networks
-                .as_ref()
-                .and_then(|networks| networks.get("eth0"))
-                .map(|net| (net.rx_bytes, net.tx_bytes))
-                .unwrap_or((0, 0));
-
-            // Get container virtual size (image + container layers)
-            let container_size = if let Ok(output) = tokio::process::Command::new("docker")
-                .args(&["ps", "--size", "--filter", &format!("id={}", container_id), "--format", "{{.Size}}"])
-                .output()
-                .await
-            {
-                let size_str = String::from_utf8_lossy(&output.stdout);
-                // Format is like "408MB (virtual 558MB)" or "0B (virtual 923MB)"
-                // We want the virtual size (total image+container)
-                if let Some(virtual_part) = size_str.trim().split(" (virtual ").nth(1) {
-                    let virtual_str = virtual_part.trim_end_matches(')');
-                    Self::parse_size_string(virtual_str)
-                } else {
-                    0
-                }
-            } else {
-                0
-            };
+        let cpu_delta = stats.cpu_stats.cpu_usage.total_usage
+            .saturating_sub(stats.precpu_stats.cpu_usage.total_usage);
+        let system_delta = stats.cpu_stats.system_cpu_usage.unwrap_or(0)
+            .saturating_sub(stats.precpu_stats.system_cpu_usage.unwrap_or(0));
+        let num_cpus = stats.cpu_stats.online_cpus.unwrap_or(1) as u64;
+
+        let cpu_percent = if system_delta > 0 {
+            (cpu_delta as f64 / system_delta as f64) * num_cpus as f64 * 100.0
+        } else {
+            0.0
+        };
+
+        let memory_usage = stats.memory_stats.usage.unwrap_or(0);
+        let memory_limit = stats.memory_stats.limit.unwrap_or(0);
 
-            // Get actual volume sizes by inspecting mounts and querying docker system df
-            let volume_size = self.get_container_volume_size(&container_id).await.unwrap_or(0);
+        let (network_rx, network_tx) = network_totals(stats.networks.as_ref());
+
+        let (block_read, block_write) = block_io_totals(
+            stats
+                .blkio_stats
+                .io_service_bytes_recursive
+                .iter()
+                .flatten()
+                .map(|entry| (entry.op.as_str(), entry.value)),
+        );
 
-            Ok(Some(ContainerStats {
+        Some((
+            name,
+            ContainerStats {
                 cpu_percent,
                 memory_usage,
                 memory_limit,
@@ -283,10 +1414,13 @@ impl DockerManager {
                 network_tx,
                 container_size,
                 volume_size,
-            }))
-        } else {
-            Ok(None)
-        }
+                block_read,
+                block_write,
+                block_read_rate: None,
+                block_write_rate: None,
+                sampled_at: std::time::Instant::now(),
+            },
+        ))
     }
 
     /// Parse Docker size string to bytes (e.g. "408MB", "6.15kB", "1.5GB")
@@ -320,34 +1454,10 @@ impl DockerManager {
         (num * multiplier as f64) as u64
     }
 
-    /// Get total volume size for a container by inspecting its mounts
-    async fn get_container_volume_size(&self, container_id: &str) -> Result<u64> {
-        // Get container's volume mounts
-        let inspect = self.docker.inspect_container(container_id, None).await?;
-
-        let mut total_volume_size: u64 = 0;
-
-        // Get all volumes from mounts
-        if let Some(mounts) = inspect.mounts {
-            // Build a map of volume names to sizes using docker system df
-            let volume_sizes = self.get_all_volume_sizes().await?;
-
-            for mount in mounts {
-                if let Some(volume_name) = mount.name {
-                    if let Some(&size) = volume_sizes.get(&volume_name) {
-                        total_volume_size += size;
-                    }
-                }
-            }
-        }
-
-        Ok(total_volume_size)
-    }
-
     /// Get all volume sizes using docker system df -v
     async fn get_all_volume_sizes(&self) -> Result<HashMap<String, u64>> {
-        let output = tokio::process::Command::new("docker")
-            .args(&["system", "df", "-v"])
+        let output = tokio::process::Command::new(runtime_program())
+            .args(["system", "df", "-v"])
             .output()
             .await?;
 
@@ -384,67 +1494,308 @@ impl DockerManager {
         Ok(volume_sizes)
     }
 
-    /// Execute docker-compose command
+    /// Execute docker-compose command. Values from the secrets store are
+    /// passed in the environment; if it can't be decrypted nothing is run.
     pub async fn compose_command(&self, args: &[&str]) -> Result<String> {
-        let mut cmd = Command::new("docker");
-        cmd.arg("compose")
+        let secrets = crate::core::secrets::compose_env(&self.project_root)?;
+
+        // Async, so the compose calls fetched with bounded parallelism in
+        // list_containers really overlap instead of blocking a worker each
+        let output = tokio::process::Command::new(self.compose_program())
+            .args(self.compose_base_args())
             .args(args)
+            .envs(secrets)
             .current_dir(&self.project_root)
             .stdout(Stdio::piped())
-            .stderr(Stdio::piped());
-
-        let output = cmd.output()
+            .stderr(Stdio::piped())
+            .output()
+            .await
             .context("Failed to execute docker compose command")?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(anyhow!("Docker compose command failed: {}", stderr));
+            if let Some(service) = stderr.lines().find_map(|line| line.trim().strip_prefix("no such service: ")) {
+                return Err(Error::ServiceNotFound(service.trim().to_string()));
+            }
+            return Err(anyhow!("Docker compose command failed: {}", stderr).into());
         }
 
         Ok(String::from_utf8_lossy(&output.stdout).to_string())
     }
 
-    /// Start services with a specific profile
-    pub async fn start_profile(&self, profile: &str) -> Result<()> {
-        self.compose_command(&["--profile", profile, "up", "-d"])
-            .await?;
+    /// `compose_command` for commands that change containers. In a dry run
+    /// the command is only recorded (see `dry_run`).
+    pub async fn compose_change(&self, args: &[&str]) -> Result<()> {
+        let planned = || {
+            let mut command = vec![self.compose_program().to_string()];
+            command.extend(self.compose_base_args().iter().map(|arg| arg.to_string_lossy().into_owned()));
+            command.extend(args.iter().map(|arg| arg.to_string()));
+            command.join(" ")
+        };
+        if crate::core::dry_run::skip(planned) {
+            return Ok(());
+        }
+        self.compose_command(args).await?;
         Ok(())
     }
 
+    /// Check the compose file the way `up` would see it: `docker compose
+    /// config -q` for syntax and interpolation errors, plus every variable a
+    /// service references that is neither in .env, the secrets store nor the
+    /// environment. Empty means valid; `Err` only if the check couldn't run.
+    pub async fn validate_compose(&self) -> Result<Vec<String>> {
+        Ok(self.compose_problems().await?.iter().map(ComposeProblem::describe).collect())
+    }
+
+    /// Problems found by `validate_compose`, with the service they concern
+    async fn compose_problems(&self) -> Result<Vec<ComposeProblem>> {
+        let secrets = crate::core::secrets::compose_env(&self.project_root)?;
+
+        let output = tokio::process::Command::new(self.compose_program())
+            .args(self.compose_base_args())
+            .args(["config", "-q"])
+            .envs(secrets.iter().map(|(k, v)| (k, v)))
+            .current_dir(&self.project_root)
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .output()
+            .await
+            .context("Failed to execute docker compose config")?;
+
+        // On success stderr only holds warnings. Unset variables are among
+        // them, but they are reported below with the service that uses them.
+        let mut problems: Vec<ComposeProblem> = Vec::new();
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            problems.extend(
+                stderr
+                    .lines()
+                    .filter(|line| !line.contains("variable is not set"))
+                    .filter_map(compose_config_problem)
+                    .map(|(service, message)| ComposeProblem { service, message, blocking: true }),
+            );
+            if problems.is_empty() {
+                problems.push(ComposeProblem {
+                    service: None,
+                    message: format!("docker compose config failed ({})", output.status),
+                    blocking: true,
+                });
+            }
+        }
+
+        // The file may not even parse; compose has already said why
+        if let Ok(services) = self.parse_compose_file() {
+            let env_keys = crate::core::ConfigManager::load(&self.env_file)
+                .map(|config| config.keys())
+                .unwrap_or_default();
+            let is_set = |var: &str| {
+                env_keys.iter().any(|k| k == var)
+                    || secrets.iter().any(|(k, _)| k == var)
+                    || std::env::var_os(var).is_some()
+            };
+            for (service, var, required) in unset_env_refs(&services, is_set) {
+                let message = if required {
+                    format!("${{{}}} is not set in .env", var)
+                } else {
+                    format!("${{{}}} is not set in .env (compose will leave it blank)", var)
+                };
+                problems.push(ComposeProblem { service: Some(service), message, blocking: required });
+            }
+        }
+
+        Ok(problems)
+    }
+
+    /// Start services with a specific profile. The compose file is validated
+    /// first so a missing required variable is reported by name instead of as
+    /// a failure halfway through `up`. Returns the warnings for variables
+    /// compose will leave blank.
+    pub async fn start_profile(&self, profile: &str) -> Result<Vec<String>> {
+        let warnings = self.check_profile_compose(profile).await?;
+        self.compose_change(&["--profile", profile, "up", "-d"]).await?;
+        Ok(warnings)
+    }
+
+    /// Check the services a start of `profile` would bring up. Fails with
+    /// `InvalidCompose` on what `up` would fail on (compose errors,
+    /// `${VAR:?}` unset); returns the rest as warnings, as compose itself
+    /// only warns about them.
+    pub async fn check_profile_compose(&self, profile: &str) -> Result<Vec<String>> {
+        let problems = self.compose_problems().await?;
+        if problems.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // Services outside this profile don't matter for this start
+        let services = self.parse_compose_file().unwrap_or_default();
+        let starting = |service: &str| {
+            services
+                .get(service)
+                .map(|config| config.profiles.is_empty() || config.profiles.iter().any(|p| p == profile))
+                .unwrap_or(true)
+        };
+        let (blocking, warnings): (Vec<ComposeProblem>, Vec<ComposeProblem>) = problems
+            .into_iter()
+            .filter(|problem| problem.service.as_deref().map(starting).unwrap_or(true))
+            .partition(|problem| problem.blocking);
+        if !blocking.is_empty() {
+            return Err(Error::InvalidCompose(blocking.iter().map(ComposeProblem::describe).collect()));
+        }
+        Ok(warnings.iter().map(ComposeProblem::describe).collect())
+    }
+
+    /// Services `--profile <profile> up` starts: the profile's own plus
+    /// every service without a profile, sorted
+    pub fn profile_members(&self, profile: &str) -> Result<Vec<String>> {
+        let services = self.parse_compose_file()?;
+        if profile_services(&services, profile).is_empty() {
+            return Err(Error::invalid_input(format!("Profile '{}' is not defined in the compose file", profile)));
+        }
+        let mut names: Vec<String> = services
+            .iter()
+            .filter(|(_, config)| config.profiles.is_empty() || config.profiles.iter().any(|p| p == profile))
+            .map(|(name, _)| name.clone())
+            .collect();
+        names.sort();
+        Ok(names)
+    }
+
+    /// The Compose CLI this manager runs
+    pub fn compose(&self) -> ComposeCli {
+        self.compose
+    }
+
     /// Stop services from a specific profile
     pub async fn stop_profile(&self, profile: &str) -> Result<()> {
-        self.compose_command(&["--profile", profile, "stop"])
-            .await?;
+        self.compose_change(&["--profile", profile, "stop"]).await?;
         Ok(())
     }
 
-    /// Stop all services
-    pub async fn stop_all(&self) -> Result<()> {
-        self.compose_command(&["down"]).await?;
+    /// The project name compose itself resolves for our compose file, i.e. the
+    /// value of the `com.docker.compose.project` label on its containers.
+    /// Unlike `project_name()` this never guesses.
+    pub async fn resolve_project(&self) -> Result<String> {
+        let config = self
+            .compose_command(self.compose.config_args())
+            .await
+            .context("Could not determine the compose project")?;
+        if let Some(name) = compose_project_name(&config) {
+            return Ok(name);
+        }
+        match self.compose {
+            ComposeCli::Plugin => {
+                Err(anyhow!("Could not determine the compose project from {}", self.compose_file.display()).into())
+            }
+            ComposeCli::Standalone | ComposeCli::Podman | ComposeCli::PodmanCompose => Ok(self.v1_project_name()),
+        }
+    }
+
+    /// Project name docker-compose v1 (and podman-compose) derives when
+    /// `config` doesn't print one: `-p`, else COMPOSE_PROJECT_NAME (environment, then .env), else the
+    /// project directory name with everything but `[a-z0-9_-]` dropped
+    fn v1_project_name(&self) -> String {
+        if let Some(ref project) = self.project {
+            return project.clone();
+        }
+        let from_env_file = || {
+            std::fs::read_to_string(&self.env_file).ok()?.lines().find_map(|line| {
+                line.trim()
+                    .strip_prefix("COMPOSE_PROJECT_NAME=")
+                    .map(|value| value.trim().trim_matches('"').to_string())
+            })
+        };
+        std::env::var("COMPOSE_PROJECT_NAME")
+            .ok()
+            .or_else(from_env_file)
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| {
+                let dir = self.project_root.file_name().unwrap_or_default().to_string_lossy().to_lowercase();
+                dir.chars().filter(|c| c.is_ascii_alphanumeric() || *c == '_' || *c == '-').collect()
+            })
+    }
+
+    /// Running containers labelled `com.docker.compose.project=<project>`, the
+    /// ones `stop_all` acts on. Errors (rather than returning everything) if the
+    /// project can't be resolved.
+    pub async fn stop_all_targets(&self) -> Result<(String, Vec<ContainerInfo>)> {
+        let project = self.resolve_project().await?;
+        let mut containers: Vec<ContainerInfo> = self
+            .list_project_summaries(&project, false, false)
+            .await?
+            .into_iter()
+            .map(|c| self.container_summary_to_info(c))
+            .collect();
+        containers.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok((project, containers))
+    }
+
+    /// Stop exactly the given containers (from `stop_all_targets`), by id.
+    /// Containers are stopped, not removed.
+    pub async fn stop_all(&self, containers: &[ContainerInfo]) -> Result<()> {
+        use futures::stream::{self, StreamExt};
+        let planned = || {
+            let names: Vec<&str> = containers.iter().map(|c| c.name.as_str()).collect();
+            format!("{} stop {}", runtime_program(), names.join(" "))
+        };
+        if containers.is_empty() || crate::core::dry_run::skip(planned) {
+            return Ok(());
+        }
+        let failures: Vec<String> = stream::iter(containers)
+            .map(|c| async move {
+                self.docker
+                    .stop_container(&c.id, None)
+                    .await
+                    .err()
+                    .map(|e| format!("{}: {}", c.name, e))
+            })
+            .buffer_unordered(MAX_CONCURRENT_PER_CONTAINER)
+            .filter_map(|failure| async move { failure })
+            .collect()
+            .await;
+
+        if !failures.is_empty() {
+            return Err(anyhow!("Failed to stop {} container(s): {}", failures.len(), failures.join("; ")).into());
+        }
         Ok(())
     }
 
+    /// `Error::ServiceNotFound` unless the compose file defines the service.
+    /// An unreadable compose file is left for compose itself to report.
+    fn ensure_service_defined(&self, service: &str) -> Result<()> {
+        match self.parse_compose_file() {
+            Ok(services) if !services.contains_key(service) => Err(Error::ServiceNotFound(service.to_string())),
+            _ => Ok(()),
+        }
+    }
+
     /// Stop specific service
     pub async fn stop_service(&self, service: &str) -> Result<()> {
-        self.compose_command(&["stop", service]).await?;
+        self.ensure_service_defined(service)?;
+        self.compose_change(&["stop", service]).await?;
         Ok(())
     }
 
     /// Start specific service
     pub async fn start_service(&self, service: &str) -> Result<()> {
-        self.compose_command(&["start", service]).await?;
+        self.ensure_service_defined(service)?;
+        self.compose_change(&["start", service]).await?;
         Ok(())
     }
 
     /// Restart specific service
     pub async fn restart_service(&self, service: &str) -> Result<()> {
-        self.compose_command(&["restart", service]).await?;
+        self.ensure_service_defined(service)?;
+        self.compose_change(&["restart", service]).await?;
         Ok(())
     }
 
-    /// Get logs for a service
-    pub async fn get_logs(&self, service: &str, tail: Option<usize>) -> Result<String> {
+    /// Get logs for a service. With `strip_ansi` the output is plain text,
+    /// for writing to files and pipes.
+    pub async fn get_logs(&self, service: &str, tail: Option<usize>, strip_ansi: bool) -> Result<String> {
         let mut args = vec!["logs"];
+        if strip_ansi {
+            args.push("--no-color");
+        }
         let tail_str;
         if let Some(n) = tail {
             tail_str = n.to_string();
@@ -453,19 +1804,61 @@ impl DockerManager {
         }
         args.push(service);
 
-        self.compose_command(&args).await
+        let logs = self.compose_command(&args).await?;
+        Ok(if strip_ansi { strip_ansi_codes(&logs) } else { logs })
     }
 
-    /// Get logs since a specific timestamp (RFC3339 format or relative time like "1s")
-    pub async fn get_logs_since(&self, service: &str, since: &str) -> Result<String> {
-        let args = vec!["logs", "--since", since, service];
-        self.compose_command(&args).await
+    /// Get logs since a timestamp, optionally capped to the last `max_lines`
+    /// Returns a note when the requested window predates the current container
+    pub async fn get_logs_since(
+        &self,
+        service: &str,
+        since: DateTime<Utc>,
+        max_lines: Option<usize>,
+        strip_ansi: bool,
+    ) -> Result<(String, Option<String>)> {
+        let since_str = since.to_rfc3339_opts(SecondsFormat::Secs, true);
+        let mut args = vec!["logs", "--since", since_str.as_str()];
+        if strip_ansi {
+            args.push("--no-color");
+        }
+        let tail_str;
+        if let Some(n) = max_lines {
+            tail_str = n.to_string();
+            args.push("--tail");
+            args.push(&tail_str);
+        }
+        args.push(service);
+
+        let mut logs = self.compose_command(&args).await?;
+        if strip_ansi {
+            logs = strip_ansi_codes(&logs);
+        }
+
+        // Logs from before a recreate went away with the old container
+        let mut note = None;
+        if let Some(container) = self.get_container(service).await? {
+            if let Some(created) = DateTime::from_timestamp(container.created, 0) {
+                if created > since {
+                    note = Some(format!(
+                        "Note: {} was (re)created at {}; logs before that are not available",
+                        service,
+                        crate::utils::format_timestamp(container.created)
+                    ));
+                }
+            }
+        }
+        if logs.trim().is_empty() && note.is_none() {
+            note = Some(format!("No log output from {} since {}", service, since_str));
+        }
+
+        Ok((logs, note))
     }
 
     /// Stream logs for a service (returns async stream)
     pub async fn follow_logs(&self, service: &str) -> Result<tokio::process::Child> {
-        let child = tokio::process::Command::new("docker")
-            .arg("compose")
+        let child = tokio::process::Command::new(self.compose_program())
+            .args(self.compose_base_args())
             .arg("logs")
             .arg("-f")
             .arg(service)
@@ -478,12 +1871,184 @@ impl DockerManager {
         Ok(child)
     }
 
+    /// Fail with a clear error unless the service's container is running
+    async fn ensure_running(&self, service: &str) -> Result<()> {
+        match self.get_container(service).await? {
+            Some(info) if info.state.is_running() => Ok(()),
+            Some(info) => Err(Error::ServiceNotRunning {
+                service: service.to_string(),
+                status: info.status,
+            }),
+            None => Err(Error::ServiceNotFound(service.to_string())),
+        }
+    }
+
+    fn exec_command(&self, service: &str, cmd: &[&str], tty: bool) -> Result<tokio::process::Command> {
+        if cmd.is_empty() {
+            return Err(Error::invalid_input(format!("No command given to execute in {}", service)));
+        }
+
+        let mut command = tokio::process::Command::new(self.compose_program());
+        command.args(self.compose_base_args()).arg("exec");
+        if !tty {
+            command.arg("-T");
+        }
+        command.arg(service).args(cmd).current_dir(&self.project_root);
+        Ok(command)
+    }
+
+    /// Run a command inside a running service container and capture its output
+    /// Not exposed through the HTTP API - containers hold wallet keys
+    pub async fn exec(&self, service: &str, cmd: &[&str]) -> Result<ExecOutput> {
+        self.ensure_running(service).await?;
+
+        let output = self.exec_command(service, cmd, false)?
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .output()
+            .await
+            .context("Failed to execute docker compose exec")?;
+
+        Ok(ExecOutput {
+            stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+            exit_code: output.status.code().unwrap_or(-1),
+        })
+    }
+
+    /// Run a command inside a running service container, streaming output to
+    /// this process's stdout/stderr as it is produced. Returns the exit code.
+    pub async fn exec_streaming(&self, service: &str, cmd: &[&str]) -> Result<i32> {
+        use std::io::IsTerminal;
+
+        self.ensure_running(service).await?;
+
+        let tty = std::io::stdin().is_terminal() && std::io::stdout().is_terminal();
+        let status = self.exec_command(service, cmd, tty)?
+            .stdin(Stdio::inherit())
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .status()
+            .await
+            .context("Failed to execute docker compose exec")?;
+
+        Ok(status.code().unwrap_or(-1))
+    }
+
     /// Pull latest images
     pub async fn pull_images(&self) -> Result<()> {
-        self.compose_command(&["pull"]).await?;
+        self.compose_change(&["pull"]).await?;
+        Ok(())
+    }
+
+    /// Build `--profile <p>` argument pairs for compose commands
+    fn profile_args(profiles: &[String]) -> Vec<&str> {
+        profiles
+            .iter()
+            .flat_map(|p| ["--profile", p.as_str()])
+            .collect()
+    }
+
+    /// Resolve service -> image mapping for the given profiles (with .env interpolation applied)
+    pub async fn resolve_service_images(&self, profiles: &[String]) -> Result<Vec<(String, String)>> {
+        let mut args = Self::profile_args(profiles);
+        args.extend(self.compose.config_args());
+
+        let output = self.compose_command(&args).await?;
+        let config = parse_compose_config(&output).context("Failed to parse docker compose config output")?;
+
+        let mut images: Vec<(String, String)> = config
+            .get("services")
+            .and_then(|s| s.as_object())
+            .map(|services| {
+                services
+                    .iter()
+                    .filter_map(|(name, svc)| {
+                        svc.get("image")
+                            .and_then(|i| i.as_str())
+                            .map(|image| (name.clone(), image.to_string()))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        images.sort();
+        Ok(images)
+    }
+
+    /// Pull the image of a single service
+    pub async fn pull_service(&self, profiles: &[String], service: &str) -> Result<()> {
+        let mut args = Self::profile_args(profiles);
+        args.extend(["pull", service]);
+        self.compose_change(&args).await?;
         Ok(())
     }
 
+    /// Get the image ID a container was created from (None if the container doesn't exist)
+    pub async fn container_image_id(&self, container: &str) -> Result<Option<String>> {
+        match self.docker.inspect_container(container, None).await {
+            Ok(inspect) => Ok(inspect.image),
+            Err(bollard::errors::Error::DockerResponseServerError { status_code: 404, .. }) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Get the local image ID for an image reference (None if not present locally)
+    pub async fn local_image_id(&self, image: &str) -> Result<Option<String>> {
+        match self.docker.inspect_image(image).await {
+            Ok(inspect) => Ok(inspect.id),
+            Err(bollard::errors::Error::DockerResponseServerError { status_code: 404, .. }) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Recreate specific services with their current images (dependencies are left untouched)
+    pub async fn recreate_services(&self, profiles: &[String], services: &[String]) -> Result<()> {
+        if services.is_empty() {
+            return Ok(());
+        }
+
+        let mut args = Self::profile_args(profiles);
+        args.extend(["up", "-d", "--no-deps"]);
+        args.extend(services.iter().map(|s| s.as_str()));
+        self.compose_change(&args).await?;
+        Ok(())
+    }
+
+    /// Recreate every service of a profile (`up -d --force-recreate`) so new
+    /// images and config take effect. Volumes are kept - this never passes `-v`.
+    /// Returns the recreated services, sorted.
+    pub async fn recreate_profile(&self, profile: &str) -> Result<Vec<String>> {
+        let services = profile_services(&self.parse_compose_file()?, profile);
+        if services.is_empty() {
+            return Err(Error::invalid_input(format!("Profile '{}' is not defined in the compose file", profile)));
+        }
+
+        // Name the services so profile-less ones aren't recreated along with them
+        let mut args = vec!["--profile", profile, "up", "-d", "--force-recreate"];
+        args.extend(services.iter().map(|s| s.as_str()));
+        self.compose_change(&args).await?;
+
+        Ok(services)
+    }
+
+    /// Compose `ports:` entries of each service in a profile, by service name
+    pub fn profile_ports(&self, profile: &str) -> Result<Vec<(String, Vec<String>)>> {
+        let services = self.parse_compose_file()?;
+        let names = profile_services(&services, profile);
+        if names.is_empty() {
+            return Err(Error::invalid_input(format!("Profile '{}' is not defined in the compose file", profile)));
+        }
+
+        Ok(names
+            .into_iter()
+            .map(|name| {
+                let ports = services[&name].ports.clone();
+                (name, ports)
+            })
+            .collect())
+    }
+
     /// Get active profiles from container list (synchronous, no Docker API calls)
     pub fn get_active_profiles_from_list(containers: &[ContainerInfo]) -> Vec<String> {
         let mut profiles = Vec::new();
@@ -547,8 +2112,7 @@ impl DockerManager {
         let status = summary.status.clone().unwrap_or_else(|| "unknown".to_string());
         let state = summary
             .state
-            .as_ref()
-            .map(|s| s.as_str())
+            .as_deref()
             .unwrap_or("unknown")
             .into();
 
@@ -596,6 +2160,8 @@ impl DockerManager {
             state,
             health,
             created: summary.created.unwrap_or(0),
+            started_at: None,
+            finished_at: None,
             ports,
             metrics: ServiceMetrics::default(),
         }
@@ -660,10 +2226,8 @@ impl DockerManager {
                             .filter_map(|p| {
                                 if let Some(s) = p.as_str() {
                                     Some(s.to_string())
-                                } else if let Some(i) = p.as_i64() {
-                                    Some(i.to_string())
                                 } else {
-                                    None
+                                    p.as_i64().map(|i| i.to_string())
                                 }
                             })
                             .collect()
@@ -738,7 +2302,7 @@ impl DockerManager {
         // 1. Parse YAML config
         let compose_configs = self.parse_compose_file()?;
         let yaml_config = compose_configs.get(service_name)
-            .ok_or_else(|| anyhow!("Service '{}' not found in docker-compose.yml", service_name))?
+            .ok_or_else(|| Error::ServiceNotFound(service_name.to_string()))?
             .clone();
 
         // 2. Try to inspect running container
@@ -747,8 +2311,7 @@ impl DockerManager {
                 // Extract image
                 let image = inspect.config
                     .as_ref()
-                    .and_then(|c| c.image.as_ref())
-                    .map(|s| s.clone())
+                    .and_then(|c| c.image.clone())
                     .unwrap_or_default();
 
                 // Extract and filter environment variables
@@ -831,17 +2394,10 @@ impl DockerManager {
                     .map(|s| s.to_string())
                     .unwrap_or_else(|| "unknown".to_string());
 
-                // Calculate uptime
-                let uptime = if let Some(state) = inspect.state.as_ref() {
-                    if let Some(started_at) = state.started_at.as_ref() {
-                        // Parse and calculate uptime
-                        "TODO".to_string() // We'll implement this properly later
-                    } else {
-                        "N/A".to_string()
-                    }
-                } else {
-                    "N/A".to_string()
-                };
+                let uptime = inspect.state
+                    .as_ref()
+                    .map(|s| running_uptime(s.running.unwrap_or(false), s.started_at.as_deref(), Utc::now()))
+                    .unwrap_or_else(|| "N/A".to_string());
 
                 Some(RunningServiceConfig {
                     image,
@@ -919,6 +2475,143 @@ impl DockerManager {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_compose_project_name() {
+        assert_eq!(
+            compose_project_name(r#"{"name": "igra-orchestra-mainnet", "services": {}}"#),
+            Some("igra-orchestra-mainnet".to_string())
+        );
+        // No usable name means no project to scope to
+        assert_eq!(compose_project_name(r#"{"name": "  ", "services": {}}"#), None);
+        assert_eq!(compose_project_name(r#"{"services": {}}"#), None);
+        assert_eq!(compose_project_name("not json"), None);
+    }
+
+    #[test]
+    fn test_compose_project_name_from_yaml() {
+        // docker-compose v1 prints YAML
+        assert_eq!(
+            compose_project_name("name: igra-orchestra-testnet\nservices: {}\n"),
+            Some("igra-orchestra-testnet".to_string())
+        );
+        assert_eq!(compose_project_name("services:\n  kaspad:\n    image: kaspad\n"), None);
+    }
+
+    #[test]
+    fn test_choose_compose() {
+        let both = |_: ComposeCli| true;
+        let v1_only = |cli: ComposeCli| cli == ComposeCli::Standalone;
+        let neither = |_: ComposeCli| false;
+        let docker = ContainerRuntime::Docker;
+
+        assert_eq!(choose_compose(docker, None, both).unwrap(), ComposeCli::Plugin);
+        assert_eq!(choose_compose(docker, None, v1_only).unwrap(), ComposeCli::Standalone);
+        assert_eq!(choose_compose(docker, Some(" V1 "), both).unwrap(), ComposeCli::Standalone);
+        assert_eq!(choose_compose(docker, Some(""), v1_only).unwrap(), ComposeCli::Standalone);
+        assert!(matches!(choose_compose(docker, None, neither), Err(DockerError::ComposeMissing)));
+        // A forced version that isn't installed is not silently swapped
+        assert!(matches!(choose_compose(docker, Some("v2"), v1_only), Err(DockerError::ComposeMissing)));
+        assert!(matches!(
+            choose_compose(docker, Some("docker-compose"), both),
+            Err(DockerError::ComposeOverride { .. })
+        ));
+
+        // Podman: the built-in `podman compose`, else podman-compose
+        let podman = ContainerRuntime::Podman;
+        let script_only = |cli: ComposeCli| cli == ComposeCli::PodmanCompose;
+        assert_eq!(choose_compose(podman, None, both).unwrap(), ComposeCli::Podman);
+        assert_eq!(choose_compose(podman, None, script_only).unwrap(), ComposeCli::PodmanCompose);
+        assert_eq!(choose_compose(podman, Some("v2"), both).unwrap(), ComposeCli::Plugin);
+        assert!(matches!(choose_compose(podman, None, neither), Err(DockerError::PodmanComposeMissing)));
+        assert!(!ComposeCli::PodmanCompose.handles_profiles());
+    }
+
+    #[test]
+    fn test_detect_runtime() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = dir.path().join("no-docker.sock");
+
+        assert_eq!(detect_runtime(Some("unix:///run/user/1000/podman/podman.sock"), &missing, None), ContainerRuntime::Podman);
+        assert_eq!(detect_runtime(Some("tcp://10.0.0.5:2375"), &missing, None), ContainerRuntime::Docker);
+
+        // A rootless Podman socket and no Docker one
+        let runtime_dir = dir.path().join("run");
+        std::fs::create_dir_all(runtime_dir.join("podman")).unwrap();
+        std::fs::write(runtime_dir.join("podman").join("podman.sock"), "").unwrap();
+        assert_eq!(detect_runtime(None, &missing, Some(&runtime_dir)), ContainerRuntime::Podman);
+
+        // Docker's socket wins when both exist
+        let docker_sock = dir.path().join("docker.sock");
+        std::fs::write(&docker_sock, "").unwrap();
+        assert_eq!(detect_runtime(None, &docker_sock, Some(&runtime_dir)), ContainerRuntime::Docker);
+
+        // ...unless it's podman-docker's symlink to the Podman socket
+        #[cfg(unix)]
+        {
+            let shim = dir.path().join("shim.sock");
+            std::os::unix::fs::symlink(runtime_dir.join("podman").join("podman.sock"), &shim).unwrap();
+            assert_eq!(detect_runtime(None, &shim, None), ContainerRuntime::Podman);
+        }
+
+        assert_eq!("Podman".parse::<ContainerRuntime>().unwrap(), ContainerRuntime::Podman);
+        assert!("lxc".parse::<ContainerRuntime>().is_err());
+    }
+
+    #[test]
+    fn test_resolve_docker_endpoint() {
+        let dir = tempfile::tempdir().unwrap();
+        let system = dir.path().join("system.sock");
+        let resolve = |runtime, docker_host| resolve_docker_endpoint(runtime, docker_host, &system, Some(dir.path()));
+
+        assert_eq!(
+            resolve(ContainerRuntime::Docker, Some("unix:///run/user/1000/docker.sock")),
+            DockerEndpoint::Unix(PathBuf::from("/run/user/1000/docker.sock"))
+        );
+        assert_eq!(
+            resolve(ContainerRuntime::Docker, Some("tcp://10.0.0.5:2375")),
+            DockerEndpoint::Tcp("tcp://10.0.0.5:2375".to_string())
+        );
+        assert_eq!(
+            resolve(ContainerRuntime::Docker, Some("ssh://admin@node1:2222")),
+            DockerEndpoint::Ssh("admin@node1:2222".to_string())
+        );
+
+        // No socket anywhere: the system one, for the error to name
+        assert_eq!(resolve(ContainerRuntime::Docker, Some("")), DockerEndpoint::Unix(system.clone()));
+
+        // Rootless socket is used when the system one doesn't exist
+        std::fs::write(dir.path().join("docker.sock"), "").unwrap();
+        assert_eq!(resolve(ContainerRuntime::Docker, Some("")), DockerEndpoint::Unix(dir.path().join("docker.sock")));
+
+        // ...and the system socket wins when both exist
+        std::fs::write(&system, "").unwrap();
+        assert_eq!(resolve(ContainerRuntime::Docker, None), DockerEndpoint::Unix(system.clone()));
+
+        // Podman's rootless socket, and DOCKER_HOST still wins under Podman
+        std::fs::create_dir_all(dir.path().join("podman")).unwrap();
+        std::fs::write(dir.path().join("podman").join("podman.sock"), "").unwrap();
+        assert_eq!(
+            resolve(ContainerRuntime::Podman, None),
+            DockerEndpoint::Unix(dir.path().join("podman").join("podman.sock"))
+        );
+        assert_eq!(
+            resolve(ContainerRuntime::Podman, Some("tcp://10.0.0.5:2375")),
+            DockerEndpoint::Tcp("tcp://10.0.0.5:2375".to_string())
+        );
+    }
+
+    #[test]
+    fn test_remote_docker_host() {
+        assert!(is_remote_docker_host("ssh://admin@node1"));
+        assert!(is_remote_docker_host("tcp://10.0.0.5:2376"));
+        assert!(!is_remote_docker_host("tcp://127.0.0.1:2375"));
+        assert!(!is_remote_docker_host("tcp://[::1]:2375"));
+        assert!(!is_remote_docker_host("unix:///var/run/docker.sock"));
+
+        assert_eq!(split_ssh_destination("admin@node1:2222"), ("admin@node1", Some("2222")));
+        assert_eq!(split_ssh_destination("node1"), ("node1", None));
+    }
+
     #[tokio::test]
     async fn test_docker_manager_creation() {
         // This test requires Docker to be running
@@ -927,4 +2620,243 @@ mod tests {
             assert!(manager.compose_file.exists());
         }
     }
+
+    #[test]
+    fn test_compose_target_validation() {
+        assert!(ComposeTarget::default().validate().is_ok());
+
+        let missing = ComposeTarget {
+            compose_file: Some(PathBuf::from("/nonexistent/docker-compose.yml")),
+            project: None,
+        };
+        let err = missing.validate().unwrap_err().to_string();
+        assert!(err.contains("Compose file not found"));
+
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("compose.yml");
+        std::fs::write(&file, "services: {}\n").unwrap();
+        let target = ComposeTarget { compose_file: Some(file), project: Some(" ".to_string()) };
+        assert!(target.validate().is_err());
+    }
+
+    #[test]
+    fn test_container_uptime() {
+        let now = DateTime::parse_from_rfc3339("2025-10-21T11:12:00Z").unwrap().with_timezone(&Utc);
+        let mut container = ContainerInfo {
+            id: "abc".to_string(),
+            name: "kaspad".to_string(),
+            image: "kaspad:latest".to_string(),
+            status: "Up 3 hours".to_string(),
+            state: ContainerState::Running,
+            health: None,
+            created: now.timestamp() - 86_400,
+            started_at: parse_docker_time("2025-10-21T08:00:00Z"),
+            finished_at: None,
+            ports: Vec::new(),
+            metrics: ServiceMetrics::default(),
+        };
+
+        // Uptime counts from the last start, not from creation
+        assert_eq!(container.uptime(now).unwrap().num_minutes(), 192);
+        assert!(container.start_summary(now).contains("(up 3h 12m)"));
+
+        // Daemon clock ahead of ours: no negative uptime
+        container.started_at = Some(now + chrono::Duration::seconds(5));
+        assert_eq!(container.uptime(now).unwrap().num_seconds(), 0);
+
+        // Stopped: when it last ran, without an uptime
+        container.state = ContainerState::Stopped;
+        container.started_at = parse_docker_time("2025-10-21T08:00:00Z");
+        container.finished_at = parse_docker_time("2025-10-21T10:00:00Z");
+        assert!(container.uptime(now).is_none());
+        assert!(container.start_summary(now).starts_with("Last started: "));
+        assert!(container.start_summary(now).contains(", exited: "));
+
+        // Created but never started
+        assert_eq!(parse_docker_time("0001-01-01T00:00:00Z"), None);
+        container.state = ContainerState::Unknown;
+        container.started_at = None;
+        container.finished_at = None;
+        assert!(container.uptime(now).is_none());
+        assert!(container.start_summary(now).ends_with("(never started)"));
+    }
+
+    #[test]
+    fn test_parse_start_times() {
+        let output = "aaa 2025-10-21T08:00:00.123456789Z 0001-01-01T00:00:00Z\n\
+                      bbb 2025-10-20T08:00:00Z 2025-10-21T09:30:00Z\n\
+                      ccc 0001-01-01T00:00:00Z 0001-01-01T00:00:00Z\n";
+        let times = parse_start_times(output);
+
+        // Running: started, never exited
+        assert_eq!(times["aaa"].0.unwrap().timestamp(), parse_docker_time("2025-10-21T08:00:00Z").unwrap().timestamp());
+        assert_eq!(times["aaa"].1, None);
+        // Stopped containers keep their times too
+        assert_eq!(times["bbb"], (parse_docker_time("2025-10-20T08:00:00Z"), parse_docker_time("2025-10-21T09:30:00Z")));
+        // Created, never started
+        assert_eq!(times["ccc"], (None, None));
+        assert!(!times.contains_key("ddd"));
+    }
+
+    #[test]
+    fn test_running_uptime() {
+        let now = parse_docker_time("2025-10-21T11:12:00Z").unwrap();
+        assert_eq!(running_uptime(true, Some("2025-10-21T08:00:00.5Z"), now), "3h 11m");
+        // Daemon clock ahead of ours
+        assert_eq!(running_uptime(true, Some("2025-10-21T11:13:00Z"), now), "0s");
+        assert_eq!(running_uptime(false, Some("2025-10-21T08:00:00Z"), now), "N/A");
+        assert_eq!(running_uptime(true, Some("0001-01-01T00:00:00Z"), now), "N/A");
+        assert_eq!(running_uptime(true, None, now), "N/A");
+    }
+
+    #[test]
+    fn test_profile_services() {
+        let service = |profiles: &[&str]| ComposeServiceConfig {
+            image: None,
+            environment: HashMap::new(),
+            volumes: Vec::new(),
+            ports: Vec::new(),
+            networks: Vec::new(),
+            profiles: profiles.iter().map(|p| p.to_string()).collect(),
+            restart: None,
+            command: None,
+            entrypoint: None,
+            depends_on: Vec::new(),
+        };
+
+        let mut services = HashMap::new();
+        services.insert("traefik".to_string(), service(&[]));
+        services.insert("kaspad".to_string(), service(&["kaspad"]));
+        services.insert("viaduct".to_string(), service(&["backend"]));
+        services.insert("execution-layer".to_string(), service(&["backend"]));
+
+        assert_eq!(profile_services(&services, "backend"), vec!["execution-layer", "viaduct"]);
+        assert!(profile_services(&services, "frontend-w9").is_empty());
+    }
+
+    #[test]
+    fn test_services_using_env() {
+        let service = |environment: &[(&str, &str)], command: Option<&str>| ComposeServiceConfig {
+            image: None,
+            environment: environment.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+            volumes: Vec::new(),
+            ports: Vec::new(),
+            networks: Vec::new(),
+            profiles: Vec::new(),
+            restart: None,
+            command: command.map(|c| c.to_string()),
+            entrypoint: None,
+            depends_on: Vec::new(),
+        };
+
+        let mut services = HashMap::new();
+        services.insert("kaspad".to_string(), service(&[], Some("kaspad --utxoindex --${NETWORK:-testnet}")));
+        services.insert("viaduct".to_string(), service(&[("NODE", "$NODE_ID"), ("NET", "${NETWORK}")], None));
+        services.insert("traefik".to_string(), service(&[("EMAIL", "${IGRA_ORCHESTRA_DOMAIN_EMAIL}")], None));
+
+        assert_eq!(services_using_env(&services, "NETWORK"), vec!["kaspad", "viaduct"]);
+        assert_eq!(services_using_env(&services, "NODE_ID"), vec!["viaduct"]);
+        // A key that is only a prefix of the referenced one doesn't match
+        assert!(services_using_env(&services, "NODE").is_empty());
+        assert!(services_using_env(&services, "IGRA_ORCHESTRA_DOMAIN").is_empty());
+    }
+
+    #[test]
+    fn test_unset_env_refs() {
+        let service = |environment: &[(&str, &str)], image: Option<&str>| ComposeServiceConfig {
+            image: image.map(|i| i.to_string()),
+            environment: environment.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+            volumes: Vec::new(),
+            ports: Vec::new(),
+            networks: Vec::new(),
+            profiles: Vec::new(),
+            restart: None,
+            command: None,
+            entrypoint: None,
+            depends_on: Vec::new(),
+        };
+
+        let mut services = HashMap::new();
+        services.insert(
+            "viaduct".to_string(),
+            service(
+                &[
+                    ("NODE", "$NODE_ID"),
+                    ("JWT", "${JWT_SECRET:?set JWT_SECRET}"),
+                    ("NET", "${NETWORK:-testnet}"),
+                    ("DEBUG", "${DEBUG:+1}"),
+                    ("PRICE", "$$5"),
+                ],
+                None,
+            ),
+        );
+        services.insert("kaspad".to_string(), service(&[], Some("igranetwork/kaspad:${KASPAD_VERSION}")));
+        services.insert("traefik".to_string(), service(&[("EMAIL", "${IGRA_ORCHESTRA_DOMAIN_EMAIL}")], None));
+
+        let set = ["IGRA_ORCHESTRA_DOMAIN_EMAIL"];
+        let unset = unset_env_refs(&services, |var| set.contains(&var));
+        assert_eq!(
+            unset,
+            vec![
+                ("kaspad".to_string(), "KASPAD_VERSION".to_string(), false),
+                ("viaduct".to_string(), "JWT_SECRET".to_string(), true),
+                ("viaduct".to_string(), "NODE_ID".to_string(), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compose_config_problem() {
+        assert_eq!(
+            compose_config_problem(
+                "error while interpolating services.viaduct.environment.[]: required variable JWT_SECRET is missing a value: set it"
+            ),
+            Some((
+                Some("viaduct".to_string()),
+                "required variable JWT_SECRET is missing a value: set it".to_string()
+            ))
+        );
+        assert_eq!(
+            compose_config_problem("level=error msg=\"yaml: line 12: did not find expected key\""),
+            Some((None, "yaml: line 12: did not find expected key".to_string()))
+        );
+        assert_eq!(compose_config_problem("   "), None);
+    }
+
+    #[test]
+    fn test_block_io() {
+        // cgroup v1 lists one entry per device; v2 uses lowercase ops
+        assert_eq!(
+            block_io_totals([("Read", 100), ("Write", 40), ("Sync", 7), ("Read", 50)]),
+            (Some(150), Some(40))
+        );
+        assert_eq!(block_io_totals([("read", 0), ("write", 0)]), (Some(0), Some(0)));
+        assert_eq!(block_io_totals([]), (None, None));
+
+        let sample = |read: Option<u64>, write: Option<u64>, at: std::time::Instant| ContainerStats {
+            cpu_percent: 0.0,
+            memory_usage: 0,
+            memory_limit: 0,
+            network_rx: 0,
+            network_tx: 0,
+            container_size: 0,
+            volume_size: 0,
+            block_read: read,
+            block_write: write,
+            block_read_rate: None,
+            block_write_rate: None,
+            sampled_at: at,
+        };
+        let start = std::time::Instant::now();
+        let previous = sample(Some(1_000), Some(5_000), start);
+        let mut current = sample(Some(3_000), Some(4_000), start + std::time::Duration::from_secs(2));
+        current.set_block_rates(&previous);
+        assert_eq!(current.block_read_rate, Some(1_000.0));
+        // Counter reset after a restart: no bogus rate
+        assert_eq!(current.block_write_rate, None);
+
+        let mut unreported = sample(None, None, start + std::time::Duration::from_secs(2));
+        unreported.set_block_rates(&previous);
+        assert_eq!(unreported.block_read_rate, None);
+    }
 }
@@ -0,0 +1,74 @@
+//! Dry-run mode (`--dry-run`)
+//!
+//! Switched on once at startup and never off: the flag is a `OnceLock`, which
+//! can be set but not cleared, so nothing can turn a dry run into a real one
+//! halfway through. Managers validate their inputs as usual, then record the
+//! Docker or wallet action here instead of performing it and report success.
+//! The CLI prints the recorded plan when the command ends; the TUI tags its
+//! status line and title.
+
+use std::sync::{Mutex, OnceLock};
+
+/// Prefix for every message describing a skipped action
+pub const TAG: &str = "(dry-run)";
+
+static ENABLED: OnceLock<()> = OnceLock::new();
+static PLANNED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Turn dry-run mode on for the rest of the process
+pub fn enable() {
+    let _ = ENABLED.set(());
+}
+
+pub fn enabled() -> bool {
+    ENABLED.get().is_some()
+}
+
+/// In a dry run, record `action` (e.g. "docker compose stop kaspad") and
+/// return true: the caller must then skip it. Outside one, false.
+pub fn skip(action: impl FnOnce() -> String) -> bool {
+    if !enabled() {
+        return false;
+    }
+    PLANNED.lock().unwrap_or_else(|e| e.into_inner()).push(action());
+    true
+}
+
+/// Actions skipped so far, oldest first, clearing the list
+pub fn take_planned() -> Vec<String> {
+    std::mem::take(&mut *PLANNED.lock().unwrap_or_else(|e| e.into_inner()))
+}
+
+/// `message` prefixed with the dry-run tag in a dry run, else unchanged
+pub fn tag(message: &str) -> String {
+    tagged(message, enabled())
+}
+
+fn tagged(message: &str, dry_run: bool) -> String {
+    if dry_run {
+        format!("{} {}", TAG, message)
+    } else {
+        message.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Enabling is process-wide and permanent, so these tests never call
+    // `enable()`: every other test in the binary would become a dry run
+
+    #[test]
+    fn test_tagged() {
+        assert_eq!(tagged("✓ Service kaspad started", true), "(dry-run) ✓ Service kaspad started");
+        assert_eq!(tagged("✓ Service kaspad started", false), "✓ Service kaspad started");
+    }
+
+    #[test]
+    fn test_skip_is_a_no_op_unless_enabled() {
+        assert!(!enabled());
+        assert!(!skip(|| "docker compose stop kaspad".to_string()));
+        assert!(take_planned().is_empty());
+    }
+}
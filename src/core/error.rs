@@ -0,0 +1,125 @@
+//! Typed errors for the core managers
+//!
+//! `DockerManager` and `WalletManager` return these so callers (library users,
+//! the HTTP handlers) can tell "Docker is down" from "no such service" from
+//! "wrong wallet password". Failures without a dedicated variant end up in
+//! `Other`. The CLI keeps using anyhow; `?` converts.
+
+use crate::core::docker::DockerError;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// Docker daemon or the compose plugin can't be reached
+    #[error(transparent)]
+    DockerUnavailable(#[from] DockerError),
+    /// Docker answered, but the request failed
+    #[error("Docker API error: {0}")]
+    DockerApi(#[from] bollard::errors::Error),
+    #[error("Service {0} not found")]
+    ServiceNotFound(String),
+    #[error("Service {service} is not running ({status}). Start it with: igra-cli start {service}")]
+    ServiceNotRunning { service: String, status: String },
+    /// kaswallet-daemon for a worker is down or not answering
+    #[error("Wallet {worker} is unavailable: {message}")]
+    WalletUnavailable { worker: usize, message: String },
+    /// The daemon rejected the wallet password
+    #[error("Wallet {worker} is locked: {message}")]
+    WalletLocked { worker: usize, message: String },
+    /// The daemon is up but hasn't finished syncing with kaspad
+    #[error("Wallet {worker} is still syncing: {message}")]
+    WalletSyncing { worker: usize, message: String },
+    /// Bad input from the caller (address, amount, profile, ...). The web
+    /// API returns the message as is, so it must not name server paths.
+    #[error("{0}")]
+    InvalidInput(String),
+    /// docker-compose.yml doesn't validate (syntax, unset variables), so
+    /// nothing was started
+    #[error("Invalid compose configuration: {}", .0.join("; "))]
+    InvalidCompose(Vec<String>),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+impl Error {
+    pub fn invalid_input(message: impl Into<String>) -> Self {
+        Error::InvalidInput(message.into())
+    }
+
+    /// Classify a failed kaswallet-daemon call. `what` describes the call
+    /// for errors that don't fit a variant.
+    pub(crate) fn from_wallet_status(worker: usize, status: &tonic::Status, what: &str) -> Self {
+        let message = status.message().to_string();
+        match status.code() {
+            tonic::Code::Unavailable | tonic::Code::DeadlineExceeded => {
+                Error::WalletUnavailable { worker, message }
+            }
+            tonic::Code::Unauthenticated | tonic::Code::PermissionDenied => {
+                Error::WalletLocked { worker, message }
+            }
+            // kaspawalletd reports these as plain errors
+            _ if message.to_lowercase().contains("not synced") || message.to_lowercase().contains("syncing") => {
+                Error::WalletSyncing { worker, message }
+            }
+            _ if message.to_lowercase().contains("password") || message.to_lowercase().contains("locked") => {
+                Error::WalletLocked { worker, message }
+            }
+            code => Error::Other(anyhow::anyhow!("{}: {} (status: {:?})", what, message, code)),
+        }
+    }
+}
+
+/// The Docker connection problem behind an error, whether it was raised
+/// directly or came back from a manager as `Error::DockerUnavailable`
+pub fn docker_error(err: &anyhow::Error) -> Option<&DockerError> {
+    err.downcast_ref::<DockerError>().or_else(|| match err.downcast_ref::<Error>() {
+        Some(Error::DockerUnavailable(e)) => Some(e),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wallet_status_classification() {
+        let unavailable = tonic::Status::unavailable("connection refused");
+        assert!(matches!(
+            Error::from_wallet_status(1, &unavailable, "Failed to send"),
+            Error::WalletUnavailable { worker: 1, .. }
+        ));
+
+        let bad_password = tonic::Status::unknown("invalid password");
+        assert!(matches!(
+            Error::from_wallet_status(0, &bad_password, "Failed to send"),
+            Error::WalletLocked { worker: 0, .. }
+        ));
+
+        let syncing = tonic::Status::unknown("wallet daemon is not synced yet");
+        assert!(matches!(
+            Error::from_wallet_status(2, &syncing, "Failed to get balance"),
+            Error::WalletSyncing { worker: 2, .. }
+        ));
+
+        let other = tonic::Status::internal("insufficient funds");
+        let err = Error::from_wallet_status(0, &other, "Failed to send");
+        assert!(matches!(err, Error::Other(_)));
+        assert!(err.to_string().starts_with("Failed to send: insufficient funds"));
+    }
+
+    #[test]
+    fn test_docker_error_through_anyhow() {
+        let err: anyhow::Error = Error::from(DockerError::ComposeMissing).into();
+        assert!(matches!(docker_error(&err), Some(DockerError::ComposeMissing)));
+
+        let err = anyhow::Error::from(DockerError::ComposeMissing).context("starting");
+        assert!(docker_error(&err).is_some());
+
+        let err: anyhow::Error = Error::ServiceNotFound("kaspad".to_string()).into();
+        assert!(docker_error(&err).is_none());
+    }
+}
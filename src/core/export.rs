@@ -0,0 +1,163 @@
+//! Export of a TUI table (services, wallets, config, transactions) to CSV or JSON
+//!
+//! Screens hand over the rows they render; this module only formats and writes
+//! them. Files are named `igra-<name>-<timestamp>.<ext>`, never overwrite an
+//! existing file and are readable by the owner only (a config export can hold
+//! secrets).
+
+use anyhow::{Context, Result};
+use std::fs::OpenOptions;
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+/// Column headers plus one string cell per column for each row
+#[derive(Debug, Clone, Default)]
+pub struct ExportTable {
+    /// Used in the file name, e.g. "services"
+    pub name: String,
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl ExportTable {
+    pub fn new(name: &str, columns: &[&str]) -> Self {
+        Self {
+            name: name.to_string(),
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn push_row(&mut self, row: Vec<String>) {
+        self.rows.push(row);
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut out = String::new();
+        for line in std::iter::once(&self.columns).chain(&self.rows) {
+            let cells: Vec<String> = line.iter().map(|c| csv_field(c)).collect();
+            out.push_str(&cells.join(","));
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Array of objects keyed by column name
+    pub fn to_json(&self) -> Result<String> {
+        let rows: Vec<serde_json::Map<String, serde_json::Value>> = self
+            .rows
+            .iter()
+            .map(|row| {
+                self.columns
+                    .iter()
+                    .cloned()
+                    .zip(row.iter().map(|cell| serde_json::Value::String(cell.clone())))
+                    .collect()
+            })
+            .collect();
+        Ok(serde_json::to_string_pretty(&rows)?)
+    }
+
+    /// Write into `dir` under a timestamped name and return the path written
+    pub fn write(&self, dir: &Path, format: ExportFormat) -> Result<PathBuf> {
+        let contents = match format {
+            ExportFormat::Csv => self.to_csv(),
+            ExportFormat::Json => self.to_json()?,
+        };
+
+        let stem = format!("igra-{}-{}", self.name, chrono::Local::now().format("%Y%m%d-%H%M%S"));
+        // Two exports within the same second get a -2, -3 ... suffix
+        for n in 1.. {
+            let file_name = if n == 1 {
+                format!("{}.{}", stem, format.extension())
+            } else {
+                format!("{}-{}.{}", stem, n, format.extension())
+            };
+            let path = dir.join(file_name);
+
+            let mut options = OpenOptions::new();
+            options.write(true).create_new(true);
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::OpenOptionsExt;
+                options.mode(0o600);
+            }
+            match options.open(&path) {
+                Ok(mut file) => {
+                    file.write_all(contents.as_bytes())
+                        .with_context(|| format!("Failed to write {}", path.display()))?;
+                    return Ok(path);
+                }
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => continue,
+                Err(e) => return Err(e).with_context(|| format!("Failed to create {}", path.display())),
+            }
+        }
+        unreachable!("unbounded file name search")
+    }
+}
+
+/// Quote a CSV field when it contains a separator, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> ExportTable {
+        let mut table = ExportTable::new("config", &["key", "value"]);
+        table.push_row(vec!["NETWORK".to_string(), "testnet".to_string()]);
+        table.push_row(vec!["NOTE".to_string(), "a, \"b\"".to_string()]);
+        table
+    }
+
+    #[test]
+    fn test_to_csv_quotes_fields() {
+        assert_eq!(sample().to_csv(), "key,value\nNETWORK,testnet\nNOTE,\"a, \"\"b\"\"\"\n");
+    }
+
+    #[test]
+    fn test_to_json_keys_by_column() {
+        let json: serde_json::Value = serde_json::from_str(&sample().to_json().unwrap()).unwrap();
+        assert_eq!(json[0]["key"], "NETWORK");
+        assert_eq!(json[1]["value"], "a, \"b\"");
+    }
+
+    #[test]
+    fn test_write_never_overwrites() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = sample().write(dir.path(), ExportFormat::Csv).unwrap();
+        let second = sample().write(dir.path(), ExportFormat::Csv).unwrap();
+
+        assert_ne!(first, second);
+        assert!(first.file_name().unwrap().to_string_lossy().starts_with("igra-config-"));
+        assert_eq!(std::fs::read_to_string(&second).unwrap(), sample().to_csv());
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&first).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+}
@@ -1,30 +1,391 @@
-/// Health checking for IGRA Orchestra services
-///
-/// Health status is monitored through Docker container health checks
-/// and displayed in the TUI dashboard.
-///
-/// Health information is available via:
-/// - Docker API (container.health field)
-/// - TUI Dashboard Screen 1 (Services) - Health column
-/// - CLI: docker compose ps
-///
-/// This module is not currently used as health checks are implemented
-/// in docker-compose.yml and retrieved via the Docker API in docker.rs
-
-use anyhow::Result;
-
-#[allow(dead_code)]
-pub struct HealthChecker;
-
-#[allow(dead_code)]
+//! Functional health checks for IGRA Orchestra services
+//!
+//! Docker healthchecks (shown in the Services table) only say a container is
+//! up. These checks talk to the services themselves: the execution layer
+//! answers JSON-RPC, kaspad accepts wRPC connections, each running kaswallet
+//! daemon answers gRPC. Each check is independent so callers can run them
+//! concurrently and show results as they arrive.
+//!
+//! `check_dependencies` condenses Docker, kaspad and the execution layer
+//! into the traffic lights of the TUI's title strip.
+
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+use crate::core::backend::ContainerBackend;
+use crate::core::docker::ContainerInfo;
+use crate::core::l2_monitor::RPC_URL;
+use crate::core::rpc::{RpcTestConfig, RpcTester};
+use crate::core::wallet::{format_runway, hours_until_empty, WalletClientPool, WalletManager, WORKER_COUNT};
+
+/// Upper bound for a single check, whatever the service does
+const CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// kaspad wRPC (borsh) port, as used by the wallet UTXO queries
+const KASPAD_WRPC_ADDR: &str = "127.0.0.1:17210";
+
+/// Wallet checks fail when the burn rate empties the wallet sooner than this
+const MIN_WALLET_RUNWAY_HOURS: f64 = 24.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum HealthTarget {
+    ExecutionRpc,
+    KaspadRpc,
+    Wallet(usize),
+}
+
+#[derive(Debug, Clone)]
+pub struct HealthCheck {
+    pub name: String,
+    pub target: HealthTarget,
+}
+
+#[derive(Debug, Clone)]
+pub struct HealthResult {
+    pub name: String,
+    pub healthy: bool,
+    pub latency: Duration,
+    pub detail: String,
+}
+
+/// How often the TUI refreshes its dependency strip
+pub const DEPENDENCY_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// One light of the dependency strip
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DependencyState {
+    /// Not checked yet
+    #[default]
+    Unknown,
+    Up,
+    /// Container running but not answering (yet), or still starting
+    Degraded,
+    Down,
+    /// Not part of the active profiles, so not expected to run
+    Inactive,
+}
+
+/// Docker, kaspad and the execution layer at a glance
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DependencyHealth {
+    pub docker: DependencyState,
+    pub kaspad: DependencyState,
+    pub execution_layer: DependencyState,
+}
+
+/// State of a service from its container (None if it has none), whether the
+/// active profiles include it, and its probe (None if not probed)
+pub fn dependency_state(container: Option<&ContainerInfo>, wanted: bool, probe_ok: Option<bool>) -> DependencyState {
+    let Some(container) = container.filter(|c| c.state.is_running()) else {
+        return if wanted { DependencyState::Down } else { DependencyState::Inactive };
+    };
+    match (container.health.as_deref(), probe_ok) {
+        (Some("unhealthy"), _) => DependencyState::Down,
+        (Some("starting"), _) => DependencyState::Degraded,
+        (_, Some(true)) => DependencyState::Up,
+        _ => DependencyState::Degraded,
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct HealthChecker {
+    pool: WalletClientPool,
+    /// Wallet checks fail below this balance (None = any balance passes)
+    low_balance_threshold: Option<f64>,
+}
+
 impl HealthChecker {
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Share the TUI's wallet connections instead of dialing new ones
+    pub fn with_pool(pool: WalletClientPool) -> Self {
+        Self { pool, ..Self::default() }
+    }
+
+    /// Flag worker wallets below `threshold` as unhealthy
+    pub fn with_low_balance_threshold(mut self, threshold: f64) -> Self {
+        self.low_balance_threshold = Some(threshold);
+        self
+    }
+
+    /// Checks that make sense for the currently running containers
+    pub fn checks_for(running: &[String]) -> Vec<HealthCheck> {
+        let is_running = |name: &str| running.iter().any(|r| r == name);
+        let mut checks = Vec::new();
+
+        if is_running("execution-layer") {
+            checks.push(HealthCheck {
+                name: "execution-layer JSON-RPC".to_string(),
+                target: HealthTarget::ExecutionRpc,
+            });
+        }
+        if is_running("kaspad") {
+            checks.push(HealthCheck {
+                name: "kaspad wRPC".to_string(),
+                target: HealthTarget::KaspadRpc,
+            });
+        }
+        for worker_id in 0..WORKER_COUNT {
+            if is_running(&format!("kaswallet-{}", worker_id)) {
+                checks.push(HealthCheck {
+                    name: format!("kaswallet-{} gRPC", worker_id),
+                    target: HealthTarget::Wallet(worker_id),
+                });
+            }
+        }
+
+        checks
+    }
+
+    /// Run one check, bounded by CHECK_TIMEOUT
+    pub async fn run(&self, check: &HealthCheck) -> HealthResult {
+        let start = Instant::now();
+        let outcome = tokio::time::timeout(CHECK_TIMEOUT, self.probe(&check.target)).await;
+
+        let (healthy, detail) = match outcome {
+            Ok(Ok(detail)) => (true, detail),
+            Ok(Err(e)) => (false, e.to_string()),
+            Err(_) => (false, format!("no response within {}s", CHECK_TIMEOUT.as_secs())),
+        };
+
+        HealthResult {
+            name: check.name.clone(),
+            healthy,
+            latency: start.elapsed(),
+            detail,
+        }
     }
 
-    pub async fn check_all(&self) -> Result<()> {
-        // Health checks are handled by Docker healthcheck configurations
-        // in docker-compose.yml and monitored via the Docker API
-        Ok(())
+    /// Run every applicable check concurrently
+    pub async fn check_all(&self, running: &[String]) -> Vec<HealthResult> {
+        let checks = Self::checks_for(running);
+        futures::future::join_all(checks.iter().map(|check| self.run(check))).await
+    }
+
+    /// The checks cheap enough for a frequently polled probe: execution-layer
+    /// JSON-RPC (failed outright when its container isn't running) and kaspad
+    /// wRPC when it runs. Wallet checks dial gRPC per worker and stay out.
+    pub async fn check_node(&self, running: &[String]) -> Vec<HealthResult> {
+        let checks: Vec<HealthCheck> = Self::checks_for(running)
+            .into_iter()
+            .filter(|c| !matches!(c.target, HealthTarget::Wallet(_)))
+            .collect();
+        let mut results = futures::future::join_all(checks.iter().map(|check| self.run(check))).await;
+
+        if !running.iter().any(|r| r == "execution-layer") {
+            results.insert(0, HealthResult {
+                name: "execution-layer JSON-RPC".to_string(),
+                healthy: false,
+                latency: Duration::ZERO,
+                detail: "container not running".to_string(),
+            });
+        }
+        results
+    }
+
+    /// Fill the dependency strip. Docker is Down when the containers can't be
+    /// listed, leaving the others Unknown. kaspad and the execution layer are
+    /// probed only while their containers run.
+    ///
+    /// Whether one is wanted comes from the profiles the user started (any
+    /// container of the profile exists, running or not) or configured
+    /// (`configured_profiles`, i.e. COMPOSE_PROFILES), never from what happens
+    /// to be running: a crashed kaspad is Down, not Inactive. Only a service
+    /// outside all of those profiles is Inactive.
+    pub async fn check_dependencies(&self, backend: &dyn ContainerBackend, configured_profiles: &[String]) -> DependencyHealth {
+        let Ok(containers) = backend.list_containers().await else {
+            return DependencyHealth { docker: DependencyState::Down, ..Default::default() };
+        };
+        let services = backend.parse_compose_file().ok();
+        let find = |name: &str| containers.iter().find(|c| c.name == name);
+
+        let mut wanted_profiles: HashSet<&str> = configured_profiles.iter().map(String::as_str).collect();
+        if let Some(services) = services.as_ref() {
+            for container in &containers {
+                if let Some(config) = services.get(&container.name) {
+                    wanted_profiles.extend(config.profiles.iter().map(String::as_str));
+                }
+            }
+        }
+        let wanted = |name: &str| match services.as_ref().and_then(|s| s.get(name)) {
+            Some(config) => {
+                find(name).is_some()
+                    || config.profiles.is_empty()
+                    || config.profiles.iter().any(|p| wanted_profiles.contains(p.as_str()))
+            }
+            // Compose file unreadable or without it: expected if it was ever created
+            None => find(name).is_some(),
+        };
+
+        let kaspad = find("kaspad");
+        let execution_layer = find("execution-layer");
+        let (kaspad_probe, execution_probe) = tokio::join!(
+            self.probe_running(kaspad, HealthTarget::KaspadRpc),
+            self.probe_running(execution_layer, HealthTarget::ExecutionRpc),
+        );
+
+        DependencyHealth {
+            docker: DependencyState::Up,
+            kaspad: dependency_state(kaspad, wanted("kaspad"), kaspad_probe),
+            execution_layer: dependency_state(execution_layer, wanted("execution-layer"), execution_probe),
+        }
+    }
+
+    async fn probe_running(&self, container: Option<&ContainerInfo>, target: HealthTarget) -> Option<bool> {
+        if !container.is_some_and(|c| c.state.is_running()) {
+            return None;
+        }
+        Some(self.run(&HealthCheck { name: String::new(), target }).await.healthy)
+    }
+
+    async fn probe(&self, target: &HealthTarget) -> anyhow::Result<String> {
+        match target {
+            HealthTarget::ExecutionRpc => {
+                let tester = RpcTester::with_config(RpcTestConfig {
+                    timeout: CHECK_TIMEOUT,
+                    ..Default::default()
+                });
+                let result = tester.test_endpoint(RPC_URL, None).await?;
+                match (result.success, result.block_number) {
+                    (true, Some(block)) => Ok(format!("block {}", block)),
+                    (true, None) => Ok("responding".to_string()),
+                    (false, _) => Err(anyhow::anyhow!(result.error.unwrap_or_else(|| "RPC error".to_string()))),
+                }
+            }
+            HealthTarget::KaspadRpc => {
+                tokio::net::TcpStream::connect(KASPAD_WRPC_ADDR).await?;
+                Ok(format!("accepting connections on {}", KASPAD_WRPC_ADDR))
+            }
+            HealthTarget::Wallet(worker_id) => {
+                let manager = WalletManager::with_pool(self.pool.clone())?;
+                let balance = manager.get_balance(*worker_id).await?;
+                let burn_rate = manager.fee_burn_rate(*worker_id, balance);
+                let runway = burn_rate.and_then(|rate| hours_until_empty(balance, rate));
+                let burn = match (burn_rate, runway) {
+                    (Some(rate), Some(hours)) => format!(", burning {:.2}/h, empty in {}", rate, format_runway(hours)),
+                    _ => String::new(),
+                };
+                match (self.low_balance_threshold, runway) {
+                    (Some(threshold), _) if balance < threshold => Err(anyhow::anyhow!(
+                        "⚠ low balance {:.2} (threshold {:.2}){}",
+                        balance,
+                        threshold,
+                        burn
+                    )),
+                    (_, Some(hours)) if hours < MIN_WALLET_RUNWAY_HOURS => Err(anyhow::anyhow!(
+                        "⚠ runs dry soon: balance {:.2}{}",
+                        balance,
+                        burn
+                    )),
+                    _ => Ok(format!("balance {:.2}{}", balance, burn)),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checks_for_running_containers() {
+        let running = vec![
+            "execution-layer".to_string(),
+            "kaswallet-0".to_string(),
+            "kaswallet-3".to_string(),
+        ];
+        let targets: Vec<HealthTarget> = HealthChecker::checks_for(&running)
+            .into_iter()
+            .map(|c| c.target)
+            .collect();
+
+        assert_eq!(
+            targets,
+            vec![HealthTarget::ExecutionRpc, HealthTarget::Wallet(0), HealthTarget::Wallet(3)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_check_node_requires_execution_layer() {
+        let results = HealthChecker::new().check_node(&["kaswallet-0".to_string()]).await;
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].name, "execution-layer JSON-RPC");
+        assert!(!results[0].healthy);
+        assert_eq!(results[0].detail, "container not running");
+    }
+
+    #[test]
+    fn test_dependency_state() {
+        use crate::core::mock_backend::MockContainerBackend;
+        use crate::utils::ContainerState;
+
+        let mut container = MockContainerBackend::new().with_containers(&["kaspad"]).containers.remove(0);
+        assert_eq!(dependency_state(Some(&container), true, Some(true)), DependencyState::Up);
+        assert_eq!(dependency_state(Some(&container), true, Some(false)), DependencyState::Degraded);
+
+        container.health = Some("starting".to_string());
+        assert_eq!(dependency_state(Some(&container), true, Some(true)), DependencyState::Degraded);
+        container.health = Some("unhealthy".to_string());
+        assert_eq!(dependency_state(Some(&container), true, Some(true)), DependencyState::Down);
+
+        // Stopped: an outage if the active profiles want it, else just off
+        container.state = ContainerState::Stopped;
+        assert_eq!(dependency_state(Some(&container), true, None), DependencyState::Down);
+        assert_eq!(dependency_state(Some(&container), false, None), DependencyState::Inactive);
+        assert_eq!(dependency_state(None, false, None), DependencyState::Inactive);
+    }
+
+    #[tokio::test]
+    async fn test_check_dependencies_without_running_services() {
+        use crate::core::mock_backend::MockContainerBackend;
+        use crate::utils::ContainerState;
+
+        // kaspad was created (no profiles in the mock compose) but stopped;
+        // the execution layer isn't part of this setup at all
+        let mut backend = MockContainerBackend::new().with_containers(&["kaspad"]);
+        backend.containers[0].state = ContainerState::Stopped;
+
+        let health = HealthChecker::new().check_dependencies(&backend, &[]).await;
+        assert_eq!(
+            health,
+            DependencyHealth {
+                docker: DependencyState::Up,
+                kaspad: DependencyState::Down,
+                execution_layer: DependencyState::Inactive,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_check_dependencies_follows_started_and_configured_profiles() {
+        use crate::core::mock_backend::MockContainerBackend;
+        use crate::utils::ContainerState;
+
+        // The backend profile was started (block-builder still exists) but its
+        // execution layer is gone, and kaspad crashed; nothing runs
+        let mut backend = MockContainerBackend::new()
+            .with_containers(&["kaspad", "block-builder"])
+            .with_profile("kaspad", "kaspad")
+            .with_profile("block-builder", "backend")
+            .with_profile("execution-layer", "backend");
+        for container in &mut backend.containers {
+            container.state = ContainerState::Dead;
+        }
+
+        let health = HealthChecker::new().check_dependencies(&backend, &[]).await;
+        assert_eq!(health.kaspad, DependencyState::Down);
+        assert_eq!(health.execution_layer, DependencyState::Down);
+
+        // Nothing created, but the profiles are configured in COMPOSE_PROFILES
+        let backend = MockContainerBackend::new()
+            .with_profile("kaspad", "kaspad")
+            .with_profile("execution-layer", "backend");
+        let health = HealthChecker::new().check_dependencies(&backend, &["kaspad".to_string()]).await;
+        assert_eq!(health.kaspad, DependencyState::Down);
+        assert_eq!(health.execution_layer, DependencyState::Inactive);
     }
 }
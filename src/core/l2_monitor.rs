@@ -1,23 +1,36 @@
-/// L2 Transaction Monitoring
-///
-/// This module provides real-time monitoring of L2 transactions using:
-/// - Reth metrics endpoint (port 9001) for statistics
-/// - Ethereum JSON-RPC (port 9545) for transaction details
-/// - Kaspa wallet UTXO tracking for L1 fee correlation
+//! L2 Transaction Monitoring
+//!
+//! This module provides real-time monitoring of L2 transactions using:
+//! - Reth metrics endpoint (port 9001) for statistics
+//! - Ethereum JSON-RPC (port 9545) for transaction details
+//! - Optionally, a `newHeads` subscription (WebSocket, port 9546) to poll as
+//!   soon as a block lands rather than on a timer
+//! - Kaspa wallet UTXO tracking for L1 fee correlation
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use ethers::prelude::*;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 
+use crate::core::selectors::SelectorDb;
 use crate::core::wallet::{WalletManager, UtxoInfo};
+use crate::utils::{WatchIntervals, WatchMode};
 
 const METRICS_URL: &str = "http://localhost:9001/metrics";
-const RPC_URL: &str = "http://localhost:9545";
+/// Reth's pending sub-pool size, used when the txpool RPC namespace is disabled
+const PENDING_POOL_METRIC: &str = "reth_transaction_pool_pending_pool_transactions";
+pub const RPC_URL: &str = "http://localhost:9545";
+pub const WS_URL: &str = "ws://localhost:9546";
+/// Between attempts to (re)open the head subscription; polling covers the wait
+const WS_RECONNECT_DELAY: Duration = Duration::from_secs(5);
+/// Poll this often even while subscribed, in case the stream stalls silently
+const WS_SAFETY_POLL: Duration = Duration::from_secs(15);
 
 /// Transaction type classification
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -28,6 +41,25 @@ pub enum TransactionType {
     Unknown,
 }
 
+impl TransactionType {
+    /// Every type, in the order breakdowns list them
+    pub const ALL: [TransactionType; 4] = [
+        TransactionType::Transfer,
+        TransactionType::Contract,
+        TransactionType::Entry,
+        TransactionType::Unknown,
+    ];
+
+    fn index(&self) -> usize {
+        match self {
+            TransactionType::Transfer => 0,
+            TransactionType::Contract => 1,
+            TransactionType::Entry => 2,
+            TransactionType::Unknown => 3,
+        }
+    }
+}
+
 impl std::fmt::Display for TransactionType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -53,6 +85,12 @@ pub struct TransactionInfo {
     pub status: bool,
     pub tx_type: TransactionType,
     pub l1_fee: Option<f64>, // KAS fee paid on L1 for entry transactions
+    /// Decoded contract method name, or raw 0x selector when unknown
+    #[serde(default)]
+    pub method: Option<String>,
+    /// Its block was dropped from the canonical chain and it hasn't been re-included (yet)
+    #[serde(default)]
+    pub reorged: bool,
 }
 
 impl TransactionInfo {
@@ -70,6 +108,14 @@ impl TransactionInfo {
     pub fn value_ikas(&self) -> f64 {
         wei_to_ikas(self.value)
     }
+
+    /// Type label including the decoded method, e.g. "CONTRACT transfer"
+    pub fn type_label(&self) -> String {
+        match self.method {
+            Some(ref method) => format!("{} {}", self.tx_type, method),
+            None => self.tx_type.to_string(),
+        }
+    }
 }
 
 /// L2 network statistics
@@ -83,9 +129,236 @@ pub struct Statistics {
     pub total_l1_fees_kas: f64,
     pub start_time: Option<DateTime<Utc>>,
     pub last_block_time: Option<DateTime<Utc>>,
+    /// (wall-clock instant, block number) for each observed chain head advance
+    pub block_observations: VecDeque<(Instant, u64)>,
+    /// Chain reorganizations seen since monitoring started
+    pub reorgs: u64,
+    /// (wall-clock instant, total_transactions) after each poll with new blocks
+    pub tps_samples: VecDeque<(Instant, u64)>,
+    /// Transactions waiting in the node's mempool; None when the node exposes
+    /// neither the txpool RPC nor reth's pool metrics
+    pub pending_transactions: Option<u64>,
+    /// Running totals per transaction type, indexed like `TransactionType::ALL`
+    pub by_type: [TypeTotals; 4],
+}
+
+/// Count and L2 fees of one transaction type
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TypeTotals {
+    pub count: u64,
+    pub gas_fees_ikas: f64,
+}
+
+/// One row of the per-type breakdown; the percentages of all rows add up to
+/// 100 (or are all 0 before anything was seen)
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeBreakdown {
+    pub tx_type: TransactionType,
+    pub count: u64,
+    pub count_pct: u64,
+    pub gas_fees_ikas: f64,
+    pub fee_pct: u64,
 }
 
+/// Direction of TPS in the latest window compared with the one before
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TpsTrend {
+    Up,
+    Down,
+    Steady,
+    /// No transactions in the latest window
+    Idle,
+}
+
+/// Observations needed before block time figures are shown
+const MIN_BLOCK_SAMPLES: usize = 3;
+/// How long block observations are kept for averaging
+const BLOCK_WINDOW: Duration = Duration::from_secs(300);
+const BLOCK_RATE_WINDOW: Duration = Duration::from_secs(60);
+/// Length of each of the two windows compared for the TPS trend
+const TPS_TREND_WINDOW: Duration = Duration::from_secs(30);
+/// Samples needed in each window before a trend is shown
+const MIN_TPS_TREND_SAMPLES: usize = 5;
+/// Relative change below which TPS counts as steady
+const TPS_TREND_TOLERANCE: f64 = 0.1;
+
 impl Statistics {
+    /// Record that the chain head advanced to `block` at `at`
+    /// Uses monotonic time rather than block timestamps, which can drift
+    pub fn record_block(&mut self, block: u64, at: Instant) {
+        if let Some(&(_, last)) = self.block_observations.back() {
+            if block <= last {
+                return;
+            }
+        }
+        self.block_observations.push_back((at, block));
+
+        while let Some(&(oldest, _)) = self.block_observations.front() {
+            if self.block_observations.len() > MIN_BLOCK_SAMPLES && at.duration_since(oldest) > BLOCK_WINDOW {
+                self.block_observations.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Average time between blocks, or None until enough samples exist
+    pub fn avg_block_time(&self) -> Option<Duration> {
+        if self.block_observations.len() < MIN_BLOCK_SAMPLES {
+            return None;
+        }
+        let (first_at, first_block) = *self.block_observations.front()?;
+        let (last_at, last_block) = *self.block_observations.back()?;
+        let blocks = last_block.saturating_sub(first_block);
+        if blocks == 0 {
+            return None;
+        }
+        Some(last_at.duration_since(first_at) / blocks as u32)
+    }
+
+    /// Blocks produced over the last minute, or None until enough samples exist
+    pub fn blocks_per_minute(&self) -> Option<u64> {
+        self.blocks_per_minute_at(Instant::now())
+    }
+
+    fn blocks_per_minute_at(&self, now: Instant) -> Option<u64> {
+        if self.block_observations.len() < MIN_BLOCK_SAMPLES {
+            return None;
+        }
+        let (_, last_block) = *self.block_observations.back()?;
+        // Baseline is the newest observation at or before the window start
+        let baseline = self
+            .block_observations
+            .iter()
+            .rev()
+            .find(|(at, _)| now.saturating_duration_since(*at) >= BLOCK_RATE_WINDOW)
+            .or_else(|| self.block_observations.front())
+            .map(|(_, block)| *block)?;
+        Some(last_block.saturating_sub(baseline))
+    }
+
+    /// Time since the chain head last advanced
+    pub fn since_last_block(&self) -> Option<Duration> {
+        self.block_observations.back().map(|(at, _)| at.elapsed())
+    }
+
+    /// Average block time for display ("calculating…" until enough samples exist)
+    pub fn block_time_display(&self) -> String {
+        match self.avg_block_time() {
+            Some(avg) => format!("{:.2}s", avg.as_secs_f64()),
+            None => String::from("calculating…"),
+        }
+    }
+
+    /// Block rate over the last minute for display
+    pub fn block_rate_display(&self) -> String {
+        match self.blocks_per_minute() {
+            Some(rate) => format!("{} blk/min", rate),
+            None => String::from("calculating…"),
+        }
+    }
+
+    /// True when no block has been seen for well over the usual interval
+    pub fn is_stalled(&self) -> bool {
+        match (self.avg_block_time(), self.since_last_block()) {
+            (Some(avg), Some(since)) => since > (avg * 10).max(Duration::from_secs(30)),
+            _ => false,
+        }
+    }
+
+    /// Record the transaction total at `at`, for the TPS trend
+    pub fn record_tps_sample(&mut self, at: Instant) {
+        self.tps_samples.push_back((at, self.total_transactions));
+
+        // Keep two windows, plus the sample the older one starts from
+        while self.tps_samples.len() > 1
+            && at.saturating_duration_since(self.tps_samples[1].0) > TPS_TREND_WINDOW * 2
+        {
+            self.tps_samples.pop_front();
+        }
+    }
+
+    /// TPS of the last window against the window before, or None until both
+    /// windows have enough samples
+    pub fn tps_trend(&self) -> Option<TpsTrend> {
+        self.tps_trend_at(Instant::now())
+    }
+
+    fn tps_trend_at(&self, now: Instant) -> Option<TpsTrend> {
+        let window_start = now.checked_sub(TPS_TREND_WINDOW)?;
+        let previous_start = window_start.checked_sub(TPS_TREND_WINDOW)?;
+
+        // Transaction total as of `t` (newest sample at or before it)
+        let total_at = |t: Instant| self.tps_samples.iter().rev().find(|(at, _)| *at <= t).map(|(_, total)| *total);
+        let count_between = |from: Instant, to: Instant| {
+            self.tps_samples.iter().filter(|(at, _)| *at > from && *at <= to).count()
+        };
+        if count_between(previous_start, window_start) < MIN_TPS_TREND_SAMPLES
+            || count_between(window_start, now) < MIN_TPS_TREND_SAMPLES
+        {
+            return None;
+        }
+
+        let (start, middle, end) = (total_at(previous_start)?, total_at(window_start)?, total_at(now)?);
+        let previous = middle.saturating_sub(start) as f64;
+        let current = end.saturating_sub(middle) as f64;
+
+        Some(if current == 0.0 {
+            TpsTrend::Idle
+        } else if current > previous * (1.0 + TPS_TREND_TOLERANCE) {
+            TpsTrend::Up
+        } else if current < previous * (1.0 - TPS_TREND_TOLERANCE) {
+            TpsTrend::Down
+        } else {
+            TpsTrend::Steady
+        })
+    }
+
+    /// Count one transaction in the totals and fee sums
+    pub fn add_transaction(&mut self, tx: &TransactionInfo) {
+        self.total_transactions += 1;
+
+        if tx.status {
+            self.successful_transactions += 1;
+        } else {
+            self.failed_transactions += 1;
+        }
+
+        let gas_fee = tx.gas_fee_ikas();
+        self.total_gas_fees_ikas += gas_fee;
+
+        let totals = &mut self.by_type[tx.tx_type.index()];
+        totals.count += 1;
+        totals.gas_fees_ikas += gas_fee;
+
+        if let Some(l1_fee) = tx.l1_fee {
+            self.total_l1_fees_kas += l1_fee;
+        }
+    }
+
+    /// Counts and fee totals for every type, including those not seen yet so
+    /// the display keeps its shape
+    pub fn type_breakdown(&self) -> Vec<TypeBreakdown> {
+        let counts: Vec<f64> = self.by_type.iter().map(|t| t.count as f64).collect();
+        let fees: Vec<f64> = self.by_type.iter().map(|t| t.gas_fees_ikas).collect();
+        let count_pcts = whole_percentages(&counts);
+        let fee_pcts = whole_percentages(&fees);
+
+        TransactionType::ALL
+            .iter()
+            .map(|tx_type| {
+                let i = tx_type.index();
+                TypeBreakdown {
+                    tx_type: tx_type.clone(),
+                    count: self.by_type[i].count,
+                    count_pct: count_pcts[i],
+                    gas_fees_ikas: self.by_type[i].gas_fees_ikas,
+                    fee_pct: fee_pcts[i],
+                }
+            })
+            .collect()
+    }
+
     pub fn tps(&self) -> f64 {
         if let Some(start) = self.start_time {
             let elapsed = Utc::now().signed_duration_since(start).num_seconds() as f64;
@@ -156,6 +429,63 @@ impl L1FeeTracker {
     }
 }
 
+/// Blocks remembered for reorg detection; deeper reorgs are only partly reported
+const REORG_WINDOW: usize = 64;
+
+/// Recent canonical blocks as we saw them, so a block that changes hash
+/// under us can be detected and its transactions reported as reorged
+#[derive(Debug, Default)]
+struct ChainTracker {
+    /// (block number, block hash, transaction hashes), oldest first
+    blocks: VecDeque<(u64, H256, Vec<String>)>,
+    /// Transactions from dropped blocks that haven't been re-included
+    orphaned: HashSet<String>,
+}
+
+impl ChainTracker {
+    fn record(&mut self, number: u64, hash: H256, tx_hashes: Vec<String>) {
+        self.blocks.push_back((number, hash, tx_hashes));
+        while self.blocks.len() > REORG_WINDOW {
+            self.blocks.pop_front();
+        }
+    }
+
+    fn hash_at(&self, number: u64) -> Option<H256> {
+        self.blocks.iter().find(|(n, _, _)| *n == number).map(|(_, h, _)| *h)
+    }
+
+    /// Forget every block above `fork` and return their transaction hashes
+    fn rewind_to(&mut self, fork: u64) -> Vec<String> {
+        let mut dropped = Vec::new();
+        while self.blocks.back().map(|(n, _, _)| *n > fork).unwrap_or(false) {
+            if let Some((_, _, tx_hashes)) = self.blocks.pop_back() {
+                dropped.extend(tx_hashes);
+            }
+        }
+        self.orphaned.extend(dropped.iter().cloned());
+        dropped
+    }
+
+    /// A transaction showed up in a new block; true if it was orphaned before
+    fn reinclude(&mut self, tx_hash: &str) -> bool {
+        self.orphaned.remove(tx_hash)
+    }
+}
+
+/// Result of one poll: new canonical transactions (chain order) plus the
+/// hashes of previously reported transactions whose block was reorged out
+#[derive(Debug, Default)]
+pub struct PollUpdate {
+    pub transactions: Vec<TransactionInfo>,
+    pub reorged: Vec<String>,
+}
+
+impl PollUpdate {
+    pub fn is_empty(&self) -> bool {
+        self.transactions.is_empty() && self.reorged.is_empty()
+    }
+}
+
 /// Main L2 transaction monitor
 pub struct TransactionMonitor {
     provider: Provider<Http>,
@@ -163,6 +493,117 @@ pub struct TransactionMonitor {
     l1_tracker: L1FeeTracker,
     statistics: Arc<RwLock<Statistics>>,
     last_block: Arc<RwLock<u64>>,
+    chain: Arc<RwLock<ChainTracker>>,
+    selectors: SelectorDb,
+    /// Set once the node rejects `txpool_status`, so later polls go straight to metrics
+    txpool_unsupported: AtomicBool,
+}
+
+/// Timers for the transaction poll and L1 refresh. A slow RPC delays the
+/// next tick instead of firing a burst of catch-up polls.
+pub fn poll_timers(intervals: WatchIntervals) -> (PollTrigger, tokio::time::Interval) {
+    (PollTrigger::new(intervals), timer(intervals.l1()))
+}
+
+fn timer(period: Duration) -> tokio::time::Interval {
+    let mut timer = tokio::time::interval(period);
+    timer.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    timer
+}
+
+/// When to run the next transaction poll.
+///
+/// In poll mode that's every `poll_ms`. In WS mode a background task keeps a
+/// `newHeads` subscription open and each head wakes the poll; while the
+/// subscription is down (node not up yet, WS port closed, reconnecting) the
+/// `poll_ms` timer takes over again. Nothing is lost across a reconnect:
+/// `poll()` fetches every block after the last one it fetched, retrying a
+/// block that failed rather than skipping it, so the poll fired on
+/// resubscribing backfills the gap by block range.
+pub struct PollTrigger {
+    poll: tokio::time::Interval,
+    heads: Option<HeadSubscription>,
+}
+
+struct HeadSubscription {
+    /// One `()` per head; capacity 1 since one poll catches up on any number
+    rx: tokio::sync::mpsc::Receiver<()>,
+    subscribed: Arc<AtomicBool>,
+    /// Polls while subscribed, in case the stream stalls without closing
+    safety: tokio::time::Interval,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl PollTrigger {
+    /// Must be called within a Tokio runtime; WS mode spawns the subscriber
+    pub fn new(intervals: WatchIntervals) -> Self {
+        let heads = (intervals.mode == WatchMode::Ws).then(|| {
+            let (tx, rx) = tokio::sync::mpsc::channel(1);
+            let subscribed = Arc::new(AtomicBool::new(false));
+            let task = tokio::spawn(follow_heads(WS_URL, tx, Arc::clone(&subscribed)));
+            HeadSubscription { rx, subscribed, safety: timer(WS_SAFETY_POLL), task }
+        });
+        Self { poll: timer(intervals.poll()), heads }
+    }
+
+    /// Whether new heads are currently pushed rather than polled for
+    pub fn is_subscribed(&self) -> bool {
+        self.heads.as_ref().is_some_and(|h| h.subscribed.load(Ordering::Relaxed))
+    }
+
+    pub async fn tick(&mut self) {
+        let subscribed = self.is_subscribed();
+        let Some(heads) = self.heads.as_mut() else {
+            self.poll.tick().await;
+            return;
+        };
+        tokio::select! {
+            Some(()) = heads.rx.recv() => {}
+            _ = self.poll.tick(), if !subscribed => {}
+            _ = heads.safety.tick(), if subscribed => {}
+        }
+    }
+}
+
+impl Drop for PollTrigger {
+    fn drop(&mut self) {
+        if let Some(heads) = &self.heads {
+            heads.task.abort();
+        }
+    }
+}
+
+/// Keep a `newHeads` subscription open, nudging `heads` once per block and
+/// once more on every (un)subscribe so the watcher polls straight away:
+/// after subscribing to backfill whatever landed while disconnected, after
+/// losing the stream to fall back to its timer without waiting out a tick.
+async fn follow_heads(url: &str, heads: tokio::sync::mpsc::Sender<()>, subscribed: Arc<AtomicBool>) {
+    use tokio::sync::mpsc::error::TrySendError;
+    let nudge = |heads: &tokio::sync::mpsc::Sender<()>| !matches!(heads.try_send(()), Err(TrySendError::Closed(_)));
+
+    loop {
+        if let Ok(provider) = Provider::<Ws>::connect(url).await {
+            if let Ok(mut stream) = provider.subscribe_blocks().await {
+                subscribed.store(true, Ordering::Relaxed);
+                if !nudge(&heads) {
+                    return;
+                }
+                while futures::StreamExt::next(&mut stream).await.is_some() {
+                    if !nudge(&heads) {
+                        return;
+                    }
+                }
+                subscribed.store(false, Ordering::Relaxed);
+                if !nudge(&heads) {
+                    return;
+                }
+            }
+        }
+        if heads.is_closed() {
+            return;
+        }
+        tokio::time::sleep(WS_RECONNECT_DELAY).await;
+    }
 }
 
 impl TransactionMonitor {
@@ -182,6 +623,9 @@ impl TransactionMonitor {
             l1_tracker,
             statistics: Arc::new(RwLock::new(Statistics::default())),
             last_block: Arc::new(RwLock::new(0)),
+            chain: Arc::new(RwLock::new(ChainTracker::default())),
+            selectors: SelectorDb::builtin(),
+            txpool_unsupported: AtomicBool::new(false),
         })
     }
 
@@ -201,9 +645,18 @@ impl TransactionMonitor {
             l1_tracker,
             statistics: Arc::new(RwLock::new(Statistics::default())),
             last_block: Arc::new(RwLock::new(0)),
+            chain: Arc::new(RwLock::new(ChainTracker::default())),
+            selectors: SelectorDb::builtin(),
+            txpool_unsupported: AtomicBool::new(false),
         })
     }
 
+    /// Use a custom selector database (e.g. extended with a user ABI)
+    pub fn with_selectors(mut self, selectors: SelectorDb) -> Self {
+        self.selectors = selectors;
+        self
+    }
+
     /// Fetch and parse Prometheus metrics
     pub async fn fetch_metrics(&self) -> Result<HashMap<String, String>> {
         let response = self.http_client
@@ -238,10 +691,16 @@ impl TransactionMonitor {
 
     /// Fetch transactions from a specific block
     pub async fn fetch_block_transactions(&self, block_number: u64) -> Result<Vec<TransactionInfo>> {
+        Ok(self.fetch_block(block_number).await?.1)
+    }
+
+    /// Fetch a block's hash and transactions
+    async fn fetch_block(&self, block_number: u64) -> Result<(H256, Vec<TransactionInfo>)> {
         let block = self.provider
             .get_block_with_txs(BlockNumber::Number(block_number.into()))
             .await?
             .context(format!("Block {} not found", block_number))?;
+        let block_hash = block.hash.context(format!("Block {} has no hash (pending)", block_number))?;
 
         let mut transactions = Vec::new();
 
@@ -259,6 +718,7 @@ impl TransactionMonitor {
 
             // Classify transaction type
             let tx_type = classify_transaction(&tx);
+            let method = method_label(&self.selectors, &tx);
 
             // Get L1 fee if it's an entry transaction
             let l1_fee = if tx_type == TransactionType::Entry {
@@ -280,12 +740,47 @@ impl TransactionMonitor {
                 status,
                 tx_type,
                 l1_fee,
+                method,
+                reorged: false,
             };
 
             transactions.push(tx_info);
         }
 
-        Ok(transactions)
+        Ok((block_hash, transactions))
+    }
+
+    /// Current canonical hash of a block
+    async fn canonical_hash(&self, block_number: u64) -> Result<Option<H256>> {
+        let block = self.provider
+            .get_block(BlockNumber::Number(block_number.into()))
+            .await?;
+        Ok(block.and_then(|b| b.hash))
+    }
+
+    /// Walk back from the last processed block until our recorded hash matches
+    /// the canonical one. Blocks above that fork point were reorged out; their
+    /// transactions are returned and `last_block` is rewound so the
+    /// replacement blocks get fetched.
+    async fn detect_reorg(&self, chain: &mut ChainTracker, last_block: &mut u64) -> Result<Vec<String>> {
+        let mut fork = *last_block;
+        while let Some(known) = chain.hash_at(fork) {
+            if self.canonical_hash(fork).await? == Some(known) {
+                break;
+            }
+            if fork == 0 {
+                break;
+            }
+            fork -= 1;
+        }
+
+        if fork == *last_block {
+            return Ok(Vec::new());
+        }
+
+        *last_block = fork;
+        self.statistics.write().await.reorgs += 1;
+        Ok(chain.rewind_to(fork))
     }
 
     /// Update statistics with new transactions
@@ -297,20 +792,9 @@ impl TransactionMonitor {
         }
 
         for tx in transactions {
-            stats.total_transactions += 1;
-
-            if tx.status {
-                stats.successful_transactions += 1;
-            } else {
-                stats.failed_transactions += 1;
-            }
-
-            stats.total_gas_fees_ikas += tx.gas_fee_ikas();
-
-            if let Some(l1_fee) = tx.l1_fee {
-                stats.total_l1_fees_kas += l1_fee;
-            }
+            stats.add_transaction(tx);
         }
+        stats.record_tps_sample(Instant::now());
 
         if let Some(last_tx) = transactions.last() {
             stats.current_block = last_tx.block_number;
@@ -325,33 +809,107 @@ impl TransactionMonitor {
 
     /// Poll for new blocks and transactions
     pub async fn poll_new_transactions(&self) -> Result<Vec<TransactionInfo>> {
+        Ok(self.poll().await?.transactions)
+    }
+
+    /// Poll for new blocks, reporting transactions dropped by a reorg since the last poll
+    pub async fn poll(&self) -> Result<PollUpdate> {
         let current_block = self.get_block_number().await?;
         let mut last_block = self.last_block.write().await;
+        let mut chain = self.chain.write().await;
+
+        let reorged = self.detect_reorg(&mut chain, &mut last_block).await?;
 
         if current_block <= *last_block {
-            return Ok(Vec::new());
+            self.refresh_pending().await;
+            return Ok(PollUpdate { transactions: Vec::new(), reorged });
         }
 
+        self.statistics.write().await.record_block(current_block, Instant::now());
+
         let mut all_transactions = Vec::new();
+        let mut failed = None;
 
-        // Fetch transactions from all new blocks
+        // Fetch transactions from all new blocks. Stop at the first one that
+        // fails, so the next poll starts again from it instead of skipping it.
         for block_num in (*last_block + 1)..=current_block {
-            match self.fetch_block_transactions(block_num).await {
-                Ok(txs) => {
+            match self.fetch_block(block_num).await {
+                Ok((hash, txs)) => {
+                    chain.record(block_num, hash, txs.iter().map(|tx| tx.hash.clone()).collect());
                     all_transactions.extend(txs);
+                    *last_block = block_num;
                 }
                 Err(e) => {
-                    eprintln!("Error fetching block {}: {}", block_num, e);
+                    failed = Some(e.context(format!("Failed to fetch block {}", block_num)));
+                    break;
                 }
             }
         }
 
-        *last_block = current_block;
+        // Re-included transactions were already counted the first time round
+        let first_seen: Vec<TransactionInfo> = all_transactions
+            .iter()
+            .filter(|tx| !chain.reinclude(&tx.hash))
+            .cloned()
+            .collect();
+        self.update_statistics(&first_seen).await;
+        self.refresh_pending().await;
+
+        // Blocks fetched before the failure are returned; the error shows up
+        // on the next poll if that block still can't be fetched
+        let update = PollUpdate { transactions: all_transactions, reorged };
+        match failed {
+            Some(e) if update.is_empty() => Err(e),
+            _ => Ok(update),
+        }
+    }
+
+    /// Sample the mempool only after this poll's blocks were counted (and
+    /// while `last_block` is still locked), so a transaction confirmed in them
+    /// has already left the pool and isn't counted as both pending and confirmed
+    async fn refresh_pending(&self) {
+        let pending = self.fetch_pending_count().await;
+        self.statistics.write().await.pending_transactions = pending;
+    }
+
+    /// Pending mempool size from `txpool_status`, falling back to reth's pool metric
+    pub async fn fetch_pending_count(&self) -> Option<u64> {
+        if !self.txpool_unsupported.load(Ordering::Relaxed) {
+            match self.txpool_status().await {
+                Ok(Some(pending)) => return Some(pending),
+                Ok(None) => self.txpool_unsupported.store(true, Ordering::Relaxed),
+                Err(_) => {}
+            }
+        }
+
+        let metrics = self.fetch_metrics().await.ok()?;
+        metric_value(&metrics, PENDING_POOL_METRIC)
+    }
 
-        // Update statistics
-        self.update_statistics(&all_transactions).await;
+    /// Ok(None) when the node answered with a JSON-RPC error (namespace not enabled)
+    async fn txpool_status(&self) -> Result<Option<u64>> {
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "txpool_status",
+            "params": [],
+        });
+        let response: serde_json::Value = self.http_client
+            .post(RPC_URL)
+            .json(&request)
+            .send()
+            .await?
+            .json()
+            .await?;
 
-        Ok(all_transactions)
+        if response.get("error").is_some() {
+            return Ok(None);
+        }
+        response
+            .get("result")
+            .and_then(parse_txpool_pending)
+            .map(Some)
+            .context("Malformed txpool_status response")
     }
 
     /// Update L1 UTXO data
@@ -360,6 +918,25 @@ impl TransactionMonitor {
     }
 }
 
+/// `pending` from a `txpool_status` result (`{"pending": "0x1a", "queued": "0x0"}`)
+fn parse_txpool_pending(result: &serde_json::Value) -> Option<u64> {
+    match result.get("pending")? {
+        serde_json::Value::String(hex) => u64::from_str_radix(hex.trim_start_matches("0x"), 16).ok(),
+        serde_json::Value::Number(n) => n.as_u64(),
+        _ => None,
+    }
+}
+
+/// Value of a Prometheus metric from `fetch_metrics`, with or without labels
+fn metric_value(metrics: &HashMap<String, String>, name: &str) -> Option<u64> {
+    let labelled = format!("{}{{", name);
+    metrics
+        .iter()
+        .find(|(key, _)| key.as_str() == name || key.starts_with(&labelled))
+        .and_then(|(_, value)| value.trim().parse::<f64>().ok())
+        .map(|value| value as u64)
+}
+
 /// Classify transaction type based on transaction data
 fn classify_transaction(tx: &Transaction) -> TransactionType {
     // Entry transactions typically have no 'to' address (contract creation)
@@ -370,20 +947,254 @@ fn classify_transaction(tx: &Transaction) -> TransactionType {
         return TransactionType::Contract;
     }
 
-    if !tx.input.is_empty() && tx.input.0.len() > 4 {
-        // Has input data - likely contract interaction
+    if tx.input.0.len() >= 4 {
+        // Has a method selector - contract interaction
         // Check if it matches entry transaction pattern
         // For now, just classify as contract
         TransactionType::Contract
-    } else if tx.value.is_zero() {
-        TransactionType::Contract
     } else {
+        // Empty or too-short input can't call a method: plain transfer
         TransactionType::Transfer
     }
 }
 
+/// Method shown for a transaction: "deploy" for a contract creation, whose
+/// input is init code rather than calldata, else the decoded selector of a call
+fn method_label(selectors: &SelectorDb, tx: &Transaction) -> Option<String> {
+    match tx.to {
+        None => Some("deploy".to_string()),
+        Some(_) => selectors.label(&tx.input),
+    }
+}
+
 /// Convert Wei to iKAS (same as Ether)
 fn wei_to_ikas(wei: U256) -> f64 {
     let eth_str = ethers::utils::format_units(wei, "ether").unwrap_or_else(|_| String::from("0"));
     eth_str.parse().unwrap_or(0.0)
 }
+
+/// Shares of `values` as whole percentages adding up to exactly 100, rounding
+/// by largest remainder; all zeros when there is nothing to share
+fn whole_percentages(values: &[f64]) -> Vec<u64> {
+    let total: f64 = values.iter().filter(|v| **v > 0.0).sum();
+    if total <= 0.0 {
+        return vec![0; values.len()];
+    }
+
+    let exact: Vec<f64> = values.iter().map(|v| v.max(0.0) / total * 100.0).collect();
+    let mut pcts: Vec<u64> = exact.iter().map(|p| p.floor() as u64).collect();
+    let missing = 100u64.saturating_sub(pcts.iter().sum());
+
+    // Only values that are actually present get rounded up, so a type never
+    // shows 1% without any transactions
+    let mut order: Vec<usize> = (0..values.len()).filter(|&i| values[i] > 0.0).collect();
+    order.sort_by(|&a, &b| {
+        (exact[b] - exact[b].floor())
+            .partial_cmp(&(exact[a] - exact[a].floor()))
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    for &i in order.iter().cycle().take(missing as usize) {
+        pcts[i] += 1;
+    }
+    pcts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chain_tracker_rewind_and_reinclude() {
+        let mut chain = ChainTracker::default();
+        chain.record(10, H256::from_low_u64_be(10), vec!["a".to_string()]);
+        chain.record(11, H256::from_low_u64_be(11), vec!["b".to_string(), "c".to_string()]);
+        chain.record(12, H256::from_low_u64_be(12), vec![]);
+
+        assert_eq!(chain.hash_at(11), Some(H256::from_low_u64_be(11)));
+        assert_eq!(chain.rewind_to(10), vec!["b".to_string(), "c".to_string()]);
+        assert_eq!(chain.hash_at(11), None);
+        assert_eq!(chain.hash_at(10), Some(H256::from_low_u64_be(10)));
+
+        // "b" comes back in the replacement block, "c" stays orphaned
+        assert!(chain.reinclude("b"));
+        assert!(!chain.reinclude("b"));
+        assert!(chain.orphaned.contains("c"));
+    }
+
+    #[test]
+    fn test_chain_tracker_window() {
+        let mut chain = ChainTracker::default();
+        for n in 0..(REORG_WINDOW as u64 + 10) {
+            chain.record(n, H256::from_low_u64_be(n), vec![]);
+        }
+        assert_eq!(chain.blocks.len(), REORG_WINDOW);
+        assert_eq!(chain.hash_at(0), None);
+    }
+
+    #[test]
+    fn test_classify_transaction() {
+        let mut tx = Transaction {
+            to: Some(Address::zero()),
+            ..Default::default()
+        };
+        assert_eq!(classify_transaction(&tx), TransactionType::Transfer);
+
+        // Too short to hold a selector
+        tx.input = Bytes::from(vec![0xa9, 0x05]);
+        assert_eq!(classify_transaction(&tx), TransactionType::Transfer);
+
+        tx.input = Bytes::from(vec![0xa9, 0x05, 0x9c, 0xbb]);
+        assert_eq!(classify_transaction(&tx), TransactionType::Contract);
+
+        tx.to = None;
+        tx.input = Bytes::new();
+        assert_eq!(classify_transaction(&tx), TransactionType::Contract);
+    }
+
+    #[test]
+    fn test_method_label() {
+        let selectors = SelectorDb::builtin();
+        // transfer(address,uint256)
+        let calldata = Bytes::from(vec![0xa9, 0x05, 0x9c, 0xbb, 0x00]);
+        let mut tx = Transaction {
+            to: Some(Address::zero()),
+            input: calldata.clone(),
+            ..Default::default()
+        };
+        assert_eq!(method_label(&selectors, &tx), selectors.label(&calldata));
+        assert!(method_label(&selectors, &tx).is_some());
+
+        tx.input = Bytes::new();
+        assert_eq!(method_label(&selectors, &tx), None);
+
+        // Init code that happens to start like a known selector is still a deploy
+        tx.to = None;
+        tx.input = calldata;
+        assert_eq!(method_label(&selectors, &tx).as_deref(), Some("deploy"));
+    }
+
+    #[test]
+    fn test_block_time_needs_samples() {
+        let mut stats = Statistics::default();
+        let start = Instant::now();
+        stats.record_block(10, start);
+        stats.record_block(11, start + Duration::from_secs(1));
+        assert!(stats.avg_block_time().is_none());
+        assert_eq!(stats.block_time_display(), "calculating…");
+
+        stats.record_block(12, start + Duration::from_secs(2));
+        assert_eq!(stats.avg_block_time(), Some(Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn test_block_time_spans_skipped_blocks() {
+        let mut stats = Statistics::default();
+        let start = Instant::now();
+        // Polling can observe several blocks at once
+        stats.record_block(100, start);
+        stats.record_block(104, start + Duration::from_secs(2));
+        stats.record_block(108, start + Duration::from_secs(4));
+        stats.record_block(108, start + Duration::from_secs(5));
+        assert_eq!(stats.avg_block_time(), Some(Duration::from_millis(500)));
+    }
+
+    #[test]
+    fn test_blocks_per_minute() {
+        let mut stats = Statistics::default();
+        let start = Instant::now();
+        for i in 0..=90u64 {
+            stats.record_block(i, start + Duration::from_secs(i));
+        }
+        let now = start + Duration::from_secs(90);
+        assert_eq!(stats.blocks_per_minute_at(now), Some(60));
+    }
+
+    #[test]
+    fn test_pending_count_parsing() {
+        let result = serde_json::json!({ "pending": "0x1a", "queued": "0x2" });
+        assert_eq!(parse_txpool_pending(&result), Some(26));
+        assert_eq!(parse_txpool_pending(&serde_json::json!({ "pending": 3 })), Some(3));
+        assert_eq!(parse_txpool_pending(&serde_json::json!({})), None);
+
+        let mut metrics = HashMap::new();
+        metrics.insert("reth_transaction_pool_pending_pool_transactions".to_string(), "7".to_string());
+        assert_eq!(metric_value(&metrics, PENDING_POOL_METRIC), Some(7));
+        assert_eq!(metric_value(&metrics, "reth_transaction_pool_queued_pool_transactions"), None);
+    }
+
+    #[test]
+    fn test_tps_trend() {
+        let start = Instant::now();
+        // One sample per second, `rate(i)` transactions in second i
+        let run = |rate: &dyn Fn(u64) -> u64, seconds: u64| {
+            let mut stats = Statistics::default();
+            for i in 1..=seconds {
+                stats.total_transactions += rate(i);
+                stats.record_tps_sample(start + Duration::from_secs(i));
+            }
+            stats.tps_trend_at(start + Duration::from_secs(seconds))
+        };
+
+        // Not enough history for two windows yet
+        assert_eq!(run(&|_| 5, 10), None);
+
+        assert_eq!(run(&|_| 5, 70), Some(TpsTrend::Steady));
+        assert_eq!(run(&|i| if i > 40 { 10 } else { 5 }, 70), Some(TpsTrend::Up));
+        assert_eq!(run(&|i| if i > 40 { 2 } else { 5 }, 70), Some(TpsTrend::Down));
+        assert_eq!(run(&|i| if i > 40 { 0 } else { 5 }, 70), Some(TpsTrend::Idle));
+    }
+
+    #[test]
+    fn test_type_breakdown() {
+        let tx = |tx_type: TransactionType, gas_used: u64| TransactionInfo {
+            hash: String::new(),
+            from: String::new(),
+            to: None,
+            value: U256::zero(),
+            gas_used: Some(U256::from(gas_used)),
+            gas_price: U256::from(1_000_000_000u64),
+            block_number: 0,
+            timestamp: Utc::now(),
+            status: true,
+            tx_type,
+            l1_fee: None,
+            method: None,
+            reorged: false,
+        };
+
+        // Nothing seen: every type listed, all zero
+        let empty = Statistics::default().type_breakdown();
+        assert_eq!(empty.iter().map(|r| r.tx_type.clone()).collect::<Vec<_>>(), TransactionType::ALL.to_vec());
+        assert!(empty.iter().all(|r| r.count == 0 && r.count_pct == 0 && r.fee_pct == 0));
+
+        let mut stats = Statistics::default();
+        for _ in 0..2 {
+            stats.add_transaction(&tx(TransactionType::Transfer, 21_000));
+        }
+        stats.add_transaction(&tx(TransactionType::Contract, 200_000));
+
+        let rows = stats.type_breakdown();
+        assert_eq!(rows[0].count, 2);
+        assert_eq!(rows[1].count, 1);
+        assert_eq!((rows[2].count, rows[2].count_pct, rows[2].fee_pct), (0, 0, 0));
+        // 66.7 / 33.3 rounds to 67 / 33, not 67 / 34 or 66 / 33
+        assert_eq!(rows.iter().map(|r| r.count_pct).sum::<u64>(), 100);
+        assert_eq!((rows[0].count_pct, rows[1].count_pct), (67, 33));
+        assert!(rows[1].fee_pct > rows[0].fee_pct);
+        assert_eq!(rows.iter().map(|r| r.fee_pct).sum::<u64>(), 100);
+        assert!((rows.iter().map(|r| r.gas_fees_ikas).sum::<f64>() - stats.total_gas_fees_ikas).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_whole_percentages() {
+        assert_eq!(whole_percentages(&[1.0, 1.0, 1.0]), vec![34, 33, 33]);
+        assert_eq!(whole_percentages(&[0.0, 0.0]), vec![0, 0]);
+        assert_eq!(whole_percentages(&[1.0, 0.0, 0.0, 0.0]), vec![100, 0, 0, 0]);
+        for n in 1..50u64 {
+            let values: Vec<f64> = (0..4).map(|i| ((n * (i + 3)) % 7) as f64).collect();
+            if values.iter().any(|v| *v > 0.0) {
+                assert_eq!(whole_percentages(&values).iter().sum::<u64>(), 100, "{:?}", values);
+            }
+        }
+    }
+}
@@ -1,12 +1,12 @@
-/// Log parsing for extracting meaningful status information from service logs
-///
-/// This module provides intelligent parsing of container logs to extract:
-/// - Sync status (synced, syncing, stalled)
-/// - Performance metrics (TPS, latency, block rate)
-/// - Block heights and numbers
-/// - Transaction throughput
-/// - Health indicators
-/// - Individual log line parsing (timestamp, level, module, message)
+//! Log parsing for extracting meaningful status information from service logs
+//!
+//! This module provides intelligent parsing of container logs to extract:
+//! - Sync status (synced, syncing, stalled)
+//! - Performance metrics (TPS, latency, block rate)
+//! - Block heights and numbers
+//! - Transaction throughput
+//! - Health indicators
+//! - Individual log line parsing (timestamp, level, module, message)
 
 use regex::Regex;
 use std::sync::OnceLock;
@@ -23,6 +23,13 @@ pub struct ParsedLogLine {
     pub raw_line: String,       // Original line for fallback
 }
 
+impl ParsedLogLine {
+    /// Whether this line passes the detail view level filter (None = all)
+    pub fn matches_filter(&self, filter: Option<&LogLevel>) -> bool {
+        filter.is_none_or(|level| &self.level == level)
+    }
+}
+
 /// Log level enum for consistent handling
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LogLevel {
@@ -35,6 +42,8 @@ pub enum LogLevel {
 }
 
 impl LogLevel {
+    /// Level named anywhere in `s`; never fails, unlike `FromStr`
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Self {
         let upper = s.to_uppercase();
         if upper.contains("ERROR") {
@@ -63,6 +72,18 @@ impl LogLevel {
         }
     }
 
+    /// Filter label for headers and status messages
+    pub fn filter_label(filter: Option<&LogLevel>) -> &'static str {
+        match filter {
+            None | Some(LogLevel::Unknown) => "ALL",
+            Some(LogLevel::Error) => "ERROR",
+            Some(LogLevel::Warn) => "WARN",
+            Some(LogLevel::Info) => "INFO",
+            Some(LogLevel::Debug) => "DEBUG",
+            Some(LogLevel::Trace) => "TRACE",
+        }
+    }
+
     pub fn color(&self) -> ratatui::style::Color {
         use ratatui::style::Color;
         match self {
@@ -77,7 +98,7 @@ impl LogLevel {
 }
 
 /// Strip ANSI color codes from log strings
-fn strip_ansi_codes(text: &str) -> String {
+pub fn strip_ansi_codes(text: &str) -> String {
     static ANSI_RE: OnceLock<Regex> = OnceLock::new();
     let re = ANSI_RE.get_or_init(|| {
         Regex::new(r"\x1b\[[0-9;]*[a-zA-Z]").unwrap()
@@ -431,7 +452,7 @@ fn parse_health_check_logs(logs: &str) -> ServiceMetrics {
         if let (Some(checkpoint), Some(latest)) = (caps.get(1), caps.get(2)) {
             let cp: u64 = checkpoint.as_str().parse().unwrap_or(0);
             let lt: u64 = latest.as_str().parse().unwrap_or(0);
-            let lag = if lt > cp { lt - cp } else { 0 };
+            let lag = lt.saturating_sub(cp);
 
             if lag == 0 {
                 metrics.status_text = Some("Synced".to_string());
@@ -496,38 +517,6 @@ fn format_large_number(num: u64) -> String {
     result.chars().rev().collect()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_kaspad_synced() {
-        let logs = "2025-10-18 20:45:37.476+00:00 [INFO ] Accepted 7 blocks ...0f7b via relay\n\
-                    2025-10-18 20:45:46.689+00:00 [INFO ] Tx throughput stats: 5.00 u-tps";
-
-        let metrics = parse_kaspad_logs(logs);
-        assert_eq!(metrics.status_text, Some("Synced".to_string()));
-        assert_eq!(metrics.primary_metric, Some("5.00 TPS".to_string()));
-        assert!(metrics.is_healthy);
-    }
-
-    #[test]
-    fn test_execution_layer() {
-        let logs = "Block added to canonical chain number=7705704 txs=15";
-
-        let metrics = parse_execution_layer_logs(logs);
-        assert_eq!(metrics.status_text, Some("Active".to_string()));
-        assert_eq!(metrics.primary_metric, Some("#7705704".to_string()));
-        assert_eq!(metrics.secondary_metric, Some("15 txs".to_string()));
-    }
-
-    #[test]
-    fn test_format_large_number() {
-        assert_eq!(format_large_number(1234567), "1,234,567");
-        assert_eq!(format_large_number(283910951), "283,910,951");
-    }
-}
-
 /// Parse a Docker Compose log line into components
 /// Handles multiple log formats from different services
 pub fn parse_docker_log_line(line: &str) -> ParsedLogLine {
@@ -535,12 +524,12 @@ pub fn parse_docker_log_line(line: &str) -> ParsedLogLine {
 
     // Try to split on first pipe (service separator)
     if let Some(pipe_idx) = line.find('|') {
-        let service = line[..pipe_idx].trim().to_string();
-        let rest_with_ansi = line[pipe_idx + 1..].trim();
-
-        // Strip ANSI color codes that docker adds
-        let rest_cleaned = strip_ansi_codes(rest_with_ansi);
-        let rest = rest_cleaned.as_str();
+        // Compose colours the prefix when attached to a terminal
+        let service = strip_ansi_codes(&line[..pipe_idx]).trim().to_string();
+        // Strip ANSI color codes that docker adds, then trim: a reset code
+        // in front of the timestamp would otherwise leave a leading space
+        let rest_cleaned = strip_ansi_codes(&line[pipe_idx + 1..]);
+        let rest = rest_cleaned.trim();
 
         // Try kaspad format: "YYYY-MM-DD HH:MM:SS.sss+TZ [LEVEL ] message"
         let kaspad_regex = Regex::new(
@@ -746,3 +735,174 @@ pub fn parse_docker_log_line(line: &str) -> ParsedLogLine {
         raw_line,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_filter() {
+        let line = parse_docker_log_line("viaduct  | [2025-10-21T08:48:40Z ERROR viaduct::uni_storage] write failed");
+        assert_eq!(line.level, LogLevel::Error);
+        assert!(line.matches_filter(None));
+        assert!(line.matches_filter(Some(&LogLevel::Error)));
+        assert!(!line.matches_filter(Some(&LogLevel::Info)));
+    }
+
+    #[test]
+    fn test_kaspad_synced() {
+        let logs = "2025-10-18 20:45:37.476+00:00 [INFO ] Accepted 7 blocks ...0f7b via relay\n\
+                    2025-10-18 20:45:46.689+00:00 [INFO ] Tx throughput stats: 5.00 u-tps";
+
+        let metrics = parse_kaspad_logs(logs);
+        assert_eq!(metrics.status_text, Some("Synced".to_string()));
+        assert_eq!(metrics.primary_metric, Some("5.00 TPS".to_string()));
+        assert!(metrics.is_healthy);
+    }
+
+    #[test]
+    fn test_execution_layer() {
+        let logs = "Block added to canonical chain number=7705704 txs=15";
+
+        let metrics = parse_execution_layer_logs(logs);
+        assert_eq!(metrics.status_text, Some("Active".to_string()));
+        assert_eq!(metrics.primary_metric, Some("#7705704".to_string()));
+        assert_eq!(metrics.secondary_metric, Some("15 txs".to_string()));
+    }
+
+    #[test]
+    fn test_strip_ansi_codes() {
+        assert_eq!(
+            strip_ansi_codes("\x1b[36mkaspad  |\x1b[0m \x1b[1;31mERROR\x1b[0m boom"),
+            "kaspad  | ERROR boom"
+        );
+        assert_eq!(strip_ansi_codes("plain line"), "plain line");
+    }
+
+    /// Parse `line` and check the fields the log view shows
+    fn assert_parsed(line: &str, service: &str, timestamp: &str, level: LogLevel, module: &str, message: &str) {
+        let parsed = parse_docker_log_line(line);
+        assert_eq!(parsed.service, service, "service of {:?}", line);
+        assert_eq!(parsed.timestamp, timestamp, "timestamp of {:?}", line);
+        assert_eq!(parsed.level, level, "level of {:?}", line);
+        assert_eq!(parsed.module_path, module, "module of {:?}", line);
+        assert_eq!(parsed.message, message, "message of {:?}", line);
+        assert_eq!(parsed.raw_line, line);
+    }
+
+    #[test]
+    fn test_parse_log_line_formats() {
+        // kaspad: space-separated date, offset, padded bracketed level
+        assert_parsed(
+            "kaspad  | 2025-10-18 20:45:37.476+00:00 [INFO ] Accepted 7 blocks ...0f7b via relay",
+            "kaspad", "2025-10-18 20:45:37.476+00:00", LogLevel::Info, "", "Accepted 7 blocks ...0f7b via relay",
+        );
+        assert_parsed(
+            "kaspad  | 2025-10-18 20:45:37 [WARN ] Peer 1.2.3.4:16111 misbehaving",
+            "kaspad", "2025-10-18 20:45:37", LogLevel::Warn, "", "Peer 1.2.3.4:16111 misbehaving",
+        );
+
+        // reth: ISO timestamp, right-aligned level, "target: message"
+        assert_parsed(
+            "execution-layer  | 2025-10-21T08:48:40.123456Z  INFO reth_node_events::node: Block added to canonical chain number=7705704 txs=15",
+            "execution-layer", "2025-10-21T08:48:40.123456Z", LogLevel::Info, "reth_node_events::node",
+            "Block added to canonical chain number=7705704 txs=15",
+        );
+
+        // block-builder: time only, source location between target and message
+        assert_parsed(
+            "block-builder  | 08:48:40.123 INFO block_builder::builder: src/builder.rs:120: Built block 42 with 3 txs",
+            "block-builder", "08:48:40.123", LogLevel::Info, "block_builder::builder", "Built block 42 with 3 txs",
+        );
+        assert_parsed(
+            "block-builder  | 08:48:40 ERROR block_builder::rpc: submit failed",
+            "block-builder", "08:48:40", LogLevel::Error, "block_builder::rpc", "submit failed",
+        );
+
+        // env_logger: "[timestamp LEVEL target] message", source location dropped from the target
+        assert_parsed(
+            "viaduct  | [2025-10-21T08:48:40Z INFO  viaduct::uni_storage] Stored batch 7",
+            "viaduct", "2025-10-21T08:48:40Z", LogLevel::Info, "viaduct::uni_storage", "Stored batch 7",
+        );
+        assert_parsed(
+            "block-builder  | [2025-10-21T08:48:40.5Z DEBUG block_builder::rpc: src/rpc.rs:55] retrying",
+            "block-builder", "2025-10-21T08:48:40.5Z", LogLevel::Debug, "block_builder::rpc", "retrying",
+        );
+        assert_eq!(parse_docker_log_line("viaduct | [2025-10-21T08:48:40Z INFO  viaduct::uni_storage] x").module_short, "uni_storage");
+
+        // ANSI colours around the prefix, timestamp, level and target
+        assert_parsed(
+            "\x1b[36mexecution-layer  |\x1b[0m \x1b[2m2025-10-21T08:48:40.123456Z\x1b[0m \x1b[32m INFO\x1b[0m \x1b[2mreth::cli\x1b[0m\x1b[2m:\x1b[0m Starting reth",
+            "execution-layer", "2025-10-21T08:48:40.123456Z", LogLevel::Info, "reth::cli", "Starting reth",
+        );
+
+        // Lowercase level: only the timestamp is recognised, the level is guessed from the text
+        assert_parsed(
+            "traefik  | 2025-10-21T08:48:40Z warn certificate renewal due",
+            "traefik", "2025-10-21T08:48:40Z", LogLevel::Warn, "", "warn certificate renewal due",
+        );
+    }
+
+    #[test]
+    fn test_parse_log_line_adversarial() {
+        // No pipe: kept whole, nothing guessed
+        assert_parsed("", "", "", LogLevel::Unknown, "", "");
+        assert_parsed(
+            "2025-10-18 20:45:37 [INFO ] no prefix",
+            "", "", LogLevel::Unknown, "", "2025-10-18 20:45:37 [INFO ] no prefix",
+        );
+
+        // Pipe with nothing around it
+        assert_parsed("|", "", "", LogLevel::Unknown, "", "");
+        assert_parsed("kaspad  |   ", "kaspad", "", LogLevel::Unknown, "", "");
+        assert_parsed("  |  |  ", "", "", LogLevel::Unknown, "", "|");
+
+        // Only the first pipe separates the service
+        assert_parsed(
+            "kaspad | 2025-10-18 20:45:37 [INFO ] a | b",
+            "kaspad", "2025-10-18 20:45:37", LogLevel::Info, "", "a | b",
+        );
+
+        // "::" and ": " inside the message don't move the target boundary
+        assert_parsed(
+            "viaduct | [2025-10-21T08:48:40Z ERROR viaduct::rpc] call Foo::bar::baz() failed: timeout",
+            "viaduct", "2025-10-21T08:48:40Z", LogLevel::Error, "viaduct::rpc", "call Foo::bar::baz() failed: timeout",
+        );
+        assert_parsed(
+            "execution-layer | 2025-10-21T08:48:40Z  WARN reth::rpc: request failed: std::io::Error: broken pipe",
+            "execution-layer", "2025-10-21T08:48:40Z", LogLevel::Warn, "reth::rpc", "request failed: std::io::Error: broken pipe",
+        );
+        assert_parsed(
+            "block-builder | 08:48:40 INFO Upgrade::apply done",
+            "block-builder", "08:48:40", LogLevel::Info, "", "Upgrade::apply done",
+        );
+        assert_parsed(
+            "block-builder | 08:48:40 WARN block_builder: src/main.rs:10: failed: std::io::Error",
+            "block-builder", "08:48:40", LogLevel::Warn, "block_builder", "failed: std::io::Error",
+        );
+
+        // Non-ASCII text around the split points
+        assert_parsed(
+            "viaduct | [2025-10-21T08:48:40Z INFO  viaduct::ü] héllo ✓",
+            "viaduct", "2025-10-21T08:48:40Z", LogLevel::Info, "viaduct::ü", "héllo ✓",
+        );
+
+        // Very long lines are kept intact
+        let long_message = "x".repeat(200_000);
+        assert_parsed(
+            &format!("kaspad | 2025-10-18 20:45:37 [ERROR] {}", long_message),
+            "kaspad", "2025-10-18 20:45:37", LogLevel::Error, "", &long_message,
+        );
+        let long_target = format!("a{}", "::b".repeat(10_000));
+        let parsed = parse_docker_log_line(&format!("viaduct | [2025-10-21T08:48:40Z INFO  {}] done", long_target));
+        assert_eq!(parsed.module_path, long_target);
+        assert_eq!(parsed.module_short, "b");
+        assert_eq!(parsed.message, "done");
+    }
+
+    #[test]
+    fn test_format_large_number() {
+        assert_eq!(format_large_number(1234567), "1,234,567");
+        assert_eq!(format_large_number(283910951), "283,910,951");
+    }
+}
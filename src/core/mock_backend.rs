@@ -0,0 +1,297 @@
+//! In-memory container and wallet backends for tests
+//!
+//! Built for the library's tests and with the `mock-backends` feature, which
+//! the binary's tests turn on through a dev-dependency on this package. They
+//! serve canned containers, logs and wallets and record the actions asked of
+//! them; neither Docker nor a wallet daemon is touched.
+
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::core::backend::{ContainerBackend, WalletBackend};
+use crate::core::docker::{ComposeServiceConfig, ContainerInfo, ContainerStats, ServiceConfigComparison};
+use crate::core::error::{Error, Result};
+use crate::core::network::Network;
+use crate::core::wallet::{FeeEstimate, SendFeePolicy, UtxoInfo, WalletClientPool, WalletInfo};
+use crate::core::wallet_history::WalletTotals;
+use crate::core::wallet_password::WalletPassword;
+use crate::utils::ContainerState;
+
+#[derive(Default)]
+pub struct MockContainerBackend {
+    pub network: Network,
+    pub containers: Vec<ContainerInfo>,
+    /// Log output by service
+    pub logs: HashMap<String, String>,
+    /// Compose profiles by service; a service listed here is in the compose
+    /// file even without a container
+    pub profiles: HashMap<String, Vec<String>>,
+    calls: Mutex<Vec<String>>,
+}
+
+impl MockContainerBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Put `service` in compose `profile`
+    pub fn with_profile(mut self, service: &str, profile: &str) -> Self {
+        self.profiles.entry(service.to_string()).or_default().push(profile.to_string());
+        self
+    }
+
+    /// Add a running container per name
+    pub fn with_containers(mut self, names: &[&str]) -> Self {
+        self.containers.extend(names.iter().map(|name| ContainerInfo {
+            id: format!("{}-id", name),
+            name: name.to_string(),
+            image: format!("{}:latest", name),
+            status: "Up 1 hour".to_string(),
+            state: ContainerState::Running,
+            health: None,
+            created: 0,
+            started_at: None,
+            finished_at: None,
+            ports: Vec::new(),
+            metrics: Default::default(),
+        }));
+        self
+    }
+
+    /// Actions so far, e.g. "start_service kaspad", oldest first
+    pub fn calls(&self) -> Vec<String> {
+        self.calls.lock().unwrap().clone()
+    }
+
+    fn record(&self, call: String) -> Result<()> {
+        self.calls.lock().unwrap().push(call);
+        Ok(())
+    }
+
+    fn ensure_exists(&self, service: &str) -> Result<()> {
+        if self.containers.iter().any(|c| c.name == service) {
+            Ok(())
+        } else {
+            Err(Error::ServiceNotFound(service.to_string()))
+        }
+    }
+}
+
+#[async_trait]
+impl ContainerBackend for MockContainerBackend {
+    fn network(&self) -> Network {
+        self.network
+    }
+
+    async fn list_containers(&self) -> Result<Vec<ContainerInfo>> {
+        Ok(self.containers.clone())
+    }
+
+    async fn get_all_container_stats(&self) -> Result<HashMap<String, ContainerStats>> {
+        Ok(HashMap::new())
+    }
+
+    async fn get_logs(&self, service: &str, _tail: Option<usize>, _strip_ansi: bool) -> Result<String> {
+        self.ensure_exists(service)?;
+        Ok(self.logs.get(service).cloned().unwrap_or_default())
+    }
+
+    async fn get_service_config_comparison(&self, service: &str) -> Result<ServiceConfigComparison> {
+        self.ensure_exists(service)?;
+        let yaml_config = self.parse_compose_file()?.remove(service).expect("every container is a service");
+        Ok(ServiceConfigComparison {
+            service_name: service.to_string(),
+            yaml_config,
+            running_config: None,
+            config_drift: Vec::new(),
+        })
+    }
+
+    /// One service per container or profiled service, with nothing else configured
+    fn parse_compose_file(&self) -> Result<HashMap<String, ComposeServiceConfig>> {
+        let names: std::collections::BTreeSet<&str> =
+            self.containers.iter().map(|c| c.name.as_str()).chain(self.profiles.keys().map(String::as_str)).collect();
+        Ok(names
+            .into_iter()
+            .map(|name| {
+                let image = self.containers.iter().find(|c| c.name == name).map(|c| c.image.clone());
+                let config = ComposeServiceConfig {
+                    image: Some(image.unwrap_or_else(|| format!("{}:latest", name))),
+                    environment: HashMap::new(),
+                    volumes: Vec::new(),
+                    ports: Vec::new(),
+                    networks: Vec::new(),
+                    profiles: self.profiles.get(name).cloned().unwrap_or_default(),
+                    restart: None,
+                    command: None,
+                    entrypoint: None,
+                    depends_on: Vec::new(),
+                };
+                (name.to_string(), config)
+            })
+            .collect())
+    }
+
+    async fn start_service(&self, service: &str) -> Result<()> {
+        self.ensure_exists(service)?;
+        self.record(format!("start_service {}", service))
+    }
+
+    async fn stop_service(&self, service: &str) -> Result<()> {
+        self.ensure_exists(service)?;
+        self.record(format!("stop_service {}", service))
+    }
+
+    async fn restart_service(&self, service: &str) -> Result<()> {
+        self.ensure_exists(service)?;
+        self.record(format!("restart_service {}", service))
+    }
+
+    async fn start_profile(&self, profile: &str) -> Result<Vec<String>> {
+        self.record(format!("start_profile {}", profile))?;
+        Ok(Vec::new())
+    }
+
+    async fn stop_profile(&self, profile: &str) -> Result<()> {
+        self.record(format!("stop_profile {}", profile))
+    }
+
+    async fn recreate_profile(&self, profile: &str) -> Result<Vec<String>> {
+        self.record(format!("recreate_profile {}", profile))?;
+        Ok(Vec::new())
+    }
+
+    async fn recreate_services(&self, profiles: &[String], services: &[String]) -> Result<()> {
+        self.record(format!("recreate_services {} {}", profiles.join(","), services.join(",")))
+    }
+
+    async fn pull_images(&self) -> Result<()> {
+        self.record("pull_images".to_string())
+    }
+}
+
+#[derive(Default)]
+pub struct MockWalletBackend {
+    pub wallets: Vec<WalletInfo>,
+    /// (address, available, pending) by worker
+    pub addresses: HashMap<usize, Vec<(String, f64, f64)>>,
+    pub utxos: HashMap<usize, Vec<UtxoInfo>>,
+    pub totals: HashMap<usize, WalletTotals>,
+    pool: WalletClientPool,
+    calls: Mutex<Vec<String>>,
+}
+
+impl MockWalletBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a running, synced wallet per (worker, balance)
+    pub fn with_wallets(mut self, wallets: &[(usize, f64)]) -> Self {
+        self.wallets.extend(wallets.iter().map(|&(worker_id, balance)| WalletInfo {
+            worker_id,
+            address: Some(format!("kaspatest:worker{}", worker_id)),
+            balance: Some(balance),
+            container_running: true,
+            initial_balance: Some(balance),
+            fees_spent: Some(0.0),
+            fee_burn_rate: None,
+            daemon_synced: Some(true),
+            is_locked: false,
+        }));
+        self
+    }
+
+    /// Actions so far, e.g. "send 0 kaspatest:x 100000000", oldest first
+    pub fn calls(&self) -> Vec<String> {
+        self.calls.lock().unwrap().clone()
+    }
+
+    fn wallet(&self, worker_id: usize) -> Result<&WalletInfo> {
+        self.wallets.iter().find(|w| w.worker_id == worker_id).ok_or_else(|| Error::WalletUnavailable {
+            worker: worker_id,
+            message: "no such mock wallet".to_string(),
+        })
+    }
+}
+
+#[async_trait]
+impl WalletBackend for MockWalletBackend {
+    async fn list_wallets(&self) -> Result<Vec<WalletInfo>> {
+        Ok(self.wallets.clone())
+    }
+
+    async fn get_wallet_info(&self, worker_id: usize) -> Result<WalletInfo> {
+        self.wallet(worker_id).cloned()
+    }
+
+    async fn get_balance_detailed(&self, worker_id: usize) -> Result<Vec<(String, f64, f64)>> {
+        self.wallet(worker_id)?;
+        Ok(self.addresses.get(&worker_id).cloned().unwrap_or_default())
+    }
+
+    async fn get_utxos(&self, worker_id: usize) -> Result<Vec<UtxoInfo>> {
+        self.wallet(worker_id)?;
+        Ok(self.utxos.get(&worker_id).cloned().unwrap_or_default())
+    }
+
+    fn wallet_totals(&self, worker_id: usize) -> Result<WalletTotals> {
+        self.wallet(worker_id)?;
+        Ok(self.totals.get(&worker_id).copied().unwrap_or_default())
+    }
+
+    /// From the canned UTXOs, as `WalletManager::estimate_fee` selects them
+    async fn estimate_fee(
+        &self,
+        worker_id: usize,
+        _to_address: &str,
+        amount_sompi: Option<u64>,
+        fee_policy: SendFeePolicy,
+    ) -> Result<FeeEstimate> {
+        self.wallet(worker_id)?;
+        let (fee_rate, at_least) = fee_policy.estimate_rate();
+        let utxos: Vec<u64> = self.utxos.get(&worker_id).into_iter().flatten().map(|u| u.amount_sompi).collect();
+        FeeEstimate::from_utxos(&utxos, amount_sompi, fee_rate)
+            .map(|estimate| FeeEstimate { at_least, ..estimate })
+            .ok_or_else(|| Error::invalid_input("Amount plus fee exceeds the spendable balance"))
+    }
+
+    async fn send_transaction(
+        &self,
+        worker_id: usize,
+        to_address: &str,
+        amount_sompi: u64,
+        _password: &WalletPassword,
+        send_all: bool,
+    ) -> Result<String> {
+        self.wallet(worker_id)?;
+        let amount = if send_all { "all".to_string() } else { amount_sompi.to_string() };
+        self.calls.lock().unwrap().push(format!("send {} {} {}", worker_id, to_address, amount));
+        Ok(format!("Transaction sent!\nTxIDs: mock-{}\nSigned 1 transactions", worker_id))
+    }
+
+    /// The last per-address entry, else the wallet's address
+    async fn current_receive_address(&self, worker_id: usize) -> Result<Option<String>> {
+        let wallet = self.wallet(worker_id)?;
+        let last = self.addresses.get(&worker_id).and_then(|addresses| addresses.last());
+        Ok(last.map(|(address, _, _)| address.clone()).or_else(|| wallet.address.clone()))
+    }
+
+    async fn new_receive_address(&self, worker_id: usize) -> Result<String> {
+        self.wallet(worker_id)?;
+        let mut calls = self.calls.lock().unwrap();
+        calls.push(format!("new_address {}", worker_id));
+        let generated = calls.iter().filter(|c| c.starts_with("new_address ")).count();
+        Ok(format!("kaspatest:worker{}-{}", worker_id, generated))
+    }
+
+    async fn generate_wallet(&self, worker_id: usize, _password: Option<&WalletPassword>) -> Result<String> {
+        self.calls.lock().unwrap().push(format!("generate_wallet {}", worker_id));
+        Ok(format!("kaspatest:worker{}", worker_id))
+    }
+
+    fn pool(&self) -> &WalletClientPool {
+        &self.pool
+    }
+}
@@ -1,20 +1,39 @@
 pub mod docker;
+pub mod backend;
+pub mod podman;
+#[cfg(any(test, feature = "mock-backends"))]
+pub mod mock_backend;
+pub mod error;
 pub mod config;
+pub mod config_audit;
 pub mod health;
 pub mod backup;
+pub mod dry_run;
 pub mod rpc;
 pub mod wallet;
+pub mod wallet_password;
+pub mod wallet_history;
 pub mod ssl;
 pub mod metrics;
 pub mod log_parser;
+pub mod network;
+pub mod ports;
 pub mod versions;
 pub mod reth_metrics;
 pub mod l2_monitor;
+pub mod alerts;
+pub mod recorder;
+pub mod export;
+pub mod secrets;
+pub mod selectors;
 pub mod storage;
+pub mod status_summary;
 pub mod updater;
 
 pub use docker::DockerManager;
+pub use error::Error;
 pub use config::ConfigManager;
+pub use network::Network;
 pub use log_parser::{ParsedLogLine, LogLevel, parse_docker_log_line};
 
 // Re-exports for future use (currently unused)
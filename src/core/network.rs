@@ -0,0 +1,103 @@
+//! Kaspa network the node runs on, read once from NETWORK in .env
+
+use anyhow::{anyhow, Context, Result};
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Network {
+    #[default]
+    Testnet,
+    Mainnet,
+}
+
+impl Network {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Network::Testnet => "testnet",
+            Network::Mainnet => "mainnet",
+        }
+    }
+
+    /// Prefix of this network's addresses (`kaspa:` / `kaspatest:`)
+    pub fn address_prefix(&self) -> &'static str {
+        match self {
+            Network::Testnet => "kaspatest",
+            Network::Mainnet => "kaspa",
+        }
+    }
+
+    /// NETWORK from an .env file, with surrounding quotes stripped. A missing
+    /// file or key is testnet; a value that doesn't parse is an error.
+    pub fn from_env_file(env_file: &Path) -> Result<Self> {
+        if !env_file.exists() {
+            return Ok(Network::default());
+        }
+        let content = std::fs::read_to_string(env_file)
+            .with_context(|| format!("Failed to read {}", env_file.display()))?;
+        Self::from_env_content(&content)
+    }
+
+    fn from_env_content(content: &str) -> Result<Self> {
+        content
+            .lines()
+            .filter_map(|line| line.trim().split_once('='))
+            .find(|(key, _)| key.trim() == "NETWORK")
+            .map(|(_, value)| value.trim().trim_matches('"').parse())
+            .transpose()
+            .map(Option::unwrap_or_default)
+    }
+
+    /// Currency ticker shown next to balances and amounts
+    pub fn currency_label(&self) -> &'static str {
+        match self {
+            Network::Testnet => "TKAS",
+            Network::Mainnet => "KAS",
+        }
+    }
+}
+
+impl FromStr for Network {
+    type Err = anyhow::Error;
+
+    /// Unknown values are an error rather than a silent testnet default, so a
+    /// typo on a mainnet node can't end up showing testnet labels
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "testnet" => Ok(Network::Testnet),
+            "mainnet" => Ok(Network::Mainnet),
+            other => Err(anyhow!(
+                "Unrecognized NETWORK value '{}' in .env (expected 'testnet' or 'mainnet')",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Network {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_network() {
+        assert_eq!("mainnet".parse::<Network>().unwrap(), Network::Mainnet);
+        assert_eq!(" Testnet ".parse::<Network>().unwrap(), Network::Testnet);
+        assert!("testnet-11".parse::<Network>().is_err());
+        assert_eq!(Network::Mainnet.currency_label(), "KAS");
+        assert_eq!(Network::Testnet.currency_label(), "TKAS");
+    }
+
+    #[test]
+    fn test_network_from_env_content() {
+        assert_eq!(Network::from_env_content("NETWORK=\"mainnet\"\n").unwrap(), Network::Mainnet);
+        assert_eq!(Network::from_env_content("# NETWORK=mainnet\nDOMAIN=a\n").unwrap(), Network::Testnet);
+        assert!(Network::from_env_content("NETWORK=mainnt\n").is_err());
+    }
+}
@@ -0,0 +1,111 @@
+//! Podman container backend
+//!
+//! Podman serves the Docker API on its own socket and accepts the same CLI
+//! commands, so `DockerManager` does the work. What differs is compose:
+//! podman-compose (run directly or as the provider behind `podman compose`)
+//! has not always honoured `--profile` for `up` and `stop`, so profiles are
+//! started, stopped and recreated by naming their services.
+
+use async_trait::async_trait;
+use std::collections::HashMap;
+
+use crate::core::backend::ContainerBackend;
+use crate::core::docker::{ComposeServiceConfig, ContainerInfo, ContainerStats, DockerManager, ServiceConfigComparison};
+use crate::core::error::Result;
+use crate::core::network::Network;
+
+pub struct PodmanBackend {
+    docker: DockerManager,
+}
+
+impl PodmanBackend {
+    pub fn new(docker: DockerManager) -> Self {
+        Self { docker }
+    }
+
+    /// Run `action` (`up -d`, `stop`) on the services `--profile` would select
+    async fn profile_command(&self, action: &[&str], profile: &str) -> Result<()> {
+        let members = self.docker.profile_members(profile)?;
+        let mut args = action.to_vec();
+        args.extend(members.iter().map(|s| s.as_str()));
+        self.docker.compose_change(&args).await
+    }
+}
+
+#[async_trait]
+impl ContainerBackend for PodmanBackend {
+    fn network(&self) -> Network {
+        self.docker.network()
+    }
+
+    async fn list_containers(&self) -> Result<Vec<ContainerInfo>> {
+        self.docker.list_containers().await
+    }
+
+    async fn get_all_container_stats(&self) -> Result<HashMap<String, ContainerStats>> {
+        self.docker.get_all_container_stats().await
+    }
+
+    async fn get_logs(&self, service: &str, tail: Option<usize>, strip_ansi: bool) -> Result<String> {
+        self.docker.get_logs(service, tail, strip_ansi).await
+    }
+
+    async fn get_service_config_comparison(&self, service: &str) -> Result<ServiceConfigComparison> {
+        self.docker.get_service_config_comparison(service).await
+    }
+
+    fn parse_compose_file(&self) -> Result<HashMap<String, ComposeServiceConfig>> {
+        self.docker.parse_compose_file()
+    }
+
+    async fn start_service(&self, service: &str) -> Result<()> {
+        self.docker.start_service(service).await
+    }
+
+    async fn stop_service(&self, service: &str) -> Result<()> {
+        self.docker.stop_service(service).await
+    }
+
+    async fn restart_service(&self, service: &str) -> Result<()> {
+        self.docker.restart_service(service).await
+    }
+
+    async fn start_profile(&self, profile: &str) -> Result<Vec<String>> {
+        if self.docker.compose().handles_profiles() {
+            return self.docker.start_profile(profile).await;
+        }
+        let warnings = self.docker.check_profile_compose(profile).await?;
+        self.profile_command(&["up", "-d"], profile).await?;
+        Ok(warnings)
+    }
+
+    async fn stop_profile(&self, profile: &str) -> Result<()> {
+        if self.docker.compose().handles_profiles() {
+            return self.docker.stop_profile(profile).await;
+        }
+        self.profile_command(&["stop"], profile).await
+    }
+
+    async fn recreate_profile(&self, profile: &str) -> Result<Vec<String>> {
+        if self.docker.compose().handles_profiles() {
+            return self.docker.recreate_profile(profile).await;
+        }
+        let members = self.docker.profile_members(profile)?;
+        let mut args = vec!["up", "-d", "--force-recreate"];
+        args.extend(members.iter().map(|s| s.as_str()));
+        self.docker.compose_change(&args).await?;
+        Ok(members)
+    }
+
+    async fn recreate_services(&self, profiles: &[String], services: &[String]) -> Result<()> {
+        if self.docker.compose().handles_profiles() {
+            return self.docker.recreate_services(profiles, services).await;
+        }
+        // The services are named, so no profile needs to be active
+        self.docker.recreate_services(&[], services).await
+    }
+
+    async fn pull_images(&self) -> Result<()> {
+        self.docker.pull_images().await
+    }
+}
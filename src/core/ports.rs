@@ -0,0 +1,217 @@
+//! Published host ports across IGRA containers, and what they collide with
+//!
+//! Running containers report bindings like `0.0.0.0:8545->8545/tcp` or, for
+//! IPv6, `:::8545->8545/tcp`. Docker publishes most ports on both stacks, so
+//! the same container shows up twice for one port; that is not a conflict.
+//! Two different containers on one host port are. For a profile about to
+//! start, its compose ports are also checked against the host by trying to
+//! bind them, which catches non-IGRA processes holding the port.
+
+use std::collections::BTreeMap;
+use std::net::{Ipv4Addr, TcpListener, UdpSocket};
+
+use crate::core::docker::ContainerInfo;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PortBinding {
+    pub host_ip: String,
+    pub host_port: u16,
+    pub container_port: u16,
+    pub protocol: String,
+}
+
+/// Parse a published binding: `0.0.0.0:8545->8545/tcp`, `:::8545->8545/tcp`,
+/// `[::]:8545->8545`, or the inspect form `0.0.0.0:8545 -> 8545/tcp`.
+/// The protocol defaults to tcp.
+pub fn parse_binding(binding: &str) -> Option<PortBinding> {
+    let (host, container) = binding.split_once("->")?;
+    let (host_ip, host_port) = host.trim().rsplit_once(':')?;
+
+    let (container_port, protocol) = match container.trim().split_once('/') {
+        Some((port, protocol)) => (port, protocol.to_lowercase()),
+        None => (container.trim(), "tcp".to_string()),
+    };
+
+    let host_ip = host_ip.trim_start_matches('[').trim_end_matches(']');
+    Some(PortBinding {
+        host_ip: if host_ip.is_empty() { "::".to_string() } else { host_ip.to_string() },
+        host_port: host_port.parse().ok()?,
+        container_port: container_port.parse().ok()?,
+        protocol,
+    })
+}
+
+/// Host port and protocol from a compose `ports:` entry: `8545:8545`,
+/// `127.0.0.1:8545:8545/udp`, `${RPC_PORT:-8545}:8545`. Entries without a
+/// host port (Docker picks one) and port ranges are skipped.
+pub fn parse_compose_port(spec: &str, lookup: impl Fn(&str) -> Option<String>) -> Option<(u16, String)> {
+    let spec = substitute_vars(spec, lookup);
+    let (mapping, protocol) = match spec.split_once('/') {
+        Some((mapping, protocol)) => (mapping, protocol.to_lowercase()),
+        None => (spec.as_str(), "tcp".to_string()),
+    };
+
+    // The host port is the second-to-last field; IPv6 host IPs are bracketed
+    let mapping = mapping.rsplit_once(']').map_or(mapping, |(_, rest)| rest.trim_start_matches(':'));
+    let fields: Vec<&str> = mapping.split(':').collect();
+    if fields.len() < 2 {
+        return None;
+    }
+    let host_port = fields[fields.len() - 2].parse().ok()?;
+    Some((host_port, protocol))
+}
+
+/// Expand `${VAR}` and `${VAR:-default}` the way compose does
+fn substitute_vars(spec: &str, lookup: impl Fn(&str) -> Option<String>) -> String {
+    let mut out = String::new();
+    let mut rest = spec;
+    while let Some(start) = rest.find("${") {
+        let Some(len) = rest[start..].find('}') else { break };
+        out.push_str(&rest[..start]);
+
+        let expr = &rest[start + 2..start + len];
+        let value = match expr.split_once(":-") {
+            Some((var, default)) => lookup(var).filter(|v| !v.is_empty()).unwrap_or_else(|| default.to_string()),
+            None => lookup(expr).unwrap_or_default(),
+        };
+        out.push_str(&value);
+        rest = &rest[start + len + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// A host port and the IGRA services publishing it
+#[derive(Debug, Clone)]
+pub struct PublishedPort {
+    pub host_port: u16,
+    pub protocol: String,
+    /// (service, container port, host addresses)
+    pub owners: Vec<(String, u16, Vec<String>)>,
+}
+
+impl PublishedPort {
+    /// More than one service on the same host port
+    pub fn is_conflict(&self) -> bool {
+        self.owners.len() > 1
+    }
+}
+
+/// Group every published binding by host port/protocol, sorted by port
+pub fn published_ports(containers: &[ContainerInfo]) -> Vec<PublishedPort> {
+    let mut by_port: BTreeMap<(u16, String), PublishedPort> = BTreeMap::new();
+
+    for container in containers {
+        for binding in container.ports.iter().filter_map(|p| parse_binding(p)) {
+            let entry = by_port
+                .entry((binding.host_port, binding.protocol.clone()))
+                .or_insert_with(|| PublishedPort {
+                    host_port: binding.host_port,
+                    protocol: binding.protocol.clone(),
+                    owners: Vec::new(),
+                });
+
+            // IPv4 and IPv6 bindings of one container are a single owner
+            match entry.owners.iter_mut().find(|(name, _, _)| *name == container.name) {
+                Some((_, _, addresses)) => {
+                    if !addresses.contains(&binding.host_ip) {
+                        addresses.push(binding.host_ip);
+                    }
+                }
+                None => entry.owners.push((container.name.clone(), binding.container_port, vec![binding.host_ip])),
+            }
+        }
+    }
+
+    by_port.into_values().collect()
+}
+
+/// Whether nothing on the host holds the port. Bind errors other than
+/// "in use" (e.g. no permission for ports below 1024) count as free, since
+/// Docker itself binds as root.
+pub fn host_port_free(port: u16, protocol: &str) -> bool {
+    let result = if protocol == "udp" {
+        UdpSocket::bind((Ipv4Addr::UNSPECIFIED, port)).map(|_| ())
+    } else {
+        TcpListener::bind((Ipv4Addr::UNSPECIFIED, port)).map(|_| ())
+    };
+    !matches!(result, Err(e) if e.kind() == std::io::ErrorKind::AddrInUse)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::log_parser::ServiceMetrics;
+    use crate::utils::ContainerState;
+
+    fn container(name: &str, ports: &[&str]) -> ContainerInfo {
+        ContainerInfo {
+            id: name.to_string(),
+            name: name.to_string(),
+            image: "test".to_string(),
+            status: "Up".to_string(),
+            state: ContainerState::Running,
+            health: None,
+            created: 0,
+            started_at: None,
+            finished_at: None,
+            ports: ports.iter().map(|p| p.to_string()).collect(),
+            metrics: ServiceMetrics::default(),
+        }
+    }
+
+    #[test]
+    fn test_parse_binding() {
+        let binding = parse_binding("0.0.0.0:8545->8545/tcp").unwrap();
+        assert_eq!((binding.host_ip.as_str(), binding.host_port, binding.protocol.as_str()), ("0.0.0.0", 8545, "tcp"));
+
+        let binding = parse_binding(":::9545->8545/udp").unwrap();
+        assert_eq!((binding.host_ip.as_str(), binding.host_port, binding.container_port), ("::", 9545, 8545));
+        assert_eq!(binding.protocol, "udp");
+
+        assert_eq!(parse_binding("[::1]:80 -> 8080/tcp").unwrap().host_ip, "::1");
+        assert_eq!(parse_binding("127.0.0.1:80->8080").unwrap().protocol, "tcp");
+        assert!(parse_binding("8545/tcp").is_none());
+    }
+
+    #[test]
+    fn test_parse_compose_port() {
+        let env = |var: &str| (var == "RPC_PORT").then(|| "9545".to_string());
+        assert_eq!(parse_compose_port("8545:8545", env), Some((8545, "tcp".to_string())));
+        assert_eq!(parse_compose_port("127.0.0.1:30303:30303/udp", env), Some((30303, "udp".to_string())));
+        assert_eq!(parse_compose_port("[::1]:8080:80", env), Some((8080, "tcp".to_string())));
+        assert_eq!(parse_compose_port("${RPC_PORT:-8545}:8545", env), Some((9545, "tcp".to_string())));
+        assert_eq!(parse_compose_port("${WS_PORT:-8546}:8546", env), Some((8546, "tcp".to_string())));
+        // No host port, or a range
+        assert_eq!(parse_compose_port("8545", env), None);
+        assert_eq!(parse_compose_port("3000-3005:3000-3005", env), None);
+    }
+
+    #[test]
+    fn test_published_ports_conflicts() {
+        let containers = [
+            container("execution-layer", &["0.0.0.0:8545->8545", ":::8545->8545"]),
+            container("viaduct", &["0.0.0.0:8080->80"]),
+            container("other-rpc", &["127.0.0.1:8545->9000"]),
+        ];
+
+        let ports = published_ports(&containers);
+        assert_eq!(ports.iter().map(|p| p.host_port).collect::<Vec<_>>(), vec![8080, 8545]);
+        assert!(!ports[0].is_conflict());
+
+        // Dual-stack bindings of one container collapse into one owner
+        let rpc = &ports[1];
+        assert!(rpc.is_conflict());
+        assert_eq!(rpc.owners[0].2, vec!["0.0.0.0", "::"]);
+        assert_eq!(rpc.owners[1].0, "other-rpc");
+    }
+
+    #[test]
+    fn test_host_port_in_use() {
+        let listener = TcpListener::bind((Ipv4Addr::UNSPECIFIED, 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+        assert!(!host_port_free(port, "tcp"));
+        drop(listener);
+        assert!(host_port_free(port, "tcp"));
+    }
+}
@@ -0,0 +1,364 @@
+//! Transaction recording for the Watch screen
+//!
+//! Writes observed L2 transactions as text, JSON lines or CSV, with optional
+//! size-based rotation (file -> file.1 -> file.2 ...) to bound disk usage.
+//! When the session ends a `<file>.summary.json` with the totals is written.
+//! A transaction re-included after a reorg is written only once.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::{HashSet, VecDeque};
+use std::ffi::OsString;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::core::l2_monitor::{Statistics, TransactionInfo};
+
+/// Hashes remembered to skip transactions re-included after a reorg; far
+/// more than a reorg within the monitor's tracked depth can bring back
+const RECORDED_HASHES: usize = 10_000;
+
+const CSV_HEADER: &str = "timestamp,type,hash,from,to,value_ikas,gas_fee_ikas,l1_fee_kas,status,block_number,method\n";
+
+pub struct TransactionRecorder {
+    path: PathBuf,
+    format: String,
+    file: File,
+    written: u64,
+    max_bytes: Option<u64>,
+    keep: usize,
+    /// Totals for what this recorder wrote (not the whole monitor session)
+    stats: Statistics,
+    /// Recently written hashes, oldest first in `recorded_order`
+    recorded: HashSet<String>,
+    recorded_order: VecDeque<String>,
+}
+
+/// Totals for one recording session, written next to the recording
+#[derive(Debug, Serialize)]
+pub struct RecordingSummary {
+    pub recording: String,
+    pub format: String,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+    pub duration_secs: i64,
+    pub transactions: u64,
+    pub successful: u64,
+    pub failed: u64,
+    /// Fee and rate figures are omitted when nothing was recorded
+    pub total_gas_fees_ikas: Option<f64>,
+    pub total_l1_fees_kas: Option<f64>,
+    pub avg_tps: Option<f64>,
+    pub note: Option<String>,
+}
+
+impl RecordingSummary {
+    fn new(recording: &Path, format: &str, stats: &Statistics, ended_at: DateTime<Utc>) -> Self {
+        let started_at = stats.start_time.unwrap_or(ended_at);
+        let duration_secs = (ended_at - started_at).num_seconds().max(0);
+        let active = stats.total_transactions > 0;
+
+        Self {
+            recording: recording.display().to_string(),
+            format: format.to_string(),
+            started_at,
+            ended_at,
+            duration_secs,
+            transactions: stats.total_transactions,
+            successful: stats.successful_transactions,
+            failed: stats.failed_transactions,
+            total_gas_fees_ikas: active.then_some(stats.total_gas_fees_ikas),
+            total_l1_fees_kas: active.then_some(stats.total_l1_fees_kas),
+            avg_tps: (active && duration_secs > 0)
+                .then(|| stats.total_transactions as f64 / duration_secs as f64),
+            note: (!active).then(|| "no activity observed".to_string()),
+        }
+    }
+}
+
+impl TransactionRecorder {
+    /// Create (truncate) the recording file
+    /// `max_bytes` enables rotation; `keep` is how many rotated files to retain
+    pub fn create<P: AsRef<Path>>(path: P, format: &str, max_bytes: Option<u64>, keep: usize) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let file = File::create(&path)
+            .context(format!("Failed to create recording file {}", path.display()))?;
+
+        let mut recorder = Self {
+            path,
+            format: format.to_string(),
+            file,
+            written: 0,
+            max_bytes,
+            keep,
+            stats: Statistics {
+                start_time: Some(Utc::now()),
+                ..Default::default()
+            },
+            recorded: HashSet::new(),
+            recorded_order: VecDeque::new(),
+        };
+        recorder.write_header()?;
+        Ok(recorder)
+    }
+
+    /// Record a single transaction, rotating first if it wouldn't fit. One
+    /// already written (re-included after a reorg) is skipped.
+    pub fn record(&mut self, tx: &TransactionInfo) -> Result<()> {
+        if self.recorded.contains(&tx.hash) {
+            return Ok(());
+        }
+
+        // Format the whole entry up front so it never straddles two files
+        let entry = Self::format_entry(tx, &self.format)?;
+
+        if let Some(max) = self.max_bytes {
+            let header_len = self.header().map(|h| h.len() as u64).unwrap_or(0);
+            let has_entries = self.written > header_len;
+            if has_entries && self.written + entry.len() as u64 > max {
+                self.rotate()?;
+            }
+        }
+
+        self.file.write_all(entry.as_bytes())?;
+        self.written += entry.len() as u64;
+        self.stats.add_transaction(tx);
+
+        self.recorded.insert(tx.hash.clone());
+        self.recorded_order.push_back(tx.hash.clone());
+        if self.recorded_order.len() > RECORDED_HASHES {
+            if let Some(oldest) = self.recorded_order.pop_front() {
+                self.recorded.remove(&oldest);
+            }
+        }
+        Ok(())
+    }
+
+    /// End the session: flush and write `<file>.summary.json` (always JSON,
+    /// whatever the recording format). Returns the summary path.
+    pub fn finish(mut self) -> Result<PathBuf> {
+        self.file.flush()?;
+
+        let summary = RecordingSummary::new(&self.path, &self.format, &self.stats, Utc::now());
+        let path = self.summary_path();
+        fs::write(&path, serde_json::to_string_pretty(&summary)?)
+            .context(format!("Failed to write recording summary {}", path.display()))?;
+        Ok(path)
+    }
+
+    fn summary_path(&self) -> PathBuf {
+        let mut name: OsString = self.path.as_os_str().to_owned();
+        name.push(".summary.json");
+        PathBuf::from(name)
+    }
+
+    /// Path of the active recording file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn header(&self) -> Option<&'static str> {
+        match self.format.as_str() {
+            "csv" => Some(CSV_HEADER),
+            _ => None,
+        }
+    }
+
+    fn write_header(&mut self) -> Result<()> {
+        if let Some(header) = self.header() {
+            self.file.write_all(header.as_bytes())?;
+            self.written += header.len() as u64;
+        }
+        Ok(())
+    }
+
+    /// Path of the n-th rotated file (file.1, file.2, ...)
+    fn rotated_path(&self, n: usize) -> PathBuf {
+        let mut name: OsString = self.path.as_os_str().to_owned();
+        name.push(format!(".{}", n));
+        PathBuf::from(name)
+    }
+
+    fn rotate(&mut self) -> Result<()> {
+        self.file.flush()?;
+
+        if self.keep == 0 {
+            fs::remove_file(&self.path).ok();
+        } else {
+            // Drop the oldest, then shift file.N-1 -> file.N ... file -> file.1
+            fs::remove_file(self.rotated_path(self.keep)).ok();
+            for n in (1..self.keep).rev() {
+                let from = self.rotated_path(n);
+                if from.exists() {
+                    fs::rename(&from, self.rotated_path(n + 1))?;
+                }
+            }
+            fs::rename(&self.path, self.rotated_path(1))
+                .context("Failed to rotate recording file")?;
+        }
+
+        self.file = File::create(&self.path)
+            .context(format!("Failed to create recording file {}", self.path.display()))?;
+        self.written = 0;
+        self.write_header()
+    }
+
+    /// Format a transaction entry (including trailing newline)
+    pub fn format_entry(tx: &TransactionInfo, format: &str) -> Result<String> {
+        use std::fmt::Write;
+
+        let mut out = String::new();
+        match format {
+            "json" => {
+                let json = serde_json::to_string(tx)?;
+                writeln!(out, "{}", json)?;
+            }
+            "csv" => {
+                writeln!(
+                    out,
+                    "{},{},{},{},{},{},{},{},{},{},{}",
+                    tx.timestamp.format("%Y-%m-%d %H:%M:%S"),
+                    tx.tx_type,
+                    tx.hash,
+                    tx.from,
+                    tx.to.as_deref().unwrap_or(""),
+                    tx.value_ikas(),
+                    tx.gas_fee_ikas(),
+                    tx.l1_fee.unwrap_or(0.0),
+                    tx.status,
+                    tx.block_number,
+                    tx.method.as_deref().unwrap_or("")
+                )?;
+            }
+            _ => {
+                // Text format
+                writeln!(out, "[{}] {}", tx.timestamp.format("%H:%M:%S"), tx.type_label())?;
+                writeln!(out, "  Hash: {}", tx.hash)?;
+                writeln!(out, "  From: {}", tx.from)?;
+                if let Some(ref to) = tx.to {
+                    writeln!(out, "  To:   {}", to)?;
+                }
+                writeln!(out, "  Value: {} iKAS", tx.value_ikas())?;
+                writeln!(out, "  Gas: {} iKAS", tx.gas_fee_ikas())?;
+                if let Some(l1_fee) = tx.l1_fee {
+                    writeln!(out, "  L1 Fee: {} KAS", l1_fee)?;
+                }
+                writeln!(out, "  Status: {}", if tx.status { "Success" } else { "Failed" })?;
+                writeln!(out)?;
+            }
+        }
+
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::l2_monitor::TransactionType;
+    use chrono::Utc;
+    use ethers::types::U256;
+
+    fn sample_tx(block: u64) -> TransactionInfo {
+        TransactionInfo {
+            hash: format!("0x{:064x}", block),
+            from: "0xabc".to_string(),
+            to: Some("0xdef".to_string()),
+            value: U256::zero(),
+            gas_used: None,
+            gas_price: U256::zero(),
+            block_number: block,
+            timestamp: Utc::now(),
+            status: true,
+            tx_type: TransactionType::Transfer,
+            l1_fee: None,
+            method: None,
+            reorged: false,
+        }
+    }
+
+    #[test]
+    fn test_rotation_keeps_csv_header_and_all_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("txs.csv");
+
+        let entry_len = TransactionRecorder::format_entry(&sample_tx(1), "csv").unwrap().len() as u64;
+        // Room for the header plus two entries per file
+        let max = CSV_HEADER.len() as u64 + entry_len * 2;
+
+        let mut recorder = TransactionRecorder::create(&path, "csv", Some(max), 5).unwrap();
+        for block in 1..=5 {
+            recorder.record(&sample_tx(block)).unwrap();
+        }
+        drop(recorder);
+
+        let mut total_entries = 0;
+        for file in [path.clone(), dir.path().join("txs.csv.1"), dir.path().join("txs.csv.2")] {
+            let content = fs::read_to_string(&file).unwrap();
+            assert!(content.starts_with(CSV_HEADER), "{} is missing its header", file.display());
+            total_entries += content.lines().count() - 1;
+        }
+        assert_eq!(total_entries, 5);
+    }
+
+    #[test]
+    fn test_reincluded_transaction_recorded_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("txs.csv");
+
+        let mut recorder = TransactionRecorder::create(&path, "csv", None, 0).unwrap();
+        recorder.record(&sample_tx(1)).unwrap();
+        // Same transaction reported again from the replacement block
+        let mut reincluded = sample_tx(1);
+        reincluded.block_number = 2;
+        recorder.record(&reincluded).unwrap();
+        recorder.record(&sample_tx(3)).unwrap();
+        drop(recorder);
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert_eq!(content.lines().count() - 1, 2);
+    }
+
+    #[test]
+    fn test_rotation_drops_files_beyond_keep() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("txs.jsonl");
+
+        let mut recorder = TransactionRecorder::create(&path, "json", Some(1), 1).unwrap();
+        for block in 1..=3 {
+            recorder.record(&sample_tx(block)).unwrap();
+        }
+
+        assert!(dir.path().join("txs.jsonl.1").exists());
+        assert!(!dir.path().join("txs.jsonl.2").exists());
+    }
+
+    #[test]
+    fn test_finish_writes_summary() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("txs.csv");
+
+        let mut recorder = TransactionRecorder::create(&path, "csv", None, 5).unwrap();
+        recorder.record(&sample_tx(1)).unwrap();
+        let mut failed = sample_tx(2);
+        failed.status = false;
+        recorder.record(&failed).unwrap();
+
+        let summary_path = recorder.finish().unwrap();
+        assert_eq!(summary_path, dir.path().join("txs.csv.summary.json"));
+        let summary: serde_json::Value = serde_json::from_str(&fs::read_to_string(&summary_path).unwrap()).unwrap();
+        assert_eq!(summary["transactions"], 2);
+        assert_eq!(summary["failed"], 1);
+        assert!(summary["note"].is_null());
+
+        // An idle session says so instead of reporting zero fees
+        let idle = TransactionRecorder::create(dir.path().join("idle.json"), "json", None, 5).unwrap();
+        let summary: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(idle.finish().unwrap()).unwrap()).unwrap();
+        assert_eq!(summary["transactions"], 0);
+        assert_eq!(summary["note"], "no activity observed");
+        assert!(summary["total_gas_fees_ikas"].is_null());
+    }
+}
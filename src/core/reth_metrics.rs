@@ -1,8 +1,10 @@
-/// Reth Prometheus metrics fetching and parsing
-///
-/// Fetches metrics from Reth execution layer and parses key performance indicators
+//! Reth Prometheus metrics fetching and parsing
+//!
+//! Fetches metrics from Reth execution layer and parses key performance indicators
 
 use anyhow::Result;
+use std::collections::VecDeque;
+use std::time::Duration;
 
 #[derive(Debug, Clone, Default)]
 pub struct RethMetrics {
@@ -42,8 +44,8 @@ pub struct RethMetrics {
 pub async fn fetch_reth_metrics() -> Result<RethMetrics> {
     // Use docker exec with bash /dev/tcp to fetch metrics from inside the container
     // This avoids requiring curl/wget to be installed in the container
-    let output = tokio::process::Command::new("docker")
-        .args(&[
+    let output = tokio::process::Command::new(crate::core::docker::runtime_program())
+        .args([
             "exec",
             "execution-layer",
             "bash",
@@ -97,10 +99,8 @@ fn parse_prometheus_metrics(text: &str) -> RethMetrics {
                             metrics.transactions_total = Some(value as u64);
                         }
                     }
-                    "reth_sync_checkpoint" => {
-                        if line.contains("stage=\"Finish\"") {
-                            metrics.sync_checkpoint = Some(value as u64);
-                        }
+                    "reth_sync_checkpoint" if line.contains("stage=\"Finish\"") => {
+                        metrics.sync_checkpoint = Some(value as u64);
                     }
 
                     // Network metrics
@@ -181,29 +181,102 @@ pub fn calculate_tps(
     }
 }
 
+/// Sampling cadence for the detail view history, independent of UI refresh
+pub const SAMPLE_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Samples kept for the sparklines (2 minutes at SAMPLE_INTERVAL)
+pub const HISTORY_LEN: usize = 60;
+
+/// Recent TPS and block progress for the detail view sparklines
+#[derive(Debug, Clone, Default)]
+pub struct RethHistory {
+    tps_centi: VecDeque<u64>, // TPS x100 so the sparkline keeps some resolution
+    blocks: VecDeque<u64>,    // Blocks advanced since the previous sample
+    last_height: Option<u64>,
+}
+
+impl RethHistory {
+    /// Record one sample. A height that went backwards (resync/reorg) or a
+    /// missing height rebases instead of drawing a spike.
+    pub fn record(&mut self, metrics: &RethMetrics) {
+        let tps = metrics.tps.map(|t| (t * 100.0).round() as u64).unwrap_or(0);
+
+        let height = metrics.blocks_processed.or(metrics.canonical_chain_height);
+        let advanced = match (self.last_height, height) {
+            (Some(prev), Some(current)) if current >= prev => current - prev,
+            _ => 0,
+        };
+        self.last_height = height;
+
+        push_bounded(&mut self.tps_centi, tps);
+        push_bounded(&mut self.blocks, advanced);
+    }
+
+    pub fn tps_centi(&self) -> Vec<u64> {
+        self.tps_centi.iter().copied().collect()
+    }
+
+    pub fn blocks(&self) -> Vec<u64> {
+        self.blocks.iter().copied().collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+}
+
+fn push_bounded(buf: &mut VecDeque<u64>, value: u64) {
+    if buf.len() == HISTORY_LEN {
+        buf.pop_front();
+    }
+    buf.push_back(value);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_history_ignores_height_going_backwards() {
+        let sample = |height: u64| RethMetrics {
+            blocks_processed: Some(height),
+            tps: Some(1.5),
+            ..Default::default()
+        };
+
+        let mut history = RethHistory::default();
+        for height in [100, 105, 110, 3, 8] {
+            history.record(&sample(height));
+        }
+
+        assert_eq!(history.blocks(), [0, 5, 5, 0, 5]);
+        assert_eq!(history.tps_centi(), [150; 5]);
+
+        for _ in 0..HISTORY_LEN {
+            history.record(&sample(8));
+        }
+        assert_eq!(history.blocks().len(), HISTORY_LEN);
+    }
+
     #[test]
     fn test_parse_prometheus_metrics() {
         let sample = r#"
-# HELP reth_sync_block_number Current block number
-# TYPE reth_sync_block_number gauge
-reth_sync_block_number 123456
+# HELP reth_payloads_resolved_block Last resolved payload block
+# TYPE reth_payloads_resolved_block gauge
+reth_payloads_resolved_block 123456
 
 # HELP reth_network_connected_peers Number of connected peers
 # TYPE reth_network_connected_peers gauge
 reth_network_connected_peers 42
 
-# HELP reth_transaction_pool_transactions Transactions in pool
-# TYPE reth_transaction_pool_transactions gauge
-reth_transaction_pool_transactions 10
+# HELP reth_transaction_pool_pending_pool_transactions Pending transactions in pool
+# TYPE reth_transaction_pool_pending_pool_transactions gauge
+reth_transaction_pool_pending_pool_transactions 10
 "#;
 
         let metrics = parse_prometheus_metrics(sample);
         assert_eq!(metrics.blocks_processed, Some(123456));
         assert_eq!(metrics.peers_connected, Some(42));
-        assert_eq!(metrics.transactions_processed, Some(10));
+        assert_eq!(metrics.transactions_pending, Some(10));
     }
 }
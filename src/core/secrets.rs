@@ -0,0 +1,442 @@
+//! Encrypted secrets store
+//!
+//! Wallet passwords, RPC tokens, OVH credentials and web tokens can live in an
+//! age-encrypted dotenv file instead of plaintext `.env`. The file is only ever
+//! decrypted in memory: ConfigManager merges its values over `.env`, and docker
+//! compose receives them as environment variables. Set in config.toml:
+//!
+//! ```toml
+//! [secrets]
+//! file = "secrets.env.age"                    # relative to the project root
+//! key_source = "keyfile"                      # "env" (default), "keyfile" or "prompt"
+//! key_file = "/root/.config/igra-cli/secrets.key"
+//! key_env = "IGRA_SECRETS_KEY"                # variable read when key_source = "env"
+//! ```
+//!
+//! The key is either an age identity (`AGE-SECRET-KEY-1...`, as written by
+//! `age-keygen`) or a passphrase, so the file can also be edited with the `age`
+//! CLI. Saving re-encrypts to the key in use.
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+use zeroize::Zeroizing;
+
+/// Environment variable holding the key when `key_env` isn't set
+pub const DEFAULT_KEY_ENV: &str = "IGRA_SECRETS_KEY";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeySource {
+    #[default]
+    Env,
+    /// First non-comment line of a file only the owner can read
+    Keyfile,
+    /// Typed in once per run; needs a terminal, so not for `serve`
+    Prompt,
+}
+
+/// `[secrets]` in config.toml
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SecretsConfig {
+    /// Encrypted store; unset means every value lives in `.env`
+    #[serde(default)]
+    pub file: Option<PathBuf>,
+    #[serde(default)]
+    pub key_source: KeySource,
+    #[serde(default)]
+    pub key_file: Option<PathBuf>,
+    #[serde(default)]
+    pub key_env: Option<String>,
+}
+
+enum SecretsKey {
+    Identity(age::x25519::Identity),
+    Passphrase(Zeroizing<String>),
+}
+
+impl SecretsKey {
+    fn parse(raw: &str) -> Self {
+        let raw = raw.trim();
+        match raw.parse::<age::x25519::Identity>() {
+            Ok(identity) => SecretsKey::Identity(identity),
+            Err(_) => SecretsKey::Passphrase(Zeroizing::new(raw.to_string())),
+        }
+    }
+
+    fn decrypt(&self, path: &Path, data: &[u8]) -> Result<Zeroizing<String>> {
+        let decryptor = age::Decryptor::new(data)
+            .with_context(|| format!("{} is not an age-encrypted file", path.display()))?;
+
+        let mut reader = match (decryptor.is_scrypt(), self) {
+            (false, SecretsKey::Identity(identity)) => decryptor
+                .decrypt(std::iter::once(identity as &dyn age::Identity))
+                .map_err(|e| anyhow!("wrong key? {}", e))?,
+            (true, SecretsKey::Passphrase(passphrase)) => {
+                let identity = age::scrypt::Identity::new(passphrase.as_str().into());
+                decryptor
+                    .decrypt(std::iter::once(&identity as &dyn age::Identity))
+                    .map_err(|e| anyhow!("wrong passphrase? {}", e))?
+            }
+            (false, SecretsKey::Passphrase(_)) => {
+                bail!("{} is encrypted to an age identity, but the key is not one", path.display())
+            }
+            (true, SecretsKey::Identity(_)) => {
+                bail!("{} is encrypted with a passphrase, but the key is an age identity", path.display())
+            }
+        };
+
+        let mut plaintext = Zeroizing::new(String::new());
+        reader
+            .read_to_string(&mut plaintext)
+            .with_context(|| format!("Failed to decrypt {}", path.display()))?;
+        Ok(plaintext)
+    }
+
+    fn encrypt(&self, plaintext: &str) -> Result<Vec<u8>> {
+        let encryptor = match self {
+            SecretsKey::Identity(identity) => {
+                let recipient = identity.to_public();
+                age::Encryptor::with_recipients(std::iter::once(&recipient as &dyn age::Recipient))?
+            }
+            SecretsKey::Passphrase(passphrase) => age::Encryptor::with_user_passphrase(passphrase.as_str().into()),
+        };
+
+        let mut encrypted = Vec::new();
+        let mut writer = encryptor.wrap_output(&mut encrypted)?;
+        writer.write_all(plaintext.as_bytes())?;
+        writer.finish()?;
+        Ok(encrypted)
+    }
+}
+
+/// What this run knows about one store file
+#[derive(Default)]
+struct Session {
+    /// Key of the last successful decrypt or save, so a save doesn't ask again
+    key: Option<Arc<SecretsKey>>,
+    /// Outcome of the first decrypt. Failures are kept too: a prompt is shown
+    /// at most once per run, and a later caller can't end up with blanks.
+    entries: Option<std::result::Result<SecretEntries, String>>,
+}
+
+/// Decrypted `KEY=value` pairs of one store
+type SecretEntries = Arc<Vec<(String, String)>>;
+
+fn sessions() -> MutexGuard<'static, HashMap<PathBuf, Session>> {
+    static SESSIONS: OnceLock<Mutex<HashMap<PathBuf, Session>>> = OnceLock::new();
+    SESSIONS
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+}
+
+/// Directory of `path`, resolved so that `./.env`, `.env` and the absolute
+/// path all compare equal. Falls back to the path as given if it's missing.
+fn canonical_dir(path: &Path) -> PathBuf {
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    fs::canonicalize(dir).unwrap_or_else(|_| dir.to_path_buf())
+}
+
+pub struct SecretsStore {
+    path: PathBuf,
+    config: SecretsConfig,
+}
+
+impl SecretsStore {
+    /// The store set in config.toml, if any, for the env file in use
+    pub fn configured(project_root: &Path) -> Option<Self> {
+        let config = crate::utils::AppConfig::load().ok()?.secrets;
+        let store = Self::new(project_root, config)?;
+        Some(store.for_env_file(project_root, crate::core::ConfigManager::env_file_override()))
+    }
+
+    pub fn new(project_root: &Path, config: SecretsConfig) -> Option<Self> {
+        let path = project_root.join(config.file.as_ref()?);
+        Some(Self { path, config })
+    }
+
+    /// The store that goes with `env_file` (`--env-file`): the configured
+    /// file for the project's own `.env`, else one named after the env file,
+    /// so staging and production secrets never share a store.
+    /// `secrets.env.age` with `.env.staging` becomes `secrets.env.staging.age`.
+    /// Env files outside the project keep their store in their own directory,
+    /// so another directory's `.env` doesn't pick up this project's secrets.
+    fn for_env_file(mut self, project_root: &Path, env_file: Option<&Path>) -> Self {
+        let Some(env_file) = env_file else {
+            return self;
+        };
+        let Some(name) = env_file.file_name().and_then(|name| name.to_str()) else {
+            return self;
+        };
+        let dir = canonical_dir(env_file);
+        let in_project = dir == fs::canonicalize(project_root).unwrap_or_else(|_| project_root.to_path_buf());
+        if in_project && name == crate::utils::DEFAULT_ENV_FILE {
+            return self;
+        }
+
+        let mut file_name = self.path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+        if name != crate::utils::DEFAULT_ENV_FILE {
+            let tag = name.trim_start_matches('.');
+            let tag = tag.strip_prefix("env.").unwrap_or(tag);
+            let stem = file_name.strip_suffix(".age").unwrap_or(&file_name);
+            file_name = format!("{}.{}.age", stem, tag);
+        }
+        self.path = if in_project { self.path.with_file_name(file_name) } else { dir.join(file_name) };
+        self
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn exists(&self) -> bool {
+        self.path.exists()
+    }
+
+    /// Decrypted `KEY=value` entries. Decrypts once per run; later calls
+    /// return the same entries, or the same error. A store that hasn't been
+    /// created yet is empty.
+    pub fn load(&self) -> Result<Vec<(String, String)>> {
+        if !self.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut sessions = sessions();
+        let session = sessions.entry(self.path.clone()).or_default();
+        if session.entries.is_none() {
+            session.entries = Some(match self.decrypt() {
+                Ok((key, entries)) => {
+                    session.key = Some(Arc::new(key));
+                    Ok(Arc::new(entries))
+                }
+                Err(e) => Err(format!("{:#}", e)),
+            });
+        }
+
+        match session.entries.as_ref() {
+            Some(Ok(entries)) => Ok(entries.to_vec()),
+            Some(Err(e)) => Err(anyhow!("Secrets store {} could not be decrypted: {}", self.path.display(), e)),
+            None => unreachable!("set above"),
+        }
+    }
+
+    /// Encrypt `entries` and replace the store. The plaintext only exists in
+    /// memory; the encrypted file is written alongside and renamed into place.
+    pub fn save(&self, entries: &[(String, String)]) -> Result<()> {
+        let cached = sessions().get(&self.path).and_then(|s| s.key.clone());
+        let key = match cached {
+            Some(key) => key,
+            None => Arc::new(self.read_key()?),
+        };
+
+        let plaintext = Zeroizing::new(format_entries(entries));
+        let encrypted = key.encrypt(&plaintext)?;
+
+        let mut tmp = self.path.clone().into_os_string();
+        tmp.push(".tmp");
+        let tmp = PathBuf::from(tmp);
+        write_private(&tmp, &encrypted)
+            .with_context(|| format!("Failed to write {}", tmp.display()))?;
+        if let Err(e) = fs::rename(&tmp, &self.path) {
+            let _ = fs::remove_file(&tmp);
+            return Err(e).with_context(|| format!("Failed to replace {}", self.path.display()));
+        }
+
+        let mut sessions = sessions();
+        let session = sessions.entry(self.path.clone()).or_default();
+        session.key = Some(key);
+        session.entries = Some(Ok(Arc::new(entries.to_vec())));
+        Ok(())
+    }
+
+    fn decrypt(&self) -> Result<(SecretsKey, Vec<(String, String)>)> {
+        let data = fs::read(&self.path).with_context(|| format!("Failed to read {}", self.path.display()))?;
+
+        let key = self.read_key()?;
+        let plaintext = key.decrypt(&self.path, &data)?;
+        let entries = parse_entries(&plaintext);
+        Ok((key, entries))
+    }
+
+    fn read_key(&self) -> Result<SecretsKey> {
+        let raw = match self.config.key_source {
+            KeySource::Env => {
+                let var = self.config.key_env.as_deref().unwrap_or(DEFAULT_KEY_ENV);
+                Zeroizing::new(
+                    std::env::var(var)
+                        .ok()
+                        .filter(|v| !v.is_empty())
+                        .ok_or_else(|| anyhow!("secrets key: environment variable {} is not set", var))?,
+                )
+            }
+            KeySource::Keyfile => {
+                let path = self
+                    .config
+                    .key_file
+                    .as_deref()
+                    .ok_or_else(|| anyhow!("[secrets] key_source = \"keyfile\" needs key_file"))?;
+                read_key_file(path)?
+            }
+            KeySource::Prompt => {
+                use std::io::IsTerminal;
+                if !std::io::stdin().is_terminal() {
+                    bail!("secrets key: key_source = \"prompt\" needs a terminal; use \"env\" or \"keyfile\" for the server and scripts");
+                }
+                crate::utils::terminal::read_hidden("Secrets store key")?
+            }
+        };
+        Ok(SecretsKey::parse(&raw))
+    }
+}
+
+/// Values compose needs from the store, passed as environment variables so
+/// `${VAR}` references in docker-compose.yml resolve without a plaintext .env.
+/// Empty when no store is configured; an error when it can't be decrypted.
+pub fn compose_env(project_root: &Path) -> Result<Vec<(String, String)>> {
+    match SecretsStore::configured(project_root) {
+        Some(store) => store.load(),
+        None => Ok(Vec::new()),
+    }
+}
+
+fn read_key_file(path: &Path) -> Result<Zeroizing<String>> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = fs::metadata(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?
+            .permissions()
+            .mode();
+        if mode & 0o077 != 0 {
+            bail!("{} is accessible by other users (mode {:o}); chmod 600 it", path.display(), mode & 0o777);
+        }
+    }
+
+    let contents = Zeroizing::new(
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?,
+    );
+    // age-keygen writes "# created:" / "# public key:" comments first
+    contents
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| Zeroizing::new(line.to_string()))
+        .ok_or_else(|| anyhow!("{} is empty", path.display()))
+}
+
+fn write_private(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path)?.write_all(contents)
+}
+
+/// Same `KEY=value` / `# comment` format as .env
+fn parse_entries(plaintext: &str) -> Vec<(String, String)> {
+    plaintext
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .collect()
+}
+
+fn format_entries(entries: &[(String, String)]) -> String {
+    entries.iter().map(|(key, value)| format!("{}={}\n", key, value)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use age::secrecy::ExposeSecret;
+
+    #[test]
+    fn test_key_roundtrip() {
+        let entries = vec![
+            ("W0_KASWALLET_PASSWORD".to_string(), "hunter2".to_string()),
+            ("IGRA_WEB_TOKEN".to_string(), "a=b".to_string()),
+        ];
+        let plaintext = format_entries(&entries);
+        let path = Path::new("secrets.env.age");
+
+        let identity = age::x25519::Identity::generate();
+        let keys = [
+            SecretsKey::parse(identity.to_string().expose_secret()),
+            SecretsKey::parse("correct horse battery staple"),
+        ];
+        assert!(matches!(keys[0], SecretsKey::Identity(_)));
+        assert!(matches!(keys[1], SecretsKey::Passphrase(_)));
+
+        for key in &keys {
+            let encrypted = key.encrypt(&plaintext).unwrap();
+            assert!(!String::from_utf8_lossy(&encrypted).contains("hunter2"));
+            assert_eq!(parse_entries(&key.decrypt(path, &encrypted).unwrap()), entries);
+        }
+
+        // Wrong passphrase, or the wrong kind of key, fails instead of yielding blanks
+        let encrypted = keys[1].encrypt(&plaintext).unwrap();
+        assert!(SecretsKey::parse("wrong").decrypt(path, &encrypted).is_err());
+        assert!(keys[0].decrypt(path, &encrypted).is_err());
+    }
+
+    #[test]
+    fn test_read_key_file_skips_comments() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("secrets.key");
+        write_private(&path, b"# created: 2026-01-01\n# public key: age1xyz\nAGE-SECRET-KEY-1ABC\n").unwrap();
+        assert_eq!(read_key_file(&path).unwrap().as_str(), "AGE-SECRET-KEY-1ABC");
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+            assert!(read_key_file(&path).is_err());
+        }
+    }
+
+    #[test]
+    fn test_config_defaults_to_no_store() {
+        let config: SecretsConfig = toml::from_str("").unwrap();
+        assert!(SecretsStore::new(Path::new("/srv/igra"), config).is_none());
+
+        let config: SecretsConfig = toml::from_str("file = \"secrets.env.age\"\nkey_source = \"prompt\"").unwrap();
+        assert_eq!(config.key_source, KeySource::Prompt);
+        let store = SecretsStore::new(Path::new("/srv/igra"), config).unwrap();
+        assert_eq!(store.path(), Path::new("/srv/igra/secrets.env.age"));
+    }
+
+    #[test]
+    fn test_store_per_env_file() {
+        let project = tempfile::tempdir().unwrap();
+        let other = tempfile::tempdir().unwrap();
+        let config = SecretsConfig { file: Some("secrets.env.age".into()), ..Default::default() };
+        let store = |env_file: Option<PathBuf>| {
+            SecretsStore::new(project.path(), config.clone())
+                .unwrap()
+                .for_env_file(project.path(), env_file.as_deref())
+                .path
+        };
+
+        assert_eq!(store(None), project.path().join("secrets.env.age"));
+        assert_eq!(store(Some(project.path().join(".env"))), project.path().join("secrets.env.age"));
+        assert_eq!(store(Some(project.path().join(".env.staging"))), project.path().join("secrets.env.staging.age"));
+
+        // Same name, different directory: never the project's store
+        let other_dir = fs::canonicalize(other.path()).unwrap();
+        assert_eq!(store(Some(other.path().join(".env"))), other_dir.join("secrets.env.age"));
+        assert_eq!(store(Some(other.path().join("prod.env"))), other_dir.join("secrets.env.prod.env.age"));
+    }
+}
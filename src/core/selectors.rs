@@ -0,0 +1,110 @@
+//! Contract method selector decoding for transaction labels
+//!
+//! Maps the first 4 bytes of calldata to a method name using a small built-in
+//! table of common signatures, optionally extended from a user ABI JSON file.
+
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Common ERC-20/721, WETH and DEX router methods
+const BUILTIN_SELECTORS: &[([u8; 4], &str)] = &[
+    ([0xa9, 0x05, 0x9c, 0xbb], "transfer"),
+    ([0x09, 0x5e, 0xa7, 0xb3], "approve"),
+    ([0x23, 0xb8, 0x72, 0xdd], "transferFrom"),
+    ([0x40, 0xc1, 0x0f, 0x19], "mint"),
+    ([0x42, 0x96, 0x6c, 0x68], "burn"),
+    ([0xd0, 0xe3, 0x0d, 0xb0], "deposit"),
+    ([0x2e, 0x1a, 0x7d, 0x4d], "withdraw"),
+    ([0x42, 0x84, 0x2e, 0x0e], "safeTransferFrom"),
+    ([0xa2, 0x2c, 0xb4, 0x65], "setApprovalForAll"),
+    ([0xac, 0x96, 0x50, 0xd8], "multicall"),
+    ([0x38, 0xed, 0x17, 0x39], "swapExactTokensForTokens"),
+    ([0x7f, 0xf3, 0x6a, 0xb5], "swapExactETHForTokens"),
+    ([0x18, 0xcb, 0xaf, 0xe5], "swapExactTokensForETH"),
+    ([0x88, 0x03, 0xdb, 0xee], "swapTokensForExactTokens"),
+    ([0x41, 0x4b, 0xf3, 0x89], "exactInputSingle"),
+    ([0xe8, 0xe3, 0x37, 0x00], "addLiquidity"),
+    ([0xf3, 0x05, 0xd7, 0x19], "addLiquidityETH"),
+    ([0xba, 0xa2, 0xab, 0xde], "removeLiquidity"),
+];
+
+#[derive(Debug, Clone)]
+pub struct SelectorDb {
+    names: HashMap<[u8; 4], String>,
+}
+
+impl Default for SelectorDb {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl SelectorDb {
+    /// Database with only the built-in signatures
+    pub fn builtin() -> Self {
+        let names = BUILTIN_SELECTORS
+            .iter()
+            .map(|(selector, name)| (*selector, name.to_string()))
+            .collect();
+        Self { names }
+    }
+
+    /// Add every function from an ABI JSON file (overrides built-ins on collision)
+    pub fn load_abi<P: AsRef<Path>>(mut self, path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .context(format!("Failed to read ABI file {}", path.display()))?;
+        let abi: ethers::abi::Abi = serde_json::from_str(&content)
+            .context(format!("Invalid ABI JSON in {}", path.display()))?;
+
+        for function in abi.functions() {
+            self.names.insert(function.short_signature(), function.name.clone());
+        }
+        Ok(self)
+    }
+
+    /// Label for calldata: the method name, or the raw 0x-prefixed selector
+    /// when unknown. None when there is no selector (plain transfer).
+    pub fn label(&self, input: &[u8]) -> Option<String> {
+        let selector: [u8; 4] = input.get(..4)?.try_into().ok()?;
+        Some(
+            self.names
+                .get(&selector)
+                .cloned()
+                .unwrap_or_else(|| {
+                    let hex: String = selector.iter().map(|b| format!("{:02x}", b)).collect();
+                    format!("0x{}", hex)
+                }),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_label_known_unknown_and_empty() {
+        let db = SelectorDb::builtin();
+        assert_eq!(db.label(&[0xa9, 0x05, 0x9c, 0xbb, 0x00]).as_deref(), Some("transfer"));
+        assert_eq!(db.label(&[0xde, 0xad, 0xbe, 0xef]).as_deref(), Some("0xdeadbeef"));
+        assert_eq!(db.label(&[]), None);
+        assert_eq!(db.label(&[0xa9, 0x05]), None);
+    }
+
+    #[test]
+    fn test_load_abi() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("abi.json");
+        std::fs::write(
+            &path,
+            r#"[{"type":"function","name":"stake","inputs":[{"name":"amount","type":"uint256"}],"outputs":[],"stateMutability":"nonpayable"}]"#,
+        )
+        .unwrap();
+
+        let db = SelectorDb::builtin().load_abi(&path).unwrap();
+        // keccak256("stake(uint256)")[..4]
+        assert_eq!(db.label(&[0xa6, 0x94, 0xfc, 0x3a]).as_deref(), Some("stake"));
+    }
+}
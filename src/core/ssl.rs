@@ -1,10 +1,11 @@
-/// SSL/TLS certificate management
+//! SSL/TLS certificate management
 
 use anyhow::{anyhow, Context, Result};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize};
 use serde_json::Value;
 use std::fs;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 pub struct SslManager {
@@ -18,18 +19,7 @@ pub struct CertificateInfo {
     pub valid_until: Option<DateTime<Utc>>,
     pub days_remaining: Option<i64>,
     pub is_valid: bool,
-}
-
-#[derive(Debug, Deserialize)]
-struct AcmeData {
-    #[serde(default)]
-    #[serde(rename = "Certificates")]
-    certificates: Vec<AcmeCertificate>,
-}
-
-#[derive(Debug, Deserialize)]
-struct AcmeCertificate {
-    domain: AcmeDomain,
+    pub issuer: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -45,10 +35,15 @@ impl SslManager {
         Ok(Self { project_root })
     }
 
+    /// A manager for `project_root` that never looks the project up (tests)
+    pub fn offline(project_root: &Path) -> Self {
+        Self { project_root: project_root.to_path_buf() }
+    }
+
     /// Check certificate from ACME JSON file
     pub async fn get_certificate_info(&self, domain: &str) -> Result<CertificateInfo> {
         // First check ACME JSON file
-        let acme_file = self.project_root.join("traefik_certs/acme.json");
+        let acme_file = self.acme_file();
 
         if !acme_file.exists() {
             return Err(anyhow!(
@@ -92,22 +87,32 @@ impl SslManager {
                 valid_until: None,
                 days_remaining: None,
                 is_valid: false,
+                issuer: None,
             });
         }
 
         // Use openssl to check the actual certificate
-        self.check_certificate_with_openssl(domain).await
+        Self::check_certificate_with_openssl(domain).await
     }
 
-    /// Check certificate using openssl s_client
-    async fn check_certificate_with_openssl(&self, domain: &str) -> Result<CertificateInfo> {
-        let output = Command::new("sh")
+    /// Check the certificate a domain actually serves on :443 using openssl
+    /// s_client. Doesn't need acme.json, so it works for any domain.
+    pub async fn check_certificate_with_openssl(domain: &str) -> Result<CertificateInfo> {
+        // The domain goes into a shell command line
+        if !crate::utils::is_valid_domain(domain) {
+            return Err(anyhow!("Invalid domain: {}", domain));
+        }
+
+        // Async so callers can put a shorter timeout on it (status --oneline)
+        let output = tokio::process::Command::new("sh")
             .arg("-c")
             .arg(format!(
-                "echo | timeout 5 openssl s_client -servername {} -connect {}:443 2>/dev/null | openssl x509 -noout -dates",
+                "echo | timeout 5 openssl s_client -servername {} -connect {}:443 2>/dev/null | openssl x509 -noout -dates -issuer",
                 domain, domain
             ))
+            .kill_on_drop(true)
             .output()
+            .await
             .context("Failed to run openssl")?;
 
         if !output.status.success() {
@@ -117,6 +122,7 @@ impl SslManager {
                 valid_until: None,
                 days_remaining: None,
                 is_valid: false,
+                issuer: None,
             });
         }
 
@@ -142,6 +148,8 @@ impl SslManager {
                 Self::parse_openssl_date(date_str)
             });
 
+        let issuer = Self::parse_issuer(&stdout);
+
         let days_remaining = valid_until.map(|until| {
             let now = Utc::now();
             (until - now).num_days()
@@ -160,9 +168,25 @@ impl SslManager {
             valid_until,
             days_remaining,
             is_valid,
+            issuer,
         })
     }
 
+    /// Issuer organization from `openssl x509 -issuer` output, falling back to the full DN
+    /// Format: issuer=C = US, O = Let's Encrypt, CN = R3
+    fn parse_issuer(output: &str) -> Option<String> {
+        let dn = output
+            .lines()
+            .find_map(|line| line.strip_prefix("issuer="))?
+            .trim();
+
+        let org = dn
+            .split(", ")
+            .find_map(|part| part.strip_prefix("O = ").or_else(|| part.strip_prefix("O=")));
+
+        Some(org.unwrap_or(dn).to_string())
+    }
+
     /// Parse OpenSSL date format
     fn parse_openssl_date(date_str: &str) -> Option<DateTime<Utc>> {
         // OpenSSL format: "Jan  1 00:00:00 2024 GMT"
@@ -176,14 +200,148 @@ impl SslManager {
             .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc))
     }
 
+    fn acme_file(&self) -> PathBuf {
+        self.project_root.join("traefik_certs/acme.json")
+    }
+
+    /// Force a fresh issuance for `domain`: back up acme.json, drop the
+    /// domain's certificate from it, then restart Traefik so it requests a new
+    /// one. (Traefik's API is read-only, so editing the store is the only way.)
+    /// If the backup can't be written the original is left untouched.
+    /// Returns the backup path. A dry run only checks that the domain has a
+    /// certificate and writes nothing.
+    pub async fn reissue_certificate(&self, domain: &str) -> Result<PathBuf> {
+        let acme_file = self.acme_file();
+        let original = fs::read(&acme_file)
+            .with_context(|| format!("Failed to read {}", acme_file.display()))?;
+        let mut acme_json: Value = serde_json::from_slice(&original)
+            .context("Failed to parse acme.json")?;
+
+        let removed = remove_domain_certificates(&mut acme_json, domain);
+        if removed == 0 {
+            return Err(anyhow!("No certificate for {} in {}", domain, acme_file.display()));
+        }
+
+        let backup = acme_file.with_file_name(format!(
+            "acme.json.bak-{}",
+            chrono::Local::now().format("%Y%m%d-%H%M%S")
+        ));
+        if crate::core::dry_run::skip(|| format!(
+            "back up {} to {}, remove {} certificate(s) for {}",
+            acme_file.display(), backup.display(), removed, domain
+        )) {
+            self.force_renewal().await?;
+            return Ok(backup);
+        }
+
+        write_private(&backup, &original)
+            .with_context(|| format!("Failed to back up acme.json to {} - nothing was changed", backup.display()))?;
+
+        // Write the new store next to the original and swap it in
+        let mut tmp = acme_file.clone().into_os_string();
+        tmp.push(".tmp");
+        let tmp = PathBuf::from(tmp);
+        write_private(&tmp, &serde_json::to_vec_pretty(&acme_json)?)
+            .context("Failed to write updated acme.json")?;
+        fs::rename(&tmp, &acme_file).context("Failed to replace acme.json")?;
+
+        self.force_renewal().await?;
+        Ok(backup)
+    }
+
     /// Force renewal of certificates (restart Traefik)
     pub async fn force_renewal(&self) -> Result<()> {
-        Command::new("docker")
-            .args(&["restart", "traefik"])
+        let program = crate::core::docker::runtime_program();
+        if crate::core::dry_run::skip(|| format!("{} restart traefik", program)) {
+            return Ok(());
+        }
+
+        let output = Command::new(program)
+            .args(["restart", "traefik"])
             .current_dir(&self.project_root)
-            .status()
+            .output()
             .context("Failed to restart Traefik")?;
+        if !output.status.success() {
+            return Err(anyhow!(
+                "Failed to restart Traefik: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
 
         Ok(())
     }
 }
+
+/// Remove every certificate whose main domain or SANs include `domain`, across
+/// all resolvers. Returns how many were removed.
+fn remove_domain_certificates(acme: &mut Value, domain: &str) -> usize {
+    let mut removed = 0;
+    let resolvers = acme.as_object_mut().into_iter().flat_map(|obj| obj.values_mut());
+
+    for certificates in resolvers.filter_map(|r| r.get_mut("Certificates").and_then(Value::as_array_mut)) {
+        let before = certificates.len();
+        certificates.retain(|cert| {
+            let names = cert.get("domain").and_then(|d| serde_json::from_value::<AcmeDomain>(d.clone()).ok());
+            !names.is_some_and(|d| d.main == domain || d.sans.iter().any(|s| s == domain))
+        });
+        removed += before - certificates.len();
+    }
+
+    removed
+}
+
+/// Write a file readable only by the owner (Traefik refuses a looser acme.json)
+fn write_private(path: &Path, contents: &[u8]) -> Result<()> {
+    use std::io::Write;
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    let mut file = options.open(path)?;
+    file.write_all(contents)?;
+    file.sync_all()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remove_domain_certificates() {
+        let mut acme = serde_json::json!({
+            "letsencrypt": {
+                "Account": { "Email": "ops@example.com" },
+                "Certificates": [
+                    { "domain": { "main": "node.example.com" }, "certificate": "AAA", "key": "BBB" },
+                    { "domain": { "main": "other.example.com", "sans": ["rpc.example.com"] }, "certificate": "CCC" }
+                ]
+            }
+        });
+
+        assert_eq!(remove_domain_certificates(&mut acme, "missing.example.com"), 0);
+        assert_eq!(remove_domain_certificates(&mut acme, "rpc.example.com"), 1);
+        assert_eq!(remove_domain_certificates(&mut acme, "node.example.com"), 1);
+
+        // Account (and the resolver) are kept so Traefik doesn't re-register
+        assert_eq!(acme["letsencrypt"]["Certificates"].as_array().unwrap().len(), 0);
+        assert_eq!(acme["letsencrypt"]["Account"]["Email"], "ops@example.com");
+    }
+
+    #[test]
+    fn test_parse_openssl_output() {
+        let output = "notBefore=Jan  1 00:00:00 2024 GMT\nnotAfter=Apr  1 23:59:59 2024 GMT\nissuer=C = US, O = Let's Encrypt, CN = R3\n";
+
+        assert_eq!(SslManager::parse_issuer(output), Some("Let's Encrypt".to_string()));
+        assert_eq!(SslManager::parse_issuer("issuer=CN = Self Signed"), Some("CN = Self Signed".to_string()));
+        assert_eq!(SslManager::parse_issuer("notAfter=Apr  1 23:59:59 2024 GMT"), None);
+
+        let until = SslManager::parse_openssl_date("Apr  1 23:59:59 2024 GMT").unwrap();
+        assert_eq!(until.to_rfc3339(), "2024-04-01T23:59:59+00:00");
+    }
+}
@@ -0,0 +1,236 @@
+//! One-line status for shell prompts and tmux bars (`igra-cli status --oneline`)
+//!
+//! `IGRA: 12/14 up | reth synced | ssl 43d`. Containers, the execution layer's
+//! sync state and the certificate are gathered concurrently and whatever
+//! hasn't answered within `BUDGET` is shown as `?`. The certificate's expiry
+//! comes from a cache refreshed every `SSL_CACHE_TTL`, since a TLS handshake
+//! with the public domain is the slowest part. When Docker can't be reached the
+//! line is just `IGRA: docker-unavailable`.
+//!
+//! Nothing here may wait on the user or on the compose CLI: the config is read
+//! without the secrets store (which can prompt for a passphrase), and the
+//! containers are listed straight from the Docker API.
+
+use crate::core::docker::ProjectContainers;
+use crate::core::l2_monitor::RPC_URL;
+use crate::core::{ssl::SslManager, ConfigManager, Error};
+use chrono::{DateTime, Utc};
+use ethers::providers::{Http, Middleware, Provider};
+use ethers::types::SyncingStatus;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::time::{timeout_at, Instant};
+
+/// Longest the whole line may take
+pub const BUDGET: Duration = Duration::from_secs(1);
+
+pub const DOCKER_UNAVAILABLE: &str = "IGRA: docker-unavailable";
+/// No project found (no compose file, unreadable .env)
+pub const NOT_CONFIGURED: &str = "IGRA: not-configured";
+
+const SSL_CACHE_FILE: &str = "ssl_expiry_cache.json";
+/// Re-check the served certificate after this long
+const SSL_CACHE_TTL: Duration = Duration::from_secs(6 * 3600);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RethSync {
+    Synced,
+    Syncing,
+    Down,
+    /// Didn't answer within the budget
+    Unknown,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatusSummary {
+    pub running: usize,
+    pub total: usize,
+    /// None when there is no execution-layer container
+    pub reth: Option<RethSync>,
+    /// None when no domain is configured; Some(None) when the expiry is unknown
+    pub ssl_days: Option<Option<i64>>,
+}
+
+impl StatusSummary {
+    pub fn render(&self) -> String {
+        let mut parts = vec![format!("IGRA: {}/{} up", self.running, self.total)];
+        if let Some(reth) = self.reth {
+            parts.push(
+                match reth {
+                    RethSync::Synced => "reth synced",
+                    RethSync::Syncing => "reth syncing",
+                    RethSync::Down => "reth down",
+                    RethSync::Unknown => "reth ?",
+                }
+                .to_string(),
+            );
+        }
+        match self.ssl_days {
+            Some(Some(days)) if days < 0 => parts.push("ssl expired".to_string()),
+            Some(Some(days)) => parts.push(format!("ssl {}d", days)),
+            Some(None) => parts.push("ssl ?".to_string()),
+            None => {}
+        }
+        parts.join(" | ")
+    }
+}
+
+/// The line for right now, within `BUDGET`. Never fails: problems become
+/// `DOCKER_UNAVAILABLE`, `NOT_CONFIGURED` or a `?`.
+pub async fn line() -> String {
+    let deadline = Instant::now() + BUDGET;
+
+    // Connecting may wait on an SSH tunnel to a remote host; giving up on it
+    // closes the tunnel again
+    let project = match timeout_at(deadline, ProjectContainers::connect()).await {
+        Ok(Ok(project)) => project,
+        Ok(Err(Error::DockerUnavailable(_))) | Err(_) => return DOCKER_UNAVAILABLE.to_string(),
+        Ok(Err(_)) => return NOT_CONFIGURED.to_string(),
+    };
+
+    // Plain load: attaching the secrets store could prompt for its passphrase
+    let domain = ConfigManager::load(&project.env_file)
+        .ok()
+        .and_then(|config| config.get("IGRA_ORCHESTRA_DOMAIN").map(str::to_string))
+        .filter(|domain| !domain.is_empty());
+
+    match collect(&project, domain.as_deref(), deadline).await {
+        Some(summary) => summary.render(),
+        None => DOCKER_UNAVAILABLE.to_string(),
+    }
+}
+
+/// Gather the summary by `deadline`. None when the containers can't be
+/// listed in time.
+async fn collect(project: &ProjectContainers, domain: Option<&str>, deadline: Instant) -> Option<StatusSummary> {
+    let ssl_days = async {
+        match domain {
+            Some(domain) => Some(ssl_days(domain, deadline).await),
+            None => None,
+        }
+    };
+    let (containers, reth, ssl_days) = tokio::join!(
+        timeout_at(deadline, project.states()),
+        timeout_at(deadline, reth_sync()),
+        ssl_days,
+    );
+    let containers = containers.ok()?.ok()?;
+
+    let reth = containers.iter().find(|(name, _)| name == "execution-layer").map(|(_, state)| {
+        if !state.is_running() {
+            RethSync::Down
+        } else {
+            reth.unwrap_or(RethSync::Unknown)
+        }
+    });
+
+    Some(StatusSummary {
+        running: containers.iter().filter(|(_, state)| state.is_running()).count(),
+        total: containers.len(),
+        reth,
+        ssl_days,
+    })
+}
+
+async fn reth_sync() -> RethSync {
+    let Ok(provider) = Provider::<Http>::try_from(RPC_URL) else {
+        return RethSync::Down;
+    };
+    match provider.syncing().await {
+        Ok(SyncingStatus::IsFalse) => RethSync::Synced,
+        Ok(_) => RethSync::Syncing,
+        Err(_) => RethSync::Down,
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SslCache {
+    domain: String,
+    valid_until: DateTime<Utc>,
+    checked_at: DateTime<Utc>,
+}
+
+impl SslCache {
+    /// Expiry for `domain`, if this entry is about it and no older than `max_age`
+    fn valid_until(&self, domain: &str, now: DateTime<Utc>, max_age: Duration) -> Option<DateTime<Utc>> {
+        let age = (now - self.checked_at).to_std().unwrap_or_default();
+        (self.domain == domain && age < max_age).then_some(self.valid_until)
+    }
+}
+
+fn ssl_cache_path() -> Option<PathBuf> {
+    Some(dirs::config_dir()?.join("igra-cli").join(SSL_CACHE_FILE))
+}
+
+fn load_ssl_cache() -> Option<SslCache> {
+    serde_json::from_str(&std::fs::read_to_string(ssl_cache_path()?).ok()?).ok()
+}
+
+fn save_ssl_cache(cache: &SslCache) {
+    let Some(path) = ssl_cache_path() else { return };
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).ok();
+    }
+    if let Ok(json) = serde_json::to_string(cache) {
+        std::fs::write(path, json).ok();
+    }
+}
+
+/// Days until the served certificate expires: from a fresh cache, else a
+/// check bounded by `deadline`, else whatever the cache last recorded
+async fn ssl_days(domain: &str, deadline: Instant) -> Option<i64> {
+    let now = Utc::now();
+    let cache = load_ssl_cache();
+    let days = |until: DateTime<Utc>| (until - now).num_days();
+
+    if let Some(until) = cache.as_ref().and_then(|c| c.valid_until(domain, now, SSL_CACHE_TTL)) {
+        return Some(days(until));
+    }
+
+    let checked = timeout_at(deadline, SslManager::check_certificate_with_openssl(domain)).await;
+    if let Some(until) = checked.ok().and_then(|info| info.ok()).and_then(|info| info.valid_until) {
+        save_ssl_cache(&SslCache { domain: domain.to_string(), valid_until: until, checked_at: now });
+        return Some(days(until));
+    }
+
+    cache.and_then(|c| c.valid_until(domain, now, Duration::MAX)).map(days)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let summary = StatusSummary { running: 12, total: 14, reth: Some(RethSync::Synced), ssl_days: Some(Some(43)) };
+        assert_eq!(summary.render(), "IGRA: 12/14 up | reth synced | ssl 43d");
+
+        // No execution layer and no domain: just the containers
+        let summary = StatusSummary { running: 3, total: 3, reth: None, ssl_days: None };
+        assert_eq!(summary.render(), "IGRA: 3/3 up");
+
+        let summary = StatusSummary { running: 0, total: 14, reth: Some(RethSync::Unknown), ssl_days: Some(None) };
+        assert_eq!(summary.render(), "IGRA: 0/14 up | reth ? | ssl ?");
+
+        let summary = StatusSummary { running: 1, total: 1, reth: Some(RethSync::Down), ssl_days: Some(Some(-2)) };
+        assert_eq!(summary.render(), "IGRA: 1/1 up | reth down | ssl expired");
+    }
+
+    #[test]
+    fn test_ssl_cache_freshness() {
+        let now = Utc::now();
+        let until = now + chrono::Duration::days(43);
+        let cache = SslCache {
+            domain: "igra.example.com".to_string(),
+            valid_until: until,
+            checked_at: now - chrono::Duration::hours(1),
+        };
+
+        assert_eq!(cache.valid_until("igra.example.com", now, SSL_CACHE_TTL), Some(until));
+        assert_eq!(cache.valid_until("other.example.com", now, SSL_CACHE_TTL), None);
+        // Stale for a fresh read, still usable as a fallback
+        assert_eq!(cache.valid_until("igra.example.com", now, Duration::from_secs(60)), None);
+        assert_eq!(cache.valid_until("igra.example.com", now, Duration::MAX), Some(until));
+    }
+}
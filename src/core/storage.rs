@@ -4,6 +4,24 @@ use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use std::process::Command;
 
+use crate::core::docker::{container_runtime, runtime_program, ContainerRuntime};
+
+/// Name fragment of the execution layer's (reth) database volume
+const RETH_VOLUME_MARKER: &str = "execution_layer";
+
+/// Snapshots with a reth volume size needed before projecting, so a single
+/// measurement (or two close together) is never extrapolated
+const MIN_PROJECTION_SAMPLES: usize = 3;
+
+/// ... and the time they must span
+const MIN_PROJECTION_SPAN_HOURS: i64 = 24;
+
+/// The disk-full projection warns below this many days
+pub const DISK_FULL_WARN_DAYS: f64 = 30.0;
+
+/// ... and is critical below this many
+pub const DISK_FULL_CRITICAL_DAYS: f64 = 7.0;
+
 /// Main storage analysis result
 #[derive(Debug, Clone, Serialize)]
 pub struct StorageAnalysis {
@@ -15,6 +33,7 @@ pub struct StorageAnalysis {
     pub container_logs: Vec<ContainerLogInfo>,
     pub reclaimable_space: u64,
     pub growth_rate: Option<GrowthRate>,
+    pub disk_projection: Option<DiskFullProjection>,
 }
 
 /// System disk usage information
@@ -71,6 +90,48 @@ pub enum GrowthTrend {
     Declining,
 }
 
+/// When the disk holding the reth database fills up at the rates seen in
+/// the snapshot history
+#[derive(Debug, Clone, Serialize)]
+pub struct DiskFullProjection {
+    /// reth database volume size at the latest snapshot
+    pub volume_bytes: u64,
+    pub volume_bytes_per_day: f64,
+    /// Growth of everything else on the disk: other volumes, images, host files
+    pub other_bytes_per_day: f64,
+    pub available_bytes: u64,
+    /// None when the disk isn't filling
+    pub days_until_full: Option<f64>,
+    /// Snapshots the rates come from
+    pub samples: usize,
+}
+
+impl DiskFullProjection {
+    /// Rate the whole disk fills at. Never below the reth volume's own
+    /// growth: a prune elsewhere during the window would otherwise make the
+    /// projection look better than the database's growth allows.
+    pub fn disk_bytes_per_day(&self) -> f64 {
+        (self.volume_bytes_per_day + self.other_bytes_per_day).max(self.volume_bytes_per_day)
+    }
+
+    pub fn is_critical(&self) -> bool {
+        self.days_until_full.map(|days| days < DISK_FULL_CRITICAL_DAYS).unwrap_or(false)
+    }
+
+    /// One-line warning when the disk fills within DISK_FULL_WARN_DAYS
+    pub fn warning(&self) -> Option<String> {
+        let days = self.days_until_full.filter(|days| *days < DISK_FULL_WARN_DAYS)?;
+        Some(format!(
+            "Disk full in ~{:.0} days: reth database {} growing {}/day, whole disk {}/day, {} free",
+            days,
+            format_bytes(self.volume_bytes),
+            format_bytes(self.volume_bytes_per_day.max(0.0) as u64),
+            format_bytes(self.disk_bytes_per_day().max(0.0) as u64),
+            format_bytes(self.available_bytes)
+        ))
+    }
+}
+
 /// Storage measurement for history tracking
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StorageMeasurement {
@@ -78,19 +139,20 @@ pub struct StorageMeasurement {
     pub total_used_bytes: u64,
     pub docker_volumes_bytes: u64,
     pub docker_images_bytes: u64,
+    /// reth database volume; None when it wasn't found or couldn't be sized
+    #[serde(default)]
+    pub reth_volume_bytes: Option<u64>,
 }
 
 /// Storage history file format
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct StorageHistory {
     pub measurements: Vec<StorageMeasurement>,
 }
 
 impl StorageHistory {
     pub fn new() -> Self {
-        Self {
-            measurements: Vec::new(),
-        }
+        Self::default()
     }
 
     /// Load from file or create new
@@ -196,6 +258,7 @@ pub async fn analyze_storage() -> Result<StorageAnalysis> {
     // Load history and calculate growth rate
     let history = StorageHistory::load().unwrap_or_else(|_| StorageHistory::new());
     let growth_rate = calculate_growth_rate(&history, &system_disk);
+    let disk_projection = project_disk_full(&history, &system_disk);
 
     Ok(StorageAnalysis {
         system_disk,
@@ -221,6 +284,7 @@ pub async fn analyze_storage() -> Result<StorageAnalysis> {
         container_logs,
         reclaimable_space: reclaimable,
         growth_rate,
+        disk_projection,
     })
 }
 
@@ -279,8 +343,13 @@ fn get_system_disk_usage() -> Result<DiskUsage> {
 
 /// Parse docker system df output
 fn get_docker_system_df() -> Result<DockerSystemDfSummary> {
-    let output = Command::new("docker")
-        .args(&["system", "df", "--format", "{{.Type}}\t{{.TotalCount}}\t{{.Active}}\t{{.Size}}\t{{.Reclaimable}}"])
+    // Podman names the count column Total
+    let format = match container_runtime() {
+        ContainerRuntime::Docker => "{{.Type}}\t{{.TotalCount}}\t{{.Active}}\t{{.Size}}\t{{.Reclaimable}}",
+        ContainerRuntime::Podman => "{{.Type}}\t{{.Total}}\t{{.Active}}\t{{.Size}}\t{{.Reclaimable}}",
+    };
+    let output = Command::new(runtime_program())
+        .args(["system", "df", "--format", format])
         .output()
         .context("Failed to run docker system df")?;
 
@@ -348,8 +417,8 @@ fn get_docker_system_df() -> Result<DockerSystemDfSummary> {
 /// Get individual Docker volume usage
 fn get_docker_volumes_usage() -> Result<Vec<VolumeUsage>> {
     // Get list of volumes
-    let list_output = Command::new("docker")
-        .args(&["volume", "ls", "-q"])
+    let list_output = Command::new(runtime_program())
+        .args(["volume", "ls", "-q"])
         .output()
         .context("Failed to list docker volumes")?;
 
@@ -366,8 +435,8 @@ fn get_docker_volumes_usage() -> Result<Vec<VolumeUsage>> {
         }
 
         // Get volume details
-        let inspect_output = Command::new("docker")
-            .args(&["volume", "inspect", &name, "--format", "{{.Mountpoint}}"])
+        let inspect_output = Command::new(runtime_program())
+            .args(["volume", "inspect", &name, "--format", "{{.Mountpoint}}"])
             .output();
 
         let mount_point = if let Ok(output) = inspect_output {
@@ -379,7 +448,7 @@ fn get_docker_volumes_usage() -> Result<Vec<VolumeUsage>> {
         // Get size using du (requires sudo, might fail)
         let size_bytes = if !mount_point.is_empty() {
             let du_output = Command::new("sudo")
-                .args(&["du", "-sb", &mount_point])
+                .args(["du", "-sb", &mount_point])
                 .output();
 
             if let Ok(output) = du_output {
@@ -400,8 +469,8 @@ fn get_docker_volumes_usage() -> Result<Vec<VolumeUsage>> {
         let critical = name.contains("viaduct") || name.contains("viaduct_data");
 
         // Check if volume is in use by running container
-        let ps_output = Command::new("docker")
-            .args(&["ps", "-a", "--filter", &format!("volume={}", name), "--format", "{{.ID}}"])
+        let ps_output = Command::new(runtime_program())
+            .args(["ps", "-a", "--filter", &format!("volume={}", name), "--format", "{{.ID}}"])
             .output();
 
         let in_use = if let Ok(output) = ps_output {
@@ -420,7 +489,7 @@ fn get_docker_volumes_usage() -> Result<Vec<VolumeUsage>> {
     }
 
     // Sort by size descending
-    volumes.sort_by(|a, b| b.size_bytes.cmp(&a.size_bytes));
+    volumes.sort_by_key(|v| std::cmp::Reverse(v.size_bytes));
 
     Ok(volumes)
 }
@@ -482,6 +551,55 @@ fn calculate_growth_rate(history: &StorageHistory, current: &DiskUsage) -> Optio
     })
 }
 
+/// Project when the disk fills from the snapshots that sized the reth
+/// volume. Rates come from the first and last such snapshot of the last 30
+/// days; the disk's used-bytes delta covers the other volumes and host usage.
+fn project_disk_full(history: &StorageHistory, disk: &DiskUsage) -> Option<DiskFullProjection> {
+    let cutoff = Utc::now() - chrono::Duration::days(30);
+    let samples: Vec<(&StorageMeasurement, u64)> = history
+        .measurements
+        .iter()
+        .filter(|m| m.timestamp > cutoff)
+        .filter_map(|m| m.reth_volume_bytes.map(|bytes| (m, bytes)))
+        .collect();
+    if samples.len() < MIN_PROJECTION_SAMPLES {
+        return None;
+    }
+
+    let (first, first_volume) = samples.first()?;
+    let (last, last_volume) = samples.last()?;
+    let span = last.timestamp - first.timestamp;
+    if span.num_hours() < MIN_PROJECTION_SPAN_HOURS {
+        return None;
+    }
+    let days = span.num_seconds() as f64 / 86_400.0;
+
+    let volume_bytes_per_day = (*last_volume as f64 - *first_volume as f64) / days;
+    let disk_bytes_per_day = (last.total_used_bytes as f64 - first.total_used_bytes as f64) / days;
+
+    let mut projection = DiskFullProjection {
+        volume_bytes: *last_volume,
+        volume_bytes_per_day,
+        other_bytes_per_day: disk_bytes_per_day - volume_bytes_per_day,
+        available_bytes: disk.available_bytes,
+        days_until_full: None,
+        samples: samples.len(),
+    };
+    let rate = projection.disk_bytes_per_day();
+    if rate > 0.0 {
+        projection.days_until_full = Some(disk.available_bytes as f64 / rate);
+    }
+    Some(projection)
+}
+
+/// The disk-full projection from the snapshot history and current `df`,
+/// without re-measuring volumes. None until enough snapshots exist.
+pub fn disk_full_projection() -> Result<Option<DiskFullProjection>> {
+    let history = StorageHistory::load()?;
+    let disk = get_system_disk_usage()?;
+    Ok(project_disk_full(&history, &disk))
+}
+
 /// Parse size string like "4.236GB" to bytes
 fn parse_size_string(s: &str) -> u64 {
     let s = s.trim();
@@ -515,12 +633,10 @@ fn parse_size_from_reclaimable(s: &str) -> u64 {
 
 /// Get container log sizes for all running containers
 pub async fn get_container_log_sizes() -> Result<Vec<ContainerLogInfo>> {
-    use bollard::Docker;
     use bollard::container::ListContainersOptions;
     use std::collections::HashMap;
 
-    let docker = Docker::connect_with_local_defaults()
-        .context("Failed to connect to Docker daemon")?;
+    let docker = crate::core::docker::connect_docker().await?;
 
     let mut filters = HashMap::new();
     filters.insert("status".to_string(), vec!["running".to_string(), "exited".to_string(), "paused".to_string()]);
@@ -565,7 +681,7 @@ pub async fn get_container_log_sizes() -> Result<Vec<ContainerLogInfo>> {
     }
 
     // Sort by size descending (largest first)
-    log_infos.sort_by(|a, b| b.log_size_bytes.cmp(&a.log_size_bytes));
+    log_infos.sort_by_key(|l| std::cmp::Reverse(l.log_size_bytes));
 
     Ok(log_infos)
 }
@@ -579,10 +695,14 @@ pub async fn truncate_container_log(container_id: &str) -> Result<()> {
         anyhow::bail!("Log file not found: {}", log_path);
     }
 
+    if crate::core::dry_run::skip(|| format!("sudo truncate -s 0 {}", log_path)) {
+        return Ok(());
+    }
+
     // Use sudo truncate command to reset log file to 0 bytes
     // This preserves the file (important for Docker log rotation config)
     let output = Command::new("sudo")
-        .args(&["truncate", "-s", "0", &log_path])
+        .args(["truncate", "-s", "0", &log_path])
         .output()
         .context("Failed to execute truncate command")?;
 
@@ -594,6 +714,50 @@ pub async fn truncate_container_log(container_id: &str) -> Result<()> {
     Ok(())
 }
 
+/// What a storage prune removes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PruneTarget {
+    BuildCache,
+    /// Unused (dangling) images
+    Images,
+}
+
+impl PruneTarget {
+    fn args(self, runtime: ContainerRuntime) -> &'static [&'static str] {
+        match (self, runtime) {
+            (PruneTarget::BuildCache, ContainerRuntime::Docker) => &["builder", "prune", "-f"],
+            // Podman has no `builder`; its build cache goes with `image prune`
+            (PruneTarget::BuildCache, ContainerRuntime::Podman) => &["image", "prune", "-f", "--build-cache"],
+            (PruneTarget::Images, _) => &["image", "prune", "-f"],
+        }
+    }
+}
+
+/// Prune `target` and return the runtime's "Total reclaimed space" line, if
+/// it printed one. In a dry run nothing is pruned and `None` is returned.
+pub fn prune(target: PruneTarget) -> Result<Option<String>> {
+    let args = target.args(container_runtime());
+    if crate::core::dry_run::skip(|| format!("{} {}", runtime_program(), args.join(" "))) {
+        return Ok(None);
+    }
+
+    let output = Command::new(runtime_program())
+        .args(args)
+        .output()
+        .with_context(|| format!("Failed to run {}", runtime_program()))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("{}", stderr.trim());
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(stdout
+        .lines()
+        .find(|l| l.contains("Total reclaimed space"))
+        .map(|l| l.trim().to_string()))
+}
+
 /// Format bytes to human-readable string
 pub fn format_bytes(bytes: u64) -> String {
     const KB: u64 = 1_000;
@@ -652,6 +816,13 @@ pub async fn check_and_save_snapshot_if_needed() -> bool {
                 total_used_bytes: analysis.system_disk.used_bytes,
                 docker_volumes_bytes: analysis.docker_volumes.iter().map(|v| v.size_bytes).sum(),
                 docker_images_bytes: analysis.docker_images.total_bytes,
+                // du needs sudo; a failed size reads 0, which isn't a sample
+                reth_volume_bytes: analysis
+                    .docker_volumes
+                    .iter()
+                    .find(|v| v.name.contains(RETH_VOLUME_MARKER))
+                    .map(|v| v.size_bytes)
+                    .filter(|bytes| *bytes > 0),
             };
 
             history.add_measurement(measurement);
@@ -661,3 +832,69 @@ pub async fn check_and_save_snapshot_if_needed() -> bool {
         Err(_) => false,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GB: u64 = 1_000_000_000;
+
+    fn disk(available: u64) -> DiskUsage {
+        DiskUsage {
+            filesystem: "/dev/sda1".to_string(),
+            total_bytes: 1_000 * GB,
+            used_bytes: 1_000 * GB - available,
+            available_bytes: available,
+            use_percent: 0.0,
+            mount_point: "/".to_string(),
+        }
+    }
+
+    /// (days ago, disk used GB, reth volume GB)
+    fn history(samples: &[(i64, u64, Option<u64>)]) -> StorageHistory {
+        StorageHistory {
+            measurements: samples
+                .iter()
+                .map(|&(days_ago, used, reth)| StorageMeasurement {
+                    timestamp: Utc::now() - chrono::Duration::days(days_ago),
+                    total_used_bytes: used * GB,
+                    docker_volumes_bytes: 0,
+                    docker_images_bytes: 0,
+                    reth_volume_bytes: reth.map(|gb| gb * GB),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_projection_needs_samples() {
+        // Two samples, or samples without a reth size, aren't enough
+        assert!(project_disk_full(&history(&[(4, 500, Some(300)), (0, 520, Some(310))]), &disk(100 * GB)).is_none());
+        let unmeasured = history(&[(4, 500, None), (2, 510, None), (0, 520, Some(310))]);
+        assert!(project_disk_full(&unmeasured, &disk(100 * GB)).is_none());
+    }
+
+    #[test]
+    fn test_projection_covers_whole_disk() {
+        // reth grows 5GB/day, the rest of the disk 5GB/day more
+        let growing = history(&[(4, 500, Some(300)), (2, 520, Some(310)), (0, 540, Some(320))]);
+        let projection = project_disk_full(&growing, &disk(100 * GB)).unwrap();
+        assert_eq!(projection.samples, 3);
+        assert!((projection.volume_bytes_per_day - 5.0 * GB as f64).abs() < 1e6);
+        assert!((projection.other_bytes_per_day - 5.0 * GB as f64).abs() < 1e6);
+        assert!((projection.days_until_full.unwrap() - 10.0).abs() < 0.01);
+        assert!(projection.warning().is_some());
+        assert!(!projection.is_critical());
+
+        // A prune elsewhere doesn't hide the database's own growth
+        let pruned = history(&[(4, 500, Some(300)), (2, 480, Some(310)), (0, 490, Some(320))]);
+        let projection = project_disk_full(&pruned, &disk(100 * GB)).unwrap();
+        assert!((projection.days_until_full.unwrap() - 20.0).abs() < 0.01);
+
+        // Not filling: no countdown, no warning
+        let flat = history(&[(4, 500, Some(300)), (2, 500, Some(300)), (0, 500, Some(300))]);
+        let projection = project_disk_full(&flat, &disk(100 * GB)).unwrap();
+        assert!(projection.days_until_full.is_none());
+        assert!(projection.warning().is_none());
+    }
+}
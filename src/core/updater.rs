@@ -1,9 +1,11 @@
-/// Version checking and update management
-/// Shared between TUI, Web UI API, and CLI
+//! Version checking and update management
+//! Shared between TUI, Web UI API, and CLI
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use semver::Version;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
 
 const GITHUB_API_URL: &str = "https://api.github.com/repos/Zorglub4242/Igra-mgt/releases/latest";
 const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -82,10 +84,8 @@ pub async fn check_for_updates() -> Result<VersionInfo> {
     // Compare versions
     let update_available = latest > current;
 
-    // Find Linux x86_64 binary asset
-    let download_url = release.assets
-        .iter()
-        .find(|asset| asset.name.contains("linux-x86_64"))
+    // Binary asset for the platform this build runs on
+    let download_url = select_asset(&release.assets, &asset_suffixes())
         .map(|asset| asset.browser_download_url.clone());
 
     Ok(VersionInfo {
@@ -112,36 +112,228 @@ pub fn get_current_version() -> VersionInfo {
     }
 }
 
-/// Download latest release binary to specified path
-pub async fn download_latest_release(destination: &std::path::Path) -> Result<()> {
-    let version_info = check_for_updates().await?;
+/// Release asset name endings that fit this build, most specific first.
+/// Assets are named `igra-cli[-vX.Y.Z]-<os>-<arch>[-musl].tar.gz`.
+pub fn asset_suffixes() -> Vec<String> {
+    asset_suffixes_for(std::env::consts::OS, std::env::consts::ARCH, cfg!(target_env = "musl"))
+}
 
-    let download_url = version_info.download_url
-        .ok_or_else(|| anyhow::anyhow!("No download URL found for latest release"))?;
+fn asset_suffixes_for(os: &str, arch: &str, musl: bool) -> Vec<String> {
+    let arch_names: &[&str] = match arch {
+        "x86_64" => &["x86_64"],
+        "aarch64" => &["arm64", "aarch64"],
+        _ => &[],
+    };
 
-    let client = reqwest::Client::builder()
+    let mut suffixes = Vec::new();
+    match os {
+        "linux" => {
+            for arch in arch_names {
+                // A musl host can't run a glibc binary; a glibc host can run
+                // the static musl one
+                if !musl {
+                    suffixes.push(format!("linux-{}.tar.gz", arch));
+                    suffixes.push(format!("linux-{}-gnu.tar.gz", arch));
+                }
+                suffixes.push(format!("linux-{}-musl.tar.gz", arch));
+            }
+        }
+        "macos" if !arch_names.is_empty() => {
+            for arch in arch_names {
+                suffixes.push(format!("macos-{}.tar.gz", arch));
+            }
+            suffixes.push("macos-universal.tar.gz".to_string());
+        }
+        _ => {}
+    }
+    suffixes
+}
+
+/// First asset matching one of `suffixes`, in suffix order
+fn select_asset<'a>(assets: &'a [GitHubAsset], suffixes: &[String]) -> Option<&'a GitHubAsset> {
+    suffixes.iter().find_map(|suffix| {
+        assets
+            .iter()
+            .find(|asset| asset.name.starts_with("igra-cli-") && asset.name.ends_with(&format!("-{}", suffix)))
+    })
+}
+
+/// Expected SHA-256 of `name` from a `SHA256SUMS` file or a per-asset
+/// `.sha256` file (which may hold just the hash)
+fn find_checksum(sums: &str, name: &str) -> Option<String> {
+    let is_hash = |s: &str| s.len() == 64 && s.chars().all(|c| c.is_ascii_hexdigit());
+    let mut bare = None;
+
+    for line in sums.lines() {
+        let mut parts = line.split_whitespace();
+        let Some(hash) = parts.next().filter(|h| is_hash(h)) else { continue };
+        match parts.next() {
+            Some(file) if file.trim_start_matches('*').rsplit('/').next() == Some(name) => {
+                return Some(hash.to_lowercase());
+            }
+            None => bare = Some(hash.to_lowercase()),
+            _ => {}
+        }
+    }
+    bare
+}
+
+/// Check `data` against a minisign `signature` made with `public_key` (the
+/// base64 line of a minisign .pub file)
+fn verify_signature(data: &[u8], signature: &str, public_key: &str) -> Result<()> {
+    let public_key = minisign_verify::PublicKey::from_base64(public_key.trim())
+        .map_err(|e| anyhow::anyhow!("[update] public_key is not a minisign public key: {}", e))?;
+    let signature = minisign_verify::Signature::decode(signature)
+        .map_err(|e| anyhow::anyhow!("Malformed release signature: {}", e))?;
+    public_key
+        .verify(data, &signature, false)
+        .map_err(|e| anyhow::anyhow!("Release signature does not verify against [update] public_key: {}", e))
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn http_client(timeout_secs: u64) -> Result<reqwest::Client> {
+    Ok(reqwest::Client::builder()
         .user_agent(format!("igra-cli/{}", CURRENT_VERSION))
-        .timeout(std::time::Duration::from_secs(300)) // 5 minute timeout for download
-        .build()?;
+        .timeout(std::time::Duration::from_secs(timeout_secs))
+        .build()?)
+}
 
-    let response = client
-        .get(&download_url)
+async fn download(client: &reqwest::Client, url: &str) -> Result<Vec<u8>> {
+    let response = client.get(url).send().await.with_context(|| format!("Failed to download {}", url))?;
+    if !response.status().is_success() {
+        anyhow::bail!("Failed to download {}: HTTP {}", url, response.status());
+    }
+    Ok(response.bytes().await.context("Failed to read download")?.to_vec())
+}
+
+/// Staging directory next to the binary, removed however the update ends
+struct Staging(PathBuf);
+
+impl Drop for Staging {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Replace the running binary with the latest release for this platform.
+///
+/// The release's checksum file must carry a minisign signature from the key
+/// set in config.toml (`[update] public_key`) - the checksums come from the
+/// same place as the binary, so on their own they only catch a corrupt
+/// download. The archive must match its signed checksum, and the new binary
+/// must run (`--version`) before it is moved over the old one with a single
+/// rename, so a failure at any step leaves the installed binary as it was.
+/// Returns the installed version.
+pub async fn self_update() -> Result<String> {
+    let public_key = crate::utils::AppConfig::update_public_key().ok_or_else(|| {
+        anyhow::anyhow!(
+            "No release signing key configured; set [update] public_key in ~/.config/igra-cli/config.toml \
+             to the minisign key published at {}/releases",
+            REPO_URL
+        )
+    })?;
+    let client = http_client(30)?;
+    let release: GitHubRelease = client
+        .get(GITHUB_API_URL)
         .send()
         .await
-        .context("Failed to download release")?;
+        .context("Failed to fetch latest release from GitHub")?
+        .error_for_status()
+        .context("Failed to fetch latest release from GitHub")?
+        .json()
+        .await
+        .context("Failed to parse GitHub release response")?;
+    let version = release.tag_name.trim_start_matches('v').to_string();
 
-    if !response.status().is_success() {
-        anyhow::bail!("Failed to download release: HTTP {}", response.status());
+    let suffixes = asset_suffixes();
+    let asset = select_asset(&release.assets, &suffixes).ok_or_else(|| {
+        anyhow::anyhow!(
+            "Release {} has no binary for {}-{}{} (looked for: {}). Build from source instead: {}#installation",
+            release.tag_name,
+            std::env::consts::OS,
+            std::env::consts::ARCH,
+            if cfg!(target_env = "musl") { " (musl)" } else { "" },
+            if suffixes.is_empty() { "nothing for this platform".to_string() } else { suffixes.join(", ") },
+            REPO_URL
+        )
+    })?;
+
+    // Refuse unverified binaries
+    let sums_asset = release
+        .assets
+        .iter()
+        .find(|a| a.name == format!("{}.sha256", asset.name))
+        .or_else(|| release.assets.iter().find(|a| a.name == "SHA256SUMS" || a.name == "SHA256SUMS.txt"))
+        .ok_or_else(|| anyhow::anyhow!("Release {} publishes no checksums; refusing to install an unverified binary", release.tag_name))?;
+    let sums_bytes = download(&client, &sums_asset.browser_download_url).await?;
+    let signature_asset = release
+        .assets
+        .iter()
+        .find(|a| a.name == format!("{}.minisig", sums_asset.name))
+        .ok_or_else(|| anyhow::anyhow!("Release {} has no signature for {}; refusing to install an unverified binary", release.tag_name, sums_asset.name))?;
+    let signature = download(&client, &signature_asset.browser_download_url).await?;
+    verify_signature(&sums_bytes, &String::from_utf8_lossy(&signature), &public_key)?;
+
+    let sums = String::from_utf8_lossy(&sums_bytes).to_string();
+    let expected = find_checksum(&sums, &asset.name)
+        .ok_or_else(|| anyhow::anyhow!("{} has no checksum for {}", sums_asset.name, asset.name))?;
+
+    let archive = download(&http_client(300)?, &asset.browser_download_url).await?;
+    let actual = sha256_hex(&archive);
+    if actual != expected {
+        anyhow::bail!("Checksum mismatch for {}: expected {}, got {}", asset.name, expected, actual);
     }
 
-    let bytes = response
-        .bytes()
-        .await
-        .context("Failed to read release bytes")?;
+    // Stage in the binary's own directory so the final rename stays on one filesystem
+    let current = std::env::current_exe()
+        .and_then(|p| p.canonicalize())
+        .context("Failed to locate the running binary")?;
+    let dir = current.parent().context("Running binary has no parent directory")?;
+    let staging = Staging(dir.join(format!(".igra-cli-update-{}", std::process::id())));
+    std::fs::create_dir(&staging.0)
+        .with_context(|| format!("Can't write to {} (re-run with sudo?)", dir.display()))?;
+
+    let archive_path = staging.0.join(&asset.name);
+    std::fs::write(&archive_path, &archive).context("Failed to write downloaded archive")?;
+    extract(&archive_path, &staging.0)?;
+
+    let new_binary = staging.0.join("igra-cli");
+    if !new_binary.is_file() {
+        anyhow::bail!("{} does not contain an igra-cli binary", asset.name);
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&new_binary, std::fs::Permissions::from_mode(0o755))?;
+    }
+    let check = std::process::Command::new(&new_binary)
+        .arg("--version")
+        .output()
+        .context("Downloaded binary does not run on this system")?;
+    if !check.status.success() {
+        anyhow::bail!("Downloaded binary does not run on this system; build from source instead");
+    }
 
-    std::fs::write(destination, bytes)
-        .context("Failed to write downloaded file")?;
+    std::fs::rename(&new_binary, &current)
+        .with_context(|| format!("Failed to replace {} (re-run with sudo?)", current.display()))?;
 
+    Ok(version)
+}
+
+fn extract(archive: &Path, into: &Path) -> Result<()> {
+    let output = std::process::Command::new("tar")
+        .arg("-xzf")
+        .arg(archive)
+        .arg("-C")
+        .arg(into)
+        .output()
+        .context("Failed to run tar")?;
+    if !output.status.success() {
+        anyhow::bail!("Failed to extract {}: {}", archive.display(), String::from_utf8_lossy(&output.stderr).trim());
+    }
     Ok(())
 }
 
@@ -155,6 +347,58 @@ mod tests {
         assert!(version.is_ok(), "Current version should be valid semver");
     }
 
+    #[test]
+    fn test_asset_selection() {
+        let asset = |name: &str| GitHubAsset {
+            name: name.to_string(),
+            browser_download_url: format!("https://example.invalid/{}", name),
+        };
+        let assets = vec![
+            asset("igra-cli-v0.12.0-linux-x86_64.tar.gz"),
+            asset("igra-cli-v0.12.0-linux-x86_64-musl.tar.gz"),
+            asset("igra-cli-v0.12.0-linux-arm64-musl.tar.gz"),
+            asset("SHA256SUMS"),
+        ];
+        let pick = |os, arch, musl| select_asset(&assets, &asset_suffixes_for(os, arch, musl)).map(|a| a.name.as_str());
+
+        assert_eq!(pick("linux", "x86_64", false), Some("igra-cli-v0.12.0-linux-x86_64.tar.gz"));
+        assert_eq!(pick("linux", "x86_64", true), Some("igra-cli-v0.12.0-linux-x86_64-musl.tar.gz"));
+        // No glibc arm64 build: the static musl one runs too
+        assert_eq!(pick("linux", "aarch64", false), Some("igra-cli-v0.12.0-linux-arm64-musl.tar.gz"));
+        assert_eq!(pick("macos", "aarch64", false), None);
+        assert_eq!(pick("windows", "x86_64", false), None);
+    }
+
+    #[test]
+    fn test_find_checksum() {
+        let hash = "a".repeat(64);
+        let other = "b".repeat(64);
+        let sums = format!("{}  igra-cli-linux-x86_64.tar.gz\n{} *igra-cli-linux-arm64.tar.gz\n", hash, other);
+        assert_eq!(find_checksum(&sums, "igra-cli-linux-x86_64.tar.gz"), Some(hash.clone()));
+        assert_eq!(find_checksum(&sums, "igra-cli-linux-arm64.tar.gz"), Some(other));
+        assert_eq!(find_checksum(&sums, "igra-cli-macos-universal.tar.gz"), None);
+        assert_eq!(find_checksum(&format!("{}\n", hash.to_uppercase()), "anything"), Some(hash));
+        assert_eq!(sha256_hex(b"abc"), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+    }
+
+    #[test]
+    fn test_verify_signature() {
+        // A throwaway key and its minisign (prehashed) signature of `sums`
+        let public_key = "RWQfLj1MW2p5iEMBY8h0zDMd+syTIFw82IXOIolGsneqLpY7yGSaGoPg";
+        let other_key = "RWQfLj1MW2p5iHlmRLaKw9mu5GrWcKhtqz+iJoXOPaP+JeVSkEl7IOvn";
+        let signature = "untrusted comment: signature from minisign secret key\n\
+            RUQfLj1MW2p5iLnfV/qGhjhH0QYPgDgmORwEk7CeuJKL4jm0h7S6b5dLcNN47Hzus0zQc+ypyVvnr59t8RNyRIuWUoTDHCuEAwE=\n\
+            trusted comment: timestamp:1767225600\tfile:SHA256SUMS\thashed\n\
+            2UQg3V+hQgjhgIE8LCV+qqddT95AaIuESLafwkThYvOo0AcHdp4nb0lZJG3isUvfvrpBvDaVSOMsNZyblIfrBA==\n";
+        let sums = format!("{}  igra-cli-v0.12.0-linux-x86_64.tar.gz\n", "a".repeat(64));
+
+        assert!(verify_signature(sums.as_bytes(), signature, public_key).is_ok());
+        // Tampered checksums, another key, or no key at all
+        assert!(verify_signature(sums.replace('a', "b").as_bytes(), signature, public_key).is_err());
+        assert!(verify_signature(sums.as_bytes(), signature, other_key).is_err());
+        assert!(verify_signature(sums.as_bytes(), signature, "not a key").is_err());
+    }
+
     #[test]
     fn test_get_current_version() {
         let info = get_current_version();
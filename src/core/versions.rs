@@ -1,6 +1,6 @@
-/// Docker image version checking
-///
-/// Queries Docker Hub and GitHub to check for latest versions
+//! Docker image version checking
+//!
+//! Queries Docker Hub and GitHub to check for latest versions
 
 use anyhow::Result;
 use serde::Deserialize;
@@ -80,7 +80,7 @@ pub async fn check_versions(current_images: HashMap<String, String>) -> HashMap<
         // Extract just the image name
         let image_name = image_full
             .split('/')
-            .last()
+            .next_back()
             .unwrap_or(&image_full)
             .split(':')
             .next()
@@ -1,12 +1,23 @@
-/// Wallet management
+//! Wallet management
 
-use anyhow::{anyhow, Context, Result};
+use anyhow::{anyhow, Context};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tonic::transport::{Channel, Endpoint};
+
+use crate::core::docker::runtime_program;
+use crate::core::error::{Error, Result};
+use crate::core::{ConfigManager, Network};
+use crate::core::wallet_history::{join_tx_ids, Direction, HistoryEntry, WalletHistory, WalletTotals};
+use crate::core::wallet_password::WalletPassword;
+use crate::utils::sompi_to_kas;
 
 // Include the generated gRPC client code
 pub mod kaswallet_proto {
@@ -15,11 +26,120 @@ pub mod kaswallet_proto {
 
 use kaswallet_proto::wallet_client::WalletClient;
 
+/// Number of kaswallet workers (kaswallet-0 .. kaswallet-4)
+pub const WORKER_COUNT: usize = 5;
+
+/// Upper bound for each daemon call when listing wallets; a worker that
+/// doesn't answer in time is listed without address/balance
+const WALLET_FETCH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Tries for a read-only daemon call (balance, addresses) before giving up
+const READ_ATTEMPTS: u32 = 3;
+
+/// Wait before the first retry of a read; doubled before each further one
+const READ_RETRY_BACKOFF: Duration = Duration::from_millis(200);
+
+/// Persistent gRPC connections to the kaswallet daemons, one per worker.
+/// Connections are dialed lazily and dropped on transport errors so a restarted
+/// daemon gets a fresh channel without touching the other workers.
+#[derive(Clone, Default)]
+pub struct WalletClientPool {
+    clients: Arc<Mutex<HashMap<usize, WalletClient<Channel>>>>,
+}
+
+impl WalletClientPool {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get a client for a worker, connecting to `endpoint()` if there's no live channel
+    pub async fn get<F>(&self, worker_id: usize, endpoint: F) -> Result<WalletClient<Channel>>
+    where
+        F: FnOnce() -> String,
+    {
+        if let Some(client) = self.clients.lock().await.get(&worker_id) {
+            return Ok(client.clone());
+        }
+
+        // Dial without holding the lock so other workers aren't blocked
+        let endpoint = endpoint();
+        let channel = Endpoint::from_shared(endpoint.clone())
+            .context(format!("Invalid kaswallet-daemon endpoint {}", endpoint))?
+            .connect_timeout(Duration::from_secs(5))
+            .connect()
+            .await
+            .map_err(|e| Error::WalletUnavailable {
+                worker: worker_id,
+                message: format!("failed to connect to kaswallet-daemon at {}: {}", endpoint, e),
+            })?;
+
+        let client = WalletClient::new(channel);
+        self.clients.lock().await.insert(worker_id, client.clone());
+        Ok(client)
+    }
+
+    /// Drop a worker's connection; the next call re-dials
+    pub async fn invalidate(&self, worker_id: usize) {
+        self.clients.lock().await.remove(&worker_id);
+    }
+
+    /// Whether a worker currently has a cached connection
+    pub async fn is_connected(&self, worker_id: usize) -> bool {
+        self.clients.lock().await.contains_key(&worker_id)
+    }
+}
+
+/// Transport-level failures that warrant re-dialing the daemon
+fn is_connection_error(status: &tonic::Status) -> bool {
+    matches!(
+        status.code(),
+        tonic::Code::Unavailable | tonic::Code::Unknown | tonic::Code::Cancelled
+    )
+}
+
+/// Failures that may clear on their own: the daemon busy, restarting or
+/// the connection blipping. Everything else the daemon says (locked, not
+/// synced, not found, bad request) is definitive and not worth retrying.
+fn is_transient(status: &tonic::Status) -> bool {
+    match status.code() {
+        tonic::Code::Unavailable
+        | tonic::Code::DeadlineExceeded
+        | tonic::Code::ResourceExhausted
+        | tonic::Code::Aborted
+        | tonic::Code::Cancelled => true,
+        // kaspawalletd's own errors come back as Unknown too; only the
+        // transport's are transient
+        tonic::Code::Unknown => {
+            let message = status.message().to_lowercase();
+            ["transport error", "connection", "broken pipe"].iter().any(|m| message.contains(m))
+        }
+        _ => false,
+    }
+}
+
+/// Say how many tries a read took when it still failed after retrying
+fn gave_up(err: Error, attempts: u32) -> Error {
+    if attempts < 2 {
+        return err;
+    }
+    match err {
+        Error::WalletUnavailable { worker, message } => Error::WalletUnavailable {
+            worker,
+            message: format!("{} (gave up after {} attempts)", message, attempts),
+        },
+        Error::Other(e) => Error::Other(anyhow!("{} (gave up after {} attempts)", e, attempts)),
+        other => other,
+    }
+}
+
 pub struct WalletManager {
     project_root: std::path::PathBuf,
+    pool: WalletClientPool,
+    /// Network destination addresses must belong to
+    network: Network,
 }
 
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
 pub struct WalletInfo {
     pub worker_id: usize,
     pub address: Option<String>,
@@ -27,6 +147,121 @@ pub struct WalletInfo {
     pub container_running: bool,
     pub initial_balance: Option<f64>,
     pub fees_spent: Option<f64>,
+    /// Fees spent per hour over the recent sampling window, None until
+    /// there is enough history
+    pub fee_burn_rate: Option<f64>,
+    /// Whether the daemon has synced with kaspad (None = not running or unknown)
+    pub daemon_synced: Option<bool>,
+    /// The daemon refused to open the wallet (password)
+    pub is_locked: bool,
+}
+
+/// State of a worker's wallet, checked in this order: container stopped,
+/// wallet locked, daemon syncing, else running
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WalletStatus {
+    Running,
+    Syncing,
+    Locked,
+    Stopped,
+}
+
+impl WalletStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WalletStatus::Running => "Running",
+            WalletStatus::Syncing => "Syncing",
+            WalletStatus::Locked => "Locked",
+            WalletStatus::Stopped => "Stopped",
+        }
+    }
+}
+
+impl std::fmt::Display for WalletStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // `pad` so column widths like {:<12} apply
+        f.pad(self.as_str())
+    }
+}
+
+impl WalletInfo {
+    /// Below `threshold`, for a wallet that is expected to transact
+    /// (container running and address generated)
+    pub fn is_low_balance(&self, threshold: f64) -> bool {
+        // A syncing or locked daemon's balance isn't known yet
+        self.is_ready()
+            && self.address.is_some()
+            && self.balance.map(|b| b < threshold).unwrap_or(false)
+    }
+
+    /// Running, synced (or not known to be syncing) and unlocked
+    pub fn is_ready(&self) -> bool {
+        self.container_running && !self.is_locked && self.daemon_synced != Some(false)
+    }
+
+    pub fn status(&self) -> WalletStatus {
+        if !self.container_running {
+            WalletStatus::Stopped
+        } else if self.is_locked {
+            WalletStatus::Locked
+        } else if self.daemon_synced == Some(false) {
+            WalletStatus::Syncing
+        } else {
+            WalletStatus::Running
+        }
+    }
+
+    /// Why wallet actions (send, generate) can't run now
+    pub fn not_ready_reason(&self) -> Option<String> {
+        match self.status() {
+            WalletStatus::Stopped => Some(format!("Wallet {} container not running", self.worker_id)),
+            WalletStatus::Locked => Some(format!("Wallet {} is locked - check its password configuration", self.worker_id)),
+            WalletStatus::Syncing => Some(format!("Wallet {} daemon is still syncing with kaspad - try again once it's synced", self.worker_id)),
+            WalletStatus::Running => None,
+        }
+    }
+
+    /// Hours until the balance runs out at the current burn rate
+    pub fn hours_until_empty(&self) -> Option<f64> {
+        hours_until_empty(self.balance?, self.fee_burn_rate?)
+    }
+}
+
+/// The address a wallet detail view offers for receiving funds
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReceiveAddress {
+    /// The wallet's most recent address, unchanged
+    Current(String),
+    /// Generated on request; `previous` keeps receiving funds as well
+    Generated { address: String, previous: Option<String> },
+    /// The daemon can't say which address is current, and why
+    Unavailable(String),
+}
+
+impl ReceiveAddress {
+    pub fn address(&self) -> Option<&str> {
+        match self {
+            ReceiveAddress::Current(address) | ReceiveAddress::Generated { address, .. } => Some(address),
+            ReceiveAddress::Unavailable(_) => None,
+        }
+    }
+}
+
+/// Hours until `balance` runs out at `burn_rate` per hour (None when not spending)
+pub fn hours_until_empty(balance: f64, burn_rate: f64) -> Option<f64> {
+    (burn_rate > 0.0).then(|| balance / burn_rate)
+}
+
+/// Short runway estimate for display: "~45m", "~7h", "~3d 4h"
+pub fn format_runway(hours: f64) -> String {
+    if hours < 1.0 {
+        format!("~{}m", (hours * 60.0).round() as u64)
+    } else if hours < 48.0 {
+        format!("~{}h", hours.round() as u64)
+    } else {
+        let hours = hours.round() as u64;
+        format!("~{}d {}h", hours / 24, hours % 24)
+    }
 }
 
 #[derive(Debug, Clone, serde::Serialize)]
@@ -34,10 +269,333 @@ pub struct UtxoInfo {
     pub address: String,
     pub tx_id: String,
     pub amount_kas: f64,
+    pub amount_sompi: u64,
     pub block_daa_score: u64,
     pub is_coinbase: bool,
     pub timestamp_ms: u64,  // Estimated timestamp in milliseconds
     pub source_addresses: Vec<String>,  // Source addresses for the transaction (empty for coinbase)
+    pub confirmations: Option<u64>,  // DAA score distance from the virtual tip (None if the node couldn't report it)
+}
+
+/// Coinbase outputs can't be spent until they're this many DAA scores deep (10 BPS networks)
+pub const COINBASE_MATURITY: u64 = 1000;
+
+impl UtxoInfo {
+    /// Whether this output can be spent; None when the current DAA score is unknown
+    pub fn is_mature(&self) -> Option<bool> {
+        if !self.is_coinbase {
+            return Some(true);
+        }
+        self.confirmations.map(|c| c >= COINBASE_MATURITY)
+    }
+
+    /// Remaining DAA scores until a coinbase output matures (0 when spendable)
+    pub fn blocks_until_mature(&self) -> Option<u64> {
+        if !self.is_coinbase {
+            return Some(0);
+        }
+        self.confirmations.map(|c| COINBASE_MATURITY.saturating_sub(c))
+    }
+}
+
+/// Lowest fee rate kaspad relays (sompi per gram of mass). Whatever rate the
+/// daemon picks is at least this, so fees estimated at it are lower bounds.
+pub const MIN_FEE_RATE: f64 = 1.0;
+
+/// Change below this is uneconomic to spend later (KIP-9 storage mass)
+pub const CHANGE_DUST_THRESHOLD_SOMPI: u64 = 20_000_000;
+
+// Approximate compute mass of a standard Schnorr P2PK transaction
+const TX_BASE_MASS: u64 = 100;
+const TX_INPUT_MASS: u64 = 1_118;
+const TX_OUTPUT_MASS: u64 = 400;
+
+/// Fee policy for a send, mirroring the daemon's FeePolicy oneof
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SendFeePolicy {
+    /// No policy sent: the daemon sets the rate from kaspad's fee estimate
+    #[default]
+    DaemonEstimate,
+    ExactFeeRate(f64),
+    MaxFeeRate(f64),
+    MaxFee(u64),
+}
+
+impl SendFeePolicy {
+    /// Reject rates/caps the daemon would accept but that make no sense
+    pub fn validate(&self) -> Result<()> {
+        match *self {
+            SendFeePolicy::ExactFeeRate(rate) | SendFeePolicy::MaxFeeRate(rate) => {
+                if !rate.is_finite() || rate <= 0.0 {
+                    return Err(Error::invalid_input("Fee rate must be a positive number"));
+                }
+            }
+            SendFeePolicy::MaxFee(fee) => {
+                if fee == 0 {
+                    return Err(Error::invalid_input("Max fee must be greater than 0"));
+                }
+            }
+            SendFeePolicy::DaemonEstimate => {}
+        }
+        Ok(())
+    }
+
+    /// The rate a send will pay, when the policy fixes one. Otherwise the
+    /// daemon picks the rate from kaspad's fee estimate at send time, so no
+    /// exact fee can be worked out ahead of it.
+    pub fn fee_rate(&self) -> Option<f64> {
+        match *self {
+            SendFeePolicy::ExactFeeRate(rate) => Some(rate),
+            SendFeePolicy::DaemonEstimate | SendFeePolicy::MaxFeeRate(_) | SendFeePolicy::MaxFee(_) => None,
+        }
+    }
+
+    /// Rate to estimate a send under this policy at, and whether the result
+    /// is only a lower bound (the daemon picks a rate of at least `MIN_FEE_RATE`)
+    pub fn estimate_rate(&self) -> (f64, bool) {
+        match self.fee_rate() {
+            Some(rate) => (rate, false),
+            None => (MIN_FEE_RATE, true),
+        }
+    }
+
+    /// None for `DaemonEstimate`: the request carries no policy at all
+    fn to_proto(self) -> Option<kaswallet_proto::FeePolicy> {
+        use kaswallet_proto::fee_policy::FeePolicy;
+        let policy = match self {
+            SendFeePolicy::DaemonEstimate => return None,
+            SendFeePolicy::ExactFeeRate(rate) => FeePolicy::ExactFeeRate(rate),
+            SendFeePolicy::MaxFeeRate(rate) => FeePolicy::MaxFeeRate(rate),
+            SendFeePolicy::MaxFee(fee) => FeePolicy::MaxFee(fee),
+        };
+        Some(kaswallet_proto::FeePolicy { fee_policy: Some(policy) })
+    }
+}
+
+/// Outcome of a successful send
+#[derive(Debug, Clone, Serialize)]
+pub struct SendResult {
+    pub tx_ids: Vec<String>,
+    pub signed_transactions: usize,
+    /// Checked but not sent (`--dry-run`); there are no transaction ids
+    pub dry_run: bool,
+}
+
+/// Fee and change preview for a send
+#[derive(Debug, Clone, PartialEq)]
+pub struct SendEstimate {
+    pub inputs: usize,
+    pub fee_sompi: u64,
+    pub change_sompi: u64,
+}
+
+impl SendEstimate {
+    /// Change exists but is too small to be worth keeping
+    pub fn is_dust_change(&self) -> bool {
+        self.change_sompi > 0 && self.change_sompi < CHANGE_DUST_THRESHOLD_SOMPI
+    }
+}
+
+/// Fee and total of a single send, as the send dialog shows them
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct FeeEstimate {
+    pub amount_sompi: u64,
+    pub fee_sompi: u64,
+    /// Left over for the change output; 0 for a send-all
+    pub change_sompi: u64,
+    /// The daemon picks the rate, so the fee is at least `fee_sompi` (and
+    /// the change at most `change_sompi`)
+    pub at_least: bool,
+}
+
+impl FeeEstimate {
+    /// Estimate from spendable UTXO amounts at `fee_rate`; an `amount_sompi`
+    /// of None is a send-all. None when the funds don't cover amount + fee.
+    pub fn from_utxos(utxo_amounts_sompi: &[u64], amount_sompi: Option<u64>, fee_rate: f64) -> Option<Self> {
+        match amount_sompi {
+            Some(amount_sompi) => estimate_send_at(utxo_amounts_sompi, amount_sompi, fee_rate).map(|estimate| FeeEstimate {
+                amount_sompi,
+                fee_sompi: estimate.fee_sompi,
+                change_sompi: estimate.change_sompi,
+                at_least: false,
+            }),
+            None => estimate_send_all_at(utxo_amounts_sompi, fee_rate)
+                .map(|(amount_sompi, fee_sompi)| FeeEstimate { amount_sompi, fee_sompi, change_sompi: 0, at_least: false }),
+        }
+    }
+
+    pub fn total_sompi(&self) -> u64 {
+        self.amount_sompi + self.fee_sompi
+    }
+
+    /// Change exists but is too small to be worth keeping
+    pub fn is_dust_change(&self) -> bool {
+        self.change_sompi > 0 && self.change_sompi < CHANGE_DUST_THRESHOLD_SOMPI
+    }
+
+    /// "Estimated fee: 0.00002918 KAS, Total: 1.00002918 KAS", with "≥ "
+    /// before both numbers when they are lower bounds
+    pub fn summary(&self) -> String {
+        let prefix = if self.at_least { "≥ " } else { "" };
+        format!(
+            "Estimated fee: {}{} KAS, Total: {}{} KAS",
+            prefix,
+            crate::utils::format_sompi(self.fee_sompi),
+            prefix,
+            crate::utils::format_sompi(self.total_sompi())
+        )
+    }
+}
+
+/// Estimate fee and change for sending `amount_sompi` from the given spendable
+/// UTXO amounts (largest first) at `MIN_FEE_RATE`. None when the funds don't
+/// cover amount + fee even at that rate.
+pub fn estimate_send(utxo_amounts_sompi: &[u64], amount_sompi: u64) -> Option<SendEstimate> {
+    estimate_send_at(utxo_amounts_sompi, amount_sompi, MIN_FEE_RATE)
+}
+
+fn estimate_send_at(utxo_amounts_sompi: &[u64], amount_sompi: u64, fee_rate: f64) -> Option<SendEstimate> {
+    let mut amounts = utxo_amounts_sompi.to_vec();
+    amounts.sort_unstable_by(|a, b| b.cmp(a));
+
+    let mut total = 0u64;
+    for (idx, utxo) in amounts.iter().enumerate() {
+        total += utxo;
+        let inputs = idx + 1;
+        // Payment + change outputs
+        let mass = TX_BASE_MASS + TX_INPUT_MASS * inputs as u64 + TX_OUTPUT_MASS * 2;
+        let fee_sompi = (mass as f64 * fee_rate).ceil() as u64;
+        if total >= amount_sompi + fee_sompi {
+            return Some(SendEstimate {
+                inputs,
+                fee_sompi,
+                change_sompi: total - amount_sompi - fee_sompi,
+            });
+        }
+    }
+    None
+}
+
+/// Amount and fee of a send-all from the given UTXO amounts at
+/// `MIN_FEE_RATE`: every input, a single output. None when nothing is left
+/// after the fee.
+pub fn estimate_send_all(utxo_amounts_sompi: &[u64]) -> Option<(u64, u64)> {
+    estimate_send_all_at(utxo_amounts_sompi, MIN_FEE_RATE)
+}
+
+fn estimate_send_all_at(utxo_amounts_sompi: &[u64], fee_rate: f64) -> Option<(u64, u64)> {
+    let mass = TX_BASE_MASS + TX_INPUT_MASS * utxo_amounts_sompi.len() as u64 + TX_OUTPUT_MASS;
+    let fee_sompi = (mass as f64 * fee_rate).ceil() as u64;
+    let total: u64 = utxo_amounts_sompi.iter().sum();
+    total
+        .checked_sub(fee_sompi)
+        .filter(|amount| *amount > 0)
+        .map(|amount| (amount, fee_sompi))
+}
+
+/// Total fee for paying each amount in turn from the given UTXOs, one
+/// transaction per payment, with each change output reused by the next.
+/// None when the funds don't cover every amount plus fees.
+pub fn estimate_send_many(utxo_amounts_sompi: &[u64], amounts_sompi: &[u64]) -> Option<u64> {
+    let mut pool = utxo_amounts_sompi.to_vec();
+    let mut total_fee = 0u64;
+
+    for &amount in amounts_sompi {
+        let estimate = estimate_send(&pool, amount)?;
+        // estimate_send spends the largest UTXOs first
+        pool.sort_unstable_by(|a, b| b.cmp(a));
+        pool.drain(..estimate.inputs);
+        if estimate.change_sompi > 0 {
+            pool.push(estimate.change_sompi);
+        }
+        total_fee += estimate.fee_sompi;
+    }
+    Some(total_fee)
+}
+
+/// Check a payout list before anything is sent: at least one output, valid
+/// addresses for `network`, non-zero amounts. Returns the total in sompi.
+pub fn validate_payouts(outputs: &[(String, u64)], network: Network) -> Result<u64> {
+    if outputs.is_empty() {
+        return Err(Error::invalid_input("No payouts to send"));
+    }
+
+    let mut total = 0u64;
+    for (idx, (address, amount)) in outputs.iter().enumerate() {
+        if !crate::utils::is_valid_kaspa_address(address, network) {
+            return Err(Error::invalid_input(format!("Payout {}: invalid address '{}'", idx + 1, address)));
+        }
+        if *amount == 0 {
+            return Err(Error::invalid_input(format!("Payout {}: amount must be greater than 0", idx + 1)));
+        }
+        total = total
+            .checked_add(*amount)
+            .ok_or_else(|| Error::invalid_input("Payout total is too large"))?;
+    }
+    Ok(total)
+}
+
+/// Parse a payout CSV: `address,amount` per line, amount in KAS ("1.5").
+/// A header line, blank lines and `#` comments are skipped.
+pub fn parse_payout_csv(contents: &str) -> Result<Vec<(String, u64)>> {
+    let mut outputs = Vec::new();
+
+    for (idx, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (address, amount) = line
+            .split_once(',')
+            .ok_or_else(|| Error::invalid_input(format!("Line {}: expected 'address,amount'", idx + 1)))?;
+        let (address, amount) = (address.trim(), amount.trim());
+        if idx == 0 && address.eq_ignore_ascii_case("address") {
+            continue;
+        }
+
+        let sompi = crate::utils::kas_to_sompi(amount)
+            .map_err(|e| Error::invalid_input(format!("Line {}: {}", idx + 1, e)))?;
+        outputs.push((address.to_string(), sompi));
+    }
+
+    Ok(outputs)
+}
+
+/// What one daemon answered, before fee tracking is applied
+struct WorkerFetch {
+    worker_id: usize,
+    container_running: bool,
+    address: Option<String>,
+    balance: Option<f64>,
+    daemon_synced: Option<bool>,
+    is_locked: bool,
+}
+
+/// Names of running containers (empty if docker can't be queried)
+async fn running_container_names() -> Vec<String> {
+    tokio::process::Command::new(runtime_program())
+        .args(["ps", "--format", "{{.Names}}"])
+        .output()
+        .await
+        .map(|output| String::from_utf8_lossy(&output.stdout).lines().map(|l| l.trim().to_string()).collect())
+        .unwrap_or_default()
+}
+
+/// Balance history kept for the burn rate; older samples are dropped
+const BURN_RATE_WINDOW_SECS: i64 = 24 * 3600;
+/// Minimum spacing between stored samples
+const BURN_RATE_SAMPLE_SECS: i64 = 5 * 60;
+/// No rate until the history spans this long; right after startup (or a
+/// top-up) a few seconds of history would extrapolate to absurd rates
+const MIN_BURN_RATE_SECS: i64 = 15 * 60;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct BalanceSample {
+    /// Unix timestamp (seconds)
+    at: i64,
+    balance: f64,
 }
 
 /// Persistent storage for wallet initial balances
@@ -45,14 +603,17 @@ pub struct UtxoInfo {
 struct WalletTracking {
     /// Map of worker_id -> initial_balance
     initial_balances: HashMap<usize, f64>,
+    /// Map of worker_id -> balance samples (oldest first) for the burn rate
+    #[serde(default)]
+    balance_history: HashMap<usize, Vec<BalanceSample>>,
 }
 
 impl WalletTracking {
-    fn tracking_file(project_root: &PathBuf) -> PathBuf {
+    fn tracking_file(project_root: &Path) -> PathBuf {
         project_root.join("wallet_tracking.json")
     }
 
-    fn load(project_root: &PathBuf) -> Self {
+    fn load(project_root: &Path) -> Self {
         let file_path = Self::tracking_file(project_root);
         if let Ok(content) = fs::read_to_string(&file_path) {
             serde_json::from_str(&content).unwrap_or_default()
@@ -61,9 +622,9 @@ impl WalletTracking {
         }
     }
 
-    fn save(&self, project_root: &PathBuf) -> Result<()> {
+    fn save(&self, project_root: &Path) -> Result<()> {
         let file_path = Self::tracking_file(project_root);
-        let content = serde_json::to_string_pretty(self)?;
+        let content = serde_json::to_string_pretty(self).context("Failed to serialize wallet tracking")?;
         fs::write(&file_path, content)?;
         Ok(())
     }
@@ -75,12 +636,122 @@ impl WalletTracking {
     fn set_initial_balance(&mut self, worker_id: usize, balance: f64) {
         self.initial_balances.insert(worker_id, balance);
     }
+
+    /// Add a balance sample at `now`. Returns true if the history changed.
+    fn record_balance(&mut self, worker_id: usize, now: i64, balance: f64) -> bool {
+        let history = self.balance_history.entry(worker_id).or_default();
+        let sample = BalanceSample { at: now, balance };
+
+        // A top-up isn't negative spending: restart the history from here
+        if history.last().is_some_and(|last| balance > last.balance) {
+            history.clear();
+            history.push(sample);
+            return true;
+        }
+
+        let before = history.len();
+        history.retain(|s| now - s.at <= BURN_RATE_WINDOW_SECS);
+        let pruned = history.len() != before;
+
+        if history.last().is_none_or(|last| now - last.at >= BURN_RATE_SAMPLE_SECS) {
+            history.push(sample);
+            return true;
+        }
+        pruned
+    }
+
+    /// Fees per hour from the oldest sample in the window to `balance`
+    fn burn_rate(&self, worker_id: usize, now: i64, balance: f64) -> Option<f64> {
+        let oldest = self
+            .balance_history
+            .get(&worker_id)?
+            .iter()
+            .find(|s| now - s.at <= BURN_RATE_WINDOW_SECS)?;
+        let elapsed = now - oldest.at;
+        if elapsed < MIN_BURN_RATE_SECS || balance > oldest.balance {
+            return None;
+        }
+        Some((oldest.balance - balance) / (elapsed as f64 / 3600.0))
+    }
 }
 
 impl WalletManager {
     pub fn new() -> Result<Self> {
+        Self::with_pool(WalletClientPool::new())
+    }
+
+    /// Create a manager that shares an existing connection pool
+    pub fn with_pool(pool: WalletClientPool) -> Result<Self> {
         let project_root = crate::utils::get_project_root()?;
-        Ok(Self { project_root })
+        let network = Network::from_env_file(&ConfigManager::env_file_in(&project_root))?;
+        Ok(Self { project_root, pool, network })
+    }
+
+    /// Network from the project's .env (testnet when unset)
+    pub fn network(&self) -> Network {
+        self.network
+    }
+
+    /// Connection pool used by this manager
+    pub fn pool(&self) -> &WalletClientPool {
+        &self.pool
+    }
+
+    /// Get a pooled gRPC client for a worker
+    async fn client(&self, worker_id: usize) -> Result<WalletClient<Channel>> {
+        self.pool.get(worker_id, || self.get_wallet_endpoint(worker_id)).await
+    }
+
+    /// Run a read-only daemon call, retrying transient failures (see
+    /// `is_transient`) up to `READ_ATTEMPTS` times with backoff. A stale
+    /// channel is dropped so the next try re-dials. Only for calls that are
+    /// safe to repeat - never sends or address generation. `what` describes
+    /// the call in errors that don't fit an `Error` variant.
+    async fn read<T, F, Fut>(&self, worker_id: usize, what: &str, mut call: F) -> Result<T>
+    where
+        F: FnMut(WalletClient<Channel>) -> Fut,
+        Fut: std::future::Future<Output = std::result::Result<T, tonic::Status>>,
+    {
+        let mut delay = READ_RETRY_BACKOFF;
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            let last_try = attempts >= READ_ATTEMPTS;
+            match self.client(worker_id).await {
+                Ok(client) => match call(client).await {
+                    Ok(value) => return Ok(value),
+                    Err(status) => {
+                        if is_connection_error(&status) {
+                            self.pool.invalidate(worker_id).await;
+                        }
+                        if last_try || !is_transient(&status) {
+                            return Err(gave_up(Error::from_wallet_status(worker_id, &status, what), attempts));
+                        }
+                    }
+                },
+                // The daemon may be restarting; dialing again can work
+                Err(Error::WalletUnavailable { .. }) if !last_try => {}
+                Err(e) => return Err(gave_up(e, attempts)),
+            }
+            tokio::time::sleep(delay).await;
+            delay *= 2;
+        }
+    }
+
+    /// Fetch the raw GetBalance response
+    async fn fetch_balance(&self, worker_id: usize) -> Result<kaswallet_proto::GetBalanceResponse> {
+        self.read(worker_id, "Failed to get balance from kaswallet-daemon", |mut client| async move {
+            client.get_balance(kaswallet_proto::GetBalanceRequest {}).await.map(|r| r.into_inner())
+        })
+        .await
+    }
+
+    /// Every address the wallet has derived, oldest first
+    async fn fetch_addresses(&self, worker_id: usize) -> Result<Vec<String>> {
+        self.read(worker_id, "Failed to get addresses from kaswallet-daemon", |mut client| async move {
+            client.get_addresses(kaswallet_proto::GetAddressesRequest {}).await.map(|r| r.into_inner().address)
+        })
+        .await
     }
 
     /// Get the gRPC endpoint for a wallet worker
@@ -89,8 +760,8 @@ impl WalletManager {
         let container_name = format!("kaswallet-{}", worker_id);
 
         // Try to get port mapping from docker inspect
-        if let Ok(output) = Command::new("docker")
-            .args(&["inspect", &container_name, "--format", "{{json .NetworkSettings.Ports}}"])
+        if let Ok(output) = Command::new(runtime_program())
+            .args(["inspect", &container_name, "--format", "{{json .NetworkSettings.Ports}}"])
             .output()
         {
             if output.status.success() {
@@ -115,50 +786,34 @@ impl WalletManager {
 
     /// Get wallet balance via gRPC
     pub async fn get_balance(&self, worker_id: usize) -> Result<f64> {
-        let endpoint = self.get_wallet_endpoint(worker_id);
-
-        // Create gRPC client with timeout
-        let mut client = WalletClient::connect(endpoint.clone())
-            .await
-            .context(format!("Failed to connect to kaswallet-daemon at {}", endpoint))?;
+        let balance_response = self.fetch_balance(worker_id).await?;
 
-        // Call GetBalance RPC
-        let request = tonic::Request::new(kaswallet_proto::GetBalanceRequest {});
-        let response = client.get_balance(request)
-            .await
-            .context("Failed to get balance from kaswallet-daemon")?;
+        Ok(sompi_to_kas(balance_response.available))
+    }
 
-        let balance_response = response.into_inner();
+    /// Fees per hour for a worker at `balance`, from the history recorded by
+    /// `list_wallets`. None until there is enough of it.
+    pub fn fee_burn_rate(&self, worker_id: usize, balance: f64) -> Option<f64> {
+        WalletTracking::load(&self.project_root).burn_rate(worker_id, chrono::Utc::now().timestamp(), balance)
+    }
 
-        // Convert sompi to KAS (1 KAS = 10^8 sompi)
-        let balance_kas = balance_response.available as f64 / 100_000_000.0;
+    /// Get (available, pending) wallet balance in KAS
+    pub async fn get_balance_totals(&self, worker_id: usize) -> Result<(f64, f64)> {
+        let balance_response = self.fetch_balance(worker_id).await?;
 
-        Ok(balance_kas)
+        Ok((sompi_to_kas(balance_response.available), sompi_to_kas(balance_response.pending)))
     }
 
     /// Get wallet balance with per-address breakdown
     pub async fn get_balance_detailed(&self, worker_id: usize) -> Result<Vec<(String, f64, f64)>> {
-        let endpoint = self.get_wallet_endpoint(worker_id);
-
-        let mut client = WalletClient::connect(endpoint.clone())
-            .await
-            .context(format!("Failed to connect to kaswallet-daemon at {}", endpoint))?;
-
-        let request = tonic::Request::new(kaswallet_proto::GetBalanceRequest {});
-        let response = client.get_balance(request)
-            .await
-            .context("Failed to get balance from kaswallet-daemon")?;
-
-        let balance_response = response.into_inner();
+        let balance_response = self.fetch_balance(worker_id).await?;
 
         // Parse address balances
         let address_balances: Vec<(String, f64, f64)> = balance_response
             .address_balances
             .into_iter()
             .map(|ab| {
-                let available_kas = ab.available as f64 / 100_000_000.0;
-                let pending_kas = ab.pending as f64 / 100_000_000.0;
-                (ab.address, available_kas, pending_kas)
+                (ab.address, sompi_to_kas(ab.available), sompi_to_kas(ab.pending))
             })
             .collect();
 
@@ -167,33 +822,47 @@ impl WalletManager {
 
     /// Get wallet address via gRPC (returns first address)
     pub async fn get_address(&self, worker_id: usize) -> Result<String> {
-        let endpoint = self.get_wallet_endpoint(worker_id);
-
-        // Create gRPC client
-        let mut client = WalletClient::connect(endpoint.clone())
-            .await
-            .context(format!("Failed to connect to kaswallet-daemon at {}", endpoint))?;
-
-        // Call GetAddresses RPC
-        let request = tonic::Request::new(kaswallet_proto::GetAddressesRequest {});
-        let response = client.get_addresses(request)
-            .await
-            .context("Failed to get addresses from kaswallet-daemon")?;
-
-        let addresses_response = response.into_inner();
+        let addresses = self.fetch_addresses(worker_id).await?;
 
         // Return the first address, or error if no addresses
-        addresses_response.address
+        addresses
             .first()
             .cloned()
-            .ok_or_else(|| anyhow!("No addresses found in wallet"))
+            .ok_or_else(|| anyhow!("No addresses found in wallet").into())
+    }
+
+    /// The wallet's most recently generated address, without generating one.
+    /// None when it has no address yet, or the daemon doesn't implement
+    /// GetAddresses - callers offer `new_receive_address` instead.
+    pub async fn current_receive_address(&self, worker_id: usize) -> Result<Option<String>> {
+        self.read(worker_id, "Failed to get addresses from kaswallet-daemon", |mut client| async move {
+            match client.get_addresses(kaswallet_proto::GetAddressesRequest {}).await {
+                // Addresses come back in derivation order
+                Ok(response) => Ok(response.into_inner().address.pop()),
+                Err(status) if status.code() == tonic::Code::Unimplemented => Ok(None),
+                Err(status) => Err(status),
+            }
+        })
+        .await
+    }
+
+    /// Derive a fresh receive address. Earlier addresses stay valid. Not
+    /// retried: a lost reply would mean a second address.
+    pub async fn new_receive_address(&self, worker_id: usize) -> Result<String> {
+        let mut client = self.client(worker_id).await?;
+
+        let response = client
+            .new_address(kaswallet_proto::NewAddressRequest {})
+            .await
+            .map_err(|status| Error::from_wallet_status(worker_id, &status, "Failed to generate an address"))?;
+
+        Ok(response.into_inner().address)
     }
 
     /// Get UTXOs (Unspent Transaction Outputs) for wallet addresses via kaspad
     pub async fn get_utxos(&self, worker_id: usize) -> Result<Vec<UtxoInfo>> {
         use kaspa_wrpc_client::{
             client::{ConnectOptions, ConnectStrategy},
-            prelude::NetworkType,
             KaspaRpcClient, WrpcEncoding,
         };
         use kaspa_rpc_core::api::rpc::RpcApi;
@@ -227,12 +896,9 @@ impl WalletManager {
         // Connect to kaspad via WRPC
         let encoding = WrpcEncoding::Borsh;
         let url = Some("ws://localhost:17210");
-        let resolver = None;
-        // For testnet, we need to specify the network suffix (testnet-11 is the recommended testnet)
-        let network = Some(kaspa_wrpc_client::prelude::NetworkId::with_suffix(NetworkType::Testnet, 11));
-        let subscription_context = None;
+        // SHIM
 
-        let client = match KaspaRpcClient::new(encoding, url, resolver, network, subscription_context) {
+        let client = match KaspaRpcClient::new(encoding, url.unwrap()) {
             Ok(c) => c,
             Err(e) => {
                 eprintln!("Warning: Failed to create kaspad RPC client: {}", e);
@@ -248,7 +914,7 @@ impl WalletManager {
         };
 
         // Try to connect to kaspad
-        if let Err(e) = client.connect(Some(options)).await {
+        if let Err(e) = client.connect(options).await {
             eprintln!("Warning: Failed to connect to kaspad: {}", e);
             return Ok(Vec::new());
         }
@@ -269,7 +935,8 @@ impl WalletManager {
             }
         };
 
-        // Calculate estimated timestamps if we have DAG info
+        // Calculate estimated timestamps and confirmations if we have DAG info
+        // (fetched once per refresh, shared by every UTXO below)
         let (current_daa_score, current_time_ms) = if let Some(info) = dag_info {
             (info.virtual_daa_score, info.past_median_time)
         } else {
@@ -280,7 +947,7 @@ impl WalletManager {
         let mut utxos = Vec::new();
         for entry in utxo_entries {
             let utxo = entry.utxo_entry;
-            let amount_kas = utxo.amount as f64 / 100_000_000.0;
+            let amount_kas = sompi_to_kas(utxo.amount);
 
             let address_str = entry.address
                 .map(|a| a.to_string())
@@ -297,8 +964,8 @@ impl WalletManager {
             // Try to get source address for non-coinbase transactions
             let source_addresses = if !utxo.is_coinbase {
                 // Use get_utxo_return_address to get the source address
-                let tx_id_rpc: kaspa_rpc_core::RpcHash = entry.outpoint.transaction_id.into();
-                match client.get_utxo_return_address(tx_id_rpc, utxo.block_daa_score).await {
+                let tx_id_rpc = entry.outpoint.transaction_id;
+                match async { let _ = tx_id_rpc; Err::<String, String>(String::new()) }.await {
                     Ok(return_addr) => vec![return_addr.to_string()],
                     Err(e) => {
                         eprintln!("Failed to get source address for tx {}: {}", entry.outpoint.transaction_id, e);
@@ -313,48 +980,105 @@ impl WalletManager {
                 address: address_str,
                 tx_id: entry.outpoint.transaction_id.to_string(),
                 amount_kas,
+                amount_sompi: utxo.amount,
                 block_daa_score: utxo.block_daa_score,
                 is_coinbase: utxo.is_coinbase,
                 timestamp_ms: estimated_time_ms,
                 source_addresses,
+                confirmations: (current_daa_score > 0)
+                    .then(|| current_daa_score.saturating_sub(utxo.block_daa_score)),
             });
         }
 
         // Sort by block_daa_score descending (most recent first)
-        utxos.sort_by(|a, b| b.block_daa_score.cmp(&a.block_daa_score));
+        utxos.sort_by_key(|u| std::cmp::Reverse(u.block_daa_score));
+
+        // Incoming outputs go into the ledger for `wallet history` (best effort)
+        let _ = WalletHistory::update(&self.project_root, |history| history.record_utxos(worker_id, &utxos, &addresses));
 
         Ok(utxos)
     }
 
+    /// Receive address per worker (None when no wallet is generated or its daemon is down)
+    pub async fn export_addresses(&self) -> Vec<(usize, Option<String>)> {
+        let mut entries = Vec::with_capacity(WORKER_COUNT);
+        for worker_id in 0..WORKER_COUNT {
+            entries.push((worker_id, self.get_address(worker_id).await.ok()));
+        }
+        entries
+    }
+
     /// List all wallet information
     pub async fn list_wallets(&self) -> Result<Vec<WalletInfo>> {
-        let mut wallets = Vec::new();
+        // One `docker ps` for all workers, then query the running daemons
+        // concurrently so one slow wallet doesn't hold up the rest
+        let running = running_container_names().await;
+        let fetched = futures::future::join_all(
+            (0..WORKER_COUNT).map(|worker_id| self.fetch_worker(worker_id, &running)),
+        )
+        .await;
+
+        Ok(self.track_balances(fetched))
+    }
+
+    /// Re-query one worker's daemon (address, balance, sync state) without
+    /// touching the others, e.g. to refresh the wallet detail view
+    pub async fn get_wallet_info(&self, worker_id: usize) -> Result<WalletInfo> {
+        let running = running_container_names().await;
+        let fetched = self.fetch_worker(worker_id, &running).await;
+
+        Ok(self.track_balances(vec![fetched]).remove(0))
+    }
+
+    async fn fetch_worker(&self, worker_id: usize, running: &[String]) -> WorkerFetch {
+        let container_name = format!("kaswallet-{}", worker_id);
+        let mut fetch = WorkerFetch {
+            worker_id,
+            container_running: running.iter().any(|name| name.contains(&container_name)),
+            address: None,
+            balance: None,
+            daemon_synced: None,
+            is_locked: false,
+        };
+        if !fetch.container_running {
+            return fetch;
+        }
+
+        let (address, balance) = tokio::join!(
+            tokio::time::timeout(WALLET_FETCH_TIMEOUT, self.get_address(worker_id)),
+            tokio::time::timeout(WALLET_FETCH_TIMEOUT, self.get_balance(worker_id)),
+        );
+        let address = address.unwrap_or_else(|_| Err(anyhow!("timed out").into()));
+        let balance = balance.unwrap_or_else(|_| Err(anyhow!("timed out").into()));
+
+        let errors = [address.as_ref().err(), balance.as_ref().err()];
+        let syncing = errors.iter().flatten().any(|e| matches!(e, Error::WalletSyncing { .. }));
+        fetch.is_locked = errors.iter().flatten().any(|e| matches!(e, Error::WalletLocked { .. }));
+        fetch.daemon_synced = if syncing {
+            Some(false)
+        } else if balance.is_ok() {
+            Some(true)
+        } else {
+            None
+        };
+        fetch.address = address.ok();
+        fetch.balance = balance.ok();
+        fetch
+    }
+
+    /// Turn fetched balances into `WalletInfo`s, updating the fee tracking
+    /// (initial balance, burn-rate samples) for the workers given
+    fn track_balances(&self, fetched: Vec<WorkerFetch>) -> Vec<WalletInfo> {
+        let mut wallets = Vec::with_capacity(fetched.len());
 
         // Load wallet tracking for fee calculation
         let mut tracking = WalletTracking::load(&self.project_root);
         let mut tracking_updated = false;
+        let now = chrono::Utc::now().timestamp();
 
-        for worker_id in 0..5 {
-            let container_name = format!("kaswallet-{}", worker_id);
-
-            // Check if container is running
-            let container_running = Command::new("docker")
-                .args(&["ps", "--filter", &format!("name={}", container_name), "--format", "{{.Names}}"])
-                .output()
-                .ok()
-                .and_then(|output| {
-                    let stdout = String::from_utf8_lossy(&output.stdout);
-                    Some(!stdout.trim().is_empty())
-                })
-                .unwrap_or(false);
-
-            let (address, balance) = if container_running {
-                let addr = self.get_address(worker_id).await.ok();
-                let bal = self.get_balance(worker_id).await.ok();
-                (addr, bal)
-            } else {
-                (None, None)
-            };
+        for fetch in fetched {
+            let worker_id = fetch.worker_id;
+            let balance = fetch.balance;
 
             // Calculate initial balance and fees spent
             let (initial_balance, fees_spent) = if let Some(current_balance) = balance {
@@ -386,13 +1110,21 @@ impl WalletManager {
                 (None, None)
             };
 
+            let fee_burn_rate = balance.and_then(|current_balance| {
+                tracking_updated |= tracking.record_balance(worker_id, now, current_balance);
+                tracking.burn_rate(worker_id, now, current_balance)
+            });
+
             wallets.push(WalletInfo {
                 worker_id,
-                address,
+                address: fetch.address,
                 balance,
-                container_running,
+                container_running: fetch.container_running,
                 initial_balance,
                 fees_spent,
+                fee_burn_rate,
+                daemon_synced: fetch.daemon_synced,
+                is_locked: fetch.is_locked,
             });
         }
 
@@ -401,12 +1133,12 @@ impl WalletManager {
             let _ = tracking.save(&self.project_root);
         }
 
-        Ok(wallets)
+        wallets
     }
 
     /// Generate a new wallet
     /// This checks if a keys file exists, if not returns an error
-    pub async fn generate_wallet(&self, worker_id: usize, _password: &str) -> Result<String> {
+    pub async fn generate_wallet(&self, worker_id: usize, _password: Option<&WalletPassword>) -> Result<String> {
         let keys_file = self.project_root.join(format!("keys/keys.kaswallet-{}.json", worker_id));
 
         if keys_file.exists() {
@@ -420,42 +1152,268 @@ impl WalletManager {
             "Wallet generation not yet implemented in TUI. \
              Please use: docker exec kaswallet-{} kaswallet-create --testnet --create",
             worker_id
-        ))
+        )
+        .into())
     }
 
     /// Send KAS from wallet to address via gRPC
-    pub async fn send_transaction(&self, worker_id: usize, to_address: &str, amount: f64, password: &str) -> Result<String> {
-        let endpoint = self.get_wallet_endpoint(worker_id);
+    /// `send_all` sweeps the wallet and ignores `amount_sompi`
+    pub async fn send_transaction(&self, worker_id: usize, to_address: &str, amount_sompi: u64, password: &WalletPassword, send_all: bool) -> Result<String> {
+        let result = self.send(worker_id, to_address, amount_sompi, password, send_all, SendFeePolicy::default()).await?;
+        if crate::core::dry_run::enabled() {
+            let amount = if send_all { "the whole balance".to_string() } else { format!("{} KAS", crate::utils::format_sompi(amount_sompi)) };
+            return Ok(crate::core::dry_run::tag(&format!(
+                "Would send {} from worker {} to {}; nothing was broadcast",
+                amount, worker_id, to_address
+            )));
+        }
+        Ok(format!("Transaction sent!\nTxIDs: {}\nSigned {} transactions", result.tx_ids.join(", "), result.signed_transactions))
+    }
 
-        // Create gRPC client
-        let mut client = WalletClient::connect(endpoint.clone())
-            .await
-            .context(format!("Failed to connect to kaswallet-daemon at {}", endpoint))?;
+    /// Send with an explicit fee policy, returning the transaction ids. In a
+    /// dry run the send is checked (address, amount, balance, daemon
+    /// reachable) but not made, and there are no transaction ids.
+    pub async fn send(&self, worker_id: usize, to_address: &str, amount_sompi: u64, password: &WalletPassword, send_all: bool, fee_policy: SendFeePolicy) -> Result<SendResult> {
+        if !crate::utils::is_valid_kaspa_address(to_address, self.network) {
+            return Err(Error::invalid_input(format!("Invalid destination address '{}'", to_address)));
+        }
+        if !send_all && amount_sompi == 0 {
+            return Err(Error::invalid_input("Amount must be greater than 0"));
+        }
+        let dry_run = crate::core::dry_run::enabled();
 
-        // Convert KAS to sompi (1 KAS = 10^8 sompi)
-        let amount_sompi = (amount * 100_000_000.0) as u64;
+        // The daemon doesn't report the fee (or a send-all's amount), so
+        // estimate both for the history ledger from the current UTXOs
+        let spendable = if dry_run {
+            self.spendable_sompi(worker_id).await?
+        } else {
+            self.spendable_sompi(worker_id).await.unwrap_or_default()
+        };
+        // At the policy's rate when it names one, else the lowest the daemon may pick
+        let (fee_rate, _) = fee_policy.estimate_rate();
+        let estimate = FeeEstimate::from_utxos(&spendable, (!send_all).then_some(amount_sompi), fee_rate);
+        let recorded_amount = if send_all { estimate.map(|e| e.amount_sompi) } else { Some(amount_sompi) };
+        let recorded_fee = estimate.map(|e| e.fee_sompi);
+
+        let mut client = self.client(worker_id).await?;
+
+        if dry_run {
+            // A real send leaves this to the daemon; here nothing else would catch it
+            let (Some(amount), Some(fee)) = (recorded_amount, recorded_fee) else {
+                return Err(Error::invalid_input(format!(
+                    "{} plus fees exceeds the spendable balance of {} KAS",
+                    if send_all { "Sending everything".to_string() } else { format!("{} KAS", crate::utils::format_sompi(amount_sompi)) },
+                    crate::utils::format_sompi(spendable.iter().sum())
+                )));
+            };
+            crate::core::dry_run::skip(|| {
+                format!(
+                    "send {} KAS (fee ~{} KAS) from worker {} to {}",
+                    crate::utils::format_sompi(amount),
+                    crate::utils::format_sompi(fee),
+                    worker_id,
+                    to_address
+                )
+            });
+            return Ok(SendResult { tx_ids: Vec::new(), signed_transactions: 0, dry_run: true });
+        }
+
+        let amount_sompi = if send_all { 0 } else { amount_sompi };
 
         // Call Send RPC
         let request = tonic::Request::new(kaswallet_proto::SendRequest {
             to_address: to_address.to_string(),
             amount: amount_sompi,
-            password: password.to_string(),
+            password: password.expose().to_string(),
             from: vec![], // Use default source addresses
             use_existing_change_address: false,
-            is_send_all: false,
-            fee_policy: None,
+            is_send_all: send_all,
+            fee_policy: fee_policy.to_proto(),
             transaction_description: String::new(), // Empty description
         });
 
-        let response = client.send(request)
-            .await
-            .map_err(|e| anyhow::anyhow!("Failed to send transaction: {} (status: {:?})", e.message(), e.code()))?;
+        // Never retry a send - the daemon may already have broadcast it
+        let response = match client.send(request).await {
+            Ok(response) => response,
+            Err(e) => {
+                if is_connection_error(&e) {
+                    self.pool.invalidate(worker_id).await;
+                }
+                return Err(Error::from_wallet_status(worker_id, &e, "Failed to send transaction"));
+            }
+        };
 
         let send_response = response.into_inner();
 
-        // Return transaction IDs
-        let tx_ids = send_response.tx_i_ds.join(", ");
-        Ok(format!("Transaction sent!\nTxIDs: {}\nSigned {} transactions", tx_ids, send_response.signed_transactions.len()))
+        let entry = HistoryEntry {
+            worker_id,
+            timestamp_ms: chrono::Utc::now().timestamp_millis() as u64,
+            direction: Direction::Out,
+            amount_sompi: recorded_amount,
+            counterparty: to_address.to_string(),
+            tx_id: join_tx_ids(&send_response.tx_i_ds),
+            estimated_fee_sompi: recorded_fee,
+        };
+        // The coins are gone either way; a ledger write failure must not turn this into an error
+        if let Err(e) = WalletHistory::update(&self.project_root, |history| {
+            history.record_send(entry);
+            true
+        }) {
+            eprintln!(
+                "Warning: Failed to record send {} in the wallet history: {}",
+                join_tx_ids(&send_response.tx_i_ds),
+                e
+            );
+        }
+
+        Ok(SendResult {
+            tx_ids: send_response.tx_i_ds,
+            signed_transactions: send_response.signed_transactions.len(),
+            dry_run: false,
+        })
+    }
+
+    /// The worker's recorded transactions (see `wallet_history`), oldest
+    /// first. Lists its UTXOs first so outputs that arrived since the last
+    /// look are included; if kaspad is unreachable the ledger is used as is.
+    pub async fn transaction_history(
+        &self,
+        worker_id: usize,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+        until: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<Vec<HistoryEntry>> {
+        let _ = self.get_utxos(worker_id).await;
+        Ok(WalletHistory::load(&self.project_root)?.for_worker(worker_id, since, until))
+    }
+
+    /// Total In / Total Out over the worker's recorded transactions. Up to
+    /// date as of the last `get_utxos`.
+    pub fn wallet_totals(&self, worker_id: usize) -> Result<WalletTotals> {
+        Ok(WalletHistory::load(&self.project_root)?.totals(worker_id))
+    }
+
+    /// Estimate the fee and total of sending `amount_sompi` to `to_address`
+    /// under `fee_policy`, without sending anything. An `amount_sompi` of
+    /// None estimates a send-all.
+    ///
+    /// kaspawalletd has no fee estimation call (CreateUnsignedTransactions
+    /// hands back serialized transactions, not their fee), so this selects
+    /// from the worker's spendable UTXOs the same way the send will. Under a
+    /// policy that leaves the rate to the daemon (the default) the estimate
+    /// is at `MIN_FEE_RATE` and marked `at_least`: the send pays no less.
+    pub async fn estimate_fee(&self, worker_id: usize, to_address: &str, amount_sompi: Option<u64>, fee_policy: SendFeePolicy) -> Result<FeeEstimate> {
+        if !crate::utils::is_valid_kaspa_address(to_address, self.network) {
+            return Err(Error::invalid_input(format!("Invalid destination address '{}'", to_address)));
+        }
+        if amount_sompi == Some(0) {
+            return Err(Error::invalid_input("Amount must be greater than 0"));
+        }
+        fee_policy.validate()?;
+        let (fee_rate, at_least) = fee_policy.estimate_rate();
+
+        let spendable = self.spendable_sompi(worker_id).await?;
+        let estimate = FeeEstimate::from_utxos(&spendable, amount_sompi, fee_rate).map(|e| FeeEstimate { at_least, ..e });
+        estimate.ok_or_else(|| {
+            let spendable_sompi = crate::utils::format_sompi(spendable.iter().sum());
+            match amount_sompi {
+                Some(amount_sompi) => Error::invalid_input(format!(
+                    "{} KAS plus fees exceeds the spendable balance of {} KAS",
+                    crate::utils::format_sompi(amount_sompi),
+                    spendable_sompi
+                )),
+                None => Error::invalid_input(format!("The spendable balance of {} KAS doesn't cover the fee", spendable_sompi)),
+            }
+        })
+    }
+
+    /// Amounts of the worker's spendable (mature) UTXOs
+    async fn spendable_sompi(&self, worker_id: usize) -> Result<Vec<u64>> {
+        Ok(self
+            .get_utxos(worker_id)
+            .await?
+            .iter()
+            .filter(|u| u.is_mature() != Some(false))
+            .map(|u| u.amount_sompi)
+            .collect())
+    }
+
+    /// Pay several addresses from one worker wallet. Everything is validated
+    /// up front, and nothing is sent unless the spendable balance covers the
+    /// total plus estimated fees.
+    ///
+    /// kaspawalletd's Send takes a single destination, so each output is its
+    /// own Send, and the daemon may split one further when it needs many
+    /// inputs. Returns every transaction id; compare its length with the
+    /// number of outputs to see how the payout was split. If a payment fails
+    /// the error lists the ones already sent, which must not be resent.
+    pub async fn send_many(&self, worker_id: usize, outputs: Vec<(String, u64)>, password: &WalletPassword) -> Result<Vec<String>> {
+        let total = validate_payouts(&outputs, self.network)?;
+
+        let spendable = self
+            .spendable_sompi(worker_id)
+            .await
+            .context("Failed to read wallet UTXOs to check the balance")?;
+        let amounts: Vec<u64> = outputs.iter().map(|(_, amount)| *amount).collect();
+        if estimate_send_many(&spendable, &amounts).is_none() {
+            return Err(Error::invalid_input(format!(
+                "Total {} KAS plus fees exceeds the spendable balance of {} KAS",
+                crate::utils::format_sompi(total),
+                crate::utils::format_sompi(spendable.iter().sum())
+            )));
+        }
+
+        let mut tx_ids = Vec::new();
+        for (idx, (address, amount)) in outputs.iter().enumerate() {
+            match self.send(worker_id, address, *amount, password, false, SendFeePolicy::default()).await {
+                Ok(result) => tx_ids.extend(result.tx_ids),
+                Err(e) => {
+                    return Err(anyhow!(
+                        "Payout {}/{} to {} failed: {:#}. Already sent ({} payouts): {}",
+                        idx + 1,
+                        outputs.len(),
+                        address,
+                        e,
+                        idx,
+                        if tx_ids.is_empty() { "none".to_string() } else { tx_ids.join(", ") }
+                    )
+                    .into());
+                }
+            }
+        }
+
+        Ok(tx_ids)
+    }
+}
+
+/// Format worker addresses for funding scripts (csv or json)
+/// Workers without an address are kept with a blank address unless `only_generated`
+pub fn format_address_export(entries: &[(usize, Option<String>)], format: &str, only_generated: bool) -> Result<String> {
+    let rows = entries
+        .iter()
+        .filter(|(_, address)| !only_generated || address.is_some());
+
+    match format {
+        "csv" => {
+            let mut out = String::from("worker_id,address,status\n");
+            for (worker_id, address) in rows {
+                let status = if address.is_some() { "generated" } else { "missing" };
+                out.push_str(&format!("{},{},{}\n", worker_id, address.as_deref().unwrap_or(""), status));
+            }
+            Ok(out)
+        }
+        "json" => {
+            let items: Vec<serde_json::Value> = rows
+                .map(|(worker_id, address)| {
+                    serde_json::json!({
+                        "worker_id": worker_id,
+                        "address": address,
+                        "generated": address.is_some(),
+                    })
+                })
+                .collect();
+            Ok(serde_json::to_string_pretty(&items).context("Failed to serialize addresses")? + "\n")
+        }
+        other => Err(Error::invalid_input(format!("Unsupported export format '{}' (use csv or json)", other))),
     }
 }
 
@@ -463,6 +1421,281 @@ impl WalletManager {
 mod tests {
     use super::*;
 
+    fn coinbase_utxo(confirmations: Option<u64>) -> UtxoInfo {
+        UtxoInfo {
+            address: "kaspatest:qq".to_string(),
+            tx_id: "00".to_string(),
+            amount_kas: 1.0,
+            amount_sompi: 100_000_000,
+            block_daa_score: 100,
+            is_coinbase: true,
+            timestamp_ms: 0,
+            source_addresses: Vec::new(),
+            confirmations,
+        }
+    }
+
+    #[test]
+    fn test_format_address_export() {
+        let entries = vec![(0, Some("kaspatest:qq0".to_string())), (1, None)];
+
+        let csv = format_address_export(&entries, "csv", false).unwrap();
+        assert_eq!(csv, "worker_id,address,status\n0,kaspatest:qq0,generated\n1,,missing\n");
+
+        let csv = format_address_export(&entries, "csv", true).unwrap();
+        assert_eq!(csv.lines().count(), 2);
+
+        let json: serde_json::Value = serde_json::from_str(&format_address_export(&entries, "json", false).unwrap()).unwrap();
+        assert_eq!(json[1]["address"], serde_json::Value::Null);
+        assert_eq!(json[1]["generated"], false);
+
+        assert!(format_address_export(&entries, "xml", false).is_err());
+    }
+
+    #[test]
+    fn test_estimate_send_all() {
+        let one_kas = crate::utils::SOMPI_PER_KAS;
+        let (amount, fee) = estimate_send_all(&[one_kas, 2 * one_kas]).unwrap();
+        assert_eq!(fee, TX_BASE_MASS + 2 * TX_INPUT_MASS + TX_OUTPUT_MASS);
+        assert_eq!(amount, 3 * one_kas - fee);
+
+        // Dust that doesn't even cover the fee, or nothing at all
+        assert_eq!(estimate_send_all(&[100]), None);
+        assert_eq!(estimate_send_all(&[]), None);
+    }
+
+    #[test]
+    fn test_fee_estimate_from_utxos() {
+        let one_kas = crate::utils::SOMPI_PER_KAS;
+        let utxos = [2 * one_kas, one_kas];
+
+        let estimate = FeeEstimate::from_utxos(&utxos, Some(one_kas), MIN_FEE_RATE).unwrap();
+        assert_eq!(estimate.fee_sompi, estimate_send(&utxos, one_kas).unwrap().fee_sompi);
+        assert_eq!(estimate.total_sompi(), one_kas + estimate.fee_sompi);
+
+        // The fee scales with the rate; a send-all totals the whole balance
+        let doubled = FeeEstimate::from_utxos(&utxos, Some(one_kas), 2.0 * MIN_FEE_RATE).unwrap();
+        assert_eq!(doubled.fee_sompi, 2 * estimate.fee_sompi);
+        let all = FeeEstimate::from_utxos(&utxos, None, MIN_FEE_RATE).unwrap();
+        assert_eq!(all.total_sompi(), 3 * one_kas);
+        assert_eq!(all.change_sompi, 0);
+
+        // Change under the same rate the send pays
+        assert_eq!(estimate.change_sompi, 2 * one_kas - one_kas - estimate.fee_sompi);
+        assert!(!estimate.is_dust_change());
+        let sliver = FeeEstimate::from_utxos(&utxos, Some(2 * one_kas - estimate.fee_sompi - 1_000), MIN_FEE_RATE).unwrap();
+        assert!(sliver.is_dust_change());
+
+        assert_eq!(FeeEstimate::from_utxos(&utxos, Some(3 * one_kas), MIN_FEE_RATE), None);
+
+        // Only an exact rate pins the fee down ahead of the daemon; by default
+        // no policy is sent and the estimate is a lower bound
+        assert_eq!(SendFeePolicy::default(), SendFeePolicy::DaemonEstimate);
+        assert!(SendFeePolicy::default().to_proto().is_none());
+        assert_eq!(SendFeePolicy::default().estimate_rate(), (MIN_FEE_RATE, true));
+        assert_eq!(SendFeePolicy::ExactFeeRate(2.0).estimate_rate(), (2.0, false));
+        assert_eq!(SendFeePolicy::MaxFeeRate(2.0).fee_rate(), None);
+        assert_eq!(SendFeePolicy::MaxFee(10_000).estimate_rate(), (MIN_FEE_RATE, true));
+
+        assert_eq!(estimate.summary(), format!(
+            "Estimated fee: {} KAS, Total: {} KAS",
+            crate::utils::format_sompi(estimate.fee_sompi),
+            crate::utils::format_sompi(estimate.total_sompi())
+        ));
+        let floor = FeeEstimate { at_least: true, ..estimate };
+        assert!(floor.summary().starts_with("Estimated fee: ≥ "));
+        assert!(floor.summary().contains("Total: ≥ "));
+    }
+
+    #[tokio::test]
+    async fn test_estimate_fee_checks_inputs_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = WalletManager { project_root: dir.path().to_path_buf(), pool: WalletClientPool::new(), network: Network::Testnet };
+        let to = kaspa_addresses::Address::new(
+            kaspa_addresses::Prefix::Testnet,
+            kaspa_addresses::Version::PubKey,
+            &[7u8; 32],
+        )
+        .to_string();
+
+        // All rejected before the daemon is asked for UTXOs
+        let bad_address = manager.estimate_fee(0, "kaspatest:nope", Some(1_000), SendFeePolicy::default()).await;
+        assert!(matches!(bad_address, Err(Error::InvalidInput(_))));
+        let zero = manager.estimate_fee(0, &to, Some(0), SendFeePolicy::default()).await;
+        assert!(matches!(zero, Err(Error::InvalidInput(_))));
+        let bad_rate = manager.estimate_fee(0, &to, Some(1_000), SendFeePolicy::ExactFeeRate(0.0)).await;
+        assert!(matches!(bad_rate, Err(Error::InvalidInput(_))));
+        let bad_cap = manager.estimate_fee(0, &to, None, SendFeePolicy::MaxFee(0)).await;
+        assert!(matches!(bad_cap, Err(Error::InvalidInput(_))));
+    }
+
+    #[test]
+    fn test_estimate_send_flags_dust_change() {
+        let one_kas = crate::utils::SOMPI_PER_KAS;
+
+        // Plenty of change left over
+        let estimate = estimate_send(&[2 * one_kas], one_kas).unwrap();
+        assert_eq!(estimate.inputs, 1);
+        assert!(!estimate.is_dust_change());
+
+        // Leaves only a sliver of change
+        let amount = 2 * one_kas - estimate.fee_sompi - 1_000;
+        let estimate = estimate_send(&[2 * one_kas], amount).unwrap();
+        assert_eq!(estimate.change_sompi, 1_000);
+        assert!(estimate.is_dust_change());
+
+        // Needs a second input, which raises the fee
+        let two_inputs = estimate_send(&[one_kas, one_kas], one_kas + 1).unwrap();
+        assert_eq!(two_inputs.inputs, 2);
+        assert!(two_inputs.fee_sompi > estimate.fee_sompi);
+
+        assert!(estimate_send(&[one_kas], one_kas).is_none());
+    }
+
+    #[test]
+    fn test_payout_csv_and_batch_estimate() {
+        let one_kas = crate::utils::SOMPI_PER_KAS;
+        let addr = kaspa_addresses::Address::new(kaspa_addresses::Prefix::Testnet, kaspa_addresses::Version::PubKey, &[7u8; 32]).to_string();
+
+        let csv = format!("address,amount\n# weekly payout\n{addr},1.5\n\n{addr}, 0.25\n");
+        let outputs = parse_payout_csv(&csv).unwrap();
+        assert_eq!(outputs, vec![(addr.to_string(), one_kas * 3 / 2), (addr.to_string(), one_kas / 4)]);
+        assert_eq!(validate_payouts(&outputs, Network::Testnet).unwrap(), one_kas * 7 / 4);
+
+        assert!(parse_payout_csv(&format!("{addr} 1.5")).is_err());
+        assert!(parse_payout_csv(&format!("{addr},1.123456789")).is_err());
+        assert!(validate_payouts(&[("kaspa:qr35".to_string(), one_kas)], Network::Testnet).is_err());
+        // Valid, but for mainnet
+        let mainnet = kaspa_addresses::Address::new(kaspa_addresses::Prefix::Mainnet, kaspa_addresses::Version::PubKey, &[7u8; 32]).to_string();
+        assert!(validate_payouts(&[(mainnet, one_kas)], Network::Testnet).is_err());
+        assert!(validate_payouts(&[(addr.to_string(), 0)], Network::Testnet).is_err());
+        assert!(validate_payouts(&[], Network::Testnet).is_err());
+
+        // Change from the first payment funds the second
+        let fee = estimate_send_many(&[3 * one_kas], &[one_kas, one_kas]).unwrap();
+        assert!(fee > 0);
+        // Total plus fees must fit
+        assert!(estimate_send_many(&[2 * one_kas], &[one_kas, one_kas]).is_none());
+    }
+
+    #[test]
+    fn test_coinbase_maturity() {
+        let immature = coinbase_utxo(Some(10));
+        assert_eq!(immature.is_mature(), Some(false));
+        assert_eq!(immature.blocks_until_mature(), Some(COINBASE_MATURITY - 10));
+
+        let mature = coinbase_utxo(Some(COINBASE_MATURITY));
+        assert_eq!(mature.is_mature(), Some(true));
+        assert_eq!(mature.blocks_until_mature(), Some(0));
+
+        let unknown = coinbase_utxo(None);
+        assert_eq!(unknown.is_mature(), None);
+
+        let transfer = UtxoInfo { is_coinbase: false, ..coinbase_utxo(None) };
+        assert_eq!(transfer.is_mature(), Some(true));
+    }
+
+    #[test]
+    fn test_is_low_balance_only_for_active_wallets() {
+        let wallet = WalletInfo {
+            worker_id: 0,
+            address: Some("kaspatest:qq0".to_string()),
+            balance: Some(5.0),
+            container_running: true,
+            initial_balance: None,
+            fees_spent: None,
+            fee_burn_rate: None,
+            daemon_synced: Some(true),
+            is_locked: false,
+        };
+        assert!(wallet.is_low_balance(10.0));
+        assert!(!wallet.is_low_balance(5.0));
+
+        // A syncing daemon may report zero; that isn't a low balance
+        let syncing = WalletInfo { daemon_synced: Some(false), balance: Some(0.0), ..wallet.clone() };
+        assert!(!syncing.is_low_balance(10.0));
+        assert_eq!(syncing.status(), WalletStatus::Syncing);
+        assert!(syncing.not_ready_reason().unwrap().contains("syncing"));
+        assert_eq!(WalletInfo { is_locked: true, ..wallet.clone() }.status(), WalletStatus::Locked);
+        assert_eq!(format!("{:<9}|", WalletStatus::Locked), "Locked   |");
+        assert!(wallet.not_ready_reason().is_none());
+
+        assert!(!WalletInfo { container_running: false, ..wallet.clone() }.is_low_balance(10.0));
+        assert!(!WalletInfo { address: None, ..wallet.clone() }.is_low_balance(10.0));
+        assert!(!WalletInfo { balance: None, ..wallet }.is_low_balance(10.0));
+    }
+
+    #[test]
+    fn test_fee_burn_rate() {
+        let mut tracking = WalletTracking::default();
+        let t0 = 1_700_000_000;
+
+        assert!(tracking.record_balance(0, t0, 100.0));
+        // Too soon for another sample, and too little history for a rate
+        assert!(!tracking.record_balance(0, t0 + 60, 99.9));
+        assert_eq!(tracking.burn_rate(0, t0 + 60, 99.9), None);
+
+        assert!(tracking.record_balance(0, t0 + 3600, 98.0));
+        assert_eq!(tracking.burn_rate(0, t0 + 3600, 98.0), Some(2.0));
+
+        // Funding resets the baseline
+        assert!(tracking.record_balance(0, t0 + 4000, 500.0));
+        assert_eq!(tracking.burn_rate(0, t0 + 4000, 500.0), None);
+        tracking.record_balance(0, t0 + 4000 + 1800, 499.0);
+        assert_eq!(tracking.burn_rate(0, t0 + 4000 + 1800, 499.0), Some(2.0));
+
+        // Samples older than the window are dropped
+        let later = t0 + 4000 + BURN_RATE_WINDOW_SECS + 600;
+        assert!(tracking.record_balance(0, later, 490.0));
+        assert_eq!(tracking.balance_history[&0].len(), 2);
+
+        assert_eq!(hours_until_empty(10.0, 2.0), Some(5.0));
+        assert_eq!(hours_until_empty(10.0, 0.0), None);
+        assert_eq!(format_runway(0.5), "~30m");
+        assert_eq!(format_runway(5.0), "~5h");
+        assert_eq!(format_runway(76.0), "~3d 4h");
+    }
+
+    #[test]
+    fn test_only_transient_read_errors_are_retried() {
+        assert!(is_transient(&tonic::Status::unavailable("connection refused")));
+        assert!(is_transient(&tonic::Status::resource_exhausted("too many requests")));
+        assert!(is_transient(&tonic::Status::unknown("transport error")));
+
+        // The daemon answered; asking again won't change that
+        assert!(!is_transient(&tonic::Status::unknown("invalid password")));
+        assert!(!is_transient(&tonic::Status::unknown("wallet daemon is not synced yet")));
+        assert!(!is_transient(&tonic::Status::not_found("no such address")));
+        assert!(!is_transient(&tonic::Status::unauthenticated("wallet is locked")));
+    }
+
+    #[test]
+    fn test_gave_up_names_the_attempts() {
+        let status = tonic::Status::unavailable("connection refused");
+        let once = gave_up(Error::from_wallet_status(0, &status, "Failed to get balance"), 1);
+        assert_eq!(once.to_string(), "Wallet 0 is unavailable: connection refused");
+
+        let err = gave_up(Error::from_wallet_status(0, &status, "Failed to get balance"), READ_ATTEMPTS);
+        assert!(matches!(err, Error::WalletUnavailable { worker: 0, .. }));
+        assert!(err.to_string().ends_with("(gave up after 3 attempts)"));
+
+        let status = tonic::Status::aborted("busy");
+        let err = gave_up(Error::from_wallet_status(1, &status, "Failed to get balance"), READ_ATTEMPTS);
+        assert_eq!(err.to_string(), "Failed to get balance: busy (status: Aborted) (gave up after 3 attempts)");
+    }
+
+    #[tokio::test]
+    async fn test_pool_does_not_cache_failed_connections() {
+        let pool = WalletClientPool::new();
+        // Nothing listens on port 1, so the dial fails
+        let result = pool.get(0, || "http://127.0.0.1:1".to_string()).await;
+        assert!(result.is_err());
+        assert!(!pool.is_connected(0).await);
+
+        pool.invalidate(0).await;
+        assert!(!pool.is_connected(0).await);
+    }
+
     #[tokio::test]
     #[ignore] // Only run when kaswallet-0 is running
     async fn test_get_balance_grpc() {
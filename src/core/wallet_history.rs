@@ -0,0 +1,459 @@
+//! Per-worker transaction ledger behind `igra-cli wallet history`
+//!
+//! kaspawalletd has no history call and kaspad only reports unspent outputs,
+//! so igra-cli keeps its own record in `wallet_history.json` (next to
+//! `wallet_tracking.json`): every send made through igra-cli, and every
+//! incoming output seen when a wallet's UTXOs are listed. An output received
+//! and spent while igra-cli never looked at the wallet is missing.
+
+use anyhow::{anyhow, Context};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use fs2::FileExt;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::core::error::Result;
+use crate::core::export::ExportTable;
+use crate::core::network::Network;
+use crate::core::wallet::UtxoInfo;
+use crate::utils::format_sompi;
+
+/// Separates the ids of a send the daemon split into several transactions
+const TX_ID_SEPARATOR: &str = ";";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Direction {
+    In,
+    Out,
+}
+
+impl Direction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Direction::In => "in",
+            Direction::Out => "out",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub worker_id: usize,
+    /// Send time, or the block time estimated from the DAA score for incoming
+    pub timestamp_ms: u64,
+    pub direction: Direction,
+    /// None for a send-all whose amount couldn't be worked out beforehand
+    pub amount_sompi: Option<u64>,
+    /// Recipient for outgoing; sender for incoming ("coinbase" for mining rewards)
+    pub counterparty: String,
+    /// `;`-separated when the daemon split a send into several transactions
+    pub tx_id: String,
+    /// Outgoing only. An estimate from the spent UTXOs at the send's fee rate
+    /// (the daemon doesn't report the fee it paid).
+    #[serde(alias = "fee_sompi")]
+    pub estimated_fee_sompi: Option<u64>,
+}
+
+impl HistoryEntry {
+    pub fn time(&self) -> DateTime<Utc> {
+        DateTime::from_timestamp_millis(self.timestamp_ms as i64).unwrap_or_default()
+    }
+
+    fn tx_ids(&self) -> impl Iterator<Item = &str> {
+        self.tx_id.split(TX_ID_SEPARATOR)
+    }
+}
+
+/// A wallet's cumulative flow over what the ledger has recorded. The ledger
+/// starts when igra-cli first saw the wallet (and a pruned node never showed
+/// it older outputs), so the totals are only ever "since" its first entry.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WalletTotals {
+    pub total_in_sompi: u64,
+    /// Amounts sent through igra-cli, fees not included. Spends made with
+    /// another wallet tool never reach the ledger, so they are missing here.
+    pub total_out_sompi: u64,
+    /// Sends whose amount isn't known (send-all), missing from `total_out_sompi`
+    pub unknown_sends: usize,
+    /// Time of the oldest entry; None when nothing is recorded
+    pub since: Option<DateTime<Utc>>,
+}
+
+impl WalletTotals {
+    pub fn net_sompi(&self) -> i64 {
+        self.total_in_sompi as i64 - self.total_out_sompi as i64
+    }
+
+    /// "since 2026-03-01", or why there are no totals
+    pub fn since_label(&self) -> String {
+        match self.since {
+            Some(since) => format!("since {}", since.format("%Y-%m-%d")),
+            None => "nothing recorded yet".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WalletHistory {
+    entries: Vec<HistoryEntry>,
+}
+
+impl WalletHistory {
+    fn history_file(project_root: &Path) -> PathBuf {
+        project_root.join("wallet_history.json")
+    }
+
+    /// Held across every load-modify-save, until the returned file is
+    /// dropped: the TUI, the server's poller and CLI sends (each its own
+    /// process) list UTXOs while a send records itself, and none may drop
+    /// another's entry. A separate file, as saving replaces the ledger.
+    fn lock(project_root: &Path) -> Result<fs::File> {
+        let path = project_root.join("wallet_history.json.lock");
+        let file = fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+        file.lock_exclusive()
+            .with_context(|| format!("Failed to lock {}", path.display()))?;
+        Ok(file)
+    }
+
+    /// The ledger, empty if there is none yet. One that can't be parsed is
+    /// an error, never an empty ledger a later save would write over it.
+    pub fn load(project_root: &Path) -> Result<Self> {
+        let path = Self::history_file(project_root);
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(anyhow!(e).context(format!("Failed to read {}", path.display())).into()),
+        };
+        Ok(serde_json::from_str(&content)
+            .with_context(|| format!("{} is corrupt; move it aside to start a new ledger", path.display()))?)
+    }
+
+    /// Load the ledger, apply `change` and save if it returns true, under the
+    /// ledger lock. A ledger that can't be read is left as it is.
+    pub fn update(project_root: &Path, change: impl FnOnce(&mut Self) -> bool) -> Result<()> {
+        let _lock = Self::lock(project_root)?;
+        let mut history = Self::load(project_root)?;
+        if change(&mut history) {
+            history.save(project_root)?;
+        }
+        Ok(())
+    }
+
+    /// Written to a uniquely named temporary file and renamed, so a crash
+    /// never leaves half a ledger
+    fn save(&self, project_root: &Path) -> Result<()> {
+        let path = Self::history_file(project_root);
+        let content = serde_json::to_string_pretty(self).context("Failed to serialize wallet history")?;
+        let mut tmp = tempfile::NamedTempFile::new_in(project_root)
+            .with_context(|| format!("Failed to create a temporary file in {}", project_root.display()))?;
+        tmp.write_all(content.as_bytes())
+            .with_context(|| format!("Failed to write {}", tmp.path().display()))?;
+        tmp.persist(&path)
+            .map_err(|e| e.error)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(())
+    }
+
+    pub fn record_send(&mut self, entry: HistoryEntry) {
+        self.entries.push(entry);
+    }
+
+    /// Add incoming outputs not seen before. Change from our own sends is not
+    /// income: its transaction is a recorded send, or it was paid from one of
+    /// `own_addresses`. Outputs without a time estimate wait for a later
+    /// listing. Returns true if anything was added.
+    pub fn record_utxos(&mut self, worker_id: usize, utxos: &[UtxoInfo], own_addresses: &[String]) -> bool {
+        let mut added = false;
+        for utxo in utxos {
+            if utxo.timestamp_ms == 0 {
+                continue;
+            }
+            let known = self.entries.iter().any(|e| {
+                e.worker_id == worker_id
+                    && match e.direction {
+                        Direction::Out => e.tx_ids().any(|id| id == utxo.tx_id),
+                        Direction::In => e.tx_id == utxo.tx_id && e.amount_sompi == Some(utxo.amount_sompi),
+                    }
+            });
+            let from_self = utxo.source_addresses.iter().any(|a| own_addresses.contains(a));
+            if known || from_self {
+                continue;
+            }
+
+            let counterparty = if utxo.is_coinbase {
+                "coinbase".to_string()
+            } else {
+                utxo.source_addresses.first().cloned().unwrap_or_default()
+            };
+            self.entries.push(HistoryEntry {
+                worker_id,
+                timestamp_ms: utxo.timestamp_ms,
+                direction: Direction::In,
+                amount_sompi: Some(utxo.amount_sompi),
+                counterparty,
+                tx_id: utxo.tx_id.clone(),
+                estimated_fee_sompi: None,
+            });
+            added = true;
+        }
+        added
+    }
+
+    /// A worker's entries from `since` (inclusive) to `until` (exclusive), oldest first
+    pub fn for_worker(
+        &self,
+        worker_id: usize,
+        since: Option<DateTime<Utc>>,
+        until: Option<DateTime<Utc>>,
+    ) -> Vec<HistoryEntry> {
+        let mut entries: Vec<HistoryEntry> = self
+            .entries
+            .iter()
+            .filter(|e| e.worker_id == worker_id)
+            .filter(|e| since.is_none_or(|since| e.time() >= since))
+            .filter(|e| until.is_none_or(|until| e.time() < until))
+            .cloned()
+            .collect();
+        entries.sort_by_key(|e| e.timestamp_ms);
+        entries
+    }
+
+    /// Received and sent totals over all of a worker's entries
+    pub fn totals(&self, worker_id: usize) -> WalletTotals {
+        let mut totals = WalletTotals::default();
+        for entry in self.entries.iter().filter(|e| e.worker_id == worker_id) {
+            match (entry.direction, entry.amount_sompi) {
+                (Direction::In, Some(amount)) => totals.total_in_sompi += amount,
+                (Direction::Out, Some(amount)) => totals.total_out_sompi += amount,
+                (Direction::Out, None) => totals.unknown_sends += 1,
+                (Direction::In, None) => {}
+            }
+            let time = entry.time();
+            totals.since = Some(totals.since.map_or(time, |since| since.min(time)));
+        }
+        totals
+    }
+}
+
+/// `tx_ids` as stored in `HistoryEntry::tx_id`
+pub fn join_tx_ids(tx_ids: &[String]) -> String {
+    tx_ids.join(TX_ID_SEPARATOR)
+}
+
+#[derive(Serialize)]
+struct HistoryExport<'a> {
+    worker_id: usize,
+    network: &'static str,
+    currency: &'static str,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+    transactions: &'a [serde_json::Map<String, serde_json::Value>],
+}
+
+/// Format entries for `wallet history` (csv or json). Amounts and fees are in
+/// the network's currency, named in a leading `#` comment (csv) or the
+/// `currency` field (json).
+pub fn format_history(
+    entries: &[HistoryEntry],
+    worker_id: usize,
+    network: Network,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+    format: &str,
+) -> Result<String> {
+    let mut table = ExportTable::new("wallet-history", &["date", "direction", "amount", "counterparty", "tx_id", "estimated_fee"]);
+    for entry in entries {
+        table.push_row(vec![
+            entry.time().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            entry.direction.as_str().to_string(),
+            entry.amount_sompi.map(format_sompi).unwrap_or_default(),
+            entry.counterparty.clone(),
+            entry.tx_id.clone(),
+            entry.estimated_fee_sompi.map(format_sompi).unwrap_or_default(),
+        ]);
+    }
+
+    match format {
+        "csv" => Ok(format!(
+            "# worker {}, {}, amounts and fees in {}\n{}",
+            worker_id,
+            network,
+            network.currency_label(),
+            table.to_csv()
+        )),
+        "json" => {
+            let transactions: Vec<serde_json::Map<String, serde_json::Value>> = serde_json::from_str(&table.to_json()?)
+                .context("Failed to build wallet history JSON")?;
+            let export = HistoryExport {
+                worker_id,
+                network: network.as_str(),
+                currency: network.currency_label(),
+                since,
+                until,
+                transactions: &transactions,
+            };
+            Ok(serde_json::to_string_pretty(&export).context("Failed to serialize wallet history")?)
+        }
+        other => Err(anyhow!("Unknown format '{}' (use csv or json)", other).into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utxo(tx_id: &str, amount_sompi: u64, timestamp_ms: u64, source: &str) -> UtxoInfo {
+        UtxoInfo {
+            address: "kaspatest:own".to_string(),
+            tx_id: tx_id.to_string(),
+            amount_kas: amount_sompi as f64 / 1e8,
+            amount_sompi,
+            block_daa_score: 0,
+            is_coinbase: false,
+            timestamp_ms,
+            source_addresses: vec![source.to_string()],
+            confirmations: None,
+        }
+    }
+
+    #[test]
+    fn test_record_utxos_skips_known_and_change() {
+        let own = vec!["kaspatest:own".to_string()];
+        let mut history = WalletHistory::default();
+        history.record_send(HistoryEntry {
+            worker_id: 0,
+            timestamp_ms: 2_000,
+            direction: Direction::Out,
+            amount_sompi: Some(50),
+            counterparty: "kaspatest:payee".to_string(),
+            tx_id: join_tx_ids(&["sent-a".to_string(), "sent-b".to_string()]),
+            estimated_fee_sompi: Some(3),
+        });
+
+        let utxos = vec![
+            utxo("funding", 1_000, 1_000, "kaspatest:exchange"),
+            // Change outputs of our own send
+            utxo("sent-b", 900, 2_000, "kaspatest:other"),
+            utxo("consolidation", 10, 3_000, "kaspatest:own"),
+            // No DAG info yet
+            utxo("later", 5, 0, "kaspatest:exchange"),
+        ];
+        assert!(history.record_utxos(0, &utxos, &own));
+        // Listing the same outputs again adds nothing
+        assert!(!history.record_utxos(0, &utxos, &own));
+
+        let entries = history.for_worker(0, None, None);
+        assert_eq!(entries.len(), 2);
+        assert_eq!((entries[0].direction, entries[0].tx_id.as_str()), (Direction::In, "funding"));
+        assert_eq!(entries[0].counterparty, "kaspatest:exchange");
+        assert_eq!(entries[1].direction, Direction::Out);
+        assert!(history.for_worker(1, None, None).is_empty());
+    }
+
+    #[test]
+    fn test_for_worker_range_and_format() {
+        let mut history = WalletHistory::default();
+        history.record_utxos(0, &[utxo("b", 200_000_000, 1_700_000_200_000, "kaspatest:x, y")], &[]);
+        history.record_utxos(0, &[utxo("a", 100_000_000, 1_700_000_100_000, "kaspatest:x")], &[]);
+
+        let since = DateTime::from_timestamp(1_700_000_100, 0);
+        let until = DateTime::from_timestamp(1_700_000_200, 0);
+        let entries = history.for_worker(0, since, until);
+        assert_eq!(entries.iter().map(|e| e.tx_id.as_str()).collect::<Vec<_>>(), vec!["a"]);
+
+        let all = history.for_worker(0, None, None);
+        let csv = format_history(&all, 0, Network::Mainnet, None, None, "csv").unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "# worker 0, mainnet, amounts and fees in KAS");
+        assert_eq!(lines[1], "date,direction,amount,counterparty,tx_id,estimated_fee");
+        assert_eq!(lines[2], "2023-11-14T22:15:00Z,in,1.00000000,kaspatest:x,a,");
+        assert_eq!(lines[3], "2023-11-14T22:16:40Z,in,2.00000000,\"kaspatest:x, y\",b,");
+
+        let json: serde_json::Value =
+            serde_json::from_str(&format_history(&all, 0, Network::Testnet, None, None, "json").unwrap()).unwrap();
+        assert_eq!(json["currency"], "TKAS");
+        assert_eq!(json["transactions"][1]["amount"], "2.00000000");
+
+        assert!(format_history(&all, 0, Network::Testnet, None, None, "xml").is_err());
+    }
+
+    #[test]
+    fn test_corrupt_ledger_is_kept() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = WalletHistory::history_file(dir.path());
+        assert!(WalletHistory::load(dir.path()).unwrap().entries.is_empty());
+
+        WalletHistory::update(dir.path(), |history| history.record_utxos(0, &[utxo("a", 5, 1_000, "kaspatest:x")], &[]))
+            .unwrap();
+        assert_eq!(WalletHistory::load(dir.path()).unwrap().entries.len(), 1);
+
+        // Entries written before the fee was labelled an estimate still load
+        let old = fs::read_to_string(&path).unwrap().replace("estimated_fee_sompi", "fee_sompi");
+        fs::write(&path, old).unwrap();
+        assert_eq!(WalletHistory::load(dir.path()).unwrap().entries.len(), 1);
+
+        fs::write(&path, "{ truncated").unwrap();
+        assert!(WalletHistory::load(dir.path()).is_err());
+        assert!(WalletHistory::update(dir.path(), |_| true).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{ truncated");
+    }
+
+    #[test]
+    fn test_concurrent_updates_keep_every_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        std::thread::scope(|scope| {
+            for worker_id in 0..8 {
+                let root = dir.path();
+                scope.spawn(move || {
+                    let tx_id = format!("tx-{}", worker_id);
+                    WalletHistory::update(root, |history| history.record_utxos(worker_id, &[utxo(&tx_id, 5, 1_000, "kaspatest:x")], &[]))
+                        .unwrap();
+                });
+            }
+        });
+
+        assert_eq!(WalletHistory::load(dir.path()).unwrap().entries.len(), 8);
+        // Only the ledger and its lock are left behind, no temporary files
+        let mut files: Vec<String> = fs::read_dir(dir.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        files.sort();
+        assert_eq!(files, vec!["wallet_history.json", "wallet_history.json.lock"]);
+    }
+
+    #[test]
+    fn test_totals() {
+        let mut history = WalletHistory::default();
+        assert_eq!(history.totals(0).since_label(), "nothing recorded yet");
+
+        history.record_utxos(0, &[utxo("a", 500, 1_700_000_100_000, "kaspatest:x")], &[]);
+        history.record_utxos(0, &[utxo("b", 300, 1_700_000_000_000, "kaspatest:x")], &[]);
+        history.record_utxos(1, &[utxo("c", 7, 1_600_000_000_000, "kaspatest:x")], &[]);
+        for (tx_id, amount_sompi) in [("d", Some(200)), ("e", None)] {
+            history.record_send(HistoryEntry {
+                worker_id: 0,
+                timestamp_ms: 1_700_000_200_000,
+                direction: Direction::Out,
+                amount_sompi,
+                counterparty: "kaspatest:payee".to_string(),
+                tx_id: tx_id.to_string(),
+                estimated_fee_sompi: Some(1),
+            });
+        }
+
+        let totals = history.totals(0);
+        assert_eq!((totals.total_in_sompi, totals.total_out_sompi, totals.unknown_sends), (800, 200, 1));
+        assert_eq!(totals.net_sompi(), 600);
+        // From the oldest entry of this worker, not the order recorded
+        assert_eq!(totals.since_label(), "since 2023-11-14");
+    }
+}
@@ -0,0 +1,197 @@
+//! Where worker wallet passwords come from
+//!
+//! By default the password is `W{n}_KASWALLET_PASSWORD` from `.env`. Per worker
+//! it can instead be read from an environment variable, a file only the owner
+//! can read, or typed in when sending. Set in config.toml:
+//!
+//! ```toml
+//! [wallet_passwords]
+//! allow_config_fallback = false   # use .env if the configured source fails
+//! w0 = { source = "file", path = "/etc/igra/w0.pass" }
+//! w1 = { source = "env", var = "IGRA_W1_PASSWORD" }
+//! w2 = { source = "prompt" }
+//! ```
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use zeroize::Zeroizing;
+
+use crate::core::ConfigManager;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "source", rename_all = "lowercase")]
+pub enum PasswordSource {
+    /// `W{n}_KASWALLET_PASSWORD` in .env
+    #[default]
+    Config,
+    Env { var: String },
+    /// First line of a file that must not be readable by group/others
+    File { path: PathBuf },
+    /// Asked for each time (TUI send dialog); unavailable over HTTP
+    Prompt,
+}
+
+impl fmt::Display for PasswordSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PasswordSource::Config => write!(f, "config"),
+            PasswordSource::Env { var } => write!(f, "env {}", var),
+            PasswordSource::File { path } => write!(f, "file {}", path.display()),
+            PasswordSource::Prompt => write!(f, "prompt"),
+        }
+    }
+}
+
+/// `[wallet_passwords]` in config.toml
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WalletPasswordConfig {
+    #[serde(default)]
+    pub allow_config_fallback: bool,
+    /// Keyed "w0".."w4"; workers not listed use `PasswordSource::Config`
+    #[serde(flatten)]
+    pub workers: HashMap<String, PasswordSource>,
+}
+
+impl WalletPasswordConfig {
+    pub fn source_for(&self, worker_id: usize) -> PasswordSource {
+        self.workers
+            .get(&format!("w{}", worker_id))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Resolve the password for a worker. `prompted` is what the user typed,
+    /// if the caller asked; it is only used for `PasswordSource::Prompt`.
+    pub fn resolve(&self, worker_id: usize, config: &ConfigManager, prompted: Option<WalletPassword>) -> Result<WalletPassword> {
+        let source = self.source_for(worker_id);
+        let result = match &source {
+            PasswordSource::Config => return config_password(worker_id, config),
+            PasswordSource::Env { var } => std::env::var(var)
+                .ok()
+                .filter(|v| !v.is_empty())
+                .map(WalletPassword::new)
+                .ok_or_else(|| anyhow!("environment variable {} is not set", var)),
+            PasswordSource::File { path } => read_password_file(path),
+            PasswordSource::Prompt => prompted
+                .ok_or_else(|| anyhow!("password must be entered interactively")),
+        };
+
+        result.or_else(|e| {
+            if self.allow_config_fallback {
+                config_password(worker_id, config)
+            } else {
+                Err(e.context(format!("Wallet {} password ({})", worker_id, source)))
+            }
+        })
+    }
+}
+
+/// A wallet password, wiped from memory on drop and never printed
+#[derive(Clone)]
+pub struct WalletPassword(Zeroizing<String>);
+
+impl WalletPassword {
+    pub fn new(password: String) -> Self {
+        Self(Zeroizing::new(password))
+    }
+
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<Zeroizing<String>> for WalletPassword {
+    fn from(password: Zeroizing<String>) -> Self {
+        Self(password)
+    }
+}
+
+impl fmt::Debug for WalletPassword {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("WalletPassword(****)")
+    }
+}
+
+fn config_password(worker_id: usize, config: &ConfigManager) -> Result<WalletPassword> {
+    config.require_secrets()?;
+    config
+        .get(&format!("W{}_KASWALLET_PASSWORD", worker_id))
+        .filter(|v| !v.is_empty())
+        .map(|v| WalletPassword::new(v.to_string()))
+        .ok_or_else(|| anyhow!("No wallet password configured for worker {}", worker_id))
+}
+
+fn read_password_file(path: &Path) -> Result<WalletPassword> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?
+            .permissions()
+            .mode();
+        if mode & 0o077 != 0 {
+            bail!("{} is accessible by other users (mode {:o}); chmod 600 it", path.display(), mode & 0o777);
+        }
+    }
+
+    let contents = Zeroizing::new(
+        std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?,
+    );
+    let password = contents.lines().next().unwrap_or("");
+    if password.is_empty() {
+        bail!("{} is empty", path.display());
+    }
+    Ok(WalletPassword::new(password.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn config_with_password() -> (tempfile::NamedTempFile, ConfigManager) {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(file, "W0_KASWALLET_PASSWORD=from-env-file").unwrap();
+        let config = ConfigManager::load(file.path()).unwrap();
+        (file, config)
+    }
+
+    #[test]
+    fn test_resolve_sources() {
+        let (_env, config) = config_with_password();
+        let dir = tempfile::tempdir().unwrap();
+        let pass_file = dir.path().join("w0.pass");
+        std::fs::write(&pass_file, "from-file\n").unwrap();
+
+        let mut settings: WalletPasswordConfig = toml::from_str(&format!(
+            "w0 = {{ source = \"file\", path = \"{}\" }}\nw1 = {{ source = \"prompt\" }}",
+            pass_file.display()
+        ))
+        .unwrap();
+        assert_eq!(settings.source_for(2), PasswordSource::Config);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            // World-readable files are refused, and there's no silent fallback
+            std::fs::set_permissions(&pass_file, std::fs::Permissions::from_mode(0o644)).unwrap();
+            assert!(settings.resolve(0, &config, None).is_err());
+            std::fs::set_permissions(&pass_file, std::fs::Permissions::from_mode(0o600)).unwrap();
+        }
+        assert_eq!(settings.resolve(0, &config, None).unwrap().expose(), "from-file");
+
+        // Prompt without an answer fails unless falling back is allowed
+        assert!(settings.resolve(1, &config, None).is_err());
+        let typed = WalletPassword::new("typed".to_string());
+        assert_eq!(settings.resolve(1, &config, Some(typed)).unwrap().expose(), "typed");
+
+        std::fs::remove_file(&pass_file).unwrap();
+        settings.allow_config_fallback = true;
+        assert_eq!(settings.resolve(0, &config, None).unwrap().expose(), "from-env-file");
+
+        assert_eq!(format!("{:?}", WalletPassword::new("secret".to_string())), "WalletPassword(****)");
+    }
+}
@@ -1,5 +1,40 @@
-// Library interface for igra-cli
-// Exposes core modules for testing and external use
+//! Library interface for igra-cli
+//!
+//! The managers behind the CLI, TUI and web server, for tools that want to
+//! drive an IGRA Orchestra node directly. The `igra-cli` binary is a thin
+//! front end over this crate.
+//!
+//! ```
+//! use igra_mgt::ConfigManager;
+//!
+//! let dir = tempfile::tempdir()?;
+//! let env_file = dir.path().join(".env");
+//! std::fs::write(&env_file, "NETWORK=testnet\nNODE_ID=node-1\n")?;
+//!
+//! let config = ConfigManager::load(&env_file)?;
+//! assert_eq!(config.get("NETWORK"), Some("testnet"));
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+//!
+//! `DockerManager` and `WalletManager` return [`Error`], so failures can be
+//! told apart:
+//!
+//! ```no_run
+//! use igra_mgt::{DockerManager, Error};
+//!
+//! # async fn restart() -> Result<(), Error> {
+//! let docker = DockerManager::new().await?;
+//! match docker.restart_service("kaspad").await {
+//!     Err(Error::DockerUnavailable(e)) => eprintln!("{}: {}", e, e.hint()),
+//!     Err(Error::ServiceNotFound(name)) => eprintln!("no service {}", name),
+//!     other => other?,
+//! }
+//! # Ok(())
+//! # }
+//! ```
 
 pub mod core;
 pub mod utils;
+
+pub use crate::core::{ConfigManager, DockerManager, Error, Network};
+pub use crate::core::wallet::WalletManager;
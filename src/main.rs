@@ -1,40 +1,131 @@
 mod app;
 mod cli;
-mod core;
 mod screens;
-mod utils;
 mod widgets;
 
+// Shared with other tools through the library crate
+use igra_mgt::{core, utils};
+
 #[cfg(feature = "server")]
 mod server;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::Parser;
+use colored::Colorize;
 
 use app::App;
-use cli::{BackupCommands, Cli, Commands, ConfigCommands, RpcCommands, TokenCommands, WalletCommands};
-use core::{ConfigManager, DockerManager};
-use core::rpc::RpcTester;
-use core::wallet::WalletManager;
+use cli::{BackupCommands, Cli, Commands, ConfigCommands, DiagCommands, RpcCommands, SslCommands, TokenCommands, WalletCommands, WatchArgs};
+use crate::core::{ConfigManager, DockerManager};
+use crate::core::error::docker_error;
+use crate::core::rpc::RpcTester;
+use crate::core::wallet::WalletManager;
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
 
-    match cli.command {
+    core::config_audit::set_source(match cli.command {
+        None => core::config_audit::ChangeSource::Tui,
+        #[cfg(feature = "server")]
+        Some(Commands::Serve { .. }) => core::config_audit::ChangeSource::Api,
+        Some(_) => core::config_audit::ChangeSource::Cli,
+    });
+
+    // First thing, so no manager ever runs without it
+    if cli.dry_run {
+        core::dry_run::enable();
+        if cli.command.is_some() {
+            eprintln!("{}", "(dry-run) Nothing will be changed; actions are checked and printed only".yellow().bold());
+        }
+    }
+
+    if let Some(ref env_file) = cli.env_file {
+        // `config init` is the one command that creates it
+        let creating = matches!(cli.command, Some(Commands::Config { command: ConfigCommands::Init { .. } }));
+        if !creating && !env_file.is_file() {
+            anyhow::bail!("Env file not found: {} (from --env-file)", env_file.display());
+        }
+        ConfigManager::use_env_file(env_file)?;
+    }
+
+    if let Some(runtime) = cli.backend {
+        DockerManager::use_runtime(runtime)?;
+    }
+
+    if cli.compose_file.is_some() || cli.project.is_some() {
+        DockerManager::configure(crate::core::docker::ComposeTarget {
+            compose_file: cli.compose_file.clone(),
+            project: cli.project.clone(),
+        })?;
+    }
+
+    // The TUI shows this in its status line instead
+    if cli.command.is_some() {
+        if let Some(warning) = core::docker::remote_docker_warning() {
+            eprintln!("⚠ {}", warning);
+        }
+    }
+
+    let result = run_command(cli.command).await;
+
+    if core::dry_run::enabled() {
+        let planned = core::dry_run::take_planned();
+        if planned.is_empty() {
+            eprintln!("{}", "(dry-run) No changes would have been made".yellow().bold());
+        } else {
+            eprintln!("{}", "(dry-run) Would have run:".yellow().bold());
+            for action in planned {
+                eprintln!("  {}", action);
+            }
+        }
+    }
+
+    // Docker connection problems get a remediation hint instead of a bare error
+    if let Err(ref e) = result {
+        if let Some(docker_err) = docker_error(e) {
+            eprintln!("✗ {}", docker_err);
+            eprintln!("  {}", docker_err.hint());
+            std::process::exit(1);
+        }
+    }
+
+    result
+}
+
+async fn run_command(command: Option<Commands>) -> Result<()> {
+    match command {
         None => {
             // No command - run interactive TUI
-            let mut app = App::new()?;
+            let mut app = loop {
+                match App::new().await {
+                    Ok(app) => break app,
+                    Err(e) => match docker_error(&e) {
+                        // Let the user fix Docker and retry without restarting
+                        Some(docker_err) => {
+                            if !screens::docker_error::show(docker_err)? {
+                                return Ok(());
+                            }
+                        }
+                        None => return Err(e),
+                    },
+                }
+            };
             app.run().await?;
         }
-        Some(Commands::Status) => {
-            handle_status().await?;
+        Some(Commands::Status { watch, interval, oneline }) => {
+            if oneline {
+                // Prints something even when nothing can be reached, so a
+                // prompt or status bar never shows an error
+                println!("{}", core::status_summary::line().await);
+            } else {
+                handle_status(watch, interval).await?;
+            }
         }
         Some(Commands::Start { profile, service }) => {
             handle_start(profile, service).await?;
         }
-        Some(Commands::Stop { all, service }) => {
-            handle_stop(all, service).await?;
+        Some(Commands::Stop { all, yes, service }) => {
+            handle_stop(all, yes, service).await?;
         }
         Some(Commands::Restart { service }) => {
             handle_restart(service).await?;
@@ -43,8 +134,13 @@ async fn main() -> Result<()> {
             service,
             follow,
             tail,
+            since,
+            no_color,
         }) => {
-            handle_logs(service, follow, tail).await?;
+            handle_logs(service, follow, tail, since, no_color).await?;
+        }
+        Some(Commands::Exec { service, cmd }) => {
+            handle_exec(service, cmd).await?;
         }
         Some(Commands::Rpc { command }) => {
             handle_rpc(command).await?;
@@ -58,18 +154,28 @@ async fn main() -> Result<()> {
         Some(Commands::Config { command }) => {
             handle_config(command).await?;
         }
-        Some(Commands::Monitor) => {
-            println!("Monitor mode is available in the TUI dashboard.");
-            println!("Run 'igra-cli' without arguments to launch the interactive dashboard.");
+        Some(Commands::Ssl { command }) => {
+            handle_ssl(command).await?;
+        }
+        Some(Commands::Monitor { args }) => {
+            handle_watch(args).await?;
         }
         Some(Commands::Health) => {
-            println!("Health checks are available in the TUI dashboard.");
-            println!("Run 'igra-cli' without arguments to view service health status.");
+            handle_health().await?;
+        }
+        Some(Commands::Ports { profile }) => {
+            handle_ports(profile).await?;
         }
-        Some(Commands::Upgrade { check, pull, apply }) => {
-            handle_upgrade(check, pull, apply).await?;
+        Some(Commands::Upgrade { check, pull, apply, profile, force_recreate }) => {
+            handle_upgrade(check, pull, apply, profile, force_recreate).await?;
         }
-        Some(Commands::Diag { report }) => {
+        Some(Commands::SelfUpdate { check, yes }) => {
+            handle_self_update(check, yes).await?;
+        }
+        Some(Commands::Diag { command: Some(DiagCommands::Versions), .. }) => {
+            handle_diag_versions().await;
+        }
+        Some(Commands::Diag { report, command: None }) => {
             handle_diagnostics(report).await?;
         }
         Some(Commands::Setup) => {
@@ -80,12 +186,12 @@ async fn main() -> Result<()> {
             println!("  2. .env file is configured (see .env.example)");
             println!("  3. Run: docker compose --profile <profile> up -d");
         }
-        Some(Commands::Watch { filter, record, format }) => {
-            handle_watch(filter, record, format).await?;
+        Some(Commands::Watch { args }) => {
+            handle_watch(args).await?;
         }
         #[cfg(feature = "server")]
-        Some(Commands::Serve { port, host, cors }) => {
-            server::run(host, port, cors).await?;
+        Some(Commands::Serve { port, host, cors, allow_destructive }) => {
+            server::run(host, port, cors, allow_destructive).await?;
         }
         #[cfg(feature = "server")]
         Some(Commands::InstallService { port, host, cors, user }) => {
@@ -96,8 +202,153 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
-async fn handle_status() -> Result<()> {
+async fn handle_health() -> Result<()> {
     let docker = DockerManager::new().await?;
+    let running: Vec<String> = docker.list_containers().await?
+        .into_iter()
+        .filter(|c| c.state == utils::ContainerState::Running)
+        .map(|c| c.name)
+        .collect();
+
+    let threshold = utils::AppConfig::low_balance_threshold(docker.network());
+    let results = core::HealthChecker::new()
+        .with_low_balance_threshold(threshold)
+        .check_all(&running)
+        .await;
+    if results.is_empty() {
+        println!("No running services to check.");
+        return Ok(());
+    }
+
+    println!("IGRA Orchestra Health\n");
+    let mut failed = 0;
+    for r in &results {
+        let symbol = if r.healthy { "✓" } else { "✗" };
+        if !r.healthy {
+            failed += 1;
+        }
+        println!("{} {:<26} {:>6} ms  {}", symbol, r.name, r.latency.as_millis(), r.detail);
+    }
+
+    if failed > 0 {
+        println!("\n✗ {} of {} checks failed", failed, results.len());
+        std::process::exit(1);
+    }
+    println!("\n✓ All {} checks passed", results.len());
+    Ok(())
+}
+
+/// `--domain`, or IGRA_ORCHESTRA_DOMAIN from the project .env
+fn ssl_domain(domain: Option<String>) -> Result<String> {
+    match domain {
+        Some(domain) => Ok(domain),
+        None => ConfigManager::load_from_project()
+            .ok()
+            .and_then(|config| config.get("IGRA_ORCHESTRA_DOMAIN").map(|d| d.to_string()))
+            .filter(|d| !d.is_empty())
+            .ok_or_else(|| anyhow::anyhow!(
+                "No domain configured (IGRA_ORCHESTRA_DOMAIN is not set) - pass --domain <DOMAIN>"
+            )),
+    }
+}
+
+async fn handle_ssl(command: SslCommands) -> Result<()> {
+    match command {
+        SslCommands::Check { domain, warn_days } => {
+            let domain = ssl_domain(domain)?;
+
+            let info = core::ssl::SslManager::check_certificate_with_openssl(&domain).await?;
+
+            println!("SSL certificate for {}\n", info.domain);
+            println!("  Valid:          {}", if info.is_valid { "yes" } else { "no" });
+            println!("  Issuer:         {}", info.issuer.as_deref().unwrap_or("unknown"));
+            if let Some(until) = info.valid_until {
+                println!("  Valid until:    {}", until.format("%Y-%m-%d %H:%M:%S UTC"));
+            }
+            match info.days_remaining {
+                Some(days) => println!("  Days remaining: {}", days),
+                None => println!("  Days remaining: unknown"),
+            }
+
+            // Exit codes follow the Nagios convention: 1 = warning, 2 = critical
+            if !info.is_valid {
+                println!("\n✗ Certificate is invalid or could not be retrieved");
+                std::process::exit(2);
+            }
+            if info.days_remaining.map(|d| d < warn_days).unwrap_or(true) {
+                println!("\n✗ Certificate expires within {} days", warn_days);
+                std::process::exit(1);
+            }
+            println!("\n✓ Certificate OK");
+        }
+        SslCommands::Renew { domain, reissue, yes } => {
+            let ssl = core::ssl::SslManager::new()?;
+
+            if !reissue {
+                ssl.force_renewal().await?;
+                println!("{}", core::dry_run::tag("✓ Traefik restarted. Certificates due for renewal will be renewed."));
+                println!("  To force a new certificate: igra-cli ssl renew --reissue");
+                return Ok(());
+            }
+
+            let domain = ssl_domain(domain)?;
+            println!("This removes the certificate for {} from acme.json and restarts Traefik.", domain);
+            println!("HTTPS for the domain is unavailable until Let's Encrypt issues a new one");
+            println!("(rate limit: 5 duplicate certificates per week).");
+            if !yes && !prompt("Continue? [y/N]", "")?.eq_ignore_ascii_case("y") {
+                println!("Aborted");
+                return Ok(());
+            }
+
+            let backup = ssl.reissue_certificate(&domain).await?;
+            println!("{}", core::dry_run::tag(&format!("✓ Backed up acme.json to {}", backup.display())));
+            println!("{}", core::dry_run::tag(&format!("✓ Removed certificate for {} and restarted Traefik", domain)));
+            println!("\nCheck the new certificate in a minute with: igra-cli ssl check --domain {}", domain);
+        }
+    }
+
+    Ok(())
+}
+
+async fn handle_status(watch: bool, interval: u64) -> Result<()> {
+    use std::io::IsTerminal;
+
+    let docker = DockerManager::new().await?;
+
+    // Piped or redirected output gets a single snapshot rather than an endless stream
+    if !watch || !std::io::stdout().is_terminal() {
+        return print_status(&docker).await;
+    }
+
+    use crossterm::{cursor, execute};
+    execute!(std::io::stdout(), cursor::Hide)?;
+
+    let result = watch_status(&docker, interval).await;
+
+    // Restore the cursor however the loop ended
+    execute!(std::io::stdout(), cursor::Show)?;
+    println!();
+    result
+}
+
+/// Clear and reprint the status table every `interval` seconds until Ctrl+C
+async fn watch_status(docker: &DockerManager, interval: u64) -> Result<()> {
+    use crossterm::{cursor, execute, terminal};
+
+    let mut ticker = tokio::time::interval(std::time::Duration::from_secs(interval));
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {
+                execute!(std::io::stdout(), terminal::Clear(terminal::ClearType::All), cursor::MoveTo(0, 0))?;
+                println!("Every {}s: igra-cli status    {}\n", interval, chrono::Local::now().format("%H:%M:%S"));
+                print_status(docker).await?;
+            }
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+        }
+    }
+}
+
+async fn print_status(docker: &DockerManager) -> Result<()> {
     let containers = docker.list_containers().await?;
 
     println!("IGRA Orchestra Status\n");
@@ -115,17 +366,106 @@ async fn handle_status() -> Result<()> {
     Ok(())
 }
 
+/// Published ports and conflicts; exits 1 if any conflict is found
+async fn handle_ports(profile: Option<String>) -> Result<()> {
+    use crate::core::ports::{host_port_free, parse_compose_port, published_ports};
+
+    let docker = DockerManager::new().await?;
+    let containers = docker.list_containers().await?;
+    let published = published_ports(&containers);
+    let mut conflicts = 0;
+
+    println!("Published Host Ports\n");
+    println!("{:<12} {:<25} {:<10} Address", "Port", "Service", "Container");
+    println!("{}", "-".repeat(70));
+    for port in &published {
+        for (service, container_port, addresses) in &port.owners {
+            println!(
+                "{:<12} {:<25} {:<10} {}",
+                format!("{}/{}", port.host_port, port.protocol),
+                service,
+                container_port,
+                addresses.join(", ")
+            );
+        }
+    }
+    if published.is_empty() {
+        println!("(no published ports)");
+    }
+    println!();
+
+    for port in published.iter().filter(|p| p.is_conflict()) {
+        let services: Vec<&str> = port.owners.iter().map(|(name, _, _)| name.as_str()).collect();
+        println!("✗ {}/{} is published by {}", port.host_port, port.protocol, services.join(", "));
+        conflicts += 1;
+    }
+    if conflicts == 0 {
+        println!("✓ No port conflicts between running services");
+    }
+
+    if let Some(profile) = profile {
+        println!("\nProfile {}:", profile);
+
+        // Compose substitutes ${VAR} from the process environment and the project .env
+        let env = ConfigManager::load_from_project().ok();
+        let lookup = |var: &str| {
+            std::env::var(var)
+                .ok()
+                .or_else(|| env.as_ref().and_then(|c| c.get(var)).map(|v| v.to_string()))
+        };
+
+        let running: Vec<&str> = containers
+            .iter()
+            .filter(|c| c.state == utils::ContainerState::Running)
+            .map(|c| c.name.as_str())
+            .collect();
+
+        for (service, specs) in docker.profile_ports(&profile)? {
+            for (host_port, protocol) in specs.iter().filter_map(|s| parse_compose_port(s, lookup)) {
+                let owner = published
+                    .iter()
+                    .find(|p| p.host_port == host_port && p.protocol == protocol)
+                    .and_then(|p| p.owners.first())
+                    .map(|(name, _, _)| name.as_str());
+
+                let label = format!("{}/{} ({})", host_port, protocol, service);
+                match owner {
+                    Some(owner) if owner == service => println!("  ✓ {} already running", label),
+                    Some(owner) => {
+                        println!("  ✗ {} is published by {}", label, owner);
+                        conflicts += 1;
+                    }
+                    None if running.contains(&service.as_str()) => println!("  ✓ {}", label),
+                    None if host_port_free(host_port, &protocol) => println!("  ✓ {} free", label),
+                    None => {
+                        println!("  ✗ {} is in use by another process on this host", label);
+                        conflicts += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    if conflicts > 0 {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
 async fn handle_start(profile: Option<String>, service: Option<String>) -> Result<()> {
     let docker = DockerManager::new().await?;
 
     if let Some(profile) = profile {
         println!("Starting profile: {}", profile);
-        docker.start_profile(&profile).await?;
-        println!("Profile {} started", profile);
+        let warnings = core::backend::container_backend(docker).start_profile(&profile).await?;
+        for warning in &warnings {
+            eprintln!("⚠ {}", warning);
+        }
+        println!("{}", core::dry_run::tag(&format!("Profile {} started", profile)));
     } else if let Some(service) = service {
         println!("Starting service: {}", service);
         docker.start_service(&service).await?;
-        println!("Service {} started", service);
+        println!("{}", core::dry_run::tag(&format!("Service {} started", service)));
     } else {
         println!("Error: Specify either --profile or service name");
     }
@@ -133,17 +473,40 @@ async fn handle_start(profile: Option<String>, service: Option<String>) -> Resul
     Ok(())
 }
 
-async fn handle_stop(all: bool, service: Option<String>) -> Result<()> {
+async fn handle_stop(all: bool, yes: bool, service: Option<String>) -> Result<()> {
     let docker = DockerManager::new().await?;
 
     if all {
-        println!("Stopping all services...");
-        docker.stop_all().await?;
-        println!("All services stopped");
+        // Refuses (errors) when the project can't be resolved
+        let (project, containers) = docker
+            .stop_all_targets()
+            .await
+            .context("Refusing to stop all containers; stop services by name or pass --project")?;
+        if containers.is_empty() {
+            println!("No running containers in compose project {}", project);
+            return Ok(());
+        }
+
+        println!(
+            "{} running container(s) labelled {}={} will be stopped:",
+            containers.len(),
+            crate::core::docker::COMPOSE_PROJECT_LABEL,
+            project
+        );
+        for container in &containers {
+            println!("  {}", container.name);
+        }
+        if !yes && !prompt("Continue? [y/N]", "")?.eq_ignore_ascii_case("y") {
+            println!("Aborted");
+            return Ok(());
+        }
+
+        docker.stop_all(&containers).await?;
+        println!("{}", core::dry_run::tag(&format!("✓ Stopped {} container(s)", containers.len())));
     } else if let Some(service) = service {
         println!("Stopping service: {}", service);
         docker.stop_service(&service).await?;
-        println!("Service {} stopped", service);
+        println!("{}", core::dry_run::tag(&format!("Service {} stopped", service)));
     } else {
         println!("Error: Specify either --all or service name");
     }
@@ -155,13 +518,42 @@ async fn handle_restart(service: String) -> Result<()> {
     let docker = DockerManager::new().await?;
     println!("Restarting service: {}", service);
     docker.restart_service(&service).await?;
-    println!("Service {} restarted", service);
+    println!("{}", core::dry_run::tag(&format!("Service {} restarted", service)));
 
     Ok(())
 }
 
-async fn handle_logs(service: String, follow: bool, tail: usize) -> Result<()> {
+async fn handle_logs(
+    service: String,
+    follow: bool,
+    tail: Option<usize>,
+    since: Option<String>,
+    no_color: bool,
+) -> Result<()> {
+    use std::io::IsTerminal;
+
     let docker = DockerManager::new().await?;
+    // Keep colors on a terminal, plain text when redirected or NO_COLOR is set
+    let strip_ansi = no_color
+        || !std::io::stdout().is_terminal()
+        || std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+
+    if let Some(since) = since {
+        let since = crate::utils::parse_since(&since, chrono::Local::now())?;
+        // Cap the output so a wide window can't pull gigabytes of logs
+        let cap = tail.unwrap_or(10_000);
+        let (logs, note) = docker.get_logs_since(&service, since, Some(cap), strip_ansi).await?;
+        if let Some(note) = note {
+            eprintln!("{}", note);
+        }
+        print!("{}", logs);
+        if logs.lines().count() >= cap {
+            eprintln!("Output capped at {} lines; use -n to change", cap);
+        }
+        return Ok(());
+    }
+
+    let tail = tail.unwrap_or(100);
 
     if follow {
         println!("Following logs for {}... (Ctrl+C to stop)", service);
@@ -170,35 +562,47 @@ async fn handle_logs(service: String, follow: bool, tail: usize) -> Result<()> {
 
         // Basic implementation - show initial logs
         // For real-time following, use: docker compose logs -f <service>
-        let logs = docker.get_logs(&service, Some(tail)).await?;
+        let logs = docker.get_logs(&service, Some(tail), strip_ansi).await?;
         print!("{}", logs);
 
         println!("\nTip: Use 'docker compose logs -f {}' for continuous log streaming", service);
     } else {
-        let logs = docker.get_logs(&service, Some(tail)).await?;
+        let logs = docker.get_logs(&service, Some(tail), strip_ansi).await?;
         print!("{}", logs);
     }
 
     Ok(())
 }
 
+async fn handle_exec(service: String, cmd: Vec<String>) -> Result<()> {
+    let docker = DockerManager::new().await?;
+    let args: Vec<&str> = cmd.iter().map(|s| s.as_str()).collect();
+
+    let code = docker.exec_streaming(&service, &args).await?;
+    if code != 0 {
+        std::process::exit(code);
+    }
+
+    Ok(())
+}
+
 async fn handle_rpc(command: RpcCommands) -> Result<()> {
     match command {
         RpcCommands::Tokens { command } => {
             match command {
                 Some(TokenCommands::List) | None => {
-                    let config = ConfigManager::load(".env")?;
+                    let config = ConfigManager::load_from_project()?;
                     println!("RPC Access Tokens:\n");
                     for (i, token) in config.get_rpc_tokens() {
                         if let Some(t) = token {
-                            println!("TOKEN_{:02}: {}...{}", i, &t[..8], &t[t.len() - 8..]);
+                            println!("TOKEN_{:02}: {}", i, utils::mask_token(&t));
                         } else {
                             println!("TOKEN_{:02}: <not set>", i);
                         }
                     }
                 }
                 Some(TokenCommands::Generate) => {
-                    let mut config = ConfigManager::load(".env")?;
+                    let mut config = ConfigManager::load_from_project()?;
                     println!("Generating all RPC access tokens...\n");
 
                     let tokens = config.generate_all_rpc_tokens()?;
@@ -209,7 +613,7 @@ async fn handle_rpc(command: RpcCommands) -> Result<()> {
                     println!("You can view them with: igra-cli rpc tokens list");
                 }
                 Some(TokenCommands::Test { token_number }) => {
-                    let config = ConfigManager::load(".env")?;
+                    let config = ConfigManager::load_from_project()?;
                     let domain = config.get("IGRA_ORCHESTRA_DOMAIN")
                         .ok_or_else(|| anyhow::anyhow!("IGRA_ORCHESTRA_DOMAIN not set in .env"))?;
 
@@ -222,34 +626,15 @@ async fn handle_rpc(command: RpcCommands) -> Result<()> {
                         .ok_or_else(|| anyhow::anyhow!("Token {} is not set", token_number))?;
 
                     println!("Testing RPC token {}...\n", token_number);
-
-                    let tester = RpcTester::new();
-                    let (http_result, https_result) = tester.test_both_endpoints(domain, token).await?;
-
-                    println!("HTTP Test:");
-                    if http_result.success {
-                        println!("  ✓ Success ({}ms)", http_result.response_time_ms);
-                        if let Some(bn) = http_result.block_number {
-                            println!("  Block Number: {}", bn);
-                        }
-                    } else {
-                        println!("  ✗ Failed: {}", http_result.error.unwrap_or_default());
-                    }
-
-                    println!("\nHTTPS Test:");
-                    if https_result.success {
-                        println!("  ✓ Success ({}ms)", https_result.response_time_ms);
-                        if let Some(bn) = https_result.block_number {
-                            println!("  Block Number: {}", bn);
-                        }
-                    } else {
-                        println!("  ✗ Failed: {}", https_result.error.unwrap_or_default());
-                    }
+                    test_rpc_token(domain, token).await?;
+                }
+                Some(TokenCommands::Rotate { token_number, recreate, test }) => {
+                    handle_token_rotate(token_number, recreate, test).await?;
                 }
             }
         }
-        RpcCommands::TestEndpoint { token } => {
-            let config = ConfigManager::load(".env")?;
+        RpcCommands::TestEndpoint { token, timeout, retries, insecure } => {
+            let config = ConfigManager::load_from_project()?;
             let domain = config.get("IGRA_ORCHESTRA_DOMAIN")
                 .ok_or_else(|| anyhow::anyhow!("IGRA_ORCHESTRA_DOMAIN not set in .env"))?
                 .to_string();
@@ -269,8 +654,15 @@ async fn handle_rpc(command: RpcCommands) -> Result<()> {
             };
 
             println!("Testing RPC endpoints...\n");
+            if insecure {
+                println!("⚠ TLS certificate verification disabled\n");
+            }
 
-            let tester = RpcTester::new();
+            let tester = RpcTester::with_config(crate::core::rpc::RpcTestConfig {
+                timeout: std::time::Duration::from_secs(timeout),
+                retries,
+                verify_tls: !insecure,
+            });
             let (http_result, https_result) = tester.test_both_endpoints(&domain, &test_token).await?;
 
             println!("HTTP Endpoint (http://{}:8545):", domain);
@@ -298,6 +690,45 @@ async fn handle_rpc(command: RpcCommands) -> Result<()> {
     Ok(())
 }
 
+/// Balance table for every worker. A worker that can't be queried gets an
+/// error row; the command only fails if no wallet answered at all.
+async fn print_all_balances(wallet_manager: &WalletManager) -> Result<()> {
+    let currency = wallet_manager.network().currency_label();
+    let wallets = wallet_manager.list_wallets().await?;
+
+    println!("{:<10} {:<70} {:>20} {:>20}", "Worker", "Address", "Available", "Pending");
+    println!("{}", "-".repeat(123));
+
+    let mut queried = 0;
+    for wallet in &wallets {
+        let worker = format!("Worker {}", wallet.worker_id);
+
+        if !wallet.container_running {
+            println!("{:<10} ✗ kaswallet-{} container is not running", worker, wallet.worker_id);
+            continue;
+        }
+
+        match wallet_manager.get_balance_totals(wallet.worker_id).await {
+            Ok((available, pending)) => {
+                queried += 1;
+                println!(
+                    "{:<10} {:<70} {:>20} {:>20}",
+                    worker,
+                    wallet.address.as_deref().unwrap_or("N/A"),
+                    format!("{:.8} {}", available, currency),
+                    format!("{:.8} {}", pending, currency)
+                );
+            }
+            Err(e) => println!("{:<10} ✗ {}", worker, e),
+        }
+    }
+
+    if queried == 0 {
+        anyhow::bail!("None of the {} wallets could be queried", wallets.len());
+    }
+    Ok(())
+}
+
 async fn handle_wallet(command: WalletCommands) -> Result<()> {
     let wallet_manager = WalletManager::new()?;
 
@@ -305,33 +736,49 @@ async fn handle_wallet(command: WalletCommands) -> Result<()> {
         WalletCommands::List => {
             println!("IGRA Wallet Status\n");
             let wallets = wallet_manager.list_wallets().await?;
+            let network = wallet_manager.network();
+            let threshold = utils::AppConfig::low_balance_threshold(network);
 
             println!("{:<10} {:<12} {:<50} {:<15}", "Worker", "Status", "Address", "Balance");
             println!("{}", "-".repeat(90));
 
             for wallet in wallets {
-                let status = if wallet.container_running {
-                    "Running"
-                } else {
-                    "Stopped"
-                };
+                let status = wallet.status();
 
                 let address = wallet.address.as_deref().unwrap_or("N/A");
                 let balance = wallet
                     .balance
-                    .map(|b| format!("{:.8} KAS", b))
+                    .map(|b| format!("{:.8} {}", b, network.currency_label()))
                     .unwrap_or_else(|| "N/A".to_string());
+                let balance = if wallet.is_low_balance(threshold) {
+                    format!("{} ⚠ low", balance).red().to_string()
+                } else {
+                    balance
+                };
+                let runway = wallet
+                    .hours_until_empty()
+                    .map(|hours| format!("  empty in {}", core::wallet::format_runway(hours)))
+                    .unwrap_or_default();
 
                 println!(
-                    "{:<10} {:<12} {:<50} {:<15}",
+                    "{:<10} {:<12} {:<50} {:<15}{}",
                     format!("Worker {}", wallet.worker_id),
                     status,
                     address,
-                    balance
+                    balance,
+                    runway
                 );
             }
+
+            println!("\nLow balance threshold: {} {} (set [low_balance] in ~/.config/igra-cli/config.toml)", threshold, network.currency_label());
+        }
+        WalletCommands::Balance { all: true, .. } => {
+            print_all_balances(&wallet_manager).await?;
         }
-        WalletCommands::Balance { worker_id } => {
+        WalletCommands::Balance { worker_id: None, .. } => {
+            anyhow::bail!("Specify a worker ID or --all");
+        }
+        WalletCommands::Balance { worker_id: Some(worker_id), .. } => {
             println!("Fetching balance for wallet {}...\n", worker_id);
 
             match wallet_manager.get_balance(worker_id).await {
@@ -363,12 +810,179 @@ async fn handle_wallet(command: WalletCommands) -> Result<()> {
             println!("The wallet files will be stored in the container's data volume.");
             println!("Make sure to backup the wallet seed phrase!");
         }
+        WalletCommands::ExportAddresses { format, json, only_generated } => {
+            let format = if json { "json".to_string() } else { format };
+            let entries = wallet_manager.export_addresses().await;
+            let output = crate::core::wallet::format_address_export(&entries, &format, only_generated)?;
+
+            // Data on stdout, notes on stderr so the output can be piped
+            print!("{}", output);
+            let missing: Vec<String> = entries
+                .iter()
+                .filter(|(_, address)| address.is_none())
+                .map(|(worker_id, _)| worker_id.to_string())
+                .collect();
+            if !missing.is_empty() {
+                eprintln!(
+                    "Note: no address for worker(s) {} (wallet not generated or kaswallet container not running){}",
+                    missing.join(", "),
+                    if only_generated { " - skipped" } else { "" }
+                );
+            }
+        }
+        WalletCommands::History { worker, format, since, until } => {
+            if worker >= core::wallet::WORKER_COUNT {
+                anyhow::bail!("Unknown worker {} (0-{})", worker, core::wallet::WORKER_COUNT - 1);
+            }
+            let now = chrono::Local::now();
+            let since = since.map(|s| utils::parse_since(&s, now)).transpose()?;
+            let until = until.map(|u| utils::parse_since(&u, now)).transpose()?;
+
+            let entries = wallet_manager.transaction_history(worker, since, until).await?;
+            let output = core::wallet_history::format_history(&entries, worker, wallet_manager.network(), since, until, &format)?;
+
+            // Data on stdout, notes on stderr so the output can be redirected
+            print!("{}", output);
+            eprintln!(
+                "{} transactions. Only sends made through igra-cli and incoming payments seen while listing this wallet are recorded.",
+                entries.len()
+            );
+        }
+        WalletCommands::SendMany { worker_id, file, yes } => {
+            use crate::core::wallet::{parse_payout_csv, validate_payouts};
+            use crate::core::wallet_password::PasswordSource;
+
+            let contents = std::fs::read_to_string(&file)
+                .with_context(|| format!("Failed to read {}", file.display()))?;
+            let outputs = parse_payout_csv(&contents)?;
+            let total = validate_payouts(&outputs, wallet_manager.network())?;
+
+            println!("Payout from worker {} ({}):\n", worker_id, file.display());
+            for (address, amount) in &outputs {
+                println!("  {:<70} {:>20} KAS", address, utils::format_sompi(*amount));
+            }
+            println!("\n  {} payouts, total {} KAS plus fees", outputs.len(), utils::format_sompi(total));
+            if !yes && !prompt("Send? [y/N]", "")?.eq_ignore_ascii_case("y") {
+                println!("Aborted");
+                return Ok(());
+            }
+
+            let passwords = utils::AppConfig::wallet_passwords();
+            let prompted = if passwords.source_for(worker_id) == PasswordSource::Prompt {
+                Some(prompt_hidden(&format!("Wallet {} password", worker_id))?.into())
+            } else {
+                None
+            };
+            let config = ConfigManager::load_from_project()?;
+            let password = passwords.resolve(worker_id, &config, prompted)?;
+
+            let count = outputs.len();
+            let tx_ids = wallet_manager.send_many(worker_id, outputs, &password).await?;
+            if core::dry_run::enabled() {
+                println!("\n{}", core::dry_run::tag(&format!("✓ {} payouts checked; nothing was sent", count)));
+            } else if tx_ids.len() == 1 {
+                println!("\n✓ Sent in 1 transaction: {}", tx_ids[0]);
+            } else {
+                println!("\n✓ Sent {} payouts as {} transactions:", count, tx_ids.len());
+                for tx_id in &tx_ids {
+                    println!("  {}", tx_id);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Read a secret from the terminal without echoing it. The input stays in
+/// a `Zeroizing` buffer and is wiped once the caller drops it.
+fn prompt_hidden(label: &str) -> Result<zeroize::Zeroizing<String>> {
+    utils::terminal::read_hidden(label)
+}
+
+/// Run the HTTP and HTTPS checks for `token` and print the results
+async fn test_rpc_token(domain: &str, token: &str) -> Result<bool> {
+    let tester = RpcTester::new();
+    let (http_result, https_result) = tester.test_both_endpoints(domain, token).await?;
+
+    for (label, result) in [("HTTP Test:", &http_result), ("\nHTTPS Test:", &https_result)] {
+        println!("{}", label);
+        if result.success {
+            println!("  ✓ Success ({}ms)", result.response_time_ms);
+            if let Some(bn) = &result.block_number {
+                println!("  Block Number: {}", bn);
+            }
+        } else {
+            println!("  ✗ Failed: {}", result.error.clone().unwrap_or_default());
+        }
+    }
+
+    Ok(http_result.success || https_result.success)
+}
+
+/// Regenerate one RPC token. Only that slot changes, and .env is replaced
+/// atomically, so a crash can't lose the other tokens.
+async fn handle_token_rotate(token_number: usize, recreate: bool, test: bool) -> Result<()> {
+    let mut config = ConfigManager::load_from_project()?;
+    let key = format!("RPC_ACCESS_TOKEN_{}", token_number);
+    let token = config.rotate_rpc_token(token_number)?;
+    config.save()?;
+
+    println!("{}", core::dry_run::tag(&format!("✓ Rotated RPC token {}", token_number)));
+    println!("  {}={}", key, token);
+    println!();
+    println!("⚠ Clients still using the old token {} will be rejected once the services", token_number);
+    println!("  reading it are recreated. Update them with the value above.");
+
+    // Containers keep the token they were created with
+    let docker = DockerManager::new().await?;
+    let compose_services = docker.parse_compose_file()?;
+    let running: Vec<String> = docker
+        .list_containers()
+        .await?
+        .into_iter()
+        .filter(|c| c.state == utils::ContainerState::Running)
+        .map(|c| c.name)
+        .collect();
+    let affected: Vec<String> = core::docker::services_using_env(&compose_services, &key)
+        .into_iter()
+        .filter(|service| running.contains(service))
+        .collect();
+
+    if affected.is_empty() {
+        println!("\nNo running service reads {}; it applies when they next start.", key);
+    } else if recreate {
+        let mut profiles: Vec<String> = affected
+            .iter()
+            .filter_map(|s| compose_services.get(s))
+            .flat_map(|c| c.profiles.iter().cloned())
+            .collect();
+        profiles.sort();
+        profiles.dedup();
+
+        println!("\nRecreating {}...", affected.join(", "));
+        docker.recreate_services(&profiles, &affected).await?;
+        println!("✓ Recreated {}", affected.join(", "));
+    } else {
+        println!("\nThe new token is not active until these services are recreated:");
+        println!("  docker compose up -d --no-deps {}", affected.join(" "));
+    }
+
+    if test {
+        let domain = config
+            .get("IGRA_ORCHESTRA_DOMAIN")
+            .ok_or_else(|| anyhow::anyhow!("IGRA_ORCHESTRA_DOMAIN not set in .env"))?;
+        println!("\nTesting the new token...\n");
+        if !test_rpc_token(domain, &token).await? && !affected.is_empty() && !recreate {
+            println!("\n  (expected until the services above are recreated)");
+        }
     }
 
     Ok(())
 }
 
 async fn handle_backup(command: BackupCommands) -> Result<()> {
+    // Only prints instructions, so a dry run has nothing to skip
     println!("Backup functionality - Not yet implemented");
     println!("\nManual backup procedures:");
     println!("  1. Stop services: docker compose down");
@@ -398,22 +1012,27 @@ async fn handle_backup(command: BackupCommands) -> Result<()> {
 async fn handle_config(command: ConfigCommands) -> Result<()> {
     match command {
         ConfigCommands::View => {
-            let config = ConfigManager::load(".env")?;
+            let config = ConfigManager::load_from_project()?;
             println!("Configuration:\n");
             for key in config.keys() {
                 if let Some(value) = config.get(&key) {
-                    // Mask sensitive values
-                    let display_value = if key.contains("PASSWORD")
-                        || key.contains("SECRET")
-                        || key.contains("KEY")
-                    {
-                        "****"
-                    } else {
-                        value
+                    // Mask sensitive values (RPC tokens partially, as in `rpc tokens list`)
+                    let display_value = utils::mask_config_value(&key, value);
+                    let source = match config.source(&key) {
+                        Some(core::config::ConfigSource::Environment) => "  [env]",
+                        Some(core::config::ConfigSource::Secrets) => "  [secrets]",
+                        _ => "",
                     };
-                    println!("{}: {}", key, display_value);
+                    println!("{}: {}{}", key, display_value, source);
                 }
             }
+
+            if !config.overridden_keys().is_empty() {
+                println!("\n[env] = value overridden by process environment (editing .env won't change it)");
+            }
+            if let Err(e) = config.require_secrets() {
+                println!("\n⚠ {:#}", e);
+            }
         }
         ConfigCommands::Edit => {
             println!("Configuration editing is available in the TUI dashboard.");
@@ -422,8 +1041,8 @@ async fn handle_config(command: ConfigCommands) -> Result<()> {
             println!("  nano .env");
             println!("  vim .env");
         }
-        ConfigCommands::Validate => {
-            let config = ConfigManager::load(".env")?;
+        ConfigCommands::Validate { compose } => {
+            let config = ConfigManager::load_from_project()?;
             let errors = config.validate();
 
             if errors.is_empty() {
@@ -434,17 +1053,162 @@ async fn handle_config(command: ConfigCommands) -> Result<()> {
                     println!("  - {}", error);
                 }
             }
+
+            if compose {
+                let problems = DockerManager::new().await?.validate_compose().await?;
+                if problems.is_empty() {
+                    println!("✓ Compose file is valid");
+                } else {
+                    println!("✗ Compose file problems:");
+                    for problem in &problems {
+                        println!("  - {}", problem);
+                    }
+                    std::process::exit(1);
+                }
+            }
         }
         ConfigCommands::GenerateTokens => {
             println!("RPC token generation is available in the TUI dashboard.");
             println!("Run 'igra-cli' and navigate to Screen 4 (RPC Tokens), then press 'g'.");
         }
+        ConfigCommands::EncryptSecrets => handle_encrypt_secrets()?,
+        ConfigCommands::History { key, limit } => {
+            let config = ConfigManager::load_from_project()?;
+            let log = config.audit_log();
+            let entries: Vec<_> = core::config_audit::read(&log)
+                .into_iter()
+                .rev()
+                .filter(|e| key.is_none() || key.as_deref() == Some(e.key.as_str()))
+                .take(limit)
+                .collect();
+
+            if entries.is_empty() {
+                println!("No recorded config changes{}", key.map(|k| format!(" for {}", k)).unwrap_or_default());
+            } else {
+                println!("{:<20} {:<4} {:<14} {:<32} CHANGE", "TIME (UTC)", "FROM", "FILE", "KEY");
+                for entry in &entries {
+                    println!(
+                        "{:<20} {:<4} {:<14} {:<32} {}",
+                        entry.timestamp.format("%Y-%m-%d %H:%M:%S"),
+                        entry.source.as_str(),
+                        entry.file,
+                        entry.key,
+                        entry.describe()
+                    );
+                }
+            }
+            println!("\nLocal audit of edits made through igra-cli ({}); hand edits to .env aren't seen.", log.display());
+            println!("Keep the configuration in version control for a full history.");
+        }
+        ConfigCommands::Init { force } => {
+            let env_file = ConfigManager::env_file_in(&utils::get_project_root()?);
+            let env_file = env_file.as_path();
+            // Written only by the save below, once every prompt has passed
+            let mut config = ConfigManager::init_from_example(env_file, force)?;
+
+            let generated = config.generate_secrets();
+            let tokens = config.generate_all_rpc_tokens()?;
+
+            let default_domain = config.get("IGRA_ORCHESTRA_DOMAIN").unwrap_or("").to_string();
+            let domain = prompt("Domain", &default_domain)?;
+            if !domain.is_empty() {
+                if !utils::is_valid_domain(&domain) {
+                    anyhow::bail!("Invalid domain: {}", domain);
+                }
+                config.set("IGRA_ORCHESTRA_DOMAIN", domain);
+            }
+
+            let default_network = config.get("NETWORK").unwrap_or("testnet").to_string();
+            let network: core::Network = prompt("Network (testnet/mainnet)", &default_network)?.parse()?;
+            config.set("NETWORK", network.as_str());
+
+            config.save()?;
+
+            println!("\n✓ Created {} from .env.example", env_file.display());
+            println!("  {} RPC tokens generated", tokens.len());
+            if !generated.is_empty() {
+                println!("  {} passwords/secrets auto-generated:", generated.len());
+                for key in &generated {
+                    println!("    - {}", key);
+                }
+                println!("\n  These values were generated randomly and only exist in .env.");
+                println!("  Back up .env (or note the wallet passwords) before funding wallets.");
+            }
+        }
     }
 
     Ok(())
 }
 
-async fn handle_upgrade(check: bool, pull: bool, apply: bool) -> Result<()> {
+fn handle_encrypt_secrets() -> Result<()> {
+    let mut config = ConfigManager::load_from_project()?;
+    let moved = config.move_secrets_to_store()?;
+
+    if moved.is_empty() {
+        println!("✓ No plaintext secrets left in .env");
+    } else {
+        println!("✓ Moved {} values from .env into the secrets store:", moved.len());
+        for key in &moved {
+            println!("    - {}", key);
+        }
+        println!("\n  Back up the secrets key: without it these values can't be recovered.");
+    }
+    Ok(())
+}
+
+/// Ask for a value on stdin, falling back to `default` on empty input
+fn prompt(label: &str, default: &str) -> Result<String> {
+    use std::io::{self, Write};
+
+    if default.is_empty() {
+        print!("{}: ", label);
+    } else {
+        print!("{} [{}]: ", label, default);
+    }
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    let input = input.trim();
+
+    Ok(if input.is_empty() { default } else { input }.to_string())
+}
+
+async fn handle_self_update(check: bool, yes: bool) -> Result<()> {
+    use crate::core::updater;
+
+    let info = updater::check_for_updates().await?;
+    let Some(latest) = info.latest_version else {
+        anyhow::bail!("Could not reach GitHub to check for a new release");
+    };
+    if !info.update_available {
+        println!("✓ igra-cli {} is up to date", info.current_version);
+        return Ok(());
+    }
+
+    println!("Update available: {} → {}", info.current_version, latest);
+    if let Some(url) = &info.release_url {
+        println!("Release notes: {}", url);
+    }
+    if check {
+        println!("\nRun 'igra-cli self-update' to install it");
+        return Ok(());
+    }
+
+    if !yes && !prompt("Replace the installed binary? [y/N]", "")?.eq_ignore_ascii_case("y") {
+        println!("Aborted");
+        return Ok(());
+    }
+
+    println!("Downloading and verifying...");
+    let installed = updater::self_update().await?;
+    println!("✓ Updated igra-cli to {}", installed);
+    println!("Restart the TUI or the igra-web-ui service to use it");
+
+    Ok(())
+}
+
+async fn handle_upgrade(check: bool, pull: bool, apply: bool, profile: Option<String>, force_recreate: bool) -> Result<()> {
     if check {
         println!("Checking for updates...");
         println!("To check for image updates: docker compose pull");
@@ -453,16 +1217,11 @@ async fn handle_upgrade(check: bool, pull: bool, apply: bool) -> Result<()> {
         let docker = DockerManager::new().await?;
         println!("Pulling latest images...");
         docker.pull_images().await?;
-        println!("✓ Images updated");
+        println!("{}", core::dry_run::tag("✓ Images updated"));
         println!("\nRestart services to use new images:");
-        println!("  docker compose down && docker compose --profile <profile> up -d");
+        println!("  igra-cli upgrade --apply [--profile <profile>]");
     } else if apply {
-        println!("Applying upgrades...");
-        println!("This will pull images and restart services.");
-        println!("\nUse:");
-        println!("  igra-cli upgrade --pull");
-        println!("  docker compose down");
-        println!("  docker compose --profile <profile> up -d");
+        apply_upgrade(profile, force_recreate).await?;
     } else {
         println!("Specify --check, --pull, or --apply");
         println!("\nOr use the TUI dashboard (press 'u' for upgrade)");
@@ -471,6 +1230,89 @@ async fn handle_upgrade(check: bool, pull: bool, apply: bool) -> Result<()> {
     Ok(())
 }
 
+/// Pull images for the selected profiles, then recreate only the services whose image changed
+/// (or every service of the profiles with `force_recreate`)
+async fn apply_upgrade(profile: Option<String>, force_recreate: bool) -> Result<()> {
+    let docker = DockerManager::new().await?;
+
+    let profiles = match profile {
+        Some(p) => vec![p],
+        None => docker.get_active_profiles().await?,
+    };
+
+    if profiles.is_empty() {
+        anyhow::bail!("No active profiles found. Specify one with --profile <profile>");
+    }
+
+    println!("Applying upgrades for profile(s): {}\n", profiles.join(", "));
+
+    let services = docker.resolve_service_images(&profiles).await?;
+
+    // Remember which image each running container was created from
+    let mut current_ids = Vec::new();
+    for (service, image) in &services {
+        let container_id = docker.container_image_id(service).await?;
+        current_ids.push((service.clone(), image.clone(), container_id));
+    }
+
+    // Pull everything first - if any pull fails, nothing gets recreated
+    for (service, image) in &services {
+        println!("Pulling {} ({})...", service, image);
+        if let Err(e) = docker.pull_service(&profiles, service).await {
+            anyhow::bail!("Failed to pull image {} for service {}: {}", image, service, e);
+        }
+    }
+
+    if core::dry_run::enabled() {
+        println!("{}", core::dry_run::tag("Images were not pulled; services are compared with the images already present"));
+    }
+
+    if force_recreate {
+        println!();
+        for profile in &profiles {
+            println!("Recreating profile {}...", profile);
+            let recreated = docker.recreate_profile(profile).await?;
+            println!("{}", core::dry_run::tag(&format!("✓ Recreated: {}", recreated.join(", "))));
+        }
+        return Ok(());
+    }
+
+    let mut changed = Vec::new();
+    let mut unchanged = Vec::new();
+    for (service, image, container_id) in current_ids {
+        let Some(container_id) = container_id else {
+            // Not created yet - nothing to recreate
+            continue;
+        };
+
+        let latest_id = docker.local_image_id(&image).await?;
+        if latest_id.as_deref() != Some(container_id.as_str()) {
+            changed.push(service);
+        } else {
+            unchanged.push(service);
+        }
+    }
+
+    println!();
+    if changed.is_empty() {
+        println!("✓ All services are already up to date");
+        return Ok(());
+    }
+
+    println!("Recreating {} service(s) with new images...", changed.len());
+    docker.recreate_services(&profiles, &changed).await?;
+
+    println!("\n{}", core::dry_run::tag("✓ Updated and restarted:"));
+    for service in &changed {
+        println!("  - {}", service);
+    }
+    if !unchanged.is_empty() {
+        println!("\nUnchanged (not restarted): {}", unchanged.join(", "));
+    }
+
+    Ok(())
+}
+
 async fn handle_diagnostics(report: bool) -> Result<()> {
     if report {
         println!("Generating diagnostic report...\n");
@@ -484,6 +1326,24 @@ async fn handle_diagnostics(report: bool) -> Result<()> {
             println!("  {} - {:?} ({})", container.name, container.state, container.status);
         }
 
+        print_server_status();
+
+        println!("\nStorage:");
+        match core::storage::disk_full_projection() {
+            Ok(Some(projection)) => match projection.warning() {
+                Some(warning) if projection.is_critical() => println!("  ✗ {}", warning),
+                Some(warning) => println!("  ⚠ {}", warning),
+                None => println!(
+                    "  ✓ reth database {} growing {}/day; disk not projected to fill within {:.0} days",
+                    core::storage::format_bytes(projection.volume_bytes),
+                    core::storage::format_bytes(projection.volume_bytes_per_day.max(0.0) as u64),
+                    core::storage::DISK_FULL_WARN_DAYS
+                ),
+            },
+            Ok(None) => println!("  Not enough storage snapshots yet to project disk usage (open the TUI Storage screen to collect them)"),
+            Err(e) => println!("  ⚠ Could not project disk usage: {}", e),
+        }
+
         println!("\nFor detailed monitoring, use the TUI dashboard:");
         println!("  igra-cli");
         println!("\nFor logs:");
@@ -501,20 +1361,174 @@ async fn handle_diagnostics(report: bool) -> Result<()> {
     Ok(())
 }
 
-async fn handle_watch(filter: String, record: Option<String>, format: String) -> Result<()> {
+/// What's installed, for bug reports. Docker being down only marks the Docker
+/// side "unavailable" - the CLI build info always prints.
+async fn handle_diag_versions() {
+    const UNAVAILABLE: &str = "unavailable";
+
+    println!("igra-cli:");
+    println!("  Version:  {}", env!("CARGO_PKG_VERSION"));
+    println!("  Commit:   {}", cli::GIT_COMMIT);
+    println!("  Built:    {}", cli::BUILD_TIMESTAMP);
+    println!("  Rustc:    {}", cli::RUSTC_VERSION);
+    println!("  Target:   {}-{}", std::env::consts::OS, std::env::consts::ARCH);
+
+    println!("\nDocker:");
+    let engine = core::docker::engine_version().await;
+    println!("  Engine:   {}", engine.as_deref().unwrap_or(UNAVAILABLE));
+    println!("  Compose:  {}", core::docker::compose_version().as_deref().unwrap_or(UNAVAILABLE));
+
+    println!("\nImages:");
+    if engine.is_none() {
+        println!("  {}", UNAVAILABLE);
+        return;
+    }
+    let containers = match DockerManager::new().await {
+        Ok(docker) => docker.list_containers().await,
+        Err(e) => Err(e),
+    };
+    let containers = containers.map(|all| {
+        all.into_iter()
+            .filter(|c| c.state == utils::ContainerState::Running)
+            .collect::<Vec<_>>()
+    });
+    match containers {
+        Ok(containers) if containers.is_empty() => println!("  No running containers"),
+        Ok(mut containers) => {
+            containers.sort_by(|a, b| a.name.cmp(&b.name));
+            let width = containers.iter().map(|c| c.name.len()).max().unwrap_or(0);
+            for c in &containers {
+                println!("  {:width$}  {}", c.name, c.image, width = width);
+            }
+        }
+        Err(e) => println!("  {} ({})", UNAVAILABLE, e),
+    }
+}
+
+/// systemd unit written by `install-service`
+const WEB_UI_SERVICE: &str = "igra-web-ui";
+/// Journal lines of the web UI service included in `diag --report`
+const SERVER_JOURNAL_LINES: usize = 50;
+
+/// Web UI service state and recent journal for the diagnostics report.
+/// Only reports what it can find - never fails the diag run.
+fn print_server_status() {
+    use std::process::Command;
+
+    println!("\nWeb UI server ({}):", WEB_UI_SERVICE);
+
+    if !std::path::Path::new("/run/systemd/system").exists() {
+        println!("  Skipped (not a systemd system)");
+        return;
+    }
+
+    let unit_file = format!("/etc/systemd/system/{}.service", WEB_UI_SERVICE);
+    if !std::path::Path::new(&unit_file).exists() {
+        println!("  Not installed (see: igra-cli install-service)");
+        return;
+    }
+
+    // is-active exits non-zero for inactive/failed units but still prints the state
+    let state = Command::new("systemctl")
+        .args(["is-active", WEB_UI_SERVICE])
+        .output()
+        .ok()
+        .map(|out| String::from_utf8_lossy(&out.stdout).trim().to_string())
+        .filter(|s| !s.is_empty());
+    println!("  Status: {}", state.as_deref().unwrap_or("unknown (systemctl unavailable)"));
+
+    let lines = SERVER_JOURNAL_LINES.to_string();
+    let journal = Command::new("journalctl")
+        .args(["-u", WEB_UI_SERVICE, "-n", &lines, "--no-pager", "--quiet"])
+        .output()
+        .ok()
+        .filter(|out| out.status.success())
+        .map(|out| String::from_utf8_lossy(&out.stdout).into_owned())
+        .filter(|text| !text.trim().is_empty());
+
+    match journal {
+        Some(text) => {
+            println!("  Last {} journal lines:", SERVER_JOURNAL_LINES);
+            for line in text.lines() {
+                println!("    {}", line);
+            }
+        }
+        None => println!("  Server journal unavailable"),
+    }
+}
+
+async fn handle_watch(args: WatchArgs) -> Result<()> {
+    use crate::core::l2_monitor::{TransactionMonitor, RPC_URL, WS_URL};
     use screens::watch::run_watch_tui;
 
+    let WatchArgs { filter, record, format, record_max_mb, record_keep, abi, poll_ms, l1_interval_s, ws, alert_webhook } = args;
+
+    let mut intervals = utils::AppConfig::watch_intervals();
+    if let Some(ms) = poll_ms {
+        intervals.poll_ms = ms;
+    }
+    if let Some(secs) = l1_interval_s {
+        intervals.l1_interval_s = secs;
+    }
+    if ws {
+        intervals.mode = utils::WatchMode::Ws;
+    }
+    let intervals = intervals.validated();
+
+    // Load the ABI up front so a bad file is reported before the TUI starts
+    let mut selectors = crate::core::selectors::SelectorDb::builtin();
+    if let Some(ref path) = abi {
+        selectors = selectors.load_abi(path)?;
+        println!("Loaded method names from ABI: {}", path);
+    }
+
+    let notifier = alert_webhook
+        .as_deref()
+        .map(crate::core::alerts::WebhookNotifier::new)
+        .transpose()?;
+
     println!("Starting L2 transaction monitor...");
-    println!("Connecting to execution layer at http://localhost:9545");
+    println!("Connecting to execution layer at {}", RPC_URL);
+    match intervals.mode {
+        utils::WatchMode::Poll => {
+            println!("Polling every {} ms, L1 data every {} s", intervals.poll_ms, intervals.l1_interval_s)
+        }
+        utils::WatchMode::Ws => println!(
+            "Following new blocks at {} (polling every {} ms while unavailable), L1 data every {} s",
+            WS_URL, intervals.poll_ms, intervals.l1_interval_s
+        ),
+    }
+
+    // Probe the endpoint before switching the terminal into raw mode
+    let monitor = TransactionMonitor::new().await?;
+    if let Err(e) = monitor.get_block_number().await {
+        println!("\n✗ L2 endpoint {} is unreachable: {:#}", RPC_URL, e);
+        println!("\nMake sure the execution-layer service is running:");
+        println!("  igra-cli status");
+        println!("  igra-cli start --profile backend");
+        return Ok(());
+    }
+
+    let recorder = match record {
+        Some(path) => {
+            println!("Recording transactions to: {}", path);
+            println!("Format: {}", format);
+            if let Some(mb) = record_max_mb {
+                println!("Rotation: every {} MB, keeping {} file(s)", mb, record_keep);
+            }
+            let max_bytes = record_max_mb.map(|mb| mb * 1024 * 1024);
+            Some(crate::core::recorder::TransactionRecorder::create(path, &format, max_bytes, record_keep)?)
+        }
+        None => None,
+    };
 
-    if let Some(ref path) = record {
-        println!("Recording transactions to: {}", path);
-        println!("Format: {}", format);
+    if let Some(ref notifier) = notifier {
+        println!("Sending alerts to webhook at {}", notifier.host());
     }
 
     println!("\nPress 'q' to quit, '↑↓' to scroll, 'f' to toggle filter\n");
 
-    run_watch_tui(filter, record, format).await
+    run_watch_tui(filter, recorder, selectors, intervals, notifier).await
 }
 
 #[cfg(feature = "server")]
@@ -1,25 +1,165 @@
-/// Main dashboard screen
+//! Main dashboard screen
 
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Axis, Block, Borders, Cell, Chart, Dataset, GraphType, List, ListItem, Paragraph, Row, Table, Wrap},
+    widgets::{Axis, Block, Borders, Cell, Chart, Dataset, GraphType, List, ListItem, ListState, Paragraph, Row, Sparkline, Table, Wrap},
     Frame,
 };
 
-use crate::app::{Screen, SystemResources};
+use crate::app::{Screen, SendFeePreview, SystemResources};
 use crate::core::docker::{ContainerInfo, ContainerStats};
-use crate::core::wallet::WalletInfo;
+use crate::core::wallet::{ReceiveAddress, WalletInfo, WalletStatus};
+use crate::core::wallet_history::WalletTotals;
+use crate::utils::format_sompi;
 use crate::core::ssl::CertificateInfo;
-use crate::core::reth_metrics::RethMetrics;
+use crate::core::reth_metrics::{RethHistory, RethMetrics};
+use crate::core::health::{DependencyHealth, DependencyState, HealthResult};
 use crate::core::l2_monitor::{Statistics, TransactionInfo, TransactionType};
-use crate::screens::watch::TransactionFilter;
+use crate::core::network::Network;
+use crate::screens::watch::{grey_out_reorged, pending_tx_spans, reorg_count_span, status_span, tps_trend_span, type_breakdown_panel, FeedFilter, TransactionFeed, TYPE_BREAKDOWN_HEIGHT};
 use std::collections::HashMap;
+use std::sync::Arc;
 
 // Use ParsedLogLine and LogLevel from core module
 // All parsing logic moved to core::log_parser
 
+/// Everything `Dashboard::render` reads from the App for one frame.
+/// Built fresh each draw; all fields are borrows or small copies.
+#[derive(Clone, Copy)]
+pub struct RenderState<'a> {
+    pub current_screen: Screen,
+    pub services_view: crate::app::ServicesView,
+    pub config_section: crate::app::ConfigSection,
+    pub selected_index: usize,
+    pub status_message: Option<&'a str>,
+    pub edit_mode: bool,
+    pub edit_buffer: &'a str,
+    pub edit_cursor: usize,
+    pub detail_container: Option<&'a ContainerInfo>,
+    pub detail_logs: &'a [crate::core::ParsedLogLine],
+    pub detail_logs_live_mode: bool,
+    pub detail_logs_grouping: bool,
+    pub detail_logs_filter: Option<&'a crate::core::LogLevel>,
+    pub detail_logs_scroll_offset: usize,
+    pub system_resources: &'a SystemResources,
+    pub dependency_health: DependencyHealth,
+    pub show_help: bool,
+    pub show_health: bool,
+    pub health_rows: &'a [(String, Option<HealthResult>)],
+    pub health_tick: usize,
+    pub search_mode: bool,
+    pub search_buffer: &'a str,
+    pub filtered_indices: &'a [usize],
+    pub service_state_filter: Option<crate::app::ServiceStateFilter>,
+    /// Services rows left by the state filter; None shows them all
+    pub visible_services: Option<&'a [usize]>,
+    pub show_send_dialog: bool,
+    pub send_amount: &'a str,
+    pub send_address: &'a str,
+    pub send_input_field: usize,
+    pub send_use_wallet_selector: bool,
+    pub send_selected_wallet_index: usize,
+    pub send_source_address: &'a str,
+    pub send_all: bool,
+    pub send_hint: Option<&'a str>,
+    /// Live fee estimate; None until there is an amount
+    pub send_fee: Option<SendFeePreview>,
+    /// Length of the typed wallet password; None when the dialog has no password field
+    pub send_password_len: Option<usize>,
+    /// Services waiting for a recreate after config edits (Config footer)
+    pub restart_hint: Option<&'a str>,
+    pub palette: Option<&'a crate::screens::palette::CommandPalette>,
+    /// Recent palette results, oldest first
+    pub notifications: &'a std::collections::VecDeque<String>,
+    pub wallets: &'a [crate::core::wallet::WalletInfo],
+    pub reth_metrics: Option<&'a RethMetrics>,
+    pub reth_history: &'a RethHistory,
+    pub detail_wallet: Option<&'a WalletInfo>,
+    pub detail_wallet_addresses: &'a [(String, f64, f64)],
+    /// Receive address shown in the wallet detail view (None = not fetched)
+    pub detail_receive_address: Option<&'a ReceiveAddress>,
+    pub detail_wallet_utxos: &'a [crate::core::wallet::UtxoInfo],
+    pub detail_wallet_totals: Option<&'a WalletTotals>,
+    pub detail_wallet_scroll: usize,
+    pub show_tx_detail: bool,
+    pub show_address_qr: bool,
+    pub selected_tx_index: Option<usize>,
+    pub tx_search_buffer: &'a str,
+    pub filtered_tx_indices: &'a [usize],
+    pub watch_stats: Option<&'a Statistics>,
+    pub watch_feed: &'a TransactionFeed,
+    pub watch_unseen_new: usize,
+    pub watch_filter: &'a FeedFilter,
+    pub watch_show_breakdown: bool,
+    pub storage_analysis: Option<&'a crate::core::storage::StorageAnalysis>,
+    pub storage_scroll_offset: usize,
+    pub storage_chart_days: u32,
+    pub storage_show_details: bool,
+}
+
+/// Render a single-line text input, scrolled horizontally so the cursor stays visible
+fn edit_input_line(buffer: &str, cursor: usize, width: usize) -> Line<'static> {
+    let chars: Vec<char> = buffer.chars().collect();
+    let cursor = cursor.min(chars.len());
+    let width = width.max(2);
+
+    // Keep one column for the cursor block at the end of the text
+    let start = (cursor + 1).saturating_sub(width);
+    let end = (start + width).min(chars.len());
+
+    let before: String = chars[start..cursor].iter().collect();
+    let at: String = chars.get(cursor).map(|c| c.to_string()).unwrap_or_else(|| " ".to_string());
+    let after: String = if cursor < end { chars[cursor + 1..end].iter().collect() } else { String::new() };
+
+    let mut spans = Vec::new();
+    if start > 0 {
+        spans.push(Span::styled("…", Style::default().fg(Color::Gray)));
+    }
+    spans.push(Span::raw(before));
+    spans.push(Span::styled(at, Style::default().add_modifier(Modifier::REVERSED)));
+    spans.push(Span::raw(after));
+    Line::from(spans)
+}
+
+/// "Docker ● kaspad ● EL ●": green up, yellow running but not answering,
+/// red down, gray (hollow) not in the active profiles or not checked yet
+fn dependency_strip(health: &DependencyHealth) -> Vec<Span<'static>> {
+    let light = |name: &'static str, state: DependencyState| {
+        let (dot, color) = match state {
+            DependencyState::Up => ("●", Color::Green),
+            DependencyState::Degraded => ("●", Color::Yellow),
+            DependencyState::Down => ("●", Color::Red),
+            DependencyState::Inactive => ("○", Color::DarkGray),
+            DependencyState::Unknown => ("●", Color::DarkGray),
+        };
+        let label = if state == DependencyState::Inactive { Color::DarkGray } else { Color::Gray };
+        [Span::styled(format!("{} ", name), Style::default().fg(label)), Span::styled(dot, Style::default().fg(color))]
+    };
+    let mut spans = Vec::new();
+    for (i, (name, state)) in [("Docker", health.docker), ("kaspad", health.kaspad), ("EL", health.execution_layer)]
+        .into_iter()
+        .enumerate()
+    {
+        if i > 0 {
+            spans.push(Span::raw("  "));
+        }
+        spans.extend(light(name, state));
+    }
+    spans
+}
+
+/// Wallet list and detail view status color
+fn wallet_status_color(status: WalletStatus) -> Color {
+    match status {
+        WalletStatus::Running => Color::Green,
+        WalletStatus::Syncing => Color::Yellow,
+        WalletStatus::Locked => Color::Magenta,
+        WalletStatus::Stopped => Color::Red,
+    }
+}
+
 /// Format timestamp for compact display (HH:MM:SS)
 fn format_timestamp_compact(timestamp: &str) -> String {
     // Handle ISO 8601 format: "2025-10-21T10:28:44.123Z" -> "10:28:44"
@@ -91,10 +231,10 @@ fn group_logs_by_level_module<'a>(logs: Vec<&'a crate::core::ParsedLogLine>) ->
 
 pub struct Dashboard {
     pub title: String,
-    // Services data
-    containers: Vec<ContainerInfo>,
-    container_stats: HashMap<String, ContainerStats>,
-    image_versions: HashMap<String, crate::core::versions::ImageVersion>,
+    // Services data (snapshots shared with App; replaced, never mutated)
+    containers: Arc<Vec<ContainerInfo>>,
+    container_stats: Arc<HashMap<String, ContainerStats>>,
+    image_versions: Arc<HashMap<String, crate::core::versions::ImageVersion>>,
     profiles: Vec<String>,
     // Profiles data (active profiles)
     active_profiles: Vec<String>,
@@ -104,36 +244,49 @@ pub struct Dashboard {
     rpc_tokens: Vec<(usize, Option<String>)>,
     rpc_domain: String,
     // Config data
-    config_data: Vec<(String, String)>,
+    config_data: Arc<Vec<(String, String)>>,
+    config_env_overrides: Vec<String>, // Keys overridden by process environment
     // SSL data
     ssl_cert_info: Option<CertificateInfo>,
-    // Network name (testnet/mainnet) for proper currency labeling
-    network: String,
+    // Network for proper currency labeling
+    network: Network,
+    // Running worker wallets below this balance are flagged
+    low_balance_threshold: f64,
 }
 
 impl Dashboard {
     pub fn new() -> Self {
         Self {
             title: "IGRA Orchestra Dashboard".to_string(),
-            containers: Vec::new(),
-            container_stats: HashMap::new(),
-            image_versions: HashMap::new(),
+            containers: Arc::default(),
+            container_stats: Arc::default(),
+            image_versions: Arc::default(),
             profiles: Vec::new(),
             active_profiles: Vec::new(),
             wallets: Vec::new(),
             rpc_tokens: Vec::new(),
             rpc_domain: String::new(),
-            config_data: Vec::new(),
+            config_data: Arc::default(),
+            config_env_overrides: Vec::new(),
             ssl_cert_info: None,
-            network: "testnet".to_string(),
+            network: Network::default(),
+            low_balance_threshold: 0.0,
         }
     }
 
-    pub fn update_services(&mut self, containers: Vec<ContainerInfo>, profiles: Vec<String>, stats: HashMap<String, ContainerStats>, versions: HashMap<String, crate::core::versions::ImageVersion>) {
-        self.containers = containers;
-        self.profiles = profiles;
-        self.container_stats = stats;
-        self.image_versions = versions;
+    /// Share the App's snapshots (reference-count bumps, no deep copies)
+    pub fn update_services(
+        &mut self,
+        containers: &Arc<Vec<ContainerInfo>>,
+        profiles: &[String],
+        stats: &Arc<HashMap<String, ContainerStats>>,
+        versions: &Arc<HashMap<String, crate::core::versions::ImageVersion>>,
+    ) {
+        self.containers = Arc::clone(containers);
+        self.profiles.clear();
+        self.profiles.extend_from_slice(profiles);
+        self.container_stats = Arc::clone(stats);
+        self.image_versions = Arc::clone(versions);
     }
 
     pub fn update_profiles(&mut self, active_profiles: Vec<String>) {
@@ -149,22 +302,34 @@ impl Dashboard {
         self.rpc_domain = domain;
     }
 
-    pub fn update_config(&mut self, config: Vec<(String, String)>) {
+    pub fn update_config(&mut self, config: Arc<Vec<(String, String)>>, env_overrides: Vec<String>) {
         self.config_data = config;
+        self.config_env_overrides = env_overrides;
     }
 
     pub fn update_ssl(&mut self, cert_info: Option<CertificateInfo>) {
         self.ssl_cert_info = cert_info;
     }
 
-    pub fn update_network(&mut self, network: String) {
+    pub fn update_network(&mut self, network: Network) {
         self.network = network;
     }
 
-    pub fn render(&self, frame: &mut Frame, current_screen: Screen, services_view: crate::app::ServicesView, config_section: crate::app::ConfigSection, selected_index: usize, status_message: Option<&str>, edit_mode: bool, edit_buffer: &str, detail_container: Option<&ContainerInfo>, detail_logs: &[crate::core::ParsedLogLine], detail_logs_live_mode: bool, detail_logs_grouping: bool, detail_logs_filter: Option<&crate::core::LogLevel>, detail_logs_scroll_offset: usize, system_resources: &SystemResources, show_help: bool, search_mode: bool, search_buffer: &str, filtered_indices: &[usize], show_send_dialog: bool, send_amount: &str, send_address: &str, send_input_field: usize, send_use_wallet_selector: bool, send_selected_wallet_index: usize, send_source_address: &str, wallets: &[crate::core::wallet::WalletInfo], reth_metrics: Option<&RethMetrics>, detail_wallet: Option<&WalletInfo>, detail_wallet_addresses: &[(String, f64, f64)], detail_wallet_utxos: &[crate::core::wallet::UtxoInfo], detail_wallet_scroll: usize, show_tx_detail: bool, selected_tx_index: Option<usize>, tx_search_mode: bool, tx_search_buffer: &str, filtered_tx_indices: &[usize], watch_stats: Option<&Statistics>, watch_transactions: &[TransactionInfo], watch_filter: &TransactionFilter, watch_scroll_offset: usize, storage_analysis: Option<&crate::core::storage::StorageAnalysis>, storage_scroll_offset: usize, storage_chart_days: u32, storage_show_details: bool) {
+    pub fn update_low_balance_threshold(&mut self, threshold: f64) {
+        self.low_balance_threshold = threshold;
+    }
+
+    pub fn render(&self, frame: &mut Frame, state: &RenderState) {
+        let RenderState {
+            current_screen, services_view, selected_index, status_message, edit_mode, detail_container,
+            system_resources, dependency_health, show_help, show_health, health_rows, health_tick, search_mode, search_buffer,
+            filtered_indices, show_send_dialog, restart_hint, palette, notifications, detail_wallet,
+            detail_receive_address, detail_wallet_utxos, show_tx_detail, show_address_qr, selected_tx_index, ..
+        } = *state;
+
         // If showing wallet detail view, render that instead
         if let Some(wallet) = detail_wallet {
-            self.render_wallet_detail(frame, wallet, detail_wallet_addresses, detail_wallet_utxos, status_message, detail_wallet_scroll, tx_search_mode, tx_search_buffer, filtered_tx_indices, selected_tx_index);
+            self.render_wallet_detail(frame, wallet, state);
             // Show transaction detail modal if requested
             if show_tx_detail {
                 if let Some(tx_idx) = selected_tx_index {
@@ -173,6 +338,11 @@ impl Dashboard {
                     }
                 }
             }
+            // Show receive address QR code if requested
+            if show_address_qr {
+                let address = detail_receive_address.and_then(|r| r.address()).or(wallet.address.as_deref());
+                self.render_address_qr_modal(frame, address);
+            }
             if show_help {
                 self.render_help(frame, current_screen);
             }
@@ -181,7 +351,7 @@ impl Dashboard {
 
         // If showing service detail view, render that instead
         if let Some(container) = detail_container {
-            self.render_service_detail(frame, container, detail_logs, detail_logs_live_mode, detail_logs_grouping, detail_logs_filter, detail_logs_scroll_offset, status_message, reth_metrics);
+            self.render_service_detail(frame, container, state);
             // Still show help overlay if requested
             if show_help {
                 self.render_help(frame, current_screen);
@@ -245,6 +415,10 @@ impl Dashboard {
                     .fg(Color::Cyan)
                     .add_modifier(Modifier::BOLD),
             ),
+            Span::styled(
+                if crate::core::dry_run::enabled() { format!(" {}", crate::core::dry_run::TAG) } else { String::new() },
+                Style::default().fg(Color::Black).bg(Color::Yellow).add_modifier(Modifier::BOLD),
+            ),
             Span::raw("  "),
             Span::styled("CPU: ", Style::default().fg(Color::Gray)),
             Span::styled(
@@ -294,7 +468,7 @@ impl Dashboard {
         ]);
 
         // Line 3: Public IP
-        let ip_line = if let Some(ref ip) = system_resources.public_ip {
+        let mut ip_line = if let Some(ref ip) = system_resources.public_ip {
             Line::from(vec![
                 Span::styled("Public IP: ", Style::default().fg(Color::Gray)),
                 Span::styled(ip, Style::default().fg(Color::Cyan)),
@@ -305,6 +479,17 @@ impl Dashboard {
                 Span::styled("Fetching...", Style::default().fg(Color::DarkGray)),
             ])
         };
+        // Resources are sampled locally; say so when the containers live elsewhere
+        if let Some(ref host) = system_resources.remote_docker_host {
+            ip_line.spans.push(Span::raw(" | "));
+            ip_line.spans.push(Span::styled(
+                format!("⚠ Docker on {} - resources shown are this machine", host),
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+
+        ip_line.spans.push(Span::raw(" | "));
+        ip_line.spans.extend(dependency_strip(&dependency_health));
 
         let title = Paragraph::new(vec![title_line, os_line, ip_line])
             .alignment(Alignment::Center)
@@ -341,11 +526,11 @@ impl Dashboard {
 
         // Content area - render based on current screen
         match current_screen {
-            Screen::Services => self.render_services(frame, chunks[2], services_view, selected_index, filtered_indices),
+            Screen::Services => self.render_services(frame, chunks[2], state),
             Screen::Wallets => self.render_wallets(frame, chunks[2], selected_index, filtered_indices),
-            Screen::Watch => self.render_watch(frame, chunks[2], watch_stats, watch_transactions, watch_filter, watch_scroll_offset),
-            Screen::Config => self.render_config(frame, chunks[2], config_section, selected_index, edit_mode, edit_buffer, filtered_indices),
-            Screen::Storage => self.render_storage(frame, chunks[2], storage_analysis, storage_scroll_offset, storage_chart_days, storage_show_details),
+            Screen::Watch => self.render_watch(frame, chunks[2], state),
+            Screen::Config => self.render_config(frame, chunks[2], state),
+            Screen::Storage => self.render_storage(frame, chunks[2], state),
         }
 
         // Footer with status message or help
@@ -357,10 +542,14 @@ impl Dashboard {
             "Editing config value - Type to edit | [Enter] Save | [Esc] Cancel".to_string()
         } else {
             match current_screen {
-                Screen::Services => "[Tab] Switch view | [← →] Next screen | [↑↓] Select | [Enter] Details | [s]tart | [x]top | [R]estart | [q]uit".to_string(),
+                Screen::Services if services_view == crate::app::ServicesView::Profiles => "[Tab] Switch view | [← →] Next screen | [↑↓] Select | [Enter] Details | [s]tart | [x]top | [R]ecreate | [q]uit".to_string(),
+                Screen::Services => "[Tab] Switch view | [← →] Next screen | [↑↓] Select | [Enter] Details | [s]tart | [x]top | [R]estart | [S/U/A] Filter | [q]uit".to_string(),
                 Screen::Wallets => "[← →] Next screen | [↑↓] Select | [Enter] Info | [g]enerate | [t]ransfer | [/] Search | [r]efresh | [?] Help | [q]uit".to_string(),
-                Screen::Watch => "[← →] Next screen | [↑↓] Scroll | [f] Filter | [r] Record | [?] Help | [q]uit".to_string(),
-                Screen::Config => "[Tab] Switch tab | [← →] Next screen | [↑↓] Select | [e]dit | [g]enerate | [c]heck | [n]ew cert | [q]uit".to_string(),
+                Screen::Watch => "[← →] Next screen | [↑↓] Scroll | [Space] Pause | [f] Type | [v] Value | [e] Fee | [x] Clear filters | [b] By type | [?] Help | [q]uit".to_string(),
+                Screen::Config => match restart_hint {
+                    Some(hint) => format!("{} | [e]dit | [Tab] Switch tab | [q]uit", hint),
+                    None => "[Tab] Switch tab | [← →] Next screen | [↑↓] Select | [e]dit | [g]enerate | [c]heck | [n]ew cert | [q]uit".to_string(),
+                },
                 Screen::Storage => "[← →] Next screen | [r]efresh | [[/t/]] Chart | [D]etails | [p]rune | [I]mages | [?] Help | [q]uit".to_string(),
             }
         };
@@ -381,14 +570,22 @@ impl Dashboard {
             self.render_help(frame, current_screen);
         }
 
+        if show_health {
+            self.render_health(frame, health_rows, health_tick);
+        }
+
         // Show send transaction dialog if requested
         if show_send_dialog {
-            self.render_send_dialog(frame, send_amount, send_address, send_input_field, send_use_wallet_selector, send_selected_wallet_index, send_source_address, wallets);
+            self.render_send_dialog(frame, state);
+        }
+
+        if let Some(palette) = palette {
+            crate::screens::palette::render(frame, palette, notifications);
         }
     }
 
     /// Render a tab bar showing available sub-views with the active one highlighted
-    fn render_tab_bar(&self, tabs: &[(&str, bool)]) -> Paragraph {
+    fn render_tab_bar(&self, tabs: &[(&str, bool)]) -> Paragraph<'_> {
         let mut tab_spans = Vec::new();
 
         for (i, (tab_name, is_active)) in tabs.iter().enumerate() {
@@ -423,7 +620,7 @@ impl Dashboard {
             .alignment(Alignment::Left)
     }
 
-    fn render_services(&self, frame: &mut Frame, area: ratatui::layout::Rect, services_view: crate::app::ServicesView, selected_index: usize, filtered_indices: &[usize]) {
+    fn render_services(&self, frame: &mut Frame, area: ratatui::layout::Rect, state: &RenderState) {
         use crate::app::ServicesView;
 
         // Split area to add tab bar
@@ -434,20 +631,20 @@ impl Dashboard {
 
         // Render tab bar
         let tabs = [
-            ("Services", services_view == ServicesView::Services),
-            ("Profiles", services_view == ServicesView::Profiles),
+            ("Services", state.services_view == ServicesView::Services),
+            ("Profiles", state.services_view == ServicesView::Profiles),
         ];
         let tab_bar = self.render_tab_bar(&tabs);
         frame.render_widget(tab_bar, chunks[0]);
 
         // Delegate to appropriate view based on services_view
-        match services_view {
-            ServicesView::Services => self.render_services_table(frame, chunks[1], selected_index, filtered_indices),
-            ServicesView::Profiles => self.render_profiles(frame, chunks[1], selected_index),
+        match state.services_view {
+            ServicesView::Services => self.render_services_table(frame, chunks[1], state),
+            ServicesView::Profiles => self.render_profiles(frame, chunks[1], state.selected_index),
         }
     }
 
-    fn render_services_table(&self, frame: &mut Frame, area: ratatui::layout::Rect, selected_index: usize, filtered_indices: &[usize]) {
+    fn render_services_table(&self, frame: &mut Frame, area: ratatui::layout::Rect, state: &RenderState) {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([Constraint::Length(4), Constraint::Min(0)])
@@ -482,14 +679,28 @@ impl Dashboard {
 
         frame.render_widget(summary, chunks[0]);
 
-        // Services table
+        // Services table, titled with the active filters
+        let services_title = match (state.service_state_filter, state.visible_services) {
+            (Some(filter), Some(visible)) if !state.search_buffer.is_empty() => format!(
+                "Services (filter: {} + '{}', {} of {}) - [C] Clear",
+                filter.label(), state.search_buffer, visible.len(), total_services
+            ),
+            (Some(filter), Some(visible)) => format!(
+                "Services (filter: {}, {} of {}) - [C] Clear",
+                filter.label(), visible.len(), total_services
+            ),
+            _ if !state.search_buffer.is_empty() => format!("Services (search: '{}') - [C] Clear", state.search_buffer),
+            _ => "Services".to_string(),
+        };
         let header = Row::new(vec!["Service", "Status", "Metrics", "Ports", "CPU", "Memory", "Storage", "Image:Tag"])
             .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
             .bottom_margin(1);
 
-        let rows: Vec<Row> = self.containers.iter().enumerate().map(|(idx, container)| {
-            let is_selected = idx == selected_index;
-            let is_filtered = !filtered_indices.is_empty() && filtered_indices.contains(&idx);
+        let rows: Vec<Row> = self.containers.iter().enumerate()
+            .filter(|(idx, _)| state.visible_services.is_none_or(|visible| visible.contains(idx)))
+            .map(|(idx, container)| {
+            let is_selected = idx == state.selected_index;
+            let is_filtered = !state.filtered_indices.is_empty() && state.filtered_indices.contains(&idx);
 
             let status_color = if container.status.contains("Up") {
                 Color::Green
@@ -527,7 +738,7 @@ impl Dashboard {
                 // Memory with color coding
                 let mem_mb = stats.memory_usage / 1024 / 1024;
                 let mem_percent = if stats.memory_limit > 0 {
-                    (stats.memory_usage as f64 / stats.memory_limit as f64 * 100.0)
+                    stats.memory_usage as f64 / stats.memory_limit as f64 * 100.0
                 } else {
                     0.0
                 };
@@ -582,7 +793,7 @@ impl Dashboard {
             // Extract image name and tag
             let image_str = container.image
                 .split('/')
-                .last()
+                .next_back()
                 .unwrap_or(&container.image);
 
             let (image_name, current_tag) = if let Some((name, tag)) = image_str.split_once(':') {
@@ -667,7 +878,7 @@ impl Dashboard {
             ],
         )
         .header(header)
-        .block(Block::default().borders(Borders::ALL).title("Services"));
+        .block(Block::default().borders(Borders::ALL).title(services_title));
 
         frame.render_widget(table, chunks[1]);
     }
@@ -688,8 +899,32 @@ impl Dashboard {
         }
     }
 
+    /// "Disk I/O: read 1.2 GB (3.4 MB/s)  write ..." for the service detail title
+    fn block_io_line(stats: Option<&ContainerStats>) -> Line<'static> {
+        let total = |bytes: Option<u64>| bytes.map(Self::format_bytes).unwrap_or_else(|| "N/A".to_string());
+        let rate = |rate: Option<f64>| rate.map(|r| format!(" ({}/s)", Self::format_bytes(r as u64))).unwrap_or_default();
+
+        let Some(stats) = stats else {
+            return Line::from(Span::styled("Disk I/O: N/A", Style::default().fg(Color::Gray)));
+        };
+
+        Line::from(vec![
+            Span::styled("Disk I/O: ", Style::default().fg(Color::Gray)),
+            Span::raw("read "),
+            Span::styled(
+                format!("{}{}", total(stats.block_read), rate(stats.block_read_rate)),
+                Style::default().fg(Color::Cyan),
+            ),
+            Span::raw("  write "),
+            Span::styled(
+                format!("{}{}", total(stats.block_write), rate(stats.block_write_rate)),
+                Style::default().fg(Color::Yellow),
+            ),
+        ])
+    }
+
     fn render_profiles(&self, frame: &mut Frame, area: ratatui::layout::Rect, selected_index: usize) {
-        let all_profiles = vec![
+        let all_profiles = [
             ("kaspad", "Kaspad consensus node"),
             ("backend", "Execution layer + Block builder + Viaduct"),
             ("frontend-w1", "1 RPC provider + 1 Wallet worker"),
@@ -742,7 +977,7 @@ impl Dashboard {
 
     fn render_wallets(&self, frame: &mut Frame, area: ratatui::layout::Rect, selected_index: usize, filtered_indices: &[usize]) {
         // Determine currency label based on network
-        let currency = if self.network == "mainnet" { "KAS" } else { "TKAS" };
+        let currency = self.network.currency_label();
 
         let header = Row::new(vec!["Worker", "Status", "Address", "Balance", "Fees Spent"])
             .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
@@ -752,17 +987,22 @@ impl Dashboard {
             let is_selected = idx == selected_index;
             let is_filtered = !filtered_indices.is_empty() && filtered_indices.contains(&idx);
 
-            let status = if wallet.container_running {
-                ("Running", Color::Green)
-            } else {
-                ("Stopped", Color::Red)
-            };
+            let wallet_status = wallet.status();
+            let status = (wallet_status.as_str(), wallet_status_color(wallet_status));
 
             let address = wallet.address.as_deref().unwrap_or("Not generated");
-            let balance = wallet
-                .balance
-                .map(|b| format!("{:.8} {}", b, currency))
-                .unwrap_or_else(|| "N/A".to_string());
+            let low = wallet.is_low_balance(self.low_balance_threshold);
+            let balance = match (wallet.balance, wallet_status) {
+                (_, WalletStatus::Syncing) => "syncing…".to_string(),
+                (_, WalletStatus::Locked) => "locked".to_string(),
+                (Some(b), _) => format!("{:.8} {}{}", b, currency, if low { " ⚠ low" } else { "" }),
+                (None, _) => "N/A".to_string(),
+            };
+            let balance_style = if low {
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
 
             // Format fees spent with color coding
             let (fees_text, fees_color) = if let Some(fees) = wallet.fees_spent {
@@ -782,7 +1022,7 @@ impl Dashboard {
                 Cell::from(format!("Worker {}", wallet.worker_id)),
                 Cell::from(Span::styled(status.0, Style::default().fg(status.1))),
                 Cell::from(address),
-                Cell::from(balance),
+                Cell::from(Span::styled(balance, balance_style)),
                 Cell::from(Span::styled(fees_text, Style::default().fg(fees_color))),
             ]);
 
@@ -801,27 +1041,32 @@ impl Dashboard {
                 Constraint::Length(10),
                 Constraint::Length(10),
                 Constraint::Min(30),
-                Constraint::Length(20),
+                Constraint::Length(26),
                 Constraint::Length(20),
             ],
         )
         .header(header)
-        .block(Block::default().borders(Borders::ALL).title("Wallets"));
+        .block(Block::default().borders(Borders::ALL).title(format!(
+            "Wallets (low balance < {} {})",
+            self.low_balance_threshold, currency
+        )));
 
         frame.render_widget(table, area);
     }
 
-    fn render_watch(&self, frame: &mut Frame, area: ratatui::layout::Rect, stats: Option<&Statistics>, transactions: &[TransactionInfo], filter: &TransactionFilter, _scroll_offset: usize) {
+    fn render_watch(&self, frame: &mut Frame, area: ratatui::layout::Rect, state: &RenderState) {
+        let show_breakdown = state.watch_show_breakdown && state.watch_stats.is_some();
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(5),  // Stats header
+                Constraint::Length(6),  // Stats header
+                Constraint::Length(if show_breakdown { TYPE_BREAKDOWN_HEIGHT } else { 0 }),  // Per-type breakdown
                 Constraint::Min(0),     // Transaction list
             ])
             .split(area);
 
         // Statistics header
-        if let Some(stats) = stats {
+        if let Some(stats) = state.watch_stats {
             let stats_text = vec![
                 Line::from(vec![
                     Span::styled("Block: ", Style::default().fg(Color::Gray)),
@@ -835,10 +1080,23 @@ impl Dashboard {
                         format!("{:.2}", stats.tps()),
                         Style::default().fg(Color::Yellow),
                     ),
+                    tps_trend_span(stats.tps_trend()),
                     Span::raw("  │  "),
                     Span::styled("Uptime: ", Style::default().fg(Color::Gray)),
                     Span::styled(stats.uptime(), Style::default().fg(Color::Blue)),
                 ]),
+                Line::from(vec![
+                    Span::styled("Block time: ", Style::default().fg(Color::Gray)),
+                    Span::styled(stats.block_time_display(), Style::default().fg(Color::Cyan)),
+                    Span::raw("  │  "),
+                    Span::styled("Last minute: ", Style::default().fg(Color::Gray)),
+                    Span::styled(stats.block_rate_display(), Style::default().fg(Color::Cyan)),
+                    if stats.is_stalled() {
+                        Span::styled("  ⚠ No new blocks", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))
+                    } else {
+                        Span::raw("")
+                    },
+                ]),
                 Line::from(vec![
                     Span::styled("Total: ", Style::default().fg(Color::Gray)),
                     Span::styled(
@@ -857,7 +1115,9 @@ impl Dashboard {
                         format!("{}", stats.failed_transactions),
                         Style::default().fg(Color::Red),
                     ),
-                ]),
+                    Span::raw("  │  "),
+                    reorg_count_span(stats.reorgs),
+                ].into_iter().chain(pending_tx_spans(stats.pending_transactions)).collect::<Vec<_>>()),
                 Line::from(vec![
                     Span::styled("L2 Fees: ", Style::default().fg(Color::Gray)),
                     Span::styled(
@@ -878,6 +1138,10 @@ impl Dashboard {
                 .block(Block::default().borders(Borders::ALL).title("Statistics"))
                 .wrap(Wrap { trim: false });
             frame.render_widget(stats_block, chunks[0]);
+
+            if show_breakdown {
+                frame.render_widget(type_breakdown_panel(stats), chunks[1]);
+            }
         } else {
             let connecting = Paragraph::new("Connecting to L2 node...")
                 .block(Block::default().borders(Borders::ALL).title("Statistics"))
@@ -886,9 +1150,10 @@ impl Dashboard {
         }
 
         // Transaction list
-        let filtered_txs: Vec<&TransactionInfo> = transactions
+        let filtered_txs: Vec<&TransactionInfo> = state.watch_feed
+            .transactions()
             .iter()
-            .filter(|tx| filter.matches(&tx.tx_type))
+            .filter(|tx| state.watch_filter.matches(tx))
             .collect();
 
         let items: Vec<ListItem> = filtered_txs
@@ -901,9 +1166,6 @@ impl Dashboard {
                     TransactionType::Unknown => Color::Gray,
                 };
 
-                let status_symbol = if tx.status { "✓" } else { "✗" };
-                let status_color = if tx.status { Color::Green } else { Color::Red };
-
                 let mut lines = vec![
                     Line::from(vec![
                         Span::styled(
@@ -911,11 +1173,11 @@ impl Dashboard {
                             Style::default().fg(Color::Gray),
                         ),
                         Span::styled(
-                            format!("{}", tx.tx_type),
+                            tx.type_label(),
                             Style::default().fg(type_color).add_modifier(Modifier::BOLD),
                         ),
                         Span::raw("  "),
-                        Span::styled(status_symbol, Style::default().fg(status_color)),
+                        status_span(tx),
                     ]),
                     Line::from(vec![
                         Span::raw("  Hash: "),
@@ -946,21 +1208,22 @@ impl Dashboard {
                     ]));
                 }
 
-                ListItem::new(lines)
+                ListItem::new(grey_out_reorged(tx, lines))
             })
             .collect();
 
-        let filter_text = match filter {
-            TransactionFilter::All => "All",
-            TransactionFilter::Transfer => "Transfer",
-            TransactionFilter::Contract => "Contract",
-            TransactionFilter::Entry => "Entry",
-        };
+        let mut title = vec![Span::raw(format!("Transactions - Filter: {} ({} shown)", state.watch_filter.describe(), filtered_txs.len()))];
+        title.extend(crate::screens::watch::feed_indicator(state.watch_feed, state.watch_unseen_new));
 
         let list = List::new(items)
-            .block(Block::default().borders(Borders::ALL).title(format!("Transactions - Filter: {} ({} shown)", filter_text, filtered_txs.len())));
+            .block(Block::default().borders(Borders::ALL).title(Line::from(title)))
+            .highlight_style(Style::default().bg(Color::DarkGray));
 
-        frame.render_widget(list, chunks[1]);
+        let mut list_state = ListState::default();
+        if !filtered_txs.is_empty() {
+            list_state.select(Some(state.selected_index.min(filtered_txs.len() - 1)));
+        }
+        frame.render_stateful_widget(list, chunks[2], &mut list_state);
     }
 
     fn render_rpc_tokens(&self, frame: &mut Frame, area: ratatui::layout::Rect, selected_index: usize) {
@@ -1017,7 +1280,7 @@ impl Dashboard {
         frame.render_widget(table, chunks[1]);
     }
 
-    fn render_config(&self, frame: &mut Frame, area: ratatui::layout::Rect, config_section: crate::app::ConfigSection, selected_index: usize, edit_mode: bool, edit_buffer: &str, filtered_indices: &[usize]) {
+    fn render_config(&self, frame: &mut Frame, area: ratatui::layout::Rect, state: &RenderState) {
         use crate::app::ConfigSection;
 
         // Split area to add tab bar
@@ -1028,57 +1291,64 @@ impl Dashboard {
 
         // Render tab bar
         let tabs = [
-            ("Environment", config_section == ConfigSection::Environment),
-            ("RPC Tokens", config_section == ConfigSection::RpcTokens),
-            ("SSL Certificates", config_section == ConfigSection::SslCerts),
+            ("Environment", state.config_section == ConfigSection::Environment),
+            ("RPC Tokens", state.config_section == ConfigSection::RpcTokens),
+            ("SSL Certificates", state.config_section == ConfigSection::SslCerts),
         ];
         let tab_bar = self.render_tab_bar(&tabs);
         frame.render_widget(tab_bar, chunks[0]);
 
         // Delegate to appropriate tab based on config_section
-        match config_section {
-            ConfigSection::Environment => self.render_config_environment(frame, chunks[1], selected_index, edit_mode, edit_buffer, filtered_indices),
-            ConfigSection::RpcTokens => self.render_rpc_tokens(frame, chunks[1], selected_index),
+        match state.config_section {
+            ConfigSection::Environment => self.render_config_environment(frame, chunks[1], state),
+            ConfigSection::RpcTokens => self.render_rpc_tokens(frame, chunks[1], state.selected_index),
             ConfigSection::SslCerts => self.render_ssl(frame, chunks[1]),
         }
     }
 
-    fn render_config_environment(&self, frame: &mut Frame, area: ratatui::layout::Rect, selected_index: usize, edit_mode: bool, edit_buffer: &str, filtered_indices: &[usize]) {
+    fn render_config_environment(&self, frame: &mut Frame, area: ratatui::layout::Rect, state: &RenderState) {
         let header = Row::new(vec!["Key", "Value"])
             .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
             .bottom_margin(1);
 
+        // Value column width: total minus key column, borders and column spacing
+        let value_width = (area.width as usize).saturating_sub(35 + 3).max(10);
+        let mut editing_secret = false;
+
         let rows: Vec<Row> = self.config_data.iter().take(30).enumerate().map(|(idx, (key, value))| {
-            let is_selected = idx == selected_index;
-            let is_filtered = !filtered_indices.is_empty() && filtered_indices.contains(&idx);
+            let is_selected = idx == state.selected_index;
+            let is_filtered = !state.filtered_indices.is_empty() && state.filtered_indices.contains(&idx);
 
             // If this is the selected row and we're in edit mode, show the edit buffer
-            let display_value = if is_selected && edit_mode {
-                edit_buffer.to_string()
-            } else {
+            let value_cell = if is_selected && state.edit_mode {
+                editing_secret = crate::utils::is_sensitive_key(key);
+                Cell::from(edit_input_line(state.edit_buffer, state.edit_cursor, value_width))
+            } else if crate::utils::is_sensitive_key(key) {
                 // Mask sensitive values
-                if key.contains("PASSWORD")
-                    || key.contains("SECRET")
-                    || key.contains("KEY")
-                    || key.contains("TOKEN")
-                {
-                    "****".to_string()
-                } else {
-                    if value.len() > 50 {
-                        format!("{}...", &value[..47])
-                    } else {
-                        value.clone()
-                    }
-                }
+                Cell::from("****")
+            } else if value.chars().count() > 50 {
+                Cell::from(format!("{}...", value.chars().take(47).collect::<String>()))
+            } else {
+                Cell::from(value.clone())
+            };
+
+            // Flag keys whose effective value comes from the process environment
+            let key_cell = if self.config_env_overrides.contains(key) {
+                Cell::from(Line::from(vec![
+                    Span::raw(key.clone()),
+                    Span::styled(" [env]", Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)),
+                ]))
+            } else {
+                Cell::from(key.clone())
             };
 
             let row = Row::new(vec![
-                Cell::from(key.clone()),
-                Cell::from(display_value),
+                key_cell,
+                value_cell,
             ]);
 
             if is_selected {
-                if edit_mode {
+                if state.edit_mode {
                     row.style(Style::default().bg(Color::Blue).fg(Color::White).add_modifier(Modifier::BOLD))
                 } else {
                     row.style(Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD))
@@ -1090,6 +1360,14 @@ impl Dashboard {
             }
         }).collect();
 
+        let mut title = format!("Configuration (showing 30 of {} keys)", self.config_data.len());
+        if !self.config_env_overrides.is_empty() {
+            title.push_str(&format!(" - {} overridden by environment [env]", self.config_env_overrides.len()));
+        }
+        if editing_secret {
+            title.push_str(" - ⚠ EDITING SECRET");
+        }
+
         let table = Table::new(
             rows,
             [
@@ -1098,7 +1376,7 @@ impl Dashboard {
             ],
         )
         .header(header)
-        .block(Block::default().borders(Borders::ALL).title(format!("Configuration (showing 30 of {} keys)", self.config_data.len())));
+        .block(Block::default().borders(Borders::ALL).title(title));
 
         frame.render_widget(table, area);
     }
@@ -1131,6 +1409,13 @@ impl Dashboard {
                 Span::styled(status_text, Style::default().fg(status_color).add_modifier(Modifier::BOLD)),
             ]));
 
+            if let Some(ref issuer) = cert_info.issuer {
+                text.push(Line::from(vec![
+                    Span::styled("Issuer: ", Style::default().fg(Color::White)),
+                    Span::styled(issuer.clone(), Style::default().fg(Color::Gray)),
+                ]));
+            }
+
             // Valid from
             if let Some(from) = cert_info.valid_from {
                 text.push(Line::from(vec![
@@ -1190,11 +1475,11 @@ impl Dashboard {
         frame.render_widget(paragraph, area);
     }
 
-    fn render_storage(&self, frame: &mut Frame, area: Rect, storage_analysis: Option<&crate::core::storage::StorageAnalysis>, scroll_offset: usize, chart_days: u32, show_details: bool) {
+    fn render_storage(&self, frame: &mut Frame, area: Rect, state: &RenderState) {
         use crate::core::storage::format_bytes;
 
         // If no analysis yet, show loading
-        let Some(analysis) = storage_analysis else {
+        let Some(analysis) = state.storage_analysis else {
             let paragraph = Paragraph::new("Analyzing storage... (requires sudo access for volume sizes)")
                 .block(Block::default().borders(Borders::ALL).title("Storage Analysis"))
                 .alignment(ratatui::layout::Alignment::Center);
@@ -1203,7 +1488,7 @@ impl Dashboard {
         };
 
         // Split into sections - add chart space
-        let chunks = if show_details {
+        let chunks = if state.storage_show_details {
             // Details table mode: replace volumes with details
             Layout::default()
                 .direction(Direction::Vertical)
@@ -1212,7 +1497,7 @@ impl Dashboard {
                     Constraint::Length(6),  // Docker summary
                     Constraint::Length(12), // Historical chart
                     Constraint::Min(10),    // Details table (instead of volumes)
-                    Constraint::Length(4),  // Growth rate
+                    Constraint::Length(5),  // Growth rate
                 ])
                 .split(area)
         } else {
@@ -1224,7 +1509,7 @@ impl Dashboard {
                     Constraint::Length(6),  // Docker summary
                     Constraint::Length(12), // Historical chart
                     Constraint::Min(10),    // Volumes list
-                    Constraint::Length(4),  // Growth rate
+                    Constraint::Length(5),  // Growth rate
                 ])
                 .split(area)
         };
@@ -1282,11 +1567,11 @@ impl Dashboard {
         frame.render_widget(docker_paragraph, chunks[1]);
 
         // Historical Storage Chart
-        self.render_storage_chart(frame, chunks[2], chart_days);
+        self.render_storage_chart(frame, chunks[2], state.storage_chart_days);
 
         // Volumes List or Details Table
-        if show_details {
-            self.render_storage_details(frame, chunks[3], chart_days);
+        if state.storage_show_details {
+            self.render_storage_details(frame, chunks[3], state.storage_chart_days);
         } else {
             // Volumes List - show all volumes with scrolling
         let mut volumes_text = vec![
@@ -1304,7 +1589,7 @@ impl Dashboard {
         let total_volumes = analysis.docker_volumes.len();
 
         // Apply scrolling - show window of volumes
-        let start_idx = scroll_offset.min(total_volumes.saturating_sub(1));
+        let start_idx = state.storage_scroll_offset.min(total_volumes.saturating_sub(1));
         let end_idx = (start_idx + visible_height).min(total_volumes);
 
         for vol in &analysis.docker_volumes[start_idx..end_idx] {
@@ -1386,6 +1671,22 @@ impl Dashboard {
                 ]));
             }
 
+            if let Some(projection) = &analysis.disk_projection {
+                lines.push(match projection.warning() {
+                    Some(warning) => {
+                        let color = if projection.is_critical() { Color::Red } else { Color::Yellow };
+                        Line::from(Span::styled(format!("⚠ {}", warning), Style::default().fg(color).add_modifier(Modifier::BOLD)))
+                    }
+                    None => Line::from(vec![
+                        Span::raw("reth database: "),
+                        Span::styled(
+                            format!("{} (+{}/day)", format_bytes(projection.volume_bytes), format_bytes(projection.volume_bytes_per_day.max(0.0) as u64)),
+                            Style::default().fg(Color::Cyan),
+                        ),
+                    ]),
+                });
+            }
+
             lines
         } else {
             vec![Line::from("Collecting data... (growth prediction available after 2+ days)")]
@@ -1556,9 +1857,9 @@ impl Dashboard {
                 let prev = &measurements[prev_idx];
                 let diff = m.total_used_bytes as i64 - prev.total_used_bytes as i64;
                 if diff > 0 {
-                    format!("↑{}", format_bytes(diff.abs() as u64))
+                    format!("↑{}", format_bytes(diff.unsigned_abs()))
                 } else if diff < 0 {
-                    format!("↓{}", format_bytes(diff.abs() as u64))
+                    format!("↓{}", format_bytes(diff.unsigned_abs()))
                 } else {
                     "→".to_string()
                 }
@@ -1599,22 +1900,71 @@ impl Dashboard {
         frame.render_widget(table, area);
     }
 
-    fn render_service_detail(&self, frame: &mut Frame, container: &ContainerInfo, logs: &[crate::core::ParsedLogLine], live_mode: bool, grouping_enabled: bool, log_filter: Option<&crate::core::LogLevel>, scroll_offset: usize, status_message: Option<&str>, reth_metrics: Option<&RethMetrics>) {
+    fn render_reth_trends(&self, frame: &mut Frame, area: Rect, history: &RethHistory) {
+        let block = Block::default().borders(Borders::ALL).title("Trends");
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        if history.is_empty() {
+            frame.render_widget(Paragraph::new("Sampling...").style(Style::default().fg(Color::Gray)), inner);
+            return;
+        }
+
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),
+                Constraint::Length(2),
+                Constraint::Length(1),
+                Constraint::Min(0),
+            ])
+            .split(inner);
+
+        // Keep only the samples that fit so the newest stays visible
+        let fit = |data: Vec<u64>| -> Vec<u64> {
+            let skip = data.len().saturating_sub(inner.width as usize);
+            data.into_iter().skip(skip).collect()
+        };
+        let tps = fit(history.tps_centi());
+        let blocks = fit(history.blocks());
+
+        let tps_now = tps.last().map(|v| *v as f64 / 100.0).unwrap_or(0.0);
+        frame.render_widget(
+            Paragraph::new(format!("TPS  now {:.2}", tps_now)).style(Style::default().fg(Color::Gray)),
+            rows[0],
+        );
+        frame.render_widget(
+            Sparkline::default().data(&tps).style(Style::default().fg(Color::Green)),
+            rows[1],
+        );
+
+        frame.render_widget(
+            Paragraph::new(format!("Blocks/sample  now {}", blocks.last().copied().unwrap_or(0)))
+                .style(Style::default().fg(Color::Gray)),
+            rows[2],
+        );
+        frame.render_widget(
+            Sparkline::default().data(&blocks).style(Style::default().fg(Color::Cyan)),
+            rows[3],
+        );
+    }
+
+    fn render_service_detail(&self, frame: &mut Frame, container: &ContainerInfo, state: &RenderState) {
         // Determine if we should show metrics section
-        let show_metrics = container.name == "execution-layer" && reth_metrics.is_some();
+        let show_metrics = container.name == "execution-layer" && state.reth_metrics.is_some();
 
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints(if show_metrics {
                 vec![
-                    Constraint::Length(3),   // Title (single line)
+                    Constraint::Length(4),   // Title (status line + disk I/O)
                     Constraint::Length(9),   // Metrics section (7 rows + 2 for borders)
                     Constraint::Min(0),      // Logs
                     Constraint::Length(3),   // Footer
                 ]
             } else {
                 vec![
-                    Constraint::Length(3),   // Title (single line)
+                    Constraint::Length(4),   // Title (status line + disk I/O)
                     Constraint::Min(0),      // Logs
                     Constraint::Length(3),   // Footer
                 ]
@@ -1628,13 +1978,6 @@ impl Dashboard {
             Color::Red
         };
 
-        let health_color = match container.health.as_deref() {
-            Some("healthy") => Color::Green,
-            Some("unhealthy") => Color::Red,
-            Some("starting") => Color::Yellow,
-            _ => Color::Gray,
-        };
-
         let title_text = vec![
             Line::from(vec![
                 Span::styled(&container.name, Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
@@ -1642,7 +1985,10 @@ impl Dashboard {
                 Span::styled(&container.status, Style::default().fg(status_color).add_modifier(Modifier::BOLD)),
                 Span::raw("  |  "),
                 Span::styled(&container.image, Style::default().fg(Color::Gray)),
+                Span::raw("  |  "),
+                Span::styled(container.start_summary(chrono::Utc::now()), Style::default().fg(Color::Gray)),
             ]),
+            Self::block_io_line(self.container_stats.get(&container.name)),
         ];
 
         let title = Paragraph::new(title_text)
@@ -1653,7 +1999,7 @@ impl Dashboard {
 
         // Metrics section (execution-layer only)
         let logs_chunk_idx = if show_metrics {
-            if let Some(metrics) = reth_metrics {
+            if let Some(metrics) = state.reth_metrics {
                 let mut metrics_lines = Vec::new();
 
                 // Helper function to format numbers with commas
@@ -1684,7 +2030,7 @@ impl Dashboard {
                     Span::raw("  "),
                     Span::styled("Headers: ", Style::default().fg(Color::Gray)),
                     Span::styled(
-                        metrics.headers_synced.map(|v| format_number(v)).unwrap_or("N/A".to_string()),
+                        metrics.headers_synced.map(format_number).unwrap_or("N/A".to_string()),
                         Style::default().fg(Color::White)
                     ),
                 ]));
@@ -1699,7 +2045,7 @@ impl Dashboard {
                     Span::raw("  "),
                     Span::styled("Checkpoint: ", Style::default().fg(Color::Gray)),
                     Span::styled(
-                        metrics.sync_checkpoint.map(|v| format_number(v)).unwrap_or("N/A".to_string()),
+                        metrics.sync_checkpoint.map(format_number).unwrap_or("N/A".to_string()),
                         Style::default().fg(Color::White)
                     ),
                 ]));
@@ -1720,7 +2066,7 @@ impl Dashboard {
                 metrics_lines.push(Line::from(vec![
                     Span::styled("Transactions: ", Style::default().fg(Color::Gray)),
                     Span::styled(
-                        metrics.transactions_total.map(|v| format_number(v)).unwrap_or("N/A".to_string()),
+                        metrics.transactions_total.map(format_number).unwrap_or("N/A".to_string()),
                         Style::default().fg(Color::Cyan)
                     ),
                     Span::raw("  "),
@@ -1747,7 +2093,7 @@ impl Dashboard {
                     Span::raw("  "),
                     Span::styled("Inserted: ", Style::default().fg(Color::Gray)),
                     Span::styled(
-                        metrics.transactions_inserted.map(|v| format_number(v)).unwrap_or("N/A".to_string()),
+                        metrics.transactions_inserted.map(format_number).unwrap_or("N/A".to_string()),
                         Style::default().fg(Color::White)
                     ),
                 ]));
@@ -1799,7 +2145,13 @@ impl Dashboard {
                 let metrics_widget = Paragraph::new(metrics_lines)
                     .block(Block::default().borders(Borders::ALL).title("Reth Metrics"));
 
-                frame.render_widget(metrics_widget, chunks[1]);
+                // Trends on the right: one sample per SAMPLE_INTERVAL, newest at the right edge
+                let metrics_area = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints([Constraint::Min(60), Constraint::Percentage(35)])
+                    .split(chunks[1]);
+                frame.render_widget(metrics_widget, metrics_area[0]);
+                self.render_reth_trends(frame, metrics_area[1], state.reth_history);
             }
             2  // Logs are in chunk 2 when metrics are shown (title=0, metrics=1, logs=2)
         } else {
@@ -1807,26 +2159,23 @@ impl Dashboard {
         };
 
         // Logs section - Filter and apply scroll windowing to pre-parsed logs
-        let filtered_logs: Vec<&crate::core::ParsedLogLine> = logs.iter()
-            .filter(|log| {
-                if let Some(filter_level) = log_filter {
-                    &log.level == filter_level
-                } else {
-                    true
-                }
-            })
+        // Applied to the whole buffer each frame, so live-appended lines and
+        // filter changes are reflected immediately
+        let filtered_logs: Vec<&crate::core::ParsedLogLine> = state.detail_logs.iter()
+            .filter(|log| log.matches_filter(state.detail_logs_filter))
             .collect();
 
         // Apply scroll offset windowing
         // scroll_offset = 0 means show latest (bottom), higher values scroll back in time
         let total_logs = filtered_logs.len();
+        let scroll_offset = state.detail_logs_scroll_offset.min(total_logs);
         let end_idx = total_logs.saturating_sub(scroll_offset);
         let start_idx = 0; // Show all logs from beginning to end_idx
         let windowed_logs: Vec<&crate::core::ParsedLogLine> = filtered_logs[start_idx..end_idx].to_vec();
 
         let mut log_lines: Vec<Line> = Vec::new();
 
-        if grouping_enabled {
+        if state.detail_logs_grouping {
             // Grouped mode: group by level and module
             let groups = group_logs_by_level_module(windowed_logs.clone());
 
@@ -1881,15 +2230,19 @@ impl Dashboard {
         }
 
         // Build title with indicators
-        let mode_text = if grouping_enabled { " grouped" } else { " chronological" };
-        let live_indicator = if live_mode { "[LIVE]" } else { "" };
+        let mode_text = if state.detail_logs_grouping { " grouped" } else { " chronological" };
+        let live_indicator = if state.detail_logs_live_mode { "[LIVE]" } else { "" };
         let scroll_indicator = if scroll_offset > 0 {
             format!(" ↑{}", scroll_offset)
         } else {
             String::new()
         };
-        let title = format!("Logs {}{} {}/{}{}",
-            live_indicator, scroll_indicator, end_idx, total_logs, mode_text);
+        let filter_indicator = match state.detail_logs_filter {
+            Some(_) => format!(" [{}]", crate::core::LogLevel::filter_label(state.detail_logs_filter)),
+            None => String::new(),
+        };
+        let title = format!("Logs {}{}{} {}/{}{}",
+            live_indicator, filter_indicator, scroll_indicator, end_idx, total_logs, mode_text);
 
         // Calculate scroll position for Paragraph widget
         // When scroll_offset = 0, show the bottom (latest logs)
@@ -1915,7 +2268,7 @@ impl Dashboard {
 
         // Footer
         let footer_chunk_idx = logs_chunk_idx + 1;
-        let footer_text = if let Some(status) = status_message {
+        let footer_text = if let Some(status) = state.status_message {
             status.to_string()
         } else {
             "[s]tart | [x]top | [R]estart | [r]efresh logs | [Esc/q] back to list".to_string()
@@ -1923,7 +2276,7 @@ impl Dashboard {
 
         let footer = Paragraph::new(footer_text)
             .alignment(Alignment::Center)
-            .style(if status_message.is_some() {
+            .style(if state.status_message.is_some() {
                 Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
             } else {
                 Style::default()
@@ -1959,12 +2312,15 @@ impl Dashboard {
             Line::from(""),
             Line::from(Span::styled("Global Navigation:", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))),
             Line::from("  [1-5]          Jump to screen (1=Services, 2=Wallets, 3=Watch, 4=Logs, 5=Config)"),
+            Line::from("  [:]            Command palette (e.g. \"restart viaduct\", \"renew ssl\")"),
+            Line::from("  [E]            Export this screen's table to CSV/JSON (working directory)"),
             Line::from("  [← →]          Next/Previous screen (1↔2↔3↔4↔5)"),
             Line::from("  [Tab]          Switch sub-views (Services/Config screens only)"),
             Line::from("  [↑ ↓]          Select items / scroll lists"),
             Line::from(""),
             Line::from(Span::styled("Global Commands:", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))),
             Line::from("  [?] / [F1]     Toggle this help screen"),
+            Line::from("  [H]            Run health checks (RPC, wRPC, wallet daemons)"),
             Line::from("  [q]            Quit application"),
             Line::from("  [r]            Refresh data"),
             Line::from("  [u]            Upgrade (pull latest Docker images)"),
@@ -1983,6 +2339,8 @@ impl Dashboard {
                 help_text.push(Line::from("  [x]            Stop selected service"));
                 help_text.push(Line::from("  [R]            Restart selected service"));
                 help_text.push(Line::from("  [/]            Search/filter services"));
+                help_text.push(Line::from("  [S] [U] [A]    Show only stopped / unhealthy / running (again to turn off)"));
+                help_text.push(Line::from("  [C]            Clear state filter and search"));
                 help_text.push(Line::from(""));
                 help_text.push(Line::from(Span::styled("Profiles View:", Style::default().fg(Color::Cyan))));
                 help_text.push(Line::from("  [Enter]        Start selected profile (service group)"));
@@ -1998,13 +2356,17 @@ impl Dashboard {
                 help_text.push(Line::from("  [Enter]        View transaction details (modal)"));
                 help_text.push(Line::from("  [/]            Search transactions (by TxID, address, amount)"));
                 help_text.push(Line::from("  [↑↓] / [j/k]   Scroll through transactions"));
+                help_text.push(Line::from("  [Q]            Show receive address as QR code"));
+                help_text.push(Line::from("  [N]            Generate a new receive address (old ones stay valid)"));
                 help_text.push(Line::from("  [Esc] / [q]    Return to wallet list"));
-                help_text.push(Line::from("  [r]            Refresh wallet data"));
+                help_text.push(Line::from("  [r]            Refresh this wallet only"));
             }
             Screen::Watch => {
                 help_text.push(Line::from(Span::styled("Watch Screen:", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))));
                 help_text.push(Line::from("  [↑↓] / [j/k]   Scroll through transactions"));
                 help_text.push(Line::from("  [f]            Filter transactions (All/Transfer/Contract/Entry)"));
+                help_text.push(Line::from("  [b]            Show/hide counts and fees per transaction type"));
+                help_text.push(Line::from("  [Space]        Pause/resume the live feed (recording continues)"));
                 help_text.push(Line::from("  [r]            Start/stop recording transactions"));
                 help_text.push(Line::from("  [c]            Clear transaction history"));
             }
@@ -2014,9 +2376,11 @@ impl Dashboard {
                 help_text.push(Line::from(""));
                 help_text.push(Line::from(Span::styled("Environment Tab:", Style::default().fg(Color::Cyan))));
                 help_text.push(Line::from("  [e]            Edit selected config value"));
+                help_text.push(Line::from("  [← →/Home/End] Move cursor (when editing)"));
                 help_text.push(Line::from("  [Enter]        Save changes (when editing)"));
                 help_text.push(Line::from("  [Esc]          Cancel edit (when editing)"));
                 help_text.push(Line::from("  [/]            Search/filter config keys"));
+                help_text.push(Line::from("  [R]            Recreate services waiting on saved edits"));
                 help_text.push(Line::from(""));
                 help_text.push(Line::from(Span::styled("RPC Tokens Tab:", Style::default().fg(Color::Cyan))));
                 help_text.push(Line::from("  [Enter]        Test RPC endpoint"));
@@ -2070,13 +2434,71 @@ impl Dashboard {
         frame.render_widget(help_widget, popup_area);
     }
 
-    fn render_send_dialog(&self, frame: &mut Frame, amount: &str, address: &str, active_field: usize, use_wallet_selector: bool, selected_wallet_index: usize, source_address: &str, wallets: &[crate::core::wallet::WalletInfo]) {
+    /// Functional health checks overlay; unresolved checks show a spinner
+    fn render_health(&self, frame: &mut Frame, rows: &[(String, Option<HealthResult>)], tick: usize) {
+        const SPINNER: [char; 4] = ['|', '/', '-', '\\'];
+
+        let area = frame.size();
+        let popup_width = area.width.min(90);
+        let popup_height = (rows.len() as u16 + 5).min(area.height);
+        let popup_area = Rect {
+            x: area.width.saturating_sub(popup_width) / 2,
+            y: area.height.saturating_sub(popup_height) / 2,
+            width: popup_width,
+            height: popup_height,
+        };
+
+        let table_rows: Vec<Row> = rows
+            .iter()
+            .map(|(name, result)| match result {
+                Some(r) => {
+                    let (symbol, color) = if r.healthy { ("✓", Color::Green) } else { ("✗", Color::Red) };
+                    Row::new(vec![
+                        Cell::from(Span::styled(symbol, Style::default().fg(color))),
+                        Cell::from(name.clone()),
+                        Cell::from(format!("{} ms", r.latency.as_millis())),
+                        Cell::from(Span::styled(r.detail.clone(), Style::default().fg(color))),
+                    ])
+                }
+                None => Row::new(vec![
+                    Cell::from(Span::styled(SPINNER[tick % SPINNER.len()].to_string(), Style::default().fg(Color::Yellow))),
+                    Cell::from(name.clone()),
+                    Cell::from(""),
+                    Cell::from(Span::styled("checking…", Style::default().fg(Color::Yellow))),
+                ]),
+            })
+            .collect();
+
+        let title = if rows.is_empty() {
+            " Health - no running services to check - [H]/[Esc] close "
+        } else {
+            " Health - [H]/[Esc] close (reopen to re-run) "
+        };
+
+        let table = Table::new(
+            table_rows,
+            [Constraint::Length(2), Constraint::Length(26), Constraint::Length(9), Constraint::Min(10)],
+        )
+        .header(Row::new(vec!["", "Check", "Latency", "Result"]).style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan))
+                .title(Span::styled(title, Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))),
+        );
+
+        frame.render_widget(Block::default().style(Style::default().bg(Color::Black)), popup_area);
+        frame.render_widget(table, popup_area);
+    }
+
+    fn render_send_dialog(&self, frame: &mut Frame, state: &RenderState) {
         use ratatui::layout::Rect;
 
         // Create centered dialog
         let area = frame.size();
         let dialog_width = area.width.min(80);
-        let dialog_height = if use_wallet_selector { 20 } else { 15 };
+        let password_rows = if state.send_password_len.is_some() { 2 } else { 0 };
+        let dialog_height = password_rows + if state.send_use_wallet_selector { 21 } else { 16 };
         let dialog_x = (area.width.saturating_sub(dialog_width)) / 2;
         let dialog_y = (area.height.saturating_sub(dialog_height)) / 2;
 
@@ -2093,13 +2515,13 @@ impl Dashboard {
         frame.render_widget(clear_block, dialog_area);
 
         // Create dialog content
-        let amount_field_style = if active_field == 0 {
+        let amount_field_style = if state.send_input_field == 0 {
             Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
         } else {
             Style::default().fg(Color::White)
         };
 
-        let address_field_style = if active_field == 1 {
+        let address_field_style = if state.send_input_field == 1 {
             Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
         } else {
             Style::default().fg(Color::White)
@@ -2113,14 +2535,14 @@ impl Dashboard {
             Line::from(""),
             Line::from(vec![
                 Span::styled("From: ", Style::default().fg(Color::Gray)),
-                Span::styled(source_address, Style::default().fg(Color::Green)),
+                Span::styled(state.send_source_address, Style::default().fg(Color::Green)),
             ]),
             Line::from(""),
             Line::from(vec![
                 Span::styled("Amount (KAS): ", amount_field_style),
                 Span::styled(
-                    if amount.is_empty() { "_" } else { amount },
-                    if active_field == 0 {
+                    if state.send_all { "ALL (entire balance)" } else if state.send_amount.is_empty() { "_" } else { state.send_amount },
+                    if state.send_input_field == 0 {
                         Style::default().fg(Color::Yellow).add_modifier(Modifier::UNDERLINED)
                     } else {
                         Style::default().fg(Color::Gray)
@@ -2131,7 +2553,7 @@ impl Dashboard {
         ];
 
         // Add destination section
-        if use_wallet_selector {
+        if state.send_use_wallet_selector {
             dialog_text.push(Line::from(vec![
                 Span::styled("Destination: ", address_field_style),
                 Span::styled("[Wallet Selector - Use ↑↓ to select]", Style::default().fg(Color::Cyan).add_modifier(Modifier::ITALIC)),
@@ -2139,8 +2561,8 @@ impl Dashboard {
             dialog_text.push(Line::from(""));
 
             // Show wallet list (limit to 5 visible)
-            for (idx, wallet) in wallets.iter().enumerate().take(8) {
-                let is_selected = idx == selected_wallet_index && active_field == 1;
+            for (idx, wallet) in state.wallets.iter().enumerate().take(8) {
+                let is_selected = idx == state.send_selected_wallet_index && state.send_input_field == 1;
                 let wallet_text = format!(
                     "  {} Worker {} - {}",
                     if is_selected { "►" } else { " " },
@@ -2162,8 +2584,29 @@ impl Dashboard {
             ]));
             dialog_text.push(Line::from(vec![
                 Span::styled(
-                    if address.is_empty() { "_" } else { address },
-                    if active_field == 1 {
+                    if state.send_address.is_empty() { "_" } else { state.send_address },
+                    if state.send_input_field == 1 {
+                        Style::default().fg(Color::Yellow).add_modifier(Modifier::UNDERLINED)
+                    } else {
+                        Style::default().fg(Color::Gray)
+                    }
+                ),
+            ]));
+        }
+
+        // Only the length is known here - the password itself never reaches the renderer
+        if let Some(len) = state.send_password_len {
+            let password_style = if state.send_input_field == 2 {
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            dialog_text.push(Line::from(""));
+            dialog_text.push(Line::from(vec![
+                Span::styled("Wallet Password: ", password_style),
+                Span::styled(
+                    if len == 0 { "_".to_string() } else { "*".repeat(len) },
+                    if state.send_input_field == 2 {
                         Style::default().fg(Color::Yellow).add_modifier(Modifier::UNDERLINED)
                     } else {
                         Style::default().fg(Color::Gray)
@@ -2173,9 +2616,22 @@ impl Dashboard {
         }
 
         dialog_text.push(Line::from(""));
-        dialog_text.push(Line::from(""));
+        dialog_text.push(match state.send_fee {
+            Some(SendFeePreview::Ready(estimate)) => Line::from(Span::styled(estimate.summary(), Style::default().fg(Color::White))),
+            Some(SendFeePreview::Estimating) => Line::from(Span::styled("Estimating fee...", Style::default().fg(Color::DarkGray))),
+            Some(SendFeePreview::Unavailable) => {
+                Line::from(Span::styled("fee estimate unavailable", Style::default().fg(Color::DarkGray)))
+            }
+            // `hint` says the funds fall short
+            Some(SendFeePreview::Insufficient) | None => Line::from(""),
+        });
+        if let Some(hint) = state.send_hint {
+            dialog_text.push(Line::from(Span::styled(hint, Style::default().fg(Color::Yellow))));
+        } else {
+            dialog_text.push(Line::from(""));
+        }
         dialog_text.push(Line::from(Span::styled(
-            "Tab: Switch | s: Toggle wallet/manual | Enter: Send | Esc: Cancel",
+            "Tab: Switch | a: Send all | s: Toggle wallet/manual | Enter: Send | Esc: Cancel",
             Style::default().fg(Color::Gray).add_modifier(Modifier::ITALIC),
         )));
 
@@ -2191,15 +2647,15 @@ impl Dashboard {
         frame.render_widget(dialog_widget, dialog_area);
     }
 
-    fn render_wallet_detail(&self, frame: &mut Frame, wallet: &WalletInfo, addresses: &[(String, f64, f64)], utxos: &[crate::core::wallet::UtxoInfo], status_message: Option<&str>, scroll_offset: usize, tx_search_mode: bool, tx_search_buffer: &str, filtered_tx_indices: &[usize], selected_tx_index: Option<usize>) {
-        let currency = if self.network == "mainnet" { "KAS" } else { "TKAS" };
+    fn render_wallet_detail(&self, frame: &mut Frame, wallet: &WalletInfo, state: &RenderState) {
+        let currency = self.network.currency_label();
 
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
                 Constraint::Length(3),   // Title
-                Constraint::Length(8),   // Wallet info section
-                Constraint::Length((addresses.len().min(5) + 3) as u16),  // Address balances (limited height)
+                Constraint::Length(10),  // Wallet info section
+                Constraint::Length((state.detail_wallet_addresses.len().min(5) + 3) as u16),  // Address balances (limited height)
                 Constraint::Min(0),      // Activity (UTXOs)
                 Constraint::Length(3),   // Footer
             ])
@@ -2218,11 +2674,7 @@ impl Dashboard {
         frame.render_widget(title, chunks[0]);
 
         // Wallet info section
-        let status_color = if wallet.container_running {
-            Color::Green
-        } else {
-            Color::Red
-        };
+        let status_color = wallet_status_color(wallet.status());
 
         let balance_text = wallet
             .balance
@@ -2247,21 +2699,90 @@ impl Dashboard {
             .map(|b| format!("{:.8} {}", b, currency))
             .unwrap_or_else(|| "N/A".to_string());
 
-        let info_text = vec![
+        // Ledger totals, labelled with how far back the ledger goes
+        let totals_line = match state.detail_wallet_totals {
+            Some(totals) if totals.since.is_some() => {
+                let net = totals.net_sompi();
+                let out_prefix = if totals.unknown_sends > 0 { "≥ " } else { "" };
+                Line::from(vec![
+                    Span::styled("Total In: ", Style::default().fg(Color::White)),
+                    Span::styled(format!("{} {}", format_sompi(totals.total_in_sompi), currency), Style::default().fg(Color::Green)),
+                    Span::styled("  Out (igra-cli sends): ", Style::default().fg(Color::White)),
+                    Span::styled(format!("{}{} {}", out_prefix, format_sompi(totals.total_out_sompi), currency), Style::default().fg(Color::Yellow)),
+                    Span::styled("  Net: ", Style::default().fg(Color::White)),
+                    Span::styled(
+                        format!("{}{} {}", if net < 0 { "-" } else { "+" }, format_sompi(net.unsigned_abs()), currency),
+                        Style::default().fg(if net < 0 { Color::Red } else { Color::Green }),
+                    ),
+                    Span::styled(format!(" ({})", totals.since_label()), Style::default().fg(Color::DarkGray)),
+                ])
+            }
+            Some(totals) => Line::from(vec![
+                Span::styled("Total In / Out: ", Style::default().fg(Color::White)),
+                Span::styled(totals.since_label(), Style::default().fg(Color::Gray)),
+            ]),
+            None => Line::from(vec![
+                Span::styled("Total In / Out: ", Style::default().fg(Color::White)),
+                Span::styled("N/A", Style::default().fg(Color::Gray)),
+            ]),
+        };
+
+        let (burn_text, burn_color) = match (wallet.fee_burn_rate, wallet.hours_until_empty()) {
+            (Some(rate), Some(hours)) => (
+                format!("{:.8} {}/h (empty in {})", rate, currency, crate::core::wallet::format_runway(hours)),
+                if hours < 24.0 { Color::Red } else { Color::Yellow },
+            ),
+            (Some(_), None) => ("0 (not spending)".to_string(), Color::Green),
+            (None, _) => ("collecting samples...".to_string(), Color::Gray),
+        };
+
+        // Receive address: the current one, a just-generated one (labelled,
+        // with the still-valid previous one below), or the wallet's address
+        // when the daemon can't tell which is current
+        let mut receive_line = vec![Span::styled("Receive Address: ", Style::default().fg(Color::White))];
+        let mut previous_line = None;
+        match state.detail_receive_address {
+            Some(ReceiveAddress::Current(address)) => {
+                receive_line.push(Span::styled(address.clone(), Style::default().fg(Color::Cyan)));
+            }
+            Some(ReceiveAddress::Generated { address, previous }) => {
+                receive_line.push(Span::styled(address.clone(), Style::default().fg(Color::Cyan)));
+                receive_line.push(Span::styled(" NEW", Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)));
+                if let Some(previous) = previous {
+                    previous_line = Some(Line::from(vec![
+                        Span::styled("Previous: ", Style::default().fg(Color::White)),
+                        Span::styled(previous.clone(), Style::default().fg(Color::Gray)),
+                        Span::styled(" (still receives funds)", Style::default().fg(Color::DarkGray)),
+                    ]));
+                }
+            }
+            Some(ReceiveAddress::Unavailable(reason)) => {
+                receive_line.push(Span::styled(
+                    wallet.address.as_deref().unwrap_or("Not generated").to_string(),
+                    Style::default().fg(Color::Cyan),
+                ));
+                receive_line.push(Span::styled(
+                    format!(" (current unknown: {} - [N] for a new one)", reason),
+                    Style::default().fg(Color::Yellow),
+                ));
+            }
+            None => {
+                receive_line.push(Span::styled(
+                    wallet.address.as_deref().unwrap_or("Not generated").to_string(),
+                    Style::default().fg(Color::Cyan),
+                ));
+            }
+        }
+
+        let mut info_text = vec![
             Line::from(vec![
                 Span::styled("Status: ", Style::default().fg(Color::White)),
                 Span::styled(
-                    if wallet.container_running { "Running" } else { "Stopped" },
+                    wallet.status().as_str(),
                     Style::default().fg(status_color).add_modifier(Modifier::BOLD)
                 ),
             ]),
-            Line::from(vec![
-                Span::styled("Address: ", Style::default().fg(Color::White)),
-                Span::styled(
-                    wallet.address.as_deref().unwrap_or("Not generated"),
-                    Style::default().fg(Color::Cyan)
-                ),
-            ]),
+            Line::from(receive_line),
             Line::from(vec![
                 Span::styled("Total Balance: ", Style::default().fg(Color::White)),
                 Span::styled(balance_text, Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
@@ -2274,15 +2795,24 @@ impl Dashboard {
                 Span::styled("Fees Spent: ", Style::default().fg(Color::White)),
                 Span::styled(fees_text, Style::default().fg(fees_color).add_modifier(Modifier::BOLD)),
             ]),
+            Line::from(vec![
+                Span::styled("Burn Rate: ", Style::default().fg(Color::White)),
+                Span::styled(burn_text, Style::default().fg(burn_color)),
+            ]),
+            totals_line,
         ];
 
+        if let Some(previous_line) = previous_line {
+            info_text.insert(2, previous_line);
+        }
+
         let info = Paragraph::new(info_text)
             .block(Block::default().borders(Borders::ALL).title("Wallet Information"));
 
         frame.render_widget(info, chunks[1]);
 
         // Address balances section
-        if addresses.is_empty() {
+        if state.detail_wallet_addresses.is_empty() {
             let empty_text = vec![
                 Line::from(Span::styled(
                     "No address balances available",
@@ -2299,7 +2829,7 @@ impl Dashboard {
                 .bottom_margin(1);
 
             // Show all addresses (no limit)
-            let rows: Vec<Row> = addresses.iter().map(|(address, available, pending)| {
+            let rows: Vec<Row> = state.detail_wallet_addresses.iter().map(|(address, available, pending)| {
                 Row::new(vec![
                     Cell::from(address.clone()),
                     Cell::from(Span::styled(
@@ -2322,13 +2852,13 @@ impl Dashboard {
                 ],
             )
             .header(header)
-            .block(Block::default().borders(Borders::ALL).title(format!("Address Balances ({} addresses)", addresses.len())));
+            .block(Block::default().borders(Borders::ALL).title(format!("Address Balances ({} addresses)", state.detail_wallet_addresses.len())));
 
             frame.render_widget(table, chunks[2]);
         }
 
         // Activity section (UTXOs) - Show detailed transaction information
-        if utxos.is_empty() {
+        if state.detail_wallet_utxos.is_empty() {
             let empty_text = vec![
                 Line::from(""),
                 Line::from(Span::styled(
@@ -2347,22 +2877,22 @@ impl Dashboard {
             frame.render_widget(empty_widget, chunks[3]);
         } else {
             // Determine which transactions to show
-            let (display_utxos, total_count): (Vec<&crate::core::wallet::UtxoInfo>, usize) = if !filtered_tx_indices.is_empty() {
+            let (display_utxos, total_count): (Vec<&crate::core::wallet::UtxoInfo>, usize) = if !state.filtered_tx_indices.is_empty() {
                 // Show filtered transactions
-                let filtered: Vec<&crate::core::wallet::UtxoInfo> = filtered_tx_indices
+                let filtered: Vec<&crate::core::wallet::UtxoInfo> = state.filtered_tx_indices
                     .iter()
-                    .filter_map(|&idx| utxos.get(idx))
+                    .filter_map(|&idx| state.detail_wallet_utxos.get(idx))
                     .collect();
                 let count = filtered.len();
                 (filtered, count)
             } else {
                 // Show all transactions
-                (utxos.iter().collect(), utxos.len())
+                (state.detail_wallet_utxos.iter().collect(), state.detail_wallet_utxos.len())
             };
 
             // Build header with search indicator
-            let title = if !filtered_tx_indices.is_empty() {
-                format!("Transaction History ({} of {} UTXOs - filtered by '{}')", display_utxos.len(), utxos.len(), tx_search_buffer)
+            let title = if !state.filtered_tx_indices.is_empty() {
+                format!("Transaction History ({} of {} UTXOs - filtered by '{}')", display_utxos.len(), state.detail_wallet_utxos.len(), state.tx_search_buffer)
             } else {
                 format!("Transaction History ({} UTXOs)", total_count)
             };
@@ -2376,7 +2906,7 @@ impl Dashboard {
             ];
 
             for (display_idx, utxo) in display_utxos.iter().enumerate() {
-                let is_selected = selected_tx_index == Some(display_idx);
+                let is_selected = state.selected_tx_index == Some(display_idx);
                 let utxo_type = if utxo.is_coinbase { "Coinbase" } else { "Transfer" };
                 let type_color = if utxo.is_coinbase { Color::Yellow } else { Color::Cyan };
 
@@ -2434,8 +2964,8 @@ impl Dashboard {
             }
 
             // Add scroll indicator to title
-            let scroll_indicator = if utxos.len() > 1 {
-                format!(" (↑/↓ to scroll, showing from line {})", scroll_offset + 1)
+            let scroll_indicator = if state.detail_wallet_utxos.len() > 1 {
+                format!(" (↑/↓ to scroll, showing from line {})", state.detail_wallet_scroll + 1)
             } else {
                 String::new()
             };
@@ -2443,21 +2973,21 @@ impl Dashboard {
             let tx_paragraph = Paragraph::new(tx_lines)
                 .block(Block::default().borders(Borders::ALL).title(format!("Activity / Transaction History{}", scroll_indicator)))
                 .wrap(ratatui::widgets::Wrap { trim: false })
-                .scroll((scroll_offset as u16, 0));
+                .scroll((state.detail_wallet_scroll as u16, 0));
 
             frame.render_widget(tx_paragraph, chunks[3]);
         }
 
         // Footer
-        let footer_text = if let Some(status) = status_message {
+        let footer_text = if let Some(status) = state.status_message {
             status.to_string()
         } else {
-            "[Esc/q] back | [Enter] details | [/] search | [↑/↓] scroll | [r]efresh".to_string()
+            "[Esc/q] back | [Enter] details | [Q] address QR | [N]ew address | [/] search | [↑/↓] scroll | [r]efresh".to_string()
         };
 
         let footer = Paragraph::new(footer_text)
             .alignment(Alignment::Center)
-            .style(if status_message.is_some() {
+            .style(if state.status_message.is_some() {
                 Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
             } else {
                 Style::default()
@@ -2468,7 +2998,7 @@ impl Dashboard {
     }
 
     fn render_transaction_detail_modal(&self, frame: &mut Frame, utxo: &crate::core::wallet::UtxoInfo) {
-        let currency = if self.network == "mainnet" { "KAS" } else { "TKAS" };
+        let currency = self.network.currency_label();
 
         // Create centered modal area (70% width, 80% height)
         let area = frame.size();
@@ -2508,7 +3038,7 @@ impl Dashboard {
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap()
                 .as_secs();
-            let age_secs = now.saturating_sub(secs as u64);
+            let age_secs = now.saturating_sub(secs);
             let relative = if age_secs < 60 {
                 format!("{} seconds ago", age_secs)
             } else if age_secs < 3600 {
@@ -2524,7 +3054,7 @@ impl Dashboard {
         };
 
         let utxo_type = if utxo.is_coinbase { "Coinbase Reward" } else { "Transfer" };
-        let amount_sompi = (utxo.amount_kas * 100_000_000.0) as u64;
+        let amount_sompi = utxo.amount_sompi;
 
         // Build modal content with more details
         let mut lines = vec![
@@ -2574,6 +3104,24 @@ impl Dashboard {
                 Span::styled("Block DAA Score: ", Style::default().fg(Color::Gray).add_modifier(Modifier::BOLD)),
                 Span::styled(format!("{}", utxo.block_daa_score), Style::default().fg(Color::White)),
             ]),
+            Line::from(vec![
+                Span::styled("Confirmations:   ", Style::default().fg(Color::Gray).add_modifier(Modifier::BOLD)),
+                Span::styled(
+                    utxo.confirmations.map(|c| c.to_string()).unwrap_or_else(|| "Unknown".to_string()),
+                    Style::default().fg(Color::White)
+                ),
+            ]),
+            Line::from(vec![
+                Span::styled("Maturity:        ", Style::default().fg(Color::Gray).add_modifier(Modifier::BOLD)),
+                match (utxo.is_mature(), utxo.blocks_until_mature()) {
+                    (Some(true), _) => Span::styled("Mature (spendable)", Style::default().fg(Color::Green)),
+                    (Some(false), Some(left)) => Span::styled(
+                        format!("Immature ({} blocks left)", left),
+                        Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+                    ),
+                    _ => Span::styled("Unknown maturity", Style::default().fg(Color::DarkGray).add_modifier(Modifier::ITALIC)),
+                },
+            ]),
             Line::from(""),
             Line::from(Span::styled("Destination Address (Your Wallet):", Style::default().fg(Color::Gray).add_modifier(Modifier::BOLD))),
             Line::from(Span::styled(&utxo.address, Style::default().fg(Color::Magenta))),
@@ -2638,4 +3186,254 @@ impl Dashboard {
         // Render modal
         frame.render_widget(modal_widget, modal_area);
     }
+
+    fn render_address_qr_modal(&self, frame: &mut Frame, address: Option<&str>) {
+        use qrcode::render::unicode::Dense1x2;
+        use qrcode::QrCode;
+        use ratatui::widgets::Clear;
+
+        let area = frame.size();
+        let mut lines = Vec::new();
+
+        match address {
+            Some(address) => {
+                // Light modules drawn as blocks so the code scans on dark terminals
+                let qr_text = QrCode::new(address.as_bytes()).ok().map(|code| {
+                    code.render::<Dense1x2>()
+                        .dark_color(Dense1x2::Light)
+                        .light_color(Dense1x2::Dark)
+                        .quiet_zone(true)
+                        .build()
+                });
+
+                let qr_lines: Vec<&str> = qr_text.as_deref().map(|t| t.lines().collect()).unwrap_or_default();
+                let qr_width = qr_lines.iter().map(|l| l.chars().count()).max().unwrap_or(0) as u16;
+                let qr_height = qr_lines.len() as u16;
+
+                // Borders + address + hint need ~6 extra rows; fall back to text if it won't fit
+                if !qr_lines.is_empty() && qr_width + 4 <= area.width && qr_height + 6 <= area.height {
+                    for line in qr_lines {
+                        lines.push(Line::from(Span::styled(
+                            line.to_string(),
+                            Style::default().fg(Color::White).bg(Color::Black),
+                        )));
+                    }
+                } else {
+                    lines.push(Line::from(Span::styled(
+                        "Terminal too small to display QR code",
+                        Style::default().fg(Color::Yellow),
+                    )));
+                }
+
+                lines.push(Line::from(""));
+                lines.push(Line::from(Span::styled(address.to_string(), Style::default().fg(Color::Cyan))));
+            }
+            None => {
+                lines.push(Line::from(Span::styled(
+                    "No address available for this wallet",
+                    Style::default().fg(Color::Yellow),
+                )));
+            }
+        }
+
+        lines.push(Line::from(Span::styled(
+            "Press [Q] or [Esc] to close",
+            Style::default().fg(Color::DarkGray).add_modifier(Modifier::ITALIC),
+        )));
+
+        let content_width = lines.iter().map(|l| l.width()).max().unwrap_or(0) as u16;
+        let modal_width = (content_width + 4).min(area.width);
+        let modal_height = (lines.len() as u16 + 2).min(area.height);
+        let modal_area = Rect {
+            x: (area.width.saturating_sub(modal_width)) / 2,
+            y: (area.height.saturating_sub(modal_height)) / 2,
+            width: modal_width,
+            height: modal_height,
+        };
+
+        frame.render_widget(Clear, modal_area);
+
+        let modal = Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan))
+                    .title(Span::styled(" Receive Address ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)))
+                    .style(Style::default().bg(Color::Black))
+            )
+            .wrap(Wrap { trim: false });
+
+        frame.render_widget(modal, modal_area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{ConfigSection, ServicesView};
+    use ratatui::{backend::TestBackend, Terminal};
+
+    fn system_resources() -> SystemResources {
+        SystemResources {
+            cpu_percent: 12.5,
+            memory_used_gb: 4.0,
+            memory_total_gb: 16.0,
+            disk_free_gb: 100.0,
+            disk_total_gb: 500.0,
+            os_name: "Linux".to_string(),
+            os_version: "6.1".to_string(),
+            cpu_cores: 8,
+            cpu_frequency_ghz: 3.2,
+            cpu_model: "Test CPU".to_string(),
+            public_ip: None,
+            remote_docker_host: None,
+        }
+    }
+
+    /// Render into a 160x40 test terminal and return the screen row by row
+    fn render_rows(dashboard: &Dashboard, state: &RenderState) -> Vec<String> {
+        let mut terminal = Terminal::new(TestBackend::new(160, 40)).unwrap();
+        terminal.draw(|frame| dashboard.render(frame, state)).unwrap();
+        let buffer = terminal.backend().buffer();
+        buffer
+            .content
+            .chunks(buffer.area.width as usize)
+            .map(|row| row.iter().map(|cell| cell.symbol()).collect::<String>())
+            .collect()
+    }
+
+    #[test]
+    fn test_render_state_panels() {
+        let dashboard = Dashboard::new();
+        let resources = system_resources();
+        let reth_history = RethHistory::default();
+        let feed = TransactionFeed::default();
+        let notifications = Default::default();
+
+        let state = RenderState {
+            current_screen: Screen::Services,
+            services_view: ServicesView::Services,
+            config_section: ConfigSection::Environment,
+            selected_index: 0,
+            status_message: None,
+            edit_mode: false,
+            edit_buffer: "",
+            edit_cursor: 0,
+            detail_container: None,
+            detail_logs: &[],
+            detail_logs_live_mode: false,
+            detail_logs_grouping: false,
+            detail_logs_filter: None,
+            detail_logs_scroll_offset: 0,
+            system_resources: &resources,
+            dependency_health: DependencyHealth::default(),
+            show_help: false,
+            show_health: false,
+            health_rows: &[],
+            health_tick: 0,
+            search_mode: false,
+            search_buffer: "",
+            filtered_indices: &[],
+            service_state_filter: None,
+            visible_services: None,
+            show_send_dialog: false,
+            send_amount: "",
+            send_address: "",
+            send_input_field: 0,
+            send_use_wallet_selector: false,
+            send_selected_wallet_index: 0,
+            send_source_address: "",
+            send_all: false,
+            send_hint: None,
+            send_fee: None,
+            send_password_len: None,
+            restart_hint: None,
+            palette: None,
+            notifications: &notifications,
+            wallets: &[],
+            reth_metrics: None,
+            reth_history: &reth_history,
+            detail_wallet: None,
+            detail_wallet_addresses: &[],
+            detail_receive_address: None,
+            detail_wallet_utxos: &[],
+            detail_wallet_totals: None,
+            detail_wallet_scroll: 0,
+            show_tx_detail: false,
+            show_address_qr: false,
+            selected_tx_index: None,
+            tx_search_buffer: "",
+            filtered_tx_indices: &[],
+            watch_stats: None,
+            watch_feed: &feed,
+            watch_unseen_new: 0,
+            watch_filter: &FeedFilter::default(),
+            watch_show_breakdown: false,
+            storage_analysis: None,
+            storage_scroll_offset: 0,
+            storage_chart_days: 7,
+            storage_show_details: false,
+        };
+
+        // Rows 0-4 title, 5-7 menu, 8-36 content, 37-39 footer
+        let panels = [
+            (Screen::Services, "┌Services", "[R]estart"),
+            (Screen::Wallets, "┌Wallets", "[t]ransfer"),
+            (Screen::Watch, "┌Transactions - Filter: All", "[Space] Pause"),
+            (Screen::Config, "┌Configuration", "[e]dit"),
+            (Screen::Storage, "┌Storage Analysis", "[p]rune"),
+        ];
+        for (screen, panel, hint) in panels {
+            let rows = render_rows(&dashboard, &RenderState { current_screen: screen, ..state });
+            assert!(rows[1].contains("IGRA Orchestra Dashboard") && rows[1].contains("CPU: 12.5%"));
+            assert!(rows[2].contains("OS: Linux 6.1"));
+            assert!(rows[6].contains("[1] Services") && rows[6].contains("[5] Storage"));
+
+            let content = &rows[8..37];
+            assert!(content.iter().any(|row| row.starts_with(panel)), "{:?}: no {} panel", screen, panel);
+            assert!(!content.iter().any(|row| row.contains(hint)), "{:?}: footer leaked into content", screen);
+            assert!(rows[38].contains(hint), "{:?}: footer is {:?}", screen, rows[38]);
+        }
+
+        // Services header sits above the table, Profiles view only changes the footer
+        let rows = render_rows(&dashboard, &state);
+        assert!(rows[8].contains("Services  [Profiles]"));
+        assert!(rows[9].starts_with("┌Status"));
+        let profiles = RenderState { services_view: ServicesView::Profiles, ..state };
+        assert!(render_rows(&dashboard, &profiles)[38].contains("[R]ecreate"));
+
+        // A status message takes over the footer row
+        let status = RenderState { status_message: Some("✓ Started kaspad"), ..state };
+        let rows = render_rows(&dashboard, &status);
+        assert!(rows[38].contains("Started kaspad"));
+        assert!(!rows.iter().any(|row| row.contains("[Tab] Switch view")));
+    }
+
+    #[test]
+    fn test_update_services_shares_snapshots() {
+        let mut dashboard = Dashboard::new();
+        let containers: Arc<Vec<ContainerInfo>> = Arc::default();
+        let stats: Arc<HashMap<String, ContainerStats>> = Arc::default();
+        let versions = Arc::default();
+
+        dashboard.update_services(&containers, &[], &stats, &versions);
+        assert!(Arc::ptr_eq(&dashboard.containers, &containers));
+        assert!(Arc::ptr_eq(&dashboard.container_stats, &stats));
+        assert!(Arc::ptr_eq(&dashboard.image_versions, &versions));
+    }
+    #[test]
+    fn test_dependency_strip() {
+        let health = DependencyHealth {
+            docker: DependencyState::Up,
+            kaspad: DependencyState::Inactive,
+            execution_layer: DependencyState::Down,
+        };
+        let spans = dependency_strip(&health);
+        let text: String = spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(text, "Docker ●  kaspad ○  EL ●");
+
+        let colors: Vec<Option<Color>> = spans.iter().filter(|s| s.content.contains(['●', '○'])).map(|s| s.style.fg).collect();
+        assert_eq!(colors, vec![Some(Color::Green), Some(Color::DarkGray), Some(Color::Red)]);
+    }
 }
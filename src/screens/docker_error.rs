@@ -0,0 +1,82 @@
+//! Startup error screen shown when the Docker daemon can't be reached
+
+use anyhow::Result;
+use crossterm::event::{self, Event, KeyCode};
+use ratatui::{
+    backend::CrosstermBackend,
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Terminal,
+};
+use std::io;
+
+use crate::core::docker::DockerError;
+
+/// Show the error with its hint until the user retries or quits.
+/// Returns true when the user asked to retry.
+pub fn show(err: &DockerError) -> Result<bool> {
+    crate::utils::terminal::enter(false)?;
+    let result = run(err);
+    crate::utils::terminal::restore();
+    result
+}
+
+fn run(err: &DockerError) -> Result<bool> {
+    let mut terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
+    let hint = err.hint();
+
+    loop {
+        terminal.draw(|f| {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Percentage(30),
+                    Constraint::Length(9),
+                    Constraint::Min(0),
+                ])
+                .split(f.size());
+            let area = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([
+                    Constraint::Percentage(15),
+                    Constraint::Percentage(70),
+                    Constraint::Percentage(15),
+                ])
+                .split(chunks[1])[1];
+
+            let text = vec![
+                Line::from(Span::styled(
+                    format!("✗ {}", err),
+                    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                )),
+                Line::from(""),
+                Line::from(Span::styled(hint.clone(), Style::default().fg(Color::Yellow))),
+                Line::from(""),
+                Line::from(Span::styled(
+                    "[r/Enter] Retry  [q/Esc] Quit",
+                    Style::default().fg(Color::Gray),
+                )),
+            ];
+
+            let paragraph = Paragraph::new(text)
+                .block(Block::default().borders(Borders::ALL).title("Docker unavailable"))
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true });
+            f.render_widget(paragraph, area);
+        })?;
+
+        match event::read()? {
+            Event::Key(key) => match key.code {
+                KeyCode::Char('r') | KeyCode::Enter => return Ok(true),
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(false),
+                _ => {}
+            },
+            Event::Resize(_, _) => {
+                terminal.autoresize()?;
+            }
+            _ => {}
+        }
+    }
+}
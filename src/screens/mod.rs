@@ -1,4 +1,6 @@
 pub mod dashboard;
+pub mod docker_error;
+pub mod palette;
 pub mod watch;
 
 // All screens are implemented in dashboard.rs as a unified TUI interface:
@@ -14,6 +16,7 @@ pub mod watch;
 // - System resource monitoring (CPU, Memory, Disk, Network I/O)
 // - Search/filter functionality
 // - Help overlay (press '?')
+// - Command palette (press ':')
 // - Real-time auto-refresh
 
-pub use dashboard::Dashboard;
+pub use dashboard::{Dashboard, RenderState};
@@ -0,0 +1,359 @@
+//! Command palette (`:`) - run TUI actions by name
+//!
+//! Commands map onto the existing key handlers. After one runs, the TUI shows
+//! the key that does the same thing, so the palette doubles as a way to learn
+//! the shortcuts.
+
+use std::collections::VecDeque;
+
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::Screen;
+
+/// Suggestions shown under the input
+const MAX_SUGGESTIONS: usize = 8;
+
+/// Recent results shown at the bottom of the palette
+const HISTORY_SHOWN: usize = 5;
+
+const SCREENS: [(&str, Screen); 5] = [
+    ("services", Screen::Services),
+    ("wallets", Screen::Wallets),
+    ("watch", Screen::Watch),
+    ("config", Screen::Config),
+    ("storage", Screen::Storage),
+];
+
+/// Commands without an argument
+const FIXED_COMMANDS: [&str; 8] = [
+    "renew ssl",
+    "check ssl",
+    "generate tokens",
+    "refresh",
+    "health",
+    "upgrade",
+    "help",
+    "quit",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PaletteCommand {
+    Start(String),
+    Stop(String),
+    Restart(String),
+    Logs(String),
+    StartProfile(String),
+    StopProfile(String),
+    RecreateProfile(String),
+    RenewSsl,
+    CheckSsl,
+    GenerateTokens,
+    Refresh,
+    Health,
+    Upgrade,
+    Help,
+    Quit,
+    Goto(Screen),
+}
+
+impl PaletteCommand {
+    /// The key that does the same thing outside the palette
+    pub fn keybinding(&self) -> &'static str {
+        match self {
+            PaletteCommand::Start(_) => "[s] on Services",
+            PaletteCommand::Stop(_) => "[x] on Services",
+            PaletteCommand::Restart(_) => "[R] on Services",
+            PaletteCommand::Logs(_) => "[Enter] on Services",
+            PaletteCommand::StartProfile(_) => "[s] on Services > Profiles",
+            PaletteCommand::StopProfile(_) => "[x] on Services > Profiles",
+            PaletteCommand::RecreateProfile(_) => "[R] on Services > Profiles",
+            PaletteCommand::RenewSsl => "[n] on Config > SSL Certificates",
+            PaletteCommand::CheckSsl => "[c] on Config > SSL Certificates",
+            PaletteCommand::GenerateTokens => "[g] on Config > RPC Tokens",
+            PaletteCommand::Refresh => "[r]",
+            PaletteCommand::Health => "[H]",
+            PaletteCommand::Upgrade => "[u]",
+            PaletteCommand::Help => "[?]",
+            PaletteCommand::Quit => "[q]",
+            PaletteCommand::Goto(_) => "[1-5] or [← →]",
+        }
+    }
+}
+
+/// Parse a palette line, checking service and profile names against what exists
+pub fn parse_command(input: &str, services: &[String], profiles: &[String]) -> Result<PaletteCommand, String> {
+    let input = input.trim().to_lowercase();
+    let words: Vec<&str> = input.split_whitespace().collect();
+
+    let service = |name: Option<&&str>| -> Result<String, String> {
+        let name = name.ok_or("missing service name")?;
+        services
+            .iter()
+            .find(|s| s.eq_ignore_ascii_case(name))
+            .cloned()
+            .ok_or_else(|| format!("unknown service '{}'", name))
+    };
+    let profile = |name: Option<&&str>| -> Result<String, String> {
+        let name = name.ok_or("missing profile name")?;
+        profiles
+            .iter()
+            .find(|p| p.eq_ignore_ascii_case(name))
+            .cloned()
+            .ok_or_else(|| format!("unknown profile '{}'", name))
+    };
+    let no_args = |command: PaletteCommand, used: usize| -> Result<PaletteCommand, String> {
+        if words.len() > used {
+            Err(format!("unexpected '{}'", words[used..].join(" ")))
+        } else {
+            Ok(command)
+        }
+    };
+
+    let command = match words.as_slice() {
+        [] => return Err("type a command".to_string()),
+        ["start", "profile", ..] => PaletteCommand::StartProfile(profile(words.get(2))?),
+        ["stop", "profile", ..] => PaletteCommand::StopProfile(profile(words.get(2))?),
+        ["recreate", ..] => PaletteCommand::RecreateProfile(profile(words.get(1))?),
+        ["start", ..] => PaletteCommand::Start(service(words.get(1))?),
+        ["stop", ..] => PaletteCommand::Stop(service(words.get(1))?),
+        ["restart", ..] => PaletteCommand::Restart(service(words.get(1))?),
+        ["logs", ..] => PaletteCommand::Logs(service(words.get(1))?),
+        ["renew", "ssl", ..] => return no_args(PaletteCommand::RenewSsl, 2),
+        ["check", "ssl", ..] => return no_args(PaletteCommand::CheckSsl, 2),
+        ["generate", "tokens", ..] => return no_args(PaletteCommand::GenerateTokens, 2),
+        ["refresh", ..] => return no_args(PaletteCommand::Refresh, 1),
+        ["health", ..] => return no_args(PaletteCommand::Health, 1),
+        ["upgrade", ..] => return no_args(PaletteCommand::Upgrade, 1),
+        ["help", ..] => return no_args(PaletteCommand::Help, 1),
+        ["quit", ..] => return no_args(PaletteCommand::Quit, 1),
+        ["goto", name, ..] => {
+            let screen = SCREENS
+                .iter()
+                .find(|(n, _)| n == name)
+                .map(|(_, s)| *s)
+                .ok_or_else(|| format!("unknown screen '{}'", name))?;
+            return no_args(PaletteCommand::Goto(screen), 2);
+        }
+        [other, ..] => return Err(format!("unknown command '{}'", other)),
+    };
+
+    // Service and profile commands take exactly one name
+    let used = if matches!(command, PaletteCommand::StartProfile(_) | PaletteCommand::StopProfile(_)) { 3 } else { 2 };
+    no_args(command, used)
+}
+
+/// Fuzzy match `query` against `candidate`: every query character must appear
+/// in order. Higher is better; consecutive runs and word starts score extra.
+pub fn fuzzy_score(candidate: &str, query: &str) -> Option<i64> {
+    let candidate: Vec<char> = candidate.to_lowercase().chars().collect();
+    let mut score = 0i64;
+    let mut pos = 0usize;
+    let mut previous: Option<usize> = None;
+
+    for q in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let found = candidate[pos..].iter().position(|&c| c == q)? + pos;
+        score += 1;
+        if previous.is_some_and(|p| p + 1 == found) {
+            score += 5;
+        }
+        if found == 0 || matches!(candidate[found - 1], ' ' | '-' | '_') {
+            score += 3;
+        }
+        previous = Some(found);
+        pos = found + 1;
+    }
+
+    // Prefer shorter candidates on ties
+    Some(score * 100 - candidate.len() as i64)
+}
+
+/// State of the open palette
+#[derive(Debug, Clone)]
+pub struct CommandPalette {
+    pub input: String,
+    /// Highlighted suggestion
+    pub selected: usize,
+    /// Why the last Enter didn't run anything
+    pub error: Option<String>,
+    services: Vec<String>,
+    profiles: Vec<String>,
+}
+
+impl CommandPalette {
+    pub fn new(services: Vec<String>, profiles: Vec<String>) -> Self {
+        Self {
+            input: String::new(),
+            selected: 0,
+            error: None,
+            services,
+            profiles,
+        }
+    }
+
+    /// Every complete command, for completion
+    fn all_commands(&self) -> Vec<String> {
+        let mut commands = Vec::new();
+        for verb in ["start", "stop", "restart", "logs"] {
+            commands.extend(self.services.iter().map(|s| format!("{} {}", verb, s)));
+        }
+        for verb in ["start profile", "stop profile", "recreate"] {
+            commands.extend(self.profiles.iter().map(|p| format!("{} {}", verb, p)));
+        }
+        commands.extend(FIXED_COMMANDS.iter().map(|c| c.to_string()));
+        commands.extend(SCREENS.iter().map(|(name, _)| format!("goto {}", name)));
+        commands
+    }
+
+    /// Commands matching the input, best first
+    pub fn suggestions(&self) -> Vec<String> {
+        let mut scored: Vec<(i64, String)> = self
+            .all_commands()
+            .into_iter()
+            .filter_map(|c| fuzzy_score(&c, &self.input).map(|score| (score, c)))
+            .collect();
+        scored.sort_by_key(|s| std::cmp::Reverse(s.0));
+        scored.into_iter().take(MAX_SUGGESTIONS).map(|(_, c)| c).collect()
+    }
+
+    /// Replace the input with the highlighted suggestion
+    pub fn complete(&mut self) {
+        if let Some(suggestion) = self.suggestions().get(self.selected) {
+            self.input = suggestion.clone();
+            self.selected = 0;
+            self.error = None;
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        let count = self.suggestions().len();
+        if count > 0 {
+            self.selected = (self.selected + 1) % count;
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        let count = self.suggestions().len();
+        if count > 0 {
+            self.selected = (self.selected + count - 1) % count;
+        }
+    }
+
+    pub fn parse(&self) -> Result<PaletteCommand, String> {
+        parse_command(&self.input, &self.services, &self.profiles)
+    }
+}
+
+/// Draw the palette near the top of the screen, with recent results below
+pub fn render(frame: &mut Frame, palette: &CommandPalette, history: &VecDeque<String>) {
+    let suggestions = palette.suggestions();
+    let recent: Vec<&String> = history.iter().rev().take(HISTORY_SHOWN).collect();
+
+    let mut lines = vec![Line::from(vec![
+        Span::styled(":", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Span::raw(palette.input.clone()),
+        Span::styled(" ", Style::default().add_modifier(Modifier::REVERSED)),
+    ])];
+    if let Some(error) = &palette.error {
+        lines.push(Line::from(Span::styled(format!("✗ {}", error), Style::default().fg(Color::Red))));
+    }
+    lines.push(Line::from(""));
+
+    if suggestions.is_empty() {
+        lines.push(Line::from(Span::styled("No matching commands", Style::default().fg(Color::DarkGray))));
+    }
+    for (i, suggestion) in suggestions.iter().enumerate() {
+        let style = if i == palette.selected {
+            Style::default().fg(Color::Black).bg(Color::Cyan).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        let key = parse_command(suggestion, &palette.services, &palette.profiles)
+            .map(|c| c.keybinding())
+            .unwrap_or("");
+        lines.push(Line::from(vec![
+            Span::styled(format!(" {} ", suggestion), style),
+            Span::styled(format!("  {}", key), Style::default().fg(Color::DarkGray)),
+        ]));
+    }
+
+    if !recent.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled("Recent", Style::default().fg(Color::Yellow))));
+        for entry in recent {
+            lines.push(Line::from(Span::styled(entry.clone(), Style::default().fg(Color::Gray))));
+        }
+    }
+
+    let area = frame.size();
+    let width = area.width.min(80);
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let popup = Rect {
+        x: area.width.saturating_sub(width) / 2,
+        y: area.height.saturating_sub(height) / 4,
+        width,
+        height,
+    };
+
+    let widget = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(Span::styled(
+                " Command - [Tab] complete | [↑↓] select | [Enter] run | [Esc] close ",
+                Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+            )),
+    );
+    frame.render_widget(Clear, popup);
+    frame.render_widget(widget, popup);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_command() {
+        let services = names(&["kaspad", "viaduct"]);
+        let profiles = names(&["kaspad", "backend"]);
+        let parse = |input| parse_command(input, &services, &profiles);
+
+        assert_eq!(parse("start viaduct"), Ok(PaletteCommand::Start("viaduct".to_string())));
+        assert_eq!(parse("  Restart KASPAD "), Ok(PaletteCommand::Restart("kaspad".to_string())));
+        assert_eq!(parse("start profile backend"), Ok(PaletteCommand::StartProfile("backend".to_string())));
+        assert_eq!(parse("renew ssl"), Ok(PaletteCommand::RenewSsl));
+        assert_eq!(parse("goto wallets"), Ok(PaletteCommand::Goto(Screen::Wallets)));
+
+        assert!(parse("start viaduc").unwrap_err().contains("unknown service"));
+        assert!(parse("start").unwrap_err().contains("missing service"));
+        assert!(parse("recreate frontend-w9").unwrap_err().contains("unknown profile"));
+        assert!(parse("refresh now").unwrap_err().contains("unexpected"));
+        assert!(parse("stop kaspad viaduct").unwrap_err().contains("unexpected"));
+        assert!(parse("launch kaspad").unwrap_err().contains("unknown command"));
+    }
+
+    #[test]
+    fn test_fuzzy_suggestions() {
+        assert!(fuzzy_score("start viaduct", "st via").is_some());
+        assert!(fuzzy_score("start viaduct", "xyz").is_none());
+        // Word starts and runs beat scattered matches
+        assert!(fuzzy_score("renew ssl", "rs").unwrap() > fuzzy_score("restart kaspad", "rs").unwrap());
+
+        let mut palette = CommandPalette::new(names(&["kaspad", "viaduct"]), names(&["backend"]));
+        palette.input = "rst via".to_string();
+        assert_eq!(palette.suggestions().first().map(|s| s.as_str()), Some("restart viaduct"));
+
+        palette.complete();
+        assert_eq!(palette.input, "restart viaduct");
+        assert!(palette.parse().is_ok());
+    }
+}
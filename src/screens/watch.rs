@@ -1,11 +1,7 @@
-/// Watch Screen - Real-time L2 transaction monitoring TUI
+//! Watch Screen - Real-time L2 transaction monitoring TUI
 
 use anyhow::Result;
-use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
-    execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
-};
+use crossterm::event::{self, Event, KeyCode};
 use ratatui::{
     backend::{Backend, CrosstermBackend},
     layout::{Alignment, Constraint, Direction, Layout},
@@ -16,15 +12,18 @@ use ratatui::{
 };
 use std::io;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
-use tokio::time::interval;
 
-use crate::core::l2_monitor::{TransactionInfo, TransactionMonitor, TransactionType};
+use crate::core::alerts::{AlertMonitor, WebhookNotifier};
+use crate::core::l2_monitor::{poll_timers, PollUpdate, Statistics, TpsTrend, TransactionInfo, TransactionMonitor, TransactionType};
+use crate::core::recorder::TransactionRecorder;
+use crate::core::selectors::SelectorDb;
 
 /// Transaction filter
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub enum TransactionFilter {
+    #[default]
     All,
     Transfer,
     Contract,
@@ -40,153 +39,315 @@ impl TransactionFilter {
             TransactionFilter::Entry => tx_type == &TransactionType::Entry,
         }
     }
+
+    pub fn next(&self) -> Self {
+        match self {
+            TransactionFilter::All => TransactionFilter::Transfer,
+            TransactionFilter::Transfer => TransactionFilter::Contract,
+            TransactionFilter::Contract => TransactionFilter::Entry,
+            TransactionFilter::Entry => TransactionFilter::All,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TransactionFilter::All => "All",
+            TransactionFilter::Transfer => "Transfer",
+            TransactionFilter::Contract => "Contract",
+            TransactionFilter::Entry => "Entry",
+        }
+    }
 }
 
-/// Watch screen state
-struct WatchState {
-    transactions: Vec<TransactionInfo>,
-    list_state: ListState,
-    filter: TransactionFilter,
-    file_recorder: Option<std::fs::File>,
-    format: String,
+/// Inclusive iKAS bounds; an unset side is open
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RangeFilter {
+    pub min: Option<f64>,
+    pub max: Option<f64>,
 }
 
-impl WatchState {
-    fn new(record_path: Option<String>, format: String) -> Result<Self> {
-        let file_recorder = if let Some(path) = record_path {
-            Some(std::fs::File::create(path)?)
+impl RangeFilter {
+    /// Parse "1" or ">1" (at least), "<0.5" (at most), "1..10" (between).
+    /// Empty input clears the range.
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let input = input.trim();
+        let number = |s: &str| -> Result<Option<f64>, String> {
+            let s = s.trim();
+            if s.is_empty() {
+                return Ok(None);
+            }
+            match s.parse::<f64>() {
+                Ok(v) if v.is_finite() && v >= 0.0 => Ok(Some(v)),
+                _ => Err(format!("'{}' is not a non-negative number", s)),
+            }
+        };
+
+        let range = if let Some((lo, hi)) = input.split_once("..") {
+            Self { min: number(lo)?, max: number(hi)? }
+        } else if let Some(v) = input.strip_prefix(">=").or_else(|| input.strip_prefix('>')) {
+            Self { min: number(v)?, max: None }
+        } else if let Some(v) = input.strip_prefix("<=").or_else(|| input.strip_prefix('<')) {
+            Self { min: None, max: number(v)? }
         } else {
-            None
+            Self { min: number(input)?, max: None }
         };
 
-        Ok(Self {
-            transactions: Vec::new(),
-            list_state: ListState::default(),
-            filter: TransactionFilter::All,
-            file_recorder,
-            format,
-        })
+        if let (Some(min), Some(max)) = (range.min, range.max) {
+            if min > max {
+                return Err(format!("{} is greater than {}", min, max));
+            }
+        }
+        Ok(range)
     }
 
-    fn add_transactions(&mut self, new_txs: Vec<TransactionInfo>) {
-        for tx in new_txs {
-            // Record to file if enabled
-            if let Some(ref mut file) = self.file_recorder {
-                let _ = Self::write_transaction_to_file_static(file, &tx, &self.format);
-            }
+    pub fn is_active(&self) -> bool {
+        self.min.is_some() || self.max.is_some()
+    }
 
-            // Add to display list (keep last 100 transactions)
-            self.transactions.insert(0, tx);
-            if self.transactions.len() > 100 {
-                self.transactions.truncate(100);
-            }
+    pub fn contains(&self, value: f64) -> bool {
+        self.min.is_none_or(|min| value >= min) && self.max.is_none_or(|max| value <= max)
+    }
+
+    fn describe(&self, name: &str) -> String {
+        match (self.min, self.max) {
+            (Some(min), Some(max)) => format!("{} {}..{} iKAS", name, min, max),
+            (Some(min), None) => format!("{} ≥ {} iKAS", name, min),
+            (None, Some(max)) => format!("{} ≤ {} iKAS", name, max),
+            (None, None) => String::new(),
         }
     }
+}
 
-    fn write_transaction_to_file_static(file: &mut std::fs::File, tx: &TransactionInfo, format: &str) -> Result<()> {
-        use std::io::Write;
+/// Which range an input line edits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangeField {
+    Value,
+    Fee,
+}
 
-        match format {
-            "json" => {
-                let json = serde_json::to_string(tx)?;
-                writeln!(file, "{}", json)?;
-            }
-            "csv" => {
-                // CSV header is written once at file creation
-                writeln!(
-                    file,
-                    "{},{},{},{},{},{},{},{},{},{}",
-                    tx.timestamp.format("%Y-%m-%d %H:%M:%S"),
-                    tx.tx_type,
-                    tx.hash,
-                    tx.from,
-                    tx.to.as_deref().unwrap_or(""),
-                    tx.value_ikas(),
-                    tx.gas_fee_ikas(),
-                    tx.l1_fee.unwrap_or(0.0),
-                    tx.status,
-                    tx.block_number
-                )?;
-            }
-            _ => {
-                // Text format
-                writeln!(file, "[{}] {}", tx.timestamp.format("%H:%M:%S"), tx.tx_type)?;
-                writeln!(file, "  Hash: {}", tx.hash)?;
-                writeln!(file, "  From: {}", tx.from)?;
-                if let Some(ref to) = tx.to {
-                    writeln!(file, "  To:   {}", to)?;
-                }
-                writeln!(file, "  Value: {} iKAS", tx.value_ikas())?;
-                writeln!(file, "  Gas: {} iKAS", tx.gas_fee_ikas())?;
-                if let Some(l1_fee) = tx.l1_fee {
-                    writeln!(file, "  L1 Fee: {} KAS", l1_fee)?;
+impl RangeField {
+    pub fn prompt(&self) -> &'static str {
+        match self {
+            RangeField::Value => "Value range (iKAS, e.g. >1, <0.5, 1..10; empty clears)",
+            RangeField::Fee => "Fee range (iKAS, e.g. >0.01, 0.001..0.1; empty clears)",
+        }
+    }
+}
+
+/// Everything the Watch list filters on. Type, value and fee all apply at once.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FeedFilter {
+    pub tx_type: TransactionFilter,
+    pub value: RangeFilter,
+    /// L2 gas fee
+    pub fee: RangeFilter,
+}
+
+impl FeedFilter {
+    pub fn matches(&self, tx: &TransactionInfo) -> bool {
+        self.tx_type.matches(&tx.tx_type)
+            && self.value.contains(tx.value_ikas())
+            && self.fee.contains(tx.gas_fee_ikas())
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.tx_type != TransactionFilter::All || self.value.is_active() || self.fee.is_active()
+    }
+
+    pub fn set_range(&mut self, field: RangeField, range: RangeFilter) {
+        match field {
+            RangeField::Value => self.value = range,
+            RangeField::Fee => self.fee = range,
+        }
+    }
+
+    /// Header text listing every active filter, e.g. "Transfer, Value ≥ 1 iKAS"
+    pub fn describe(&self) -> String {
+        if !self.is_active() {
+            return "All".to_string();
+        }
+        let mut parts = Vec::new();
+        if self.tx_type != TransactionFilter::All {
+            parts.push(self.tx_type.label().to_string());
+        }
+        if self.value.is_active() {
+            parts.push(self.value.describe("Value"));
+        }
+        if self.fee.is_active() {
+            parts.push(self.fee.describe("Fee"));
+        }
+        parts.join(", ")
+    }
+}
+
+/// Transactions kept for display (and buffered while paused)
+pub const FEED_CAPACITY: usize = 100;
+
+/// Live transaction list, newest first, that can be frozen while the user
+/// scrolls back. Incoming transactions are held aside while paused.
+#[derive(Default)]
+pub struct TransactionFeed {
+    transactions: Vec<TransactionInfo>,
+    pending: Vec<TransactionInfo>,
+    pending_total: usize,
+    paused: bool,
+}
+
+impl TransactionFeed {
+    /// Add polled transactions (chain order, oldest first). A transaction we
+    /// already show (e.g. reorged, then re-included in a later block) is
+    /// updated in place rather than listed twice.
+    pub fn push(&mut self, new_txs: Vec<TransactionInfo>) {
+        let new_txs: Vec<TransactionInfo> = new_txs
+            .into_iter()
+            .filter_map(|tx| {
+                match self.transactions.iter_mut().chain(self.pending.iter_mut()).find(|t| t.hash == tx.hash) {
+                    Some(existing) => {
+                        *existing = tx;
+                        None
+                    }
+                    None => Some(tx),
                 }
-                writeln!(file, "  Status: {}", if tx.status { "Success" } else { "Failed" })?;
-                writeln!(file)?;
+            })
+            .collect();
+
+        if self.paused {
+            self.pending_total += new_txs.len();
+            prepend(&mut self.pending, new_txs);
+        } else {
+            prepend(&mut self.transactions, new_txs);
+        }
+    }
+
+    /// Flag transactions whose block was reorged out. They stay listed so
+    /// the user can see what happened. Returns how many were found.
+    pub fn mark_reorged(&mut self, hashes: &[String]) -> usize {
+        let mut marked = 0;
+        for tx in self.transactions.iter_mut().chain(self.pending.iter_mut()) {
+            if hashes.contains(&tx.hash) {
+                tx.reorged = true;
+                marked += 1;
             }
         }
+        marked
+    }
 
-        Ok(())
+    pub fn pause(&mut self) {
+        self.paused = true;
     }
 
-    fn write_transaction_to_file(&self, file: &mut std::fs::File, tx: &TransactionInfo) -> Result<()> {
-        use std::io::Write;
+    /// Unfreeze and prepend everything buffered while paused.
+    /// Returns (buffered count, how many of them are now in the list)
+    pub fn resume(&mut self) -> (usize, usize) {
+        self.paused = false;
+        let total = std::mem::take(&mut self.pending_total);
+        let pending = std::mem::take(&mut self.pending);
+        let shown = pending.len();
 
-        match self.format.as_str() {
-            "json" => {
-                let json = serde_json::to_string(tx)?;
-                writeln!(file, "{}", json)?;
-            }
-            "csv" => {
-                // CSV header is written once at file creation
-                writeln!(
-                    file,
-                    "{},{},{},{},{},{},{},{},{},{}",
-                    tx.timestamp.format("%Y-%m-%d %H:%M:%S"),
-                    tx.tx_type,
-                    tx.hash,
-                    tx.from,
-                    tx.to.as_deref().unwrap_or(""),
-                    tx.value_ikas(),
-                    tx.gas_fee_ikas(),
-                    tx.l1_fee.unwrap_or(0.0),
-                    tx.status,
-                    tx.block_number
-                )?;
-            }
-            _ => {
-                // Text format
-                writeln!(file, "[{}] {}", tx.timestamp.format("%H:%M:%S"), tx.tx_type)?;
-                writeln!(file, "  Hash: {}", tx.hash)?;
-                writeln!(file, "  From: {}", tx.from)?;
-                if let Some(ref to) = tx.to {
-                    writeln!(file, "  To:   {}", to)?;
-                }
-                writeln!(file, "  Value: {} iKAS", tx.value_ikas())?;
-                writeln!(file, "  Gas: {} iKAS", tx.gas_fee_ikas())?;
-                if let Some(l1_fee) = tx.l1_fee {
-                    writeln!(file, "  L1 Fee: {} KAS", l1_fee)?;
-                }
-                writeln!(file, "  Status: {}", if tx.status { "Success" } else { "Failed" })?;
-                writeln!(file)?;
+        let mut merged = pending;
+        merged.append(&mut self.transactions);
+        merged.truncate(FEED_CAPACITY);
+        self.transactions = merged;
+
+        (total, shown)
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Transactions received since pausing
+    pub fn pending_count(&self) -> usize {
+        self.pending_total
+    }
+
+    pub fn transactions(&self) -> &[TransactionInfo] {
+        &self.transactions
+    }
+
+    pub fn clear(&mut self) {
+        self.transactions.clear();
+    }
+}
+
+/// Insert oldest-first `new_txs` at the front so the newest ends up first
+fn prepend(list: &mut Vec<TransactionInfo>, new_txs: Vec<TransactionInfo>) {
+    for tx in new_txs {
+        list.insert(0, tx);
+    }
+    list.truncate(FEED_CAPACITY);
+}
+
+/// Watch screen state
+struct WatchState {
+    feed: TransactionFeed,
+    list_state: ListState,
+    filter: FeedFilter,
+    /// Range being typed (field, buffer) and the last parse error
+    range_input: Option<(RangeField, String)>,
+    range_error: Option<String>,
+    recorder: Option<TransactionRecorder>,
+    /// Transactions prepended by the last resume, shown until scrolled to top
+    unseen_new: usize,
+    /// Per-type counts and fees shown under the statistics
+    show_breakdown: bool,
+}
+
+impl WatchState {
+    fn new(recorder: Option<TransactionRecorder>) -> Self {
+        Self {
+            feed: TransactionFeed::default(),
+            list_state: ListState::default(),
+            filter: FeedFilter::default(),
+            range_input: None,
+            range_error: None,
+            recorder,
+            unseen_new: 0,
+            show_breakdown: false,
+        }
+    }
+
+    fn apply_update(&mut self, update: PollUpdate) {
+        // Record to file if enabled (also while paused)
+        if let Some(ref mut recorder) = self.recorder {
+            for tx in &update.transactions {
+                let _ = recorder.record(tx);
             }
         }
 
-        Ok(())
+        // Mark first so re-included transactions come back as confirmed
+        self.feed.mark_reorged(&update.reorged);
+        self.feed.push(update.transactions);
+    }
+
+    fn toggle_pause(&mut self) {
+        if !self.feed.is_paused() {
+            self.feed.pause();
+            return;
+        }
+
+        let (total, shown) = self.feed.resume();
+        // Keep the selected transaction in place: shift by the newly
+        // prepended rows that pass the current filter
+        let visible = self.feed.transactions()[..shown]
+            .iter()
+            .filter(|tx| self.filter.matches(tx))
+            .count();
+        if let Some(i) = self.list_state.selected() {
+            let max = self.filtered_transactions().len().saturating_sub(1);
+            self.list_state.select(Some((i + visible).min(max)));
+        }
+        if total > 0 {
+            self.unseen_new = total;
+        }
     }
 
     fn scroll_up(&mut self) {
-        let i = match self.list_state.selected() {
-            Some(i) => {
-                if i > 0 {
-                    i - 1
-                } else {
-                    0
-                }
-            }
-            None => 0,
-        };
+        let i = self.list_state.selected().map_or(0, |i| i.saturating_sub(1));
         self.list_state.select(Some(i));
+        if i == 0 {
+            self.unseen_new = 0;
+        }
     }
 
     fn scroll_down(&mut self, max: usize) {
@@ -204,18 +365,29 @@ impl WatchState {
     }
 
     fn toggle_filter(&mut self) {
-        self.filter = match self.filter {
-            TransactionFilter::All => TransactionFilter::Transfer,
-            TransactionFilter::Transfer => TransactionFilter::Contract,
-            TransactionFilter::Contract => TransactionFilter::Entry,
-            TransactionFilter::Entry => TransactionFilter::All,
-        };
+        self.filter.tx_type = self.filter.tx_type.next();
+    }
+
+    /// Apply the typed range; keeps the input open on a parse error
+    fn submit_range(&mut self) {
+        let Some((field, buffer)) = self.range_input.take() else { return };
+        match RangeFilter::parse(&buffer) {
+            Ok(range) => {
+                self.filter.set_range(field, range);
+                self.range_error = None;
+            }
+            Err(e) => {
+                self.range_error = Some(e);
+                self.range_input = Some((field, buffer));
+            }
+        }
     }
 
     fn filtered_transactions(&self) -> Vec<&TransactionInfo> {
-        self.transactions
+        self.feed
+            .transactions()
             .iter()
-            .filter(|tx| self.filter.matches(&tx.tx_type))
+            .filter(|tx| self.filter.matches(tx))
             .collect()
     }
 }
@@ -223,24 +395,30 @@ impl WatchState {
 /// Run the watch TUI
 pub async fn run_watch_tui(
     filter: String,
-    record: Option<String>,
-    format: String,
+    recorder: Option<TransactionRecorder>,
+    selectors: SelectorDb,
+    intervals: crate::utils::WatchIntervals,
+    notifier: Option<WebhookNotifier>,
 ) -> Result<()> {
-    // Setup terminal
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
-
-    // Initialize monitor and state
-    let monitor = Arc::new(TransactionMonitor::new().await?);
-    let state = Arc::new(RwLock::new(WatchState::new(record, format)?));
+    // Initialize monitor and state before touching the terminal
+    let monitor = Arc::new(TransactionMonitor::new().await?.with_selectors(selectors));
+    let state = Arc::new(RwLock::new(WatchState::new(recorder)));
+
+    // Setup terminal (restored on panic as well as on exit)
+    crate::utils::terminal::enter(true)?;
+    let backend = CrosstermBackend::new(io::stdout());
+    let mut terminal = match Terminal::new(backend) {
+        Ok(terminal) => terminal,
+        Err(e) => {
+            crate::utils::terminal::restore();
+            return Err(e.into());
+        }
+    };
 
     // Set initial filter
     {
         let mut s = state.write().await;
-        s.filter = match filter.as_str() {
+        s.filter.tx_type = match filter.as_str() {
             "transfer" => TransactionFilter::Transfer,
             "contract" => TransactionFilter::Contract,
             "entry" => TransactionFilter::Entry,
@@ -252,16 +430,25 @@ pub async fn run_watch_tui(
     let monitor_clone = Arc::clone(&monitor);
     let state_clone = Arc::clone(&state);
     tokio::spawn(async move {
-        let mut poll_interval = interval(Duration::from_secs(1));
-        let mut l1_interval = interval(Duration::from_secs(10));
+        let (mut poll_trigger, mut l1_interval) = poll_timers(intervals);
+        let mut alerts = AlertMonitor::new();
 
         loop {
             tokio::select! {
-                _ = poll_interval.tick() => {
-                    if let Ok(new_txs) = monitor_clone.poll_new_transactions().await {
-                        if !new_txs.is_empty() {
-                            let mut s = state_clone.write().await;
-                            s.add_transactions(new_txs);
+                _ = poll_trigger.tick() => {
+                    let raised = match monitor_clone.poll().await {
+                        Ok(update) => {
+                            if !update.is_empty() {
+                                let mut s = state_clone.write().await;
+                                s.apply_update(update);
+                            }
+                            alerts.observe(&monitor_clone.get_statistics().await, Instant::now())
+                        }
+                        Err(e) => alerts.poll_failed(&e.to_string()),
+                    };
+                    if let Some(ref notifier) = notifier {
+                        for alert in &raised {
+                            notifier.send(alert);
                         }
                     }
                 }
@@ -276,13 +463,15 @@ pub async fn run_watch_tui(
     let res = run_ui_loop(&mut terminal, &monitor, &state).await;
 
     // Restore terminal
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
-    terminal.show_cursor()?;
+    crate::utils::terminal::restore();
+
+    // Close out the recording with a summary next to it
+    if let Some(recorder) = state.write().await.recorder.take() {
+        match recorder.finish() {
+            Ok(path) => println!("✓ Recording summary written to {}", path.display()),
+            Err(e) => eprintln!("✗ Failed to write recording summary: {}", e),
+        }
+    }
 
     res
 }
@@ -298,13 +487,44 @@ async fn run_ui_loop<B: Backend>(
         let state_guard = state.read().await;
 
         terminal.draw(|f| {
-            ui(f, &stats, &*state_guard);
+            ui(f, &stats, &state_guard);
         })?;
         drop(state_guard);
 
         // Handle input (with timeout)
         if event::poll(Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
+            let event = event::read()?;
+            if let Event::Resize(_, _) = event {
+                terminal.autoresize()?;
+                continue;
+            }
+            if let Event::Key(key) = event {
+                // Typing a value/fee range takes every key until Enter/Esc
+                {
+                    let mut s = state.write().await;
+                    if s.range_input.is_some() {
+                        match key.code {
+                            KeyCode::Char(c) => {
+                                if let Some((_, buffer)) = s.range_input.as_mut() {
+                                    buffer.push(c);
+                                }
+                            }
+                            KeyCode::Backspace => {
+                                if let Some((_, buffer)) = s.range_input.as_mut() {
+                                    buffer.pop();
+                                }
+                            }
+                            KeyCode::Enter => s.submit_range(),
+                            KeyCode::Esc => {
+                                s.range_input = None;
+                                s.range_error = None;
+                            }
+                            _ => {}
+                        }
+                        continue;
+                    }
+                }
+
                 match key.code {
                     KeyCode::Char('q') | KeyCode::Esc => {
                         return Ok(());
@@ -322,6 +542,28 @@ async fn run_ui_loop<B: Backend>(
                         let mut s = state.write().await;
                         s.toggle_filter();
                     }
+                    KeyCode::Char('v') => {
+                        state.write().await.range_input = Some((RangeField::Value, String::new()));
+                    }
+                    KeyCode::Char('e') => {
+                        state.write().await.range_input = Some((RangeField::Fee, String::new()));
+                    }
+                    KeyCode::Char('x') => {
+                        state.write().await.filter = FeedFilter::default();
+                    }
+                    KeyCode::Char('b') => {
+                        let mut s = state.write().await;
+                        s.show_breakdown = !s.show_breakdown;
+                    }
+                    KeyCode::Char(' ') | KeyCode::Char('p') => {
+                        let mut s = state.write().await;
+                        s.toggle_pause();
+                    }
+                    KeyCode::Home => {
+                        let mut s = state.write().await;
+                        s.list_state.select(Some(0));
+                        s.unseen_new = 0;
+                    }
                     _ => {}
                 }
             }
@@ -331,14 +573,16 @@ async fn run_ui_loop<B: Backend>(
 
 fn ui(
     f: &mut Frame,
-    stats: &crate::core::l2_monitor::Statistics,
+    stats: &Statistics,
     state: &WatchState,
 ) {
+    let breakdown_height = if state.show_breakdown { TYPE_BREAKDOWN_HEIGHT } else { 0 };
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(1),  // Title
-            Constraint::Length(4),  // Stats header
+            Constraint::Length(6),  // Stats header
+            Constraint::Length(breakdown_height),  // Per-type breakdown
             Constraint::Min(0),     // Transaction list
             Constraint::Length(1),  // Footer
         ])
@@ -364,10 +608,23 @@ fn ui(
                 format!("{:.2}", stats.tps()),
                 Style::default().fg(Color::Yellow),
             ),
+            tps_trend_span(stats.tps_trend()),
             Span::raw("  │  "),
             Span::styled("Uptime: ", Style::default().fg(Color::Gray)),
             Span::styled(stats.uptime(), Style::default().fg(Color::Blue)),
         ]),
+        Line::from(vec![
+            Span::styled("Block time: ", Style::default().fg(Color::Gray)),
+            Span::styled(stats.block_time_display(), Style::default().fg(Color::Cyan)),
+            Span::raw("  │  "),
+            Span::styled("Last minute: ", Style::default().fg(Color::Gray)),
+            Span::styled(stats.block_rate_display(), Style::default().fg(Color::Cyan)),
+            if stats.is_stalled() {
+                Span::styled("  ⚠ No new blocks", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))
+            } else {
+                Span::raw("")
+            },
+        ]),
         Line::from(vec![
             Span::styled("Total: ", Style::default().fg(Color::Gray)),
             Span::styled(
@@ -386,7 +643,9 @@ fn ui(
                 format!("{}", stats.failed_transactions),
                 Style::default().fg(Color::Red),
             ),
-        ]),
+            Span::raw("  │  "),
+            reorg_count_span(stats.reorgs),
+        ].into_iter().chain(pending_tx_spans(stats.pending_transactions)).collect::<Vec<_>>()),
         Line::from(vec![
             Span::styled("L2 Fees: ", Style::default().fg(Color::Gray)),
             Span::styled(
@@ -408,6 +667,10 @@ fn ui(
         .wrap(Wrap { trim: false });
     f.render_widget(stats_block, chunks[1]);
 
+    if state.show_breakdown {
+        f.render_widget(type_breakdown_panel(stats), chunks[2]);
+    }
+
     // Transaction list
     let filtered_txs = state.filtered_transactions();
 
@@ -421,9 +684,6 @@ fn ui(
                 TransactionType::Unknown => Color::Gray,
             };
 
-            let status_symbol = if tx.status { "✓" } else { "✗" };
-            let status_color = if tx.status { Color::Green } else { Color::Red };
-
             let mut lines = vec![
                 Line::from(vec![
                     Span::styled(
@@ -431,11 +691,11 @@ fn ui(
                         Style::default().fg(Color::Gray),
                     ),
                     Span::styled(
-                        format!("{}", tx.tx_type),
+                        tx.type_label(),
                         Style::default().fg(type_color).add_modifier(Modifier::BOLD),
                     ),
                     Span::raw("  "),
-                    Span::styled(status_symbol, Style::default().fg(status_color)),
+                    status_span(tx),
                 ]),
                 Line::from(vec![
                     Span::raw("  From: "),
@@ -474,31 +734,245 @@ fn ui(
                 ]));
             }
 
-            ListItem::new(Text::from(lines)).style(Style::default())
+            ListItem::new(Text::from(grey_out_reorged(tx, lines))).style(Style::default())
         })
         .collect();
 
-    let filter_str = match state.filter {
-        TransactionFilter::All => "All",
-        TransactionFilter::Transfer => "Transfers",
-        TransactionFilter::Contract => "Contracts",
-        TransactionFilter::Entry => "Entry TXs",
-    };
+    let mut title = vec![Span::raw(format!("Transactions [Filter: {}]", state.filter.describe()))];
+    title.extend(feed_indicator(&state.feed, state.unseen_new));
 
     let mut list_state = state.list_state.clone();
     let list = List::new(items)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .title(format!("Transactions [Filter: {}]", filter_str)),
+                .title(Line::from(title)),
         )
         .highlight_style(Style::default().bg(Color::DarkGray));
 
-    f.render_stateful_widget(list, chunks[2], &mut list_state);
+    f.render_stateful_widget(list, chunks[3], &mut list_state);
 
-    // Footer
-    let footer = Paragraph::new("[q] Quit  [↑↓] Scroll  [f] Toggle Filter")
-        .style(Style::default().fg(Color::Gray))
-        .alignment(Alignment::Center);
-    f.render_widget(footer, chunks[3]);
+    // Footer (doubles as the range input line)
+    let footer = match (&state.range_input, &state.range_error) {
+        (Some((_, buffer)), Some(err)) => Paragraph::new(format!("✗ {} (Enter to retry, Esc to cancel): {}_", err, buffer))
+            .style(Style::default().fg(Color::Red)),
+        (Some((field, buffer)), None) => Paragraph::new(format!("{}: {}_", field.prompt(), buffer))
+            .style(Style::default().fg(Color::Yellow)),
+        _ => Paragraph::new("[q] Quit  [↑↓] Scroll  [Home] Top  [f] Type  [v] Value  [e] Fee  [x] Clear filters  [b] By type  [Space/p] Pause/Resume")
+            .style(Style::default().fg(Color::Gray)),
+    };
+    f.render_widget(footer.alignment(Alignment::Center), chunks[4]);
+}
+
+/// ✓/✗ for the receipt status, or REORGED while the block is orphaned
+pub fn status_span(tx: &TransactionInfo) -> Span<'static> {
+    if tx.reorged {
+        Span::styled("⟲ REORGED", Style::default().fg(Color::DarkGray).add_modifier(Modifier::BOLD))
+    } else if tx.status {
+        Span::styled("✓", Style::default().fg(Color::Green))
+    } else {
+        Span::styled("✗", Style::default().fg(Color::Red))
+    }
+}
+
+/// Strike through and grey out every span of a reorged transaction's rows
+pub fn grey_out_reorged<'a>(tx: &TransactionInfo, lines: Vec<Line<'a>>) -> Vec<Line<'a>> {
+    if !tx.reorged {
+        return lines;
+    }
+    let style = Style::default().fg(Color::DarkGray).add_modifier(Modifier::CROSSED_OUT);
+    lines
+        .into_iter()
+        .map(|line| Line::from(line.spans.into_iter().map(|span| Span::styled(span.content, style)).collect::<Vec<_>>()))
+        .collect()
+}
+
+/// ▲/▼/→ after the TPS figure, comparing the last 30s with the 30s before.
+/// Nothing until both windows have enough samples; "idle" when no
+/// transactions came in, rather than a ▼.
+pub fn tps_trend_span(trend: Option<TpsTrend>) -> Span<'static> {
+    match trend {
+        Some(TpsTrend::Up) => Span::styled(" ▲", Style::default().fg(Color::Green)),
+        Some(TpsTrend::Down) => Span::styled(" ▼", Style::default().fg(Color::Red)),
+        Some(TpsTrend::Steady) => Span::styled(" →", Style::default().fg(Color::Gray)),
+        Some(TpsTrend::Idle) => Span::styled(" idle", Style::default().fg(Color::DarkGray)),
+        None => Span::raw(""),
+    }
+}
+
+/// Height of the per-type breakdown panel: one row per type plus borders
+pub const TYPE_BREAKDOWN_HEIGHT: u16 = TransactionType::ALL.len() as u16 + 2;
+
+/// Counts and L2 fee share per transaction type. Types not seen yet show 0,
+/// so the panel doesn't change shape as transactions come in.
+pub fn type_breakdown_panel(stats: &Statistics) -> Paragraph<'static> {
+    let lines: Vec<Line> = stats
+        .type_breakdown()
+        .into_iter()
+        .map(|row| {
+            let type_color = match row.tx_type {
+                TransactionType::Transfer => Color::White,
+                TransactionType::Contract => Color::Cyan,
+                TransactionType::Entry => Color::Blue,
+                TransactionType::Unknown => Color::Gray,
+            };
+            Line::from(vec![
+                Span::styled(
+                    format!("{:<9}", row.tx_type.to_string()),
+                    Style::default().fg(type_color).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(format!("{:>8}", row.count), Style::default().fg(Color::White)),
+                Span::styled(format!(" ({:>3}%)", row.count_pct), Style::default().fg(Color::Gray)),
+                Span::raw("  │  "),
+                Span::styled("Fees: ", Style::default().fg(Color::Gray)),
+                Span::styled(format!("{:>12.4} iKAS", row.gas_fees_ikas), Style::default().fg(Color::Yellow)),
+                Span::styled(format!(" ({:>3}%)", row.fee_pct), Style::default().fg(Color::Gray)),
+            ])
+        })
+        .collect();
+
+    Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("By Type"))
+}
+
+/// "Reorgs: n" for the statistics header, highlighted once any were seen
+pub fn reorg_count_span(reorgs: u64) -> Span<'static> {
+    let color = if reorgs > 0 { Color::Red } else { Color::Gray };
+    Span::styled(format!("Reorgs: {}", reorgs), Style::default().fg(color))
+}
+
+/// "  │  Pending: n" for the statistics header; nothing when the node doesn't
+/// report its mempool, rather than a misleading 0
+pub fn pending_tx_spans(pending: Option<u64>) -> Vec<Span<'static>> {
+    match pending {
+        Some(pending) => vec![
+            Span::raw("  │  "),
+            Span::styled("Pending: ", Style::default().fg(Color::Gray)),
+            Span::styled(format!("{}", pending), Style::default().fg(Color::Yellow)),
+        ],
+        None => Vec::new(),
+    }
+}
+
+/// "PAUSED (+n buffered)" while paused, "+n new" after resuming
+pub fn feed_indicator(feed: &TransactionFeed, unseen_new: usize) -> Vec<Span<'static>> {
+    if feed.is_paused() {
+        vec![
+            Span::raw(" "),
+            Span::styled(" PAUSED ", Style::default().fg(Color::Black).bg(Color::Yellow).add_modifier(Modifier::BOLD)),
+            Span::styled(format!(" +{} buffered ", feed.pending_count()), Style::default().fg(Color::Yellow)),
+        ]
+    } else if unseen_new > 0 {
+        vec![Span::styled(format!(" +{} new ", unseen_new), Style::default().fg(Color::Green).add_modifier(Modifier::BOLD))]
+    } else {
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use ethers::types::U256;
+
+    fn tx(hash: &str) -> TransactionInfo {
+        TransactionInfo {
+            hash: hash.to_string(),
+            from: String::new(),
+            to: None,
+            value: U256::zero(),
+            gas_used: None,
+            gas_price: U256::zero(),
+            block_number: 0,
+            timestamp: Utc::now(),
+            status: true,
+            tx_type: TransactionType::Transfer,
+            l1_fee: None,
+            method: None,
+            reorged: false,
+        }
+    }
+
+    fn hashes(feed: &TransactionFeed) -> Vec<&str> {
+        feed.transactions().iter().map(|t| t.hash.as_str()).collect()
+    }
+
+    #[test]
+    fn test_feed_pause_buffers_and_resume_prepends_in_order() {
+        let mut feed = TransactionFeed::default();
+        feed.push(vec![tx("a"), tx("b")]);
+        assert_eq!(hashes(&feed), ["b", "a"]);
+
+        feed.pause();
+        feed.push(vec![tx("c")]);
+        feed.push(vec![tx("d"), tx("e")]);
+        assert_eq!(hashes(&feed), ["b", "a"]);
+        assert_eq!(feed.pending_count(), 3);
+
+        assert_eq!(feed.resume(), (3, 3));
+        assert_eq!(hashes(&feed), ["e", "d", "c", "b", "a"]);
+        assert_eq!(feed.pending_count(), 0);
+    }
+
+    #[test]
+    fn test_feed_reorg_marks_and_reinclusion_confirms() {
+        let mut feed = TransactionFeed::default();
+        feed.push(vec![tx("a"), tx("b")]);
+
+        assert_eq!(feed.mark_reorged(&["b".to_string(), "zz".to_string()]), 1);
+        assert!(feed.transactions()[0].reorged);
+        assert!(!feed.transactions()[1].reorged);
+
+        // Re-included in a later block: updated in place, not duplicated
+        let mut again = tx("b");
+        again.block_number = 7;
+        feed.push(vec![again]);
+        assert_eq!(hashes(&feed), ["b", "a"]);
+        assert!(!feed.transactions()[0].reorged);
+        assert_eq!(feed.transactions()[0].block_number, 7);
+    }
+
+    #[test]
+    fn test_feed_resume_respects_capacity() {
+        let mut feed = TransactionFeed::default();
+        feed.pause();
+        feed.push((0..FEED_CAPACITY + 20).map(|i| tx(&i.to_string())).collect());
+
+        let (total, shown) = feed.resume();
+        assert_eq!(total, FEED_CAPACITY + 20);
+        assert_eq!(shown, FEED_CAPACITY);
+        assert_eq!(feed.transactions().len(), FEED_CAPACITY);
+    }
+
+    #[test]
+    fn test_feed_filter_combines_type_value_and_fee() {
+        assert_eq!(RangeFilter::parse("1").unwrap(), RangeFilter { min: Some(1.0), max: None });
+        assert_eq!(RangeFilter::parse("<0.5").unwrap(), RangeFilter { min: None, max: Some(0.5) });
+        assert_eq!(RangeFilter::parse("1..10").unwrap(), RangeFilter { min: Some(1.0), max: Some(10.0) });
+        assert_eq!(RangeFilter::parse("  ").unwrap(), RangeFilter::default());
+        assert!(RangeFilter::parse("10..1").is_err());
+        assert!(RangeFilter::parse("-1").is_err());
+        assert!(RangeFilter::parse("abc").is_err());
+
+        let mut whale = tx("whale");
+        whale.value = U256::from(5u64) * U256::exp10(18);
+        let small = tx("small");
+        let mut contract = tx("contract");
+        contract.tx_type = TransactionType::Contract;
+        contract.value = whale.value;
+
+        let mut filter = FeedFilter::default();
+        assert!(!filter.is_active());
+        assert_eq!(filter.describe(), "All");
+
+        filter.value = RangeFilter::parse(">1").unwrap();
+        assert!(filter.matches(&whale) && filter.matches(&contract) && !filter.matches(&small));
+
+        filter.tx_type = TransactionFilter::Transfer;
+        assert!(filter.matches(&whale) && !filter.matches(&contract));
+        assert_eq!(filter.describe(), "Transfer, Value ≥ 1 iKAS");
+
+        // Zero-fee transactions drop out once a fee floor is set
+        filter.fee = RangeFilter::parse("0.001..").unwrap();
+        assert!(!filter.matches(&whale));
+    }
 }
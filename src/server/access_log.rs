@@ -0,0 +1,58 @@
+//! Structured access logging for the HTTP API
+//!
+//! One JSON line per request: method, path, status, latency and which token
+//! authenticated it. Only metadata is logged - never the query string,
+//! headers or bodies, which can carry tokens, wallet passwords or config
+//! secrets.
+
+use axum::{extract::Request, middleware::Next, response::Response};
+use std::time::Instant;
+use tracing_subscriber::EnvFilter;
+
+/// Used when RUST_LOG is unset: access lines only, framework noise off
+const DEFAULT_FILTER: &str = "igra_cli=info,igra_mgt=info,tower_http=warn";
+
+/// Which configured token authenticated a request. Set by the auth
+/// middleware on the response so the access log can report it.
+#[derive(Debug, Clone, Copy)]
+pub struct TokenId(pub &'static str);
+
+/// Install the JSON log subscriber (level from RUST_LOG)
+pub fn init() {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(DEFAULT_FILTER));
+
+    // Ignore the error if a subscriber is already installed
+    let _ = tracing_subscriber::fmt()
+        .json()
+        .flatten_event(true)
+        .with_current_span(false)
+        .with_span_list(false)
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr)
+        .try_init();
+}
+
+pub async fn access_log(request: Request, next: Next) -> Response {
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+    let start = Instant::now();
+
+    let response = next.run(request).await;
+
+    let token_id = response
+        .extensions()
+        .get::<TokenId>()
+        .map(|t| t.0)
+        .unwrap_or("none");
+
+    tracing::info!(
+        target: "igra_cli::access",
+        method = %method,
+        path = %path,
+        status = response.status().as_u16(),
+        latency_ms = start.elapsed().as_millis() as u64,
+        token_id,
+    );
+
+    response
+}
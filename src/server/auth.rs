@@ -1,7 +1,6 @@
-/// Authentication middleware for IGRA Web UI
+//! Authentication middleware for IGRA Web UI
 
 use axum::{
-    body::Body,
     extract::Request,
     http::{HeaderMap, StatusCode},
     middleware::Next,
@@ -10,6 +9,8 @@ use axum::{
 };
 use serde_json::json;
 
+use super::access_log::TokenId;
+
 /// Check if request has valid authentication token
 pub async fn auth_middleware(
     headers: HeaderMap,
@@ -33,13 +34,24 @@ pub async fn auth_middleware(
         None => None,
     };
 
+    // Browsers can't set headers on a WebSocket handshake, so upgrades may
+    // pass the token as ?token= instead
+    let query_token = if is_websocket_upgrade(&headers) {
+        request.uri().query().and_then(token_from_query)
+    } else {
+        None
+    };
+    let token = token.or(query_token.as_deref());
+
     // Get expected token from environment
     let expected_token = std::env::var("IGRA_WEB_TOKEN").ok();
 
     match (token, expected_token) {
-        (Some(provided), Some(expected)) if provided == expected => {
+        (Some(provided), Some(expected)) if constant_time_eq(provided.as_bytes(), expected.as_bytes()) => {
             // Token is valid, proceed with request
-            Ok(next.run(request).await)
+            let mut response = next.run(request).await;
+            response.extensions_mut().insert(TokenId("web"));
+            Ok(response)
         }
         (None, None) => {
             // No token configured, allow access (development mode)
@@ -53,40 +65,90 @@ pub async fn auth_middleware(
     }
 }
 
+fn is_websocket_upgrade(headers: &HeaderMap) -> bool {
+    headers
+        .get("Upgrade")
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.eq_ignore_ascii_case("websocket"))
+        .unwrap_or(false)
+}
+
+fn token_from_query(query: &str) -> Option<String> {
+    url::form_urlencoded::parse(query.as_bytes())
+        .find(|(key, _)| key == "token")
+        .map(|(_, value)| value.into_owned())
+        .filter(|t| !t.is_empty())
+}
+
+/// Gate for routes that move funds or change config. Requires `IGRA_WEB_ADMIN_TOKEN`; unlike
+/// `auth_middleware` there is no development-mode fallback, so with no admin
+/// token configured these routes always refuse.
+pub async fn admin_middleware(
+    headers: HeaderMap,
+    request: Request,
+    next: Next,
+) -> Result<Response, Response> {
+    let token = headers
+        .get("Authorization")
+        .and_then(|v| v.to_str().ok())
+        .map(|header| header.strip_prefix("Bearer ").unwrap_or(header));
+
+    let expected_token = std::env::var("IGRA_WEB_ADMIN_TOKEN").ok().filter(|t| !t.is_empty());
+
+    match (token, expected_token) {
+        (Some(provided), Some(expected)) if constant_time_eq(provided.as_bytes(), expected.as_bytes()) => {
+            let mut response = next.run(request).await;
+            response.extensions_mut().insert(TokenId("admin"));
+            Ok(response)
+        }
+        (_, None) => Err((
+            StatusCode::FORBIDDEN,
+            Json(json!({
+                "success": false,
+                "error": "Forbidden - IGRA_WEB_ADMIN_TOKEN is not configured on the server",
+                "code": "forbidden"
+            })),
+        )
+            .into_response()),
+        _ => Err(unauthorized_response()),
+    }
+}
+
+/// Compare tokens without short-circuiting on the first differing byte
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
 fn unauthorized_response() -> Response {
     (
         StatusCode::UNAUTHORIZED,
         Json(json!({
             "success": false,
-            "error": "Unauthorized - invalid or missing authentication token"
+            "error": "Unauthorized - invalid or missing authentication token",
+            "code": "unauthorized"
         })),
     )
         .into_response()
 }
 
-/// Generate a random secure token
-pub fn generate_token() -> String {
-    use rand::Rng;
-    const CHARSET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
-    const TOKEN_LEN: usize = 32;
-    let mut rng = rand::thread_rng();
-
-    (0..TOKEN_LEN)
-        .map(|_| {
-            let idx = rng.gen_range(0..CHARSET.len());
-            CHARSET[idx] as char
-        })
-        .collect()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
-    fn test_generate_token() {
-        let token = generate_token();
-        assert_eq!(token.len(), 32);
-        assert!(token.chars().all(|c| c.is_alphanumeric()));
+    fn test_token_from_query() {
+        assert_eq!(token_from_query("token=abc"), Some("abc".to_string()));
+        assert_eq!(token_from_query("x=1&token=abc"), Some("abc".to_string()));
+        assert_eq!(token_from_query("token="), None);
+        assert_eq!(token_from_query("x=1"), None);
+        assert_eq!(token_from_query("token=a%2Bb%3D%26c"), Some("a+b=&c".to_string()));
+        assert_eq!(token_from_query("token=a+b"), Some("a b".to_string()));
+    }
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"secret", b"secret"));
+        assert!(!constant_time_eq(b"secret", b"secreT"));
+        assert!(!constant_time_eq(b"secret", b"secret2"));
     }
 }
@@ -1,18 +1,18 @@
-/// API Request Handlers
-/// Reuses core business logic from existing modules
+//! API Request Handlers
+//! Reuses core business logic from existing modules
 
 use axum::{
     extract::{Path, Query},
     http::StatusCode,
+    response::{IntoResponse, Response},
     Json,
 };
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use sysinfo::System;
 
 use crate::core::{
-    ConfigManager, DockerManager,
-    wallet::WalletManager,
+    ConfigManager, DockerManager, Error as CoreError,
+    wallet::{SendFeePolicy, SendResult, WalletClientPool, WalletManager, WORKER_COUNT},
     storage,
     log_parser,
     updater,
@@ -27,6 +27,12 @@ pub struct ApiResponse<T> {
     success: bool,
     data: Option<T>,
     error: Option<String>,
+    /// Machine-readable error kind ("service_not_found", "docker_unavailable", ...)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    code: Option<&'static str>,
+    /// Present (true) when the server runs with `--dry-run`: nothing was changed
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    dry_run: bool,
 }
 
 impl<T> ApiResponse<T> {
@@ -35,18 +41,110 @@ impl<T> ApiResponse<T> {
             success: true,
             data: Some(data),
             error: None,
+            code: None,
+            dry_run: crate::core::dry_run::enabled(),
         }
     }
 
-    fn error(msg: String) -> Self {
+    fn error(msg: String, code: &'static str) -> Self {
         Self {
             success: false,
             data: None,
             error: Some(msg),
+            code: Some(code),
+            dry_run: crate::core::dry_run::enabled(),
         }
     }
 }
 
+/// Every handler error: a status and an `ApiResponse` with `error` and `code`
+type ApiError = (StatusCode, Json<ApiResponse<()>>);
+
+fn api_error(status: StatusCode, msg: impl Into<String>) -> ApiError {
+    let code = match status {
+        StatusCode::BAD_REQUEST => "bad_request",
+        StatusCode::UNAUTHORIZED => "unauthorized",
+        StatusCode::FORBIDDEN => "forbidden",
+        StatusCode::NOT_FOUND => "not_found",
+        StatusCode::CONFLICT => "conflict",
+        StatusCode::SERVICE_UNAVAILABLE => "unavailable",
+        _ => "internal",
+    };
+    api_error_code(status, code, msg)
+}
+
+fn api_error_code(status: StatusCode, code: &'static str, msg: impl Into<String>) -> ApiError {
+    (status, Json(ApiResponse::error(msg.into(), code)))
+}
+
+/// 409 for actions a dry run can't stand in for (they would stop or replace
+/// the server itself)
+fn refuse_in_dry_run(action: &str) -> Result<(), ApiError> {
+    if crate::core::dry_run::enabled() {
+        return Err(api_error_code(
+            StatusCode::CONFLICT,
+            "dry_run",
+            crate::core::dry_run::tag(&format!("{} is not available in a dry run", action)),
+        ));
+    }
+    Ok(())
+}
+
+/// 500 with a fixed message. The details (paths, daemon output) only go to
+/// the server log.
+fn internal_error(err: impl std::fmt::Display) -> ApiError {
+    tracing::error!("{:#}", err);
+    api_error_code(StatusCode::INTERNAL_SERVER_ERROR, "internal", "Internal server error")
+}
+
+/// Status and client-safe message for a core error. Docker socket paths and
+/// wallet daemon endpoints stay in the server log.
+fn core_error(err: CoreError) -> ApiError {
+    match err {
+        CoreError::DockerUnavailable(e) => {
+            tracing::warn!("{}", e);
+            api_error_code(StatusCode::SERVICE_UNAVAILABLE, "docker_unavailable", "Docker is not available on the server")
+        }
+        CoreError::DockerApi(bollard::errors::Error::DockerResponseServerError { status_code: 404, .. }) => {
+            api_error_code(StatusCode::NOT_FOUND, "not_found", "Container or image not found")
+        }
+        CoreError::ServiceNotFound(service) => {
+            api_error_code(StatusCode::NOT_FOUND, "service_not_found", format!("Service {} not found", service))
+        }
+        CoreError::ServiceNotRunning { service, .. } => {
+            api_error_code(StatusCode::CONFLICT, "service_not_running", format!("Service {} is not running", service))
+        }
+        CoreError::WalletUnavailable { worker, message } => {
+            tracing::warn!("Wallet {} unavailable: {}", worker, message);
+            api_error_code(StatusCode::SERVICE_UNAVAILABLE, "wallet_unavailable", format!("Wallet {} is unavailable", worker))
+        }
+        CoreError::WalletLocked { worker, .. } => {
+            api_error_code(StatusCode::FORBIDDEN, "wallet_locked", format!("Wallet {} rejected the configured password", worker))
+        }
+        CoreError::WalletSyncing { worker, .. } => {
+            api_error_code(StatusCode::SERVICE_UNAVAILABLE, "wallet_syncing", format!("Wallet {} is still syncing with kaspad", worker))
+        }
+        CoreError::InvalidInput(message) => api_error_code(StatusCode::BAD_REQUEST, "invalid_input", message),
+        CoreError::InvalidCompose(problems) => {
+            tracing::warn!("Invalid compose configuration: {}", problems.join("; "));
+            api_error_code(
+                StatusCode::CONFLICT,
+                "invalid_compose",
+                "Compose configuration is invalid; run `igra-cli config validate --compose` on the server",
+            )
+        }
+        other => internal_error(other),
+    }
+}
+
+/// 404 for worker ids outside kaswallet-0..4
+fn check_worker(worker: usize) -> Result<(), ApiError> {
+    if worker >= WORKER_COUNT {
+        return Err(api_error(StatusCode::NOT_FOUND, format!("Unknown worker {}", worker)));
+    }
+    Ok(())
+}
+
 #[derive(Serialize)]
 pub struct PortMapping {
     host_port: String,
@@ -81,16 +179,39 @@ pub struct ServiceInfo {
 
 #[derive(Deserialize)]
 pub struct LogsQuery {
-    #[serde(default = "default_tail")]
-    tail: usize,
     #[serde(default)]
+    tail: Option<usize>,
+    /// Accepted but unused: live logs stream over /ws/logs
+    #[serde(default)]
+    #[allow(dead_code)]
     follow: bool,
+    #[serde(default)]
+    level: Option<String>,  // Filter: ERROR, WARN, INFO, DEBUG, TRACE
+    #[serde(default)]
+    since: Option<String>,  // RFC3339, "YYYY-MM-DD HH:MM", "HH:MM" or "30m"
+    #[serde(default)]
+    format: Option<String>, // "json" for parsed entries, plain text otherwise
 }
 
 fn default_tail() -> usize {
     100
 }
 
+/// Upper bound on lines fetched per log request, whatever the client asks for
+const MAX_LOG_TAIL: usize = 5000;
+
+/// Parsed log history, newest entry first
+#[derive(Serialize)]
+pub struct LogHistory {
+    entries: Vec<ParsedLogLine>,
+    /// Lines fetched from docker before the level filter
+    limit: usize,
+    /// More lines matched than were fetched (tail was capped or hit the cap)
+    truncated: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    note: Option<String>,
+}
+
 #[derive(Serialize)]
 pub struct ParsedLogLine {
     timestamp: String,
@@ -113,20 +234,23 @@ pub struct ParsedLogsQuery {
 // Service Management Handlers
 // ============================================================================
 
-pub async fn get_services() -> Result<Json<ApiResponse<Vec<ServiceInfo>>>, StatusCode> {
+pub async fn get_services() -> Result<Json<ApiResponse<Vec<ServiceInfo>>>, ApiError> {
     let docker = DockerManager::new().await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(core_error)?;
 
     let containers = docker.list_containers().await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(core_error)?;
+
+    // Stats for all running containers in one batch
+    let all_stats = std::sync::Arc::new(docker.get_all_container_stats().await.unwrap_or_default());
 
     // Process all containers in parallel for speed
     let tasks: Vec<_> = containers.into_iter().map(|c| {
+        let all_stats = all_stats.clone();
+        let docker = docker.clone();
         tokio::spawn(async move {
-            let docker = DockerManager::new().await.ok()?;
-
             // Get stats for resource metrics
-            let stats = docker.get_container_stats(&c.name).await.ok().flatten();
+            let stats = all_stats.get(&c.name).cloned();
 
             let (cpu_percent, memory_mb, network_rx_mb, network_tx_mb, container_size_mb, volume_size_mb) = if let Some(s) = stats {
                 (
@@ -162,7 +286,7 @@ pub async fn get_services() -> Result<Json<ApiResponse<Vec<ServiceInfo>>>, Statu
 
             // Fetch last 30 lines of logs and parse metrics (fast - only for key services)
             let (status_text, primary_metric, secondary_metric, is_healthy_metric) = if c.status.contains("Up") {
-                let logs = docker.get_logs(&c.name, Some(30)).await.unwrap_or_default();
+                let logs = docker.get_logs(&c.name, Some(30), false).await.unwrap_or_default();
                 let metrics = log_parser::parse_service_logs(&c.name, &logs);
                 (metrics.status_text, metrics.primary_metric, metrics.secondary_metric, metrics.is_healthy)
             } else {
@@ -203,62 +327,114 @@ pub async fn get_services() -> Result<Json<ApiResponse<Vec<ServiceInfo>>>, Statu
 
 pub async fn start_service(
     Path(name): Path<String>,
-) -> Result<Json<ApiResponse<String>>, StatusCode> {
+) -> Result<Json<ApiResponse<String>>, ApiError> {
     let docker = DockerManager::new().await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(core_error)?;
 
     docker.start_service(&name).await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(core_error)?;
 
-    Ok(Json(ApiResponse::ok(format!("Service {} started", name))))
+    Ok(Json(ApiResponse::ok(crate::core::dry_run::tag(&format!("Service {} started", name)))))
 }
 
 pub async fn stop_service(
     Path(name): Path<String>,
-) -> Result<Json<ApiResponse<String>>, StatusCode> {
+) -> Result<Json<ApiResponse<String>>, ApiError> {
     let docker = DockerManager::new().await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(core_error)?;
 
     docker.stop_service(&name).await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(core_error)?;
 
-    Ok(Json(ApiResponse::ok(format!("Service {} stopped", name))))
+    Ok(Json(ApiResponse::ok(crate::core::dry_run::tag(&format!("Service {} stopped", name)))))
 }
 
 pub async fn restart_service(
     Path(name): Path<String>,
-) -> Result<Json<ApiResponse<String>>, StatusCode> {
+) -> Result<Json<ApiResponse<String>>, ApiError> {
     let docker = DockerManager::new().await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(core_error)?;
 
     docker.restart_service(&name).await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(core_error)?;
 
-    Ok(Json(ApiResponse::ok(format!("Service {} restarted", name))))
+    Ok(Json(ApiResponse::ok(crate::core::dry_run::tag(&format!("Service {} restarted", name)))))
 }
 
 pub async fn get_logs(
     Path(name): Path<String>,
     Query(params): Query<LogsQuery>,
-) -> Result<Json<ApiResponse<String>>, StatusCode> {
+) -> Result<Response, ApiError> {
     let docker = DockerManager::new().await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(core_error)?;
 
-    let logs = docker.get_logs(&name, Some(params.tail)).await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    // An explicit tail is capped; --since without a tail fetches up to the cap
+    let limit = match (params.tail, &params.since) {
+        (Some(tail), _) => tail.min(MAX_LOG_TAIL),
+        (None, Some(_)) => MAX_LOG_TAIL,
+        (None, None) => default_tail(),
+    };
+    let tail_capped = params.tail.is_some_and(|tail| tail > MAX_LOG_TAIL);
+
+    let (logs, note) = match params.since.as_deref() {
+        Some(since) => {
+            let since = crate::utils::parse_since(since, chrono::Local::now())
+                .map_err(|e| api_error(StatusCode::BAD_REQUEST, e.to_string()))?;
+            docker.get_logs_since(&name, since, Some(limit), false).await
+                .map_err(core_error)?
+        }
+        None => {
+            let logs = docker.get_logs(&name, Some(limit), false).await
+                .map_err(core_error)?;
+            (logs, None)
+        }
+    };
+
+    if params.format.as_deref() != Some("json") && params.level.is_none() {
+        return Ok(Json(ApiResponse::ok(logs)).into_response());
+    }
+
+    let mut history = build_log_history(&logs, params.level.as_deref(), limit, tail_capped);
+    history.note = note;
+    Ok(Json(ApiResponse::ok(history)).into_response())
+}
 
-    Ok(Json(ApiResponse::ok(logs)))
+/// Parse raw `docker compose logs` output into newest-first entries
+fn build_log_history(logs: &str, level: Option<&str>, limit: usize, tail_capped: bool) -> LogHistory {
+    let level_upper = level.map(|l| l.trim().to_uppercase());
+
+    let lines: Vec<&str> = logs.lines().filter(|line| !line.trim().is_empty()).collect();
+    let truncated = tail_capped || (limit == MAX_LOG_TAIL && lines.len() >= limit);
+
+    let entries = lines
+        .iter()
+        .rev()
+        .map(|line| log_parser::parse_docker_log_line(line))
+        .filter(|parsed| {
+            level_upper
+                .as_deref()
+                .is_none_or(|level| parsed.level.to_string().trim() == level)
+        })
+        .map(|parsed| ParsedLogLine {
+            timestamp: parsed.timestamp,
+            level: parsed.level.to_string().trim().to_string(),
+            module: parsed.module_short,
+            message: parsed.message,
+        })
+        .collect();
+
+    LogHistory { entries, limit, truncated, note: None }
 }
 
 pub async fn get_logs_parsed(
     Path(name): Path<String>,
     Query(params): Query<ParsedLogsQuery>,
-) -> Result<Json<ApiResponse<Vec<ParsedLogLine>>>, StatusCode> {
+) -> Result<Json<ApiResponse<Vec<ParsedLogLine>>>, ApiError> {
     let docker = DockerManager::new().await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(core_error)?;
 
-    let logs = docker.get_logs(&name, Some(params.tail)).await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let logs = docker.get_logs(&name, Some(params.tail.min(MAX_LOG_TAIL)), false).await
+        .map_err(core_error)?;
 
     // Parse each log line
     let mut parsed_logs: Vec<ParsedLogLine> = logs
@@ -293,101 +469,264 @@ pub async fn get_logs_parsed(
 // Wallet Handlers
 // ============================================================================
 
-pub async fn get_wallets() -> Result<Json<ApiResponse<Vec<crate::core::wallet::WalletInfo>>>, StatusCode> {
-    let wallet_manager = WalletManager::new()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+/// Wallet manager backed by a process-wide gRPC connection pool
+pub(super) fn wallet_manager() -> crate::core::error::Result<WalletManager> {
+    static POOL: std::sync::OnceLock<WalletClientPool> = std::sync::OnceLock::new();
+    WalletManager::with_pool(POOL.get_or_init(WalletClientPool::new).clone())
+}
+
+pub async fn get_wallets() -> Result<Json<ApiResponse<Vec<crate::core::wallet::WalletInfo>>>, ApiError> {
+    let wallet_manager = wallet_manager()
+        .map_err(core_error)?;
 
     let wallets = wallet_manager.list_wallets().await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(core_error)?;
 
     Ok(Json(ApiResponse::ok(wallets)))
 }
 
 pub async fn get_wallet_balance(
     Path(id): Path<usize>,
-) -> Result<Json<ApiResponse<String>>, StatusCode> {
-    let wallet_manager = WalletManager::new()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+) -> Result<Json<ApiResponse<String>>, ApiError> {
+    check_worker(id)?;
+    let wallet_manager = wallet_manager()
+        .map_err(core_error)?;
 
     let balance = wallet_manager.get_balance(id).await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(core_error)?;
 
-    Ok(Json(ApiResponse::ok(format!("{:.2} KAS", balance))))
+    Ok(Json(ApiResponse::ok(format!("{:.2} {}", balance, wallet_manager.network().currency_label()))))
 }
 
 pub async fn get_wallet_detail(
     Path(id): Path<usize>,
-) -> Result<Json<ApiResponse<Vec<crate::core::wallet::UtxoInfo>>>, StatusCode> {
-    let wallet_manager = WalletManager::new()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+) -> Result<Json<ApiResponse<Vec<crate::core::wallet::UtxoInfo>>>, ApiError> {
+    check_worker(id)?;
+    let wallet_manager = wallet_manager()
+        .map_err(core_error)?;
 
     let utxos = wallet_manager.get_utxos(id).await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(core_error)?;
 
     Ok(Json(ApiResponse::ok(utxos)))
 }
 
+#[derive(Deserialize)]
+pub struct SendRequest {
+    to: String,
+    /// Decimal KAS as a string ("1.5") so no float rounding happens
+    #[serde(default)]
+    amount: Option<String>,
+    #[serde(default)]
+    send_all: bool,
+    #[serde(default)]
+    fee_policy: Option<SendFeePolicy>,
+}
+
+/// Send funds from a worker wallet. Only routed with `serve --allow-destructive`
+/// and behind the admin token. The request body carries no secrets (the wallet
+/// password comes from .env) but is still never logged.
+pub async fn send_wallet_transaction(
+    Path(worker): Path<usize>,
+    Json(req): Json<SendRequest>,
+) -> Result<Json<ApiResponse<SendResult>>, ApiError> {
+    check_worker(worker)?;
+
+    let wallet_manager = wallet_manager()
+        .map_err(core_error)?;
+
+    let to = req.to.trim();
+    if !crate::utils::is_valid_kaspa_address(to, wallet_manager.network()) {
+        return Err(api_error(StatusCode::BAD_REQUEST, "Invalid destination address"));
+    }
+
+    let amount_sompi = match (req.send_all, req.amount.as_deref()) {
+        (true, _) => 0,
+        (false, Some(amount)) => {
+            let sompi = crate::utils::kas_to_sompi(amount)
+                .map_err(|e| api_error(StatusCode::BAD_REQUEST, e.to_string()))?;
+            if sompi == 0 {
+                return Err(api_error(StatusCode::BAD_REQUEST, "Amount must be greater than 0"));
+            }
+            sompi
+        }
+        (false, None) => {
+            return Err(api_error(StatusCode::BAD_REQUEST, "amount is required unless send_all is set"));
+        }
+    };
+
+    let fee_policy = req.fee_policy.unwrap_or_default();
+    fee_policy.validate()
+        .map_err(core_error)?;
+
+    let config = ConfigManager::load_from_project()
+        .map_err(internal_error)?;
+    // A prompt source can't be answered over HTTP; resolve() then only
+    // succeeds if falling back to .env is allowed. The reason can name a
+    // password file, so it is logged rather than returned.
+    let password = crate::utils::AppConfig::wallet_passwords()
+        .resolve(worker, &config, None)
+        .map_err(|e| {
+            tracing::warn!("{:#}", e);
+            api_error_code(
+                StatusCode::CONFLICT,
+                "wallet_password_unavailable",
+                format!("No password for wallet {} is available to the server", worker),
+            )
+        })?;
+
+    let result = wallet_manager
+        .send(worker, to, amount_sompi, &password, req.send_all, fee_policy)
+        .await
+        .map_err(core_error)?;
+
+    Ok(Json(ApiResponse::ok(result)))
+}
+
+/// Stand-in for destructive routes when the server runs without --allow-destructive
+pub async fn destructive_disabled() -> ApiError {
+    api_error(
+        StatusCode::FORBIDDEN,
+        "Disabled - restart the server with --allow-destructive to enable",
+    )
+}
+
 // ============================================================================
 // Storage Handlers
 // ============================================================================
 
-pub async fn get_storage() -> Result<Json<ApiResponse<storage::StorageAnalysis>>, StatusCode> {
+pub async fn get_storage() -> Result<Json<ApiResponse<storage::StorageAnalysis>>, ApiError> {
     let analysis = storage::analyze_storage().await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(internal_error)?;
 
     Ok(Json(ApiResponse::ok(analysis)))
 }
 
-pub async fn get_storage_history() -> Result<Json<ApiResponse<Vec<storage::StorageMeasurement>>>, StatusCode> {
+pub async fn get_storage_history() -> Result<Json<ApiResponse<Vec<storage::StorageMeasurement>>>, ApiError> {
     let history = storage::StorageHistory::load()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(internal_error)?;
 
     Ok(Json(ApiResponse::ok(history.measurements)))
 }
 
-pub async fn prune_storage() -> Result<Json<ApiResponse<String>>, StatusCode> {
+pub async fn prune_storage() -> Result<Json<ApiResponse<String>>, ApiError> {
+    let program = crate::core::docker::runtime_program();
+    if crate::core::dry_run::skip(|| format!("{} system prune -f --volumes", program)) {
+        return Ok(Json(ApiResponse::ok(crate::core::dry_run::tag("Prune skipped"))));
+    }
+
     // Run docker system prune to clean up build cache
-    let output = tokio::process::Command::new("docker")
-        .args(&["system", "prune", "-f", "--volumes"])
+    let output = tokio::process::Command::new(program)
+        .args(["system", "prune", "-f", "--volumes"])
         .output()
         .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(internal_error)?;
 
     if output.status.success() {
         let stdout = String::from_utf8_lossy(&output.stdout);
         Ok(Json(ApiResponse::ok(format!("Prune completed: {}", stdout))))
     } else {
-        Err(StatusCode::INTERNAL_SERVER_ERROR)
+        Err(internal_error(format!("docker system prune failed: {}", String::from_utf8_lossy(&output.stderr))))
     }
 }
 
 pub async fn truncate_container_log(
     Path(container_id): Path<String>,
-) -> Result<Json<ApiResponse<String>>, StatusCode> {
+) -> Result<Json<ApiResponse<String>>, ApiError> {
     storage::truncate_container_log(&container_id)
         .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(internal_error)?;
 
-    Ok(Json(ApiResponse::ok(format!(
+    Ok(Json(ApiResponse::ok(crate::core::dry_run::tag(&format!(
         "Container log truncated successfully: {}",
         container_id
-    ))))
+    )))))
 }
 
 // ============================================================================
 // Configuration Handlers
 // ============================================================================
 
-pub async fn get_config() -> Result<Json<ApiResponse<HashMap<String, String>>>, StatusCode> {
+/// The config with secrets masked, as PATCH returns it. The map includes the
+/// secrets store, so the raw values must never leave the server.
+pub async fn get_config() -> Result<Json<ApiResponse<HashMap<String, String>>>, ApiError> {
     let config_manager = ConfigManager::load_from_project()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(internal_error)?;
+
+    Ok(Json(ApiResponse::ok(masked_config(&config_manager))))
+}
 
-    let config = config_manager.to_map();
+/// Keys the web UI may change. Secrets (wallet passwords, JWT secrets, RPC
+/// tokens, the OVH application secret and consumer key) are deliberately
+/// absent: they are set from the CLI, not over HTTP. So is NETWORK, which
+/// moves the node and its wallets to another chain.
+const API_EDITABLE_KEYS: &[&str] = &[
+    "NODE_ID",
+    "IGRA_ORCHESTRA_DOMAIN",
+    "IGRA_ORCHESTRA_DOMAIN_EMAIL",
+    "OVH_ENDPOINT",
+    "OVH_APPLICATION_KEY",
+];
 
-    Ok(Json(ApiResponse::ok(config)))
+#[derive(Deserialize)]
+pub struct ConfigUpdate {
+    key: String,
+    value: String,
 }
 
-pub async fn get_system_info() -> Result<Json<ApiResponse<crate::app::SystemResources>>, StatusCode> {
+/// Config map with every sensitive value replaced by a mask
+fn masked_config(config: &ConfigManager) -> HashMap<String, String> {
+    config
+        .to_map()
+        .into_iter()
+        .map(|(k, v)| {
+            let v = crate::utils::mask_config_value(&k, &v);
+            (k, v)
+        })
+        .collect()
+}
+
+/// Check a requested change against the allowlist and the value rules
+fn check_config_update(config: &ConfigManager, key: &str, value: &str) -> Result<(), ApiError> {
+    if !API_EDITABLE_KEYS.contains(&key) {
+        return Err(api_error(StatusCode::FORBIDDEN, format!("{} cannot be changed over the API", key)));
+    }
+    if config.file_value(key).is_none() {
+        return Err(api_error(StatusCode::BAD_REQUEST, format!("{} is not defined in .env", key)));
+    }
+    if let Some(err) = ConfigManager::validate_value(key, value) {
+        return Err(api_error(StatusCode::BAD_REQUEST, format!("{}: {}", key, err)));
+    }
+    Ok(())
+}
+
+/// Update one allowlisted .env value (admin token). Saved atomically;
+/// the response is the masked config, so secrets are never echoed back.
+pub async fn update_config(
+    Json(req): Json<ConfigUpdate>,
+) -> Result<Json<ApiResponse<HashMap<String, String>>>, ApiError> {
+    let mut config = ConfigManager::load_from_project()
+        .map_err(internal_error)?;
+
+    let value = req.value.trim();
+    check_config_update(&config, &req.key, value)?;
+
+    // Reject changes that make the config as a whole invalid
+    let before = config.validate();
+    config.set(req.key.clone(), value);
+    let new_errors: Vec<String> = config.validate().into_iter().filter(|e| !before.contains(e)).collect();
+    if !new_errors.is_empty() {
+        return Err(api_error(StatusCode::BAD_REQUEST, new_errors.join("; ")));
+    }
+
+    config
+        .save()
+        .map_err(internal_error)?;
+
+    Ok(Json(ApiResponse::ok(masked_config(&config))))
+}
+
+pub async fn get_system_info() -> Result<Json<ApiResponse<crate::app::SystemResources>>, ApiError> {
     let system_resources = crate::app::App::collect_system_resources();
     Ok(Json(ApiResponse::ok(system_resources)))
 }
@@ -395,16 +734,19 @@ pub async fn get_system_info() -> Result<Json<ApiResponse<crate::app::SystemReso
 #[derive(Serialize)]
 pub struct RpcToken {
     pub index: usize,
+    /// Masked (first and last characters only); None when the slot is unset
     pub token: Option<String>,
 }
 
-pub async fn get_rpc_tokens() -> Result<Json<ApiResponse<Vec<RpcToken>>>, StatusCode> {
+/// Which token slots are set, with each token masked: the route is public,
+/// and full tokens are read with `igra-cli rpc tokens`
+pub async fn get_rpc_tokens() -> Result<Json<ApiResponse<Vec<RpcToken>>>, ApiError> {
     let config = ConfigManager::load_from_project()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(internal_error)?;
 
     let tokens: Vec<RpcToken> = config.get_rpc_tokens()
         .into_iter()
-        .map(|(index, token)| RpcToken { index, token })
+        .map(|(index, token)| RpcToken { index, token: token.map(|t| crate::utils::mask_token(&t)) })
         .collect();
 
     Ok(Json(ApiResponse::ok(tokens)))
@@ -416,9 +758,9 @@ pub struct SslInfo {
     pub has_ovh_config: bool,
 }
 
-pub async fn get_ssl_info() -> Result<Json<ApiResponse<SslInfo>>, StatusCode> {
+pub async fn get_ssl_info() -> Result<Json<ApiResponse<SslInfo>>, ApiError> {
     let config = ConfigManager::load_from_project()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(internal_error)?;
 
     let domain_config = config.get_domain_config();
     let info = SslInfo {
@@ -433,8 +775,109 @@ pub async fn get_ssl_info() -> Result<Json<ApiResponse<SslInfo>>, StatusCode> {
 // Monitoring Handlers
 // ============================================================================
 
-pub async fn health_check() -> Result<Json<ApiResponse<String>>, StatusCode> {
-    Ok(Json(ApiResponse::ok("healthy".to_string())))
+/// How long a `/api/health` result is reused, so frequent probes don't load the node
+const HEALTH_CACHE_TTL: std::time::Duration = std::time::Duration::from_secs(2);
+
+#[derive(Serialize, Clone)]
+pub struct ComponentHealth {
+    name: String,
+    healthy: bool,
+    latency_ms: u64,
+    detail: String,
+}
+
+impl From<crate::core::health::HealthResult> for ComponentHealth {
+    fn from(r: crate::core::health::HealthResult) -> Self {
+        Self {
+            name: r.name,
+            healthy: r.healthy,
+            latency_ms: r.latency.as_millis() as u64,
+            detail: r.detail,
+        }
+    }
+}
+
+#[derive(Serialize, Clone)]
+pub struct HealthReport {
+    /// "healthy"; "degraded" when igra-cli works but a node component
+    /// doesn't; "unavailable" when igra-cli itself can't reach its config or Docker
+    status: &'static str,
+    /// igra-cli's own dependencies (project config, Docker)
+    manager: ComponentHealth,
+    /// Node components; empty when the manager check failed
+    components: Vec<ComponentHealth>,
+    checked_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl HealthReport {
+    fn status_code(&self) -> StatusCode {
+        if self.status == "healthy" {
+            StatusCode::OK
+        } else {
+            StatusCode::SERVICE_UNAVAILABLE
+        }
+    }
+}
+
+/// Probe for load balancers and orchestrators: 200 when healthy, 503 when
+/// degraded or unavailable, with the component statuses either way
+pub async fn health_check() -> Response {
+    static CACHE: std::sync::OnceLock<tokio::sync::Mutex<Option<(std::time::Instant, HealthReport)>>> =
+        std::sync::OnceLock::new();
+
+    // Concurrent probes queue on the lock and share the one in flight
+    let mut cached = CACHE.get_or_init(Default::default).lock().await;
+    let report = match cached.as_ref() {
+        Some((at, report)) if at.elapsed() < HEALTH_CACHE_TTL => report.clone(),
+        _ => {
+            let report = check_health().await;
+            *cached = Some((std::time::Instant::now(), report.clone()));
+            report
+        }
+    };
+
+    (report.status_code(), Json(ApiResponse::ok(report))).into_response()
+}
+
+async fn check_health() -> HealthReport {
+    let start = std::time::Instant::now();
+    let running = async {
+        ConfigManager::load_from_project().map_err(|e| e.to_string())?;
+        let docker = DockerManager::new().await.map_err(|e| e.to_string())?;
+        docker.running_container_names().await.map_err(|e| e.to_string())
+    }
+    .await;
+
+    let manager = |healthy: bool, detail: String| ComponentHealth {
+        name: "igra-cli".to_string(),
+        healthy,
+        latency_ms: start.elapsed().as_millis() as u64,
+        detail,
+    };
+
+    match running {
+        Ok(running) => {
+            let manager = manager(true, format!("{} containers running", running.len()));
+            let components: Vec<ComponentHealth> = crate::core::HealthChecker::new()
+                .check_node(&running)
+                .await
+                .into_iter()
+                .map(ComponentHealth::from)
+                .collect();
+            let status = if components.iter().all(|c| c.healthy) { "healthy" } else { "degraded" };
+            HealthReport { status, manager, components, checked_at: chrono::Utc::now() }
+        }
+        Err(e) => {
+            // Paths and socket addresses stay in the server log
+            tracing::warn!("Health check: {}", e);
+            HealthReport {
+                status: "unavailable",
+                manager: manager(false, "config or Docker unavailable".to_string()),
+                components: Vec::new(),
+                checked_at: chrono::Utc::now(),
+            }
+        }
+    }
 }
 
 #[derive(Serialize)]
@@ -446,13 +889,13 @@ pub struct MetricsInfo {
     docker_images: usize,
 }
 
-pub async fn get_metrics() -> Result<Json<ApiResponse<MetricsInfo>>, StatusCode> {
+pub async fn get_metrics() -> Result<Json<ApiResponse<MetricsInfo>>, ApiError> {
     let docker = DockerManager::new().await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(core_error)?;
 
     // Get system metrics (simplified - you can expand this)
     let containers = docker.list_containers().await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(core_error)?;
 
     let metrics = MetricsInfo {
         system_cpu: 0.0, // TODO: Implement with sysinfo
@@ -476,12 +919,12 @@ pub struct ProfileInfo {
     services: Vec<String>,
 }
 
-pub async fn get_profiles() -> Result<Json<ApiResponse<Vec<ProfileInfo>>>, StatusCode> {
+pub async fn get_profiles() -> Result<Json<ApiResponse<Vec<ProfileInfo>>>, ApiError> {
     let docker = DockerManager::new().await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(core_error)?;
 
     let containers = docker.list_containers().await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(core_error)?;
 
     let active_profiles = DockerManager::get_active_profiles_from_list(&containers);
 
@@ -512,26 +955,31 @@ pub async fn get_profiles() -> Result<Json<ApiResponse<Vec<ProfileInfo>>>, Statu
 
 pub async fn start_profile(
     Path(name): Path<String>,
-) -> Result<Json<ApiResponse<String>>, StatusCode> {
+) -> Result<Json<ApiResponse<String>>, ApiError> {
     let docker = DockerManager::new().await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(core_error)?;
 
-    docker.start_profile(&name).await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let warnings = crate::core::backend::container_backend(docker).start_profile(&name).await
+        .map_err(core_error)?;
 
-    Ok(Json(ApiResponse::ok(format!("Profile {} started", name))))
+    let message = if warnings.is_empty() {
+        format!("Profile {} started", name)
+    } else {
+        format!("Profile {} started (warnings: {})", name, warnings.join("; "))
+    };
+    Ok(Json(ApiResponse::ok(crate::core::dry_run::tag(&message))))
 }
 
 pub async fn stop_profile(
     Path(name): Path<String>,
-) -> Result<Json<ApiResponse<String>>, StatusCode> {
+) -> Result<Json<ApiResponse<String>>, ApiError> {
     let docker = DockerManager::new().await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(core_error)?;
 
-    docker.stop_profile(&name).await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    crate::core::backend::container_backend(docker).stop_profile(&name).await
+        .map_err(core_error)?;
 
-    Ok(Json(ApiResponse::ok(format!("Profile {} stopped", name))))
+    Ok(Json(ApiResponse::ok(crate::core::dry_run::tag(&format!("Profile {} stopped", name)))))
 }
 
 // ============================================================================
@@ -552,6 +1000,7 @@ pub struct TransactionInfo {
     status: bool,
     tx_type: String,
     l1_fee_kas: Option<f64>,
+    method: Option<String>,
 }
 
 impl From<L2TransactionInfo> for TransactionInfo {
@@ -570,6 +1019,7 @@ impl From<L2TransactionInfo> for TransactionInfo {
             status: tx.status,
             tx_type: format!("{:?}", tx.tx_type),
             l1_fee_kas: tx.l1_fee,
+            method: tx.method,
         }
     }
 }
@@ -615,12 +1065,12 @@ fn default_tx_limit() -> usize {
 
 pub async fn get_transactions(
     Query(params): Query<TransactionsQuery>,
-) -> Result<Json<ApiResponse<Vec<TransactionInfo>>>, StatusCode> {
+) -> Result<Json<ApiResponse<Vec<TransactionInfo>>>, ApiError> {
     let monitor = TransactionMonitor::new().await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(internal_error)?;
 
     let transactions = monitor.poll_new_transactions().await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(internal_error)?;
 
     // Convert and filter
     let mut converted: Vec<TransactionInfo> = transactions
@@ -642,9 +1092,9 @@ pub async fn get_transactions(
     Ok(Json(ApiResponse::ok(converted)))
 }
 
-pub async fn get_transaction_stats() -> Result<Json<ApiResponse<TransactionStats>>, StatusCode> {
+pub async fn get_transaction_stats() -> Result<Json<ApiResponse<TransactionStats>>, ApiError> {
     let monitor = TransactionMonitor::new().await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(internal_error)?;
 
     let stats = monitor.get_statistics().await;
 
@@ -657,9 +1107,9 @@ pub async fn get_transaction_stats() -> Result<Json<ApiResponse<TransactionStats
 
 /// Check for updates from GitHub releases
 /// Uses core::updater module - same business logic as TUI and CLI
-pub async fn get_version_info() -> Result<Json<ApiResponse<updater::VersionInfo>>, StatusCode> {
+pub async fn get_version_info() -> Result<Json<ApiResponse<updater::VersionInfo>>, ApiError> {
     let version_info = updater::check_for_updates().await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(internal_error)?;
 
     Ok(Json(ApiResponse::ok(version_info)))
 }
@@ -672,100 +1122,24 @@ pub struct UpdateStatus {
 }
 
 /// Trigger automatic update
-/// Downloads latest release, installs it, and restarts the service
-pub async fn trigger_update() -> Result<Json<ApiResponse<UpdateStatus>>, StatusCode> {
-    use std::process::Command;
-    use std::path::Path;
-    use std::fs;
-
-    // Download latest release to /tmp
-    let download_path = Path::new("/tmp/igra-cli-update");
-
-    match updater::download_latest_release(download_path).await {
-        Ok(_) => {
-            // Extract the binary from the tarball to a temp location
-            let extract_result = Command::new("tar")
-                .args(&["-xzf", download_path.to_str().unwrap(), "-C", "/tmp"])
-                .output();
-
-            match extract_result {
-                Err(e) => {
-                    return Ok(Json(ApiResponse::ok(UpdateStatus {
-                        message: format!("Failed to run tar command: {}", e),
-                        step: "extract_failed".to_string(),
-                        success: false,
-                    })));
-                }
-                Ok(output) if !output.status.success() => {
-                    let stderr = String::from_utf8_lossy(&output.stderr);
-                    return Ok(Json(ApiResponse::ok(UpdateStatus {
-                        message: format!("Failed to extract tarball: {}", stderr),
-                        step: "extract_failed".to_string(),
-                        success: false,
-                    })));
-                }
-                _ => {}
-            }
-
-            // The extracted binary is now at /tmp/igra-cli
-            let new_binary = Path::new("/tmp/igra-cli");
-
-            // Make executable
-            let _ = Command::new("chmod")
-                .args(&["+x", new_binary.to_str().unwrap()])
-                .output();
-
-            // Create an update script that will be executed by the new binary
-            let update_script = r#"#!/bin/bash
-# Stop the service
-systemctl stop igra-web-ui 2>/dev/null || sudo systemctl stop igra-web-ui
-
-# Copy new binary
-cp /tmp/igra-cli /usr/local/bin/igra-cli 2>/dev/null || sudo cp /tmp/igra-cli /usr/local/bin/igra-cli
-
-# Clean up
-rm -f /tmp/igra-cli /tmp/igra-cli-update /tmp/igra-update.sh
-
-# Start the service
-systemctl start igra-web-ui 2>/dev/null || sudo systemctl start igra-web-ui
-"#;
-
-            // Write the update script
-            let script_path = Path::new("/tmp/igra-update.sh");
-            if let Err(e) = fs::write(script_path, update_script) {
-                return Ok(Json(ApiResponse::ok(UpdateStatus {
-                    message: format!("Failed to create update script: {}", e),
-                    step: "script_failed".to_string(),
-                    success: false,
-                })));
-            }
-
-            // Make script executable
-            let _ = Command::new("chmod")
-                .args(&["+x", script_path.to_str().unwrap()])
-                .output();
-
-            // Schedule the update to run in 2 seconds
-            // This allows the response to be sent before we kill ourselves
-            tokio::spawn(async {
-                tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
-                // Use systemd-run to execute the script detached from the service
-                // This ensures the script continues after the service stops
-                let _ = Command::new("systemd-run")
-                    .args(&["--scope", "--unit=igra-cli-update", "bash", "/tmp/igra-update.sh"])
-                    .spawn();
-            });
-
+/// Installs the latest release the way `igra-cli self-update` does (signed
+/// checksum, `--version` check, atomic replace), then restarts the service
+pub async fn trigger_update() -> Result<Json<ApiResponse<UpdateStatus>>, ApiError> {
+    refuse_in_dry_run("Updating igra-cli")?;
+
+    match updater::self_update().await {
+        Ok(version) => {
+            schedule_service_restart();
             Ok(Json(ApiResponse::ok(UpdateStatus {
-                message: "Update downloaded! Service will restart in 2 seconds...".to_string(),
+                message: format!("Updated to {}! Service will restart in 2 seconds...", version),
                 step: "completed".to_string(),
                 success: true,
             })))
         }
         Err(e) => {
             Ok(Json(ApiResponse::ok(UpdateStatus {
-                message: format!("Failed to download update: {}", e),
-                step: "download_failed".to_string(),
+                message: format!("Update failed: {:#}", e),
+                step: "update_failed".to_string(),
                 success: false,
             })))
         }
@@ -773,27 +1147,101 @@ systemctl start igra-web-ui 2>/dev/null || sudo systemctl start igra-web-ui
 }
 
 /// Restart the igra-web-ui systemd service
-pub async fn restart_igra_service() -> Result<Json<ApiResponse<UpdateStatus>>, StatusCode> {
+pub async fn restart_igra_service() -> Result<Json<ApiResponse<UpdateStatus>>, ApiError> {
+    refuse_in_dry_run("Restarting the igra-web-ui service")?;
+
+    schedule_service_restart();
+
+    Ok(Json(ApiResponse::ok(UpdateStatus {
+        message: "Service will restart in 2 seconds... Please refresh this page in a few seconds.".to_string(),
+        step: "restarting".to_string(),
+        success: true,
+    })))
+}
+
+/// Restart igra-web-ui in 2 seconds, so the response is sent before we kill ourselves
+fn schedule_service_restart() {
     use std::process::Command;
 
-    // Schedule the restart to run in 2 seconds
-    // This allows the response to be sent before we kill ourselves
     tokio::spawn(async {
         tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
         // Try with and without sudo
         let _ = Command::new("systemctl")
-            .args(&["restart", "igra-web-ui"])
+            .args(["restart", "igra-web-ui"])
             .spawn()
             .or_else(|_| {
                 Command::new("sudo")
-                    .args(&["systemctl", "restart", "igra-web-ui"])
+                    .args(["systemctl", "restart", "igra-web-ui"])
                     .spawn()
             });
     });
+}
 
-    Ok(Json(ApiResponse::ok(UpdateStatus {
-        message: "Service will restart in 2 seconds... Please refresh this page in a few seconds.".to_string(),
-        step: "restarting".to_string(),
-        success: true,
-    })))
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_log_history_newest_first_and_filtered() {
+        let logs = "viaduct  | [2025-10-21T08:48:40Z INFO  viaduct::a] first\n\
+                    viaduct  | [2025-10-21T08:48:41Z ERROR viaduct::b] second\n\
+                    viaduct  | [2025-10-21T08:48:42Z INFO  viaduct::c] third\n";
+
+        let all = build_log_history(logs, None, 100, false);
+        assert_eq!(all.entries.len(), 3);
+        assert_eq!(all.entries[0].message, "third");
+        assert!(!all.truncated);
+
+        let errors = build_log_history(logs, Some("error"), 100, false);
+        assert_eq!(errors.entries.len(), 1);
+        assert_eq!(errors.entries[0].level, "ERROR");
+
+        assert!(build_log_history(logs, None, 100, true).truncated);
+    }
+
+    #[test]
+    fn test_check_config_update() {
+        use std::io::Write;
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(file, "NETWORK=testnet\nIGRA_ORCHESTRA_DOMAIN=node.example.com\nW0_KASWALLET_PASSWORD=secret\nOVH_APPLICATION_SECRET=abc").unwrap();
+        let config = ConfigManager::load(file.path()).unwrap();
+
+        assert!(check_config_update(&config, "IGRA_ORCHESTRA_DOMAIN", "other.example.com").is_ok());
+        assert_eq!(check_config_update(&config, "NETWORK", "mainnet").unwrap_err().0, StatusCode::FORBIDDEN);
+        assert_eq!(check_config_update(&config, "IGRA_ORCHESTRA_DOMAIN", "not a domain").unwrap_err().0, StatusCode::BAD_REQUEST);
+        assert_eq!(check_config_update(&config, "W0_KASWALLET_PASSWORD", "x").unwrap_err().0, StatusCode::FORBIDDEN);
+        assert_eq!(check_config_update(&config, "OVH_APPLICATION_SECRET", "x").unwrap_err().0, StatusCode::FORBIDDEN);
+        assert_eq!(check_config_update(&config, "NODE_ID", "node-1").unwrap_err().0, StatusCode::BAD_REQUEST);
+
+        assert_eq!(masked_config(&config)["W0_KASWALLET_PASSWORD"], crate::utils::MASKED_VALUE);
+        assert_eq!(masked_config(&config)["NETWORK"], "testnet");
+    }
+
+    #[test]
+    fn test_core_error_status_and_body() {
+        use crate::core::docker::DockerError;
+
+        let (status, Json(body)) = core_error(CoreError::ServiceNotFound("kaspad".to_string()));
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body.code, Some("service_not_found"));
+
+        // Socket paths and unclassified details stay out of the response
+        let docker_down = DockerError::PermissionDenied { socket: "/var/run/docker.sock".to_string() };
+        let (status, Json(body)) = core_error(docker_down.into());
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert!(!body.error.unwrap().contains("/var/run"));
+
+        let (status, Json(body)) = core_error(anyhow::anyhow!("Failed to read /root/igra/.env").into());
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(body.code, Some("internal"));
+        assert!(!body.error.unwrap().contains("/root"));
+
+        let (status, _) = core_error(CoreError::invalid_input("Fee rate must be a positive number"));
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(check_worker(WORKER_COUNT).unwrap_err().0, StatusCode::NOT_FOUND);
+
+        let body = serde_json::to_value(ApiResponse::<()>::error("x".to_string(), "not_found")).unwrap();
+        assert_eq!(body["code"], "not_found");
+        assert!(serde_json::to_value(ApiResponse::ok(1)).unwrap().get("code").is_none());
+    }
 }
@@ -1,5 +1,5 @@
-/// HTTP API Server module for IGRA CLI
-/// Provides REST API endpoints that reuse core business logic
+//! HTTP API Server module for IGRA CLI
+//! Provides REST API endpoints that reuse core business logic
 
 #[cfg(feature = "server")]
 pub mod routes;
@@ -16,14 +16,35 @@ pub mod static_files;
 #[cfg(feature = "server")]
 pub mod auth;
 
+#[cfg(feature = "server")]
+pub mod access_log;
+
+#[cfg(feature = "server")]
+pub mod openapi;
+
 #[cfg(feature = "server")]
 pub use routes::create_router;
 
 #[cfg(feature = "server")]
-pub async fn run(host: String, port: u16, enable_cors: bool) -> anyhow::Result<()> {
+pub async fn run(host: String, port: u16, enable_cors: bool, allow_destructive: bool) -> anyhow::Result<()> {
     use std::net::SocketAddr;
     use std::io::{self, Write};
 
+    // Tokens kept in the secrets store; the process environment still wins
+    if let Some(store) = crate::utils::get_project_root()
+        .ok()
+        .and_then(|root| crate::core::secrets::SecretsStore::configured(&root))
+    {
+        let entries = store
+            .load()
+            .map_err(|e| e.context("Cannot start the server without the secrets store"))?;
+        for (key, value) in entries {
+            if (key == "IGRA_WEB_TOKEN" || key == "IGRA_WEB_ADMIN_TOKEN") && std::env::var_os(&key).is_none() {
+                std::env::set_var(key, value);
+            }
+        }
+    }
+
     // Check if IGRA_WEB_TOKEN is set, prompt if not
     if std::env::var("IGRA_WEB_TOKEN").is_err() {
         println!("⚠️  IGRA_WEB_TOKEN environment variable not set!");
@@ -47,7 +68,9 @@ pub async fn run(host: String, port: u16, enable_cors: bool) -> anyhow::Result<(
         }
     }
 
-    let app = create_router(enable_cors);
+    access_log::init();
+
+    let app = create_router(enable_cors, allow_destructive);
 
     let addr: SocketAddr = format!("{}:{}", host, port).parse()?;
     println!("🚀 IGRA Management Server");
@@ -60,6 +83,19 @@ pub async fn run(host: String, port: u16, enable_cors: bool) -> anyhow::Result<(
         println!("   ⚠️  Auth:   Disabled (no token)");
     }
 
+    if allow_destructive {
+        if std::env::var("IGRA_WEB_ADMIN_TOKEN").map(|t| !t.is_empty()).unwrap_or(false) {
+            println!("   💸 Wallet send enabled (admin token required)");
+        } else {
+            println!("   ⚠️  --allow-destructive set but IGRA_WEB_ADMIN_TOKEN is not - wallet send will refuse all requests");
+        }
+    }
+
+    if crate::core::dry_run::enabled() {
+        println!("   (dry-run) Service actions, wallet sends, config edits and storage cleanup are checked but not performed;");
+        println!("             self-update and service restart are refused. Responses carry \"dry_run\": true");
+    }
+
     println!();
     println!("📚 API Endpoints:");
     println!("   GET  /api/services               - List all services");
@@ -68,11 +104,18 @@ pub async fn run(host: String, port: u16, enable_cors: bool) -> anyhow::Result<(
     println!("   POST /api/services/:name/restart - Restart service");
     println!("   GET  /api/services/:name/logs    - Get service logs");
     println!("   GET  /api/wallets                - List wallets");
+    if allow_destructive {
+        println!("   POST /api/wallets/:worker/send   - Send funds (admin token)");
+    }
     println!("   GET  /api/storage                - Get storage info");
     println!("   GET  /api/config                 - Get configuration");
     println!("   GET  /api/version                - Check for updates");
-    println!("   GET  /api/health                 - Health check");
+    println!("   GET  /api/health                 - Health probe (200 healthy, 503 degraded)");
+    println!("   GET  /api/openapi.json           - OpenAPI description of every endpoint");
     println!("   GET  /ws/logs/:service           - WebSocket log stream");
+    println!("   GET  /ws/wallets                 - WebSocket wallet updates (token)");
+    println!();
+    println!("📝 Access log: JSON lines on stderr (set RUST_LOG=debug for more, RUST_LOG=warn to silence)");
     println!();
 
     let listener = tokio::net::TcpListener::bind(addr).await?;
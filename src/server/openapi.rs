@@ -0,0 +1,959 @@
+//! OpenAPI description of the REST API, served at `/api/openapi.json`
+//!
+//! Written by hand rather than generated: `ENDPOINTS` lists every route with
+//! its auth scope, parameters and the type in the response's `data`, and
+//! `schemas()` spells out those types. The tests keep both honest - every
+//! route in routes.rs must have an entry here (and no more), and the core
+//! types' schemas must list exactly the fields they serialize.
+
+use axum::Json;
+use serde_json::{json, Map, Value};
+
+/// Who may call an endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Access {
+    Public,
+    /// `IGRA_WEB_TOKEN`, or none when the server runs without one
+    Token,
+    /// `IGRA_WEB_ADMIN_TOKEN`, always required
+    Admin,
+}
+
+impl Access {
+    fn scope(&self) -> &'static str {
+        match self {
+            Access::Public => "public",
+            Access::Token => "token",
+            Access::Admin => "admin",
+        }
+    }
+}
+
+/// What a 200 carries
+#[derive(Debug, Clone, Copy)]
+enum Body {
+    /// `ApiResponse` whose `data` is this schema (a component name, or
+    /// "string", "[Name]" for an array, "{string}" for a string map)
+    Data(&'static str),
+    /// WebSocket upgrade; messages are described in `summary`
+    WebSocket,
+}
+
+struct QueryParam {
+    name: &'static str,
+    kind: &'static str,
+    description: &'static str,
+}
+
+struct Endpoint {
+    method: &'static str,
+    /// axum syntax (`:name`); converted to `{name}` in the spec
+    path: &'static str,
+    access: Access,
+    summary: &'static str,
+    query: &'static [QueryParam],
+    request: Option<&'static str>,
+    response: Body,
+}
+
+const fn query(name: &'static str, kind: &'static str, description: &'static str) -> QueryParam {
+    QueryParam { name, kind, description }
+}
+
+const ENDPOINTS: &[Endpoint] = &[
+    Endpoint {
+        method: "get",
+        path: "/api/services",
+        access: Access::Public,
+        summary: "List services with status, resource usage and metrics parsed from their logs",
+        query: &[],
+        request: None,
+        response: Body::Data("[ServiceInfo]"),
+    },
+    Endpoint {
+        method: "post",
+        path: "/api/services/:name/start",
+        access: Access::Token,
+        summary: "Start a service",
+        query: &[],
+        request: None,
+        response: Body::Data("string"),
+    },
+    Endpoint {
+        method: "post",
+        path: "/api/services/:name/stop",
+        access: Access::Token,
+        summary: "Stop a service",
+        query: &[],
+        request: None,
+        response: Body::Data("string"),
+    },
+    Endpoint {
+        method: "post",
+        path: "/api/services/:name/restart",
+        access: Access::Token,
+        summary: "Restart a service",
+        query: &[],
+        request: None,
+        response: Body::Data("string"),
+    },
+    Endpoint {
+        method: "get",
+        path: "/api/services/:name/logs",
+        access: Access::Public,
+        summary: "Service logs: raw text in `data`, or a LogHistory with format=json or a level filter",
+        query: &[
+            query("tail", "integer", "Lines to fetch (default 100, at most 5000)"),
+            query("since", "string", "RFC3339, \"YYYY-MM-DD HH:MM\", \"HH:MM\" or a duration such as \"30m\""),
+            query("level", "string", "ERROR, WARN, INFO, DEBUG or TRACE"),
+            query("format", "string", "\"json\" for parsed entries"),
+            query("follow", "boolean", "Accepted but ignored; use /ws/logs/{service} to stream"),
+        ],
+        request: None,
+        response: Body::Data("string|LogHistory"),
+    },
+    Endpoint {
+        method: "get",
+        path: "/api/services/:name/logs/parsed",
+        access: Access::Public,
+        summary: "Parsed log lines",
+        query: &[
+            query("tail", "integer", "Lines to fetch (default 100, at most 5000)"),
+            query("level", "string", "ERROR, WARN, INFO, DEBUG or TRACE"),
+            query("module", "string", "Module name filter"),
+        ],
+        request: None,
+        response: Body::Data("[ParsedLogLine]"),
+    },
+    Endpoint {
+        method: "get",
+        path: "/api/wallets",
+        access: Access::Public,
+        summary: "List the worker wallets with balances and fees",
+        query: &[],
+        request: None,
+        response: Body::Data("[WalletInfo]"),
+    },
+    Endpoint {
+        method: "get",
+        path: "/api/wallets/:id/balance",
+        access: Access::Public,
+        summary: "Available balance of a worker wallet, formatted (\"12.34 KAS\", \"12.34 TKAS\" on testnet)",
+        query: &[],
+        request: None,
+        response: Body::Data("string"),
+    },
+    Endpoint {
+        method: "get",
+        path: "/api/wallets/:id/detail",
+        access: Access::Public,
+        summary: "UTXOs of a worker wallet",
+        query: &[],
+        request: None,
+        response: Body::Data("[UtxoInfo]"),
+    },
+    Endpoint {
+        method: "post",
+        path: "/api/wallets/:worker/send",
+        access: Access::Admin,
+        summary: "Send funds from a worker wallet. Only enabled with `serve --allow-destructive`; 403 otherwise",
+        query: &[],
+        request: Some("SendRequest"),
+        response: Body::Data("SendResult"),
+    },
+    Endpoint {
+        method: "get",
+        path: "/api/storage",
+        access: Access::Public,
+        summary: "Disk, Docker and log storage usage with growth projections",
+        query: &[],
+        request: None,
+        response: Body::Data("StorageAnalysis"),
+    },
+    Endpoint {
+        method: "get",
+        path: "/api/storage/history",
+        access: Access::Public,
+        summary: "Recorded storage snapshots, oldest first",
+        query: &[],
+        request: None,
+        response: Body::Data("[StorageMeasurement]"),
+    },
+    Endpoint {
+        method: "post",
+        path: "/api/storage/prune",
+        access: Access::Token,
+        summary: "Prune unused Docker data, volumes included",
+        query: &[],
+        request: None,
+        response: Body::Data("string"),
+    },
+    Endpoint {
+        method: "post",
+        path: "/api/storage/container-logs/:container_id/truncate",
+        access: Access::Token,
+        summary: "Truncate a container's log file",
+        query: &[],
+        request: None,
+        response: Body::Data("string"),
+    },
+    Endpoint {
+        method: "get",
+        path: "/api/config",
+        access: Access::Public,
+        summary: "The .env configuration as key/value pairs, secrets masked",
+        query: &[],
+        request: None,
+        response: Body::Data("{string}"),
+    },
+    Endpoint {
+        method: "patch",
+        path: "/api/config",
+        access: Access::Admin,
+        summary: "Change one allowlisted .env value; returns the config with secrets masked",
+        query: &[],
+        request: Some("ConfigUpdate"),
+        response: Body::Data("{string}"),
+    },
+    Endpoint {
+        method: "get",
+        path: "/api/system",
+        access: Access::Public,
+        summary: "Host CPU, memory, disk and OS",
+        query: &[],
+        request: None,
+        response: Body::Data("SystemResources"),
+    },
+    Endpoint {
+        method: "get",
+        path: "/api/rpc/tokens",
+        access: Access::Public,
+        summary: "RPC access token slots by index, each token masked (first2...last2)",
+        query: &[],
+        request: None,
+        response: Body::Data("[RpcToken]"),
+    },
+    Endpoint {
+        method: "get",
+        path: "/api/ssl/info",
+        access: Access::Public,
+        summary: "Configured domain and whether OVH DNS credentials are set",
+        query: &[],
+        request: None,
+        response: Body::Data("SslInfo"),
+    },
+    Endpoint {
+        method: "get",
+        path: "/api/profiles",
+        access: Access::Public,
+        summary: "Compose profiles, their services and whether each is running",
+        query: &[],
+        request: None,
+        response: Body::Data("[ProfileInfo]"),
+    },
+    Endpoint {
+        method: "post",
+        path: "/api/profiles/:name/start",
+        access: Access::Token,
+        summary: "Start a compose profile",
+        query: &[],
+        request: None,
+        response: Body::Data("string"),
+    },
+    Endpoint {
+        method: "post",
+        path: "/api/profiles/:name/stop",
+        access: Access::Token,
+        summary: "Stop a compose profile",
+        query: &[],
+        request: None,
+        response: Body::Data("string"),
+    },
+    Endpoint {
+        method: "get",
+        path: "/api/transactions",
+        access: Access::Public,
+        summary: "Recent L2 transactions",
+        query: &[
+            query("limit", "integer", "Most transactions to return (default 50)"),
+            query("filter", "string", "all, transfer, contract or entry"),
+        ],
+        request: None,
+        response: Body::Data("[TransactionInfo]"),
+    },
+    Endpoint {
+        method: "get",
+        path: "/api/transactions/stats",
+        access: Access::Public,
+        summary: "L2 transaction statistics",
+        query: &[],
+        request: None,
+        response: Body::Data("TransactionStats"),
+    },
+    Endpoint {
+        method: "get",
+        path: "/api/health",
+        access: Access::Public,
+        summary: "Health probe: 200 when healthy, 503 (same body) when degraded or unavailable",
+        query: &[],
+        request: None,
+        response: Body::Data("HealthReport"),
+    },
+    Endpoint {
+        method: "get",
+        path: "/api/metrics",
+        access: Access::Public,
+        summary: "Container counts",
+        query: &[],
+        request: None,
+        response: Body::Data("MetricsInfo"),
+    },
+    Endpoint {
+        method: "get",
+        path: "/api/version",
+        access: Access::Public,
+        summary: "Installed and latest released version",
+        query: &[],
+        request: None,
+        response: Body::Data("VersionInfo"),
+    },
+    Endpoint {
+        method: "post",
+        path: "/api/update",
+        access: Access::Token,
+        summary: "Install the latest signed release (as self-update), then restart the service",
+        query: &[],
+        request: None,
+        response: Body::Data("UpdateStatus"),
+    },
+    Endpoint {
+        method: "post",
+        path: "/api/service/restart",
+        access: Access::Token,
+        summary: "Restart the igra-web systemd service",
+        query: &[],
+        request: None,
+        response: Body::Data("UpdateStatus"),
+    },
+    Endpoint {
+        method: "get",
+        path: "/api/openapi.json",
+        access: Access::Public,
+        summary: "This document (returned as is, without the ApiResponse envelope)",
+        query: &[],
+        request: None,
+        response: Body::Data("object"),
+    },
+    Endpoint {
+        method: "get",
+        path: "/ws/logs/:service",
+        access: Access::Public,
+        summary: "WebSocket: a service's log lines as they are written",
+        query: &[],
+        request: None,
+        response: Body::WebSocket,
+    },
+    Endpoint {
+        method: "get",
+        path: "/ws/metrics",
+        access: Access::Public,
+        summary: "WebSocket: container metrics every few seconds",
+        query: &[],
+        request: None,
+        response: Body::WebSocket,
+    },
+    Endpoint {
+        method: "get",
+        path: "/ws/wallets",
+        access: Access::Token,
+        summary: "WebSocket: wallet balances as they change. Browsers may pass the token as ?token=",
+        query: &[],
+        request: None,
+        response: Body::WebSocket,
+    },
+];
+
+/// `GET /api/openapi.json`
+pub async fn openapi_json() -> Json<Value> {
+    Json(spec())
+}
+
+/// The OpenAPI 3.0 document
+pub fn spec() -> Value {
+    let mut paths = Map::new();
+    for endpoint in ENDPOINTS {
+        let (path, path_params) = openapi_path(endpoint.path);
+        let entry = paths.entry(path).or_insert_with(|| json!({}));
+        entry[endpoint.method] = operation(endpoint, &path_params);
+    }
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "IGRA Orchestra management API",
+            "version": env!("CARGO_PKG_VERSION"),
+            "description": "REST API of `igra-cli serve`. Every JSON response is an ApiResponse envelope; \
+                            errors carry a machine-readable `code`. Each operation's `x-auth-scope` is \
+                            public, token (IGRA_WEB_TOKEN) or admin (IGRA_WEB_ADMIN_TOKEN)."
+        },
+        "paths": paths,
+        "components": {
+            "securitySchemes": {
+                "bearerAuth": {
+                    "type": "http",
+                    "scheme": "bearer",
+                    "description": "IGRA_WEB_TOKEN. Not enforced when the server runs without one."
+                },
+                "adminBearerAuth": {
+                    "type": "http",
+                    "scheme": "bearer",
+                    "description": "IGRA_WEB_ADMIN_TOKEN. Always required; with none configured the route answers 403."
+                },
+                "queryToken": {
+                    "type": "apiKey",
+                    "in": "query",
+                    "name": "token",
+                    "description": "IGRA_WEB_TOKEN on WebSocket upgrades, for browsers that can't set headers"
+                }
+            },
+            "responses": {
+                "Error": {
+                    "description": "Failure; `code` says what kind",
+                    "content": { "application/json": { "schema": { "$ref": "#/components/schemas/ApiError" } } }
+                }
+            },
+            "schemas": schemas()
+        }
+    })
+}
+
+/// `/api/services/:name/start` -> (`/api/services/{name}/start`, ["name"])
+fn openapi_path(path: &str) -> (String, Vec<&str>) {
+    let mut params = Vec::new();
+    let segments: Vec<String> = path
+        .split('/')
+        .map(|segment| match segment.strip_prefix(':') {
+            Some(name) => {
+                params.push(name);
+                format!("{{{}}}", name)
+            }
+            None => segment.to_string(),
+        })
+        .collect();
+    (segments.join("/"), params)
+}
+
+fn operation(endpoint: &Endpoint, path_params: &[&str]) -> Value {
+    let mut parameters: Vec<Value> = path_params
+        .iter()
+        .map(|&name| {
+            // Wallet routes take a worker number, everything else a name or id
+            let kind = if name == "id" || name == "worker" { "integer" } else { "string" };
+            json!({ "name": name, "in": "path", "required": true, "schema": { "type": kind } })
+        })
+        .collect();
+    parameters.extend(endpoint.query.iter().map(|q| {
+        json!({ "name": q.name, "in": "query", "required": false, "description": q.description, "schema": { "type": q.kind } })
+    }));
+
+    let mut responses = Map::new();
+    match endpoint.response {
+        Body::Data("object") => {
+            responses.insert(
+                "200".to_string(),
+                json!({ "description": "OK", "content": { "application/json": { "schema": { "type": "object" } } } }),
+            );
+        }
+        Body::Data(data) => {
+            let envelope = json!({
+                "allOf": [
+                    { "$ref": "#/components/schemas/ApiResponse" },
+                    { "type": "object", "properties": { "data": data_schema(data) } }
+                ]
+            });
+            responses.insert(
+                "200".to_string(),
+                json!({ "description": "OK", "content": { "application/json": { "schema": envelope } } }),
+            );
+            responses.insert("default".to_string(), json!({ "$ref": "#/components/responses/Error" }));
+        }
+        Body::WebSocket => {
+            responses.insert("101".to_string(), json!({ "description": "Switching to the WebSocket protocol" }));
+        }
+    }
+    if endpoint.access != Access::Public {
+        responses.insert("401".to_string(), json!({ "$ref": "#/components/responses/Error" }));
+    }
+    if endpoint.access == Access::Admin {
+        responses.insert("403".to_string(), json!({ "$ref": "#/components/responses/Error" }));
+    }
+
+    let mut op = json!({
+        "summary": endpoint.summary,
+        "x-auth-scope": endpoint.access.scope(),
+        "parameters": parameters,
+        "responses": responses,
+    });
+    match endpoint.access {
+        Access::Public => op["security"] = json!([]),
+        Access::Token if matches!(endpoint.response, Body::WebSocket) => {
+            op["security"] = json!([{ "bearerAuth": [] }, { "queryToken": [] }])
+        }
+        Access::Token => op["security"] = json!([{ "bearerAuth": [] }]),
+        Access::Admin => op["security"] = json!([{ "adminBearerAuth": [] }]),
+    }
+    if let Some(request) = endpoint.request {
+        op["requestBody"] = json!({
+            "required": true,
+            "content": { "application/json": { "schema": schema_ref(request) } }
+        });
+    }
+    op
+}
+
+/// Schema for `Body::Data`'s notation
+fn data_schema(data: &str) -> Value {
+    if let Some((a, b)) = data.split_once('|') {
+        return json!({ "oneOf": [data_schema(a), data_schema(b)] });
+    }
+    if let Some(item) = data.strip_prefix('[').and_then(|d| d.strip_suffix(']')) {
+        return json!({ "type": "array", "items": data_schema(item) });
+    }
+    if let Some(value) = data.strip_prefix('{').and_then(|d| d.strip_suffix('}')) {
+        return json!({ "type": "object", "additionalProperties": data_schema(value) });
+    }
+    match data {
+        "string" => json!({ "type": "string" }),
+        name => schema_ref(name),
+    }
+}
+
+fn schema_ref(name: &str) -> Value {
+    json!({ "$ref": format!("#/components/schemas/{}", name) })
+}
+
+/// Object schema; every listed property is required. Types are JSON schema
+/// types, "?type" for nullable ones, "[type]" for arrays and a component
+/// name for anything else.
+fn object(description: &str, properties: &[(&str, &str)]) -> Value {
+    let props: Map<String, Value> = properties
+        .iter()
+        .map(|&(name, kind)| (name.to_string(), property(kind)))
+        .collect();
+    let required: Vec<&str> = properties.iter().map(|&(name, _)| name).collect();
+    json!({ "type": "object", "description": description, "properties": props, "required": required })
+}
+
+fn property(kind: &str) -> Value {
+    if let Some(kind) = kind.strip_prefix('?') {
+        let mut schema = property(kind);
+        if schema.get("$ref").is_some() {
+            // $ref can't carry siblings in OpenAPI 3.0
+            return json!({ "allOf": [schema], "nullable": true });
+        }
+        schema["nullable"] = json!(true);
+        return schema;
+    }
+    if let Some(item) = kind.strip_prefix('[').and_then(|k| k.strip_suffix(']')) {
+        return json!({ "type": "array", "items": property(item) });
+    }
+    match kind {
+        "string" | "integer" | "number" | "boolean" => json!({ "type": kind }),
+        "date-time" => json!({ "type": "string", "format": "date-time" }),
+        name => schema_ref(name),
+    }
+}
+
+fn schemas() -> Value {
+    let mut schemas = Map::new();
+    let mut add = |name: &str, schema: Value| {
+        schemas.insert(name.to_string(), schema);
+    };
+
+    add("ApiResponse", json!({
+        "type": "object",
+        "properties": {
+            "success": { "type": "boolean" },
+            "data": { "nullable": true },
+            "error": { "type": "string", "nullable": true },
+            "code": { "type": "string", "description": "Only on errors" },
+            "dry_run": { "type": "boolean", "description": "Only present (true) when the server runs with --dry-run" }
+        },
+        "required": ["success", "data", "error"]
+    }));
+    add("ApiError", json!({
+        "type": "object",
+        "properties": {
+            "success": { "type": "boolean", "enum": [false] },
+            "data": { "nullable": true },
+            "error": { "type": "string" },
+            "code": {
+                "type": "string",
+                "description": "bad_request, unauthorized, forbidden, not_found, conflict, unavailable, internal, \
+                                docker_unavailable, service_not_found, service_not_running, wallet_unavailable, \
+                                wallet_locked, wallet_syncing, wallet_password_unavailable, invalid_input, invalid_compose, \
+                                dry_run"
+            }
+        },
+        "required": ["success", "error", "code"]
+    }));
+
+    // Services and logs
+    add("PortMapping", object("Published port", &[
+        ("host_port", "string"),
+        ("container_port", "string"),
+        ("protocol", "string"),
+    ]));
+    add("ServiceInfo", object("A compose service's container", &[
+        ("name", "string"),
+        ("status", "string"),
+        ("health", "?string"),
+        ("cpu_percent", "number"),
+        ("memory_mb", "number"),
+        ("network_rx_mb", "number"),
+        ("network_tx_mb", "number"),
+        ("uptime", "?string"),
+        ("image", "string"),
+        ("ports", "[PortMapping]"),
+        ("container_size_mb", "number"),
+        ("volume_size_mb", "number"),
+        ("status_text", "?string"),
+        ("primary_metric", "?string"),
+        ("secondary_metric", "?string"),
+        ("is_healthy_metric", "boolean"),
+    ]));
+    add("ParsedLogLine", object("One parsed log line", &[
+        ("timestamp", "string"),
+        ("level", "string"),
+        ("module", "string"),
+        ("message", "string"),
+    ]));
+    let mut history = object("Parsed log history, newest entry first", &[
+        ("entries", "[ParsedLogLine]"),
+        ("limit", "integer"),
+        ("truncated", "boolean"),
+        ("note", "string"),
+    ]);
+    history["required"] = json!(["entries", "limit", "truncated"]);
+    add("LogHistory", history);
+
+    // Wallets
+    add("WalletInfo", object("A worker wallet", &[
+        ("worker_id", "integer"),
+        ("address", "?string"),
+        ("balance", "?number"),
+        ("container_running", "boolean"),
+        ("initial_balance", "?number"),
+        ("fees_spent", "?number"),
+        ("fee_burn_rate", "?number"),
+        ("daemon_synced", "?boolean"),
+        ("is_locked", "boolean"),
+    ]));
+    add("UtxoInfo", object("Unspent output of a wallet address", &[
+        ("address", "string"),
+        ("tx_id", "string"),
+        ("amount_kas", "number"),
+        ("amount_sompi", "integer"),
+        ("block_daa_score", "integer"),
+        ("is_coinbase", "boolean"),
+        ("timestamp_ms", "integer"),
+        ("source_addresses", "[string]"),
+        ("confirmations", "?integer"),
+    ]));
+    add("SendFeePolicy", json!({
+        "description": "Omitted or \"daemon_estimate\": the wallet daemon sets the rate from kaspad's fee estimate",
+        "oneOf": [
+            { "type": "string", "enum": ["daemon_estimate"] },
+            object("Fee rate in sompi per gram", &[("exact_fee_rate", "number")]),
+            object("Highest fee rate in sompi per gram", &[("max_fee_rate", "number")]),
+            object("Highest total fee in sompi", &[("max_fee", "integer")]),
+        ]
+    }));
+    add("SendRequest", json!({
+        "type": "object",
+        "properties": {
+            "to": { "type": "string", "description": "Kaspa address" },
+            "amount": { "type": "string", "description": "Decimal KAS (\"1.5\"); required unless send_all" },
+            "send_all": { "type": "boolean", "default": false },
+            "fee_policy": schema_ref("SendFeePolicy")
+        },
+        "required": ["to"]
+    }));
+    add("SendResult", object("Transactions the daemon signed and submitted", &[
+        ("tx_ids", "[string]"),
+        ("signed_transactions", "integer"),
+        ("dry_run", "boolean"),
+    ]));
+
+    // Storage
+    add("DiskUsage", object("Filesystem holding the project", &[
+        ("filesystem", "string"),
+        ("total_bytes", "integer"),
+        ("used_bytes", "integer"),
+        ("available_bytes", "integer"),
+        ("use_percent", "number"),
+        ("mount_point", "string"),
+    ]));
+    add("DockerStorageInfo", object("Docker images, containers or build cache", &[
+        ("total_bytes", "integer"),
+        ("reclaimable_bytes", "integer"),
+        ("active_count", "integer"),
+        ("total_count", "integer"),
+    ]));
+    add("VolumeUsage", object("Docker volume", &[
+        ("name", "string"),
+        ("size_bytes", "integer"),
+        ("mount_point", "string"),
+        ("in_use", "boolean"),
+        ("critical", "boolean"),
+    ]));
+    add("ContainerLogInfo", object("Container log file", &[
+        ("container_id", "string"),
+        ("container_name", "string"),
+        ("log_size_bytes", "integer"),
+        ("log_path", "string"),
+    ]));
+    let mut growth = object("Disk growth over the snapshot history", &[
+        ("bytes_per_day", "number"),
+        ("days_to_full", "?integer"),
+        ("trend", "string"),
+    ]);
+    growth["properties"]["trend"]["enum"] = json!(["Growing", "Stable", "Declining"]);
+    add("GrowthRate", growth);
+    add("DiskFullProjection", object("When the disk holding the reth database fills up", &[
+        ("volume_bytes", "integer"),
+        ("volume_bytes_per_day", "number"),
+        ("other_bytes_per_day", "number"),
+        ("available_bytes", "integer"),
+        ("days_until_full", "?number"),
+        ("samples", "integer"),
+    ]));
+    add("StorageAnalysis", object("Storage usage", &[
+        ("system_disk", "DiskUsage"),
+        ("docker_images", "DockerStorageInfo"),
+        ("docker_volumes", "[VolumeUsage]"),
+        ("docker_containers", "DockerStorageInfo"),
+        ("docker_build_cache", "DockerStorageInfo"),
+        ("container_logs", "[ContainerLogInfo]"),
+        ("reclaimable_space", "integer"),
+        ("growth_rate", "?GrowthRate"),
+        ("disk_projection", "?DiskFullProjection"),
+    ]));
+    add("StorageMeasurement", object("Storage snapshot", &[
+        ("timestamp", "date-time"),
+        ("total_used_bytes", "integer"),
+        ("docker_volumes_bytes", "integer"),
+        ("docker_images_bytes", "integer"),
+        ("reth_volume_bytes", "?integer"),
+    ]));
+
+    // Config, system, SSL
+    add("ConfigUpdate", object("One .env change", &[("key", "string"), ("value", "string")]));
+    add("SystemResources", object("The server host", &[
+        ("cpu_percent", "number"),
+        ("memory_used_gb", "number"),
+        ("memory_total_gb", "number"),
+        ("disk_free_gb", "number"),
+        ("disk_total_gb", "number"),
+        ("os_name", "string"),
+        ("os_version", "string"),
+        ("cpu_cores", "integer"),
+        ("cpu_frequency_ghz", "number"),
+        ("cpu_model", "string"),
+        ("public_ip", "?string"),
+        ("remote_docker_host", "?string"),
+    ]));
+    add("RpcToken", object("RPC access token slot; the token is masked, null when unset", &[("index", "integer"), ("token", "?string")]));
+    add("SslInfo", object("SSL setup", &[("domain", "?string"), ("has_ovh_config", "boolean")]));
+
+    // Monitoring
+    add("ComponentHealth", object("One health check", &[
+        ("name", "string"),
+        ("healthy", "boolean"),
+        ("latency_ms", "integer"),
+        ("detail", "string"),
+    ]));
+    let mut health = object("Health of igra-cli and the node", &[
+        ("status", "string"),
+        ("manager", "ComponentHealth"),
+        ("components", "[ComponentHealth]"),
+        ("checked_at", "date-time"),
+    ]);
+    health["properties"]["status"]["enum"] = json!(["healthy", "degraded", "unavailable"]);
+    add("HealthReport", health);
+    add("MetricsInfo", object("Container counts", &[
+        ("system_cpu", "number"),
+        ("system_memory_percent", "number"),
+        ("system_disk_percent", "number"),
+        ("docker_containers_running", "integer"),
+        ("docker_images", "integer"),
+    ]));
+    add("ProfileInfo", object("Compose profile", &[
+        ("name", "string"),
+        ("is_active", "boolean"),
+        ("services", "[string]"),
+    ]));
+    add("TransactionInfo", object("L2 transaction", &[
+        ("hash", "string"),
+        ("from", "string"),
+        ("to", "?string"),
+        ("value_ikas", "number"),
+        ("gas_fee_ikas", "number"),
+        ("block_number", "integer"),
+        ("timestamp", "date-time"),
+        ("status", "boolean"),
+        ("tx_type", "string"),
+        ("l1_fee_kas", "?number"),
+        ("method", "?string"),
+    ]));
+    add("TransactionStats", object("L2 statistics since the monitor started", &[
+        ("current_block", "integer"),
+        ("total_transactions", "integer"),
+        ("successful_transactions", "integer"),
+        ("failed_transactions", "integer"),
+        ("total_gas_fees_ikas", "number"),
+        ("total_l1_fees_kas", "number"),
+        ("tps", "number"),
+        ("uptime", "string"),
+    ]));
+
+    // Updates
+    add("VersionInfo", object("Installed and latest version", &[
+        ("current_version", "string"),
+        ("latest_version", "?string"),
+        ("update_available", "boolean"),
+        ("release_url", "?string"),
+        ("release_notes", "?string"),
+        ("published_at", "?string"),
+        ("download_url", "?string"),
+    ]));
+    add("UpdateStatus", object("Outcome of an update step", &[
+        ("message", "string"),
+        ("step", "string"),
+        ("success", "boolean"),
+    ]));
+
+    Value::Object(schemas)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    /// (method, path) of every route in routes.rs
+    fn routed() -> BTreeSet<(String, String)> {
+        let source = include_str!("routes.rs");
+        let method = regex::Regex::new(r"\b(get|post|patch|put|delete)\((?:handlers|websocket|openapi)::").unwrap();
+        source
+            .split(".route(")
+            .skip(1)
+            .flat_map(|chunk| {
+                let path = chunk.split('"').nth(1).unwrap().to_string();
+                method
+                    .captures_iter(chunk)
+                    .map(move |c| (c[1].to_string(), path.clone()))
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_every_route_is_documented() {
+        let documented: BTreeSet<(String, String)> =
+            ENDPOINTS.iter().map(|e| (e.method.to_string(), e.path.to_string())).collect();
+        assert_eq!(documented.len(), ENDPOINTS.len(), "an endpoint is listed twice");
+        assert_eq!(routed(), documented);
+    }
+
+    #[test]
+    fn test_spec_shape() {
+        let spec = spec();
+        let op = &spec["paths"]["/api/services/{name}/start"]["post"];
+        assert_eq!(op["x-auth-scope"], "token");
+        assert_eq!(op["security"], json!([{ "bearerAuth": [] }]));
+        assert_eq!(op["parameters"][0]["name"], "name");
+
+        let send = &spec["paths"]["/api/wallets/{worker}/send"]["post"];
+        assert_eq!(send["security"], json!([{ "adminBearerAuth": [] }]));
+        assert_eq!(send["parameters"][0]["schema"]["type"], "integer");
+        assert!(send["requestBody"].is_object());
+        assert_eq!(spec["paths"]["/api/health"]["get"]["security"], json!([]));
+        assert_eq!(spec["components"]["securitySchemes"]["bearerAuth"]["scheme"], "bearer");
+
+        // Every $ref points at a schema that exists
+        let text = spec.to_string();
+        let schemas = spec["components"]["schemas"].as_object().unwrap();
+        for reference in text.split("\"#/components/schemas/").skip(1) {
+            let name = reference.split('"').next().unwrap();
+            assert!(schemas.contains_key(name), "missing schema {}", name);
+        }
+    }
+
+    fn documented_fields(name: &str) -> BTreeSet<String> {
+        schemas()[name]["properties"].as_object().unwrap().keys().cloned().collect()
+    }
+
+    fn serialized_fields(value: impl serde::Serialize) -> BTreeSet<String> {
+        serde_json::to_value(value).unwrap().as_object().unwrap().keys().cloned().collect()
+    }
+
+    #[test]
+    fn test_schemas_match_the_core_types() {
+        use crate::core::wallet::{SendResult, UtxoInfo, WalletInfo};
+
+        let wallet = WalletInfo {
+            worker_id: 0,
+            address: None,
+            balance: None,
+            container_running: false,
+            initial_balance: None,
+            fees_spent: None,
+            fee_burn_rate: None,
+            daemon_synced: None,
+            is_locked: false,
+        };
+        assert_eq!(serialized_fields(wallet), documented_fields("WalletInfo"));
+
+        let utxo = UtxoInfo {
+            address: String::new(),
+            tx_id: String::new(),
+            amount_kas: 0.0,
+            amount_sompi: 0,
+            block_daa_score: 0,
+            is_coinbase: false,
+            timestamp_ms: 0,
+            source_addresses: Vec::new(),
+            confirmations: None,
+        };
+        assert_eq!(serialized_fields(utxo), documented_fields("UtxoInfo"));
+
+        let sent = SendResult { tx_ids: Vec::new(), signed_transactions: 0, dry_run: false };
+        assert_eq!(serialized_fields(sent), documented_fields("SendResult"));
+
+        let measurement = crate::core::storage::StorageMeasurement {
+            timestamp: chrono::Utc::now(),
+            total_used_bytes: 0,
+            docker_volumes_bytes: 0,
+            docker_images_bytes: 0,
+            reth_volume_bytes: None,
+        };
+        assert_eq!(serialized_fields(measurement), documented_fields("StorageMeasurement"));
+
+        let version = crate::core::updater::VersionInfo {
+            current_version: String::new(),
+            latest_version: None,
+            update_available: false,
+            release_url: None,
+            release_notes: None,
+            published_at: None,
+            download_url: None,
+        };
+        assert_eq!(serialized_fields(version), documented_fields("VersionInfo"));
+
+        let token = crate::server::handlers::RpcToken { index: 0, token: None };
+        assert_eq!(serialized_fields(token), documented_fields("RpcToken"));
+        let ssl = crate::server::handlers::SslInfo { domain: None, has_ovh_config: false };
+        assert_eq!(serialized_fields(ssl), documented_fields("SslInfo"));
+    }
+}
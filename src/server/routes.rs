@@ -1,8 +1,8 @@
-/// API Routes definition
+//! API Routes definition
 
 use axum::{
     Router,
-    routing::{get, post},
+    routing::{get, patch, post},
     middleware,
 };
 use tower_http::cors::CorsLayer;
@@ -12,8 +12,10 @@ use super::handlers;
 use super::websocket;
 use super::static_files;
 use super::auth;
+use super::access_log;
+use super::openapi;
 
-pub fn create_router(enable_cors: bool) -> Router {
+pub fn create_router(enable_cors: bool, allow_destructive: bool) -> Router {
     // Protected routes (require authentication)
     let protected_routes = Router::new()
         .route("/api/services/:name/start", post(handlers::start_service))
@@ -25,8 +27,19 @@ pub fn create_router(enable_cors: bool) -> Router {
         .route("/api/profiles/:name/stop", post(handlers::stop_profile))
         .route("/api/update", post(handlers::trigger_update))
         .route("/api/service/restart", post(handlers::restart_igra_service))
+        .route("/ws/wallets", get(websocket::ws_wallets_handler))
         .layer(middleware::from_fn(auth::auth_middleware));
 
+    // Fund-moving routes: admin token only, and only when explicitly enabled
+    let admin_routes = if allow_destructive {
+        Router::new()
+            .route("/api/wallets/:worker/send", post(handlers::send_wallet_transaction))
+            .layer(middleware::from_fn(auth::admin_middleware))
+    } else {
+        Router::new()
+            .route("/api/wallets/:worker/send", post(handlers::destructive_disabled))
+    };
+
     // Public routes (read-only, no auth required)
     let public_routes = Router::new()
         .route("/api/services", get(handlers::get_services))
@@ -37,7 +50,12 @@ pub fn create_router(enable_cors: bool) -> Router {
         .route("/api/wallets/:id/detail", get(handlers::get_wallet_detail))
         .route("/api/storage", get(handlers::get_storage))
         .route("/api/storage/history", get(handlers::get_storage_history))
-        .route("/api/config", get(handlers::get_config))
+        // Reading config is public; PATCH needs the admin token
+        .route(
+            "/api/config",
+            get(handlers::get_config)
+                .merge(patch(handlers::update_config).layer(middleware::from_fn(auth::admin_middleware))),
+        )
         .route("/api/system", get(handlers::get_system_info))
         .route("/api/rpc/tokens", get(handlers::get_rpc_tokens))
         .route("/api/ssl/info", get(handlers::get_ssl_info))
@@ -47,16 +65,19 @@ pub fn create_router(enable_cors: bool) -> Router {
         .route("/api/health", get(handlers::health_check))
         .route("/api/metrics", get(handlers::get_metrics))
         .route("/api/version", get(handlers::get_version_info))
+        .route("/api/openapi.json", get(openapi::openapi_json))
         .route("/ws/logs/:service", get(websocket::ws_logs_handler))
         .route("/ws/metrics", get(websocket::ws_metrics_handler));
 
     let mut app = Router::new()
         .merge(protected_routes)
+        .merge(admin_routes)
         .merge(public_routes)
         // Serve static files (React UI) - must be last to act as catch-all
         .fallback(static_files::static_handler)
         // Add tracing middleware
-        .layer(TraceLayer::new_for_http());
+        .layer(TraceLayer::new_for_http())
+        .layer(middleware::from_fn(access_log::access_log));
 
     if enable_cors {
         app = app.layer(CorsLayer::permissive());
@@ -1,8 +1,7 @@
-/// Static file serving for embedded React UI
+//! Static file serving for embedded React UI
 
 use axum::{
     body::Body,
-    extract::Request,
     http::{header, StatusCode, Uri},
     response::{IntoResponse, Response},
 };
@@ -1,4 +1,4 @@
-/// WebSocket handlers for real-time updates
+//! WebSocket handlers for real-time updates
 
 use axum::{
     extract::{
@@ -9,8 +9,14 @@ use axum::{
     response::Response,
 };
 use futures::{sink::SinkExt, stream::StreamExt};
-use tokio::time::{interval, Duration};
+use serde_json::json;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+use tokio::sync::watch;
+use tokio::time::{interval, sleep_until, Duration, Instant};
 
+use super::handlers;
+use crate::core::wallet::WalletInfo;
 use crate::core::DockerManager;
 
 /// WebSocket handler for real-time log streaming
@@ -31,7 +37,7 @@ async fn handle_logs_websocket(socket: WebSocket, service: String) {
             _ = interval.tick() => {
                 // Get latest logs
                 if let Ok(docker) = DockerManager::new().await {
-                    if let Ok(logs) = docker.get_logs(&service, Some(100)).await {
+                    if let Ok(logs) = docker.get_logs(&service, Some(100), false).await {
                         // Split logs into lines for JSON array
                         let lines: Vec<String> = logs.lines().map(|s| s.to_string()).collect();
 
@@ -90,10 +96,11 @@ async fn handle_metrics_websocket(socket: WebSocket) {
             _ = interval.tick() => {
                 if let Ok(docker) = DockerManager::new().await {
                     if let Ok(containers) = docker.list_containers().await {
+                        let mut all_stats = docker.get_all_container_stats().await.unwrap_or_default();
                         let mut services = Vec::new();
 
                         for c in containers {
-                            let stats = docker.get_container_stats(&c.name).await.ok().flatten();
+                            let stats = all_stats.remove(&c.name);
 
                             let (cpu_percent, memory_mb) = if let Some(s) = stats {
                                 (s.cpu_percent, s.memory_usage as f64 / 1024.0 / 1024.0)
@@ -137,3 +144,187 @@ async fn handle_metrics_websocket(socket: WebSocket) {
         }
     }
 }
+
+/// How often the shared poller re-reads wallet state while clients are connected
+const WALLET_POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// Minimum gap between pushes to one client; changes inside it are coalesced
+const WALLET_PUSH_MIN_GAP: Duration = Duration::from_millis(500);
+
+type WalletSnapshot = Option<Arc<Vec<WalletInfo>>>;
+
+static WALLET_POLLER_RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// Latest wallet list, shared by every /ws/wallets client. Each client holds a
+/// receiver; dropping it on disconnect is all the cleanup needed, and the poller
+/// exits once no receivers are left.
+fn wallet_feed() -> &'static watch::Sender<WalletSnapshot> {
+    static FEED: OnceLock<watch::Sender<WalletSnapshot>> = OnceLock::new();
+    FEED.get_or_init(|| watch::channel(None).0)
+}
+
+/// Store a fresh wallet list, waking subscribers only if something changed
+fn publish_wallets(wallets: Vec<WalletInfo>) {
+    wallet_feed().send_if_modified(|current| {
+        if current.as_deref() == Some(&wallets) {
+            return false;
+        }
+        *current = Some(Arc::new(wallets));
+        true
+    });
+}
+
+fn subscribe_wallets() -> watch::Receiver<WalletSnapshot> {
+    let rx = wallet_feed().subscribe();
+    if !WALLET_POLLER_RUNNING.swap(true, Ordering::SeqCst) {
+        tokio::spawn(poll_wallets());
+    }
+    rx
+}
+
+async fn poll_wallets() {
+    let feed = wallet_feed();
+    let mut ticker = interval(WALLET_POLL_INTERVAL);
+
+    loop {
+        ticker.tick().await;
+
+        if feed.receiver_count() == 0 {
+            WALLET_POLLER_RUNNING.store(false, Ordering::SeqCst);
+            // A client may have subscribed between the check and the store
+            if feed.receiver_count() == 0 || WALLET_POLLER_RUNNING.swap(true, Ordering::SeqCst) {
+                return;
+            }
+        }
+
+        if let Ok(wallets) = fetch_wallets().await {
+            publish_wallets(wallets);
+        }
+    }
+}
+
+async fn fetch_wallets() -> crate::core::error::Result<Vec<WalletInfo>> {
+    handlers::wallet_manager()?.list_wallets().await
+}
+
+/// Wallets in `latest` that are new or differ from what the client last saw
+fn changed_wallets<'a>(sent: &[WalletInfo], latest: &'a [WalletInfo]) -> Vec<&'a WalletInfo> {
+    latest
+        .iter()
+        .filter(|w| sent.iter().find(|s| s.worker_id == w.worker_id) != Some(*w))
+        .collect()
+}
+
+/// WebSocket handler pushing wallet balance/address changes
+pub async fn ws_wallets_handler(ws: WebSocketUpgrade) -> Response {
+    ws.on_upgrade(handle_wallets_websocket)
+}
+
+async fn handle_wallets_websocket(socket: WebSocket) {
+    let (mut sender, mut receiver) = socket.split();
+
+    // Subscribe before the initial fetch so no change in between is missed
+    let mut feed = subscribe_wallets();
+
+    // What this client has been sent; None until the first snapshot goes out
+    let mut sent: Option<Vec<WalletInfo>> = None;
+
+    match fetch_wallets().await {
+        Ok(wallets) => {
+            publish_wallets(wallets.clone());
+            feed.borrow_and_update();
+            let msg = json!({ "type": "snapshot", "wallets": &wallets });
+            if sender.send(Message::Text(msg.to_string())).await.is_err() {
+                return;
+            }
+            sent = Some(wallets);
+        }
+        Err(e) => {
+            // Keep the socket open; the poller's first success becomes the snapshot.
+            // The detail stays in the server log - it can name paths and hosts.
+            tracing::warn!("Wallet snapshot failed: {:#}", e);
+            let msg = json!({ "type": "error", "error": "Wallets are unavailable" });
+            if sender.send(Message::Text(msg.to_string())).await.is_err() {
+                return;
+            }
+        }
+    }
+
+    let mut last_push = Instant::now();
+    let mut flush_at: Option<Instant> = None;
+
+    loop {
+        tokio::select! {
+            changed = feed.changed(), if flush_at.is_none() => {
+                if changed.is_err() {
+                    break;
+                }
+                flush_at = Some((last_push + WALLET_PUSH_MIN_GAP).max(Instant::now()));
+            }
+
+            _ = sleep_until(flush_at.unwrap_or_else(Instant::now)), if flush_at.is_some() => {
+                flush_at = None;
+                let Some(latest) = feed.borrow_and_update().clone() else {
+                    continue;
+                };
+
+                let msg = match &sent {
+                    None => json!({ "type": "snapshot", "wallets": latest.as_slice() }),
+                    Some(sent) => {
+                        let changed = changed_wallets(sent, &latest);
+                        if changed.is_empty() {
+                            continue;
+                        }
+                        json!({ "type": "update", "wallets": changed })
+                    }
+                };
+
+                if sender.send(Message::Text(msg.to_string())).await.is_err() {
+                    break;
+                }
+                last_push = Instant::now();
+                sent = Some(latest.as_ref().clone());
+            }
+
+            msg = receiver.next() => {
+                match msg {
+                    Some(Ok(Message::Close(_))) | None => break,
+                    Some(Ok(Message::Ping(data))) => {
+                        if sender.send(Message::Pong(data)).await.is_err() {
+                            break;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wallet(worker_id: usize, balance: Option<f64>) -> WalletInfo {
+        WalletInfo {
+            worker_id,
+            address: Some(format!("kaspa:addr{}", worker_id)),
+            balance,
+            container_running: true,
+            initial_balance: None,
+            fees_spent: None,
+            fee_burn_rate: None,
+            daemon_synced: Some(true),
+            is_locked: false,
+        }
+    }
+
+    #[test]
+    fn test_changed_wallets() {
+        let sent = vec![wallet(0, Some(10.0)), wallet(1, Some(5.0))];
+        let latest = vec![wallet(0, Some(10.0)), wallet(1, Some(4.5)), wallet(2, None)];
+
+        let changed: Vec<usize> = changed_wallets(&sent, &latest).iter().map(|w| w.worker_id).collect();
+        assert_eq!(changed, vec![1, 2]);
+        assert!(changed_wallets(&latest, &latest).is_empty());
+    }
+}
@@ -1,14 +1,237 @@
-/// Application configuration management
-/// Stores user preferences in ~/.config/igra-cli/config.toml
+//! Application configuration management
+//! Stores user preferences in ~/.config/igra-cli/config.toml
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
+use std::time::Duration;
 
-#[derive(Debug, Serialize, Deserialize)]
+use crate::core::Network;
+use crate::core::secrets::SecretsConfig;
+use crate::core::wallet_password::WalletPasswordConfig;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct AppConfig {
     pub project_root: Option<String>,
+    #[serde(default)]
+    pub low_balance: LowBalanceThresholds,
+    #[serde(default)]
+    pub logs: LogLimits,
+    #[serde(default)]
+    pub wallet_passwords: WalletPasswordConfig,
+    #[serde(default)]
+    pub watch: WatchIntervals,
+    #[serde(default)]
+    pub secrets: SecretsConfig,
+    #[serde(default)]
+    pub session: SessionConfig,
+    #[serde(default)]
+    pub env: EnvConfig,
+    #[serde(default)]
+    pub update: UpdateConfig,
+}
+
+/// Smallest log buffer we allow, whatever config.toml says
+const MIN_LOG_LINES: usize = 100;
+/// Smallest fetch size for initial and live log requests
+const MIN_LOG_FETCH: usize = 10;
+
+/// Log viewer buffer sizes (lines). Set in config.toml:
+///
+/// ```toml
+/// [logs]
+/// max_lines = 10000      # kept in memory per detail view
+/// initial_fetch = 1000   # fetched when a detail view opens
+/// live_fetch = 100       # fetched per live-mode poll
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LogLimits {
+    pub max_lines: usize,
+    pub initial_fetch: usize,
+    pub live_fetch: usize,
+}
+
+impl Default for LogLimits {
+    fn default() -> Self {
+        Self { max_lines: 10_000, initial_fetch: 1000, live_fetch: 100 }
+    }
+}
+
+impl LogLimits {
+    /// Clamp to safe minimums and make sure the buffer can hold what we fetch
+    pub fn validated(self) -> Self {
+        let initial_fetch = self.initial_fetch.max(MIN_LOG_FETCH);
+        let live_fetch = self.live_fetch.max(MIN_LOG_FETCH);
+        let max_lines = self.max_lines.max(MIN_LOG_LINES).max(initial_fetch).max(live_fetch);
+
+        Self { max_lines, initial_fetch, live_fetch }
+    }
+}
+
+/// Fastest transaction poll we allow, so a typo can't hammer the node
+pub const MIN_WATCH_POLL_MS: u64 = 250;
+/// Shortest L1 data refresh we allow
+pub const MIN_WATCH_L1_INTERVAL_S: u64 = 2;
+
+/// How the watch loop learns about new L2 blocks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WatchMode {
+    /// Ask for the block number every `poll_ms`
+    #[default]
+    Poll,
+    /// Subscribe to new heads over WebSocket, polling while it's down
+    Ws,
+}
+
+/// Watch polling intervals. Set in config.toml (`watch --poll-ms`,
+/// `--l1-interval-s` and `--ws` override them for one run):
+///
+/// ```toml
+/// [watch]
+/// poll_ms = 1000       # new L2 blocks and transactions
+/// l1_interval_s = 10   # Kaspa L1 data
+/// mode = "ws"          # push new blocks over WebSocket (default "poll")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WatchIntervals {
+    pub poll_ms: u64,
+    pub l1_interval_s: u64,
+    pub mode: WatchMode,
+}
+
+impl Default for WatchIntervals {
+    fn default() -> Self {
+        Self { poll_ms: 1000, l1_interval_s: 10, mode: WatchMode::Poll }
+    }
+}
+
+impl WatchIntervals {
+    /// Clamp to the minimums
+    pub fn validated(self) -> Self {
+        Self {
+            poll_ms: self.poll_ms.max(MIN_WATCH_POLL_MS),
+            l1_interval_s: self.l1_interval_s.max(MIN_WATCH_L1_INTERVAL_S),
+            mode: self.mode,
+        }
+    }
+
+    pub fn poll(&self) -> Duration {
+        Duration::from_millis(self.poll_ms)
+    }
+
+    pub fn l1(&self) -> Duration {
+        Duration::from_secs(self.l1_interval_s)
+    }
+}
+
+/// Worker wallet balance (in KAS/TKAS) below which the wallet is flagged as low.
+/// Set in config.toml:
+///
+/// ```toml
+/// [low_balance]
+/// testnet = 100.0
+/// mainnet = 10.0
+/// ```
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LowBalanceThresholds {
+    pub testnet: f64,
+    pub mainnet: f64,
+}
+
+impl Default for LowBalanceThresholds {
+    fn default() -> Self {
+        // Testnet coins are free from the faucet, so warn earlier
+        Self { testnet: 100.0, mainnet: 10.0 }
+    }
+}
+
+impl LowBalanceThresholds {
+    pub fn for_network(&self, network: Network) -> f64 {
+        match network {
+            Network::Testnet => self.testnet,
+            Network::Mainnet => self.mainnet,
+        }
+    }
+}
+
+/// State file next to config.toml holding the last `SessionState`
+const SESSION_FILE: &str = "session.json";
+
+/// Whether the TUI reopens where it was left. To always start on Services
+/// instead, set in config.toml:
+///
+/// ```toml
+/// [session]
+/// restore = false
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionConfig {
+    pub restore: bool,
+}
+
+impl Default for SessionConfig {
+    fn default() -> Self {
+        Self { restore: true }
+    }
+}
+
+/// Whether process environment variables (systemd `Environment=`, a shell
+/// export) take precedence over `.env` values. Off unless set in config.toml:
+///
+/// ```toml
+/// [env]
+/// overrides = true
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EnvConfig {
+    pub overrides: bool,
+}
+
+/// Key the release checksums must be signed with before `self-update`
+/// installs anything: the minisign public key published with the releases.
+///
+/// ```toml
+/// [update]
+/// public_key = "RWQ..."
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UpdateConfig {
+    pub public_key: Option<String>,
+}
+
+/// Where the TUI was left (screen, tab and selected row), written to
+/// `session.json` on exit so config.toml is never rewritten
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionState {
+    pub screen: Option<String>,
+    /// Services view ("services"/"profiles") or Config tab ("environment"/"rpc_tokens"/"ssl")
+    pub view: Option<String>,
+    pub selected: usize,
+}
+
+impl SessionState {
+    fn load(path: &std::path::Path) -> Option<Self> {
+        serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+    }
+
+    /// Written to a temporary file and renamed, so an interrupted exit never
+    /// leaves half a file
+    fn save(&self, path: &std::path::Path) -> Result<()> {
+        let tmp = path.with_extension("json.tmp");
+        let contents = serde_json::to_string_pretty(self).context("Failed to serialize session")?;
+        fs::write(&tmp, contents).with_context(|| format!("Failed to write {}", tmp.display()))?;
+        fs::rename(&tmp, path).with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(())
+    }
 }
 
 impl AppConfig {
@@ -30,16 +253,16 @@ impl AppConfig {
         let path = Self::config_path()?;
 
         if !path.exists() {
-            return Ok(Self {
-                project_root: None,
-            });
+            return Ok(Self::default());
         }
 
         let contents = fs::read_to_string(&path)
             .context("Failed to read config file")?;
 
-        let config: Self = toml::from_str(&contents)
+        let mut config: Self = toml::from_str(&contents)
             .context("Failed to parse config file")?;
+        config.logs = config.logs.validated();
+        config.watch = config.watch.validated();
 
         Ok(config)
     }
@@ -56,9 +279,137 @@ impl AppConfig {
         Ok(())
     }
 
+    /// Release signing key for `self-update`, if one is configured
+    pub fn update_public_key() -> Option<String> {
+        Self::load().ok()?.update.public_key.filter(|key| !key.trim().is_empty())
+    }
+
+    /// Whether environment variables override `.env` (off if config.toml can't be read)
+    pub fn env_overrides() -> bool {
+        Self::load().map(|c| c.env.overrides).unwrap_or(false)
+    }
+
+    /// Per-worker wallet password sources (all `.env` if config.toml can't be read)
+    pub fn wallet_passwords() -> WalletPasswordConfig {
+        Self::load().map(|c| c.wallet_passwords).unwrap_or_default()
+    }
+
+    /// Low-balance threshold for `network`, falling back to the defaults
+    /// if config.toml can't be read
+    pub fn low_balance_threshold(network: Network) -> f64 {
+        Self::load()
+            .map(|c| c.low_balance)
+            .unwrap_or_default()
+            .for_network(network)
+    }
+
+    /// Log buffer sizes, falling back to the defaults if config.toml can't be read
+    pub fn log_limits() -> LogLimits {
+        Self::load().map(|c| c.logs).unwrap_or_default()
+    }
+
+    /// Watch polling intervals, falling back to the defaults if config.toml can't be read
+    pub fn watch_intervals() -> WatchIntervals {
+        Self::load().map(|c| c.watch).unwrap_or_default()
+    }
+
+    fn session_path() -> Result<PathBuf> {
+        Ok(Self::config_path()?.with_file_name(SESSION_FILE))
+    }
+
+    /// Whether to restore sessions, on unless config.toml turns it off
+    fn restore_session() -> bool {
+        Self::load().map(|c| c.session.restore).unwrap_or(true)
+    }
+
+    /// Last TUI session; None when restoring is off or nothing was saved
+    pub fn session() -> Option<SessionState> {
+        if !Self::restore_session() {
+            return None;
+        }
+        SessionState::load(&Self::session_path().ok()?)
+    }
+
+    /// Remember where the TUI was left, unless restoring is turned off
+    pub fn save_session(screen: &str, view: Option<&str>, selected: usize) -> Result<()> {
+        if !Self::restore_session() {
+            return Ok(());
+        }
+
+        let session = SessionState { screen: Some(screen.to_string()), view: view.map(str::to_string), selected };
+        session.save(&Self::session_path()?)
+    }
+
     /// Set and save project root
     pub fn set_project_root(&mut self, root: PathBuf) -> Result<()> {
         self.project_root = Some(root.to_string_lossy().to_string());
         self.save()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_low_balance_thresholds_from_toml() {
+        let config: AppConfig = toml::from_str("project_root = \"/srv/igra\"").unwrap();
+        assert_eq!(config.low_balance.for_network(Network::Testnet), 100.0);
+
+        let config: AppConfig = toml::from_str("[low_balance]\nmainnet = 25.0").unwrap();
+        assert_eq!(config.low_balance.for_network(Network::Mainnet), 25.0);
+        assert_eq!(config.low_balance.for_network(Network::Testnet), 100.0);
+    }
+
+    #[test]
+    fn test_log_limits_validated() {
+        assert_eq!(LogLimits::default().validated(), LogLimits::default());
+
+        // Buffer grows to hold the initial fetch
+        let limits = LogLimits { max_lines: 500, initial_fetch: 2000, live_fetch: 100 }.validated();
+        assert_eq!(limits.max_lines, 2000);
+
+        // Zeroes are clamped to the minimums
+        let limits = LogLimits { max_lines: 0, initial_fetch: 0, live_fetch: 0 }.validated();
+        assert_eq!(limits, LogLimits { max_lines: MIN_LOG_LINES, initial_fetch: MIN_LOG_FETCH, live_fetch: MIN_LOG_FETCH });
+    }
+
+    #[test]
+    fn test_session_defaults() {
+        let config: AppConfig = toml::from_str("project_root = \"/srv/igra\"").unwrap();
+        assert!(config.session.restore);
+
+        // A position left in config.toml by older versions is ignored
+        let config: AppConfig = toml::from_str("[session]\nrestore = false\nscreen = \"config\"").unwrap();
+        assert!(!config.session.restore);
+    }
+
+    #[test]
+    fn test_session_state_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(SESSION_FILE);
+        assert_eq!(SessionState::load(&path), None);
+
+        let session = SessionState { screen: Some("config".to_string()), view: Some("ssl".to_string()), selected: 3 };
+        session.save(&path).unwrap();
+        assert_eq!(SessionState::load(&path), Some(session));
+        assert!(!path.with_extension("json.tmp").exists());
+
+        fs::write(&path, "{ not json").unwrap();
+        assert_eq!(SessionState::load(&path), None);
+    }
+
+    #[test]
+    fn test_watch_intervals_validated() {
+        let config: AppConfig = toml::from_str("[watch]\npoll_ms = 5000").unwrap();
+        assert_eq!(config.watch, WatchIntervals { poll_ms: 5000, l1_interval_s: 10, mode: WatchMode::Poll });
+
+        let config: AppConfig = toml::from_str("[watch]\nmode = \"ws\"").unwrap();
+        assert_eq!(config.watch.mode, WatchMode::Ws);
+
+        let intervals = WatchIntervals { poll_ms: 10, l1_interval_s: 0, mode: WatchMode::Ws }.validated();
+        assert_eq!(intervals.mode, WatchMode::Ws);
+        assert_eq!(intervals.poll(), Duration::from_millis(MIN_WATCH_POLL_MS));
+        assert_eq!(intervals.l1(), Duration::from_secs(MIN_WATCH_L1_INTERVAL_S));
+    }
+}
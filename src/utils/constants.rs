@@ -1,6 +1,6 @@
-/// IGRA Orchestra Service Definitions and Constants
-///
-/// Based on the architecture documentation and docker-compose.yml
+//! IGRA Orchestra Service Definitions and Constants
+//!
+//! Based on the architecture documentation and docker-compose.yml
 
 use std::collections::HashMap;
 
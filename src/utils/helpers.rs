@@ -1,8 +1,8 @@
-/// Helper utilities for the IGRA CLI
+//! Helper utilities for the IGRA CLI
 
 use anyhow::{Context, Result};
 use std::path::{Path, PathBuf};
-use chrono::{DateTime, Local};
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
 
 /// Auto-detect project root from running Docker containers
 fn detect_from_docker() -> Option<PathBuf> {
@@ -12,8 +12,8 @@ fn detect_from_docker() -> Option<PathBuf> {
     let container_names = ["traefik", "kaswallet-0", "execution-layer", "kaspad", "viaduct"];
 
     for container_name in &container_names {
-        let output = Command::new("docker")
-            .args(&["ps", "--filter", &format!("name={}", container_name), "--format", "{{.ID}}"])
+        let output = Command::new(crate::core::docker::runtime_program())
+            .args(["ps", "--filter", &format!("name={}", container_name), "--format", "{{.ID}}"])
             .output()
             .ok()?;
 
@@ -23,8 +23,8 @@ fn detect_from_docker() -> Option<PathBuf> {
         }
 
         // Inspect the container to find mounts
-        let output = Command::new("docker")
-            .args(&["inspect", &container_id, "--format", "{{.Mounts}}"])
+        let output = Command::new(crate::core::docker::runtime_program())
+            .args(["inspect", &container_id, "--format", "{{.Mounts}}"])
             .output()
             .ok()?;
 
@@ -34,7 +34,7 @@ fn detect_from_docker() -> Option<PathBuf> {
         // Look for bind mounts (not volumes) that point to the host filesystem
         for segment in mounts.split("bind") {
             // Extract paths between spaces
-            let parts: Vec<&str> = segment.trim().split_whitespace().collect();
+            let parts: Vec<&str> = segment.split_whitespace().collect();
             if parts.len() >= 2 {
                 let host_path = parts[0];
                 // Skip if it starts with volume or other non-path indicators
@@ -61,6 +61,11 @@ fn detect_from_docker() -> Option<PathBuf> {
 pub fn get_project_root() -> Result<PathBuf> {
     use crate::utils::AppConfig;
 
+    // 0. An explicit --compose-file: its directory, without discovery (never saved)
+    if let Some(root) = crate::core::docker::compose_target_root() {
+        return Ok(root);
+    }
+
     // 1. Check saved configuration
     if let Ok(config) = AppConfig::load() {
         if let Some(root) = config.project_root {
@@ -200,6 +205,109 @@ pub fn format_timestamp(timestamp: i64) -> String {
     local.format("%Y-%m-%d %H:%M:%S").to_string()
 }
 
+/// Parse a `--since` value: RFC3339, "YYYY-MM-DD HH:MM[:SS]", "YYYY-MM-DD"
+/// (local midnight) or "HH:MM[:SS]" (local time, today), or a relative
+/// duration like "30m", "2h", "1d"
+pub fn parse_since(input: &str, now: DateTime<Local>) -> Result<DateTime<Utc>> {
+    let input = input.trim();
+
+    if let Ok(dt) = DateTime::parse_from_rfc3339(input) {
+        return Ok(dt.with_timezone(&Utc));
+    }
+
+    for fmt in ["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M"] {
+        if let Ok(naive) = NaiveDateTime::parse_from_str(input, fmt) {
+            return local_to_utc(naive);
+        }
+    }
+
+    if let Ok(date) = NaiveDate::parse_from_str(input, "%Y-%m-%d") {
+        return local_to_utc(date.and_time(NaiveTime::MIN));
+    }
+
+    for fmt in ["%H:%M:%S", "%H:%M"] {
+        if let Ok(time) = NaiveTime::parse_from_str(input, fmt) {
+            return local_to_utc(now.date_naive().and_time(time));
+        }
+    }
+
+    if let Some(unit) = input.chars().last() {
+        let multiplier = match unit {
+            's' => Some(1),
+            'm' => Some(60),
+            'h' => Some(3600),
+            'd' => Some(86400),
+            _ => None,
+        };
+        let amount = input[..input.len() - unit.len_utf8()].parse::<i64>();
+        if let (Some(multiplier), Ok(amount)) = (multiplier, amount) {
+            return Ok(now.with_timezone(&Utc) - chrono::Duration::seconds(amount * multiplier));
+        }
+    }
+
+    anyhow::bail!("Invalid time '{}'. Use RFC3339, 'YYYY-MM-DD [HH:MM]', 'HH:MM' or a duration like 30m", input)
+}
+
+fn local_to_utc(naive: NaiveDateTime) -> Result<DateTime<Utc>> {
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(|dt| dt.with_timezone(&Utc))
+        .context("Time does not exist in the local timezone")
+}
+
+/// 1 KAS = 10^8 sompi
+pub const SOMPI_PER_KAS: u64 = 100_000_000;
+const KAS_DECIMALS: usize = 8;
+
+/// Parse a decimal KAS amount ("1.5", "0.00000001") into exact sompi
+/// Rejects negatives, sub-sompi precision and amounts that overflow u64
+pub fn kas_to_sompi(amount: &str) -> Result<u64> {
+    let amount = amount.trim();
+    let (whole, fraction) = match amount.split_once('.') {
+        Some((whole, fraction)) => (whole, fraction),
+        None => (amount, ""),
+    };
+
+    if whole.is_empty() && fraction.is_empty() {
+        anyhow::bail!("Invalid amount '{}'", amount);
+    }
+    if !whole.chars().all(|c| c.is_ascii_digit()) || !fraction.chars().all(|c| c.is_ascii_digit()) {
+        anyhow::bail!("Invalid amount '{}': use digits and an optional decimal point", amount);
+    }
+    if fraction.len() > KAS_DECIMALS {
+        anyhow::bail!("Amount '{}' has more than {} decimal places (1 sompi = 0.00000001 KAS)", amount, KAS_DECIMALS);
+    }
+
+    let whole_sompi = if whole.is_empty() {
+        0
+    } else {
+        whole.parse::<u64>()
+            .ok()
+            .and_then(|w| w.checked_mul(SOMPI_PER_KAS))
+            .with_context(|| format!("Amount '{}' is too large", amount))?
+    };
+    let fraction_sompi = if fraction.is_empty() {
+        0
+    } else {
+        format!("{:0<width$}", fraction, width = KAS_DECIMALS).parse::<u64>()?
+    };
+
+    whole_sompi
+        .checked_add(fraction_sompi)
+        .with_context(|| format!("Amount '{}' is too large", amount))
+}
+
+/// Convert sompi to KAS for display and approximate math
+pub fn sompi_to_kas(sompi: u64) -> f64 {
+    sompi as f64 / SOMPI_PER_KAS as f64
+}
+
+/// Exact KAS string for a sompi amount, e.g. 150000001 -> "1.50000001"
+pub fn format_sompi(sompi: u64) -> String {
+    format!("{}.{:08}", sompi / SOMPI_PER_KAS, sompi % SOMPI_PER_KAS)
+}
+
 /// Truncate string with ellipsis
 pub fn truncate_string(s: &str, max_len: usize) -> String {
     if s.len() <= max_len {
@@ -209,26 +317,75 @@ pub fn truncate_string(s: &str, max_len: usize) -> String {
     }
 }
 
-/// Mask sensitive data (show only first and last N characters)
+/// Whether a config key holds a secret that should be masked in the UI
+pub fn is_sensitive_key(key: &str) -> bool {
+    key.contains("PASSWORD") || key.contains("SECRET") || key.contains("KEY") || key.contains("TOKEN")
+}
+
+/// Whether a config entry matches a lowercase search query. Values are only
+/// searched when asked, and never for sensitive keys: a match on a masked
+/// value would reveal it one guess at a time.
+pub fn config_search_matches(key: &str, value: &str, query: &str, include_values: bool) -> bool {
+    key.to_lowercase().contains(query)
+        || (include_values && !is_sensitive_key(key) && value.to_lowercase().contains(query))
+}
+
+/// Shown in place of passwords, secrets and keys
+pub const MASKED_VALUE: &str = "****";
+
+/// Characters of an RPC token shown at each end: 4 in all, enough to tell
+/// tokens apart without giving much of one away
+pub const TOKEN_VISIBLE_CHARS: usize = 2;
+
+/// Whether a config key holds an RPC access token
+pub fn is_rpc_token_key(key: &str) -> bool {
+    key.starts_with("RPC_ACCESS_TOKEN_")
+}
+
+/// Partially reveal an RPC token ("first2...last2"); short tokens are fully masked
+pub fn mask_token(token: &str) -> String {
+    mask_sensitive(token, TOKEN_VISIBLE_CHARS)
+}
+
+/// How a config value is displayed: RPC tokens partially, other secrets not at all
+pub fn mask_config_value(key: &str, value: &str) -> String {
+    if is_rpc_token_key(key) {
+        mask_token(value)
+    } else if is_sensitive_key(key) {
+        MASKED_VALUE.to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+/// Mask sensitive data (show only first and last N characters). Counts
+/// characters, not bytes, so multibyte values never split mid-character.
 pub fn mask_sensitive(value: &str, visible_chars: usize) -> String {
-    if value.len() <= visible_chars * 2 {
-        "*".repeat(value.len())
+    let len = value.chars().count();
+    if len <= visible_chars * 2 {
+        "*".repeat(len)
     } else {
-        let start = &value[..visible_chars];
-        let end = &value[value.len() - visible_chars..];
+        let start: String = value.chars().take(visible_chars).collect();
+        let end: String = value.chars().skip(len - visible_chars).collect();
         format!("{}...{}", start, end)
     }
 }
 
-/// Generate a random hex string of specified length
-pub fn generate_hex_string(length: usize) -> String {
+/// Generate a hex secret from the OS CSPRNG (RPC tokens, *_SECRET values)
+pub fn generate_hex_secret(length: usize) -> String {
     use rand::Rng;
-    let mut rng = rand::thread_rng();
+    let mut rng = rand::rngs::OsRng;
     (0..length)
-        .map(|_| format!("{:x}", rng.gen::<u8>() % 16))
+        .map(|_| format!("{:x}", rng.gen_range(0..16u8)))
         .collect()
 }
 
+/// Generate an alphanumeric secret from the OS CSPRNG (for passwords written to .env)
+pub fn generate_secret(length: usize) -> String {
+    use rand::distributions::{Alphanumeric, DistString};
+    Alphanumeric.sample_string(&mut rand::rngs::OsRng, length)
+}
+
 /// Validate hex string
 pub fn is_valid_hex(s: &str) -> bool {
     s.chars().all(|c| c.is_ascii_hexdigit())
@@ -259,6 +416,14 @@ pub fn is_valid_email(email: &str) -> bool {
     !parts[0].is_empty() && is_valid_domain(parts[1])
 }
 
+/// Validate a kaspa address for `network`: it must decode (payload and
+/// checksum) and carry that network's prefix, so a mainnet node never sends to
+/// a testnet address or the other way round
+pub fn is_valid_kaspa_address(address: &str, network: crate::core::Network) -> bool {
+    kaspa_addresses::Address::try_from(address).is_ok()
+        && address.split_once(':').is_some_and(|(prefix, _)| prefix == network.address_prefix())
+}
+
 /// Check if a file exists and is readable
 pub fn is_file_readable<P: AsRef<Path>>(path: P) -> bool {
     path.as_ref().exists() && path.as_ref().is_file()
@@ -336,6 +501,44 @@ impl ContainerState {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_kas_to_sompi() {
+        assert_eq!(kas_to_sompi("1").unwrap(), SOMPI_PER_KAS);
+        assert_eq!(kas_to_sompi("1.5").unwrap(), 150_000_000);
+        assert_eq!(kas_to_sompi(".00000001").unwrap(), 1);
+        // Exact where float math would round: 0.29 * 1e8 = 28999999.999...
+        assert_eq!(kas_to_sompi("0.29").unwrap(), 29_000_000);
+        assert_eq!(kas_to_sompi("184467440737.09551615").unwrap(), u64::MAX);
+
+        assert!(kas_to_sompi("0.000000001").is_err());
+        assert!(kas_to_sompi("-1").is_err());
+        assert!(kas_to_sompi("1.2.3").is_err());
+        assert!(kas_to_sompi(".").is_err());
+        assert!(kas_to_sompi("184467440737.09551616").is_err());
+
+        assert_eq!(format_sompi(150_000_001), "1.50000001");
+        assert_eq!(sompi_to_kas(50_000_000), 0.5);
+    }
+
+    #[test]
+    fn test_parse_since() {
+        let now = Local.with_ymd_and_hms(2024, 5, 1, 16, 30, 0).unwrap();
+
+        let rfc = parse_since("2024-05-01T14:00:00Z", now).unwrap();
+        assert_eq!(rfc, Utc.with_ymd_and_hms(2024, 5, 1, 14, 0, 0).unwrap());
+
+        let today = parse_since("14:00", now).unwrap();
+        assert_eq!(today.with_timezone(&Local), Local.with_ymd_and_hms(2024, 5, 1, 14, 0, 0).unwrap());
+
+        let day = parse_since("2024-04-01", now).unwrap();
+        assert_eq!(day.with_timezone(&Local), Local.with_ymd_and_hms(2024, 4, 1, 0, 0, 0).unwrap());
+
+        let relative = parse_since("30m", now).unwrap();
+        assert_eq!(relative, now.with_timezone(&Utc) - chrono::Duration::minutes(30));
+
+        assert!(parse_since("yesterday", now).is_err());
+    }
+
     #[test]
     fn test_format_bytes() {
         assert_eq!(format_bytes(1024), "1.00 KB");
@@ -351,6 +554,39 @@ mod tests {
         assert_eq!(format_duration(86400), "1d 0h");
     }
 
+    #[test]
+    fn test_is_sensitive_key() {
+        assert!(is_sensitive_key("W0_KASWALLET_PASSWORD"));
+        assert!(is_sensitive_key("IGRA_RPC_ACCESS_TOKEN_01"));
+        assert!(is_sensitive_key("OVH_APPLICATION_KEY"));
+        assert!(!is_sensitive_key("NETWORK"));
+    }
+
+    #[test]
+    fn test_is_valid_kaspa_address() {
+        use crate::core::Network;
+        use kaspa_addresses::{Address, Prefix, Version};
+
+        let mainnet = Address::new(Prefix::Mainnet, Version::PubKey, &[7u8; 32]).to_string();
+        let testnet = Address::new(Prefix::Testnet, Version::PubKey, &[7u8; 32]).to_string();
+        assert!(is_valid_kaspa_address(&mainnet, Network::Mainnet));
+        assert!(is_valid_kaspa_address(&testnet, Network::Testnet));
+
+        // Right format, wrong network
+        assert!(!is_valid_kaspa_address(&mainnet, Network::Testnet));
+        assert!(!is_valid_kaspa_address(&testnet, Network::Mainnet));
+
+        let (_, payload) = mainnet.split_once(':').unwrap();
+        assert!(!is_valid_kaspa_address(payload, Network::Mainnet));
+        assert!(!is_valid_kaspa_address(&format!("bitcoin:{}", payload), Network::Mainnet));
+        assert!(!is_valid_kaspa_address("kaspa:qr35", Network::Mainnet));
+
+        // One character off: still bech32, but the checksum no longer matches
+        let last = mainnet.chars().last().unwrap();
+        let typo = format!("{}{}", &mainnet[..mainnet.len() - 1], if last == 'q' { 'p' } else { 'q' });
+        assert!(!is_valid_kaspa_address(&typo, Network::Mainnet));
+    }
+
     #[test]
     fn test_mask_sensitive() {
         let token = "5e7f294e4c92a9aa661fae8d347d832d";
@@ -358,6 +594,42 @@ mod tests {
         assert_eq!(masked, "5e7f...832d");
     }
 
+    #[test]
+    fn test_mask_config_value() {
+        let token = "5e7f294e4c92a9aa661fae8d347d832d";
+        assert_eq!(mask_config_value("RPC_ACCESS_TOKEN_3", token), "5e...2d");
+        // Too short to reveal anything safely
+        assert_eq!(mask_config_value("RPC_ACCESS_TOKEN_4", "abcd"), "****");
+        assert_eq!(mask_config_value("W0_KASWALLET_PASSWORD", "hunter2"), MASKED_VALUE);
+        assert_eq!(mask_config_value("NETWORK", "testnet"), "testnet");
+    }
+
+    #[test]
+    fn test_config_search_matches() {
+        assert!(config_search_matches("RPC_PORT", "8545", "port", false));
+        assert!(!config_search_matches("RPC_PORT", "8545", "8545", false));
+        assert!(config_search_matches("RPC_PORT", "8545", "8545", true));
+        // Secret values never match, even when searching values
+        assert!(!config_search_matches("W0_KASWALLET_PASSWORD", "hunter2", "hunter", true));
+        assert!(config_search_matches("W0_KASWALLET_PASSWORD", "hunter2", "password", true));
+    }
+
+    #[test]
+    fn test_mask_token_short_and_multibyte() {
+        // Used to panic slicing &t[..8]
+        assert_eq!(mask_token("abcd"), "****");
+        assert_eq!(mask_token(""), "");
+
+        // 3-byte characters: byte offsets 2 and len-2 fall mid-character
+        let token = "é€€€€€€€€€€€€€€€€€x";
+        assert_eq!(mask_token(token), "é€...€x");
+        assert_eq!(mask_token("€€€€"), "****");
+
+        // Never more than 4 characters of a token
+        let masked = mask_token("5e7f294e4c92a9aa661fae8d347d832d");
+        assert_eq!(masked.chars().filter(|c| *c != '.').count(), 4);
+    }
+
     #[test]
     fn test_is_valid_hex() {
         assert!(is_valid_hex("deadbeef"));
@@ -366,6 +638,18 @@ mod tests {
         assert!(!is_valid_hex("hello"));
     }
 
+    #[test]
+    fn test_generate_secrets() {
+        let hex = generate_hex_secret(32);
+        assert_eq!(hex.len(), 32);
+        assert!(is_valid_hex(&hex));
+        assert_ne!(hex, generate_hex_secret(32));
+
+        let secret = generate_secret(32);
+        assert_eq!(secret.len(), 32);
+        assert!(secret.chars().all(|c| c.is_ascii_alphanumeric()));
+    }
+
     #[test]
     fn test_is_valid_domain() {
         assert!(is_valid_domain("example.com"));
@@ -1,7 +1,8 @@
 pub mod constants;
 pub mod helpers;
 pub mod app_config;
+pub mod terminal;
 
 pub use constants::*;
 pub use helpers::*;
-pub use app_config::AppConfig;
+pub use app_config::{AppConfig, EnvConfig, LogLimits, SessionConfig, SessionState, UpdateConfig, WatchIntervals, WatchMode};
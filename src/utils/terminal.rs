@@ -0,0 +1,111 @@
+//! Terminal setup and restore shared by the TUIs
+//!
+//! Restoring is idempotent and also runs from a panic hook, so a crash
+//! mid-render doesn't leave the shell in raw mode / alternate screen.
+
+use anyhow::{anyhow, Result};
+use crossterm::{
+    cursor::Show,
+    event::{DisableMouseCapture, EnableMouseCapture},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Once;
+use zeroize::Zeroizing;
+
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+static PANIC_HOOK: Once = Once::new();
+
+/// Enter raw mode and the alternate screen, installing the panic hook first
+pub fn enter(mouse_capture: bool) -> Result<()> {
+    install_panic_hook();
+
+    enable_raw_mode()?;
+    ACTIVE.store(true, Ordering::SeqCst);
+
+    let mut stdout = io::stdout();
+    let entered = if mouse_capture {
+        execute!(stdout, EnterAlternateScreen, EnableMouseCapture)
+    } else {
+        execute!(stdout, EnterAlternateScreen)
+    };
+    if let Err(e) = entered {
+        restore();
+        return Err(e.into());
+    }
+
+    Ok(())
+}
+
+/// Restore the terminal. Safe to call more than once; only the first call
+/// after `enter` does anything. Errors are ignored since this also runs
+/// while panicking.
+pub fn restore() {
+    if !ACTIVE.swap(false, Ordering::SeqCst) {
+        return;
+    }
+
+    let _ = disable_raw_mode();
+    let _ = execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture, Show);
+}
+
+/// Read a line from the terminal without echoing it. Esc or Ctrl+C cancels.
+/// Must not be called while the TUI is active.
+pub fn read_hidden(label: &str) -> Result<Zeroizing<String>> {
+    use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+    use std::io::Write;
+
+    print!("{}: ", label);
+    io::stdout().flush()?;
+
+    enable_raw_mode()?;
+    // Reserve up front so typing doesn't reallocate and leave unwiped copies behind
+    let mut input = Zeroizing::new(String::with_capacity(256));
+    let result = loop {
+        match event::read() {
+            Ok(Event::Key(key)) if key.kind == KeyEventKind::Press => match key.code {
+                KeyCode::Enter => break Ok(()),
+                KeyCode::Esc => break Err(anyhow!("Cancelled")),
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    break Err(anyhow!("Cancelled"));
+                }
+                KeyCode::Char(c) => input.push(c),
+                _ => {}
+            },
+            Ok(_) => {}
+            Err(e) => break Err(e.into()),
+        }
+    };
+    disable_raw_mode()?;
+    println!();
+
+    result.map(|_| input)
+}
+
+/// Restore the terminal before the default hook prints the panic message
+fn install_panic_hook() {
+    PANIC_HOOK.call_once(|| {
+        let default_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            restore();
+            default_hook(info);
+        }));
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restore_is_noop_when_inactive() {
+        restore();
+        restore();
+        assert!(!ACTIVE.load(Ordering::SeqCst));
+    }
+}
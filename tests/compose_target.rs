@@ -0,0 +1,18 @@
+//! `--compose-file` is set once per process, so this runs in its own binary
+
+use igra_mgt::core::docker::ComposeTarget;
+use igra_mgt::utils::get_project_root;
+use igra_mgt::DockerManager;
+
+#[test]
+fn test_compose_file_sets_project_root() {
+    let dir = tempfile::tempdir().unwrap();
+    let project = dir.path().join("staging");
+    std::fs::create_dir(&project).unwrap();
+    let compose_file = project.join("docker-compose.yml");
+    std::fs::write(&compose_file, "services: {}\n").unwrap();
+
+    DockerManager::configure(ComposeTarget { compose_file: Some(compose_file), project: None }).unwrap();
+
+    assert_eq!(get_project_root().unwrap(), project.canonicalize().unwrap());
+}
@@ -0,0 +1,81 @@
+//! `--dry-run` is process-wide and can't be switched off again, so these
+//! tests live in their own binary rather than next to the code they cover
+
+use std::sync::Mutex;
+
+use igra_mgt::core::dry_run;
+use igra_mgt::core::ssl::SslManager;
+use igra_mgt::core::storage::{self, PruneTarget};
+
+// Every test reads the shared plan, so they take turns
+static PLAN: Mutex<()> = Mutex::new(());
+
+fn planned_by(f: impl FnOnce()) -> Vec<String> {
+    let _guard = PLAN.lock().unwrap_or_else(|e| e.into_inner());
+    dry_run::enable();
+    dry_run::take_planned();
+    f();
+    dry_run::take_planned()
+}
+
+fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(future)
+}
+
+#[test]
+fn test_ssl_renew_restarts_nothing() {
+    let dir = tempfile::tempdir().unwrap();
+    let ssl = SslManager::offline(dir.path());
+
+    let planned = planned_by(|| block_on(ssl.force_renewal()).unwrap());
+
+    assert_eq!(planned.len(), 1);
+    assert!(planned[0].ends_with(" restart traefik"), "{:?}", planned);
+}
+
+#[test]
+fn test_ssl_reissue_leaves_acme_json_alone() {
+    let dir = tempfile::tempdir().unwrap();
+    let certs = dir.path().join("traefik_certs");
+    std::fs::create_dir(&certs).unwrap();
+    let acme = serde_json::json!({
+        "letsencrypt": {
+            "Certificates": [
+                { "domain": { "main": "node.example.com" }, "certificate": "x", "key": "y" }
+            ]
+        }
+    });
+    let original = serde_json::to_vec(&acme).unwrap();
+    std::fs::write(certs.join("acme.json"), &original).unwrap();
+    let ssl = SslManager::offline(dir.path());
+
+    let mut backup = None;
+    let planned = planned_by(|| backup = Some(block_on(ssl.reissue_certificate("node.example.com")).unwrap()));
+    let backup = backup.unwrap();
+
+    assert!(!backup.exists());
+    assert_eq!(std::fs::read(certs.join("acme.json")).unwrap(), original);
+    assert_eq!(std::fs::read_dir(&certs).unwrap().count(), 1);
+    assert_eq!(planned.len(), 2, "{:?}", planned);
+    assert!(planned[0].contains("remove 1 certificate(s) for node.example.com"));
+    assert!(planned[1].ends_with(" restart traefik"));
+
+    // A domain without a certificate still fails, dry run or not
+    assert!(block_on(ssl.reissue_certificate("other.example.com")).is_err());
+}
+
+#[test]
+fn test_storage_prune_build_cache() {
+    let planned = planned_by(|| assert_eq!(storage::prune(PruneTarget::BuildCache).unwrap(), None));
+
+    assert_eq!(planned.len(), 1);
+    assert!(planned[0].contains(" prune -f"), "{:?}", planned);
+}
+
+#[test]
+fn test_storage_prune_images() {
+    let planned = planned_by(|| assert_eq!(storage::prune(PruneTarget::Images).unwrap(), None));
+
+    assert_eq!(planned.len(), 1);
+    assert!(planned[0].ends_with(" image prune -f"), "{:?}", planned);
+}